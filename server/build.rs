@@ -0,0 +1,49 @@
+//! Build script: embeds git commit and build timestamp for `GET /version`, and generates
+//! the gRPC service from `proto/` (compiled with protox, so no `protoc` is needed).
+//! `TRUTHLAYER_GIT_COMMIT` / `TRUTHLAYER_BUILD_TIMESTAMP` override the detected values
+//! (e.g. Docker builds where `.git` is not in the build context).
+
+use std::process::Command;
+
+fn main() {
+    compile_protos();
+
+    let git_commit = std::env::var("TRUTHLAYER_GIT_COMMIT").unwrap_or_else(|_| {
+        Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    });
+
+    // Unix seconds; formatted as RFC 3339 at runtime (no chrono build-dependency).
+    let build_timestamp = std::env::var("TRUTHLAYER_BUILD_TIMESTAMP").unwrap_or_else(|_| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_else(|_| "0".to_string())
+    });
+
+    println!("cargo:rustc-env=TRUTHLAYER_GIT_COMMIT={}", git_commit);
+    println!(
+        "cargo:rustc-env=TRUTHLAYER_BUILD_TIMESTAMP={}",
+        build_timestamp
+    );
+    println!("cargo:rerun-if-env-changed=TRUTHLAYER_GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=TRUTHLAYER_BUILD_TIMESTAMP");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs");
+}
+
+fn compile_protos() {
+    let protos = ["proto/truthlayer/v1/context.proto"];
+    let descriptors = protox::compile(protos, ["proto"]).expect("failed to compile protos");
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_fds(descriptors)
+        .expect("failed to generate gRPC code");
+    println!("cargo:rerun-if-changed=proto");
+}
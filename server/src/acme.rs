@@ -0,0 +1,422 @@
+//! ACME (Let's Encrypt) automatic certificates via TLS-ALPN-01.
+//!
+//! Optional: enabled when `acme.domains` (config.json) or `TRUTHTLAYER_ACME_DOMAINS` is set.
+//! Certificates are served through [`AcmeCertResolver`], shared by the QUIC and TLS TCP
+//! listeners, so renewals take effect without a restart.
+//!
+//! ```text
+//! renewal task (every check_interval_secs)
+//!   ├── cached cert valid for > renew_before_days → nothing to do
+//!   └── otherwise: order → TLS-ALPN-01 challenge cert installed in resolver
+//!         → CA connects to the TLS TCP listener with ALPN acme-tls/1
+//!         → finalize → cert.pem / key.pem persisted under cache_dir → resolver updated
+//! ```
+//!
+//! TLS-ALPN-01 is validated on TCP port 443, so the TLS TCP listener must be enabled and
+//! reachable on :443 (directly or via port forwarding).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use instant_acme::{
+    Account, AccountCredentials, AuthorizationStatus, ChallengeType, Identifier, NewAccount,
+    NewOrder, OrderStatus, RetryPolicy,
+};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use serde::{Deserialize, Serialize};
+
+/// ALPN protocol used by TLS-ALPN-01 validation (RFC 8737).
+pub const ACME_TLS_ALPN: &[u8] = b"acme-tls/1";
+
+/// Let's Encrypt production directory.
+pub const LETS_ENCRYPT_DIRECTORY: &str = "https://acme-v02.api.letsencrypt.org/directory";
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// ACME configuration (`acme` in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcmeConfig {
+    /// DNS names to include in the certificate (first is the primary name).
+    pub domains: Vec<String>,
+    /// Contact email registered with the ACME account.
+    #[serde(default)]
+    pub contact_email: Option<String>,
+    /// ACME directory URL. Default: Let's Encrypt production.
+    #[serde(default = "default_directory_url")]
+    pub directory_url: String,
+    /// Directory (relative to config root) for account credentials and issued certs.
+    #[serde(default = "default_cache_dir")]
+    pub cache_dir: String,
+    /// Renew when the certificate expires within this many days.
+    #[serde(default = "default_renew_before_days")]
+    pub renew_before_days: u64,
+    /// How often to check whether renewal is due.
+    #[serde(default = "default_check_interval_secs")]
+    pub check_interval_secs: u64,
+}
+
+fn default_directory_url() -> String {
+    LETS_ENCRYPT_DIRECTORY.to_string()
+}
+
+fn default_cache_dir() -> String {
+    "acme".to_string()
+}
+
+fn default_renew_before_days() -> u64 {
+    30
+}
+
+fn default_check_interval_secs() -> u64 {
+    12 * 60 * 60
+}
+
+impl AcmeConfig {
+    /// Config with defaults for the given domains.
+    pub fn for_domains(domains: Vec<String>) -> Self {
+        Self {
+            domains,
+            contact_email: None,
+            directory_url: default_directory_url(),
+            cache_dir: default_cache_dir(),
+            renew_before_days: default_renew_before_days(),
+            check_interval_secs: default_check_interval_secs(),
+        }
+    }
+}
+
+/// Certificate resolver shared by all TLS listeners.
+///
+/// Serves the TLS-ALPN-01 challenge certificate when the client offers `acme-tls/1`,
+/// otherwise the current (issued, cached or placeholder) certificate.
+#[derive(Debug, Default)]
+pub struct AcmeCertResolver {
+    current: RwLock<Option<Arc<CertifiedKey>>>,
+    challenges: RwLock<HashMap<String, Arc<CertifiedKey>>>,
+}
+
+impl AcmeCertResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the certificate served to regular clients.
+    pub fn set_certificate(&self, key: Arc<CertifiedKey>) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Some(key);
+    }
+
+    fn set_challenge(&self, domain: &str, key: Arc<CertifiedKey>) {
+        self.challenges
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(domain.to_ascii_lowercase(), key);
+    }
+
+    fn clear_challenges(&self) {
+        self.challenges
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Select the certificate for a handshake (`acme` = client offered `acme-tls/1`).
+    fn select(&self, server_name: Option<&str>, acme: bool) -> Option<Arc<CertifiedKey>> {
+        if acme {
+            let name = server_name?.to_ascii_lowercase();
+            return self
+                .challenges
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&name)
+                .cloned();
+        }
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl ResolvesServerCert for AcmeCertResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let acme = client_hello
+            .alpn()
+            .is_some_and(|mut protos| protos.any(|p| p == ACME_TLS_ALPN));
+        self.select(client_hello.server_name(), acme)
+    }
+}
+
+fn certified_key(
+    certs: Vec<CertificateDer<'static>>,
+    key: &PrivateKeyDer<'static>,
+) -> Result<Arc<CertifiedKey>, BoxError> {
+    let signing_key = rustls::crypto::ring::sign::any_supported_type(key)
+        .map_err(|e| format!("unsupported ACME key: {}", e))?;
+    Ok(Arc::new(CertifiedKey::new(certs, signing_key)))
+}
+
+/// Self-signed TLS-ALPN-01 challenge certificate carrying the key authorization digest.
+fn challenge_cert(domain: &str, digest: &[u8]) -> Result<Arc<CertifiedKey>, BoxError> {
+    let key_pair = rcgen::KeyPair::generate()?;
+    let mut params = rcgen::CertificateParams::new(vec![domain.to_string()])?;
+    params.custom_extensions = vec![rcgen::CustomExtension::new_acme_identifier(digest)];
+    let cert = params.self_signed(&key_pair)?;
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der()));
+    certified_key(vec![cert.der().clone()], &key)
+}
+
+/// Self-signed placeholder for the configured domains, served until the first issuance.
+fn placeholder_cert(domains: &[String]) -> Result<Arc<CertifiedKey>, BoxError> {
+    let certified = rcgen::generate_simple_self_signed(domains.to_vec())?;
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der()));
+    certified_key(vec![certified.cert.der().clone()], &key)
+}
+
+/// True when the leaf certificate expires within `renew_before` (or cannot be parsed).
+pub fn needs_renewal(cert: &CertificateDer<'_>, renew_before: Duration) -> bool {
+    let Ok((_, parsed)) = x509_parser::parse_x509_certificate(cert.as_ref()) else {
+        return true;
+    };
+    let not_after = parsed.validity().not_after.timestamp();
+    let renew_at = not_after - renew_before.as_secs() as i64;
+    chrono::Utc::now().timestamp() >= renew_at
+}
+
+/// Account credentials, issued certificate and key under the cache directory.
+struct AcmeCache {
+    dir: PathBuf,
+}
+
+impl AcmeCache {
+    fn cert_path(&self) -> PathBuf {
+        self.dir.join("cert.pem")
+    }
+
+    fn key_path(&self) -> PathBuf {
+        self.dir.join("key.pem")
+    }
+
+    fn account_path(&self) -> PathBuf {
+        self.dir.join("account.json")
+    }
+
+    /// Cached certificate chain and key, if both files exist and parse.
+    fn load_cert(&self) -> Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+        if !self.cert_path().exists() || !self.key_path().exists() {
+            return None;
+        }
+        match crate::tls::load_certs_from_pem(&self.cert_path(), &self.key_path()) {
+            Ok(pair) => Some(pair),
+            Err(e) => {
+                tracing::warn!(error = %e, "ignoring unreadable cached ACME certificate");
+                None
+            }
+        }
+    }
+
+    fn load_account(&self) -> Option<AccountCredentials> {
+        let s = std::fs::read_to_string(self.account_path()).ok()?;
+        serde_json::from_str(&s).ok()
+    }
+
+    fn save_account(&self, credentials: &AccountCredentials) -> Result<(), BoxError> {
+        write_atomic(
+            &self.account_path(),
+            serde_json::to_string_pretty(credentials)?.as_bytes(),
+        )
+    }
+
+    fn save_cert(&self, cert_pem: &str, key_pem: &str) -> Result<(), BoxError> {
+        write_atomic(&self.key_path(), key_pem.as_bytes())?;
+        write_atomic(&self.cert_path(), cert_pem.as_bytes())
+    }
+}
+
+/// Write via temp file + rename so a crash never leaves a truncated file.
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), BoxError> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Install the cached certificate (or a placeholder) and start the renewal task.
+///
+/// `config_root` anchors `cache_dir`. Returns the resolver to plug into the TLS configs.
+pub fn start(config: AcmeConfig, config_root: &Path) -> Result<Arc<AcmeCertResolver>, BoxError> {
+    if config.domains.is_empty() {
+        return Err("ACME enabled but no domains configured".into());
+    }
+    let cache = AcmeCache {
+        dir: config_root.join(&config.cache_dir),
+    };
+    std::fs::create_dir_all(&cache.dir)
+        .map_err(|e| format!("failed to create ACME cache {}: {}", cache.dir.display(), e))?;
+
+    let resolver = Arc::new(AcmeCertResolver::new());
+    match cache.load_cert() {
+        Some((certs, key)) => {
+            tracing::info!(path = %cache.cert_path().display(), "loaded cached ACME certificate");
+            resolver.set_certificate(certified_key(certs, &key)?);
+        }
+        None => {
+            tracing::warn!("no ACME certificate yet; serving self-signed placeholder until issued");
+            resolver.set_certificate(placeholder_cert(&config.domains)?);
+        }
+    }
+
+    let task_resolver = resolver.clone();
+    tokio::spawn(async move {
+        let interval = Duration::from_secs(config.check_interval_secs.max(60));
+        loop {
+            if let Err(e) = renew_if_due(&config, &cache, &task_resolver).await {
+                tracing::error!(error = %e, "ACME certificate renewal failed");
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+    Ok(resolver)
+}
+
+async fn renew_if_due(
+    config: &AcmeConfig,
+    cache: &AcmeCache,
+    resolver: &AcmeCertResolver,
+) -> Result<(), BoxError> {
+    let renew_before = Duration::from_secs(config.renew_before_days * 24 * 60 * 60);
+    if let Some((certs, _)) = cache.load_cert() {
+        if certs
+            .first()
+            .is_some_and(|leaf| !needs_renewal(leaf, renew_before))
+        {
+            return Ok(());
+        }
+    }
+    tracing::info!(domains = ?config.domains, "requesting ACME certificate");
+    let result = issue(config, cache, resolver).await;
+    resolver.clear_challenges();
+    let (cert_pem, key_pem) = result?;
+    cache.save_cert(&cert_pem, &key_pem)?;
+    let (certs, key) = crate::tls::load_certs_from_pem(&cache.cert_path(), &cache.key_path())?;
+    resolver.set_certificate(certified_key(certs, &key)?);
+    tracing::info!(domains = ?config.domains, "ACME certificate issued and installed");
+    Ok(())
+}
+
+/// Run one ACME order end to end; returns (certificate chain PEM, private key PEM).
+async fn issue(
+    config: &AcmeConfig,
+    cache: &AcmeCache,
+    resolver: &AcmeCertResolver,
+) -> Result<(String, String), BoxError> {
+    let account = match cache.load_account() {
+        Some(credentials) => Account::builder()?.from_credentials(credentials).await?,
+        None => {
+            let contact = config
+                .contact_email
+                .as_ref()
+                .map(|email| format!("mailto:{}", email));
+            let contact: Vec<&str> = contact.iter().map(String::as_str).collect();
+            let (account, credentials) = Account::builder()?
+                .create(
+                    &NewAccount {
+                        contact: &contact,
+                        terms_of_service_agreed: true,
+                        only_return_existing: false,
+                    },
+                    config.directory_url.clone(),
+                    None,
+                )
+                .await?;
+            cache.save_account(&credentials)?;
+            account
+        }
+    };
+
+    let identifiers: Vec<Identifier> = config
+        .domains
+        .iter()
+        .map(|d| Identifier::Dns(d.clone()))
+        .collect();
+    let mut order = account.new_order(&NewOrder::new(&identifiers)).await?;
+
+    let mut authorizations = order.authorizations();
+    while let Some(result) = authorizations.next().await {
+        let mut authz = result?;
+        match authz.status {
+            AuthorizationStatus::Pending => {}
+            AuthorizationStatus::Valid => continue,
+            status => return Err(format!("ACME authorization is {:?}", status).into()),
+        }
+        let mut challenge = authz
+            .challenge(ChallengeType::TlsAlpn01)
+            .ok_or("ACME server offered no tls-alpn-01 challenge")?;
+        let domain = challenge.identifier().to_string();
+        let key_auth = challenge.key_authorization();
+        resolver.set_challenge(
+            &domain,
+            challenge_cert(&domain, key_auth.digest().as_ref())?,
+        );
+        challenge.set_ready().await?;
+    }
+
+    let status = order.poll_ready(&RetryPolicy::default()).await?;
+    if status != OrderStatus::Ready {
+        return Err(format!("ACME order not ready: {:?}", status).into());
+    }
+
+    let key_pair = rcgen::KeyPair::generate()?;
+    let mut params = rcgen::CertificateParams::new(config.domains.clone())?;
+    params.distinguished_name = rcgen::DistinguishedName::new();
+    let csr = params.serialize_request(&key_pair)?;
+    order.finalize_csr(csr.der()).await?;
+    let cert_pem = order.poll_certificate(&RetryPolicy::default()).await?;
+    Ok((cert_pem, key_pair.serialize_pem()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults() {
+        let cfg: AcmeConfig =
+            serde_json::from_str(r#"{ "domains": ["ctx.example.com"] }"#).unwrap();
+        assert_eq!(cfg.directory_url, LETS_ENCRYPT_DIRECTORY);
+        assert_eq!(cfg.cache_dir, "acme");
+        assert_eq!(cfg.renew_before_days, 30);
+    }
+
+    #[test]
+    fn resolver_serves_challenge_cert_only_for_acme_alpn() {
+        let resolver = AcmeCertResolver::new();
+        let regular = placeholder_cert(&["ctx.example.com".to_string()]).unwrap();
+        let challenge = challenge_cert("ctx.example.com", &[0u8; 32]).unwrap();
+        resolver.set_certificate(regular.clone());
+        resolver.set_challenge("ctx.example.com", challenge.clone());
+
+        let served = resolver.select(Some("CTX.example.com"), true).unwrap();
+        assert!(Arc::ptr_eq(&served, &challenge));
+        let served = resolver.select(Some("ctx.example.com"), false).unwrap();
+        assert!(Arc::ptr_eq(&served, &regular));
+        assert!(resolver.select(Some("other.example.com"), true).is_none());
+    }
+
+    #[test]
+    fn needs_renewal_checks_expiry_window() {
+        // rcgen's default validity runs to 4096, far outside any renewal window.
+        let (certs, _) = crate::tls::generate_dev_cert().unwrap();
+        assert!(!needs_renewal(
+            &certs[0],
+            Duration::from_secs(30 * 24 * 60 * 60)
+        ));
+        assert!(needs_renewal(
+            &CertificateDer::from(vec![0u8; 4]),
+            Duration::ZERO
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Architecture Decision Records: decision nodes rendered as ADR Markdown
+//! (`GET /decisions/:id/adr`, `GET /decisions/adr`, `export adr`), so teams that keep
+//! ADRs in git can keep doing so.
+//!
+//! The layout follows the common Nygard template — title, status, context, decision —
+//! with the node's rationale, alternatives, relationships and provenance (who created it
+//! and which applied proposals changed it, from `metadata.fieldChanges`) as extra
+//! sections. Bulk exports number the records `0001`, `0002`, … in decision order
+//! (`decidedAt`, else `createdAt`), as ADR tools do.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::types::{ContextNode, NodeStatus, NodeType};
+
+/// One rendered record of a bulk export.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdrFile {
+    /// File name, e.g. `0003-use-postgres.md`.
+    pub path: String,
+    pub node_id: String,
+    pub content: String,
+}
+
+/// ADR status word for a node status.
+fn status_word(status: NodeStatus) -> &'static str {
+    match status {
+        NodeStatus::Proposed => "Proposed",
+        NodeStatus::Accepted => "Accepted",
+        NodeStatus::Rejected => "Rejected",
+        NodeStatus::Superseded => "Superseded",
+        NodeStatus::Archived => "Deprecated",
+    }
+}
+
+/// The record's title: the node title, else the first line of the decision or content.
+pub fn title(node: &ContextNode) -> String {
+    if let Some(title) = node.title.as_deref().filter(|t| !t.trim().is_empty()) {
+        return title.trim().to_string();
+    }
+    let text = node.decision.as_deref().unwrap_or(&node.content);
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.is_empty() {
+        node.id.key()
+    } else {
+        line.chars().take(80).collect()
+    }
+}
+
+/// Lowercase words of the title joined by `-`, for file names.
+fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "decision".to_string()
+    } else {
+        slug.chars()
+            .take(60)
+            .collect::<String>()
+            .trim_end_matches('-')
+            .to_string()
+    }
+}
+
+/// When the decision was made: `decidedAt`, else `createdAt`.
+fn decided_at(node: &ContextNode) -> &str {
+    node.decided_at
+        .as_deref()
+        .unwrap_or(&node.metadata.created_at)
+}
+
+/// Render one decision node as ADR Markdown; `number` prefixes the title in bulk exports.
+pub fn render(node: &ContextNode, number: Option<usize>) -> String {
+    let mut out = String::new();
+    let title = title(node);
+    match number {
+        Some(n) => writeln!(out, "# {}. {}", n, title),
+        None => writeln!(out, "# {}", title),
+    }
+    .ok();
+    out.push('\n');
+    writeln!(out, "- Status: {}", status_word(node.status)).ok();
+    writeln!(
+        out,
+        "- Date: {}",
+        decided_at(node).get(..10).unwrap_or_default()
+    )
+    .ok();
+    writeln!(out, "- Deciders: {}", node.metadata.created_by).ok();
+    writeln!(out, "- TruthLayer node: `{}`", node.id.key()).ok();
+    if let Some(tags) = node.metadata.tags.as_ref().filter(|t| !t.is_empty()) {
+        writeln!(out, "- Tags: {}", tags.join(", ")).ok();
+    }
+
+    let context = node
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+        .unwrap_or(&node.content);
+    section(&mut out, "Context", context);
+    section(
+        &mut out,
+        "Decision",
+        node.decision.as_deref().unwrap_or(&node.content),
+    );
+    if let Some(rationale) = node.rationale.as_deref().filter(|r| !r.trim().is_empty()) {
+        section(&mut out, "Rationale", rationale);
+    }
+    if let Some(alternatives) = node.alternatives.as_ref().filter(|a| !a.is_empty()) {
+        let list: Vec<String> = alternatives.iter().map(|a| format!("- {}", a)).collect();
+        section(&mut out, "Alternatives considered", &list.join("\n"));
+    }
+
+    let mut related: Vec<String> = node
+        .relationships
+        .iter()
+        .flatten()
+        .map(|r| {
+            let kind = serde_json::to_value(r.relationship_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            format!("- {}: `{}`", kind, r.target.key())
+        })
+        .collect();
+    related.extend(
+        node.relations
+            .iter()
+            .flatten()
+            .map(|id| format!("- related-to: `{}`", id.key())),
+    );
+    if !related.is_empty() {
+        section(&mut out, "Related", &related.join("\n"));
+    }
+
+    let mut provenance = vec![format!(
+        "- Created by {} on {}",
+        node.metadata.created_by, node.metadata.created_at
+    )];
+    // Proposal id -> (version, line), so each proposal is listed once, in order.
+    let mut proposals: BTreeMap<&str, (u32, String)> = BTreeMap::new();
+    for blame in node.metadata.field_changes.iter().flat_map(|f| f.values()) {
+        proposals.entry(&blame.proposal_id).or_insert_with(|| {
+            (
+                blame.version,
+                format!(
+                    "- Version {}: proposal `{}` by {}, applied by {} on {}",
+                    blame.version,
+                    blame.proposal_id,
+                    blame.author,
+                    blame.applied_by,
+                    blame.changed_at
+                ),
+            )
+        });
+    }
+    let mut changes: Vec<(u32, String)> = proposals.into_values().collect();
+    changes.sort();
+    provenance.extend(changes.into_iter().map(|(_, line)| line));
+    provenance.push(format!(
+        "- Last modified by {} on {} (version {})",
+        node.metadata.modified_by, node.metadata.modified_at, node.metadata.version
+    ));
+    section(&mut out, "Provenance", &provenance.join("\n"));
+    out
+}
+
+fn section(out: &mut String, heading: &str, body: &str) {
+    write!(out, "\n## {}\n\n{}\n", heading, body.trim_end()).ok();
+}
+
+/// Number and render `nodes` (decisions only) in decision order.
+pub fn render_all(nodes: &[ContextNode]) -> Vec<AdrFile> {
+    let mut decisions: Vec<&ContextNode> = nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Decision)
+        .collect();
+    decisions.sort_by(|a, b| {
+        decided_at(a)
+            .cmp(decided_at(b))
+            .then_with(|| a.id.key().cmp(&b.id.key()))
+    });
+    decisions
+        .into_iter()
+        .enumerate()
+        .map(|(i, node)| AdrFile {
+            path: format!("{:04}-{}.md", i + 1, slug(&title(node))),
+            node_id: node.id.key(),
+            content: render(node, Some(i + 1)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(id: &str, decided_at: &str) -> ContextNode {
+        serde_json::from_value(serde_json::json!({
+            "id": { "id": id }, "type": "decision", "status": "accepted",
+            "title": format!("Use Postgres ({})", id),
+            "content": "We need a database.",
+            "decision": "Use Postgres for the store.",
+            "rationale": "Team knows it.",
+            "alternatives": ["MongoDB", "SQLite"],
+            "decidedAt": decided_at,
+            "relationships": [{ "type": "depends-on", "target": { "id": "goal-1" } }],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z", "createdBy": "alice",
+                "modifiedAt": "2026-02-01T00:00:00Z", "modifiedBy": "bob", "version": 2,
+                "fieldChanges": {
+                    "rationale": { "proposalId": "p-2", "author": "bob", "appliedBy": "carol",
+                                   "changedAt": "2026-02-01T00:00:00Z", "version": 2 }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn decisions_render_as_numbered_adrs() {
+        let files = render_all(&[
+            decision("decision-2", "2026-03-01T00:00:00Z"),
+            decision("decision-1", "2026-01-15T00:00:00Z"),
+        ]);
+        assert_eq!(files[0].path, "0001-use-postgres-decision-1.md");
+        assert_eq!(files[1].node_id, "decision-2");
+        let adr = &files[0].content;
+        assert!(adr.starts_with(
+            "# 1. Use Postgres (decision-1)\n\n- Status: Accepted\n- Date: 2026-01-15\n"
+        ));
+        assert!(adr.contains("## Decision\n\nUse Postgres for the store.\n"));
+        assert!(adr.contains("## Alternatives considered\n\n- MongoDB\n- SQLite\n"));
+        assert!(adr.contains("- depends-on: `goal-1`"));
+        assert!(adr.contains("proposal `p-2` by bob, applied by carol"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Actor directory for access reviews and DSAR subject discovery: `GET /actors` lists
+//! every actor the server knows, from the audit log and from the configuration that
+//! grants access (mTLS identities, SCIM users, Slack and forge identities).
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Extension, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+
+/// Audit events read per page while scanning the log.
+const AUDIT_PAGE: u32 = 1000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/actors", get(list_actors))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActorSummary {
+    pub actor_id: String,
+    /// From the configuration, else the latest audit event; None when neither says.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_type: Option<String>,
+    /// Roles the configuration grants (tokens may carry others).
+    pub roles: Vec<Role>,
+    /// Where the actor was found: `audit`, `mtls`, `scim`, `slack`, `forge`.
+    pub sources: Vec<&'static str>,
+    /// False for deprovisioned SCIM users; None for actors SCIM does not manage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+    /// Audit events by the actor.
+    pub event_count: u64,
+    /// Audit events by the actor, per action.
+    pub action_counts: BTreeMap<String, u64>,
+}
+
+impl ActorSummary {
+    fn add_source(&mut self, source: &'static str) {
+        if !self.sources.contains(&source) {
+            self.sources.push(source);
+        }
+    }
+
+    fn add_roles(&mut self, roles: &[Role]) {
+        for role in roles {
+            if !self.roles.contains(role) {
+                self.roles.push(*role);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActorsResponse {
+    pub actors: Vec<ActorSummary>,
+    pub total: usize,
+}
+
+fn entry<'a>(actors: &'a mut BTreeMap<String, ActorSummary>, id: &str) -> &'a mut ActorSummary {
+    actors
+        .entry(id.to_string())
+        .or_insert_with(|| ActorSummary {
+            actor_id: id.to_string(),
+            ..Default::default()
+        })
+}
+
+fn earlier(a: &str, b: &str) -> bool {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a < b,
+        _ => a < b,
+    }
+}
+
+/// `GET /actors` — every actor in the audit log or the access configuration, by actor
+/// id, with first/last seen and activity counts (Admin).
+async fn list_actors(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<ActorsResponse>, ApiError> {
+    service::require_route(&state, &actor, "GET /actors", Role::Admin)?;
+    let mut actors: BTreeMap<String, ActorSummary> = BTreeMap::new();
+
+    let mut offset = 0;
+    loop {
+        let page = state
+            .store
+            .query_audit(None, None, None, None, None, Some(AUDIT_PAGE), Some(offset))
+            .await?;
+        for event in &page.events {
+            let summary = entry(&mut actors, &event.actor_id);
+            summary.add_source("audit");
+            summary.event_count += 1;
+            let action = serde_json::to_value(&event.action)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            *summary.action_counts.entry(action).or_default() += 1;
+            if summary
+                .first_seen
+                .as_deref()
+                .is_none_or(|t| earlier(&event.timestamp, t))
+            {
+                summary.first_seen = Some(event.timestamp.clone());
+            }
+            if summary
+                .last_seen
+                .as_deref()
+                .is_none_or(|t| !earlier(&event.timestamp, t))
+            {
+                summary.last_seen = Some(event.timestamp.clone());
+                summary.actor_type = Some(event.actor_type.clone());
+            }
+        }
+        offset += page.events.len() as u32;
+        if !page.has_more || page.events.is_empty() {
+            break;
+        }
+    }
+
+    let config = state.runtime.config.get();
+    for mapping in config.mtls.iter().flat_map(|m| &m.identities) {
+        let actor_id = mapping.actor_id.as_deref().unwrap_or(&mapping.subject);
+        let summary = entry(&mut actors, actor_id);
+        summary.add_source("mtls");
+        summary.add_roles(&mapping.roles);
+        summary.actor_type = serde_json::to_value(mapping.actor_type)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string));
+    }
+    let directory = state.store.get_directory().await?;
+    for user in directory.users.values() {
+        let summary = entry(&mut actors, &user.user_name);
+        summary.add_source("scim");
+        summary.active = Some(user.active);
+        if let Some(access) = directory.access(&user.user_name) {
+            if let Some(roles) = config.scim.roles_for(&access.groups) {
+                summary.add_roles(&roles);
+            }
+        }
+    }
+    // Slack and forge reviews carry the roles of their integration (see
+    // `service::mapped_actor`).
+    for slack in config.slack.iter() {
+        for actor_id in slack.identities.values() {
+            let summary = entry(&mut actors, actor_id);
+            summary.add_source("slack");
+            summary.add_roles(slack.roles.get(actor_id).map_or(&[], Vec::as_slice));
+        }
+    }
+    for ws in config.forge.workspaces.values() {
+        for actor_id in ws.identities.values() {
+            let summary = entry(&mut actors, actor_id);
+            summary.add_source("forge");
+            summary.add_roles(ws.roles.get(actor_id).map_or(&[], Vec::as_slice));
+        }
+    }
+
+    let actors: Vec<ActorSummary> = actors.into_values().collect();
+    Ok(Json(ActorsResponse {
+        total: actors.len(),
+        actors,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn lists_audited_and_configured_actors() {
+        let config = crate::config::ServerConfig {
+            mtls: Some(crate::mtls::MtlsConfig {
+                client_ca_path: "ca.pem".to_string(),
+                required: false,
+                identities: vec![crate::mtls::ClientIdentityMapping {
+                    subject: "ci.example.com".to_string(),
+                    actor_id: Some("ci-bot".to_string()),
+                    actor_type: crate::auth::ActorType::Agent,
+                    roles: vec![Role::Contributor],
+                }],
+            }),
+            ..Default::default()
+        };
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            crate::reload::RuntimeConfig::new(config, crate::policy::PolicyConfig::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let proposal = serde_json::json!({
+            "id": "p-actors",
+            "status": "open",
+            "operations": [],
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
+        });
+        let create = Request::builder()
+            .method("POST")
+            .uri("/proposals")
+            .header("content-type", "application/json")
+            .body(Body::from(proposal.to_string()))
+            .unwrap();
+        assert!(app
+            .clone()
+            .oneshot(create)
+            .await
+            .unwrap()
+            .status()
+            .is_success());
+
+        let res = app
+            .oneshot(Request::get("/actors").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let list: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(list["total"], 2);
+        let bot = &list["actors"][0];
+        assert_eq!(bot["actorId"], "ci-bot");
+        assert_eq!(bot["actorType"], "agent");
+        assert_eq!(bot["roles"], serde_json::json!(["contributor"]));
+        assert_eq!(bot["sources"], serde_json::json!(["mtls"]));
+        assert_eq!(bot["eventCount"], 0);
+        let dev = &list["actors"][1];
+        assert_eq!(dev["actorId"], "dev-user");
+        assert_eq!(dev["sources"], serde_json::json!(["audit"]));
+        assert!(dev["eventCount"].as_u64().unwrap() >= 1);
+        assert_eq!(dev["actionCounts"]["proposal_created"], 1);
+        assert!(dev["firstSeen"].is_string() && dev["lastSeen"].is_string());
+    }
+}
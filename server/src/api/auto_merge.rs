@@ -0,0 +1,350 @@
+//! Auto-merge (`POST /proposals/auto-merge`): open proposals that touch the same nodes
+//! but different fields become one combined proposal, and the originals are superseded.
+//!
+//! The proposals must be trivially compatible: the field merge (`POST /proposals/merge`)
+//! finds no conflict, and every node more than one of them touches is only updated by
+//! them (no create, delete or status change). The combined proposal:
+//!
+//! - has each proposal's operations in turn, ids prefixed `{proposalId}:`; a node's
+//!   updates become one update with the merged fields, where its first update was;
+//! - is created by the caller, with the originals' authors in `metadata.authors`, the
+//!   union of their `requiredApprovers`, the oldest base version of each node and the
+//!   originals in `relations`;
+//! - is checked and audited like any new proposal.
+//!
+//! Each original becomes `superseded` with `metadata.supersededBy` set, audited as
+//! `proposal_superseded`. Reviews of the originals do not carry over.
+
+use std::collections::{BTreeSet, HashMap};
+
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::proposal_operations::{set_id, set_order};
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::api::validate;
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::rbac;
+use crate::store::context_store::StoreError;
+use crate::store::lifecycle::{self, Transition};
+use crate::store::WriteBatch;
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, MergeResult, Operation, Proposal,
+    ProposalMetadata, ProposalStatus,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/proposals/auto-merge", post(auto_merge))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoMergeRequest {
+    pub proposal_ids: Vec<String>,
+    /// Rationale of the combined proposal; default names the originals.
+    #[serde(default)]
+    pub rationale: Option<String>,
+}
+
+fn node_key(op: &Operation) -> String {
+    match op {
+        Operation::Create { node, .. } => node.id.key(),
+        Operation::Update { node_id, .. }
+        | Operation::Delete { node_id, .. }
+        | Operation::StatusChange { node_id, .. } => node_id.key(),
+    }
+}
+
+/// The operations of `proposals` as one list, or why they are not trivially compatible.
+/// `merge` is their field merge.
+pub fn combine(proposals: &[Proposal], merge: &MergeResult) -> Result<Vec<Operation>, String> {
+    if let Some(conflict) = merge.conflicts.first() {
+        return Err(format!(
+            "node {}: the proposals set {} to different values",
+            conflict.node_id.key(),
+            conflict.field
+        ));
+    }
+    // Node key -> the proposals touching it, and whether all of them only update it.
+    let mut touched: HashMap<String, (BTreeSet<&str>, bool)> = HashMap::new();
+    for proposal in proposals {
+        for op in &proposal.operations {
+            let entry = touched
+                .entry(node_key(op))
+                .or_insert_with(|| (BTreeSet::new(), true));
+            entry.0.insert(&proposal.id);
+            entry.1 &= matches!(op, Operation::Update { .. });
+        }
+    }
+    let mut shared: Vec<_> = touched
+        .iter()
+        .filter(|(_, (ids, only_updates))| ids.len() > 1 && !only_updates)
+        .collect();
+    shared.sort_by(|a, b| a.0.cmp(b.0));
+    if let Some((key, (ids, _))) = shared.first() {
+        return Err(format!(
+            "node {}: {} do more than update it",
+            key,
+            ids.iter().copied().collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    let mut fields: HashMap<String, serde_json::Map<String, serde_json::Value>> = HashMap::new();
+    for change in merge.merged.iter().chain(&merge.auto_merged) {
+        fields
+            .entry(change.node_id.key())
+            .or_default()
+            .insert(change.field.clone(), change.new_value.clone());
+    }
+    let mut operations = Vec::new();
+    for proposal in proposals {
+        let mut ops: Vec<&Operation> = proposal.operations.iter().collect();
+        ops.sort_by_key(|op| validate::op_id_and_order(op).1);
+        for op in ops {
+            let mut combined = op.clone();
+            if let Operation::Update { changes, .. } = &mut combined {
+                // Updates after a node's first one are already in it.
+                let Some(fields) = fields.remove(&node_key(op)) else {
+                    continue;
+                };
+                *changes = serde_json::from_value(serde_json::Value::Object(fields))
+                    .map_err(|e| format!("node {}: {}", node_key(op), e))?;
+            }
+            let id = validate::op_id_and_order(op).0;
+            set_id(&mut combined, format!("{}:{}", proposal.id, id));
+            set_order(&mut combined, operations.len() as u32 + 1);
+            operations.push(combined);
+        }
+    }
+    Ok(operations)
+}
+
+/// `POST /proposals/auto-merge` (Reviewer; humans only) → `201` with the combined
+/// proposal.
+async fn auto_merge(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(body): StrictJson<AutoMergeRequest>,
+) -> Result<(StatusCode, Json<Proposal>), ApiError> {
+    service::require_route(&state, &actor, "POST /proposals/auto-merge", Role::Reviewer)?;
+    rbac::reject_agent(&actor, "auto-merge proposals")?;
+    let ids = body.proposal_ids;
+    if ids.iter().collect::<BTreeSet<_>>().len() != ids.len() {
+        return Err(ApiError::Invalid(
+            "proposalIds: each proposal is named once".to_string(),
+        ));
+    }
+    let mut originals = Vec::new();
+    for id in &ids {
+        let proposal = service::get_proposal(&state, &actor, id).await?;
+        lifecycle::next_status(proposal.status, Transition::Supersede).map_err(StoreError::from)?;
+        originals.push(proposal);
+    }
+    let merge = service::merge_proposals(&state, &actor, &ids).await?;
+    let operations = combine(&originals, &merge).map_err(ApiError::Invalid)?;
+
+    let mut authors: Vec<String> = Vec::new();
+    let mut required: Vec<String> = Vec::new();
+    let mut base_versions: HashMap<String, u32> = HashMap::new();
+    for original in &originals {
+        let metadata = &original.metadata;
+        if !authors.contains(&metadata.created_by) {
+            authors.push(metadata.created_by.clone());
+        }
+        for approver in metadata.required_approvers.iter().flatten() {
+            if !required.contains(approver) {
+                required.push(approver.clone());
+            }
+        }
+        for (key, version) in metadata.base_versions.iter().flatten() {
+            let base = base_versions.entry(key.clone()).or_insert(*version);
+            *base = (*base).min(*version);
+        }
+    }
+    let combined = Proposal {
+        id: String::new(),
+        status: ProposalStatus::Open,
+        operations,
+        metadata: ProposalMetadata {
+            rationale: Some(
+                body.rationale
+                    .unwrap_or_else(|| format!("Auto-merge of {}", ids.join(", "))),
+            ),
+            required_approvers: (!required.is_empty()).then_some(required),
+            base_versions: (!base_versions.is_empty()).then_some(base_versions),
+            authors: Some(authors),
+            ..Default::default()
+        },
+        comments: None,
+        relations: Some(ids.clone()),
+        applied: None,
+    };
+    let combined = service::create_server_proposal(&state, &actor, combined).await?;
+
+    let mut batch = WriteBatch::new();
+    for original in &originals {
+        batch = batch
+            .supersede_proposal(&original.id, &combined.id)
+            .audit(
+                AuditEvent::new(
+                    &actor.actor_id,
+                    actor_type_str(&actor),
+                    AuditAction::ProposalSuperseded,
+                    &original.id,
+                    AuditOutcome::Success,
+                )
+                .in_workspace(original.workspace())
+                .with_details(AuditDetails::Superseded {
+                    superseded_by: combined.id.clone(),
+                }),
+            )
+            .publish(service::server_event(
+                EventKind::ProposalUpdated {
+                    status: ProposalStatus::Superseded,
+                },
+                &original.id,
+                &actor,
+            ));
+    }
+    if let Err(e) = service::execute(&state, batch.in_workspace(combined.workspace())).await {
+        // An original changed meanwhile (reviewed, withdrawn): drop the combined one.
+        let withdraw = WriteBatch::new()
+            .withdraw_proposal(&combined.id)
+            .audit(AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(&actor),
+                AuditAction::ProposalWithdrawn,
+                &combined.id,
+                AuditOutcome::Success,
+            ))
+            .publish(service::server_event(
+                EventKind::ProposalUpdated {
+                    status: ProposalStatus::Withdrawn,
+                },
+                &combined.id,
+                &actor,
+            ));
+        let _ = service::execute(&state, withdraw.in_workspace(combined.workspace())).await;
+        return Err(e);
+    }
+    Ok((StatusCode::CREATED, Json(combined)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn proposal(id: &str, author: &str, operations: serde_json::Value) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "status": "open", "operations": operations,
+            "metadata": { "createdBy": author }
+        }))
+        .unwrap()
+    }
+
+    fn update(id: &str, order: u32, changes: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "type": "update", "id": id, "order": order,
+            "node_id": { "id": "n1" }, "changes": changes })
+    }
+
+    #[tokio::test]
+    async fn compatible_proposals_merge_and_are_superseded() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let created = serde_json::json!({ "type": "create", "id": "op-2", "order": 2,
+            "node": { "id": { "id": "n2" }, "type": "note", "status": "proposed",
+                      "content": "new", "metadata": { "createdAt": "", "createdBy": "bob", "modifiedAt": "",
+                                "modifiedBy": "bob", "version": 0 } } });
+        for p in [
+            proposal(
+                "p-a",
+                "alice",
+                serde_json::json!([update("op-1", 1, serde_json::json!({ "title": "T" }))]),
+            ),
+            proposal(
+                "p-b",
+                "bob",
+                serde_json::json!([
+                    update("op-1", 1, serde_json::json!({ "content": "C" })),
+                    created
+                ]),
+            ),
+            proposal(
+                "p-c",
+                "carol",
+                serde_json::json!([update("op-1", 1, serde_json::json!({ "title": "other" }))]),
+            ),
+        ] {
+            store.create_proposal(p).await.unwrap();
+        }
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let merge = |ids: serde_json::Value| {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/proposals/auto-merge")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "proposalIds": ids }).to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, _) = merge(serde_json::json!(["p-a", "p-c"])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "both set the title");
+        let (status, combined) = merge(serde_json::json!(["p-a", "p-b"])).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let ops = combined["operations"].as_array().unwrap();
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0]["id"], "p-a:op-1");
+        assert_eq!(
+            ops[0]["changes"],
+            serde_json::json!({ "title": "T", "content": "C" })
+        );
+        assert_eq!(ops[1]["id"], "p-b:op-2");
+        assert_eq!(ops[1]["order"], 2);
+        assert_eq!(
+            combined["metadata"]["authors"],
+            serde_json::json!(["alice", "bob"])
+        );
+        assert_eq!(combined["metadata"]["createdBy"], "dev-user");
+        assert_eq!(combined["relations"], serde_json::json!(["p-a", "p-b"]));
+
+        let id = combined["id"].as_str().unwrap();
+        for original in ["p-a", "p-b"] {
+            let stored = store.get_proposal(original).await.unwrap().unwrap();
+            assert_eq!(stored.status, ProposalStatus::Superseded);
+            assert_eq!(stored.metadata.superseded_by.as_deref(), Some(id));
+        }
+        let (status, _) = merge(serde_json::json!(["p-a", "p-c"])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "p-a is superseded");
+    }
+}
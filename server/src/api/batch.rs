@@ -0,0 +1,277 @@
+//! `POST /agent/batch`: several node / proposal reads in one request.
+//!
+//! Agents typically issue dozens of small GETs per task; over high-latency links the
+//! round trips dominate. A batch runs each item through the same shared operations as
+//! the single routes (per-item RBAC, sensitivity filtering and audit) and returns every
+//! outcome in order: one failing item does not fail the batch.
+
+use axum::{
+    extract::{Extension, State},
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState, ProvenanceResponse};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::ActorContext;
+use crate::types::{NodeId, NodeQuery};
+
+/// Most items accepted in one batch.
+pub const MAX_BATCH_ITEMS: usize = 50;
+
+/// One read in a batch; mirrors a single GET route.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchQuery {
+    /// `GET /nodes/:id`
+    GetNode {
+        id: String,
+        #[serde(default)]
+        namespace: Option<String>,
+    },
+    /// `GET /nodes` with the full node query (type, status, search, tags, paging, ...).
+    QueryNodes {
+        #[serde(default)]
+        query: NodeQuery,
+    },
+    /// `GET /nodes/:id/provenance`
+    GetProvenance {
+        id: String,
+        #[serde(default)]
+        namespace: Option<String>,
+    },
+    /// `GET /proposals/:id`
+    GetProposal { id: String },
+    /// `GET /proposals`
+    ListProposals {
+        #[serde(default)]
+        limit: Option<u32>,
+        #[serde(default)]
+        offset: Option<u32>,
+    },
+    /// `GET /proposals/:id/reviews`
+    GetReviewHistory {
+        #[serde(rename = "proposalId")]
+        proposal_id: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItem {
+    /// Caller's correlation ID, echoed in the result.
+    #[serde(default, rename = "ref")]
+    pub reference: Option<String>,
+    #[serde(flatten)]
+    pub query: BatchQuery,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchRequest {
+    pub items: Vec<BatchItem>,
+}
+
+/// Outcome of one item: the status and body the single route would have returned.
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchResponse {
+    pub results: Vec<BatchResult>,
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/agent/batch", post(agent_batch))
+}
+
+async fn agent_batch(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(request): StrictJson<BatchRequest>,
+) -> Result<Json<BatchResponse>, ApiError> {
+    if request.items.len() > MAX_BATCH_ITEMS {
+        return Err(ApiError::Invalid(format!(
+            "batch has {} items; at most {} are allowed",
+            request.items.len(),
+            MAX_BATCH_ITEMS
+        )));
+    }
+
+    let mut results = Vec::with_capacity(request.items.len());
+    for item in request.items {
+        let (status, body) = match run(&state, &actor, item.query).await {
+            Ok(body) => (200, body),
+            Err(e) => {
+                let (status, body) = e.status_and_body();
+                (status.as_u16(), body)
+            }
+        };
+        results.push(BatchResult {
+            reference: item.reference,
+            status,
+            body,
+        });
+    }
+    Ok(Json(BatchResponse { results }))
+}
+
+fn to_json<T: Serialize>(value: T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+async fn run(
+    state: &AppState,
+    actor: &ActorContext,
+    query: BatchQuery,
+) -> Result<serde_json::Value, ApiError> {
+    Ok(match query {
+        BatchQuery::GetNode { id, namespace } => {
+            let node_id = NodeId { id, namespace };
+            service::get_node(state, actor, &node_id).await?.to_json()
+        }
+        BatchQuery::QueryNodes { query } => {
+            to_json(service::query_nodes(state, actor, query).await?)
+        }
+        BatchQuery::GetProvenance { id, namespace } => {
+            let id = NodeId { id, namespace }.key();
+            let events = service::get_provenance(state, actor, &id).await?;
+            to_json(ProvenanceResponse {
+                resource_id: id,
+                events,
+            })
+        }
+        BatchQuery::GetProposal { id } => to_json(service::get_proposal(state, actor, &id).await?),
+        BatchQuery::ListProposals { limit, offset } => {
+            to_json(service::list_open_proposals(state, actor, limit, offset).await?)
+        }
+        BatchQuery::GetReviewHistory { proposal_id } => {
+            to_json(service::get_review_history(state, actor, &proposal_id).await?)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{ActorType, Role};
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::sensitivity::Sensitivity;
+    use crate::store::{ContextStore, StoreBundle};
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn app(actor: ActorContext) -> Router<()> {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let mut bundle: StoreBundle =
+            serde_json::from_str(include_str!("../../fixtures/demo/demo.json")).unwrap();
+        for node in &mut bundle.nodes {
+            if node.id.id == "risk-001" {
+                node.metadata.sensitivity = Some(Sensitivity::Restricted);
+            }
+        }
+        store.import_bundle(bundle).await.unwrap();
+        crate::api::routes::router(
+            store,
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            move |mut req: Request<Body>, next: axum::middleware::Next| {
+                let actor = actor.clone();
+                async move {
+                    req.extensions_mut().insert(actor);
+                    next.run(req).await
+                }
+            },
+        ))
+    }
+
+    async fn batch(app: Router<()>, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/agent/batch")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let status = res.status();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn runs_each_item_with_its_own_outcome() {
+        let agent = ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: ActorType::Agent,
+            roles: vec![Role::Reader],
+        };
+        let (status, res) = batch(
+            app(agent).await,
+            serde_json::json!({ "items": [
+                { "ref": "g", "type": "get_node", "id": "goal-001" },
+                { "ref": "r", "type": "get_node", "id": "risk-001" },
+                { "type": "get_node", "id": "missing" },
+                { "type": "query_nodes", "query": { "status": ["accepted"] } },
+                { "type": "get_proposal", "id": "proposal-demo-002" },
+                { "type": "get_review_history", "proposalId": "proposal-demo-002" },
+            ]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let results = res["results"].as_array().unwrap();
+        assert_eq!(results.len(), 6);
+        assert_eq!(results[0]["ref"], "g");
+        assert_eq!(results[0]["status"], 200);
+        assert_eq!(results[0]["body"]["id"]["id"], "goal-001");
+        // Sensitivity filtering applies per item.
+        assert_eq!(results[1]["body"]["redacted"], true);
+        assert_eq!(results[2]["status"], 404);
+        let queried = results[3]["body"]["nodes"].as_array().unwrap();
+        assert!(queried.iter().all(|n| n["id"]["id"] != "risk-001"));
+        assert_eq!(results[4]["body"]["id"], "proposal-demo-002");
+        assert_eq!(results[5]["body"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rbac_is_per_item_and_size_is_capped() {
+        let nobody = ActorContext {
+            actor_id: "nobody".to_string(),
+            actor_type: ActorType::Human,
+            roles: vec![],
+        };
+        let (status, res) = batch(
+            app(nobody).await,
+            serde_json::json!({ "items": [{ "type": "get_node", "id": "goal-001" }] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(res["results"][0]["status"], 403);
+
+        let items: Vec<_> = (0..=MAX_BATCH_ITEMS)
+            .map(|_| serde_json::json!({ "type": "list_proposals" }))
+            .collect();
+        let (status, _) = batch(
+            app(ActorContext::dev_default()).await,
+            serde_json::json!({ "items": items }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
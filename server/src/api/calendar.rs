@@ -0,0 +1,394 @@
+//! Change calendar (`GET /calendar?from=&to=`): what landed, what is queued and when
+//! changes may land, on one timeline, for release managers planning around CAB windows.
+//!
+//! - `applied`: proposals applied in the range, at their apply time.
+//! - `queued`: accepted proposals awaiting apply (whatever the range), at their
+//!   acceptance, with `nextApplyAt`: the first time from now the enforced change windows
+//!   and freezes allow the apply.
+//! - `window`: each day's interval of a `change_window` policy rule (UTC).
+//! - `freeze`: `change_freeze` policy rules overlapping the range.
+//!
+//! Entries are sorted by start. Windows and freezes of rules that are not enforced are
+//! listed with their `enforcement` and do not hold `nextApplyAt` back.
+
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveDate, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+use crate::policy::{Enforcement, PolicyConfig, PolicyRule};
+use crate::types::{ProposalQuery, ProposalStatus};
+
+/// Longest range one request covers; also how far ahead `nextApplyAt` is looked for.
+pub const MAX_RANGE_DAYS: i64 = 366;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/calendar", get(calendar))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CalendarParams {
+    /// RFC 3339 or `YYYY-MM-DD` (UTC midnight); default a week ago.
+    pub from: Option<String>,
+    /// RFC 3339 or `YYYY-MM-DD`; default two weeks from now.
+    pub to: Option<String>,
+    /// Only proposals in this workspace.
+    pub workspace: Option<String>,
+}
+
+/// One item on the timeline.
+#[derive(Debug, Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum CalendarEntry {
+    Applied {
+        start: DateTime<Utc>,
+        proposal_id: String,
+        workspace: String,
+        applied_by: String,
+        revision: String,
+    },
+    Queued {
+        start: DateTime<Utc>,
+        proposal_id: String,
+        workspace: String,
+        /// None when no time in the next [`MAX_RANGE_DAYS`] allows it.
+        next_apply_at: Option<DateTime<Utc>>,
+    },
+    Window {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        #[serde(skip_serializing_if = "Enforcement::is_enforce")]
+        enforcement: Enforcement,
+    },
+    Freeze {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+        #[serde(skip_serializing_if = "Enforcement::is_enforce")]
+        enforcement: Enforcement,
+    },
+}
+
+impl CalendarEntry {
+    fn start(&self) -> DateTime<Utc> {
+        match self {
+            CalendarEntry::Applied { start, .. }
+            | CalendarEntry::Queued { start, .. }
+            | CalendarEntry::Window { start, .. }
+            | CalendarEntry::Freeze { start, .. } => *start,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarResponse {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub entries: Vec<CalendarEntry>,
+}
+
+fn parse_time(name: &str, value: &str) -> Result<DateTime<Utc>, ApiError> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+        .ok_or_else(|| {
+            ApiError::Invalid(format!(
+                "{}: '{}' is not an RFC 3339 time or YYYY-MM-DD",
+                name, value
+            ))
+        })
+}
+
+/// Whether `day` (0 = Monday) and `hour` are inside a `change_window` rule.
+fn in_window(allowed_days: &[u8], hour_start: u8, hour_end: u8, at: DateTime<Utc>) -> bool {
+    let day = at.weekday().num_days_from_monday() as u8;
+    let hour = at.hour() as u8;
+    allowed_days.contains(&day) && hour_start <= hour && hour < hour_end
+}
+
+/// Whether the enforced windows and freezes allow an apply at `at`, as
+/// `policy::evaluate_on_apply` would judge it then.
+fn apply_allowed(policies: &PolicyConfig, at: DateTime<Utc>) -> bool {
+    policies.enforced().all(|rule| match rule {
+        PolicyRule::ChangeWindow {
+            allowed_days,
+            allowed_hour_start,
+            allowed_hour_end,
+        } => in_window(allowed_days, *allowed_hour_start, *allowed_hour_end, at),
+        PolicyRule::ChangeFreeze { start, end, .. } => !(*start <= at && at < *end),
+        _ => true,
+    })
+}
+
+/// First time from `now` an apply is allowed: now, a top of the hour (windows are whole
+/// hours) or the end of a freeze.
+fn next_apply_at(policies: &PolicyConfig, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let horizon = now + Duration::days(MAX_RANGE_DAYS);
+    let hour = now.duration_trunc(Duration::hours(1)).unwrap_or(now);
+    let mut candidates: Vec<DateTime<Utc>> = std::iter::once(now)
+        .chain(
+            (1..)
+                .map(|h| hour + Duration::hours(h))
+                .take_while(|t| *t <= horizon),
+        )
+        .chain(policies.enforced().filter_map(|rule| match rule {
+            PolicyRule::ChangeFreeze { end, .. } if *end > now && *end <= horizon => Some(*end),
+            _ => None,
+        }))
+        .collect();
+    candidates.sort();
+    candidates.into_iter().find(|t| apply_allowed(policies, *t))
+}
+
+/// Window and freeze entries overlapping `from`..`to`.
+fn windows(policies: &PolicyConfig, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<CalendarEntry> {
+    let mut entries = Vec::new();
+    for configured in &policies.rules {
+        let enforcement = configured.enforcement;
+        match &configured.rule {
+            PolicyRule::ChangeWindow {
+                allowed_days,
+                allowed_hour_start,
+                allowed_hour_end,
+            } if allowed_hour_start < allowed_hour_end => {
+                let mut day = from.duration_trunc(Duration::days(1)).unwrap_or(from);
+                while day <= to {
+                    let start = day + Duration::hours((*allowed_hour_start).into());
+                    let end = day + Duration::hours((*allowed_hour_end).into());
+                    let weekday = day.weekday().num_days_from_monday() as u8;
+                    if allowed_days.contains(&weekday) && start <= to && end > from {
+                        entries.push(CalendarEntry::Window {
+                            start,
+                            end,
+                            enforcement,
+                        });
+                    }
+                    day += Duration::days(1);
+                }
+            }
+            PolicyRule::ChangeFreeze { start, end, reason } if *start <= to && *end > from => {
+                entries.push(CalendarEntry::Freeze {
+                    start: *start,
+                    end: *end,
+                    reason: reason.clone(),
+                    enforcement,
+                });
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// `GET /calendar?from=&to=&workspace=` (Reader).
+async fn calendar(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<CalendarParams>,
+) -> Result<Json<CalendarResponse>, ApiError> {
+    service::require_route(&state, &actor, "GET /calendar", Role::Reader)?;
+    let now = Utc::now();
+    let from = match params.from.as_deref() {
+        Some(from) => parse_time("from", from)?,
+        None => now - Duration::days(7),
+    };
+    let to = match params.to.as_deref() {
+        Some(to) => parse_time("to", to)?,
+        None => now + Duration::days(14),
+    };
+    if to < from {
+        return Err(ApiError::Invalid("to: must not be before from".to_string()));
+    }
+    if to - from > Duration::days(MAX_RANGE_DAYS) {
+        return Err(ApiError::Invalid(format!(
+            "from..to: at most {} days",
+            MAX_RANGE_DAYS
+        )));
+    }
+
+    let policies = state.runtime.policies.get();
+    let mut proposals = state
+        .store
+        .query_proposals(ProposalQuery {
+            status: Some(vec![ProposalStatus::Applied, ProposalStatus::Accepted]),
+            limit: Some(u32::MAX),
+            ..Default::default()
+        })
+        .await?;
+    proposals.retain(|p| {
+        params
+            .workspace
+            .as_deref()
+            .is_none_or(|ws| p.workspace() == ws)
+    });
+    let next_apply = next_apply_at(&policies, now);
+    let mut entries = windows(&policies, from, to);
+    for proposal in proposals {
+        let workspace = proposal.workspace().to_string();
+        match (proposal.status, &proposal.applied) {
+            (ProposalStatus::Applied, Some(applied)) => {
+                let Ok(at) = DateTime::parse_from_rfc3339(&applied.applied_at) else {
+                    continue;
+                };
+                let start = at.with_timezone(&Utc);
+                if start < from || start > to {
+                    continue;
+                }
+                entries.push(CalendarEntry::Applied {
+                    start,
+                    proposal_id: proposal.id,
+                    workspace,
+                    applied_by: applied.applied_by.clone(),
+                    revision: applied.applied_to_revision_id.clone(),
+                });
+            }
+            (ProposalStatus::Accepted, _) => {
+                let start = DateTime::parse_from_rfc3339(&proposal.metadata.modified_at)
+                    .map(|at| at.with_timezone(&Utc))
+                    .unwrap_or(now);
+                entries.push(CalendarEntry::Queued {
+                    start,
+                    proposal_id: proposal.id,
+                    workspace,
+                    next_apply_at: next_apply,
+                });
+            }
+            _ => {}
+        }
+    }
+    entries.sort_by_key(CalendarEntry::start);
+    Ok(Json(CalendarResponse { from, to, entries }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use crate::types::Proposal;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn proposal(id: &str, status: &str) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "status": status, "operations": [],
+            "metadata": { "modifiedAt": "2026-10-05T08:00:00Z" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn next_apply_waits_for_the_window_and_past_freezes() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let policies: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [
+                { "type": "change_window", "allowed_days": [1, 2, 3],
+                  "allowed_hour_start": 9, "allowed_hour_end": 17 },
+                { "type": "change_freeze", "start": "2026-10-06T00:00:00Z",
+                  "end": "2026-10-07T12:30:00Z" }
+            ]
+        }))
+        .unwrap();
+        // Monday 2026-10-05 is outside the days; Tuesday is frozen, Wednesday until 12:30.
+        assert_eq!(
+            next_apply_at(&policies, at("2026-10-05T10:15:00Z")),
+            Some(at("2026-10-07T12:30:00Z"))
+        );
+        assert_eq!(
+            next_apply_at(&policies, at("2026-10-08T10:15:00Z")),
+            Some(at("2026-10-08T10:15:00Z"))
+        );
+        assert_eq!(
+            next_apply_at(&PolicyConfig::default(), at("2026-10-05T10:15:00Z")),
+            Some(at("2026-10-05T10:15:00Z"))
+        );
+    }
+
+    #[tokio::test]
+    async fn calendar_lists_applied_queued_windows_and_freezes() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        store
+            .create_proposal(proposal("p-applied", "accepted"))
+            .await
+            .unwrap();
+        store.apply_proposal("p-applied", "carol").await.unwrap();
+        store
+            .create_proposal(proposal("p-queued", "accepted"))
+            .await
+            .unwrap();
+        store
+            .create_proposal(proposal("p-open", "open"))
+            .await
+            .unwrap();
+        let policies: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [
+                { "type": "change_window", "allowed_days": [0, 1, 2, 3, 4, 5, 6],
+                  "allowed_hour_start": 0, "allowed_hour_end": 24 },
+                { "type": "change_freeze", "start": "2000-01-01T00:00:00Z",
+                  "end": "2000-01-02T00:00:00Z", "reason": "y2k" }
+            ]
+        }))
+        .unwrap();
+        let app = crate::api::routes::router(
+            store,
+            crate::reload::RuntimeConfig::new(Default::default(), policies),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let get = |uri: &str| {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, body) = get("/calendar").await;
+        assert_eq!(status, StatusCode::OK);
+        let entries = body["entries"].as_array().unwrap();
+        let kinds = |kind: &str| entries.iter().filter(|e| e["kind"] == kind).count();
+        assert_eq!(kinds("applied"), 1);
+        assert_eq!(kinds("queued"), 1);
+        assert_eq!(kinds("freeze"), 0);
+        assert!(kinds("window") >= 21);
+        let queued = entries.iter().find(|e| e["kind"] == "queued").unwrap();
+        assert_eq!(queued["proposalId"], "p-queued");
+        assert_eq!(queued["start"], "2026-10-05T08:00:00Z");
+        assert!(queued["nextApplyAt"].is_string());
+        let applied = entries.iter().find(|e| e["kind"] == "applied").unwrap();
+        assert_eq!(applied["appliedBy"], "carol");
+
+        let (_, y2k) = get("/calendar?from=1999-12-31&to=2000-01-03").await;
+        let entries = y2k["entries"].as_array().unwrap();
+        assert!(entries
+            .iter()
+            .any(|e| e["kind"] == "freeze" && e["reason"] == "y2k"));
+        assert!(entries.iter().all(|e| e["kind"] != "applied"));
+        let (status, _) = get("/calendar?from=2026-10-05&to=2026-10-01").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,171 @@
+//! `GET /changes?since=<token>&wait=30s` — long-poll alternative to the `GET /events`
+//! stream, for clients behind proxies that cut long-lived connections.
+//!
+//! Reads the event bus log (see `crate::events`), the one SSE resumes from. Without
+//! `since` the response is immediate and only carries the current token. With it, the
+//! events after the token are returned as soon as there are any, else after `wait`
+//! (default [`DEFAULT_WAIT`], at most [`MAX_WAIT`]) with none. Either way `next` is the
+//! token for the following call; `reset` says events were missed (the token is older
+//! than the log, or from before a restart) and the client should refresh.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+use crate::events::{Cursor, ServerEvent};
+
+/// Wait when `wait` is not given.
+pub const DEFAULT_WAIT: Duration = Duration::from_secs(30);
+
+/// Longest `wait` honoured; longer requests are cut to this.
+pub const MAX_WAIT: Duration = Duration::from_secs(60);
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/changes", get(changes))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangesParams {
+    /// Token from a previous response's `next`.
+    pub since: Option<String>,
+    /// Seconds, as `30` or `30s`.
+    pub wait: Option<String>,
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesResponse {
+    pub events: Vec<ServerEvent>,
+    pub next: String,
+    pub reset: bool,
+}
+
+fn parse_wait(wait: Option<&str>) -> Result<Duration, ApiError> {
+    let Some(wait) = wait else {
+        return Ok(DEFAULT_WAIT);
+    };
+    let secs: u64 = wait
+        .strip_suffix('s')
+        .unwrap_or(wait)
+        .parse()
+        .map_err(|_| ApiError::Invalid(format!("wait: '{}' is not a number of seconds", wait)))?;
+    Ok(Duration::from_secs(secs).min(MAX_WAIT))
+}
+
+async fn changes(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<ChangesParams>,
+) -> Result<Json<ChangesResponse>, ApiError> {
+    service::require_route(&state, &actor, "GET /changes", Role::Reader)?;
+    let wait = parse_wait(params.wait.as_deref())?;
+    let bus = &state.event_bus;
+    let Some(token) = params.since else {
+        return Ok(Json(ChangesResponse {
+            events: Vec::new(),
+            next: bus.cursor().token(),
+            reset: false,
+        }));
+    };
+    let mut cursor = Cursor::parse(&token)
+        .ok_or_else(|| ApiError::Invalid(format!("since: '{}' is not a token", token)))?;
+
+    // Subscribed before reading the log, so nothing published in between is missed.
+    let mut rx = bus.subscribe();
+    let deadline = tokio::time::Instant::now() + wait;
+    loop {
+        let read = bus.read_since(&cursor);
+        cursor = read.cursor;
+        let events: Vec<ServerEvent> = read
+            .events
+            .into_iter()
+            .map(|(_, event)| event)
+            .filter(|e| {
+                params
+                    .workspace
+                    .as_deref()
+                    .is_none_or(|ws| e.workspace_id.as_deref() == Some(ws))
+            })
+            .collect();
+        if !events.is_empty() || read.reset {
+            return Ok(Json(ChangesResponse {
+                events,
+                next: cursor.token(),
+                reset: read.reset,
+            }));
+        }
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => {}
+            Ok(Err(RecvError::Closed)) | Err(_) => {
+                return Ok(Json(ChangesResponse {
+                    events: Vec::new(),
+                    next: cursor.token(),
+                    reset: false,
+                }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reload::RuntimeConfig;
+    use crate::store::{ContextStore, InMemoryStore};
+    use axum::body::Body;
+    use http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn get_json(app: &Router, uri: &str) -> serde_json::Value {
+        let res = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn long_poll_returns_when_an_event_is_published() {
+        let store: Arc<dyn ContextStore> = Arc::new(InMemoryStore::new());
+        let runtime = RuntimeConfig::new(Default::default(), Default::default());
+        let bus = crate::events::EventBus::new();
+        let app = crate::api::routes::router(store, runtime, bus.clone(), Default::default())
+            .layer(axum::middleware::from_fn(
+                |mut req: axum::extract::Request, next: axum::middleware::Next| {
+                    req.extensions_mut().insert(ActorContext::dev_default());
+                    next.run(req)
+                },
+            ));
+
+        let start = get_json(&app, "/changes").await;
+        let token = start["next"].as_str().unwrap().to_string();
+        let timed_out = get_json(&app, &format!("/changes?since={}&wait=0", token)).await;
+        assert!(timed_out["events"].as_array().unwrap().is_empty());
+        assert_eq!(timed_out["next"], token);
+
+        let publisher = bus.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            publisher.publish(crate::events::test_event("proposal_updated", None));
+        });
+        let changed = get_json(&app, &format!("/changes?since={}&wait=10s", token)).await;
+        assert_eq!(changed["events"][0]["resourceId"], "p-1");
+        assert_eq!(changed["reset"], false);
+        assert_ne!(changed["next"], token);
+    }
+}
@@ -0,0 +1,209 @@
+//! Commit linkage: `POST /nodes/:id/commits` links a node to the git commit that
+//! implements or references it; `GET /nodes?commit=<sha>` finds the nodes linked to a
+//! commit (`NodeTable` indexes `implementedInCommit` and `referencedInCommits`).
+//!
+//! A link is a change to the node like any other, so the endpoint opens a proposal
+//! (one update setting the commit fields) rather than writing the node directly.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::types::{NodeId, Operation, Proposal, ProposalMetadata, ProposalStatus, UpdateChanges};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/nodes/:id/commits", post(link_commit))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommitLinkRequest {
+    /// Commit SHA, 7 to 64 hex digits (abbreviated, SHA-1 or SHA-256).
+    pub sha: String,
+    /// The commit implements the node (`implementedInCommit`); otherwise it only
+    /// references it (added to `referencedInCommits`).
+    #[serde(default)]
+    pub implements: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// `POST /nodes/:id/commits` — propose linking a commit to a node (Contributor). Like
+/// `POST /nodes/:id/archive`, returns the open proposal with `201`.
+async fn link_commit(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(request): StrictJson<CommitLinkRequest>,
+) -> Result<(StatusCode, Json<Proposal>), ApiError> {
+    service::require_route(&state, &actor, "POST /nodes/:id/commits", Role::Contributor)?;
+    let sha = request.sha.trim().to_lowercase();
+    if !(7..=64).contains(&sha.len()) || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::Invalid(format!(
+            "'{}' is not a commit SHA (7 to 64 hex digits)",
+            request.sha
+        )));
+    }
+    let node_id = NodeId {
+        id,
+        namespace: request.namespace,
+    };
+    let key = node_id.key();
+    let node = state
+        .store
+        .get_node(&node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", key)))?;
+
+    let mut changes = UpdateChanges::default();
+    if request.implements {
+        if node.metadata.implemented_in_commit.as_deref() == Some(sha.as_str()) {
+            return Err(ApiError::Invalid(format!(
+                "node {} is already implemented in {}",
+                key, sha
+            )));
+        }
+        changes.implemented_in_commit = Some(sha);
+    } else {
+        let mut referenced = node.metadata.referenced_in_commits.unwrap_or_default();
+        if referenced.contains(&sha) {
+            return Err(ApiError::Invalid(format!(
+                "node {} already references {}",
+                key, sha
+            )));
+        }
+        referenced.push(sha);
+        changes.referenced_in_commits = Some(referenced);
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let proposal = Proposal {
+        id: format!("commit-{}", uuid::Uuid::new_v4()),
+        status: ProposalStatus::Open,
+        operations: vec![Operation::Update {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id,
+            changes,
+        }],
+        metadata: ProposalMetadata {
+            created_at: now.clone(),
+            created_by: actor.actor_id.clone(),
+            modified_at: now,
+            modified_by: actor.actor_id.clone(),
+            rationale: request.reason,
+            required_approvers: None,
+            approved_by: None,
+            base_versions: Some([(key, node.metadata.version)].into()),
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
+        },
+        comments: None,
+        relations: None,
+        applied: None,
+    };
+    let proposal = service::create_proposal(&state, &actor, proposal).await?;
+    Ok((StatusCode::CREATED, Json(proposal)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use crate::types::ProposalPatch;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn linked_commits_find_their_nodes() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let seed: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-seed",
+            "status": "accepted",
+            "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                "id": { "id": "decision-1" }, "type": "decision", "status": "accepted",
+                "content": "Use Postgres",
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                    "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                }
+            }}],
+            "metadata": { "createdBy": "u" }
+        }))
+        .unwrap();
+        store.create_proposal(seed).await.unwrap();
+        store.apply_proposal("p-seed", "u").await.unwrap();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let sha = "9fceb02d0ae598e95dc970b74767f19372d61af8";
+        let (status, _) = send(
+            "POST",
+            "/nodes/decision-1/commits",
+            serde_json::json!({ "sha": "not-a-sha" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, proposal) = send(
+            "POST",
+            "/nodes/decision-1/commits",
+            serde_json::json!({ "sha": sha.to_uppercase(), "implements": true }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = proposal["id"].as_str().unwrap().to_string();
+        store
+            .update_proposal(&id, ProposalPatch::status(ProposalStatus::Accepted))
+            .await
+            .unwrap();
+        store.apply_proposal(&id, "u").await.unwrap();
+
+        let (_, found) = send("GET", "/nodes?commit=9fceb02", serde_json::json!(null)).await;
+        assert_eq!(found["total"], 1);
+        assert_eq!(found["nodes"][0]["metadata"]["implementedInCommit"], sha);
+        let (_, none) = send("GET", "/nodes?commit=abcdef0", serde_json::json!(null)).await;
+        assert_eq!(none["total"], 0);
+    }
+}
@@ -0,0 +1,286 @@
+//! Conflict resolution (`POST /proposals/:id/resolve-conflicts`): a human settles the
+//! fields open proposals set differently by choosing each field's value for one of them.
+//!
+//! The proposal's last update of the node is changed to set the chosen value, and the
+//! choice is kept in `metadata.conflictResolutions`: who chose what, when, and the
+//! values it discarded (the other open proposals' and the proposal's own earlier one,
+//! unless equal to the choice). The resolution is audited as `conflicts_resolved`.
+//!
+//! Resolving changes the proposal's operations, so it takes what
+//! `PATCH /proposals/:id/operations` takes: an open proposal nobody approved yet, edited
+//! by its author or an Admin; the result is checked against the create-time policies
+//! and scored again.
+
+use axum::{
+    extract::{Extension, Path, State},
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::proposal_operations;
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::complexity;
+use crate::events::EventKind;
+use crate::policy;
+use crate::rbac;
+use crate::store::WriteBatch;
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, ConflictResolution, DiscardedValue,
+    NodeId, Operation, Proposal, ProposalMetadataPatch, ProposalPatch,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/proposals/:id/resolve-conflicts", post(resolve_conflicts))
+}
+
+/// The value chosen for one field of one node.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChoice {
+    pub node_id: NodeId,
+    /// A field an update sets (`title`, `content`, `tags`, …).
+    pub field: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResolveConflictsRequest {
+    pub resolutions: Vec<FieldChoice>,
+}
+
+/// Values the other open proposals set for `field` of the node `key`.
+fn proposed_values(open: &[Proposal], own: &str, key: &str, field: &str) -> Vec<DiscardedValue> {
+    let mut values = Vec::new();
+    for proposal in open.iter().filter(|p| p.id != own) {
+        for op in &proposal.operations {
+            if let Operation::Update {
+                node_id, changes, ..
+            } = op
+            {
+                if let Some(value) = changes.fields().get(field).filter(|_| node_id.key() == key) {
+                    values.push(DiscardedValue {
+                        proposal_id: proposal.id.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+    }
+    values
+}
+
+/// `POST /proposals/:id/resolve-conflicts` (Contributor; the author or an Admin; humans
+/// only). Returns the proposal as stored.
+async fn resolve_conflicts(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(body): StrictJson<ResolveConflictsRequest>,
+) -> Result<Json<Proposal>, ApiError> {
+    service::require_route(
+        &state,
+        &actor,
+        "POST /proposals/:id/resolve-conflicts",
+        Role::Contributor,
+    )?;
+    rbac::reject_agent(&actor, "resolve conflicts")?;
+    if body.resolutions.is_empty() {
+        return Err(ApiError::Invalid(
+            "resolutions: at least one field is resolved".to_string(),
+        ));
+    }
+    let existing = service::get_proposal(&state, &actor, &id).await?;
+    proposal_operations::check_editable(&state, &actor, &existing).await?;
+
+    let open = state.store.get_open_proposals().await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut patched = existing.clone();
+    let mut resolutions = Vec::new();
+    for choice in body.resolutions {
+        let key = choice.node_id.key();
+        let mut discarded = proposed_values(&open, &id, &key, &choice.field);
+        if discarded.is_empty() {
+            return Err(ApiError::Invalid(format!(
+                "node {}: no other open proposal sets {}",
+                key, choice.field
+            )));
+        }
+        let changes = patched
+            .operations
+            .iter_mut()
+            .rev()
+            .find_map(|op| match op {
+                Operation::Update {
+                    node_id, changes, ..
+                } if node_id.key() == key => Some(changes),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ApiError::Invalid(format!("node {}: proposal {} does not update it", key, id))
+            })?;
+        let mut fields = changes.fields();
+        if let Some(own) = fields.insert(choice.field.clone(), choice.value.clone()) {
+            discarded.insert(
+                0,
+                DiscardedValue {
+                    proposal_id: id.clone(),
+                    value: own,
+                },
+            );
+        }
+        *changes = serde_json::from_value(serde_json::Value::Object(fields))
+            .map_err(|e| ApiError::Invalid(format!("{}: {}", choice.field, e)))?;
+        if changes.fields().get(&choice.field) != Some(&choice.value) {
+            return Err(ApiError::Invalid(format!(
+                "{}: not a field an update sets, or null",
+                choice.field
+            )));
+        }
+        discarded.retain(|d| d.value != choice.value);
+        resolutions.push(ConflictResolution {
+            node_id: choice.node_id,
+            field: choice.field,
+            value: choice.value,
+            resolved_by: actor.actor_id.clone(),
+            resolved_at: now.clone(),
+            discarded,
+        });
+    }
+
+    let mut recorded = existing
+        .metadata
+        .conflict_resolutions
+        .clone()
+        .unwrap_or_default();
+    recorded.extend(resolutions.iter().cloned());
+    let mut patch = ProposalPatch {
+        metadata: Some(ProposalMetadataPatch {
+            modified_by: Some(actor.actor_id.clone()),
+            conflict_resolutions: Some(recorded),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    service::stamper(&state)
+        .update(&mut patch, &existing)
+        .map_err(ApiError::Invalid)?;
+    let complexity = complexity::assess(&*state.store, &patched).await?;
+    let violations = policy::evaluate_on_create(
+        &patched,
+        actor_type_str(&actor),
+        &state.runtime.policies.get(),
+    );
+    service::check_policies(&state, &actor, &id, patched.workspace(), violations).await?;
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::ConflictsResolved,
+        &id,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::Resolutions { resolutions });
+    let batch = WriteBatch::new()
+        .edit_operations(&id, patched.operations, complexity)
+        .update_proposal(&id, patch)
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ProposalUpdated {
+                status: existing.status,
+            },
+            &id,
+            &actor,
+        ));
+    service::execute(&state, batch.in_workspace(existing.workspace())).await?;
+    Ok(Json(service::get_proposal(&state, &actor, &id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn proposal(id: &str, title: &str) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "status": "open",
+            "operations": [{ "type": "update", "id": "op-1", "order": 1,
+                "node_id": { "id": "n1" }, "changes": { "title": title, "content": id } }],
+            "metadata": { "createdBy": "dev-user" }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_resolution_updates_the_operation_and_is_recorded() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        for p in [
+            proposal("p-a", "A"),
+            proposal("p-b", "B"),
+            proposal("p-c", "C"),
+        ] {
+            store.create_proposal(p).await.unwrap();
+        }
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let resolve = |choice: serde_json::Value| {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/proposals/p-a/resolve-conflicts")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "resolutions": [choice] }).to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, _) = resolve(
+            serde_json::json!({ "nodeId": { "id": "n2" }, "field": "title", "value": "B" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "nobody else sets n2");
+        let (status, body) = resolve(
+            serde_json::json!({ "nodeId": { "id": "n1" }, "field": "title", "value": "B" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["operations"][0]["changes"],
+            serde_json::json!({ "title": "B", "content": "p-a" })
+        );
+        let resolution = &body["metadata"]["conflictResolutions"][0];
+        assert_eq!(resolution["resolvedBy"], "dev-user");
+        assert_eq!(
+            resolution["discarded"],
+            serde_json::json!([
+                { "proposalId": "p-a", "value": "A" },
+                { "proposalId": "p-c", "value": "C" }
+            ])
+        );
+        let stored = store.get_proposal("p-a").await.unwrap().unwrap();
+        assert_eq!(stored.metadata.conflict_resolutions.unwrap().len(), 1);
+    }
+}
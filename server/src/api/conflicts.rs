@@ -0,0 +1,200 @@
+//! Conflict matrix (`GET /admin/conflicts?workspace=`): pairwise conflict detection
+//! across the open proposals, so release coordinators can plan the apply order instead
+//! of finding conflicts one apply at a time.
+//!
+//! Two open proposals conflict when they touch a common node, as
+//! `GET /proposals/:id?include=conflicts` judges it (`store::reconcile`). The report has
+//! each conflicting pair once, a matrix of shared node counts and the groups of
+//! proposals linked by conflicts: within a group, applying one makes the others need
+//! rebasing; proposals in no group apply in any order.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+use crate::store::reconcile;
+use crate::types::{ProposalConflict, ProposalQuery, ProposalStatus};
+
+/// Most open proposals one report compares (the matrix is quadratic).
+pub const MAX_PROPOSALS: usize = 1000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/admin/conflicts", get(conflict_matrix))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ConflictParams {
+    /// Only proposals in this workspace; all workspaces when absent.
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ConflictMatrix {
+    /// Open proposal ids, sorted; the rows and columns of `matrix`.
+    pub proposals: Vec<String>,
+    /// `matrix[i][j]`: how many nodes proposals `i` and `j` both touch (0 on the
+    /// diagonal).
+    pub matrix: Vec<Vec<usize>>,
+    /// Each conflicting pair once, the lower id first.
+    pub conflicts: Vec<ProposalConflict>,
+    /// Proposals linked by conflicts, directly or through others (two or more each).
+    pub groups: Vec<Vec<String>>,
+}
+
+/// Root of `i` in the union-find `parent`.
+fn root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// `GET /admin/conflicts?workspace=` (Admin).
+async fn conflict_matrix(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<ConflictParams>,
+) -> Result<Json<ConflictMatrix>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/conflicts", Role::Admin)?;
+    let mut open = state
+        .store
+        .query_proposals(ProposalQuery {
+            status: Some(vec![ProposalStatus::Open]),
+            limit: Some(u32::MAX),
+            ..Default::default()
+        })
+        .await?;
+    open.retain(|p| {
+        params
+            .workspace
+            .as_deref()
+            .is_none_or(|ws| p.workspace() == ws)
+    });
+    if open.len() > MAX_PROPOSALS {
+        return Err(ApiError::Invalid(format!(
+            "{} open proposals; at most {} are compared, narrow with workspace=",
+            open.len(),
+            MAX_PROPOSALS
+        )));
+    }
+    open.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let index: BTreeMap<&str, usize> = open
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.id.as_str(), i))
+        .collect();
+    let mut matrix = vec![vec![0; open.len()]; open.len()];
+    let mut parent: Vec<usize> = (0..open.len()).collect();
+    let mut conflicts = Vec::new();
+    for (i, proposal) in open.iter().enumerate() {
+        for conflict in reconcile::detect_conflicts(proposal, &open[i + 1..]).conflicts {
+            let j = index[conflict.proposals[1].as_str()];
+            matrix[i][j] = conflict.conflicting_nodes.len();
+            matrix[j][i] = conflict.conflicting_nodes.len();
+            let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+            parent[a.max(b)] = a.min(b);
+            conflicts.push(conflict);
+        }
+    }
+    let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (i, proposal) in open.iter().enumerate() {
+        let r = root(&mut parent, i);
+        groups.entry(r).or_default().push(proposal.id.clone());
+    }
+
+    Ok(Json(ConflictMatrix {
+        proposals: open.into_iter().map(|p| p.id).collect(),
+        matrix,
+        conflicts,
+        groups: groups.into_values().filter(|g| g.len() > 1).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use crate::types::Proposal;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn proposal(id: &str, status: &str, nodes: &[&str]) -> Proposal {
+        let operations: Vec<_> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                serde_json::json!({ "type": "delete", "id": format!("op-{}", i),
+                    "order": i + 1, "node_id": { "id": node } })
+            })
+            .collect();
+        serde_json::from_value(
+            serde_json::json!({ "id": id, "status": status, "operations": operations }),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn conflicts_are_listed_pairwise_with_groups() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        for p in [
+            proposal("p-a", "open", &["n1", "n2"]),
+            proposal("p-b", "open", &["n2", "n3"]),
+            proposal("p-c", "open", &["n3"]),
+            proposal("p-d", "open", &["n9"]),
+            proposal("p-e", "accepted", &["n1"]),
+        ] {
+            store.create_proposal(p).await.unwrap();
+        }
+        let app = crate::api::routes::router(
+            store,
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let req = Request::builder()
+            .uri("/admin/conflicts")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(
+            report["proposals"],
+            serde_json::json!(["p-a", "p-b", "p-c", "p-d"])
+        );
+        assert_eq!(
+            report["matrix"],
+            serde_json::json!([[0, 1, 0, 0], [1, 0, 1, 0], [0, 1, 0, 0], [0, 0, 0, 0]])
+        );
+        let pairs: Vec<_> = report["conflicts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["proposals"].clone())
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                serde_json::json!(["p-a", "p-b"]),
+                serde_json::json!(["p-b", "p-c"])
+            ]
+        );
+        assert_eq!(report["groups"], serde_json::json!([["p-a", "p-b", "p-c"]]));
+    }
+}
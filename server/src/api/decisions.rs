@@ -0,0 +1,80 @@
+//! Decision records as ADR Markdown (`crate::adr`): `GET /decisions/:id/adr` for one
+//! decision node, `GET /decisions/adr` for all of them as numbered files.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::adr::{self, AdrFile};
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, NodeRead};
+use crate::auth::ActorContext;
+use crate::rbac;
+use crate::types::{NodeId, NodeType};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/decisions/adr", get(export_adrs))
+        .route("/decisions/:id/adr", get(decision_adr))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AdrParams {
+    pub namespace: Option<String>,
+}
+
+/// `GET /decisions/:id/adr` — one decision as `text/markdown` (Reader).
+async fn decision_adr(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    Query(params): Query<AdrParams>,
+) -> Result<Response, ApiError> {
+    let node_id = NodeId {
+        id,
+        namespace: params.namespace,
+    };
+    let key = node_id.key();
+    let node = match service::get_node(&state, &actor, &node_id).await? {
+        NodeRead::Full(node) => node,
+        NodeRead::Redacted { .. } => {
+            return Err(rbac::Forbidden(format!(
+                "node {} is above your sensitivity clearance",
+                key
+            ))
+            .into())
+        }
+    };
+    if node.node_type != NodeType::Decision {
+        return Err(ApiError::Invalid(format!("node {} is not a decision", key)));
+    }
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        adr::render(&node, None),
+    )
+        .into_response())
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdrExport {
+    pub files: Vec<AdrFile>,
+}
+
+/// `GET /decisions/adr` — every (non-archived) decision the caller may read, numbered in
+/// decision order, as `{ files: [{ path, nodeId, content }] }` (Reader).
+async fn export_adrs(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<AdrParams>,
+) -> Result<Json<AdrExport>, ApiError> {
+    let nodes =
+        service::nodes_of_type(&state, &actor, NodeType::Decision, params.namespace).await?;
+    Ok(Json(AdrExport {
+        files: adr::render_all(&nodes),
+    }))
+}
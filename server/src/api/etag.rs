@@ -0,0 +1,118 @@
+//! ETags and conditional GET for polled reads (`GET /nodes`, `/nodes/:id`, `/proposals`,
+//! `/proposals/:id`).
+//!
+//! A single node's tag comes from its `version` and `contentHash` (both change whenever
+//! the node does); a redaction stub gets its own tag so clearance changes are seen.
+//! Lists and proposals have no version, so their tag is a hash of the response body.
+//! Responses vary by caller (agent filtering, redaction), hence `Cache-Control: private`.
+//! A matching `If-None-Match` gets `304 Not Modified` with no body.
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::api::service::NodeRead;
+use crate::types::ContextNode;
+
+/// Hex digits of SHA-256 kept in body-derived tags.
+const HASH_CHARS: usize = 32;
+
+fn short_hash(bytes: &[u8]) -> String {
+    let mut hex = format!("{:x}", Sha256::digest(bytes));
+    hex.truncate(HASH_CHARS);
+    hex
+}
+
+/// Strong ETag for one node, from `version` and `contentHash` (content hash when unset).
+pub fn node_etag(node: &ContextNode) -> String {
+    let content_hash = node
+        .metadata
+        .content_hash
+        .clone()
+        .unwrap_or_else(|| crate::sensitivity::content_hash(&node.content));
+    format!(
+        "\"v{}-{}\"",
+        node.metadata.version,
+        &content_hash[..content_hash.len().min(16)]
+    )
+}
+
+/// ETag for a node read: the node's tag, or a distinct tag for the redaction stub.
+pub fn node_read_etag(read: &NodeRead) -> String {
+    match read {
+        NodeRead::Full(node) => node_etag(node),
+        NodeRead::Redacted { .. } => body_etag(&read.to_json()),
+    }
+}
+
+/// Strong ETag over the serialized response body.
+pub fn body_etag<T: Serialize>(body: &T) -> String {
+    format!(
+        "\"{}\"",
+        short_hash(&serde_json::to_vec(body).unwrap_or_default())
+    )
+}
+
+/// Whether an `If-None-Match` value matches `etag` (weak comparison, as RFC 9110 requires
+/// for `If-None-Match`).
+fn if_none_match_matches(header_value: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    header_value
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// `304 Not Modified` when `If-None-Match` matches `etag`, else `200` with the JSON body;
+/// both carry the `ETag`.
+pub fn conditional<T: Serialize>(headers: &HeaderMap, etag: String, body: T) -> Response {
+    let not_modified = headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| if_none_match_matches(v, &etag));
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(body).into_response()
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("private, no-cache"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_none_match_handles_lists_weak_tags_and_wildcard() {
+        assert!(if_none_match_matches("\"a\"", "\"a\""));
+        assert!(if_none_match_matches("\"x\", W/\"a\"", "\"a\""));
+        assert!(if_none_match_matches("*", "\"a\""));
+        assert!(!if_none_match_matches("\"b\"", "\"a\""));
+    }
+
+    #[test]
+    fn conditional_returns_not_modified_for_matching_tag() {
+        let etag = body_etag(&serde_json::json!({ "ok": true }));
+        let mut headers = HeaderMap::new();
+        let fresh = conditional(&headers, etag.clone(), serde_json::json!({ "ok": true }));
+        assert_eq!(fresh.status(), StatusCode::OK);
+        assert_eq!(fresh.headers()[header::ETAG], etag.as_str());
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&etag).unwrap());
+        let cached = conditional(&headers, etag.clone(), serde_json::json!({ "ok": true }));
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers()[header::ETAG], etag.as_str());
+    }
+}
@@ -0,0 +1,557 @@
+//! Background export jobs (`/admin/exports`).
+//!
+//! `GET /audit/export` builds the whole artifact inside the request and times out on
+//! large stores. Here `POST /admin/exports` records a job and returns `202 Accepted`
+//! at once; an `export` job on the job queue (`crate::jobs`) pages through the data,
+//! updating `processed` as it goes, and stores the finished artifact. Clients poll
+//! `GET /admin/exports/:id` and fetch `GET /admin/exports/:id/download`, which honors a
+//! single `Range` so interrupted downloads can resume. Job records live in the store
+//! next to the data.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Extension, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::jobs::JobHandler;
+use crate::store::context_store::StoreError;
+use crate::store::{ContextStore, WriteBatch};
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, ExportFormat, ExportJob, ExportJobStatus,
+    ExportKind, JobRecord,
+};
+
+/// Audit events read per page while an audit export runs.
+const AUDIT_PAGE_SIZE: u32 = 1000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/exports", post(start_export).get(list_exports))
+        .route("/admin/exports/:id", get(get_export))
+        .route("/admin/exports/:id/download", get(download_export))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StartExportRequest {
+    pub kind: ExportKind,
+    #[serde(default = "default_format")]
+    pub format: ExportFormat,
+}
+
+fn default_format() -> ExportFormat {
+    ExportFormat::Json
+}
+
+async fn start_export(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(body): StrictJson<StartExportRequest>,
+) -> Result<Response, ApiError> {
+    service::require_route(&state, &actor, "POST /admin/exports", Role::Admin)?;
+    if body.kind == ExportKind::Bundle && body.format == ExportFormat::Csv {
+        return Err(ApiError::Invalid(
+            "bundle exports are JSON only".to_string(),
+        ));
+    }
+
+    let job = ExportJob::new(body.kind, body.format, &actor.actor_id);
+    state.store.save_export_job(job.clone()).await?;
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::ExportRequested,
+        &job.id,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::Export {
+        kind: job.kind,
+        format: job.format,
+    });
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ExportRequested,
+            &job.id,
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
+
+    state
+        .jobs
+        .enqueue(EXPORT_JOB, serde_json::json!({ "exportId": job.id }))
+        .await?;
+
+    let location = format!("/admin/exports/{}", job.id);
+    Ok((
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, location)],
+        Json(job),
+    )
+        .into_response())
+}
+
+async fn list_exports(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<Vec<ExportJob>>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/exports", Role::Admin)?;
+    Ok(Json(state.store.list_export_jobs().await?))
+}
+
+async fn get_export(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Json<ExportJob>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/exports/:id", Role::Admin)?;
+    let job = load_job(&state, &id).await?;
+    Ok(Json(job))
+}
+
+async fn load_job(state: &AppState, id: &str) -> Result<ExportJob, ApiError> {
+    state
+        .store
+        .get_export_job(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("export job {} not found", id)))
+}
+
+async fn download_export(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    service::require_route(
+        &state,
+        &actor,
+        "GET /admin/exports/:id/download",
+        Role::Admin,
+    )?;
+    let job = load_job(&state, &id).await?;
+    if job.status != ExportJobStatus::Completed {
+        return Err(StoreError::conflict(format!(
+            "export job {} is not completed (status: {})",
+            id,
+            crate::api::service::enum_str(&job.status)
+        ))
+        .into());
+    }
+    let data = state
+        .store
+        .get_export_artifact(&id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("artifact for export job {} missing", id)))?;
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(job.content_type()),
+    );
+    if let Ok(v) = HeaderValue::from_str(&format!("attachment; filename={}", job.file_name())) {
+        response_headers.insert(header::CONTENT_DISPOSITION, v);
+    }
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+    let len = data.len() as u64;
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_range(v, len));
+    match range {
+        Some(RangeRequest::Satisfiable(start, end)) => {
+            if let Ok(v) = HeaderValue::from_str(&format!("bytes {}-{}/{}", start, end, len)) {
+                response_headers.insert(header::CONTENT_RANGE, v);
+            }
+            let slice = data[start as usize..=end as usize].to_vec();
+            Ok((StatusCode::PARTIAL_CONTENT, response_headers, slice).into_response())
+        }
+        Some(RangeRequest::Unsatisfiable) => {
+            if let Ok(v) = HeaderValue::from_str(&format!("bytes */{}", len)) {
+                response_headers.insert(header::CONTENT_RANGE, v);
+            }
+            Ok((StatusCode::RANGE_NOT_SATISFIABLE, response_headers).into_response())
+        }
+        // No header, or one we don't serve (multiple ranges, other units): whole body.
+        Some(RangeRequest::Ignored) | None => {
+            Ok((StatusCode::OK, response_headers, data).into_response())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    /// Inclusive byte range within the artifact.
+    Satisfiable(u64, u64),
+    Unsatisfiable,
+    Ignored,
+}
+
+/// Parse a single `bytes=a-b`, `bytes=a-` or `bytes=-n` range against `len` bytes.
+fn parse_range(value: &str, len: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Ignored;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Ignored;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Ignored;
+    };
+    let (start, end) = (start.trim(), end.trim());
+    let parsed = match (start.is_empty(), end.is_empty()) {
+        // Suffix range: the last n bytes.
+        (true, false) => match end.parse::<u64>() {
+            Ok(0) => return RangeRequest::Unsatisfiable,
+            Ok(n) => (len.saturating_sub(n), len.saturating_sub(1)),
+            Err(_) => return RangeRequest::Ignored,
+        },
+        (false, true) => match start.parse::<u64>() {
+            Ok(s) => (s, len.saturating_sub(1)),
+            Err(_) => return RangeRequest::Ignored,
+        },
+        (false, false) => match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(s), Ok(e)) if s <= e => (s, e.min(len.saturating_sub(1))),
+            _ => return RangeRequest::Ignored,
+        },
+        (true, true) => return RangeRequest::Ignored,
+    };
+    if len == 0 || parsed.0 >= len {
+        return RangeRequest::Unsatisfiable;
+    }
+    RangeRequest::Satisfiable(parsed.0, parsed.1)
+}
+
+/// Job kind that runs an export; the payload is `{ "exportId": ... }`.
+pub const EXPORT_JOB: &str = "export";
+
+/// Runs [`EXPORT_JOB`] jobs on the job queue.
+pub struct ExportJobHandler;
+
+#[async_trait]
+impl JobHandler for ExportJobHandler {
+    fn kind(&self) -> &'static str {
+        EXPORT_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let export_id = job
+            .payload
+            .get("exportId")
+            .and_then(|v| v.as_str())
+            .ok_or("payload has no exportId")?;
+        let export = store
+            .get_export_job(export_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("export job {} not found", export_id))?;
+        let retries_left = job.attempts < job.max_attempts;
+        run_export(store, export, retries_left).await?;
+        Ok(None)
+    }
+}
+
+/// Job kind that takes a full store snapshot as a new bundle export (downloadable from
+/// `/admin/exports`); the scheduler's `snapshot` task. The result names the export.
+pub const SNAPSHOT_JOB: &str = "snapshot";
+
+pub struct SnapshotJobHandler;
+
+#[async_trait]
+impl JobHandler for SnapshotJobHandler {
+    fn kind(&self) -> &'static str {
+        SNAPSHOT_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        _job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let export = ExportJob::new(ExportKind::Bundle, ExportFormat::Json, "system");
+        let export_id = export.id.clone();
+        store
+            .save_export_job(export.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+        // Each attempt is its own export, so a failed one stays failed.
+        run_export(store, export, false).await?;
+        Ok(Some(serde_json::json!({ "exportId": export_id })))
+    }
+}
+
+/// Run one export attempt, recording progress and the outcome on the export job.
+/// A failed attempt leaves the export queued while the job queue will retry it.
+async fn run_export(
+    store: Arc<dyn ContextStore>,
+    mut job: ExportJob,
+    retries_left: bool,
+) -> Result<(), String> {
+    job.status = ExportJobStatus::Running;
+    job.started_at = Some(chrono::Utc::now().to_rfc3339());
+    job.processed = 0;
+    job.error = None;
+    store
+        .save_export_job(job.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let result = match job.kind {
+        ExportKind::Audit => export_audit(store.as_ref(), &mut job).await,
+        ExportKind::Bundle => export_bundle(store.as_ref(), &mut job).await,
+    };
+    let result = match result {
+        Ok(data) => {
+            let size = data.len() as u64;
+            store.put_export_artifact(&job.id, data).await.map(|_| size)
+        }
+        Err(e) => Err(e),
+    };
+
+    let outcome = match result {
+        Ok(size) => {
+            job.status = ExportJobStatus::Completed;
+            job.size_bytes = Some(size);
+            job.completed_at = Some(chrono::Utc::now().to_rfc3339());
+            Ok(())
+        }
+        Err(e) => {
+            job.status = if retries_left {
+                ExportJobStatus::Queued
+            } else {
+                job.completed_at = Some(chrono::Utc::now().to_rfc3339());
+                ExportJobStatus::Failed
+            };
+            job.error = Some(e.to_string());
+            Err(e.to_string())
+        }
+    };
+    if let Err(e) = store.save_export_job(job.clone()).await {
+        tracing::warn!(job = %job.id, error = %e, "export job result not saved");
+    }
+    outcome
+}
+
+async fn export_audit(
+    store: &dyn ContextStore,
+    job: &mut ExportJob,
+) -> Result<Vec<u8>, StoreError> {
+    let mut events = Vec::new();
+    let mut offset = 0u32;
+    loop {
+        let page = store
+            .query_audit(
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(AUDIT_PAGE_SIZE),
+                Some(offset),
+            )
+            .await?;
+        let n = page.events.len() as u32;
+        events.extend(page.events);
+        job.processed = events.len() as u64;
+        store.save_export_job(job.clone()).await?;
+        if !page.has_more || n == 0 {
+            break;
+        }
+        offset += n;
+    }
+    match job.format {
+        ExportFormat::Csv => Ok(AuditEvent::to_csv(&events).into_bytes()),
+        ExportFormat::Json => {
+            serde_json::to_vec(&events).map_err(|e| StoreError::internal(e.to_string()))
+        }
+    }
+}
+
+async fn export_bundle(
+    store: &dyn ContextStore,
+    job: &mut ExportJob,
+) -> Result<Vec<u8>, StoreError> {
+    let bundle = store.export_bundle().await?;
+    job.processed = (bundle.nodes.len()
+        + bundle.proposals.len()
+        + bundle.reviews.values().map(Vec::len).sum::<usize>()
+        + bundle.audit.len()) as u64;
+    serde_json::to_vec(&bundle).map_err(|e| StoreError::internal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    fn app() -> Router<()> {
+        crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ))
+    }
+
+    async fn send(app: &Router<()>, req: Request<Body>) -> (StatusCode, HeaderMap, Vec<u8>) {
+        let res = app.clone().oneshot(req).await.unwrap();
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = res.into_body().collect().await.unwrap().to_bytes().to_vec();
+        (status, headers, body)
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn parses_single_byte_ranges() {
+        assert_eq!(
+            parse_range("bytes=0-9", 100),
+            RangeRequest::Satisfiable(0, 9)
+        );
+        assert_eq!(
+            parse_range("bytes=90-", 100),
+            RangeRequest::Satisfiable(90, 99)
+        );
+        assert_eq!(
+            parse_range("bytes=-10", 100),
+            RangeRequest::Satisfiable(90, 99)
+        );
+        assert_eq!(
+            parse_range("bytes=50-500", 100),
+            RangeRequest::Satisfiable(50, 99)
+        );
+        assert_eq!(parse_range("bytes=100-", 100), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,5-6", 100), RangeRequest::Ignored);
+        assert_eq!(parse_range("items=0-1", 100), RangeRequest::Ignored);
+    }
+
+    #[tokio::test]
+    async fn audit_export_completes_and_serves_ranges() {
+        let app = app();
+        // A first export request leaves an audit event for the second one to export.
+        let (status, _, _) = send(
+            &app,
+            Request::builder()
+                .method("POST")
+                .uri("/admin/exports")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"kind":"bundle"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let (status, headers, body) = send(
+            &app,
+            Request::builder()
+                .method("POST")
+                .uri("/admin/exports")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"kind":"audit","format":"csv"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id = job["id"].as_str().unwrap().to_string();
+        assert_eq!(
+            headers.get(header::LOCATION).unwrap(),
+            &format!("/admin/exports/{}", id)
+        );
+
+        let mut job = serde_json::Value::Null;
+        for _ in 0..50 {
+            let (_, _, body) = send(&app, get(&format!("/admin/exports/{}", id))).await;
+            job = serde_json::from_slice(&body).unwrap();
+            if job["status"] == "completed" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(job["status"], "completed");
+        assert!(job["processed"].as_u64().unwrap() >= 1);
+
+        let url = format!("/admin/exports/{}/download", id);
+        let (status, headers, full) = send(&app, get(&url)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers.get(header::CONTENT_TYPE).unwrap(), "text/csv");
+        assert_eq!(headers.get(header::ACCEPT_RANGES).unwrap(), "bytes");
+        assert!(full.starts_with(b"event_id,"));
+        assert_eq!(job["sizeBytes"].as_u64(), Some(full.len() as u64));
+
+        let ranged = Request::builder()
+            .uri(&url)
+            .header(header::RANGE, "bytes=0-7")
+            .body(Body::empty())
+            .unwrap();
+        let (status, headers, part) = send(&app, ranged).await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(part, &full[..8]);
+        assert_eq!(
+            headers.get(header::CONTENT_RANGE).unwrap(),
+            &format!("bytes 0-7/{}", full.len())
+        );
+
+        let beyond = Request::builder()
+            .uri(&url)
+            .header(header::RANGE, format!("bytes={}-", full.len()))
+            .body(Body::empty())
+            .unwrap();
+        let (status, _, _) = send(&app, beyond).await;
+        assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+    }
+
+    #[tokio::test]
+    async fn rejects_csv_bundles_and_unknown_jobs() {
+        let app = app();
+        let (status, _, _) = send(
+            &app,
+            Request::builder()
+                .method("POST")
+                .uri("/admin/exports")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"kind":"bundle","format":"csv"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _, _) = send(&app, get("/admin/exports/export-missing")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
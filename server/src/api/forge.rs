@@ -0,0 +1,474 @@
+//! Forge endpoints (`crate::forge`): `POST /proposals/:id/forge` links a proposal to a
+//! pull / merge request, `POST /webhooks/forge/:workspace` receives the forge's review
+//! and pipeline events.
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::forge::{self, ForgeEvent};
+use crate::rbac;
+use crate::store::lifecycle;
+use crate::store::WriteBatch;
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, ForgeLink, Proposal,
+    ProposalMetadataPatch, ProposalPatch, ProposalQuery, ProposalStatus, Review,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/proposals/:id/forge", post(link_request))
+        .route("/webhooks/forge/:workspace", post(webhook))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForgeLinkRequest {
+    /// Pull request number or merge request IID.
+    pub number: u64,
+}
+
+/// `POST /proposals/:id/forge` — link an open or accepted proposal to a request in its
+/// workspace's forge project and queue a summary comment there (Contributor). Linking
+/// again moves the link (and forgets the pipeline result).
+async fn link_request(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(request): StrictJson<ForgeLinkRequest>,
+) -> Result<Json<Proposal>, ApiError> {
+    service::require_route(
+        &state,
+        &actor,
+        "POST /proposals/:id/forge",
+        Role::Contributor,
+    )?;
+    let proposal = service::get_proposal(&state, &actor, &id).await?;
+    if lifecycle::is_closed(proposal.status) {
+        return Err(ApiError::Invalid(format!(
+            "proposal {} is closed; only open or accepted proposals can be linked",
+            id
+        )));
+    }
+    let workspace = proposal.workspace().to_string();
+    let config = state.runtime.config.get();
+    let forge_ws = config.forge.workspaces.get(&workspace).ok_or_else(|| {
+        ApiError::Invalid(format!(
+            "no forge is configured for workspace '{}' (forge.workspaces in config.json)",
+            workspace
+        ))
+    })?;
+
+    let link = ForgeLink {
+        workspace,
+        number: request.number,
+        linked_by: actor.actor_id.clone(),
+        linked_at: chrono::Utc::now().to_rfc3339(),
+        pipeline: None,
+        pipeline_sha: None,
+    };
+    save_link(&state, &id, &link).await?;
+    state
+        .jobs
+        .enqueue(
+            forge::COMMENT_JOB,
+            forge_ws.comment_job(link.number, forge::summary(&proposal)),
+        )
+        .await?;
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::ProposalUpdated,
+        &id,
+        AuditOutcome::Success,
+    )
+    .in_workspace(proposal.workspace())
+    .with_details(AuditDetails::Forge { forge: link });
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ProposalUpdated {
+                status: proposal.status,
+            },
+            &id,
+            &actor,
+        ))
+        .in_workspace(proposal.workspace());
+    service::execute(&state, batch).await?;
+    Ok(Json(service::get_proposal(&state, &actor, &id).await?))
+}
+
+async fn save_link(state: &AppState, id: &str, link: &ForgeLink) -> Result<(), ApiError> {
+    state
+        .store
+        .update_proposal(
+            id,
+            ProposalPatch {
+                metadata: Some(ProposalMetadataPatch {
+                    forge: Some(link.clone()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+/// Queue a note on the linked request that `proposal` was applied. Best effort: the
+/// proposal is applied either way.
+pub(crate) async fn note_applied(state: &AppState, proposal: &Proposal, applied_by: &str) {
+    let Some(link) = &proposal.metadata.forge else {
+        return;
+    };
+    let config = state.runtime.config.get();
+    let Some(forge_ws) = config.forge.workspaces.get(&link.workspace) else {
+        return;
+    };
+    let body = format!(
+        "TruthLayer proposal `{}` was applied to accepted truth by {}.",
+        proposal.id, applied_by
+    );
+    if let Err(e) = state
+        .jobs
+        .enqueue(forge::COMMENT_JOB, forge_ws.comment_job(link.number, body))
+        .await
+    {
+        tracing::warn!(proposal = %proposal.id, error = %e, "cannot queue forge comment");
+    }
+}
+
+/// Webhook response: what the delivery changed.
+#[derive(Debug, Serialize)]
+pub struct WebhookOutcome {
+    /// `review`, `pipeline` or `ignored`.
+    pub handled: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub proposals: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl WebhookOutcome {
+    fn ignored(reason: impl Into<String>) -> Json<Self> {
+        Json(Self {
+            handled: "ignored",
+            proposals: Vec::new(),
+            reason: Some(reason.into()),
+        })
+    }
+}
+
+/// `POST /webhooks/forge/:workspace` — forge webhook. Not JWT-authenticated: the delivery
+/// must carry the workspace's secret (`401` otherwise). Approvals by mapped logins become
+/// reviews of the linked proposals; pipeline results are recorded on their links.
+async fn webhook(
+    State(state): State<AppState>,
+    Path(workspace): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let config = state.runtime.config.get();
+    let forge_ws = config.forge.workspaces.get(&workspace).ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "no forge is configured for workspace '{}'",
+            workspace
+        ))
+    })?;
+    let verified = std::env::var(&forge_ws.webhook_secret_env)
+        .is_ok_and(|secret| forge::verify(forge_ws.provider, &headers, &body, &secret));
+    if !verified {
+        return Err(ApiError::Unauthorized(
+            "invalid webhook signature".to_string(),
+        ));
+    }
+    let event =
+        forge::parse_event(forge_ws.provider, &headers, &body).map_err(ApiError::Invalid)?;
+
+    let outcome = match event {
+        ForgeEvent::Ignored(reason) => WebhookOutcome::ignored(reason),
+        ForgeEvent::Review {
+            number,
+            login,
+            action,
+            comment,
+        } => {
+            let Some(actor_id) = forge_ws.identities.get(&login) else {
+                return Ok(
+                    WebhookOutcome::ignored(format!("forge user {} is not mapped", login))
+                        .into_response(),
+                );
+            };
+            // A deprovisioned or non-reviewing actor's approvals are skipped.
+            let roles = forge_ws.roles.get(actor_id);
+            let reviewer = match service::mapped_actor(&state, actor_id, roles).await {
+                Ok(reviewer) => reviewer,
+                Err(ApiError::Unauthorized(refused)) => {
+                    return Ok(WebhookOutcome::ignored(refused).into_response());
+                }
+                Err(e) => return Err(e),
+            };
+            if let Err(refused) = rbac::require_role(&reviewer, Role::Reviewer) {
+                return Ok(WebhookOutcome::ignored(refused.0).into_response());
+            }
+            let mut reviewed = Vec::new();
+            for proposal in linked_proposals(&state, &workspace, number, true).await? {
+                let review = Review {
+                    id: format!("forge-review-{}", uuid::Uuid::new_v4()),
+                    proposal_id: proposal.id.clone(),
+                    reviewer: actor_id.clone(),
+                    reviewer_role: None,
+                    reviewer_groups: None,
+                    reviewed_at: chrono::Utc::now().to_rfc3339(),
+                    action,
+                    comment: comment.clone(),
+                    comments: None,
+                    operation_ids: None,
+                    is_approval: None,
+                    on_behalf_of: None,
+                };
+                service::submit_review(&state, &reviewer, &proposal.id, review).await?;
+                reviewed.push(proposal.id);
+            }
+            Json(WebhookOutcome {
+                handled: "review",
+                proposals: reviewed,
+                reason: None,
+            })
+        }
+        ForgeEvent::Pipeline {
+            numbers,
+            sha,
+            status,
+        } => {
+            let mut updated = Vec::new();
+            for number in numbers {
+                for proposal in linked_proposals(&state, &workspace, number, false).await? {
+                    let Some(mut link) = proposal.metadata.forge.clone() else {
+                        continue;
+                    };
+                    link.pipeline = Some(status);
+                    link.pipeline_sha = sha.clone();
+                    save_link(&state, &proposal.id, &link).await?;
+                    updated.push(proposal.id);
+                }
+            }
+            Json(WebhookOutcome {
+                handled: "pipeline",
+                proposals: updated,
+                reason: None,
+            })
+        }
+    };
+    Ok(outcome.into_response())
+}
+
+/// Open (and, unless `open_only`, accepted) proposals linked to request `number` of
+/// `workspace`.
+async fn linked_proposals(
+    state: &AppState,
+    workspace: &str,
+    number: u64,
+    open_only: bool,
+) -> Result<Vec<Proposal>, ApiError> {
+    let statuses = if open_only {
+        vec![ProposalStatus::Open]
+    } else {
+        vec![ProposalStatus::Open, ProposalStatus::Accepted]
+    };
+    let proposals = state
+        .store
+        .query_proposals(ProposalQuery {
+            status: Some(statuses),
+            limit: Some(u32::MAX),
+            ..Default::default()
+        })
+        .await?;
+    Ok(proposals
+        .into_iter()
+        .filter(|p| {
+            p.metadata
+                .forge
+                .as_ref()
+                .is_some_and(|l| l.workspace == workspace && l.number == number)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn approvals_and_pipelines_gate_apply() {
+        std::env::set_var("TL_TEST_FORGE_SECRET", "s3cret");
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        for proposal in [
+            serde_json::json!({
+                "id": "p-seed", "status": "accepted",
+                "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                    "id": { "id": "goal-1" }, "type": "goal", "status": "accepted",
+                    "content": "v1",
+                    "metadata": {
+                        "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                        "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                    }
+                }}],
+                "metadata": { "createdBy": "u" }
+            }),
+            serde_json::json!({
+                "id": "p-1", "status": "open",
+                "operations": [{ "id": "op1", "order": 1, "type": "update",
+                    "node_id": { "id": "goal-1" }, "changes": { "content": "v2" } }],
+                "metadata": { "createdBy": "alice" }
+            }),
+        ] {
+            store
+                .create_proposal(serde_json::from_value(proposal).unwrap())
+                .await
+                .unwrap();
+        }
+        store.apply_proposal("p-seed", "u").await.unwrap();
+
+        let mut config = crate::config::ServerConfig::default();
+        config.forge.workspaces.insert(
+            "default".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "provider": "gitlab",
+                "project": "team/docs",
+                "token_env": "TL_TEST_FORGE_TOKEN",
+                "webhook_secret_env": "TL_TEST_FORGE_SECRET",
+                "identities": { "gl-bob": "bob", "gl-eve": "eve" },
+                "roles": { "bob": ["reviewer"] }
+            }))
+            .unwrap(),
+        );
+        // eve reviews with her client certificate, not through the forge.
+        config.mtls = Some(crate::mtls::MtlsConfig {
+            client_ca_path: "ca.pem".to_string(),
+            required: false,
+            identities: vec![crate::mtls::ClientIdentityMapping {
+                subject: "eve@example.com".to_string(),
+                actor_id: Some("eve".to_string()),
+                actor_type: crate::auth::ActorType::Human,
+                roles: vec![Role::Reviewer],
+            }],
+        });
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(config, crate::policy::PolicyConfig::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let send =
+            |uri: &str, headers: Vec<(&'static str, &'static str)>, body: serde_json::Value| {
+                let mut req = Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json");
+                for (name, value) in headers {
+                    req = req.header(name, value);
+                }
+                let req = req.body(Body::from(body.to_string())).unwrap();
+                let app = app.clone();
+                async move {
+                    let res = app.oneshot(req).await.unwrap();
+                    let status = res.status();
+                    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                    (
+                        status,
+                        serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                    )
+                }
+            };
+        let hook =
+            |event: &'static str| vec![("x-gitlab-event", event), ("x-gitlab-token", "s3cret")];
+
+        let (status, linked) = send(
+            "/proposals/p-1/forge",
+            Vec::new(),
+            serde_json::json!({ "number": 7 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(linked["metadata"]["forge"]["number"], 7);
+
+        let approval = serde_json::json!({
+            "object_attributes": { "action": "approved", "iid": 7 },
+            "user": { "username": "gl-bob" }
+        });
+        let (status, _) = send(
+            "/webhooks/forge/default",
+            vec![
+                ("x-gitlab-event", "Merge Request Hook"),
+                ("x-gitlab-token", "wrong"),
+            ],
+            approval.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        // eve is mapped but has no forge role: her approval is skipped.
+        let (_, outcome) = send(
+            "/webhooks/forge/default",
+            hook("Merge Request Hook"),
+            serde_json::json!({
+                "object_attributes": { "action": "approved", "iid": 7 },
+                "user": { "username": "gl-eve" }
+            }),
+        )
+        .await;
+        assert_eq!(outcome["handled"], "ignored");
+        assert!(outcome["reason"]
+            .as_str()
+            .unwrap()
+            .contains("insufficient role"));
+        assert!(store.get_review_history("p-1").await.unwrap().is_empty());
+        let (_, outcome) = send(
+            "/webhooks/forge/default",
+            hook("Merge Request Hook"),
+            approval,
+        )
+        .await;
+        assert_eq!(outcome["proposals"], serde_json::json!(["p-1"]));
+        let reviews = store.get_review_history("p-1").await.unwrap();
+        assert_eq!(reviews[0].reviewer, "bob");
+
+        store
+            .update_proposal("p-1", ProposalPatch::status(ProposalStatus::Accepted))
+            .await
+            .unwrap();
+        let (status, _) = send("/proposals/p-1/apply", Vec::new(), serde_json::json!(null)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let (_, outcome) = send(
+            "/webhooks/forge/default",
+            hook("Pipeline Hook"),
+            serde_json::json!({
+                "object_attributes": { "status": "success", "sha": "abc" },
+                "merge_request": { "iid": 7 }
+            }),
+        )
+        .await;
+        assert_eq!(outcome["handled"], "pipeline");
+        let (status, _) = send("/proposals/p-1/apply", Vec::new(), serde_json::json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
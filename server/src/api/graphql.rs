@@ -0,0 +1,698 @@
+//! GraphQL query endpoint (`POST /graphql`; `GET /graphql` returns the schema SDL).
+//!
+//! Read-only view of nodes, relationships, proposals and reviews with nested resolution
+//! (e.g. proposal → operations → target node → relationships → target node), so UI
+//! clients fetch exactly the shape they need in one round trip. Every resolver goes
+//! through the shared operations in [`service`]: RBAC applies per field and agents get
+//! redacted nodes (no content, no relationships) above their sensitivity clearance,
+//! including nodes nested in proposal operations. Writes stay on REST / gRPC.
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, SimpleObject,
+};
+use axum::{extract::Extension, response::IntoResponse, routing::get, Json, Router};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, enum_str, NodeRead};
+use crate::auth::ActorContext;
+use crate::types::{self, NodeQuery, NodeStatus};
+
+/// Maximum query nesting depth (bounds the cost of nested resolution).
+const MAX_DEPTH: usize = 12;
+/// Maximum query complexity (one point per field by default).
+const MAX_COMPLEXITY: usize = 2_000;
+
+pub type TruthLayerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn schema(state: AppState) -> TruthLayerSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// `/graphql` routes; merged into the main router so auth and limits apply.
+pub fn routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/graphql", get(sdl).post(execute))
+        .layer(Extension(schema(state)))
+}
+
+async fn execute(
+    Extension(schema): Extension<TruthLayerSchema>,
+    Extension(actor): Extension<ActorContext>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request.data(actor)).await)
+}
+
+async fn sdl(Extension(schema): Extension<TruthLayerSchema>) -> impl IntoResponse {
+    schema.sdl()
+}
+
+fn gql_error(e: ApiError) -> async_graphql::Error {
+    let (code, message) = match e {
+        ApiError::NotFound(m) => ("NOT_FOUND", m),
+        ApiError::Invalid(m) => ("BAD_REQUEST", m),
+        ApiError::Forbidden(f) => ("FORBIDDEN", f.0),
+        ApiError::Unauthorized(m) => ("UNAUTHENTICATED", m),
+        ApiError::ReadOnly(m) => ("READ_ONLY", m),
+        ApiError::PolicyViolation(v) => (
+            "POLICY_VIOLATION",
+            format!(
+                "policy violation: {}",
+                serde_json::to_string(&v).unwrap_or_default()
+            ),
+        ),
+        ApiError::Store(s) => {
+            let code = s.code.as_str().to_uppercase();
+            let retryable = s.retryable;
+            return async_graphql::Error::new(s.message).extend_with(|_, ext| {
+                ext.set("code", code.as_str());
+                ext.set("retryable", retryable);
+            });
+        }
+    };
+    async_graphql::Error::new(message).extend_with(|_, ext| ext.set("code", code))
+}
+
+/// Request-scoped state and caller.
+fn scope<'a>(ctx: &Context<'a>) -> async_graphql::Result<(&'a AppState, &'a ActorContext)> {
+    Ok((ctx.data::<AppState>()?, ctx.data::<ActorContext>()?))
+}
+
+/// Resolve a node reference; `None` when it does not exist (dangling relationship or
+/// a node a pending proposal would delete).
+async fn resolve_node(
+    ctx: &Context<'_>,
+    id: &types::NodeId,
+) -> async_graphql::Result<Option<Node>> {
+    let (state, actor) = scope(ctx)?;
+    match service::get_node(state, actor, id).await {
+        Ok(read) => Ok(Some(Node(read))),
+        Err(ApiError::NotFound(_)) => Ok(None),
+        Err(e) => Err(gql_error(e)),
+    }
+}
+
+fn parse_node_status(s: &str) -> async_graphql::Result<NodeStatus> {
+    match s.trim() {
+        "accepted" => Ok(NodeStatus::Accepted),
+        "proposed" => Ok(NodeStatus::Proposed),
+        "rejected" => Ok(NodeStatus::Rejected),
+        "superseded" => Ok(NodeStatus::Superseded),
+        "archived" => Ok(NodeStatus::Archived),
+        other => Err(gql_error(ApiError::Invalid(format!(
+            "unknown node status: {}",
+            other
+        )))),
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A node by ID (and optional namespace).
+    async fn node(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        namespace: Option<String>,
+    ) -> async_graphql::Result<Option<Node>> {
+        resolve_node(ctx, &types::NodeId { id, namespace }).await
+    }
+
+    /// Query nodes; `status` values: accepted, proposed, rejected, superseded.
+    async fn nodes(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<Vec<String>>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<NodeConnection> {
+        let (state, actor) = scope(ctx)?;
+        let mut query = NodeQuery::default();
+        if let Some(statuses) = status.filter(|s| !s.is_empty()) {
+            query.status = Some(
+                statuses
+                    .iter()
+                    .map(|s| parse_node_status(s))
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        query.limit = limit;
+        query.offset = offset;
+        let result = service::query_nodes(state, actor, query)
+            .await
+            .map_err(gql_error)?;
+        Ok(NodeConnection {
+            nodes: result
+                .nodes
+                .into_iter()
+                .map(|n| Node(NodeRead::Full(Box::new(n))))
+                .collect(),
+            total: result.total,
+            limit: result.limit,
+            offset: result.offset,
+            has_more: result.has_more,
+        })
+    }
+
+    async fn proposal(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Proposal>> {
+        let (state, actor) = scope(ctx)?;
+        match service::get_proposal(state, actor, &id).await {
+            Ok(p) => Ok(Some(Proposal(p))),
+            Err(ApiError::NotFound(_)) => Ok(None),
+            Err(e) => Err(gql_error(e)),
+        }
+    }
+
+    /// Open proposals.
+    async fn proposals(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<ProposalConnection> {
+        let (state, actor) = scope(ctx)?;
+        let page = service::list_open_proposals(state, actor, limit, offset)
+            .await
+            .map_err(gql_error)?;
+        Ok(ProposalConnection {
+            proposals: page.proposals.into_iter().map(Proposal).collect(),
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+        })
+    }
+
+    async fn reviews(
+        &self,
+        ctx: &Context<'_>,
+        proposal_id: String,
+    ) -> async_graphql::Result<Vec<Review>> {
+        let (state, actor) = scope(ctx)?;
+        let reviews = service::get_review_history(state, actor, &proposal_id)
+            .await
+            .map_err(gql_error)?;
+        Ok(reviews.into_iter().map(Review).collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct NodeConnection {
+    nodes: Vec<Node>,
+    total: u64,
+    limit: u32,
+    offset: u32,
+    has_more: bool,
+}
+
+#[derive(SimpleObject)]
+pub struct ProposalConnection {
+    proposals: Vec<Proposal>,
+    total: u64,
+    limit: u32,
+    offset: u32,
+    has_more: bool,
+}
+
+/// Node identity (ID plus optional namespace).
+#[derive(SimpleObject)]
+pub struct NodeRef {
+    id: String,
+    namespace: Option<String>,
+    /// `namespace:id`, or `id` without a namespace.
+    key: String,
+}
+
+impl From<&types::NodeId> for NodeRef {
+    fn from(id: &types::NodeId) -> Self {
+        Self {
+            id: id.id.clone(),
+            namespace: id.namespace.clone(),
+            key: id.key(),
+        }
+    }
+}
+
+/// A node as the caller may see it: redacted nodes expose only identity, type, status
+/// and sensitivity.
+pub struct Node(NodeRead);
+
+impl Node {
+    fn full(&self) -> Option<&types::ContextNode> {
+        match &self.0 {
+            NodeRead::Full(node) => Some(node),
+            NodeRead::Redacted { .. } => None,
+        }
+    }
+}
+
+#[Object]
+impl Node {
+    async fn id(&self) -> NodeRef {
+        match &self.0 {
+            NodeRead::Full(node) => (&node.id).into(),
+            NodeRead::Redacted { id, .. } => id.into(),
+        }
+    }
+
+    #[graphql(name = "type")]
+    async fn node_type(&self) -> String {
+        match &self.0 {
+            NodeRead::Full(node) => enum_str(&node.node_type),
+            NodeRead::Redacted { node_type, .. } => enum_str(node_type),
+        }
+    }
+
+    async fn status(&self) -> String {
+        match &self.0 {
+            NodeRead::Full(node) => enum_str(&node.status),
+            NodeRead::Redacted { status, .. } => enum_str(status),
+        }
+    }
+
+    async fn sensitivity(&self) -> Option<String> {
+        match &self.0 {
+            NodeRead::Full(node) => node.metadata.sensitivity.map(|s| s.as_str().to_string()),
+            NodeRead::Redacted { sensitivity, .. } => Some(sensitivity.as_str().to_string()),
+        }
+    }
+
+    /// True when the caller (an agent) may not read this node's content.
+    async fn redacted(&self) -> bool {
+        self.full().is_none()
+    }
+
+    async fn title(&self) -> Option<String> {
+        self.full().and_then(|n| n.title.clone())
+    }
+
+    async fn description(&self) -> Option<String> {
+        self.full().and_then(|n| n.description.clone())
+    }
+
+    async fn content(&self) -> Option<String> {
+        self.full().map(|n| n.content.clone())
+    }
+
+    async fn version(&self) -> Option<u32> {
+        self.full().map(|n| n.metadata.version)
+    }
+
+    async fn tags(&self) -> Vec<String> {
+        self.full()
+            .and_then(|n| n.metadata.tags.clone())
+            .unwrap_or_default()
+    }
+
+    async fn created_at(&self) -> Option<String> {
+        self.full().map(|n| n.metadata.created_at.clone())
+    }
+
+    async fn created_by(&self) -> Option<String> {
+        self.full().map(|n| n.metadata.created_by.clone())
+    }
+
+    async fn modified_at(&self) -> Option<String> {
+        self.full().map(|n| n.metadata.modified_at.clone())
+    }
+
+    async fn modified_by(&self) -> Option<String> {
+        self.full().map(|n| n.metadata.modified_by.clone())
+    }
+
+    async fn relationships(&self) -> Vec<Relationship> {
+        self.full()
+            .and_then(|n| n.relationships.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(Relationship)
+            .collect()
+    }
+
+    /// The full node document as REST returns it; null when redacted.
+    async fn document(&self) -> Option<async_graphql::Json<serde_json::Value>> {
+        self.full()
+            .and_then(|n| serde_json::to_value(n).ok())
+            .map(async_graphql::Json)
+    }
+}
+
+pub struct Relationship(types::NodeRelationship);
+
+#[Object]
+impl Relationship {
+    #[graphql(name = "type")]
+    async fn relationship_type(&self) -> String {
+        enum_str(&self.0.relationship_type)
+    }
+
+    async fn reverse_type(&self) -> Option<String> {
+        self.0.reverse_type.as_ref().map(enum_str)
+    }
+
+    async fn target(&self) -> NodeRef {
+        (&self.0.target).into()
+    }
+
+    /// The target node; null when it does not exist.
+    async fn target_node(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Node>> {
+        resolve_node(ctx, &self.0.target).await
+    }
+}
+
+pub struct Proposal(types::Proposal);
+
+#[Object]
+impl Proposal {
+    async fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    async fn status(&self) -> String {
+        enum_str(&self.0.status)
+    }
+
+    async fn created_at(&self) -> &str {
+        &self.0.metadata.created_at
+    }
+
+    async fn created_by(&self) -> &str {
+        &self.0.metadata.created_by
+    }
+
+    async fn modified_at(&self) -> &str {
+        &self.0.metadata.modified_at
+    }
+
+    async fn modified_by(&self) -> &str {
+        &self.0.metadata.modified_by
+    }
+
+    async fn rationale(&self) -> Option<&str> {
+        self.0.metadata.rationale.as_deref()
+    }
+
+    async fn applied_at(&self) -> Option<&str> {
+        self.0.applied.as_ref().map(|a| a.applied_at.as_str())
+    }
+
+    async fn applied_by(&self) -> Option<&str> {
+        self.0.applied.as_ref().map(|a| a.applied_by.as_str())
+    }
+
+    async fn operations(&self) -> Vec<Operation> {
+        let mut ops: Vec<Operation> = self.0.operations.iter().cloned().map(Operation).collect();
+        ops.sort_by_key(|op| op.order());
+        ops
+    }
+
+    async fn reviews(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Review>> {
+        let (state, actor) = scope(ctx)?;
+        let reviews = service::get_review_history(state, actor, &self.0.id)
+            .await
+            .map_err(gql_error)?;
+        Ok(reviews.into_iter().map(Review).collect())
+    }
+}
+
+pub struct Operation(types::Operation);
+
+impl Operation {
+    fn order(&self) -> u32 {
+        match &self.0 {
+            types::Operation::Create { order, .. }
+            | types::Operation::Update { order, .. }
+            | types::Operation::Delete { order, .. }
+            | types::Operation::StatusChange { order, .. } => *order,
+        }
+    }
+
+    fn target_id(&self) -> &types::NodeId {
+        match &self.0 {
+            types::Operation::Create { node, .. } => &node.id,
+            types::Operation::Update { node_id, .. }
+            | types::Operation::Delete { node_id, .. }
+            | types::Operation::StatusChange { node_id, .. } => node_id,
+        }
+    }
+}
+
+#[Object]
+impl Operation {
+    async fn id(&self) -> &str {
+        match &self.0 {
+            types::Operation::Create { id, .. }
+            | types::Operation::Update { id, .. }
+            | types::Operation::Delete { id, .. }
+            | types::Operation::StatusChange { id, .. } => id,
+        }
+    }
+
+    /// create | update | delete | status-change
+    #[graphql(name = "type")]
+    async fn operation_type(&self) -> &str {
+        match &self.0 {
+            types::Operation::Create { .. } => "create",
+            types::Operation::Update { .. } => "update",
+            types::Operation::Delete { .. } => "delete",
+            types::Operation::StatusChange { .. } => "status-change",
+        }
+    }
+
+    #[graphql(name = "order")]
+    async fn op_order(&self) -> u32 {
+        self.order()
+    }
+
+    async fn node_id(&self) -> NodeRef {
+        self.target_id().into()
+    }
+
+    /// The node this operation targets: for `create`, the proposed node; otherwise the
+    /// current node in the store (null if it does not exist).
+    async fn target_node(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Node>> {
+        match &self.0 {
+            types::Operation::Create { node, .. } => {
+                let (state, actor) = scope(ctx)?;
+                Ok(Some(Node(
+                    service::read_node(state, actor, node.clone()).await,
+                )))
+            }
+            _ => resolve_node(ctx, self.target_id()).await,
+        }
+    }
+
+    async fn new_status(&self) -> Option<String> {
+        match &self.0 {
+            types::Operation::StatusChange { new_status, .. } => Some(enum_str(new_status)),
+            _ => None,
+        }
+    }
+
+    async fn old_status(&self) -> Option<String> {
+        match &self.0 {
+            types::Operation::StatusChange { old_status, .. } => Some(enum_str(old_status)),
+            _ => None,
+        }
+    }
+
+    async fn reason(&self) -> Option<&str> {
+        match &self.0 {
+            types::Operation::Delete { reason, .. }
+            | types::Operation::StatusChange { reason, .. } => reason.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub struct Review(types::Review);
+
+#[Object]
+impl Review {
+    async fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    async fn proposal_id(&self) -> &str {
+        &self.0.proposal_id
+    }
+
+    async fn reviewer(&self) -> &str {
+        &self.0.reviewer
+    }
+
+    /// accept | reject | request-changes
+    async fn action(&self) -> String {
+        enum_str(&self.0.action)
+    }
+
+    async fn reviewed_at(&self) -> &str {
+        &self.0.reviewed_at
+    }
+
+    async fn comment(&self) -> Option<&str> {
+        self.0.comment.as_deref()
+    }
+
+    async fn proposal(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Proposal>> {
+        let (state, actor) = scope(ctx)?;
+        match service::get_proposal(state, actor, &self.0.proposal_id).await {
+            Ok(p) => Ok(Some(Proposal(p))),
+            Err(ApiError::NotFound(_)) => Ok(None),
+            Err(e) => Err(gql_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{ActorType, Role};
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::sensitivity::Sensitivity;
+    use crate::store::{ContextStore, StoreBundle};
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// Router over the demo fixture, with `risk-001` marked confidential; requests run
+    /// as `actor`.
+    async fn app(actor: ActorContext) -> Router<()> {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let mut bundle: StoreBundle =
+            serde_json::from_str(include_str!("../../fixtures/demo/demo.json")).unwrap();
+        for node in &mut bundle.nodes {
+            if node.id.id == "risk-001" {
+                node.metadata.sensitivity = Some(Sensitivity::Confidential);
+            }
+        }
+        store.import_bundle(bundle).await.unwrap();
+        crate::api::routes::router(
+            store,
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            move |mut req: Request<Body>, next: axum::middleware::Next| {
+                let actor = actor.clone();
+                async move {
+                    req.extensions_mut().insert(actor);
+                    next.run(req).await
+                }
+            },
+        ))
+    }
+
+    async fn query(app: Router<()>, query: &str) -> serde_json::Value {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/graphql")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "query": query }).to_string(),
+            ))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn agent() -> ActorContext {
+        ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: ActorType::Agent,
+            roles: vec![Role::Contributor],
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_nested_proposal_shape() {
+        let app = app(ActorContext::dev_default()).await;
+        let res = query(
+            app,
+            r#"{ proposal(id: "proposal-demo-002") {
+                status
+                operations { type nodeId { id } targetNode {
+                    title relationships { type targetNode { id { id } title } }
+                } }
+                reviews { reviewer action }
+            } }"#,
+        )
+        .await;
+        assert!(res.get("errors").is_none(), "{}", res);
+        let proposal = &res["data"]["proposal"];
+        assert_eq!(proposal["status"], "open");
+        let op = &proposal["operations"][0];
+        assert_eq!(op["type"], "update");
+        assert_eq!(op["nodeId"]["id"], "risk-001");
+        let rel = &op["targetNode"]["relationships"][0];
+        assert_eq!(rel["type"], "related-to");
+        assert_eq!(rel["targetNode"]["id"]["id"], "decision-001");
+        assert!(rel["targetNode"]["title"].is_string());
+        assert_eq!(proposal["reviews"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn agents_get_redacted_nodes_at_every_level() {
+        let res = query(
+            app(agent()).await,
+            r#"{ node(id: "risk-001") { redacted sensitivity content relationships { type } }
+                 proposal(id: "proposal-demo-002") {
+                    operations { targetNode { redacted content } } } }"#,
+        )
+        .await;
+        assert!(res.get("errors").is_none(), "{}", res);
+        let node = &res["data"]["node"];
+        assert_eq!(node["redacted"], true);
+        assert_eq!(node["sensitivity"], "confidential");
+        assert!(node["content"].is_null());
+        assert_eq!(node["relationships"], serde_json::json!([]));
+        let target = &res["data"]["proposal"]["operations"][0]["targetNode"];
+        assert_eq!(target["redacted"], true);
+        assert!(target["content"].is_null());
+
+        let res = query(
+            app(agent()).await,
+            "{ nodes { total nodes { id { id } } } }",
+        )
+        .await;
+        let ids: Vec<&str> = res["data"]["nodes"]["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"]["id"].as_str().unwrap())
+            .collect();
+        assert!(!ids.contains(&"risk-001"));
+    }
+
+    #[tokio::test]
+    async fn rbac_errors_carry_codes() {
+        let nobody = ActorContext {
+            actor_id: "nobody".to_string(),
+            actor_type: ActorType::Human,
+            roles: vec![],
+        };
+        let res = query(app(nobody).await, r#"{ node(id: "goal-001") { title } }"#).await;
+        assert_eq!(res["errors"][0]["extensions"]["code"], "FORBIDDEN");
+        assert!(res["data"].is_null());
+    }
+}
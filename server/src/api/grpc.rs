@@ -0,0 +1,588 @@
+//! gRPC API (`truthlayer.v1.ContextService`, see `proto/truthlayer/v1/context.proto`).
+//!
+//! Mounted on the axum router, so requests pass through the same auth, limits and
+//! telemetry layers as REST; every RPC calls the shared operations in [`service`],
+//! which enforce RBAC, policies, sensitivity redaction and audit. gRPC needs HTTP/2:
+//! it is served on the TLS TCP listener (ALPN `h2`) and the dev TCP listener (h2c),
+//! not over HTTP/3.
+
+use std::pin::Pin;
+
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use tokio_stream::wrappers::BroadcastStream;
+use tonic::{Request, Response, Status};
+
+use crate::api::routes::{ApiError, AppState, AuditQueryParams};
+use crate::api::service::{self, enum_str, NodeRead};
+use crate::auth::{ActorContext, Role};
+use crate::store::context_store::StoreErrorCode;
+use crate::types::{self, NodeQuery, NodeStatus};
+
+/// Generated protobuf messages and service trait.
+pub mod pb {
+    tonic::include_proto!("truthlayer.v1");
+}
+
+pub use pb::context_service_server::ContextServiceServer;
+
+/// Router path prefix of the gRPC service (`/<package>.<service>/<method>`).
+pub const GRPC_PATH: &str = "/truthlayer.v1.ContextService/*rpc";
+
+/// `ContextService` implementation over the shared application state.
+#[derive(Clone)]
+pub struct GrpcContextService {
+    state: AppState,
+}
+
+impl GrpcContextService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    pub fn into_server(self) -> ContextServiceServer<Self> {
+        ContextServiceServer::new(self)
+    }
+}
+
+/// The actor injected by `AuthLayer` (request extensions carry over into tonic requests).
+fn actor<T>(request: &Request<T>) -> Result<ActorContext, Status> {
+    request
+        .extensions()
+        .get::<ActorContext>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("no authenticated actor"))
+}
+
+impl From<ApiError> for Status {
+    fn from(e: ApiError) -> Self {
+        match e {
+            ApiError::NotFound(m) => Status::not_found(m),
+            ApiError::Invalid(m) => Status::invalid_argument(m),
+            ApiError::Forbidden(f) => Status::permission_denied(f.0),
+            ApiError::Unauthorized(m) => Status::unauthenticated(m),
+            ApiError::ReadOnly(m) => Status::unavailable(m),
+            ApiError::PolicyViolation(violations) => Status::failed_precondition(format!(
+                "policy violation: {}",
+                serde_json::to_string(&violations).unwrap_or_default()
+            )),
+            ApiError::Store(s) => match s.code {
+                StoreErrorCode::NotFound => Status::not_found(s.message),
+                StoreErrorCode::Conflict => Status::aborted(s.message),
+                StoreErrorCode::Invalid => Status::invalid_argument(s.message),
+                StoreErrorCode::InvalidTransition => Status::failed_precondition(s.message),
+                StoreErrorCode::CapacityExceeded => Status::resource_exhausted(s.message),
+                StoreErrorCode::Corrupt => Status::data_loss(s.message),
+                _ if s.retryable => Status::unavailable(s.message),
+                _ => Status::internal(s.to_string()),
+            },
+        }
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(json: &str, what: &str) -> Result<T, Status> {
+    serde_json::from_str(json)
+        .map_err(|e| Status::invalid_argument(format!("invalid {} JSON: {}", what, e)))
+}
+
+fn node_id_pb(id: &types::NodeId) -> pb::NodeId {
+    pb::NodeId {
+        id: id.id.clone(),
+        namespace: id.namespace.clone(),
+    }
+}
+
+fn node_pb(node: &types::ContextNode) -> pb::Node {
+    pb::Node {
+        id: Some(node_id_pb(&node.id)),
+        r#type: enum_str(&node.node_type),
+        status: enum_str(&node.status),
+        title: node.title.clone(),
+        version: node.metadata.version,
+        tags: node.metadata.tags.clone().unwrap_or_default(),
+        sensitivity: node.metadata.sensitivity.map(|s| s.as_str().to_string()),
+        modified_at: node.metadata.modified_at.clone(),
+        modified_by: node.metadata.modified_by.clone(),
+        redacted: false,
+        json: to_json(node),
+    }
+}
+
+fn node_read_pb(read: NodeRead) -> pb::Node {
+    match read {
+        NodeRead::Full(node) => node_pb(&node),
+        NodeRead::Redacted {
+            id,
+            node_type,
+            status,
+            sensitivity,
+        } => pb::Node {
+            id: Some(node_id_pb(&id)),
+            r#type: enum_str(&node_type),
+            status: enum_str(&status),
+            sensitivity: Some(sensitivity.as_str().to_string()),
+            redacted: true,
+            ..Default::default()
+        },
+    }
+}
+
+fn proposal_pb(proposal: &types::Proposal) -> pb::Proposal {
+    pb::Proposal {
+        id: proposal.id.clone(),
+        status: enum_str(&proposal.status),
+        created_by: proposal.metadata.created_by.clone(),
+        created_at: proposal.metadata.created_at.clone(),
+        modified_at: proposal.metadata.modified_at.clone(),
+        operation_count: proposal.operations.len() as u32,
+        json: to_json(proposal),
+    }
+}
+
+fn review_pb(review: &types::Review) -> pb::Review {
+    pb::Review {
+        id: review.id.clone(),
+        proposal_id: review.proposal_id.clone(),
+        reviewer: review.reviewer.clone(),
+        action: enum_str(&review.action),
+        reviewed_at: review.reviewed_at.clone(),
+        json: to_json(review),
+    }
+}
+
+fn audit_event_pb(event: &types::AuditEvent) -> pb::AuditEvent {
+    pb::AuditEvent {
+        event_id: event.event_id.clone(),
+        timestamp: event.timestamp.clone(),
+        actor_id: event.actor_id.clone(),
+        actor_type: event.actor_type.clone(),
+        action: enum_str(&event.action),
+        resource_id: event.resource_id.clone(),
+        outcome: enum_str(&event.outcome),
+        json: to_json(event),
+    }
+}
+
+fn server_event_pb(event: &crate::events::ServerEvent) -> pb::ServerEvent {
+    pb::ServerEvent {
+        event_type: event.event_type().to_string(),
+        workspace_id: event.workspace_id.clone(),
+        resource_id: event.resource_id.clone(),
+        actor_id: event.actor_id.clone(),
+        timestamp: event.timestamp.clone(),
+        json: to_json(event),
+    }
+}
+
+fn parse_node_status(s: &str) -> Result<NodeStatus, Status> {
+    match s.trim() {
+        "accepted" => Ok(NodeStatus::Accepted),
+        "proposed" => Ok(NodeStatus::Proposed),
+        "rejected" => Ok(NodeStatus::Rejected),
+        "superseded" => Ok(NodeStatus::Superseded),
+        "archived" => Ok(NodeStatus::Archived),
+        other => Err(Status::invalid_argument(format!(
+            "unknown node status: {}",
+            other
+        ))),
+    }
+}
+
+type WatchStream = Pin<Box<dyn Stream<Item = Result<pb::ServerEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl pb::context_service_server::ContextService for GrpcContextService {
+    async fn get_node(
+        &self,
+        request: Request<pb::GetNodeRequest>,
+    ) -> Result<Response<pb::Node>, Status> {
+        let actor = actor(&request)?;
+        let id = request
+            .into_inner()
+            .id
+            .ok_or_else(|| Status::invalid_argument("id is required"))?;
+        let node_id = types::NodeId {
+            id: id.id,
+            namespace: id.namespace,
+        };
+        let read = service::get_node(&self.state, &actor, &node_id).await?;
+        Ok(Response::new(node_read_pb(read)))
+    }
+
+    async fn query_nodes(
+        &self,
+        request: Request<pb::QueryNodesRequest>,
+    ) -> Result<Response<pb::QueryNodesResponse>, Status> {
+        let actor = actor(&request)?;
+        let req = request.into_inner();
+        let mut query = NodeQuery::default();
+        if !req.status.is_empty() {
+            query.status = Some(
+                req.status
+                    .iter()
+                    .map(|s| parse_node_status(s))
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        query.limit = req.limit;
+        query.offset = req.offset;
+        let result = service::query_nodes(&self.state, &actor, query).await?;
+        Ok(Response::new(pb::QueryNodesResponse {
+            nodes: result.nodes.iter().map(node_pb).collect(),
+            total: result.total,
+            limit: result.limit,
+            offset: result.offset,
+            has_more: result.has_more,
+        }))
+    }
+
+    async fn get_proposal(
+        &self,
+        request: Request<pb::GetProposalRequest>,
+    ) -> Result<Response<pb::Proposal>, Status> {
+        let actor = actor(&request)?;
+        let id = request.into_inner().id;
+        let proposal = service::get_proposal(&self.state, &actor, &id).await?;
+        Ok(Response::new(proposal_pb(&proposal)))
+    }
+
+    async fn list_proposals(
+        &self,
+        request: Request<pb::ListProposalsRequest>,
+    ) -> Result<Response<pb::ListProposalsResponse>, Status> {
+        let actor = actor(&request)?;
+        let req = request.into_inner();
+        let page = service::list_open_proposals(&self.state, &actor, req.limit, req.offset).await?;
+        Ok(Response::new(pb::ListProposalsResponse {
+            proposals: page.proposals.iter().map(proposal_pb).collect(),
+            total: page.total,
+            has_more: page.has_more,
+        }))
+    }
+
+    async fn create_proposal(
+        &self,
+        request: Request<pb::CreateProposalRequest>,
+    ) -> Result<Response<pb::Proposal>, Status> {
+        let actor = actor(&request)?;
+        let proposal: types::Proposal = from_json(&request.into_inner().json, "proposal")?;
+        let id = service::create_proposal(&self.state, &actor, proposal)
+            .await?
+            .id;
+        let created = service::get_proposal(&self.state, &actor, &id).await?;
+        Ok(Response::new(proposal_pb(&created)))
+    }
+
+    async fn apply_proposal(
+        &self,
+        request: Request<pb::ApplyProposalRequest>,
+    ) -> Result<Response<pb::Proposal>, Status> {
+        let actor = actor(&request)?;
+        let req = request.into_inner();
+        service::apply_proposal(&self.state, &actor, &req.id, req.applied_by).await?;
+        let applied = service::get_proposal(&self.state, &actor, &req.id).await?;
+        Ok(Response::new(proposal_pb(&applied)))
+    }
+
+    async fn get_review_history(
+        &self,
+        request: Request<pb::GetReviewHistoryRequest>,
+    ) -> Result<Response<pb::ReviewList>, Status> {
+        let actor = actor(&request)?;
+        let proposal_id = request.into_inner().proposal_id;
+        let reviews = service::get_review_history(&self.state, &actor, &proposal_id).await?;
+        Ok(Response::new(pb::ReviewList {
+            reviews: reviews.iter().map(review_pb).collect(),
+        }))
+    }
+
+    async fn submit_review(
+        &self,
+        request: Request<pb::SubmitReviewRequest>,
+    ) -> Result<Response<pb::Review>, Status> {
+        let actor = actor(&request)?;
+        let review: types::Review = from_json(&request.into_inner().json, "review")?;
+        let proposal_id = review.proposal_id.clone();
+        let review = service::submit_review(&self.state, &actor, &proposal_id, review).await?;
+        Ok(Response::new(review_pb(&review)))
+    }
+
+    async fn query_audit(
+        &self,
+        request: Request<pb::QueryAuditRequest>,
+    ) -> Result<Response<pb::AuditEventList>, Status> {
+        let actor = actor(&request)?;
+        let req = request.into_inner();
+        let params = AuditQueryParams {
+            actor: req.actor,
+            action: req.action,
+            resource_id: req.resource_id,
+            from: req.from,
+            to: req.to,
+            limit: req.limit,
+            offset: req.offset,
+        };
+        let page = service::query_audit(&self.state, &actor, &params).await?;
+        Ok(Response::new(pb::AuditEventList {
+            events: page.events.iter().map(audit_event_pb).collect(),
+            total: page.total,
+            has_more: page.has_more,
+        }))
+    }
+
+    type WatchStream = WatchStream;
+
+    async fn watch(
+        &self,
+        request: Request<pb::WatchRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let actor = actor(&request)?;
+        // The event stream `GET /events` serves.
+        service::require_route(&self.state, &actor, "GET /events", Role::Reader)
+            .map_err(ApiError::from)?;
+        let req = request.into_inner();
+
+        let rx = self.state.event_bus.subscribe();
+        let stream = BroadcastStream::new(rx).filter_map(move |msg| {
+            let workspace = req.workspace.clone();
+            let event_types = req.event_types.clone();
+            async move {
+                // Lagged receivers skip missed events, as with SSE.
+                let event = msg.ok()?;
+                if let Some(ref ws_id) = workspace {
+                    if event.workspace_id.as_deref() != Some(ws_id.as_str()) {
+                        return None;
+                    }
+                }
+                if !event_types.is_empty() && !event_types.iter().any(|t| t == event.event_type()) {
+                    return None;
+                }
+                Some(Ok(server_event_pb(&event)))
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pb::context_service_server::ContextService;
+    use super::*;
+    use crate::auth::ActorType;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::version::ServerInfo;
+    use std::sync::Arc;
+
+    fn grpc() -> GrpcContextService {
+        let store: Arc<dyn crate::store::ContextStore> =
+            Arc::new(crate::store::InMemoryStore::new());
+        GrpcContextService::new(AppState {
+            store: store.clone(),
+            runtime: RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            event_bus: EventBus::new(),
+            server_info: Arc::new(ServerInfo::default()),
+            jobs: crate::jobs::JobQueue::new(store.clone(), Default::default()),
+            scheduler: crate::scheduler::Scheduler::new(
+                crate::jobs::JobQueue::new(store.clone(), Default::default()),
+                store.clone(),
+                &Default::default(),
+                Default::default(),
+            ),
+            cluster: crate::cluster::Cluster::new(store, &Default::default()),
+            outbox: Default::default(),
+        })
+    }
+
+    fn request<T>(message: T, actor: ActorContext) -> Request<T> {
+        let mut request = Request::new(message);
+        request.extensions_mut().insert(actor);
+        request
+    }
+
+    fn proposal_json(id: &str) -> String {
+        serde_json::json!({
+            "id": id,
+            "status": "open",
+            "operations": [],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "dev-user",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "dev-user"
+            }
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn create_and_list_proposals_are_audited() {
+        let svc = grpc();
+        let created = svc
+            .create_proposal(request(
+                pb::CreateProposalRequest {
+                    json: proposal_json("p-grpc-1"),
+                },
+                ActorContext::dev_default(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(created.id, "p-grpc-1");
+        assert_eq!(created.status, "open");
+
+        let list = svc
+            .list_proposals(request(
+                pb::ListProposalsRequest::default(),
+                ActorContext::dev_default(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(list.total, 1);
+        assert!(list.proposals[0].json.contains("\"p-grpc-1\""));
+
+        let audit = svc
+            .query_audit(request(
+                pb::QueryAuditRequest {
+                    resource_id: Some("p-grpc-1".to_string()),
+                    ..Default::default()
+                },
+                ActorContext::dev_default(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(audit.events.len(), 1);
+        assert_eq!(audit.events[0].action, "proposal_created");
+        assert_eq!((audit.total, audit.has_more), (1, false));
+    }
+
+    #[tokio::test]
+    async fn rbac_and_errors_map_to_status_codes() {
+        let svc = grpc();
+        let reader = ActorContext {
+            actor_id: "reader".to_string(),
+            actor_type: ActorType::Human,
+            roles: vec![Role::Reader],
+        };
+        let err = svc
+            .create_proposal(request(
+                pb::CreateProposalRequest {
+                    json: proposal_json("p-grpc-2"),
+                },
+                reader.clone(),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let err = svc
+            .query_audit(request(pb::QueryAuditRequest::default(), reader))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let err = svc
+            .get_proposal(request(
+                pb::GetProposalRequest {
+                    id: "missing".to_string(),
+                },
+                ActorContext::dev_default(),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        let err = svc
+            .create_proposal(request(
+                pb::CreateProposalRequest {
+                    json: "{".to_string(),
+                },
+                ActorContext::dev_default(),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        let err = svc
+            .get_node(Request::new(pb::GetNodeRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn watch_streams_filtered_events() {
+        let svc = grpc();
+        let mut stream = svc
+            .watch(request(
+                pb::WatchRequest {
+                    workspace: None,
+                    event_types: vec!["proposal_updated".to_string()],
+                },
+                ActorContext::dev_default(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let bus = &svc.state.event_bus;
+        bus.publish(crate::events::test_event("review_submitted", None));
+        bus.publish(crate::events::ServerEvent {
+            resource_id: "p-2".into(),
+            ..crate::events::test_event("proposal_updated", None)
+        });
+
+        let event = stream.next().await.unwrap().unwrap();
+        assert_eq!(event.event_type, "proposal_updated");
+        assert_eq!(event.resource_id, "p-2");
+    }
+
+    #[tokio::test]
+    async fn served_through_router_with_auth_extension() {
+        use axum::body::Body;
+        use http_body_util::BodyExt;
+        use tower::ServiceExt;
+
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: axum::http::Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+
+        // Empty ListProposalsRequest: uncompressed flag + zero length.
+        let req = axum::http::Request::builder()
+            .method("POST")
+            .uri("/truthlayer.v1.ContextService/ListProposals")
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .body(Body::from(vec![0u8, 0, 0, 0, 0]))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::OK);
+        let collected = res.into_body().collect().await.unwrap();
+        let trailers = collected.trailers().cloned().unwrap_or_default();
+        assert_eq!(
+            trailers.get("grpc-status").and_then(|v| v.to_str().ok()),
+            Some("0")
+        );
+    }
+}
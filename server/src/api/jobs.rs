@@ -0,0 +1,186 @@
+//! Background job status (`/admin/jobs`): list and inspect queued, running, completed
+//! and failed jobs, and re-queue failed ones. See `crate::jobs` for the queue itself.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::store::WriteBatch;
+use crate::types::{AuditAction, AuditDetails, AuditEvent, AuditOutcome, JobRecord, JobStatus};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/jobs", get(list_jobs))
+        .route("/admin/jobs/:id", get(get_job))
+        .route("/admin/jobs/:id/retry", post(retry_job))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobListParams {
+    pub status: Option<JobStatus>,
+    pub kind: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+async fn list_jobs(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<JobListParams>,
+) -> Result<Json<Vec<JobRecord>>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/jobs", Role::Admin)?;
+    let jobs = state
+        .store
+        .list_jobs(params.status, params.kind.as_deref())
+        .await?;
+    Ok(Json(
+        jobs.into_iter()
+            .skip(params.offset.unwrap_or(0))
+            .take(params.limit.unwrap_or(100))
+            .collect(),
+    ))
+}
+
+async fn get_job(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Json<JobRecord>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/jobs/:id", Role::Admin)?;
+    state
+        .store
+        .get_job(&id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("job {} not found", id)))
+}
+
+async fn retry_job(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Json<JobRecord>, ApiError> {
+    service::require_route(&state, &actor, "POST /admin/jobs/:id/retry", Role::Admin)?;
+    let job = state.jobs.retry(&id).await?;
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::JobRetried,
+        &job.id,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::JobRetried {
+        kind: job.kind.clone(),
+    });
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::JobRetried,
+            &job.id,
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
+
+    Ok(Json(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn lists_finished_jobs_and_rejects_retry_of_completed() {
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+        let send = |req: Request<Body>| {
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+
+        let (status, _) = send(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/exports")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"kind":"bundle"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let mut jobs = serde_json::Value::Null;
+        for _ in 0..50 {
+            let (_, body) = send(
+                Request::builder()
+                    .uri("/admin/jobs?status=completed&kind=export")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            jobs = body;
+            if jobs.as_array().is_some_and(|a| !a.is_empty()) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let id = jobs[0]["id"].as_str().unwrap().to_string();
+        assert_eq!(jobs[0]["attempts"], 1);
+
+        let (status, body) = send(
+            Request::builder()
+                .uri(format!("/admin/jobs/{}", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "completed");
+
+        let (status, _) = send(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/admin/jobs/{}/retry", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,777 @@
+//! Model Context Protocol (MCP) server: the store as tools and resources for LLM agents
+//! and IDE assistants.
+//!
+//! JSON-RPC 2.0 over two transports:
+//! - **Streamable HTTP**: `POST /mcp` on the main router (same auth as REST; responses
+//!   are plain JSON, no server-initiated SSE stream, so `GET /mcp` is 405).
+//! - **stdio**: `truthlayer-server mcp` reads one message per line from stdin and
+//!   writes responses to stdout ([`serve_stdio`]).
+//!
+//! Tools: `query_nodes`, `get_node`, `create_proposal`, `get_provenance`. Resources:
+//! accepted truth per namespace as Markdown (`truthlayer://accepted/{namespace}`; nodes
+//! without a namespace are under `default`). Everything goes through [`service`], so
+//! RBAC, policies, agent sensitivity redaction and audit apply as on REST.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::ActorContext;
+use crate::context_pack;
+use crate::types::{ContextNode, NodeId, NodeQuery, NodeStatus, Proposal};
+
+/// MCP protocol revision implemented (streamable HTTP transport).
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// URI prefix of the accepted-truth resources.
+const ACCEPTED_URI_PREFIX: &str = "truthlayer://accepted/";
+/// Namespace name for nodes without one.
+const DEFAULT_NAMESPACE: &str = "default";
+
+// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Implementation-defined server error (RBAC denial, store failure).
+const SERVER_ERROR: i64 = -32000;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// `/mcp` route; merged into the main router so auth and limits apply.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/mcp", post(http_post).get(http_get))
+}
+
+async fn http_post(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    body: Bytes,
+) -> Response {
+    let message = match serde_json::from_slice::<Value>(&body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = error_response(Value::Null, PARSE_ERROR, &format!("parse error: {}", e));
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
+    match handle_message(&state, &actor, message).await {
+        Some(response) => Json(response).into_response(),
+        // Only notifications / responses: nothing to return.
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// No server-initiated stream is offered (streamable HTTP allows 405 here).
+async fn http_get() -> Response {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, "POST")]).into_response()
+}
+
+/// Serve MCP over stdio until stdin closes: one JSON-RPC message (or batch) per line.
+/// `issued_at` is the issue time of the actor's token, when it has one.
+pub async fn serve_stdio(
+    state: AppState,
+    actor: ActorContext,
+    issued_at: Option<u64>,
+) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => stdio_message(&state, &actor, issued_at, message).await,
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("parse error: {}", e),
+            )),
+        };
+        if let Some(response) = response {
+            let mut out = serde_json::to_vec(&response).unwrap_or_default();
+            out.push(b'\n');
+            stdout.write_all(&out).await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle a message read from stdio. The actor directory is applied to each one, as the
+/// HTTP middleware does to each request, so deprovisioning the actor or revoking its
+/// token ends a running session's access.
+async fn stdio_message(
+    state: &AppState,
+    actor: &ActorContext,
+    issued_at: Option<u64>,
+    message: Value,
+) -> Option<Value> {
+    let credential = service::Credential::Token(issued_at);
+    match service::apply_directory(state, actor.clone(), credential).await {
+        Ok(actor) => handle_message(state, &actor, message).await,
+        Err(e) => {
+            let id = message.get("id").cloned().unwrap_or(Value::Null);
+            Some(error_response(id, SERVER_ERROR, &error_message(e)))
+        }
+    }
+}
+
+/// Handle one JSON-RPC message or batch. `None` when nothing is to be sent back
+/// (notifications, or a batch of only notifications).
+pub async fn handle_message(
+    state: &AppState,
+    actor: &ActorContext,
+    message: Value,
+) -> Option<Value> {
+    match message {
+        Value::Array(batch) if batch.is_empty() => {
+            Some(error_response(Value::Null, INVALID_REQUEST, "empty batch"))
+        }
+        Value::Array(batch) => {
+            let mut responses = Vec::new();
+            for message in batch {
+                if let Some(response) = handle_single(state, actor, message).await {
+                    responses.push(response);
+                }
+            }
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        message => handle_single(state, actor, message).await,
+    }
+}
+
+async fn handle_single(state: &AppState, actor: &ActorContext, message: Value) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let method = message.get("method").and_then(Value::as_str);
+    let Some(method) = method.filter(|_| message.get("jsonrpc") == Some(&json!("2.0"))) else {
+        // A response from the client (we never send requests) is ignored.
+        if message.get("result").is_some() || message.get("error").is_some() {
+            return None;
+        }
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            "expected a JSON-RPC 2.0 request",
+        ));
+    };
+    // Notifications (`notifications/initialized`, `notifications/cancelled`, ...) need no reply.
+    let id = id?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(initialize_result()),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(state, actor, &params).await,
+        "resources/list" => list_resources(state, actor).await,
+        "resources/templates/list" => Ok(json!({
+            "resourceTemplates": [{
+                "uriTemplate": format!("{}{{namespace}}", ACCEPTED_URI_PREFIX),
+                "name": "Accepted truth",
+                "description": "Accepted nodes of one namespace as Markdown",
+                "mimeType": "text/markdown",
+            }]
+        })),
+        "resources/read" => read_resource(state, actor, &params).await,
+        other => Err((METHOD_NOT_FOUND, format!("method not found: {}", other))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "listChanged": false, "subscribe": false },
+        },
+        "serverInfo": { "name": "truthlayer", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "TruthLayer holds the team's accepted truth (goals, decisions, \
+            constraints, risks, ...). Read accepted nodes before acting; propose changes \
+            with create_proposal (humans review and apply them).",
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "query_nodes",
+            "description": "Query context nodes (goals, decisions, constraints, tasks, risks, ...). \
+                Returns { nodes, total, limit, offset, hasMore }.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "status": {
+                        "type": "array",
+                        "items": { "enum": ["accepted", "proposed", "rejected", "superseded", "archived"] },
+                        "description": "Only nodes with these statuses (default: any but archived)",
+                    },
+                    "include_archived": {
+                        "type": "boolean",
+                        "description": "Also return archived nodes (default: false)",
+                    },
+                    "commit": {
+                        "type": "string",
+                        "description": "Only nodes implemented in or referencing this commit (SHA or prefix)",
+                    },
+                    "limit": { "type": "integer", "minimum": 1 },
+                    "offset": { "type": "integer", "minimum": 0 },
+                },
+            },
+        },
+        {
+            "name": "get_node",
+            "description": "Get one node by ID. Nodes above the caller's sensitivity clearance \
+                are returned redacted.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "namespace": { "type": "string" },
+                },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "create_proposal",
+            "description": "Propose changes to the accepted truth. Takes a proposal document \
+                as accepted by POST /proposals; it stays open until humans review and apply it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "proposal": { "type": "object", "description": "Proposal document" },
+                },
+                "required": ["proposal"],
+            },
+        },
+        {
+            "name": "get_provenance",
+            "description": "Audit trail (who created, reviewed, applied, read) of a node \
+                (id and namespace, or its key) or proposal ID.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "namespace": { "type": "string" },
+                },
+                "required": ["id"],
+            },
+        },
+    ])
+}
+
+type RpcResult = Result<Value, (i64, String)>;
+
+fn tool_text(value: &Value) -> Value {
+    json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(value).unwrap_or_default(),
+        }],
+        "isError": false,
+    })
+}
+
+fn error_message(e: ApiError) -> String {
+    match e {
+        ApiError::NotFound(m) | ApiError::Invalid(m) | ApiError::ReadOnly(m) => m,
+        ApiError::Forbidden(f) => format!("forbidden: {}", f.0),
+        ApiError::Unauthorized(m) => format!("unauthorized: {}", m),
+        ApiError::PolicyViolation(v) => format!(
+            "policy violation: {}",
+            serde_json::to_string(&v).unwrap_or_default()
+        ),
+        ApiError::Store(s) => s.to_string(),
+    }
+}
+
+/// Tool failures (RBAC, policy, not found) are results with `isError`, so the model
+/// sees them; protocol problems are JSON-RPC errors.
+fn tool_error(e: ApiError) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": error_message(e) }],
+        "isError": true,
+    })
+}
+
+fn string_arg(args: &Value, name: &str) -> Result<String, (i64, String)> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| {
+            (
+                INVALID_PARAMS,
+                format!("missing string argument '{}'", name),
+            )
+        })
+}
+
+fn u32_arg(args: &Value, name: &str) -> Result<Option<u32>, (i64, String)> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| {
+                (
+                    INVALID_PARAMS,
+                    format!("'{}' must be a non-negative integer", name),
+                )
+            }),
+    }
+}
+
+async fn call_tool(state: &AppState, actor: &ActorContext, params: &Value) -> RpcResult {
+    let name = string_arg(params, "name")?;
+    let args = params
+        .get("arguments")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    let outcome = match name.as_str() {
+        "query_nodes" => {
+            let mut query = NodeQuery::default();
+            if let Some(statuses) = args.get("status") {
+                let statuses: Vec<NodeStatus> = serde_json::from_value(statuses.clone())
+                    .map_err(|e| (INVALID_PARAMS, format!("invalid 'status': {}", e)))?;
+                if !statuses.is_empty() {
+                    query.status = Some(statuses);
+                }
+            }
+            query.limit = u32_arg(&args, "limit")?;
+            query.offset = u32_arg(&args, "offset")?;
+            query.include_archived = args.get("include_archived").and_then(Value::as_bool);
+            query.commit = args
+                .get("commit")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            service::query_nodes(state, actor, query)
+                .await
+                .map(|result| serde_json::to_value(result).unwrap_or_default())
+        }
+        "get_node" => {
+            let node_id = NodeId {
+                id: string_arg(&args, "id")?,
+                namespace: args
+                    .get("namespace")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            };
+            service::get_node(state, actor, &node_id)
+                .await
+                .map(|read| read.to_json())
+        }
+        "create_proposal" => {
+            let proposal: Proposal =
+                serde_json::from_value(args.get("proposal").cloned().unwrap_or(Value::Null))
+                    .map_err(|e| (INVALID_PARAMS, format!("invalid 'proposal': {}", e)))?;
+            service::create_proposal(state, actor, proposal)
+                .await
+                .map(|created| json!({ "ok": true, "proposalId": created.id }))
+        }
+        "get_provenance" => {
+            let id = NodeId {
+                id: string_arg(&args, "id")?,
+                namespace: args
+                    .get("namespace")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            }
+            .key();
+            service::get_provenance(state, actor, &id)
+                .await
+                .map(|events| json!({ "resourceId": id, "events": events }))
+        }
+        other => return Err((INVALID_PARAMS, format!("unknown tool: {}", other))),
+    };
+    Ok(match outcome {
+        Ok(value) => tool_text(&value),
+        Err(e) => tool_error(e),
+    })
+}
+
+/// Accepted nodes the caller may read, grouped by namespace.
+async fn accepted_by_namespace(
+    state: &AppState,
+    actor: &ActorContext,
+) -> Result<BTreeMap<String, Vec<ContextNode>>, ApiError> {
+    let mut groups: BTreeMap<String, Vec<ContextNode>> = BTreeMap::new();
+    for node in service::accepted_nodes(state, actor).await? {
+        let namespace = node
+            .id
+            .namespace
+            .clone()
+            .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string());
+        groups.entry(namespace).or_default().push(node);
+    }
+    Ok(groups)
+}
+
+fn api_rpc_error(e: ApiError) -> (i64, String) {
+    (SERVER_ERROR, error_message(e))
+}
+
+async fn list_resources(state: &AppState, actor: &ActorContext) -> RpcResult {
+    let groups = accepted_by_namespace(state, actor)
+        .await
+        .map_err(api_rpc_error)?;
+    let resources: Vec<Value> = groups
+        .iter()
+        .map(|(namespace, nodes)| {
+            json!({
+                "uri": format!("{}{}", ACCEPTED_URI_PREFIX, namespace),
+                "name": format!("Accepted truth: {}", namespace),
+                "description": format!("{} accepted nodes", nodes.len()),
+                "mimeType": "text/markdown",
+            })
+        })
+        .collect();
+    Ok(json!({ "resources": resources }))
+}
+
+async fn read_resource(state: &AppState, actor: &ActorContext, params: &Value) -> RpcResult {
+    let uri = string_arg(params, "uri")?;
+    let namespace = uri
+        .strip_prefix(ACCEPTED_URI_PREFIX)
+        .filter(|ns| !ns.is_empty())
+        .ok_or_else(|| (RESOURCE_NOT_FOUND, format!("resource not found: {}", uri)))?;
+    let groups = accepted_by_namespace(state, actor)
+        .await
+        .map_err(api_rpc_error)?;
+    let nodes = groups
+        .get(namespace)
+        .ok_or_else(|| (RESOURCE_NOT_FOUND, format!("resource not found: {}", uri)))?;
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "text/markdown",
+            "text": accepted_markdown(namespace, nodes),
+        }]
+    }))
+}
+
+/// Markdown view of a namespace's accepted nodes, for prompts.
+fn accepted_markdown(namespace: &str, nodes: &[ContextNode]) -> String {
+    let mut out = format!("# Accepted truth: {}\n", namespace);
+    for node in nodes {
+        out.push_str(&context_pack::node_markdown(node));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{ActorType, Role};
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::store::{ContextStore, StoreBundle};
+    use crate::version::ServerInfo;
+    use std::sync::Arc;
+
+    async fn state() -> AppState {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let bundle: StoreBundle =
+            serde_json::from_str(include_str!("../../fixtures/demo/demo.json")).unwrap();
+        store.import_bundle(bundle).await.unwrap();
+        AppState {
+            store: store.clone(),
+            runtime: RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            event_bus: EventBus::new(),
+            server_info: Arc::new(ServerInfo::default()),
+            jobs: crate::jobs::JobQueue::new(store.clone(), Default::default()),
+            scheduler: crate::scheduler::Scheduler::new(
+                crate::jobs::JobQueue::new(store.clone(), Default::default()),
+                store.clone(),
+                &Default::default(),
+                Default::default(),
+            ),
+            cluster: crate::cluster::Cluster::new(store, &Default::default()),
+            outbox: Default::default(),
+        }
+    }
+
+    async fn call(state: &AppState, actor: &ActorContext, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        handle_message(state, actor, request).await.unwrap()
+    }
+
+    fn tool_json(response: &Value) -> Value {
+        assert_eq!(response["result"]["isError"], false, "{}", response);
+        serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn handshake_and_listing() {
+        let state = state().await;
+        let actor = ActorContext::dev_default();
+        let init = call(&state, &actor, "initialize", json!({})).await;
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle_message(&state, &actor, notification).await.is_none());
+
+        let tools = call(&state, &actor, "tools/list", Value::Null).await;
+        let names: Vec<&str> = tools["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "query_nodes",
+                "get_node",
+                "create_proposal",
+                "get_provenance"
+            ]
+        );
+
+        let unknown = call(&state, &actor, "sampling/createMessage", Value::Null).await;
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn tools_use_store_rules() {
+        let state = state().await;
+        let actor = ActorContext::dev_default();
+
+        let nodes = call(
+            &state,
+            &actor,
+            "tools/call",
+            json!({ "name": "query_nodes", "arguments": { "status": ["accepted"] } }),
+        )
+        .await;
+        assert_eq!(tool_json(&nodes)["total"], 4);
+
+        let node = call(
+            &state,
+            &actor,
+            "tools/call",
+            json!({ "name": "get_node", "arguments": { "id": "goal-001" } }),
+        )
+        .await;
+        assert_eq!(tool_json(&node)["id"]["id"], "goal-001");
+
+        let proposal = json!({
+            "id": "p-mcp-1",
+            "status": "open",
+            "operations": [],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "agent-1",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "agent-1"
+            }
+        });
+        let reader = ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: ActorType::Agent,
+            roles: vec![Role::Reader],
+        };
+        let denied = call(
+            &state,
+            &reader,
+            "tools/call",
+            json!({ "name": "create_proposal", "arguments": { "proposal": proposal } }),
+        )
+        .await;
+        assert_eq!(denied["result"]["isError"], true);
+
+        // An agent cannot write a proposal in a human's name.
+        let contributor = ActorContext {
+            roles: vec![Role::Contributor],
+            ..reader
+        };
+        let mut impersonating = proposal.clone();
+        impersonating["metadata"]["createdBy"] = "alice".into();
+        let refused = call(
+            &state,
+            &contributor,
+            "tools/call",
+            json!({ "name": "create_proposal", "arguments": { "proposal": impersonating } }),
+        )
+        .await;
+        assert_eq!(refused["result"]["isError"], true);
+
+        let created = call(
+            &state,
+            &contributor,
+            "tools/call",
+            json!({ "name": "create_proposal", "arguments": { "proposal": proposal } }),
+        )
+        .await;
+        assert_eq!(tool_json(&created)["proposalId"], "p-mcp-1");
+
+        let provenance = call(
+            &state,
+            &actor,
+            "tools/call",
+            json!({ "name": "get_provenance", "arguments": { "id": "p-mcp-1" } }),
+        )
+        .await;
+        assert_eq!(
+            tool_json(&provenance)["events"][0]["action"],
+            "proposal_created"
+        );
+
+        let bad = call(
+            &state,
+            &actor,
+            "tools/call",
+            json!({ "name": "get_node", "arguments": {} }),
+        )
+        .await;
+        assert_eq!(bad["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn stdio_sessions_follow_the_directory() {
+        let state = state().await;
+        let agent = ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: ActorType::Agent,
+            roles: vec![Role::Reader],
+        };
+        let user = |active| crate::store::DirectoryUser {
+            id: "u-1".to_string(),
+            user_name: "agent-1".to_string(),
+            external_id: None,
+            display_name: None,
+            emails: Vec::new(),
+            active,
+            created: "2026-01-01T00:00:00Z".to_string(),
+            last_modified: "2026-01-01T00:00:00Z".to_string(),
+        };
+        let list = || json!({ "jsonrpc": "2.0", "id": 7, "method": "resources/list" });
+
+        state.store.save_directory_user(user(true)).await.unwrap();
+        let listed = stdio_message(&state, &agent, Some(1), list())
+            .await
+            .unwrap();
+        assert!(listed["result"]["resources"].is_array(), "{}", listed);
+
+        // Deprovisioning takes effect on the running session's next message.
+        state.store.save_directory_user(user(false)).await.unwrap();
+        let refused = stdio_message(&state, &agent, Some(1), list())
+            .await
+            .unwrap();
+        assert_eq!(refused["id"], 7);
+        assert_eq!(refused["error"]["code"], SERVER_ERROR);
+        assert!(refused["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("deprovisioned"));
+    }
+
+    #[tokio::test]
+    async fn accepted_truth_resources() {
+        let state = state().await;
+        let actor = ActorContext::dev_default();
+        let list = call(&state, &actor, "resources/list", Value::Null).await;
+        assert_eq!(
+            list["result"]["resources"][0]["uri"],
+            "truthlayer://accepted/default"
+        );
+
+        let read = call(
+            &state,
+            &actor,
+            "resources/read",
+            json!({ "uri": "truthlayer://accepted/default" }),
+        )
+        .await;
+        let text = read["result"]["contents"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("# Accepted truth: default"));
+        assert!(text.contains("`decision-001` · decision"));
+
+        let missing = call(
+            &state,
+            &actor,
+            "resources/read",
+            json!({ "uri": "truthlayer://accepted/other" }),
+        )
+        .await;
+        assert_eq!(missing["error"]["code"], RESOURCE_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn streamable_http_transport() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let app = crate::api::routes::router(
+            state().await.store,
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+        let post = |body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/mcp")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let res = app
+            .clone()
+            .oneshot(post(r#"{"jsonrpc":"2.0","id":7,"method":"ping"}"#))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = app
+            .clone()
+            .oneshot(post(
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::ACCEPTED);
+        let res = app.clone().oneshot(post("{")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res = app
+            .oneshot(Request::get("/mcp").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
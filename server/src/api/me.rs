@@ -0,0 +1,475 @@
+//! `GET /me` — what the authenticated actor may do, so clients can adapt their UI
+//! (hide Apply buttons, …) instead of discovering permissions through `403`s —,
+//! `GET/PUT /me/preferences`, the actor's settings shared across its devices, and
+//! `/me/delegations`, the review delegations it has made (see `types::delegation`).
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{delete, get},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, ActorType, Role};
+use crate::policy;
+use crate::rbac;
+use crate::sensitivity::Sensitivity;
+use crate::types::{Delegation, UserPreferences};
+
+const ALL_ROLES: [Role; 5] = [
+    Role::Reader,
+    Role::Contributor,
+    Role::Reviewer,
+    Role::Applier,
+    Role::Admin,
+];
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/me", get(me))
+        .route("/me/preferences", get(get_preferences).put(put_preferences))
+        .route(
+            "/me/delegations",
+            get(list_delegations).post(create_delegation),
+        )
+        .route("/me/delegations/:id", delete(revoke_delegation))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeResponse {
+    pub actor_id: String,
+    pub actor_type: &'static str,
+    /// Roles held, after the SCIM directory (if any) has applied its group roles.
+    pub roles: Vec<Role>,
+    /// Every role the held ones include (Admin includes all the others).
+    pub effective_roles: Vec<Role>,
+    /// Extra roles granted in one workspace only: forge identities review their
+    /// workspace's pull / merge requests as Reviewer.
+    pub workspace_roles: BTreeMap<String, Vec<Role>>,
+    /// Highest node sensitivity served unredacted.
+    pub sensitivity_clearance: Sensitivity,
+    pub capabilities: Capabilities,
+    pub rate_limit: RateLimitStatus,
+}
+
+/// The write actions the actor can take, per roles, actor type and policies.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub read: bool,
+    pub propose: bool,
+    pub review: bool,
+    pub apply: bool,
+    pub admin: bool,
+}
+
+/// Request rate limits the actor's requests are subject to.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+    /// Requests per second per HTTP/3 connection (`quic_limits.max_requests_per_sec`);
+    /// None when unlimited. Excess requests get `429`.
+    pub http3_requests_per_sec: Option<u32>,
+    /// HTTP/1.1 and HTTP/2 requests are not rate limited.
+    pub tcp_limited: bool,
+}
+
+async fn me(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Json<MeResponse> {
+    let config = state.runtime.config.get();
+    let policies = state.runtime.policies.get();
+    let is_agent = actor.actor_type == ActorType::Agent;
+
+    let mut workspace_roles = BTreeMap::new();
+    for (name, workspace) in &config.forge.workspaces {
+        if workspace.identities.values().any(|a| a == &actor.actor_id)
+            && !actor.has_role(&Role::Reviewer)
+        {
+            workspace_roles.insert(name.clone(), vec![Role::Reviewer]);
+        }
+    }
+    let sensitivity_clearance = service::sensitivity_clearance(&state, &actor);
+    let apply_blocked = is_agent
+        && policies.enforced().any(|rule| {
+            matches!(rule, policy::PolicyRule::AgentRestriction { blocked_actions }
+                if blocked_actions.iter().any(|a| a == "apply"))
+        });
+    let capabilities = Capabilities {
+        read: actor.has_role(&Role::Reader),
+        propose: actor.has_role(&Role::Contributor),
+        review: actor.has_role(&Role::Reviewer) && !is_agent,
+        apply: actor.has_role(&Role::Applier) && !is_agent && !apply_blocked,
+        admin: actor.has_role(&Role::Admin),
+    };
+    let max_per_sec = config.quic_limits.max_requests_per_sec;
+
+    Json(MeResponse {
+        actor_id: actor.actor_id.clone(),
+        actor_type: actor_type_str(&actor),
+        roles: actor.roles.clone(),
+        effective_roles: ALL_ROLES
+            .into_iter()
+            .filter(|r| actor.has_role(r))
+            .collect(),
+        workspace_roles,
+        sensitivity_clearance,
+        capabilities,
+        rate_limit: RateLimitStatus {
+            http3_requests_per_sec: (max_per_sec > 0).then_some(max_per_sec),
+            tcp_limited: false,
+        },
+    })
+}
+
+/// `GET /me/preferences` — the caller's saved preferences, or the defaults.
+async fn get_preferences(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<UserPreferences>, ApiError> {
+    let preferences = state.store.get_preferences(&actor.actor_id).await?;
+    Ok(Json(preferences.unwrap_or_default()))
+}
+
+/// `PUT /me/preferences` — replace the caller's preferences; `400` listing the problems
+/// when they do not validate.
+async fn put_preferences(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(mut preferences): StrictJson<UserPreferences>,
+) -> Result<Json<UserPreferences>, ApiError> {
+    let issues = preferences.validate();
+    if !issues.is_empty() {
+        return Err(ApiError::Invalid(issues.join("; ")));
+    }
+    preferences.updated_at = Some(chrono::Utc::now().to_rfc3339());
+    state
+        .store
+        .save_preferences(&actor.actor_id, preferences.clone())
+        .await?;
+    Ok(Json(preferences))
+}
+
+/// Body of `POST /me/delegations`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationRequest {
+    /// Actor id to approve in the caller's place.
+    pub delegate: String,
+    /// Defaults to now.
+    #[serde(default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// The caller's delegations whose window is not over.
+async fn current_delegations(
+    state: &AppState,
+    actor: &ActorContext,
+) -> Result<Vec<Delegation>, ApiError> {
+    let now = Utc::now();
+    let mut delegations = state.store.get_delegations(&actor.actor_id).await?;
+    delegations.retain(|d| !d.is_expired(now));
+    Ok(delegations)
+}
+
+/// `GET /me/delegations` — the delegations the caller has made, current and upcoming.
+async fn list_delegations(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<Vec<Delegation>>, ApiError> {
+    Ok(Json(current_delegations(&state, &actor).await?))
+}
+
+/// `POST /me/delegations` (humans only) — let `delegate` approve for the caller from
+/// `startsAt` until `endsAt`. Expired delegations are dropped on the way.
+async fn create_delegation(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(request): StrictJson<DelegationRequest>,
+) -> Result<(StatusCode, Json<Delegation>), ApiError> {
+    rbac::reject_agent(&actor, "delegate approval")?;
+    let now = Utc::now();
+    let starts_at = request.starts_at.unwrap_or(now);
+    let delegate = request.delegate.trim();
+    if delegate.is_empty() {
+        return Err(ApiError::Invalid("delegate is empty".to_string()));
+    }
+    if delegate == actor.actor_id {
+        return Err(ApiError::Invalid("cannot delegate to yourself".to_string()));
+    }
+    if request.ends_at <= starts_at || request.ends_at <= now {
+        return Err(ApiError::Invalid(
+            "endsAt must be after startsAt and in the future".to_string(),
+        ));
+    }
+
+    let delegation = Delegation {
+        id: uuid::Uuid::new_v4().to_string(),
+        delegator: actor.actor_id.clone(),
+        delegate: delegate.to_string(),
+        starts_at,
+        ends_at: request.ends_at,
+        reason: request.reason,
+        created_at: now.to_rfc3339(),
+    };
+    let mut delegations = current_delegations(&state, &actor).await?;
+    delegations.push(delegation.clone());
+    state
+        .store
+        .save_delegations(&actor.actor_id, delegations)
+        .await?;
+    Ok((StatusCode::CREATED, Json(delegation)))
+}
+
+/// `DELETE /me/delegations/:id` — revoke one of the caller's delegations.
+async fn revoke_delegation(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let mut delegations = current_delegations(&state, &actor).await?;
+    let before = delegations.len();
+    delegations.retain(|d| d.id != id);
+    if delegations.len() == before {
+        return Err(ApiError::NotFound(format!("delegation {}", id)));
+    }
+    state
+        .store
+        .save_delegations(&actor.actor_id, delegations)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn me_reports_roles_clearance_and_capabilities() {
+        let policies = policy::PolicyConfig {
+            rules: vec![policy::PolicyRule::EgressControl {
+                max_sensitivity: Sensitivity::Internal,
+                destinations: Vec::new(),
+            }
+            .into()],
+        };
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            crate::reload::RuntimeConfig::new(crate::config::ServerConfig::default(), policies),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        );
+        let get_me = |actor: ActorContext| {
+            let app = app.clone();
+            async move {
+                let mut req = Request::get("/me").body(Body::empty()).unwrap();
+                req.extensions_mut().insert(actor);
+                let res = app.oneshot(req).await.unwrap();
+                assert_eq!(res.status(), StatusCode::OK);
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let agent = get_me(ActorContext {
+            actor_id: "bot".to_string(),
+            actor_type: ActorType::Agent,
+            roles: vec![Role::Applier],
+        })
+        .await;
+        assert_eq!(agent["actorType"], "agent");
+        assert_eq!(
+            agent["effectiveRoles"],
+            serde_json::json!(["reader", "contributor", "reviewer", "applier"])
+        );
+        assert_eq!(agent["sensitivityClearance"], "internal");
+        assert_eq!(agent["capabilities"]["propose"], true);
+        assert_eq!(agent["capabilities"]["review"], false);
+        assert_eq!(agent["capabilities"]["apply"], false);
+
+        let admin = get_me(ActorContext::dev_default()).await;
+        assert_eq!(admin["actorId"], "dev-user");
+        assert_eq!(admin["sensitivityClearance"], "restricted");
+        assert_eq!(admin["capabilities"]["apply"], true);
+        assert_eq!(admin["capabilities"]["admin"], true);
+    }
+
+    #[tokio::test]
+    async fn preferences_round_trip_per_actor() {
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                policy::PolicyConfig::default(),
+            ),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        );
+        let send = |actor: &str, method: &str, body: Option<serde_json::Value>| {
+            let mut req = Request::builder()
+                .method(method)
+                .uri("/me/preferences")
+                .header("content-type", "application/json")
+                .body(body.map_or(Body::empty(), |b| Body::from(b.to_string())))
+                .unwrap();
+            req.extensions_mut().insert(ActorContext {
+                actor_id: actor.to_string(),
+                actor_type: ActorType::Human,
+                roles: vec![Role::Reader],
+            });
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+
+        let prefs = serde_json::json!({
+            "notificationChannels": [{ "kind": "slack", "target": "U024BE7LH" }],
+            "defaultWorkspace": "platform",
+            "savedFilters": [{ "name": "open", "resource": "proposals", "query": "status=open" }],
+            "eventTypes": ["proposal_updated"]
+        });
+        let (status, saved) = send("alice", "PUT", Some(prefs)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(saved["updatedAt"].is_string());
+        let (_, got) = send("alice", "GET", None).await;
+        assert_eq!(got, saved);
+        let (_, other) = send("bob", "GET", None).await;
+        assert_eq!(other["savedFilters"], serde_json::json!([]));
+
+        let (status, _) = send(
+            "alice",
+            "PUT",
+            Some(
+                serde_json::json!({ "notificationChannels": [{ "kind": "email", "target": "" }] }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn a_delegate_approves_for_a_required_approver() {
+        let store: Arc<dyn crate::store::ContextStore> =
+            Arc::new(crate::store::InMemoryStore::new());
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                policy::PolicyConfig::default(),
+            ),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        );
+        let send = |actor: &str, method: &str, uri: &str, body: serde_json::Value| {
+            let mut req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            req.extensions_mut().insert(ActorContext {
+                actor_id: actor.to_string(),
+                actor_type: ActorType::Human,
+                roles: vec![Role::Reviewer],
+            });
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+                (status, json)
+            }
+        };
+        let review = |id: &str| {
+            serde_json::json!({
+                "id": id, "proposalId": "p-1", "reviewer": "x",
+                "reviewedAt": "2026-01-02T00:00:00Z", "action": "accept"
+            })
+        };
+
+        let proposal = serde_json::json!({
+            "id": "p-1", "status": "open", "operations": [],
+            "metadata": { "requiredApprovers": ["alice"] }
+        });
+        let (status, _) = send("bob", "POST", "/proposals", proposal).await;
+        assert_eq!(status, StatusCode::CREATED);
+        // Another reviewer's approval does not stand in for alice's.
+        send("dave", "POST", "/proposals/p-1/review", review("r-1")).await;
+        let p = store.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(p.status, crate::types::ProposalStatus::Open);
+
+        let ends_at = (Utc::now() + chrono::Duration::days(7)).to_rfc3339();
+        let (status, _) = send(
+            "alice",
+            "POST",
+            "/me/delegations",
+            serde_json::json!({ "delegate": "alice", "endsAt": ends_at }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, delegation) = send(
+            "alice",
+            "POST",
+            "/me/delegations",
+            serde_json::json!({ "delegate": "carol", "endsAt": ends_at, "reason": "on leave" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (_, listed) = send("alice", "GET", "/me/delegations", serde_json::Value::Null).await;
+        assert_eq!(listed[0]["delegate"], "carol");
+
+        let (status, _) = send("carol", "POST", "/proposals/p-1/review", review("r-2")).await;
+        assert_eq!(status, StatusCode::OK);
+        let reviews = store.get_review_history("p-1").await.unwrap();
+        assert_eq!(reviews[1].on_behalf_of, Some(vec!["alice".to_string()]));
+        let p = store.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(p.status, crate::types::ProposalStatus::Accepted);
+        let audit = store
+            .query_audit(
+                Some("carol"),
+                Some("review_submitted"),
+                Some("p-1"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let details = audit.events[0].details.as_ref().unwrap();
+        assert_eq!(details["delegate"], "carol");
+        assert_eq!(details["onBehalfOf"], serde_json::json!(["alice"]));
+        assert_eq!(details["delegationIds"][0], delegation["id"]);
+
+        let uri = format!("/me/delegations/{}", delegation["id"].as_str().unwrap());
+        let (status, _) = send("alice", "DELETE", &uri, serde_json::Value::Null).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send("alice", "DELETE", &uri, serde_json::Value::Null).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -1 +1,36 @@
+pub mod actors;
+pub mod auto_merge;
+pub mod batch;
+pub mod calendar;
+pub mod changes;
+pub mod commits;
+pub mod conflict_resolution;
+pub mod conflicts;
+pub mod decisions;
+pub mod etag;
+pub mod exports;
+pub mod forge;
+pub mod graphql;
+pub mod grpc;
+pub mod jobs;
+pub mod mcp;
+pub mod me;
+pub mod node_edits;
+pub mod node_locks;
+pub mod policy_violations;
+pub mod projection;
+pub mod proposal_operations;
+pub mod questions;
+pub mod read_only;
+pub mod risks;
 pub mod routes;
+pub mod scim;
+pub mod service;
+pub mod slack;
+pub mod strict;
+pub mod task_workflow;
+pub mod tasks;
+pub mod trash;
+pub mod usage;
+pub mod validate;
+pub mod ws;
@@ -0,0 +1,223 @@
+//! Server-built update proposals: `POST /nodes/:id/propose-update` takes just the
+//! fields to change and opens the proposal around them, so clients (agents above all)
+//! do not hand-build operations, orders, base versions and metadata.
+//!
+//! The proposal has one update operation with the fields that differ from the node as
+//! stored; fields already at the requested value are dropped, and a request that changes
+//! nothing is refused. Unknown field names are always refused, strict mode or not. The
+//! base version is the node's current one unless the caller names the version it read.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::types::{NodeId, Operation, Proposal, ProposalMetadata, ProposalStatus, UpdateChanges};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/nodes/:id/propose-update", post(propose_update))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposeUpdateRequest {
+    /// Fields to set, named as in an update operation (`title`, `content`, `tags`, …).
+    pub changes: serde_json::Map<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// The node version the changes were made against; the current version when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_version: Option<u32>,
+}
+
+/// `changes` as an update, keeping only the fields that differ from `node_json` (the
+/// node as serialized; metadata fields are looked up under `metadata`).
+pub fn changed_fields(
+    changes: serde_json::Map<String, serde_json::Value>,
+    node_json: &serde_json::Value,
+) -> Result<UpdateChanges, String> {
+    let requested: Vec<String> = changes.keys().cloned().collect();
+    let update: UpdateChanges = serde_json::from_value(serde_json::Value::Object(changes))
+        .map_err(|e| format!("changes: {}", e))?;
+    let mut fields = update.fields();
+    if let Some(unknown) = requested.iter().find(|f| !fields.contains_key(*f)) {
+        return Err(format!(
+            "changes.{}: not a field an update sets, or null",
+            unknown
+        ));
+    }
+    fields.retain(|field, value| {
+        let current = node_json
+            .get(field)
+            .or_else(|| node_json["metadata"].get(field));
+        current != Some(value)
+    });
+    serde_json::from_value(serde_json::Value::Object(fields)).map_err(|e| e.to_string())
+}
+
+/// `POST /nodes/:id/propose-update` (Contributor): open a proposal updating the node
+/// → `201` with the proposal, checked and stored like `POST /proposals`.
+async fn propose_update(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(request): StrictJson<ProposeUpdateRequest>,
+) -> Result<(StatusCode, Json<Proposal>), ApiError> {
+    service::require_route(
+        &state,
+        &actor,
+        "POST /nodes/:id/propose-update",
+        Role::Contributor,
+    )?;
+    let node_id = NodeId {
+        id,
+        namespace: request.namespace,
+    };
+    let key = node_id.key();
+    let node = state
+        .store
+        .get_node(&node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", key)))?;
+    let base_version = request.base_version.unwrap_or(node.metadata.version);
+    if base_version > node.metadata.version {
+        return Err(ApiError::Invalid(format!(
+            "baseVersion: node {} is at version {}",
+            key, node.metadata.version
+        )));
+    }
+    let node_json = serde_json::to_value(&node).unwrap_or_default();
+    let changes = changed_fields(request.changes, &node_json).map_err(ApiError::Invalid)?;
+    if changes.fields().is_empty() {
+        return Err(ApiError::Invalid(format!(
+            "changes: node {} already has these values",
+            key
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let proposal = Proposal {
+        id: format!("update-{}", uuid::Uuid::new_v4()),
+        status: ProposalStatus::Open,
+        operations: vec![Operation::Update {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id,
+            changes,
+        }],
+        metadata: ProposalMetadata {
+            created_at: now.clone(),
+            created_by: actor.actor_id.clone(),
+            modified_at: now,
+            modified_by: actor.actor_id.clone(),
+            rationale: request.rationale,
+            required_approvers: None,
+            approved_by: None,
+            base_versions: Some([(key, base_version)].into()),
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
+        },
+        comments: None,
+        relations: None,
+        applied: None,
+    };
+    let proposal = service::create_proposal(&state, &actor, proposal).await?;
+    Ok((StatusCode::CREATED, Json(proposal)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn only_changed_fields_are_proposed_against_the_current_version() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let node = serde_json::json!({
+            "id": { "id": "n1" }, "type": "decision", "status": "accepted",
+            "title": "Database", "content": "Use Postgres",
+            "metadata": { "createdAt": "2026-01-01T00:00:00Z", "createdBy": "x",
+                "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "x", "version": 3,
+                "tags": ["db"] }
+        });
+        store
+            .import_bundle(serde_json::from_value(serde_json::json!({ "nodes": [node] })).unwrap())
+            .await
+            .unwrap();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let propose = |body: serde_json::Value| {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/nodes/n1/propose-update")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, proposal) = propose(serde_json::json!({
+            "changes": { "title": "Database", "content": "Use SQLite", "tags": ["db", "ops"] },
+            "rationale": "Smaller footprint"
+        }))
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(
+            proposal["operations"][0]["changes"],
+            serde_json::json!({ "content": "Use SQLite", "tags": ["db", "ops"] })
+        );
+        assert_eq!(proposal["operations"][0]["order"], 1);
+        assert_eq!(
+            proposal["metadata"]["baseVersions"],
+            serde_json::json!({ "n1": 3 })
+        );
+        assert_eq!(proposal["metadata"]["createdBy"], "dev-user");
+
+        let (status, body) = propose(serde_json::json!({ "changes": { "titel": "X" } })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.to_string().contains("changes.titel"), "{}", body);
+        let (status, _) = propose(serde_json::json!({ "changes": { "title": "Database" } })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "nothing changes");
+        let (status, _) = propose(serde_json::json!({
+            "changes": { "content": "x" }, "baseVersion": 4
+        }))
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::BAD_REQUEST,
+            "a version the node never had"
+        );
+    }
+}
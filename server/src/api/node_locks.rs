@@ -0,0 +1,230 @@
+//! Node locks: advisory edit reservations (`POST /nodes/:id/lock`), so an author about
+//! to rework a node can tell others before they build a colliding proposal.
+//!
+//! A lock is a store lease named `node:{key}` held by the caller's actor id (see
+//! `store::lease`): it expires after its TTL unless renewed by locking again, and only
+//! its holder releases it early. Nothing is refused because of a lock; other authors see
+//! it in `GET /nodes/:id?include=lock`, `GET /nodes/locks` and as `lockedNodes` in a
+//! proposal's conflicts. Locks live in server memory and do not survive a restart.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState, NamespaceParams};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::store::context_store::StoreError;
+use crate::store::lease::{node_lock_name, NODE_LOCK_PREFIX};
+use crate::types::{NodeId, NodeLock};
+
+/// Lock TTL when the request names none.
+pub const DEFAULT_TTL_SECS: u64 = 30 * 60;
+/// Longest lock TTL; a longer reservation is renewed.
+pub const MAX_TTL_SECS: u64 = 24 * 60 * 60;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/nodes/locks", get(list_locks))
+        .route("/nodes/:id/lock", post(lock_node).delete(unlock_node))
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockRequest {
+    pub namespace: Option<String>,
+    /// How long the lock lasts, in seconds (default 30 minutes, at most a day).
+    pub ttl_seconds: Option<u64>,
+}
+
+/// The live lock on `node`, if any.
+pub async fn node_lock(state: &AppState, node: &NodeId) -> Result<Option<NodeLock>, ApiError> {
+    let name = node_lock_name(node);
+    Ok(state
+        .store
+        .list_leases(&name)
+        .await?
+        .into_iter()
+        .find(|l| l.name == name)
+        .and_then(|l| l.node_lock()))
+}
+
+/// `POST /nodes/:id/lock` (Contributor): take or renew the lock. `409` while another
+/// actor holds it.
+async fn lock_node(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(body): StrictJson<LockRequest>,
+) -> Result<Json<NodeLock>, ApiError> {
+    service::require_route(&state, &actor, "POST /nodes/:id/lock", Role::Contributor)?;
+    let ttl = body.ttl_seconds.unwrap_or(DEFAULT_TTL_SECS);
+    if ttl == 0 || ttl > MAX_TTL_SECS {
+        return Err(ApiError::Invalid(format!(
+            "ttlSeconds: between 1 and {}",
+            MAX_TTL_SECS
+        )));
+    }
+    let node_id = NodeId {
+        id,
+        namespace: body.namespace,
+    };
+    service::get_node(&state, &actor, &node_id).await?;
+    let lease = state
+        .store
+        .acquire_lease(
+            &node_lock_name(&node_id),
+            &actor.actor_id,
+            Duration::from_secs(ttl),
+        )
+        .await?;
+    lease
+        .node_lock()
+        .map(Json)
+        .ok_or_else(|| ApiError::Store(StoreError::internal("node lock without a node")))
+}
+
+/// `DELETE /nodes/:id/lock?namespace=` (Contributor): release the caller's lock. `409`
+/// when another actor holds it; releasing an unlocked node is a no-op.
+async fn unlock_node(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    Query(params): Query<NamespaceParams>,
+) -> Result<StatusCode, ApiError> {
+    service::require_route(&state, &actor, "DELETE /nodes/:id/lock", Role::Contributor)?;
+    let node_id = NodeId {
+        id,
+        namespace: params.namespace,
+    };
+    if let Some(lock) = node_lock(&state, &node_id).await? {
+        if lock.holder != actor.actor_id {
+            return Err(ApiError::Store(StoreError::locked(format!(
+                "node {} is locked by {} until {}",
+                node_id.key(),
+                lock.holder,
+                lock.expires_at
+            ))));
+        }
+    }
+    state
+        .store
+        .release_lease(&node_lock_name(&node_id), &actor.actor_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /nodes/locks` (Reader): every live node lock, by node.
+async fn list_locks(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<Vec<NodeLock>>, ApiError> {
+    service::require_route(&state, &actor, "GET /nodes/locks", Role::Reader)?;
+    let leases = state.store.list_leases(NODE_LOCK_PREFIX).await?;
+    Ok(Json(leases.iter().filter_map(|l| l.node_lock()).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::ActorType;
+    use crate::store::ContextStore;
+    use crate::types::Proposal;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn locks_warn_other_authors_until_released() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let node = serde_json::json!({
+            "id": { "id": "n1" }, "type": "decision", "status": "accepted",
+            "content": "Use Postgres",
+            "metadata": { "createdAt": "2026-01-01T00:00:00Z", "createdBy": "x",
+                "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "x", "version": 1 }
+        });
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-bob", "status": "open",
+            "operations": [{ "type": "update", "id": "op-1", "order": 1,
+                "node_id": { "id": "n1" }, "changes": { "content": "Use SQLite" } }],
+            "metadata": { "createdBy": "bob" }
+        }))
+        .unwrap();
+        store
+            .import_bundle(serde_json::from_value(serde_json::json!({ "nodes": [node] })).unwrap())
+            .await
+            .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        );
+        let call = |actor: &str, method: &str, uri: &str, body: serde_json::Value| {
+            let mut req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            req.extensions_mut().insert(ActorContext {
+                actor_id: actor.to_string(),
+                actor_type: ActorType::Human,
+                roles: vec![Role::Contributor],
+            });
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+        let none = serde_json::Value::Null;
+
+        let (status, lock) = call(
+            "alice",
+            "POST",
+            "/nodes/n1/lock",
+            serde_json::json!({ "ttlSeconds": 600 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(lock["holder"], "alice");
+        let (status, _) = call("bob", "POST", "/nodes/n1/lock", serde_json::json!({})).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = call("bob", "DELETE", "/nodes/n1/lock", none.clone()).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (_, node) = call("bob", "GET", "/nodes/n1?include=lock", none.clone()).await;
+        assert_eq!(node["included"]["lock"]["holder"], "alice");
+        let (_, locks) = call("bob", "GET", "/nodes/locks", none.clone()).await;
+        assert_eq!(locks[0]["nodeId"]["id"], "n1");
+        let conflicts = store.detect_conflicts("p-bob").await.unwrap();
+        assert_eq!(conflicts.locked_nodes[0].holder, "alice");
+
+        let (status, _) = call("alice", "DELETE", "/nodes/n1/lock", none.clone()).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, node) = call("bob", "GET", "/nodes/n1?include=lock", none).await;
+        assert!(node["included"]["lock"].is_null());
+        assert!(store
+            .detect_conflicts("p-bob")
+            .await
+            .unwrap()
+            .locked_nodes
+            .is_empty());
+    }
+}
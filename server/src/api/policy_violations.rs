@@ -0,0 +1,306 @@
+//! Policy violation analytics (`GET /admin/policy/violations`): the `policy_evaluated`
+//! audit events that record violations, counted per rule, per actor and per workspace,
+//! with a trend over time, so governance owners can see which rules fire most.
+//!
+//! Refused writes (outcome `policy_violation`) and the would-be violations of `warn` and
+//! `shadow` rules (outcome `policy_warning`, see `crate::policy`) are both counted; each
+//! rule's count is split by enforcement. An event naming several violations counts once
+//! per violation.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Datelike, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+use crate::policy::{Enforcement, PolicyViolation};
+use crate::types::{AuditEvent, AuditOutcome, DEFAULT_WORKSPACE};
+
+/// Audit events read from the store per page.
+const PAGE: u32 = 1000;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/admin/policy/violations", get(violations_report))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ViolationParams {
+    /// Timestamp bounds, inclusive, as `GET /audit` takes them.
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Only this rule (`min_approvals`, …).
+    pub rule: Option<String>,
+    /// Trend bucket: `hour`, `day` (default) or `week` (starting Monday).
+    pub bucket: Option<String>,
+}
+
+/// Trend bucket width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bucket {
+    Hour,
+    Day,
+    Week,
+}
+
+impl Bucket {
+    fn parse(bucket: Option<&str>) -> Result<Self, ApiError> {
+        match bucket.unwrap_or("day") {
+            "hour" => Ok(Bucket::Hour),
+            "day" => Ok(Bucket::Day),
+            "week" => Ok(Bucket::Week),
+            other => Err(ApiError::Invalid(format!(
+                "bucket: '{}' is not one of hour, day, week",
+                other
+            ))),
+        }
+    }
+
+    /// Start of the bucket `at` falls in.
+    fn start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let day = at.duration_trunc(Duration::days(1)).unwrap_or(at);
+        match self {
+            Bucket::Hour => at.duration_trunc(Duration::hours(1)).unwrap_or(at),
+            Bucket::Day => day,
+            Bucket::Week => day - Duration::days(day.weekday().num_days_from_monday().into()),
+        }
+    }
+}
+
+/// A rule's violations, by the enforcement they were raised under.
+#[derive(Debug, Default, Serialize)]
+pub struct RuleCount {
+    pub total: u64,
+    pub enforce: u64,
+    pub warn: u64,
+    pub shadow: u64,
+}
+
+impl RuleCount {
+    fn add(&mut self, enforcement: Enforcement) {
+        self.total += 1;
+        match enforcement {
+            Enforcement::Enforce => self.enforce += 1,
+            Enforcement::Warn => self.warn += 1,
+            Enforcement::Shadow => self.shadow += 1,
+        }
+    }
+}
+
+/// Violations in one trend bucket.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendBucket {
+    pub start: String,
+    pub total: u64,
+    pub by_rule: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViolationReport {
+    pub total: u64,
+    pub by_rule: BTreeMap<String, RuleCount>,
+    pub by_actor: BTreeMap<String, u64>,
+    pub by_workspace: BTreeMap<String, u64>,
+    /// Buckets with violations, oldest first.
+    pub trend: Vec<TrendBucket>,
+}
+
+impl ViolationReport {
+    /// Count the violations `event` records (those of `rule` only, when given).
+    fn add(&mut self, event: &AuditEvent, rule: Option<&str>, bucket: Bucket) {
+        if !matches!(
+            event.outcome,
+            AuditOutcome::PolicyViolation | AuditOutcome::PolicyWarning
+        ) {
+            return;
+        }
+        let violations: Vec<PolicyViolation> = event
+            .details
+            .as_ref()
+            .and_then(|d| d.get("violations"))
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let start = DateTime::parse_from_rfc3339(&event.timestamp)
+            .ok()
+            .map(|at| bucket.start(at.with_timezone(&Utc)));
+        let workspace = event.workspace_id.as_deref().unwrap_or(DEFAULT_WORKSPACE);
+        for violation in violations
+            .iter()
+            .filter(|v| rule.is_none_or(|r| v.rule == r))
+        {
+            self.total += 1;
+            self.by_rule
+                .entry(violation.rule.clone())
+                .or_default()
+                .add(violation.enforcement);
+            *self.by_actor.entry(event.actor_id.clone()).or_default() += 1;
+            *self.by_workspace.entry(workspace.to_string()).or_default() += 1;
+            if let Some(start) = start {
+                let start = start.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+                let slot = match self.trend.iter_mut().find(|b| b.start == start) {
+                    Some(slot) => slot,
+                    None => {
+                        self.trend.push(TrendBucket {
+                            start,
+                            total: 0,
+                            by_rule: BTreeMap::new(),
+                        });
+                        self.trend.last_mut().expect("pushed above")
+                    }
+                };
+                slot.total += 1;
+                *slot.by_rule.entry(violation.rule.clone()).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// `GET /admin/policy/violations?from=&to=&rule=&bucket=day` (Admin).
+async fn violations_report(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<ViolationParams>,
+) -> Result<Json<ViolationReport>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/policy/violations", Role::Admin)?;
+    let bucket = Bucket::parse(params.bucket.as_deref())?;
+
+    let mut report = ViolationReport::default();
+    let mut offset = 0;
+    loop {
+        let page = state
+            .store
+            .query_audit(
+                None,
+                Some("policy_evaluated"),
+                None,
+                params.from.as_deref(),
+                params.to.as_deref(),
+                Some(PAGE),
+                Some(offset),
+            )
+            .await?;
+        for event in &page.events {
+            report.add(event, params.rule.as_deref(), bucket);
+        }
+        if !page.has_more || page.events.is_empty() {
+            break;
+        }
+        offset += page.events.len() as u32;
+    }
+    report.trend.sort_by(|a, b| a.start.cmp(&b.start));
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use crate::types::{AuditAction, AuditDetails};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn evaluated(
+        actor: &str,
+        at: &str,
+        outcome: AuditOutcome,
+        violations: Vec<PolicyViolation>,
+    ) -> AuditEvent {
+        let mut event =
+            AuditEvent::new(actor, "agent", AuditAction::PolicyEvaluated, "p-1", outcome)
+                .in_workspace("ui")
+                .with_details(AuditDetails::Violations { violations });
+        event.timestamp = at.to_string();
+        event
+    }
+
+    #[tokio::test]
+    async fn violations_are_counted_per_rule_actor_workspace_and_day() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let limit = || PolicyViolation::new("agent_proposal_limit", "too big".to_string());
+        let mut shadow = PolicyViolation::new("min_approvals", "needs 2".to_string());
+        shadow.enforcement = Enforcement::Shadow;
+        for event in [
+            evaluated(
+                "agent-1",
+                "2026-10-01T09:00:00Z",
+                AuditOutcome::PolicyViolation,
+                vec![limit()],
+            ),
+            evaluated(
+                "agent-1",
+                "2026-10-01T17:00:00Z",
+                AuditOutcome::PolicyViolation,
+                vec![limit()],
+            ),
+            evaluated(
+                "bob",
+                "2026-10-02T10:00:00Z",
+                AuditOutcome::PolicyWarning,
+                vec![shadow],
+            ),
+            // A status transition the review policy settled is not a violation.
+            AuditEvent::new(
+                "bob",
+                "human",
+                AuditAction::PolicyEvaluated,
+                "p-1",
+                AuditOutcome::Success,
+            ),
+        ] {
+            store.append_audit(event).await.unwrap();
+        }
+        let app = crate::api::routes::router(
+            store,
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let get = |uri: &str| {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, report) = get("/admin/policy/violations").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["total"], 3);
+        assert_eq!(
+            report["byRule"]["agent_proposal_limit"],
+            serde_json::json!({ "total": 2, "enforce": 2, "warn": 0, "shadow": 0 })
+        );
+        assert_eq!(report["byRule"]["min_approvals"]["shadow"], 1);
+        assert_eq!(report["byActor"]["agent-1"], 2);
+        assert_eq!(report["byWorkspace"]["ui"], 3);
+        assert_eq!(report["trend"][0]["start"], "2026-10-01T00:00:00Z");
+        assert_eq!(report["trend"][0]["total"], 2);
+        assert_eq!(report["trend"][1]["byRule"]["min_approvals"], 1);
+
+        let (_, hourly) =
+            get("/admin/policy/violations?rule=agent_proposal_limit&bucket=hour&from=2026-10-01T12:00:00Z")
+                .await;
+        assert_eq!(hourly["total"], 1);
+        assert_eq!(hourly["trend"][0]["start"], "2026-10-01T17:00:00Z");
+        let (status, _) = get("/admin/policy/violations?bucket=month").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,143 @@
+//! Response shaping.
+//!
+//! - **`fields=`** on list responses (`GET /nodes`, `GET /proposals`): each item is
+//!   trimmed to the listed fields after serialization, so listings that only show a few
+//!   columns do not carry every node field. Fields are the JSON names (`type`, `status`,
+//!   `title`, …), comma separated; a dotted name keeps one field of an object
+//!   (`metadata.sensitivity`). `id` is always kept, so trimmed items can still be
+//!   fetched in full. Names an item lacks are left out.
+//! - **`include=`** on single reads (`GET /proposals/:id`, `GET /nodes/:id`): related
+//!   resources are added under `included`, keyed by the requested name, so a client gets
+//!   them in one round trip.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::api::routes::ApiError;
+
+/// Parsed `fields=` parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct Fields(Vec<String>);
+
+impl Fields {
+    /// None when `fields` is absent, i.e. full items.
+    pub fn parse(fields: Option<&str>) -> Result<Option<Self>, ApiError> {
+        let Some(fields) = fields else {
+            return Ok(None);
+        };
+        let mut names: Vec<String> = fields
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(bad) = names
+            .iter()
+            .find(|f| f.split('.').any(|part| part.is_empty()))
+        {
+            return Err(ApiError::Invalid(format!(
+                "fields: '{}' is not a field",
+                bad
+            )));
+        }
+        if !names.iter().any(|f| f == "id") {
+            names.push("id".to_string());
+        }
+        Ok(Some(Self(names)))
+    }
+
+    /// `body` as JSON with each item of its `list` array trimmed to the fields.
+    pub fn apply<T: Serialize>(&self, body: &T, list: &str) -> Value {
+        let mut value = serde_json::to_value(body).unwrap_or_default();
+        if let Some(Value::Array(items)) = value.get_mut(list) {
+            let paths: Vec<Vec<&str>> = self.0.iter().map(|f| f.split('.').collect()).collect();
+            for item in items.iter_mut() {
+                *item = project(std::mem::take(item), &paths);
+            }
+        }
+        value
+    }
+}
+
+/// Parsed `include=` parameter: the requested names, each one of `allowed`.
+pub fn parse_include(include: Option<&str>, allowed: &[&str]) -> Result<Vec<String>, ApiError> {
+    let mut names: Vec<String> = Vec::new();
+    for name in include.unwrap_or_default().split(',').map(str::trim) {
+        if name.is_empty() || names.iter().any(|n| n == name) {
+            continue;
+        }
+        if !allowed.contains(&name) {
+            return Err(ApiError::Invalid(format!(
+                "include: '{}' is not one of {}",
+                name,
+                allowed.join(", ")
+            )));
+        }
+        names.push(name.to_string());
+    }
+    Ok(names)
+}
+
+/// `body` as JSON with `included` added; unchanged when nothing was requested.
+pub fn with_included<T: Serialize>(body: &T, included: Map<String, Value>) -> Value {
+    let mut value = serde_json::to_value(body).unwrap_or_default();
+    if let (Value::Object(object), false) = (&mut value, included.is_empty()) {
+        object.insert("included".to_string(), Value::Object(included));
+    }
+    value
+}
+
+/// Keep the parts of `value` the paths name.
+fn project(value: Value, paths: &[Vec<&str>]) -> Value {
+    let Value::Object(object) = value else {
+        return value;
+    };
+    let mut kept = Map::new();
+    for (key, field) in object {
+        let rest: Vec<Vec<&str>> = paths
+            .iter()
+            .filter(|p| p[0] == key)
+            .map(|p| p[1..].to_vec())
+            .collect();
+        if rest.is_empty() {
+            continue;
+        }
+        if rest.iter().any(|p| p.is_empty()) {
+            kept.insert(key, field);
+        } else {
+            kept.insert(key, project(field, &rest));
+        }
+    }
+    Value::Object(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_keep_listed_fields_and_id() {
+        let Ok(Some(fields)) = Fields::parse(Some("status, metadata.sensitivity")) else {
+            panic!("fields not parsed");
+        };
+        let body = serde_json::json!({
+            "total": 1,
+            "nodes": [{
+                "id": { "id": "goal-1" }, "type": "goal", "status": "accepted",
+                "content": "long text",
+                "metadata": { "sensitivity": "internal", "createdBy": "alice" }
+            }]
+        });
+        let trimmed = fields.apply(&body, "nodes");
+        assert_eq!(trimmed["total"], 1);
+        assert_eq!(
+            trimmed["nodes"][0],
+            serde_json::json!({
+                "id": { "id": "goal-1" }, "status": "accepted",
+                "metadata": { "sensitivity": "internal" }
+            })
+        );
+        assert!(Fields::parse(Some("metadata.")).is_err());
+        assert!(matches!(Fields::parse(None), Ok(None)));
+    }
+}
@@ -0,0 +1,371 @@
+//! Operation editing (`PATCH /proposals/:id/operations`): add, replace, remove and
+//! reorder the operations of an open proposal, instead of withdrawing it and proposing
+//! again when one operation in ten needs fixing.
+//!
+//! The edits apply in this order: `remove` (operation ids), `replace` (operations
+//! matched by id), `add` (appended). The result is then sorted, by the ids in `order`
+//! when given (it must name every remaining operation once) or else by each
+//! operation's `order`, and renumbered from 1.
+//!
+//! Only the author (or an Admin) edits, only while the proposal is open and before
+//! anyone approved it: an approval covers the operations it saw. The edited proposal is
+//! checked like a new one (ids, structural issues, create-time policies) and scored
+//! again (`crate::complexity`).
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Extension, Path, State},
+    routing::patch,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::api::validate;
+use crate::auth::{ActorContext, Role};
+use crate::complexity;
+use crate::events::EventKind;
+use crate::ids;
+use crate::policy;
+use crate::rbac;
+use crate::store::context_store::StoreError;
+use crate::store::lifecycle::{self, Transition};
+use crate::store::WriteBatch;
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, Operation, Proposal,
+    ProposalMetadataPatch, ProposalPatch, ReviewAction,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/proposals/:id/operations", patch(edit_operations))
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationsPatch {
+    /// Operations appended; an empty `id` gets a ULID.
+    #[serde(default)]
+    pub add: Vec<Operation>,
+    /// Operations put in place of the ones with the same id.
+    #[serde(default)]
+    pub replace: Vec<Operation>,
+    /// Ids of operations dropped.
+    #[serde(default)]
+    pub remove: Vec<String>,
+    /// Every remaining operation id, in the new order.
+    #[serde(default)]
+    pub order: Option<Vec<String>>,
+}
+
+fn op_id(op: &Operation) -> &str {
+    validate::op_id_and_order(op).0
+}
+
+pub(crate) fn set_order(op: &mut Operation, to: u32) {
+    match op {
+        Operation::Create { order, .. }
+        | Operation::Update { order, .. }
+        | Operation::Delete { order, .. }
+        | Operation::StatusChange { order, .. } => *order = to,
+    }
+}
+
+pub(crate) fn set_id(op: &mut Operation, to: String) {
+    match op {
+        Operation::Create { id, .. }
+        | Operation::Update { id, .. }
+        | Operation::Delete { id, .. }
+        | Operation::StatusChange { id, .. } => *id = to,
+    }
+}
+
+/// `operations` with `edits` made, renumbered from 1.
+pub fn edit(operations: &[Operation], edits: OperationsPatch) -> Result<Vec<Operation>, String> {
+    let mut edited = operations.to_vec();
+    for id in &edits.remove {
+        let before = edited.len();
+        edited.retain(|op| op_id(op) != id);
+        if edited.len() == before {
+            return Err(format!("remove: no operation '{}'", id));
+        }
+    }
+    for op in edits.replace {
+        let slot = edited
+            .iter_mut()
+            .find(|o| op_id(o) == op_id(&op))
+            .ok_or_else(|| format!("replace: no operation '{}'", op_id(&op)))?;
+        *slot = op;
+    }
+    for mut op in edits.add {
+        if op_id(&op).is_empty() {
+            set_id(&mut op, ids::ulid());
+        }
+        edited.push(op);
+    }
+
+    match edits.order {
+        Some(order) => {
+            let named: HashSet<&str> = order.iter().map(String::as_str).collect();
+            let current: HashSet<&str> = edited.iter().map(op_id).collect();
+            if named.len() != order.len() || named != current {
+                return Err(
+                    "order: must name every remaining operation id exactly once".to_string()
+                );
+            }
+            edited.sort_by_key(|op| order.iter().position(|id| id == op_id(op)));
+        }
+        None => edited.sort_by_key(|op| validate::op_id_and_order(op).1),
+    }
+    for (i, op) in edited.iter_mut().enumerate() {
+        set_order(op, i as u32 + 1);
+    }
+    Ok(edited)
+}
+
+/// Refuse to change the operations of `proposal` unless it is open, `actor` is its
+/// author or an Admin, and no one approved it yet.
+pub(crate) async fn check_editable(
+    state: &AppState,
+    actor: &ActorContext,
+    proposal: &Proposal,
+) -> Result<(), ApiError> {
+    lifecycle::next_status(proposal.status, Transition::EditOperations)
+        .map_err(StoreError::from)?;
+    if proposal.metadata.created_by != actor.actor_id && !actor.has_role(&Role::Admin) {
+        return Err(rbac::Forbidden(format!(
+            "only the author ({}) or an Admin can edit the operations of proposal {}",
+            proposal.metadata.created_by, proposal.id
+        ))
+        .into());
+    }
+    let reviews = state.store.get_review_history(&proposal.id).await?;
+    if reviews.iter().any(|r| r.action == ReviewAction::Accept) {
+        return Err(ApiError::Invalid(format!(
+            "proposal {} already has approvals; withdraw it and propose again",
+            proposal.id
+        )));
+    }
+    Ok(())
+}
+
+/// `PATCH /proposals/:id/operations` (Contributor; the author or an Admin). Returns the
+/// proposal as stored.
+async fn edit_operations(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(mut edits): StrictJson<OperationsPatch>,
+) -> Result<Json<Proposal>, ApiError> {
+    service::require_route(
+        &state,
+        &actor,
+        "PATCH /proposals/:id/operations",
+        Role::Contributor,
+    )?;
+
+    let existing = service::get_proposal(&state, &actor, &id).await?;
+    check_editable(&state, &actor, &existing).await?;
+
+    let replaced: Vec<String> = edits
+        .replace
+        .iter()
+        .map(|op| op_id(op).to_string())
+        .collect();
+    let removed = edits.remove.clone();
+    // New and replaced operations get the server's times; the others keep theirs.
+    let stamper = service::stamper(&state);
+    for ops in [&mut edits.add, &mut edits.replace] {
+        stamper.operations(ops).map_err(ApiError::Invalid)?;
+    }
+    let mut patched = existing.clone();
+    patched.operations = edit(&existing.operations, edits).map_err(ApiError::Invalid)?;
+    ids::assign(&mut patched).map_err(ApiError::Invalid)?;
+    if let Some(issue) = validate::structural_issues(&patched.operations).first() {
+        return Err(ApiError::Invalid(format!(
+            "operation {}: {}",
+            issue.operation_id, issue.message
+        )));
+    }
+    let mut patch = ProposalPatch {
+        metadata: Some(ProposalMetadataPatch {
+            modified_by: Some(actor.actor_id.clone()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    stamper
+        .update(&mut patch, &existing)
+        .map_err(ApiError::Invalid)?;
+    let complexity = complexity::assess(&*state.store, &patched).await?;
+
+    let violations = policy::evaluate_on_create(
+        &patched,
+        actor_type_str(&actor),
+        &state.runtime.policies.get(),
+    );
+    service::check_policies(&state, &actor, &id, patched.workspace(), violations).await?;
+
+    let reordered = {
+        let kept: Vec<&str> = existing
+            .operations
+            .iter()
+            .map(op_id)
+            .filter(|op| !removed.iter().any(|r| r == op))
+            .collect();
+        let now: Vec<&str> = patched
+            .operations
+            .iter()
+            .map(op_id)
+            .filter(|op| kept.contains(op))
+            .collect();
+        kept != now
+    };
+    let added = patched
+        .operations
+        .iter()
+        .map(op_id)
+        .filter(|op| !existing.operations.iter().any(|o| op_id(o) == *op))
+        .map(str::to_string)
+        .collect();
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::ProposalUpdated,
+        &id,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::OperationsEdited {
+        added,
+        replaced,
+        removed,
+        reordered,
+    });
+    let batch = WriteBatch::new()
+        .edit_operations(&id, patched.operations, complexity)
+        .update_proposal(&id, patch)
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ProposalUpdated {
+                status: existing.status,
+            },
+            &id,
+            &actor,
+        ));
+    service::execute(&state, batch.in_workspace(existing.workspace())).await?;
+    Ok(Json(service::get_proposal(&state, &actor, &id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::ActorType;
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn delete(id: &str, order: u32, node: &str) -> serde_json::Value {
+        serde_json::json!({ "type": "delete", "id": id, "order": order, "node_id": { "id": node } })
+    }
+
+    #[tokio::test]
+    async fn operations_are_added_removed_and_reordered() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-1", "status": "open",
+            "operations": [delete("op-1", 1, "a"), delete("op-2", 2, "b"), delete("op-3", 3, "c")],
+            "metadata": { "createdBy": "alice" }
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(Default::default(), Default::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        );
+        let send = |actor: &str, body: serde_json::Value| {
+            let mut req = Request::builder()
+                .method("PATCH")
+                .uri("/proposals/p-1/operations")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            req.extensions_mut().insert(ActorContext {
+                actor_id: actor.to_string(),
+                actor_type: ActorType::Human,
+                roles: vec![Role::Contributor],
+            });
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, _) = send("bob", serde_json::json!({ "remove": ["op-2"] })).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send("alice", serde_json::json!({ "remove": ["op-9"] })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(
+            "alice",
+            serde_json::json!({
+                "remove": ["op-2"],
+                "add": [delete("", 0, "d")],
+                "order": ["op-3", "op-1"]
+            }),
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::BAD_REQUEST,
+            "order must name the added one"
+        );
+
+        let (status, body) = send(
+            "alice",
+            serde_json::json!({
+                "remove": ["op-2"],
+                "replace": [delete("op-3", 0, "c2")],
+                "add": [delete("op-4", 9, "d")]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let ops = body["operations"].as_array().unwrap();
+        let ids: Vec<_> = ops.iter().map(|o| o["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["op-3", "op-1", "op-4"]);
+        assert_eq!(ops[0]["node_id"]["id"], "c2");
+        assert_eq!(ops[2]["order"], 3);
+        assert_eq!(body["metadata"]["complexity"]["operations"], 3);
+
+        let (status, body) = send(
+            "alice",
+            serde_json::json!({ "order": ["op-1", "op-4", "op-3"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["operations"][0]["id"], "op-1");
+
+        store
+            .update_proposal(
+                "p-1",
+                crate::types::ProposalPatch::status(crate::types::ProposalStatus::Rejected),
+            )
+            .await
+            .unwrap();
+        let (status, _) = send("alice", serde_json::json!({ "remove": ["op-1"] })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
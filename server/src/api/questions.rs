@@ -0,0 +1,250 @@
+//! Question lifecycle: `GET /questions` lists question nodes (`unanswered=true` for the
+//! open ones) and `POST /nodes/:id/answer` proposes an answer.
+//!
+//! An answer goes through review like any change: the endpoint opens a proposal setting
+//! the question's `answer` and `answeredAt`. Applying it publishes a `question_answered`
+//! event addressed to the question's author (`service::apply_proposal`).
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::types::{
+    ContextNode, NodeId, NodeQueryResult, NodeType, Operation, Proposal, ProposalMetadata,
+    ProposalStatus, UpdateChanges,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/questions", get(list_questions))
+        .route("/nodes/:id/answer", post(answer_question))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct QuestionListParams {
+    /// Only questions without an answer.
+    pub unanswered: Option<bool>,
+    pub namespace: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+fn is_answered(node: &ContextNode) -> bool {
+    node.answer.as_deref().is_some_and(|a| !a.trim().is_empty())
+}
+
+/// `GET /questions?unanswered=true` — question nodes (Reader, sensitivity-filtered like
+/// `GET /nodes`), oldest first so long-open questions come up first.
+async fn list_questions(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<QuestionListParams>,
+) -> Result<Json<NodeQueryResult>, ApiError> {
+    let mut questions: Vec<ContextNode> =
+        service::nodes_of_type(&state, &actor, NodeType::Question, params.namespace.clone())
+            .await?
+            .into_iter()
+            .filter(|node| params.unanswered != Some(true) || !is_answered(node))
+            .collect();
+    questions.sort_by(|a, b| a.metadata.created_at.cmp(&b.metadata.created_at));
+
+    let total = questions.len() as u64;
+    let limit = params.limit.unwrap_or(50);
+    let offset = params.offset.unwrap_or(0);
+    let nodes: Vec<ContextNode> = questions
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    let has_more = u64::from(offset) + (nodes.len() as u64) < total;
+    Ok(Json(NodeQueryResult {
+        nodes,
+        total,
+        limit,
+        offset,
+        has_more,
+    }))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnswerRequest {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// `POST /nodes/:id/answer` — propose an answer to a question (Contributor). Like
+/// `POST /nodes/:id/archive`, this opens a proposal (one update setting `answer` and
+/// `answeredAt`); returns it with `201`. Answering an answered question proposes a
+/// replacement answer.
+async fn answer_question(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(request): StrictJson<AnswerRequest>,
+) -> Result<(StatusCode, Json<Proposal>), ApiError> {
+    service::require_route(&state, &actor, "POST /nodes/:id/answer", Role::Contributor)?;
+    if request.answer.trim().is_empty() {
+        return Err(ApiError::Invalid("answer must not be empty".to_string()));
+    }
+    let node_id = NodeId {
+        id,
+        namespace: request.namespace,
+    };
+    let key = node_id.key();
+    let node = state
+        .store
+        .get_node(&node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", key)))?;
+    if node.node_type != NodeType::Question {
+        return Err(ApiError::Invalid(format!("node {} is not a question", key)));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let proposal = Proposal {
+        id: format!("answer-{}", uuid::Uuid::new_v4()),
+        status: ProposalStatus::Open,
+        operations: vec![Operation::Update {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id,
+            changes: UpdateChanges {
+                answer: Some(request.answer),
+                answered_at: Some(now.clone()),
+                ..Default::default()
+            },
+        }],
+        metadata: ProposalMetadata {
+            created_at: now.clone(),
+            created_by: actor.actor_id.clone(),
+            modified_at: now,
+            modified_by: actor.actor_id.clone(),
+            rationale: request.reason,
+            required_approvers: None,
+            approved_by: None,
+            base_versions: Some([(key, node.metadata.version)].into()),
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
+        },
+        comments: None,
+        relations: None,
+        applied: None,
+    };
+    let proposal = service::create_proposal(&state, &actor, proposal).await?;
+    Ok((StatusCode::CREATED, Json(proposal)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::store::ContextStore;
+    use crate::types::ProposalPatch;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn answering_a_question_notifies_its_author() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let create: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-ask",
+            "status": "accepted",
+            "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                "id": { "id": "question-1" }, "type": "question", "status": "accepted",
+                "content": "Which database?", "question": "Which database?",
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z", "createdBy": "alice",
+                    "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "alice", "version": 0
+                }
+            }}],
+            "metadata": { "createdBy": "alice" }
+        }))
+        .unwrap();
+        store.create_proposal(create).await.unwrap();
+        store.apply_proposal("p-ask", "alice").await.unwrap();
+
+        let events = EventBus::new();
+        let mut received = events.subscribe();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            events,
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (_, open) = send("GET", "/questions?unanswered=true", serde_json::json!(null)).await;
+        assert_eq!(open["total"], 1);
+
+        let (status, proposal) = send(
+            "POST",
+            "/nodes/question-1/answer",
+            serde_json::json!({ "answer": "Postgres" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = proposal["id"].as_str().unwrap().to_string();
+        store
+            .update_proposal(&id, ProposalPatch::status(ProposalStatus::Accepted))
+            .await
+            .unwrap();
+        let (status, _) = send(
+            "POST",
+            &format!("/proposals/{}/apply", id),
+            serde_json::json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let event = loop {
+            let event = received.recv().await.unwrap();
+            if event.event_type() == "question_answered" {
+                break event;
+            }
+        };
+        let data = serde_json::to_value(&event).unwrap()["data"].clone();
+        assert_eq!(data["recipient"], "alice");
+        assert_eq!(data["to"], "Postgres");
+        let (_, open) = send("GET", "/questions?unanswered=true", serde_json::json!(null)).await;
+        assert_eq!(open["total"], 0);
+    }
+}
@@ -0,0 +1,210 @@
+//! Read-only mode toggle (`/admin/read-only`). See `crate::read_only`.
+
+use axum::{
+    extract::{Extension, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::read_only::{self, ReadOnlyMode};
+use crate::store::WriteBatch;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/admin/read-only", get(get_read_only).put(set_read_only))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadOnlyRequest {
+    pub enabled: bool,
+    /// Shown to clients in the 503 body. Default: "maintenance".
+    pub reason: Option<String>,
+}
+
+fn status(mode: Option<&ReadOnlyMode>) -> serde_json::Value {
+    match mode {
+        Some(mode) => serde_json::json!({
+            "enabled": true,
+            "reason": mode.reason,
+            "since": mode.since,
+            "changedBy": mode.changed_by,
+        }),
+        None => serde_json::json!({ "enabled": false }),
+    }
+}
+
+async fn get_read_only(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/read-only", Role::Admin)?;
+    Ok(Json(status(
+        state.runtime.read_only.get().as_ref().as_ref(),
+    )))
+}
+
+/// Turn read-only mode on or off. Setting the current state again changes nothing and is
+/// not audited; turning it on while on replaces the reason.
+async fn set_read_only(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(body): StrictJson<ReadOnlyRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    service::require_route(&state, &actor, "PUT /admin/read-only", Role::Admin)?;
+    let current = state.runtime.read_only.get();
+    let mode = body.enabled.then(|| {
+        ReadOnlyMode::new(
+            body.reason.as_deref().unwrap_or("maintenance"),
+            &actor.actor_id,
+        )
+    });
+    let unchanged = match (current.as_ref(), &mode) {
+        (None, None) => true,
+        (Some(current), Some(mode)) => current.reason == mode.reason,
+        _ => false,
+    };
+    if unchanged {
+        return Ok(Json(status(current.as_ref().as_ref())));
+    }
+    state.runtime.read_only.set(mode.clone());
+    tracing::warn!(
+        enabled = body.enabled,
+        actor = %actor.actor_id,
+        "read-only mode changed via API"
+    );
+
+    let event = read_only::audit_event(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        mode.as_ref(),
+        "api",
+    );
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ConfigChanged {
+                target: "read_only".to_string(),
+            },
+            "read_only",
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
+
+    Ok(Json(status(mode.as_ref())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::store::ContextStore;
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn toggle_blocks_writes_keeps_reads_and_is_audited() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let app = crate::api::routes::router(
+            store.clone(),
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            let app = app.clone();
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body)
+                        .unwrap_or(serde_json::Value::Null),
+                )
+            }
+        };
+        let proposal = |id: &str| {
+            serde_json::json!({
+                "id": id,
+                "status": "open",
+                "operations": [],
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z",
+                    "createdBy": "dev-user",
+                    "modifiedAt": "2026-01-01T00:00:00Z",
+                    "modifiedBy": "dev-user"
+                }
+            })
+        };
+
+        let (status, body) = send(
+            "PUT",
+            "/admin/read-only",
+            serde_json::json!({ "enabled": true, "reason": "restore from backup" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["changedBy"], "dev-user");
+
+        let (status, body) = send("POST", "/proposals", proposal("p-1")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["readOnly"], true);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .contains("restore from backup"));
+        let (status, _) = send("GET", "/proposals", serde_json::Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send("GET", "/admin/read-only", serde_json::Value::Null).await;
+        assert_eq!(body["enabled"], true);
+
+        let (status, _) = send(
+            "PUT",
+            "/admin/read-only",
+            serde_json::json!({ "enabled": false }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send("POST", "/proposals", proposal("p-1")).await;
+        assert!(status.is_success(), "{}", status);
+
+        let audit = store
+            .query_audit(
+                None,
+                Some("read_only_changed"),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(audit.total, 2);
+    }
+}
@@ -0,0 +1,227 @@
+//! Risk register (`GET /risks`): risk nodes with a computed score, highest first.
+//!
+//! The score is severity × likelihood, each weighted 1–4 (`ContextNode::risk_score`), so
+//! 1–16; a risk missing either has no score and sorts last. The `risk_review_reminder`
+//! scheduled task audits high and critical risks that need attention
+//! (`crate::maintenance`).
+
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::ActorContext;
+use crate::types::{ContextNode, NodeType, RiskLikelihood, RiskSeverity};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/risks", get(list_risks))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RiskListParams {
+    /// Comma-separated severities (`high,critical`).
+    pub severity: Option<String>,
+    /// Comma-separated likelihoods (`likely,certain`).
+    pub likelihood: Option<String>,
+    /// Only risks scoring at least this much.
+    pub min_score: Option<u8>,
+    /// Only risks without a mitigation.
+    pub unmitigated: Option<bool>,
+    pub namespace: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// A risk node and its score.
+#[derive(Debug, Serialize)]
+pub struct RiskEntry {
+    #[serde(flatten)]
+    pub node: ContextNode,
+    pub score: Option<u8>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskListResponse {
+    pub risks: Vec<RiskEntry>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+    pub has_more: bool,
+}
+
+/// `GET /risks?severity=high,critical&min_score=9` — risk nodes (Reader,
+/// sensitivity-filtered like `GET /nodes`), by score then key.
+async fn list_risks(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<RiskListParams>,
+) -> Result<Json<RiskListResponse>, ApiError> {
+    let severities: Option<Vec<RiskSeverity>> = parse_list("severity", &params.severity)?;
+    let likelihoods: Option<Vec<RiskLikelihood>> = parse_list("likelihood", &params.likelihood)?;
+
+    let mut risks: Vec<RiskEntry> =
+        service::nodes_of_type(&state, &actor, NodeType::Risk, params.namespace.clone())
+            .await?
+            .into_iter()
+            .filter(|node| {
+                severities
+                    .as_ref()
+                    .is_none_or(|s| node.severity.is_some_and(|v| s.contains(&v)))
+                    && likelihoods
+                        .as_ref()
+                        .is_none_or(|l| node.likelihood.is_some_and(|v| l.contains(&v)))
+                    && params
+                        .min_score
+                        .is_none_or(|min| node.risk_score().is_some_and(|s| s >= min))
+                    && (params.unmitigated != Some(true)
+                        || node
+                            .mitigation
+                            .as_deref()
+                            .is_none_or(|m| m.trim().is_empty()))
+            })
+            .map(|node| RiskEntry {
+                score: node.risk_score(),
+                node,
+            })
+            .collect();
+    risks.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.node.id.key().cmp(&b.node.id.key()))
+    });
+
+    let total = risks.len() as u64;
+    let limit = params.limit.unwrap_or(50);
+    let offset = params.offset.unwrap_or(0);
+    let risks: Vec<RiskEntry> = risks
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    let has_more = u64::from(offset) + (risks.len() as u64) < total;
+    Ok(Json(RiskListResponse {
+        risks,
+        total,
+        limit,
+        offset,
+        has_more,
+    }))
+}
+
+/// Parse a comma-separated query parameter of kebab-case enum values.
+fn parse_list<T: DeserializeOwned>(
+    name: &str,
+    value: &Option<String>,
+) -> Result<Option<Vec<T>>, ApiError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    value
+        .split(',')
+        .map(|v| {
+            serde_json::from_value(serde_json::Value::String(v.trim().to_string()))
+                .map_err(|_| ApiError::Invalid(format!("unknown {} '{}'", name, v.trim())))
+        })
+        .collect::<Result<Vec<T>, _>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContextStore;
+    use crate::types::Proposal;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn risk(id: &str, severity: &str, likelihood: &str, mitigation: Option<&str>) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("p-{}", id),
+            "status": "accepted",
+            "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                "id": { "id": id }, "type": "risk", "status": "accepted", "content": id,
+                "severity": severity, "likelihood": likelihood, "mitigation": mitigation,
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                    "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                }
+            }}],
+            "metadata": { "createdBy": "u" }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn risks_are_scored_and_filtered() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        for proposal in [
+            risk("risk-1", "medium", "likely", Some("monitor")),
+            risk("risk-2", "critical", "certain", None),
+            risk("risk-3", "low", "unlikely", None),
+        ] {
+            let id = proposal.id.clone();
+            store.create_proposal(proposal).await.unwrap();
+            store.apply_proposal(&id, "u").await.unwrap();
+        }
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let get = |uri: &str| {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+        let scored = |body: &serde_json::Value| -> Vec<(String, serde_json::Value)> {
+            body["risks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|r| {
+                    (
+                        r["id"]["id"].as_str().unwrap().to_string(),
+                        r["score"].clone(),
+                    )
+                })
+                .collect()
+        };
+
+        let (_, all) = get("/risks").await;
+        assert_eq!(
+            scored(&all),
+            [
+                ("risk-2".to_string(), 16.into()),
+                ("risk-1".to_string(), 6.into()),
+                ("risk-3".to_string(), 1.into()),
+            ]
+        );
+        let (_, filtered) = get("/risks?severity=medium,critical&unmitigated=true").await;
+        assert_eq!(scored(&filtered), [("risk-2".to_string(), 16.into())]);
+        let (_, high) = get("/risks?min_score=6").await;
+        assert_eq!(high["total"], 2);
+        let (status, _) = get("/risks?likelihood=sometimes").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
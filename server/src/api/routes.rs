@@ -1,4 +1,5 @@
-//! Axum HTTP routes: health, nodes, proposals, review, apply, audit, provenance, SSE events.
+//! Axum HTTP routes: health, nodes, proposals, review, apply, audit, provenance, SSE events
+//! (WebSocket events live in `ws`).
 //!
 //! Verb usage: GET (read), POST (create / actions), PATCH (partial update).
 //! All state-changing routes enforce RBAC and emit audit events.
@@ -6,62 +7,207 @@
 
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::{
         sse::{Event, KeepAlive, Sse},
-        IntoResponse,
+        IntoResponse, Response,
     },
-    routing::{get, post},
+    routing::{any, get, post},
     Json, Router,
 };
 use futures_util::StreamExt;
 use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio_stream::wrappers::BroadcastStream;
-
-use crate::auth::{ActorContext, ActorType, Role};
-use crate::events::{EventBus, ServerEvent};
-use crate::policy::{self, PolicyConfig};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::api::actors;
+use crate::api::auto_merge;
+use crate::api::batch;
+use crate::api::calendar;
+use crate::api::changes;
+use crate::api::commits;
+use crate::api::conflict_resolution;
+use crate::api::conflicts;
+use crate::api::decisions;
+use crate::api::etag;
+use crate::api::exports;
+use crate::api::forge;
+use crate::api::graphql;
+use crate::api::grpc::{self, GrpcContextService};
+use crate::api::jobs;
+use crate::api::mcp;
+use crate::api::me;
+use crate::api::node_edits;
+use crate::api::node_locks;
+use crate::api::policy_violations;
+use crate::api::projection::{self, Fields};
+use crate::api::proposal_operations;
+use crate::api::questions;
+use crate::api::read_only;
+use crate::api::risks;
+use crate::api::scim;
+use crate::api::service::{self, actor_type_str};
+use crate::api::slack;
+use crate::api::strict::{OptionalJson, StrictJson};
+use crate::api::task_workflow;
+use crate::api::tasks;
+use crate::api::trash;
+use crate::api::usage;
+use crate::api::validate;
+use crate::api::ws;
+use crate::auth::{ActorContext, Role};
+use crate::cluster::Cluster;
+use crate::events::{Cursor, EventBus, EventKind};
+use crate::jobs::JobQueue;
+use crate::outbox::OutboxDispatcher;
+use crate::policy;
 use crate::rbac::{self, Forbidden};
-use crate::store::ContextStore;
-use crate::types::{AuditAction, AuditEvent, AuditOutcome, NodeId, NodeQuery, Proposal, Review};
+use crate::reload::RuntimeConfig;
+use crate::scheduler::Scheduler;
+use crate::store::context_store::{StoreError, StoreErrorCode};
+use crate::store::{
+    CompactOptions, CompactReport, ContextStore, ImportSummary, StoreBundle, WriteBatch,
+};
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, AuditQueryResult, NodeId, NodeQuery,
+    Proposal, ProposalPatch, Review,
+};
+use crate::version::{ServerInfo, VersionInfo};
 
 /// Shared application state available to all routes.
 #[derive(Clone)]
 pub struct AppState {
     pub store: Arc<dyn ContextStore>,
-    pub policies: Arc<PolicyConfig>,
+    /// Reloadable settings: policies, effective config (for `/admin/config`).
+    pub runtime: RuntimeConfig,
     pub event_bus: EventBus,
+    pub server_info: Arc<ServerInfo>,
+    /// Background job queue (exports, retention sweeps).
+    pub jobs: JobQueue,
+    /// Cron-scheduled maintenance tasks (`/admin/tasks`).
+    pub scheduler: Scheduler,
+    /// Leases shared with other instances on the same store.
+    pub cluster: Cluster,
+    /// Delivers the events and audit events stored with batched writes.
+    pub outbox: OutboxDispatcher,
 }
 
+/// The REST router. Also starts the background job workers, the task scheduler, the
+/// usage meter's flushes and the outbox dispatcher, so it must be called inside a tokio
+/// runtime.
 pub fn router(
     store: Arc<dyn ContextStore>,
-    policies: Arc<PolicyConfig>,
+    runtime: RuntimeConfig,
     event_bus: EventBus,
+    server_info: ServerInfo,
 ) -> Router<()> {
+    let config = runtime.config.get();
+    let cluster = Cluster::new(store.clone(), &config.cluster);
+    let jobs = JobQueue::standard(store.clone(), config.jobs.clone());
+    jobs.start();
+    let scheduler = Scheduler::new(
+        jobs.clone(),
+        store.clone(),
+        &config.tasks,
+        config.retention_file(),
+    )
+    .with_cluster(cluster.clone());
+    scheduler.start();
+    let meter = crate::usage::UsageMeter::default();
+    meter.start(store.clone());
+    let outbox = OutboxDispatcher::default();
+    outbox.start(store.clone(), event_bus.clone(), config.audit_sink.clone());
     let state = AppState {
         store,
-        policies,
+        runtime,
         event_bus,
+        server_info: Arc::new(server_info),
+        jobs,
+        scheduler,
+        cluster,
+        outbox,
     };
     Router::new()
         .route("/health", get(health))
+        .route("/version", get(version))
         .route("/events", get(events_stream))
+        // `any`: axum 0.7 cannot route CONNECT by method; the handler checks it instead.
+        .route(
+            crate::webtransport::WEBTRANSPORT_PATH,
+            any(webtransport_connect),
+        )
         .route("/nodes", get(query_nodes))
         .route("/nodes/:id", get(get_node))
         .route("/nodes/:id/provenance", get(get_provenance))
+        .route("/nodes/:id/archive", post(archive_node))
+        .route("/nodes/:id/blame", get(node_blame))
+        .route("/nodes/:id/proposals", get(node_proposals))
+        .route("/context-pack", get(context_pack))
         .route("/proposals", get(list_proposals).post(create_proposal))
+        .route("/proposals/merge", post(merge_proposals))
+        .route("/proposals/quarantine", get(list_quarantined_proposals))
+        .route("/proposals/triage", post(triage_proposals))
         .route("/proposals/:id", get(get_proposal).patch(update_proposal))
         .route("/proposals/:id/reviews", get(get_review_history))
         .route("/proposals/:id/review", post(submit_review))
         .route("/proposals/:id/apply", post(apply_proposal))
         .route("/proposals/:id/withdraw", post(withdraw_proposal))
         .route("/reset", post(reset_store))
+        .route("/admin/seed", post(seed_store))
         .route("/audit", get(query_audit))
         .route("/audit/export", get(export_audit))
         .route("/admin/dsar/export", get(dsar_export))
         .route("/admin/dsar/erase", post(dsar_erase))
+        .route("/admin/config", get(admin_config))
+        .route("/admin/store/status", get(admin_store_status))
+        .route("/admin/store/compact", post(admin_store_compact))
+        .merge(graphql::routes(state.clone()))
+        .merge(mcp::routes())
+        .merge(me::routes())
+        .merge(batch::routes())
+        .merge(calendar::routes())
+        .merge(changes::routes())
+        .merge(actors::routes())
+        .merge(exports::routes())
+        .merge(jobs::routes())
+        .merge(tasks::routes())
+        .merge(task_workflow::routes())
+        .merge(risks::routes())
+        .merge(questions::routes())
+        .merge(decisions::routes())
+        .merge(commits::routes())
+        .merge(forge::routes())
+        .merge(slack::routes())
+        .merge(scim::routes())
+        .merge(read_only::routes())
+        .merge(trash::routes())
+        .merge(usage::routes())
+        .merge(policy_violations::routes())
+        .merge(conflicts::routes())
+        .merge(proposal_operations::routes())
+        .merge(auto_merge::routes())
+        .merge(conflict_resolution::routes())
+        .merge(node_locks::routes())
+        .merge(node_edits::routes())
+        .merge(validate::routes())
+        .merge(ws::routes())
+        .route_service(
+            grpc::GRPC_PATH,
+            GrpcContextService::new(state.clone()).into_server(),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            scim::enforce_directory,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.runtime.read_only.clone(),
+            crate::read_only::guard,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            meter,
+            crate::usage::count,
+        ))
         .with_state(state)
 }
 
@@ -69,6 +215,12 @@ async fn health() -> impl IntoResponse {
     (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
 }
 
+/// `GET /version` — crate version, git commit, build timestamp, enabled transports and
+/// storage backend. No role required (like `/health`).
+async fn version(State(state): State<AppState>) -> Json<VersionInfo> {
+    Json(VersionInfo::new(&state.server_info))
+}
+
 // --- SSE events endpoint ---
 
 #[derive(Debug, serde::Deserialize)]
@@ -78,35 +230,77 @@ pub struct EventsParams {
 
 /// `GET /events?workspace={id}` — Server-Sent Events stream for real-time notifications.
 /// Subscribes to the EventBus and filters by workspace ID.
-/// Each event is sent as an SSE `data:` line with JSON payload.
-/// Keep-alive pings every 15s prevent connection timeouts.
+/// Each event is sent as an SSE `data:` line with JSON payload and an `id:` cursor token;
+/// a reconnect with `Last-Event-ID` first replays what it missed from the bus log (a
+/// `reset` event when some of it is gone). Keep-alive pings every 15s prevent connection
+/// timeouts.
 async fn events_stream(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Query(params): Query<EventsParams>,
+    headers: HeaderMap,
 ) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>>, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
-
-    let rx = state.event_bus.subscribe();
+    service::require_route(&state, &actor, "GET /events", Role::Reader)?;
+    let resumed = headers.contains_key("last-event-id");
+    service::audit_subscription(
+        &state,
+        &actor,
+        "sse",
+        AuditDetails::Subscription {
+            workspace: params.workspace.clone(),
+            event_types: None,
+            resumed,
+        },
+    )
+    .await?;
+
+    let bus = state.event_bus.clone();
+    let rx = bus.subscribe();
+    let start = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(Cursor::parse)
+        .unwrap_or_else(|| bus.cursor());
     let workspace_filter = params.workspace;
 
-    let stream = BroadcastStream::new(rx).filter_map(move |msg: Result<ServerEvent, _>| {
+    // Each wake-up (an event, or lag) reads the log from the last cursor, so every
+    // event carries its id and none is sent twice.
+    let stream = futures_util::stream::unfold((rx, start, true), move |(mut rx, cursor, first)| {
+        let bus = bus.clone();
         let ws = workspace_filter.clone();
         async move {
-            let event = msg.ok()?;
-            // Filter by workspace if specified; pass through all if no filter
-            if let Some(ref ws_id) = ws {
-                if event.workspace_id.as_deref() != Some(ws_id.as_str()) {
-                    return None;
+            if !first && matches!(rx.recv().await, Err(RecvError::Closed)) {
+                return None;
+            }
+            let read = bus.read_since(&cursor);
+            let mut out = Vec::new();
+            if read.reset {
+                out.push(Event::default().event("reset").data("{}"));
+            }
+            for (seq, event) in read.events {
+                // Filter by workspace if specified; pass through all if no filter
+                if let Some(ref ws_id) = ws {
+                    if event.workspace_id.as_deref() != Some(ws_id.as_str()) {
+                        continue;
+                    }
+                }
+                let id = Cursor {
+                    epoch: read.cursor.epoch.clone(),
+                    seq: seq + 1,
+                };
+                if let Ok(sse) = Event::default()
+                    .id(id.token())
+                    .event(event.event_type())
+                    .json_data(&event)
+                {
+                    out.push(sse);
                 }
             }
-            let sse = Event::default()
-                .event(&event.event_type)
-                .json_data(&event)
-                .ok()?;
-            Some(Ok::<_, Infallible>(sse))
+            let events = futures_util::stream::iter(out.into_iter().map(Ok::<_, Infallible>));
+            Some((events, (rx, read.cursor, false)))
         }
-    });
+    })
+    .flatten();
 
     Ok(Sse::new(stream).keep_alive(
         KeepAlive::new()
@@ -115,29 +309,21 @@ async fn events_stream(
     ))
 }
 
-/// Helper: publish a server event to SSE subscribers.
-fn publish_event(
-    event_bus: &EventBus,
-    event_type: &str,
-    resource_id: &str,
-    actor: &ActorContext,
-) {
-    event_bus.publish(ServerEvent {
-        event_type: event_type.to_string(),
-        workspace_id: None, // TODO: extract workspace from request context when workspace isolation is implemented
-        resource_id: resource_id.to_string(),
-        actor_id: actor.actor_id.clone(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-        data: None,
-    });
-}
-
-fn actor_type_str(actor: &ActorContext) -> &'static str {
-    match actor.actor_type {
-        ActorType::Human => "human",
-        ActorType::Agent => "agent",
-        ActorType::System => "system",
+/// Extended CONNECT `/webtransport` (HTTP/3 only): authorizes a WebTransport event
+/// session. The HTTP/3 server accepts the session when this returns 200; the event
+/// streams themselves are served by `crate::webtransport`.
+async fn webtransport_connect(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    req: axum::extract::Request,
+) -> Result<StatusCode, ApiError> {
+    if !crate::webtransport::is_webtransport_connect(&req) {
+        return Err(ApiError::Invalid(
+            "WebTransport sessions require an HTTP/3 extended CONNECT".to_string(),
+        ));
     }
+    service::require_route(&state, &actor, "CONNECT /webtransport", Role::Reader)?;
+    Ok(StatusCode::OK)
 }
 
 // --- Node routes ---
@@ -147,15 +333,20 @@ pub struct NodeQueryParams {
     pub status: Option<String>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    pub include_archived: Option<bool>,
+    /// Commit SHA (or prefix) the nodes are linked to.
+    pub commit: Option<String>,
+    /// Comma-separated node fields to return (see `api::projection`).
+    pub fields: Option<String>,
 }
 
 async fn query_nodes(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Query(params): Query<NodeQueryParams>,
-) -> Result<Json<NodeQueryResultResponse>, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
-
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let fields = Fields::parse(params.fields.as_deref())?;
     let mut query = NodeQuery::default();
     if let Some(s) = params.status {
         let statuses: Vec<crate::types::NodeStatus> = s
@@ -165,6 +356,7 @@ async fn query_nodes(
                 "proposed" => Some(crate::types::NodeStatus::Proposed),
                 "rejected" => Some(crate::types::NodeStatus::Rejected),
                 "superseded" => Some(crate::types::NodeStatus::Superseded),
+                "archived" => Some(crate::types::NodeStatus::Archived),
                 _ => None,
             })
             .collect();
@@ -174,149 +366,138 @@ async fn query_nodes(
     }
     query.limit = params.limit;
     query.offset = params.offset;
-    let result = state.store.query_nodes(query).await?;
-
-    // Agent sensitivity filtering: redact nodes above agent's allowed sensitivity
-    let nodes = if actor.actor_type == ActorType::Agent {
-        let max_sensitivity = policy::agent_max_sensitivity(&state.policies);
-        let mut filtered_nodes = Vec::new();
-        let mut redacted_count = 0u64;
-        for node in result.nodes {
-            let node_sensitivity = node
-                .metadata
-                .sensitivity
-                .unwrap_or(crate::sensitivity::Sensitivity::Internal);
-            if crate::sensitivity::agent_can_read(node_sensitivity, max_sensitivity) {
-                // Log agent reads of confidential+ content
-                if node_sensitivity >= crate::sensitivity::Sensitivity::Confidential {
-                    let event = AuditEvent::new(
-                        &actor.actor_id,
-                        actor_type_str(&actor),
-                        AuditAction::SensitiveRead,
-                        &node.id.key(),
-                        AuditOutcome::Success,
-                    );
-                    let _ = state.store.append_audit(event).await;
-                }
-                filtered_nodes.push(node);
-            } else {
-                redacted_count += 1;
-            }
-        }
-        if redacted_count > 0 {
-            let event = AuditEvent::new(
-                &actor.actor_id,
-                actor_type_str(&actor),
-                AuditAction::SensitiveRead,
-                "query_nodes",
-                AuditOutcome::Denied,
-            )
-            .with_details(serde_json::json!({
-                "redactedCount": redacted_count,
-                "agentMaxSensitivity": max_sensitivity.as_str(),
-            }));
-            let _ = state.store.append_audit(event).await;
-        }
-        filtered_nodes
-    } else {
-        result.nodes
-    };
+    query.include_archived = params.include_archived;
+    query.commit = params.commit;
+    let result = service::query_nodes(&state, &actor, query).await?;
 
-    Ok(Json(NodeQueryResultResponse {
+    let body = NodeQueryResultResponse {
         total: result.total,
         limit: result.limit,
         offset: result.offset,
         has_more: result.has_more,
-        nodes,
-    }))
+        nodes: result.nodes,
+    };
+    // Node tags plus paging: a list changes exactly when one of these does.
+    let tags: Vec<String> = body
+        .nodes
+        .iter()
+        .map(|n| format!("{}@{}", n.id.key(), etag::node_etag(n)))
+        .collect();
+    let tag = etag::body_etag(&(&tags, body.total, body.limit, body.offset, &fields));
+    Ok(match fields {
+        Some(fields) => etag::conditional(&headers, tag, fields.apply(&body, "nodes")),
+        None => etag::conditional(&headers, tag, body),
+    })
+}
+
+/// `?namespace=` of the node routes; the node is looked up without one when absent.
+#[derive(Debug, serde::Deserialize)]
+pub struct NamespaceParams {
+    pub namespace: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct GetNodeParams {
+    pub namespace: Option<String>,
+    /// `relationships.targets`, `lock` (see `api::projection`).
+    pub include: Option<String>,
 }
 
 async fn get_node(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
-) -> Result<axum::response::Response, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
+    Query(params): Query<GetNodeParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let include = projection::parse_include(params.include.as_deref(), service::NODE_INCLUDES)?;
+    let node_id = NodeId {
+        id,
+        namespace: params.namespace,
+    };
+    // Agents above their sensitivity clearance get a redaction stub
+    let read = service::get_node(&state, &actor, &node_id).await?;
+    if include.is_empty() {
+        return Ok(etag::conditional(
+            &headers,
+            etag::node_read_etag(&read),
+            read.to_json(),
+        ));
+    }
+    let included = service::node_includes(&state, &actor, &read, &include).await?;
+    let body = projection::with_included(&read.to_json(), included);
+    Ok(etag::conditional(&headers, etag::body_etag(&body), body))
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ArchiveNodeRequest {
+    pub namespace: Option<String>,
+    pub reason: Option<String>,
+}
 
+/// `POST /nodes/:id/archive` — open a proposal that archives the node; 201 with the
+/// proposal. The node changes only once the proposal is accepted and applied.
+async fn archive_node(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    OptionalJson(body): OptionalJson<ArchiveNodeRequest>,
+) -> Result<(StatusCode, Json<Proposal>), ApiError> {
+    let request = body.unwrap_or_default();
     let node_id = NodeId {
-        id: id.clone(),
-        namespace: None,
+        id,
+        namespace: request.namespace,
     };
-    let node = state
-        .store
-        .get_node(&node_id)
-        .await?
-        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", id)))?;
-
-    // Agent sensitivity redaction and read logging
-    if actor.actor_type == ActorType::Agent {
-        let node_sensitivity = node
-            .metadata
-            .sensitivity
-            .unwrap_or(crate::sensitivity::Sensitivity::Internal);
-        let max_sensitivity = policy::agent_max_sensitivity(&state.policies);
-
-        if !crate::sensitivity::agent_can_read(node_sensitivity, max_sensitivity) {
-            // Redact content for agents exceeding sensitivity level
-            let event = AuditEvent::new(
-                &actor.actor_id,
-                actor_type_str(&actor),
-                AuditAction::SensitiveRead,
-                &id,
-                AuditOutcome::Denied,
-            )
-            .with_details(serde_json::json!({
-                "nodeSensitivity": node_sensitivity.as_str(),
-                "agentMaxSensitivity": max_sensitivity.as_str(),
-            }));
-            let _ = state.store.append_audit(event).await;
-            return Ok((
-                StatusCode::OK,
-                Json(serde_json::json!({
-                    "id": node.id,
-                    "type": node.node_type,
-                    "status": node.status,
-                    "redacted": true,
-                    "reason": "sensitivity",
-                    "metadata": { "sensitivity": node_sensitivity.as_str() }
-                })),
-            )
-                .into_response());
-        }
+    let proposal = service::archive_node(&state, &actor, &node_id, request.reason).await?;
+    Ok((StatusCode::CREATED, Json(proposal)))
+}
 
-        // Log agent read (even for non-restricted) of confidential+ content
-        if node_sensitivity >= crate::sensitivity::Sensitivity::Confidential {
-            let event = AuditEvent::new(
-                &actor.actor_id,
-                actor_type_str(&actor),
-                AuditAction::SensitiveRead,
-                &id,
-                AuditOutcome::Success,
-            )
-            .with_details(serde_json::json!({
-                "nodeSensitivity": node_sensitivity.as_str(),
-            }));
-            let _ = state.store.append_audit(event).await;
-        }
-    }
+/// `GET /nodes/:id/blame` — who last changed each field, through which proposal.
+async fn node_blame(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    Query(params): Query<NamespaceParams>,
+) -> Result<Json<service::NodeBlame>, ApiError> {
+    let node_id = NodeId {
+        id,
+        namespace: params.namespace,
+    };
+    Ok(Json(service::node_blame(&state, &actor, &node_id).await?))
+}
 
-    Ok(Json(node).into_response())
+/// `GET /nodes/:id/proposals` — the applied proposals that shaped a node, oldest first.
+async fn node_proposals(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    Query(params): Query<NamespaceParams>,
+) -> Result<Json<service::NodeProposals>, ApiError> {
+    let node_id = NodeId {
+        id,
+        namespace: params.namespace,
+    };
+    Ok(Json(
+        service::node_proposals(&state, &actor, &node_id).await?,
+    ))
 }
 
 // --- Provenance ---
 
+/// `GET /nodes/:id/provenance` — audit events of a node (`?namespace=`) or proposal.
 async fn get_provenance(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
+    Query(params): Query<NamespaceParams>,
 ) -> Result<Json<ProvenanceResponse>, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
-
-    // Collect all audit events for this resource
-    let events = state
-        .store
-        .query_audit(None, None, Some(&id), None, None, Some(1000), None)
-        .await?;
+    // Node audit events name the node by its key.
+    let id = NodeId {
+        id,
+        namespace: params.namespace,
+    }
+    .key();
+    let events = service::get_provenance(&state, &actor, &id).await?;
 
     Ok(Json(ProvenanceResponse {
         resource_id: id,
@@ -324,99 +505,173 @@ async fn get_provenance(
     }))
 }
 
+// --- Context pack ---
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ContextPackParams {
+    pub task: String,
+    pub budget_tokens: Option<usize>,
+    /// Comma-separated tags to favour.
+    pub tags: Option<String>,
+    /// `markdown` (default) or `json`.
+    pub format: Option<String>,
+}
+
+/// `GET /context-pack?task=...&budget_tokens=N` — relevant accepted nodes for an LLM
+/// prompt, within a token budget. The included nodes are audited.
+async fn context_pack(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<ContextPackParams>,
+) -> Result<axum::response::Response, ApiError> {
+    let tags: Vec<String> = params
+        .tags
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect();
+    let budget = params
+        .budget_tokens
+        .unwrap_or(crate::context_pack::DEFAULT_BUDGET_TOKENS);
+    let pack = service::context_pack(&state, &actor, &params.task, &tags, budget).await?;
+    match params.format.as_deref().unwrap_or("markdown") {
+        "json" => Ok(Json(pack).into_response()),
+        "markdown" => Ok((
+            [(
+                axum::http::header::CONTENT_TYPE,
+                "text/markdown; charset=utf-8",
+            )],
+            pack.to_markdown(),
+        )
+            .into_response()),
+        other => Err(ApiError::Invalid(format!(
+            "format must be markdown or json, got '{}'",
+            other
+        ))),
+    }
+}
+
 // --- Proposal routes ---
 
 #[derive(Debug, serde::Deserialize)]
 pub struct ProposalListParams {
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    /// Comma-separated proposal fields to return (see `api::projection`).
+    pub fields: Option<String>,
 }
 
 async fn list_proposals(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Query(params): Query<ProposalListParams>,
-) -> Result<Json<ProposalListResponse>, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
-
-    let full = state.store.get_open_proposals().await?;
-    let total = full.len() as u64;
-    let limit = params.limit.unwrap_or(50).min(1000);
-    let offset = (params.offset.unwrap_or(0) as usize).min(full.len());
-    let end = (offset + limit as usize).min(full.len());
-    let proposals = full[offset..end].to_vec();
-    let has_more = end < full.len();
-    Ok(Json(ProposalListResponse {
-        proposals,
-        total,
-        limit,
-        offset: offset as u32,
-        has_more,
-    }))
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let fields = Fields::parse(params.fields.as_deref())?;
+    let body = service::list_open_proposals(&state, &actor, params.limit, params.offset).await?;
+    Ok(match fields {
+        Some(fields) => {
+            let body = fields.apply(&body, "proposals");
+            etag::conditional(&headers, etag::body_etag(&body), body)
+        }
+        None => etag::conditional(&headers, etag::body_etag(&body), body),
+    })
 }
 
 async fn create_proposal(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
-    Json(proposal): Json<Proposal>,
+    StrictJson(proposal): StrictJson<Proposal>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Contributor)?;
-
-    // Policy: evaluate on create
-    let violations = policy::evaluate_on_create(&proposal, actor_type_str(&actor), &state.policies);
-    if !violations.is_empty() {
-        let event = AuditEvent::new(
-            &actor.actor_id,
-            actor_type_str(&actor),
-            AuditAction::PolicyEvaluated,
-            &proposal.id,
-            AuditOutcome::PolicyViolation,
-        )
-        .with_details(serde_json::json!({ "violations": violations }));
-        let _ = state.store.append_audit(event).await;
-        return Err(ApiError::PolicyViolation(violations));
-    }
-
-    let proposal_id = proposal.id.clone();
-    state.store.create_proposal(proposal).await?;
-
-    let event = AuditEvent::new(
-        &actor.actor_id,
-        actor_type_str(&actor),
-        AuditAction::ProposalCreated,
-        &proposal_id,
-        AuditOutcome::Success,
-    );
-    let _ = state.store.append_audit(event).await;
-    publish_event(&state.event_bus, "proposal_updated", &proposal_id, &actor);
+    let created = service::create_proposal(&state, &actor, proposal).await?;
+    let node_ids: Vec<&NodeId> = created
+        .operations
+        .iter()
+        .filter_map(|op| match op {
+            crate::types::Operation::Create { node, .. } => Some(&node.id),
+            _ => None,
+        })
+        .collect();
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "ok": true, "id": created.id, "nodeIds": node_ids })),
+    ))
+}
 
-    Ok((StatusCode::CREATED, Json(serde_json::json!({ "ok": true }))))
+#[derive(Debug, serde::Deserialize)]
+pub struct GetProposalParams {
+    /// Any of `reviews`, `comments`, `conflicts` (see `api::projection`).
+    pub include: Option<String>,
 }
 
 async fn get_proposal(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
-) -> Result<Json<Proposal>, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
-
-    let proposal = state
-        .store
-        .get_proposal(&id)
-        .await?
-        .ok_or_else(|| ApiError::NotFound(format!("proposal {} not found", id)))?;
-    Ok(Json(proposal))
+    Query(params): Query<GetProposalParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let include = projection::parse_include(params.include.as_deref(), service::PROPOSAL_INCLUDES)?;
+    let proposal = service::get_proposal(&state, &actor, &id).await?;
+    if include.is_empty() {
+        return Ok(etag::conditional(
+            &headers,
+            etag::body_etag(&proposal),
+            proposal,
+        ));
+    }
+    let included = service::proposal_includes(&state, &actor, &id, &include).await?;
+    let body = projection::with_included(&proposal, included);
+    Ok(etag::conditional(&headers, etag::body_etag(&body), body))
 }
 
 async fn update_proposal(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
-    Json(updates): Json<serde_json::Value>,
+    StrictJson(mut patch): StrictJson<ProposalPatch>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Contributor)?;
+    service::require_route(&state, &actor, "PATCH /proposals/:id", Role::Contributor)?;
+    if patch.metadata.as_ref().is_some_and(|m| m.forge.is_some()) {
+        return Err(ApiError::Invalid(
+            "metadata.forge is set by POST /proposals/:id/forge and forge webhooks".to_string(),
+        ));
+    }
+    if patch
+        .metadata
+        .as_ref()
+        .is_some_and(|m| m.conflict_resolutions.is_some())
+    {
+        return Err(ApiError::Invalid(
+            "metadata.conflictResolutions is set by POST /proposals/:id/resolve-conflicts"
+                .to_string(),
+        ));
+    }
 
-    state.store.update_proposal(&id, updates).await?;
+    let existing = service::get_proposal(&state, &actor, &id).await?;
+    service::stamper(&state)
+        .update(&mut patch, &existing)
+        .map_err(ApiError::Invalid)?;
+    let metadata = patch.metadata.get_or_insert_with(Default::default);
+    let mut modified_by = metadata.modified_by.take().unwrap_or_default();
+    rbac::attribute(&actor, "metadata.modifiedBy", &mut modified_by)?;
+    metadata.modified_by = Some(modified_by);
+
+    // Refuse a status the state machine does not allow, then the policies, before writing.
+    let mut patched = existing.clone();
+    crate::store::lifecycle::apply_update(&mut patched, &patch)?;
+    let reviews = state.store.get_review_history(&id).await?;
+    let violations = policy::evaluate_on_update(
+        &patched,
+        existing.status,
+        &reviews,
+        actor_type_str(&actor),
+        &state.runtime.policies.get(),
+    );
+    service::check_policies(&state, &actor, &id, patched.workspace(), violations).await?;
 
     let event = AuditEvent::new(
         &actor.actor_id,
@@ -425,174 +680,241 @@ async fn update_proposal(
         &id,
         AuditOutcome::Success,
     );
-    let _ = state.store.append_audit(event).await;
-    publish_event(&state.event_bus, "proposal_updated", &id, &actor);
+    let added = service::comments_added(
+        &actor,
+        &id,
+        existing.comments.as_deref().unwrap_or_default(),
+        patched.comments.as_deref().unwrap_or_default(),
+    );
+    let mut batch = WriteBatch::new()
+        .update_proposal(&id, patch)
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ProposalUpdated {
+                status: patched.status,
+            },
+            &id,
+            &actor,
+        ));
+    for event in added {
+        batch = batch.audit(event);
+    }
+    service::execute(&state, batch.in_workspace(patched.workspace())).await?;
 
     Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))))
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    pub proposal_ids: Vec<String>,
+}
+
+/// `POST /proposals/merge` — preview how proposals combine; nothing is written.
+async fn merge_proposals(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(body): StrictJson<MergeRequest>,
+) -> Result<Json<crate::types::MergeResult>, ApiError> {
+    Ok(Json(
+        service::merge_proposals(&state, &actor, &body.proposal_ids).await?,
+    ))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct QuarantineParams {
+    pub workspace: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// `GET /proposals/quarantine` — agent proposals awaiting triage.
+async fn list_quarantined_proposals(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<QuarantineParams>,
+) -> Result<Json<ProposalListResponse>, ApiError> {
+    Ok(Json(
+        service::list_quarantined_proposals(
+            &state,
+            &actor,
+            params.workspace.as_deref(),
+            params.limit,
+            params.offset,
+        )
+        .await?,
+    ))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriageRequest {
+    #[serde(default)]
+    pub proposal_ids: Option<Vec<String>>,
+    /// Triage this workspace's whole queue when `proposalIds` is not given.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    pub action: crate::types::TriageAction,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// `POST /proposals/triage` — release quarantined proposals into review, or reject them.
+async fn triage_proposals(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(body): StrictJson<TriageRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let results = service::triage_proposals(
+        &state,
+        &actor,
+        body.proposal_ids,
+        body.workspace.as_deref(),
+        body.action,
+        body.reason,
+    )
+    .await?;
+    Ok(Json(serde_json::json!({ "results": results })))
+}
+
 async fn get_review_history(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
 ) -> Result<Json<Vec<Review>>, ApiError> {
-    rbac::require_role(&actor, Role::Reader)?;
-
-    let reviews = state.store.get_review_history(&id).await?;
-    Ok(Json(reviews))
+    Ok(Json(
+        service::get_review_history(&state, &actor, &id).await?,
+    ))
 }
 
 async fn submit_review(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
-    Json(review): Json<Review>,
+    StrictJson(review): StrictJson<Review>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Reviewer)?;
-    rbac::reject_agent(&actor, "submit review")?;
-
-    if review.proposal_id != id {
-        return Err(ApiError::Invalid("proposal_id mismatch".to_string()));
-    }
-
-    state.store.submit_review(review).await?;
-
-    let event = AuditEvent::new(
-        &actor.actor_id,
-        actor_type_str(&actor),
-        AuditAction::ReviewSubmitted,
-        &id,
-        AuditOutcome::Success,
-    );
-    let _ = state.store.append_audit(event).await;
-    publish_event(&state.event_bus, "review_submitted", &id, &actor);
-
-    // Policy: evaluate on review for multi-approval
-    let proposal = state.store.get_proposal(&id).await?;
-    if let Some(proposal) = proposal {
-        let reviews = state.store.get_review_history(&id).await?;
-        let (new_status, _violations) =
-            policy::evaluate_on_review(&proposal, &reviews, &state.policies);
-        if let Some(status) = new_status {
-            let status_str = match status {
-                crate::types::ProposalStatus::Accepted => "accepted",
-                crate::types::ProposalStatus::Rejected => "rejected",
-                _ => return Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true })))),
-            };
-            let _ = state
-                .store
-                .update_proposal(&id, serde_json::json!({ "status": status_str }))
-                .await;
-
-            let event = AuditEvent::new(
-                &actor.actor_id,
-                actor_type_str(&actor),
-                AuditAction::PolicyEvaluated,
-                &id,
-                AuditOutcome::Success,
-            )
-            .with_details(serde_json::json!({ "newStatus": status_str }));
-            let _ = state.store.append_audit(event).await;
-        }
-    }
-
+    service::submit_review(&state, &actor, &id, review).await?;
     Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))))
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplyBody {
     #[serde(default)]
     pub applied_by: Option<String>,
+    /// Break-glass apply (Admins only, see `service::emergency_apply`).
+    #[serde(default)]
+    pub emergency: bool,
+    /// Required with `emergency`.
+    #[serde(default)]
+    pub justification: Option<String>,
 }
 
 async fn apply_proposal(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
-    body: Option<Json<ApplyBody>>,
+    OptionalJson(body): OptionalJson<ApplyBody>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Applier)?;
-    rbac::reject_agent(&actor, "apply proposal")?;
-
-    // Policy: evaluate on apply
-    let proposal = state.store.get_proposal(&id).await?;
-    if let Some(ref proposal) = proposal {
-        let violations =
-            policy::evaluate_on_apply(proposal, actor_type_str(&actor), &state.policies);
-        if !violations.is_empty() {
-            let event = AuditEvent::new(
-                &actor.actor_id,
-                actor_type_str(&actor),
-                AuditAction::PolicyEvaluated,
-                &id,
-                AuditOutcome::PolicyViolation,
-            )
-            .with_details(serde_json::json!({ "violations": violations }));
-            let _ = state.store.append_audit(event).await;
-            return Err(ApiError::PolicyViolation(violations));
-        }
+    let body = body.unwrap_or_default();
+    if !body.emergency {
+        service::apply_proposal(&state, &actor, &id, body.applied_by).await?;
+        return Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))));
     }
+    let justification = body.justification.unwrap_or_default();
+    let follow_up =
+        service::emergency_apply(&state, &actor, &id, body.applied_by, &justification).await?;
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "ok": true, "followUp": follow_up.id })),
+    ))
+}
 
-    let applied_by = body
-        .and_then(|b| b.applied_by.clone())
-        .unwrap_or_else(|| actor.actor_id.clone());
-    state.store.apply_proposal(&id, &applied_by).await?;
-
-    let event = AuditEvent::new(
-        &actor.actor_id,
-        actor_type_str(&actor),
-        AuditAction::ProposalApplied,
-        &id,
-        AuditOutcome::Success,
-    );
-    let _ = state.store.append_audit(event).await;
-    publish_event(&state.event_bus, "proposal_updated", &id, &actor);
-
-    Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))))
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct WithdrawBody {
+    /// Required when an Admin withdraws someone else's proposal.
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 async fn withdraw_proposal(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Path(id): Path<String>,
+    OptionalJson(body): OptionalJson<WithdrawBody>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    let reason = body.and_then(|b| b.reason);
+    service::withdraw_proposal(&state, &actor, &id, reason).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))))
+}
+
+async fn reset_store(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Contributor)?;
+    service::require_route(&state, &actor, "POST /reset", Role::Admin)?;
 
-    state.store.withdraw_proposal(&id).await?;
+    state.store.reset().await?;
 
     let event = AuditEvent::new(
         &actor.actor_id,
         actor_type_str(&actor),
-        AuditAction::ProposalWithdrawn,
-        &id,
+        AuditAction::StoreReset,
+        "store",
         AuditOutcome::Success,
     );
-    let _ = state.store.append_audit(event).await;
-    publish_event(&state.event_bus, "proposal_updated", &id, &actor);
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ConfigChanged {
+                target: "store".to_string(),
+            },
+            "store",
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
 
     Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))))
 }
 
-async fn reset_store(
+/// Load fixture data (a store bundle) into the store. Admin only, and only when
+/// `server.allow_seed` / `TRUTHTLAYER_ALLOW_SEED` is enabled (never in production).
+async fn seed_store(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
-) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Admin)?;
+    StrictJson(bundle): StrictJson<StoreBundle>,
+) -> Result<(StatusCode, Json<ImportSummary>), ApiError> {
+    service::require_route(&state, &actor, "POST /admin/seed", Role::Admin)?;
+    if !state.runtime.config.get().allow_seed {
+        return Err(ApiError::Forbidden(Forbidden(
+            "seeding is disabled; set server.allow_seed or TRUTHTLAYER_ALLOW_SEED=true \
+             (non-production environments only)"
+                .to_string(),
+        )));
+    }
 
-    state.store.reset().await?;
+    let summary = state.store.import_bundle(bundle).await?;
 
     let event = AuditEvent::new(
         &actor.actor_id,
         actor_type_str(&actor),
-        AuditAction::StoreReset,
+        AuditAction::StoreSeeded,
         "store",
         AuditOutcome::Success,
-    );
-    let _ = state.store.append_audit(event).await;
-    publish_event(&state.event_bus, "config_changed", "store", &actor);
+    )
+    .with_details(AuditDetails::Seeded(summary.clone()));
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ConfigChanged {
+                target: "store".to_string(),
+            },
+            "store",
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
 
-    Ok((StatusCode::OK, Json(serde_json::json!({ "ok": true }))))
+    Ok((StatusCode::OK, Json(summary)))
 }
 
 // --- Audit routes ---
@@ -613,81 +935,111 @@ async fn query_audit(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Query(params): Query<AuditQueryParams>,
-) -> Result<Json<Vec<AuditEvent>>, ApiError> {
-    rbac::require_role(&actor, Role::Admin)?;
-
-    let events = state
-        .store
-        .query_audit(
-            params.actor.as_deref(),
-            params.action.as_deref(),
-            params.resource_id.as_deref(),
-            params.from.as_deref(),
-            params.to.as_deref(),
-            params.limit,
-            params.offset,
-        )
-        .await?;
-    Ok(Json(events))
+) -> Result<Json<AuditQueryResult>, ApiError> {
+    Ok(Json(service::query_audit(&state, &actor, &params).await?))
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct ExportParams {
     pub format: Option<String>,
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub resource_id: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Only events recorded in this workspace (events naming none are left out).
+    pub workspace: Option<String>,
+}
+
+impl ExportParams {
+    fn keeps(&self, event: &AuditEvent) -> bool {
+        self.workspace
+            .as_deref()
+            .is_none_or(|ws| event.workspace_id.as_deref() == Some(ws))
+    }
 }
 
+/// Audit events read from the store per chunk of a CSV export.
+const AUDIT_EXPORT_PAGE: u32 = 1000;
+
+/// `GET /audit/export?format=json|csv` with the filters of `GET /audit`, and `workspace`.
+/// CSV is streamed a page of events at a time rather than built in memory.
 async fn export_audit(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
     Query(params): Query<ExportParams>,
 ) -> Result<axum::response::Response, ApiError> {
-    rbac::require_role(&actor, Role::Admin)?;
+    service::require_route(&state, &actor, "GET /audit/export", Role::Admin)?;
+
+    let format = params.format.clone().unwrap_or_else(|| "json".to_string());
+    if format != "csv" {
+        let mut events = state
+            .store
+            .query_audit(
+                params.actor.as_deref(),
+                params.action.as_deref(),
+                params.resource_id.as_deref(),
+                params.from.as_deref(),
+                params.to.as_deref(),
+                Some(100_000),
+                None,
+            )
+            .await?
+            .events;
+        events.retain(|e| params.keeps(e));
+        service::redact_audit(&state, &actor, &mut events).await?;
+        return Ok((StatusCode::OK, Json(events)).into_response());
+    }
 
-    let events = state
-        .store
-        .query_audit(None, None, None, None, None, Some(100_000), None)
-        .await?;
-
-    let format = params.format.as_deref().unwrap_or("json");
-    match format {
-        "csv" => {
-            let mut csv =
-                String::from("event_id,timestamp,actor_id,actor_type,action,resource_id,outcome\n");
-            for e in &events {
-                let action_str = serde_json::to_string(&e.action)
-                    .unwrap_or_default()
-                    .replace('"', "");
-                let outcome_str = serde_json::to_string(&e.outcome)
-                    .unwrap_or_default()
-                    .replace('"', "");
-                csv.push_str(&format!(
-                    "{},{},{},{},{},{},{}\n",
-                    e.event_id,
-                    e.timestamp,
-                    e.actor_id,
-                    e.actor_type,
-                    action_str,
-                    e.resource_id,
-                    outcome_str
-                ));
+    // Each step reads and writes one page; the offset is None once the last one is out.
+    let header = futures_util::stream::once(async {
+        Ok::<_, std::io::Error>(AuditEvent::CSV_HEADER.to_string())
+    });
+    let rows = futures_util::stream::unfold(Some(0u32), move |offset| {
+        let (state, actor, filters) = (state.clone(), actor.clone(), params.clone());
+        async move {
+            let offset = offset?;
+            let page = match state
+                .store
+                .query_audit(
+                    filters.actor.as_deref(),
+                    filters.action.as_deref(),
+                    filters.resource_id.as_deref(),
+                    filters.from.as_deref(),
+                    filters.to.as_deref(),
+                    Some(AUDIT_EXPORT_PAGE),
+                    Some(offset),
+                )
+                .await
+            {
+                Ok(page) if page.events.is_empty() => return None,
+                Ok(page) => page,
+                Err(e) => return Some((Err(std::io::Error::other(e.to_string())), None)),
+            };
+            let next = page.has_more.then_some(offset + page.events.len() as u32);
+            let mut events = page.events;
+            events.retain(|e| filters.keeps(e));
+            if let Err(e) = service::redact_audit(&state, &actor, &mut events).await {
+                let (_, body) = e.status_and_body();
+                return Some((Err(std::io::Error::other(body.to_string())), None));
             }
-            Ok((
-                StatusCode::OK,
-                [
-                    ("content-type", "text/csv"),
-                    ("content-disposition", "attachment; filename=audit.csv"),
-                ],
-                csv,
-            )
-                .into_response())
+            Some((Ok(events.iter().map(AuditEvent::csv_row).collect()), next))
         }
-        _ => Ok((StatusCode::OK, Json(events)).into_response()),
-    }
+    });
+    Ok((
+        StatusCode::OK,
+        [
+            ("content-type", "text/csv"),
+            ("content-disposition", "attachment; filename=audit.csv"),
+        ],
+        axum::body::Body::from_stream(header.chain(rows)),
+    )
+        .into_response())
 }
 
 // --- DSAR (Data Subject Access Request) routes ---
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct DsarParams {
     pub subject: String,
 }
@@ -698,7 +1050,7 @@ async fn dsar_export(
     Extension(actor): Extension<ActorContext>,
     Query(params): Query<DsarParams>,
 ) -> Result<Json<DsarExportResponse>, ApiError> {
-    rbac::require_role(&actor, Role::Admin)?;
+    service::require_route(&state, &actor, "GET /admin/dsar/export", Role::Admin)?;
 
     let audit_events = state
         .store
@@ -711,7 +1063,20 @@ async fn dsar_export(
             Some(100_000),
             None,
         )
-        .await?;
+        .await?
+        .events;
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::DsarExport,
+        &params.subject,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::Dsar {
+        subject: params.subject.clone(),
+        audit_events: audit_events.len() as u64,
+    });
+    service::audit(&state, event).await?;
 
     Ok(Json(DsarExportResponse {
         subject: params.subject,
@@ -725,19 +1090,27 @@ async fn dsar_export(
 async fn dsar_erase(
     State(state): State<AppState>,
     Extension(actor): Extension<ActorContext>,
-    Json(params): Json<DsarParams>,
+    StrictJson(params): StrictJson<DsarParams>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    rbac::require_role(&actor, Role::Admin)?;
+    service::require_route(&state, &actor, "POST /admin/dsar/erase", Role::Admin)?;
 
+    let recorded = state
+        .store
+        .query_audit(Some(&params.subject), None, None, None, None, Some(1), None)
+        .await?
+        .total;
     let event = AuditEvent::new(
         &actor.actor_id,
         actor_type_str(&actor),
-        AuditAction::RoleChanged, // repurpose for DSAR action
+        AuditAction::DsarErase,
         &params.subject,
         AuditOutcome::Success,
     )
-    .with_details(serde_json::json!({ "dsar": "erase", "subject": params.subject }));
-    let _ = state.store.append_audit(event).await;
+    .with_details(AuditDetails::Dsar {
+        subject: params.subject.clone(),
+        audit_events: recorded,
+    });
+    service::audit(&state, event).await?;
 
     Ok((
         StatusCode::OK,
@@ -748,10 +1121,74 @@ async fn dsar_erase(
     ))
 }
 
-// --- Response types ---
+/// `GET /admin/config` — effective configuration (startup values plus reloaded settings)
+/// with secrets redacted, the active policy rules, and when config was last reloaded.
+async fn admin_config(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/config", Role::Admin)?;
+
+    Ok(Json(serde_json::json!({
+        "config": state.runtime.config.get().redacted(),
+        "policies": *state.runtime.policies.get(),
+        "reloadedAt": *state.runtime.reloaded_at.get(),
+    })))
+}
 
-#[derive(serde::Serialize)]
-#[serde(rename_all = "camelCase")]
+/// `GET /admin/store/status` — record counts, approximate memory use and (memory
+/// backend) the configured limits, with the backend name.
+async fn admin_store_status(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/store/status", Role::Admin)?;
+
+    let status = state.store.status().await?;
+    let mut body = serde_json::to_value(status).unwrap_or_default();
+    body["backend"] = serde_json::json!(state.server_info.storage_backend);
+    Ok(Json(body))
+}
+
+/// `POST /admin/store/compact` — prune closed proposals past retention, remove orphaned
+/// files and defragment the audit log (see `store::compact`). Optional body:
+/// `{ "proposalRetentionDays": N }`.
+async fn admin_store_compact(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    OptionalJson(body): OptionalJson<CompactOptions>,
+) -> Result<Json<CompactReport>, ApiError> {
+    service::require_route(&state, &actor, "POST /admin/store/compact", Role::Admin)?;
+
+    let options = body.unwrap_or_default();
+    let report = state.store.compact(&options).await?;
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::StoreCompacted,
+        "store",
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::Compacted(report.clone()));
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::ConfigChanged {
+                target: "store".to_string(),
+            },
+            "store",
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
+
+    Ok(Json(report))
+}
+
+// --- Response types ---
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NodeQueryResultResponse {
     pub nodes: Vec<crate::types::ContextNode>,
     pub total: u64,
@@ -789,13 +1226,17 @@ pub struct DsarExportResponse {
 pub enum ApiError {
     NotFound(String),
     Invalid(String),
-    Store(crate::store::context_store::StoreError),
+    Store(StoreError),
     Forbidden(Forbidden),
     PolicyViolation(Vec<policy::PolicyViolation>),
+    /// The server is in read-only mode (see [`crate::read_only`]).
+    ReadOnly(String),
+    /// The directory refuses the actor: deprovisioned, or its token was revoked.
+    Unauthorized(String),
 }
 
-impl From<crate::store::context_store::StoreError> for ApiError {
-    fn from(e: crate::store::context_store::StoreError) -> Self {
+impl From<StoreError> for ApiError {
+    fn from(e: StoreError) -> Self {
         ApiError::Store(e)
     }
 }
@@ -806,26 +1247,81 @@ impl From<Forbidden> for ApiError {
     }
 }
 
+impl ApiError {
+    /// HTTP status and `application/problem+json` body, as the REST API returns them.
+    /// `type` is `urn:truthlayer:error:<code>`, stable per error kind (per
+    /// `StoreErrorCode` for store errors); `error` repeats `detail`.
+    pub fn status_and_body(&self) -> (StatusCode, serde_json::Value) {
+        let mut extra = serde_json::Map::new();
+        let (status, code, detail) = match self {
+            ApiError::NotFound(m) => (StatusCode::NOT_FOUND, "not_found", m.clone()),
+            ApiError::Invalid(m) => (StatusCode::BAD_REQUEST, "invalid", m.clone()),
+            ApiError::Store(s) => {
+                extra.insert("code".to_string(), s.code.as_str().into());
+                extra.insert("retryable".to_string(), s.retryable.into());
+                let detail = if s.redacted {
+                    // Paths and OS errors go to the log, not to the client.
+                    tracing::error!(error = %s, resource = ?s.resource, "storage error");
+                    format!("{}: see the server log", s.code.as_str())
+                } else {
+                    if let Some(resource) = &s.resource {
+                        extra.insert("resource".to_string(), resource.as_str().into());
+                    }
+                    s.to_string()
+                };
+                (store_status(s), s.code.as_str(), detail)
+            }
+            ApiError::Forbidden(f) => (StatusCode::FORBIDDEN, "forbidden", f.0.clone()),
+            ApiError::Unauthorized(m) => (StatusCode::UNAUTHORIZED, "unauthorized", m.clone()),
+            ApiError::PolicyViolation(violations) => {
+                extra.insert("violations".to_string(), serde_json::json!(violations));
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "policy_violation",
+                    "policy violation".to_string(),
+                )
+            }
+            ApiError::ReadOnly(m) => {
+                extra.insert("readOnly".to_string(), true.into());
+                (StatusCode::SERVICE_UNAVAILABLE, "read_only", m.clone())
+            }
+        };
+        let body = crate::api::strict::error_body(status, code, detail, extra);
+        (status, body)
+    }
+}
+
+/// HTTP status of a store error. Retryable I/O failures are `503`; a held lock or lease
+/// is a `409` the client may retry.
+pub fn store_status(e: &StoreError) -> StatusCode {
+    match e.code {
+        StoreErrorCode::NotFound => StatusCode::NOT_FOUND,
+        StoreErrorCode::Conflict | StoreErrorCode::Locked => StatusCode::CONFLICT,
+        StoreErrorCode::Invalid | StoreErrorCode::InvalidTransition => StatusCode::BAD_REQUEST,
+        StoreErrorCode::CapacityExceeded => StatusCode::INSUFFICIENT_STORAGE,
+        StoreErrorCode::Io if e.retryable => StatusCode::SERVICE_UNAVAILABLE,
+        StoreErrorCode::Io | StoreErrorCode::Corrupt | StoreErrorCode::Internal => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let (status, body) = match &self {
-            ApiError::NotFound(m) => (StatusCode::NOT_FOUND, serde_json::json!({ "error": m })),
-            ApiError::Invalid(m) => (StatusCode::BAD_REQUEST, serde_json::json!({ "error": m })),
-            ApiError::Store(s) => (
-                match s {
-                    crate::store::context_store::StoreError::NotFound(_) => StatusCode::NOT_FOUND,
-                    crate::store::context_store::StoreError::Conflict(_) => StatusCode::CONFLICT,
-                    _ => StatusCode::INTERNAL_SERVER_ERROR,
-                },
-                serde_json::json!({ "error": s.to_string() }),
-            ),
-            ApiError::Forbidden(f) => (StatusCode::FORBIDDEN, serde_json::json!({ "error": f.0 })),
-            ApiError::PolicyViolation(violations) => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                serde_json::json!({ "error": "policy violation", "violations": violations }),
-            ),
-        };
-        (status, Json(body)).into_response()
+        let (status, body) = self.status_and_body();
+        let retry = matches!(&self, ApiError::Store(s) if s.retryable);
+        let mut response = (
+            status,
+            [(header::CONTENT_TYPE, crate::api::strict::PROBLEM_JSON)],
+            body.to_string(),
+        )
+            .into_response();
+        if retry {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, header::HeaderValue::from_static("1"));
+        }
+        response
     }
 }
 
@@ -839,15 +1335,42 @@ mod tests {
     use tower::ServiceExt;
 
     fn app() -> Router<()> {
-        let store = Arc::new(crate::store::InMemoryStore::new());
-        let policies = Arc::new(PolicyConfig::default());
-        let event_bus = crate::events::EventBus::new();
-        let r = router(store, policies, event_bus);
+        app_with_config(crate::config::ServerConfig::default())
+    }
+
+    fn app_with_config(config: crate::config::ServerConfig) -> Router<()> {
+        app_with_store(Arc::new(crate::store::InMemoryStore::new()), config)
+    }
+
+    fn app_with_store(
+        store: Arc<dyn ContextStore>,
+        config: crate::config::ServerConfig,
+    ) -> Router<()> {
         // In tests, inject a default ActorContext (simulates AUTH_DISABLED=true)
+        app_as(store, config, ActorContext::dev_default())
+    }
+
+    fn app_as(
+        store: Arc<dyn ContextStore>,
+        config: crate::config::ServerConfig,
+        actor: ActorContext,
+    ) -> Router<()> {
+        app_with_policies(store, config, Default::default(), actor)
+    }
+
+    fn app_with_policies(
+        store: Arc<dyn ContextStore>,
+        config: crate::config::ServerConfig,
+        policies: crate::policy::PolicyConfig,
+        actor: ActorContext,
+    ) -> Router<()> {
+        let runtime = RuntimeConfig::new(config, policies);
+        let event_bus = crate::events::EventBus::new();
+        let r = router(store, runtime, event_bus, ServerInfo::default());
         r.layer(axum::middleware::from_fn(
-            |mut req: Request<Body>, next: axum::middleware::Next| async move {
-                req.extensions_mut().insert(ActorContext::dev_default());
-                next.run(req).await
+            move |mut req: Request<Body>, next: axum::middleware::Next| {
+                req.extensions_mut().insert(actor.clone());
+                next.run(req)
             },
         ))
     }
@@ -866,6 +1389,46 @@ mod tests {
         assert_eq!(json.get("status").and_then(|v| v.as_str()), Some("ok"));
     }
 
+    #[tokio::test]
+    async fn webtransport_connect_requires_extended_connect() {
+        let plain = Request::builder()
+            .method("CONNECT")
+            .uri(crate::webtransport::WEBTRANSPORT_PATH)
+            .body(Body::empty())
+            .unwrap();
+        let res = app().oneshot(plain).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let mut extended = Request::builder()
+            .method("CONNECT")
+            .uri(crate::webtransport::WEBTRANSPORT_PATH)
+            .body(Body::empty())
+            .unwrap();
+        extended
+            .extensions_mut()
+            .insert(h3::ext::Protocol::WEB_TRANSPORT);
+        let res = app().oneshot(extended).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn version_returns_build_info() {
+        let app = app();
+        let req = Request::builder()
+            .uri("/version")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert!(json["gitCommit"].as_str().is_some());
+        assert!(json["buildTimestamp"].as_str().is_some());
+        assert_eq!(json["transports"], serde_json::json!(["h3"]));
+        assert_eq!(json["storageBackend"], "memory");
+    }
+
     #[tokio::test]
     async fn get_node_404_when_missing() {
         let app = app();
@@ -920,6 +1483,381 @@ mod tests {
         assert_eq!(res.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn get_proposal_includes_reviews_comments_and_conflicts() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-inc", "status": "open", "operations": []
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let comment: crate::types::Comment = serde_json::from_value(serde_json::json!({
+            "id": "c-1", "content": "Why now?", "author": "alice",
+            "createdAt": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        store.add_proposal_comment("p-inc", comment).await.unwrap();
+        let app = app_with_store(store, Default::default());
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let res = app
+            .clone()
+            .oneshot(get("/proposals/p-inc?include=reviews,comments,conflicts"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["id"], "p-inc");
+        assert!(json["included"]["reviews"].as_array().unwrap().is_empty());
+        assert_eq!(json["included"]["comments"][0]["content"], "Why now?");
+        assert!(json["included"]["conflicts"].is_object());
+
+        let res = app.clone().oneshot(get("/proposals/p-inc")).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json.get("included").is_none());
+
+        let res = app
+            .oneshot(get("/proposals/p-inc?include=author"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn strict_requests_refuse_unknown_fields() {
+        let proposal = serde_json::json!({
+            "id": "p-typo",
+            "status": "open",
+            "operations": [],
+            "propsalId": "p-typo",
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "dev-user",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "dev-user"
+            }
+        });
+        let create = || {
+            Request::builder()
+                .method("POST")
+                .uri("/proposals")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&proposal).unwrap()))
+                .unwrap()
+        };
+        // Off by default: serde ignores the typo.
+        let res = app().oneshot(create()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let strict = app_with_config(crate::config::ServerConfig {
+            strict_requests: true,
+            ..Default::default()
+        });
+        let res = strict.clone().oneshot(create()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["path"], "propsalId");
+        assert_eq!(json["error"], "propsalId: unknown field");
+
+        // Optional bodies may still be omitted.
+        let compact = Request::builder()
+            .method("POST")
+            .uri("/admin/store/compact")
+            .body(Body::empty())
+            .unwrap();
+        let res = strict.oneshot(compact).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn route_roles_let_reviewers_read_their_own_audit_trail() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        for (id, author) in [("p-mine", "rita"), ("p-other", "alice")] {
+            let proposal: Proposal = serde_json::from_value(serde_json::json!({
+                "id": id, "status": "open", "operations": [],
+                "metadata": { "createdBy": author }
+            }))
+            .unwrap();
+            store.create_proposal(proposal).await.unwrap();
+        }
+        let reviewer = ActorContext {
+            actor_id: "rita".to_string(),
+            actor_type: crate::auth::ActorType::Human,
+            roles: vec![Role::Reviewer],
+        };
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let defaults = app_as(store.clone(), Default::default(), reviewer.clone());
+        let res = defaults
+            .oneshot(get("/audit?resourceId=p-mine"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let mut config = crate::config::ServerConfig::default();
+        config
+            .route_roles
+            .0
+            .insert("GET /audit".to_string(), Role::Reviewer);
+        let app = app_as(store, config, reviewer);
+        let res = app
+            .clone()
+            .oneshot(get("/audit?resourceId=p-mine"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        for uri in ["/audit?resourceId=p-other", "/audit"] {
+            let res = app.clone().oneshot(get(uri)).await.unwrap();
+            assert_eq!(res.status(), StatusCode::FORBIDDEN, "{}", uri);
+        }
+        // Routes not configured keep their default role.
+        let res = app.oneshot(get("/admin/jobs")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn provenance_details_are_redacted_above_clearance() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-vault", "status": "open",
+            "operations": [{
+                "type": "create", "id": "op-1", "order": 1,
+                "node": {
+                    "id": { "id": "vault-keys" }, "type": "constraint", "status": "proposed",
+                    "content": "rotate with the master key 7f3a",
+                    "metadata": {
+                        "createdAt": "2026-01-01T00:00:00Z", "createdBy": "alice",
+                        "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "alice",
+                        "version": 1, "sensitivity": "restricted"
+                    }
+                }
+            }]
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let event = AuditEvent::new(
+            "alice",
+            "human",
+            crate::types::AuditAction::ProposalCreated,
+            "p-vault",
+            crate::types::AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::Withdrawal {
+            author: "alice".to_string(),
+            admin_override: true,
+            reason: Some("rotate with the master key 7f3a".to_string()),
+        });
+        store.append_audit(event).await.unwrap();
+
+        let agent = ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: crate::auth::ActorType::Agent,
+            roles: vec![Role::Reader],
+        };
+        let get = || {
+            Request::get("/nodes/p-vault/provenance")
+                .body(Body::empty())
+                .unwrap()
+        };
+        for (actor, redacted) in [(agent, true), (ActorContext::dev_default(), false)] {
+            let res = app_as(store.clone(), Default::default(), actor)
+                .oneshot(get())
+                .await
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            let provenance: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let details = &provenance["events"][0]["details"];
+            assert_eq!(details["redacted"] == true, redacted, "{}", details);
+            assert_eq!(details["reason"].is_null(), redacted);
+        }
+    }
+
+    #[tokio::test]
+    async fn provenance_of_deleted_nodes_stays_redacted() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let proposals: Vec<Proposal> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "p-vault", "status": "open",
+                "operations": [{
+                    "type": "create", "id": "op-1", "order": 1,
+                    "node": {
+                        "id": { "id": "vault-keys" }, "type": "constraint", "status": "accepted",
+                        "content": "rotate with the master key 7f3a",
+                        "metadata": {
+                            "createdAt": "2026-01-01T00:00:00Z", "createdBy": "alice",
+                            "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "alice",
+                            "version": 1, "sensitivity": "restricted"
+                        }
+                    }
+                }]
+            },
+            {
+                "id": "p-drop", "status": "open",
+                "operations": [{
+                    "type": "delete", "id": "op-1", "order": 1,
+                    "node_id": { "id": "vault-keys" }
+                }]
+            }
+        ]))
+        .unwrap();
+        for proposal in proposals {
+            let id = proposal.id.clone();
+            store
+                .execute(
+                    WriteBatch::new()
+                        .create_proposal(proposal)
+                        .apply_unreviewed(&id, "alice"),
+                )
+                .await
+                .unwrap();
+        }
+        let event = AuditEvent::new(
+            "alice",
+            "human",
+            crate::types::AuditAction::NodeDeleted,
+            "vault-keys",
+            crate::types::AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::Withdrawal {
+            author: "alice".to_string(),
+            admin_override: true,
+            reason: Some("rotate with the master key 7f3a".to_string()),
+        });
+        store.append_audit(event).await.unwrap();
+
+        let agent = ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: crate::auth::ActorType::Agent,
+            roles: vec![Role::Reader],
+        };
+        // In the trash, then purged: the label is gone with the node.
+        for purge in [false, true] {
+            if purge {
+                let purged = store.purge_trash("9999-01-01T00:00:00Z").await.unwrap();
+                assert_eq!(purged.len(), 1);
+            }
+            let res = app_as(store.clone(), Default::default(), agent.clone())
+                .oneshot(
+                    Request::get("/nodes/vault-keys/provenance")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            let provenance: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let details = &provenance["events"][0]["details"];
+            assert_eq!(details["redacted"], true, "purged: {}", purge);
+            assert_eq!(details["sensitivity"], "restricted");
+        }
+    }
+
+    #[tokio::test]
+    async fn proposal_authorship_comes_from_the_actor() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let agent = ActorContext {
+            actor_id: "agent-1".to_string(),
+            actor_type: crate::auth::ActorType::Agent,
+            roles: vec![Role::Contributor],
+        };
+        let app = app_as(store.clone(), Default::default(), agent);
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let claimed = serde_json::json!({
+            "id": "p-claimed", "status": "open", "operations": [],
+            "metadata": { "createdBy": "alice", "modifiedBy": "alice" }
+        });
+        let res = app
+            .clone()
+            .oneshot(send("POST", "/proposals", claimed))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let unclaimed = serde_json::json!({ "id": "p-own", "status": "open", "operations": [] });
+        let res = app
+            .clone()
+            .oneshot(send("POST", "/proposals", unclaimed))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let stored = store.get_proposal("p-own").await.unwrap().unwrap();
+        assert_eq!(stored.metadata.created_by, "agent-1");
+        assert_eq!(stored.metadata.modified_by, "agent-1");
+
+        let patch = serde_json::json!({ "metadata": { "modified_by": "alice" } });
+        let res = app
+            .clone()
+            .oneshot(send("PATCH", "/proposals/p-own", patch))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let misspelt = serde_json::json!({ "stauts": "withdrawn" });
+        let res = app
+            .clone()
+            .oneshot(send("PATCH", "/proposals/p-own", misspelt))
+            .await
+            .unwrap();
+        assert!(res.status().is_client_error());
+        let rationale = serde_json::json!({ "metadata": { "rationale": "clearer wording" } });
+        let res = app
+            .clone()
+            .oneshot(send("PATCH", "/proposals/p-own", rationale))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let stored = store.get_proposal("p-own").await.unwrap().unwrap();
+        assert_eq!(
+            stored.metadata.rationale.as_deref(),
+            Some("clearer wording")
+        );
+        assert_eq!(stored.status, crate::types::ProposalStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn proposals_credited_to_another_actor_are_forbidden() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let app = app_with_store(store.clone(), Default::default());
+        let create = |id: &str, created_by: &str| {
+            let proposal = serde_json::json!({
+                "id": id, "status": "open", "operations": [],
+                "metadata": { "createdBy": created_by, "modifiedBy": "dev-user" }
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/proposals")
+                .header("content-type", "application/json")
+                .body(Body::from(proposal.to_string()))
+                .unwrap()
+        };
+
+        let res = app
+            .clone()
+            .oneshot(create("p-other", "integration"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("metadata.createdBy 'integration'"));
+        assert!(store.get_proposal("p-other").await.unwrap().is_none());
+
+        let res = app.oneshot(create("p-mine", "dev-user")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
     #[tokio::test]
     async fn create_proposal_then_get_and_patch() {
         let app = app();
@@ -929,9 +1867,9 @@ mod tests {
             "operations": [],
             "metadata": {
                 "createdAt": "2026-01-01T00:00:00Z",
-                "createdBy": "test",
+                "createdBy": "dev-user",
                 "modifiedAt": "2026-01-01T00:00:00Z",
-                "modifiedBy": "test"
+                "modifiedBy": "dev-user"
             }
         });
         let create_req = Request::builder()
@@ -954,16 +1892,101 @@ mod tests {
         assert_eq!(got["id"], "p-1");
         assert_eq!(got["status"], "open");
 
-        let patch_req = Request::builder()
+        let patch = |status: &str| {
+            Request::builder()
+                .method("PATCH")
+                .uri("/proposals/p-1")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({ "status": status })).unwrap(),
+                ))
+                .unwrap()
+        };
+        // Accepting by PATCH needs the approvals a review would.
+        let patch_res = app.clone().oneshot(patch("accepted")).await.unwrap();
+        assert_eq!(patch_res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = patch_res.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("min_approvals"));
+
+        // So does rejecting it, on reviews of any kind.
+        let patch_res = app.clone().oneshot(patch("rejected")).await.unwrap();
+        assert_eq!(patch_res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let patch_res = app.clone().oneshot(patch("applied")).await.unwrap();
+        assert_eq!(patch_res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn patch_does_not_withdraw_or_reopen() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        for (id, status) in [("p-open", "open"), ("p-gone", "withdrawn")] {
+            let proposal = serde_json::json!({
+                "id": id, "status": status, "operations": [],
+                "metadata": { "createdBy": "alice" }
+            });
+            store
+                .create_proposal(serde_json::from_value(proposal).unwrap())
+                .await
+                .unwrap();
+        }
+        let bob = ActorContext {
+            actor_id: "bob".to_string(),
+            actor_type: crate::auth::ActorType::Human,
+            roles: vec![Role::Contributor],
+        };
+        let app = app_as(store.clone(), Default::default(), bob);
+        let patch = |id: &str, status: &str| {
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/proposals/{}", id))
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "status": status }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let res = app
+            .clone()
+            .oneshot(patch("p-open", "withdrawn"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("/withdraw"));
+        let stored = store.get_proposal("p-open").await.unwrap().unwrap();
+        assert_eq!(stored.status, crate::types::ProposalStatus::Open);
+
+        let res = app.oneshot(patch("p-gone", "open")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let stored = store.get_proposal("p-gone").await.unwrap().unwrap();
+        assert_eq!(stored.status, crate::types::ProposalStatus::Withdrawn);
+    }
+
+    #[tokio::test]
+    async fn patch_refuses_misspelt_fields() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let proposal = serde_json::json!({
+            "id": "p-1", "status": "open", "operations": [],
+            "metadata": { "createdBy": "dev-user" }
+        });
+        store
+            .create_proposal(serde_json::from_value(proposal).unwrap())
+            .await
+            .unwrap();
+        let app = app_with_store(store.clone(), Default::default());
+        let req = Request::builder()
             .method("PATCH")
             .uri("/proposals/p-1")
             .header("content-type", "application/json")
-            .body(Body::from(
-                serde_json::to_vec(&serde_json::json!({ "status": "accepted" })).unwrap(),
-            ))
+            .body(Body::from(r#"{"Status":"accepted"}"#))
             .unwrap();
-        let patch_res = app.clone().oneshot(patch_req).await.unwrap();
-        assert_eq!(patch_res.status(), StatusCode::OK);
+
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("unknown field `Status`"));
+        let stored = store.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(stored.status, crate::types::ProposalStatus::Open);
     }
 
     #[tokio::test]
@@ -980,7 +2003,7 @@ mod tests {
             "id": "p-apply",
             "status": "accepted",
             "operations": [{"id":"op1","order":1,"type":"create","node": node}],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1014,6 +2037,205 @@ mod tests {
         assert_eq!(apply_res.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn store_errors_are_problem_json_per_code() {
+        use crate::store::context_store::StoreErrorCode as C;
+        let io = |kind| StoreError::io("read /data/nodes/n1.json", std::io::Error::from(kind));
+        let cases = [
+            (StoreError::not_found("node n1"), 404, "not_found"),
+            (StoreError::conflict("stale"), 409, "conflict"),
+            (StoreError::invalid("bad"), 400, "invalid"),
+            (
+                StoreError::new(C::InvalidTransition, "closed"),
+                400,
+                "invalid_transition",
+            ),
+            (
+                StoreError::capacity_exceeded("full"),
+                507,
+                "capacity_exceeded",
+            ),
+            (StoreError::locked("held"), 409, "locked"),
+            (io(std::io::ErrorKind::TimedOut), 503, "io"),
+            (io(std::io::ErrorKind::PermissionDenied), 500, "io"),
+            (
+                StoreError::corrupt(
+                    "/data/nodes/n1.json",
+                    serde_json::from_str::<u8>("x").unwrap_err(),
+                ),
+                500,
+                "corrupt",
+            ),
+            (StoreError::internal("boom"), 500, "internal"),
+        ];
+        for (error, status, code) in cases {
+            let res = ApiError::Store(error).into_response();
+            assert_eq!(res.status().as_u16(), status, "{}", code);
+            assert_eq!(
+                res.headers()[header::CONTENT_TYPE],
+                crate::api::strict::PROBLEM_JSON
+            );
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["type"], format!("urn:truthlayer:error:{}", code));
+            assert_eq!(body["status"], status);
+            assert_eq!(body["code"], code);
+            assert_eq!(body["detail"], body["error"]);
+            let text = body.to_string();
+            assert!(!text.contains("/data"), "{}: {}", code, text);
+            assert!(!text.contains("denied"), "{}: {}", code, text);
+        }
+
+        let locked = StoreError::locked("data directory /data is locked")
+            .with_resource("/data/.lock")
+            .redacted();
+        let (_, body) = ApiError::Store(locked).status_and_body();
+        assert_eq!(body["detail"], "locked: see the server log");
+        assert!(body.get("resource").is_none());
+        let (_, body) = ApiError::Invalid("no".into()).status_and_body();
+        assert_eq!(body["type"], "urn:truthlayer:error:invalid");
+    }
+
+    #[tokio::test]
+    async fn apply_waits_for_another_instances_lease() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let proposal: crate::types::Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-lease",
+            "status": "accepted",
+            "operations": [],
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let app = app_with_store(store.clone(), crate::config::ServerConfig::default());
+        let apply = || {
+            Request::builder()
+                .method("POST")
+                .uri("/proposals/p-lease/apply")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let ttl = Duration::from_secs(60);
+        store
+            .acquire_lease("apply:p-lease", "replica-2", ttl)
+            .await
+            .unwrap();
+        let res = app.clone().oneshot(apply()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert_eq!(res.headers()[header::RETRY_AFTER], "1");
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "locked");
+        assert_eq!(body["retryable"], true);
+        assert_eq!(body["resource"], "lease apply:p-lease");
+
+        store
+            .release_lease("apply:p-lease", "replica-2")
+            .await
+            .unwrap();
+        let res = app.clone().oneshot(apply()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        // Released after the apply.
+        assert!(store
+            .acquire_lease("apply:p-lease", "replica-2", ttl)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn archived_nodes_leave_default_queries() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let node = |id: &str| {
+            serde_json::json!({
+                "type": "create", "id": format!("op-{}", id), "order": 1,
+                "node": {
+                    "id": { "id": id }, "type": "decision", "status": "accepted", "content": id,
+                    "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u","version":1}
+                }
+            })
+        };
+        let seed: crate::types::Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-seed",
+            "status": "accepted",
+            "operations": [node("d-old"), node("d-new")],
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
+        }))
+        .unwrap();
+        store.create_proposal(seed).await.unwrap();
+        store.apply_proposal("p-seed", "u").await.unwrap();
+        let app = app_with_store(store.clone(), crate::config::ServerConfig::default());
+        let get = |uri: &str| {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/nodes/d-old/archive")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"reason":"replaced by d-new"}"#))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let proposal: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(proposal["status"], "open");
+        assert_eq!(proposal["operations"][0]["new_status"], "archived");
+        // Nothing changes until the proposal is applied.
+        assert_eq!(get("/nodes").await["total"], 2);
+
+        let id = proposal["id"].as_str().unwrap();
+        store
+            .update_proposal(
+                id,
+                ProposalPatch::status(crate::types::ProposalStatus::Accepted),
+            )
+            .await
+            .unwrap();
+        store.apply_proposal(id, "u").await.unwrap();
+
+        let default = get("/nodes").await;
+        assert_eq!(default["total"], 1);
+        assert_eq!(default["nodes"][0]["id"]["id"], "d-new");
+        assert_eq!(get("/nodes?include_archived=true").await["total"], 2);
+        assert_eq!(get("/nodes?status=archived").await["total"], 1);
+        assert_eq!(get("/nodes/d-old").await["status"], "archived");
+        let blame = get("/nodes/d-old/blame").await;
+        assert_eq!(blame["fields"]["status"]["proposalId"], id);
+        assert_eq!(blame["fields"]["status"]["author"], "dev-user");
+        assert_eq!(blame["fields"]["content"]["proposalId"], "p-seed");
+        let trace = get("/nodes/d-old/proposals").await;
+        let ids: Vec<&str> = trace["proposals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["proposalId"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["p-seed", id]);
+        assert_eq!(
+            trace["proposals"][1]["operations"][0]["type"],
+            "status-change"
+        );
+        assert_eq!(
+            get("/nodes/d-new/proposals").await["proposals"][0]["appliedBy"],
+            "u"
+        );
+
+        let again = Request::builder()
+            .method("POST")
+            .uri("/nodes/d-old/archive")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(again).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn withdraw_proposal() {
         let app = app();
@@ -1021,7 +2243,7 @@ mod tests {
             "id": "p-withdraw",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1051,11 +2273,62 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn reset_returns_ok() {
-        let app = app();
-        let req = Request::builder()
-            .method("POST")
-            .uri("/reset")
+    async fn only_the_author_or_an_admin_with_a_reason_withdraws() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-alice", "status": "open", "operations": [],
+            "metadata": { "createdBy": "alice", "modifiedBy": "alice" }
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let withdraw = |body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/proposals/p-alice/withdraw")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let bob = ActorContext {
+            actor_id: "bob".to_string(),
+            actor_type: crate::auth::ActorType::Human,
+            roles: vec![Role::Contributor],
+        };
+        let as_bob = app_as(store.clone(), Default::default(), bob);
+        let res = as_bob.oneshot(withdraw("")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let denied = store
+            .query_audit(
+                Some("bob"),
+                Some("proposal_withdrawn"),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(denied.events.len(), 1);
+
+        let admin = app_with_store(store.clone(), Default::default());
+        let res = admin.clone().oneshot(withdraw("")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res = admin
+            .oneshot(withdraw(r#"{ "reason": "duplicate of p-1" }"#))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let got = store.get_proposal("p-alice").await.unwrap().unwrap();
+        assert_eq!(got.status, crate::types::ProposalStatus::Withdrawn);
+    }
+
+    #[tokio::test]
+    async fn reset_returns_ok() {
+        let app = app();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/reset")
             .body(Body::empty())
             .unwrap();
         let res = app.oneshot(req).await.unwrap();
@@ -1070,7 +2343,7 @@ mod tests {
             "id": "p-audit",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1087,18 +2360,24 @@ mod tests {
         let audit_res = app.oneshot(audit_req).await.unwrap();
         assert_eq!(audit_res.status(), StatusCode::OK);
         let body = audit_res.into_body().collect().await.unwrap().to_bytes();
-        let events: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
-        assert!(!events.is_empty());
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(!page["events"].as_array().unwrap().is_empty());
+        assert_eq!(
+            page["total"].as_u64().unwrap() as usize,
+            page["events"].as_array().unwrap().len()
+        );
+        assert_eq!(page["hasMore"], false);
     }
 
     #[tokio::test]
     async fn submit_review_and_get_review_history() {
-        let app = app();
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let app = app_with_store(store.clone(), crate::config::ServerConfig::default());
         let proposal = serde_json::json!({
             "id": "p-review",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1135,8 +2414,21 @@ mod tests {
         let body = history_res.into_body().collect().await.unwrap().to_bytes();
         let reviews: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
         assert_eq!(reviews.len(), 1);
-        assert_eq!(reviews[0]["reviewer"], "reviewer-1");
+        // The claimed reviewer is replaced by the authenticated actor and its role.
+        assert_eq!(reviews[0]["reviewer"], "dev-user");
+        assert_eq!(reviews[0]["reviewerRole"], "admin");
         assert_eq!(reviews[0]["action"], "accept");
+
+        // The review policies' settlement is written and audited with the review.
+        let proposal = store.get_proposal("p-review").await.unwrap().unwrap();
+        assert_eq!(proposal.status, crate::types::ProposalStatus::Accepted);
+        let audit = store
+            .query_audit(None, None, Some("p-review"), None, None, None, None)
+            .await
+            .unwrap();
+        let actions: Vec<&AuditAction> = audit.events.iter().map(|e| &e.action).collect();
+        assert!(actions.contains(&&AuditAction::ReviewSubmitted));
+        assert!(actions.contains(&&AuditAction::PolicyEvaluated));
     }
 
     #[tokio::test]
@@ -1146,7 +2438,7 @@ mod tests {
             "id": "p-mismatch",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1180,7 +2472,7 @@ mod tests {
             "id": "p-prov",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1199,7 +2491,7 @@ mod tests {
         let body = prov_res.into_body().collect().await.unwrap().to_bytes();
         let prov: serde_json::Value = serde_json::from_slice(&body).unwrap();
         assert_eq!(prov["resourceId"], "p-prov");
-        assert!(prov["events"].as_array().unwrap().len() >= 1);
+        assert!(!prov["events"].as_array().unwrap().is_empty());
     }
 
     #[tokio::test]
@@ -1210,7 +2502,7 @@ mod tests {
             "id": "p-csv",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1236,9 +2528,36 @@ mod tests {
         assert!(ct.contains("text/csv"), "Expected text/csv, got {}", ct);
         let body = csv_res.into_body().collect().await.unwrap().to_bytes();
         let csv_text = String::from_utf8(body.to_vec()).unwrap();
-        assert!(csv_text
-            .starts_with("event_id,timestamp,actor_id,actor_type,action,resource_id,outcome\n"));
+        assert!(csv_text.starts_with(AuditEvent::CSV_HEADER));
         assert!(csv_text.lines().count() >= 2); // header + at least one data row
+
+        // Filtered like GET /audit.
+        let filtered = Request::builder()
+            .uri("/audit/export?format=csv&action=proposal_created&resource_id=p-csv")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(filtered).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let rows: Vec<&str> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .skip(1)
+            .collect();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].contains(",proposal_created,p-csv,"));
+
+        // And by workspace.
+        for (workspace, count) in [(crate::types::DEFAULT_WORKSPACE, 1), ("other", 0)] {
+            let uri = format!(
+                "/audit/export?format=csv&action=proposal_created&workspace={}",
+                workspace
+            );
+            let req = Request::get(uri).body(Body::empty()).unwrap();
+            let res = app.clone().oneshot(req).await.unwrap();
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            let rows = std::str::from_utf8(&body).unwrap().lines().skip(1).count();
+            assert_eq!(rows, count, "{}", workspace);
+        }
     }
 
     #[tokio::test]
@@ -1248,7 +2567,7 @@ mod tests {
             "id": "p-json-audit",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1277,7 +2596,7 @@ mod tests {
             "id": "p-dsar",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1301,7 +2620,16 @@ mod tests {
 
     #[tokio::test]
     async fn dsar_erase_records_event() {
-        let app = app();
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let event = AuditEvent::new(
+            "user-to-erase",
+            "human",
+            AuditAction::ProposalCreated,
+            "p-1",
+            AuditOutcome::Success,
+        );
+        store.append_audit(event).await.unwrap();
+        let app = app_with_store(store.clone(), Default::default());
         let erase_req = Request::builder()
             .method("POST")
             .uri("/admin/dsar/erase")
@@ -1319,6 +2647,341 @@ mod tests {
             .as_str()
             .unwrap()
             .contains("user-to-erase"));
+
+        let erased = store
+            .query_audit(None, Some("dsar_erase"), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(erased.events[0].resource_id, "user-to-erase");
+        assert_eq!(
+            erased.events[0].details,
+            Some(serde_json::json!({ "subject": "user-to-erase", "auditEvents": 1 }))
+        );
+    }
+
+    #[tokio::test]
+    async fn comments_merges_conflicts_and_dsar_exports_are_audited() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        for id in ["p-a", "p-b"] {
+            let proposal: Proposal = serde_json::from_value(serde_json::json!({
+                "id": id, "status": "open", "operations": []
+            }))
+            .unwrap();
+            store.create_proposal(proposal).await.unwrap();
+        }
+        let app = app_with_store(store.clone(), Default::default());
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let comment = serde_json::json!({
+            "comments": [{ "id": "c-1", "content": "Why now?", "author": "dev" }]
+        });
+        let res = app
+            .clone()
+            .oneshot(send("PATCH", "/proposals/p-a", comment))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let merge = serde_json::json!({ "proposalIds": ["p-a", "p-b"] });
+        let res = app
+            .clone()
+            .oneshot(send("POST", "/proposals/merge", merge))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let merged: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(merged["conflicts"].as_array().unwrap().is_empty());
+        for uri in [
+            "/proposals/p-a?include=conflicts",
+            "/admin/dsar/export?subject=alice",
+        ] {
+            let res = app.clone().oneshot(get(uri)).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+        }
+
+        let audit = store
+            .query_audit(None, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        let action = |action: AuditAction| {
+            audit
+                .events
+                .iter()
+                .find(|e| e.action == action)
+                .unwrap_or_else(|| panic!("no {:?} event", action))
+        };
+        assert_eq!(
+            action(AuditAction::CommentAdded).details.as_ref().unwrap()["commentId"],
+            "c-1"
+        );
+        assert_eq!(action(AuditAction::ProposalsMerged).resource_id, "p-a,p-b");
+        assert_eq!(action(AuditAction::ConflictsDetected).resource_id, "p-a");
+        assert_eq!(action(AuditAction::DsarExport).resource_id, "alice");
+    }
+
+    #[tokio::test]
+    async fn admin_config_returns_effective_config() {
+        let req = Request::builder()
+            .uri("/admin/config")
+            .body(Body::empty())
+            .unwrap();
+        let res = app().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["config"]["listen_addr"], "127.0.0.1:3080");
+        assert_eq!(json["config"]["policies_path"], "policies.json");
+        assert!(json["policies"]["rules"].is_array());
+        assert!(json["reloadedAt"].is_null());
+    }
+
+    #[tokio::test]
+    async fn admin_store_status_reports_counts_and_limits() {
+        let req = Request::builder()
+            .uri("/admin/store/status")
+            .body(Body::empty())
+            .unwrap();
+        let res = app().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["nodes"], 0);
+        assert_eq!(json["auditEventsSpilled"], 0);
+        assert!(json["approxBytes"].is_number());
+        assert_eq!(json["limits"]["audit_overflow"], "spill");
+        assert!(json["backend"].is_string());
+    }
+
+    #[tokio::test]
+    async fn admin_store_compact_prunes_closed_proposals() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let mut old: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-old",
+            "status": "withdrawn",
+            "operations": [],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "alice",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "alice"
+            }
+        }))
+        .unwrap();
+        store.create_proposal(old.clone()).await.unwrap();
+        old.id = "p-open".to_string();
+        old.status = crate::types::ProposalStatus::Open;
+        store.create_proposal(old).await.unwrap();
+
+        let app = app_with_store(store.clone(), Default::default());
+        let req = Request::builder()
+            .method("POST")
+            .uri("/admin/store/compact")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"proposalRetentionDays":30}"#))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["prunedProposals"], serde_json::json!(["p-old"]));
+        assert!(store.get_proposal("p-old").await.unwrap().is_none());
+        assert!(store.get_proposal("p-open").await.unwrap().is_some());
+        let audit = store
+            .query_audit(None, Some("store_compacted"), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(audit.total, 1);
+    }
+
+    #[tokio::test]
+    async fn seed_requires_opt_in_and_loads_fixture() {
+        let seed = || {
+            Request::builder()
+                .method("POST")
+                .uri("/admin/seed")
+                .header("content-type", "application/json")
+                .body(Body::from(include_str!("../../fixtures/demo/demo.json")))
+                .unwrap()
+        };
+        let res = app().oneshot(seed()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let app = app_with_config(crate::config::ServerConfig {
+            allow_seed: true,
+            ..Default::default()
+        });
+        let res = app.clone().oneshot(seed()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["nodes"], 4);
+        assert_eq!(json["proposals"], 3);
+        assert_eq!(json["reviews"], 2);
+
+        let req = Request::builder()
+            .uri("/proposals/proposal-demo-002/reviews")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn context_pack_fits_budget_and_is_audited() {
+        let app = app_with_config(crate::config::ServerConfig {
+            allow_seed: true,
+            ..Default::default()
+        });
+        let seed = Request::builder()
+            .method("POST")
+            .uri("/admin/seed")
+            .header("content-type", "application/json")
+            .body(Body::from(include_str!("../../fixtures/demo/demo.json")))
+            .unwrap();
+        assert_eq!(
+            app.clone().oneshot(seed).await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app
+            .clone()
+            .oneshot(get(
+                "/context-pack?task=QUIC%20transport&budget_tokens=300&format=json",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let pack: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(pack["estimatedTokens"].as_u64().unwrap() <= 300);
+        let included: Vec<&str> = pack["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(included[0], "risk-001");
+
+        let res = app
+            .clone()
+            .oneshot(get("/context-pack?task=QUIC&budget_tokens=300"))
+            .await
+            .unwrap();
+        assert_eq!(
+            res.headers()["content-type"],
+            "text/markdown; charset=utf-8"
+        );
+
+        let res = app
+            .clone()
+            .oneshot(get("/context-pack?task=QUIC&budget_tokens=0"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = app
+            .oneshot(get("/audit?action=context_pack_generated"))
+            .await
+            .unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let events = &page["events"];
+        assert_eq!(events.as_array().unwrap().len(), 2);
+        assert_eq!(events[0]["details"]["nodes"][0].as_str(), Some(included[0]));
+    }
+
+    #[tokio::test]
+    async fn node_etag_revalidates_until_node_changes() {
+        let app = app();
+        let node = serde_json::json!({
+            "id": {"id": "etag-node"},
+            "type": "goal",
+            "status": "accepted",
+            "content": "First",
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u","version":1}
+        });
+        let apply = |id: &str, operation: serde_json::Value| {
+            let app = app.clone();
+            let proposal = serde_json::json!({
+                "id": id,
+                "status": "accepted",
+                "operations": [operation],
+                "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
+            });
+            let id = id.to_string();
+            async move {
+                let create = Request::builder()
+                    .method("POST")
+                    .uri("/proposals")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&proposal).unwrap()))
+                    .unwrap();
+                assert_eq!(
+                    app.clone().oneshot(create).await.unwrap().status(),
+                    StatusCode::CREATED
+                );
+                let apply = Request::builder()
+                    .method("POST")
+                    .uri(format!("/proposals/{}/apply", id))
+                    .body(Body::empty())
+                    .unwrap();
+                assert_eq!(app.oneshot(apply).await.unwrap().status(), StatusCode::OK);
+            }
+        };
+        let get = |uri: &str, etag: Option<&str>| {
+            let mut req = Request::builder().uri(uri);
+            if let Some(etag) = etag {
+                req = req.header("if-none-match", etag);
+            }
+            app.clone().oneshot(req.body(Body::empty()).unwrap())
+        };
+
+        apply(
+            "p-etag-1",
+            serde_json::json!({"id":"op1","order":1,"type":"create","node": node}),
+        )
+        .await;
+        let first = get("/nodes/etag-node", None).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers()["etag"].to_str().unwrap().to_string();
+        let cached = get("/nodes/etag-node", Some(&etag)).await.unwrap();
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert!(cached
+            .into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .is_empty());
+
+        let list = get("/nodes", None).await.unwrap();
+        let list_etag = list.headers()["etag"].to_str().unwrap().to_string();
+        assert_eq!(
+            get("/nodes", Some(&list_etag)).await.unwrap().status(),
+            StatusCode::NOT_MODIFIED
+        );
+
+        apply(
+            "p-etag-2",
+            serde_json::json!({"id":"op1","order":1,"type":"update","node_id":{"id":"etag-node"},"changes":{"content":"Second"}}),
+        )
+        .await;
+        let changed = get("/nodes/etag-node", Some(&etag)).await.unwrap();
+        assert_eq!(changed.status(), StatusCode::OK);
+        assert_ne!(changed.headers()["etag"].to_str().unwrap(), etag);
+        assert_eq!(
+            get("/nodes", Some(&list_etag)).await.unwrap().status(),
+            StatusCode::OK
+        );
     }
 
     #[tokio::test]
@@ -1335,7 +2998,7 @@ mod tests {
             "id": "p-apply-node",
             "status": "accepted",
             "operations": [{"id":"op1","order":1,"type":"create","node": node}],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1366,6 +3029,337 @@ mod tests {
         assert_eq!(got["content"], "Applied goal");
     }
 
+    #[tokio::test]
+    async fn events_and_audit_carry_the_proposal_workspace() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let app = app_with_store(store.clone(), Default::default());
+        let get_json = |uri: String| {
+            let app = app.clone();
+            async move {
+                let res = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+        let token = get_json("/changes".to_string()).await["next"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let proposal = serde_json::json!({
+            "id": "p-ui",
+            "status": "open",
+            "operations": [{"id": "op1", "order": 1, "type": "create", "node": {
+                "id": {"id": "goal-1", "namespace": "ui"},
+                "type": "goal",
+                "status": "proposed",
+                "content": "UI goal",
+                "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u","version":1}
+            }}],
+        });
+        let create_req = Request::post("/proposals")
+            .header("content-type", "application/json")
+            .body(Body::from(proposal.to_string()))
+            .unwrap();
+        assert_eq!(
+            app.clone().oneshot(create_req).await.unwrap().status(),
+            StatusCode::CREATED
+        );
+
+        let changes = get_json(format!("/changes?since={}&wait=5&workspace=ui", token)).await;
+        assert_eq!(changes["events"][0]["resourceId"], "p-ui");
+        assert_eq!(changes["events"][0]["workspaceId"], "ui");
+        let audit = store
+            .query_audit(
+                None,
+                Some("proposal_created"),
+                Some("p-ui"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(audit.events[0].workspace_id.as_deref(), Some("ui"));
+    }
+
+    #[tokio::test]
+    async fn agent_proposals_wait_in_quarantine_until_triaged() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let policies: crate::policy::PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [{ "type": "agent_quarantine", "workspaces": ["ui"] }]
+        }))
+        .unwrap();
+        let agent = app_with_policies(
+            store.clone(),
+            Default::default(),
+            policies.clone(),
+            ActorContext {
+                actor_id: "agent-1".to_string(),
+                actor_type: crate::auth::ActorType::Agent,
+                roles: vec![Role::Contributor],
+            },
+        );
+        let human = app_with_policies(
+            store.clone(),
+            Default::default(),
+            policies,
+            ActorContext::dev_default(),
+        );
+        let send = |app: &Router<()>, method: &str, uri: &str, body: serde_json::Value| {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(if body.is_null() {
+                    Body::empty()
+                } else {
+                    Body::from(body.to_string())
+                })
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+                (status, json)
+            }
+        };
+        let proposal = |id: &str, namespace: Option<&str>| {
+            serde_json::json!({
+                "id": id,
+                "status": "open",
+                "operations": [{"id": "op1", "order": 1, "type": "create", "node": {
+                    "id": {"id": format!("goal-{}", id), "namespace": namespace},
+                    "type": "goal",
+                    "status": "proposed",
+                    "content": "goal",
+                    "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"agent-1","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"agent-1","version":1}
+                }}],
+            })
+        };
+        for (id, namespace) in [
+            ("p-ui", Some("ui")),
+            ("p-ui-2", Some("ui")),
+            ("p-main", None),
+        ] {
+            let (status, _) = send(&agent, "POST", "/proposals", proposal(id, namespace)).await;
+            assert_eq!(status, StatusCode::CREATED, "{}", id);
+        }
+
+        // Quarantined proposals stay out of the review queue.
+        let (_, open) = send(&human, "GET", "/proposals", serde_json::Value::Null).await;
+        let open: Vec<&str> = open["proposals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(open, ["p-main"]);
+        let (_, queue) = send(
+            &human,
+            "GET",
+            "/proposals/quarantine",
+            serde_json::Value::Null,
+        )
+        .await;
+        assert_eq!(queue["total"], 2);
+        let (status, _) = send(
+            &human,
+            "POST",
+            "/proposals/p-ui/review",
+            serde_json::json!({
+                "id": "r-1", "proposalId": "p-ui", "reviewer": "dev-user",
+                "reviewedAt": "2026-01-02T00:00:00Z", "action": "accept"
+            }),
+        )
+        .await;
+        assert!(!status.is_success());
+
+        let release = serde_json::json!({ "proposalIds": ["p-ui", "p-main"], "action": "release" });
+        let (status, _) = send(&agent, "POST", "/proposals/triage", release.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, triaged) = send(&human, "POST", "/proposals/triage", release).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(triaged["results"][0]["status"], 200);
+        assert_eq!(triaged["results"][0]["body"]["status"], "open");
+        assert_ne!(triaged["results"][1]["status"], 200);
+
+        let reject = serde_json::json!({ "workspace": "ui", "action": "reject", "reason": "spam" });
+        let (_, triaged) = send(&human, "POST", "/proposals/triage", reject).await;
+        assert_eq!(triaged["results"].as_array().unwrap().len(), 1);
+        assert_eq!(triaged["results"][0]["id"], "p-ui-2");
+        assert_eq!(triaged["results"][0]["body"]["status"], "rejected");
+        let audit = store
+            .query_audit(
+                None,
+                Some("proposal_triaged"),
+                Some("p-ui-2"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let event = &audit.events[0];
+        assert_eq!(event.workspace_id.as_deref(), Some("ui"));
+        assert_eq!(
+            event.details,
+            Some(serde_json::json!({ "action": "reject", "reason": "spam" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn namespaced_nodes_are_read_with_the_namespace_param() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let app = app_with_store(store.clone(), Default::default());
+        let node = serde_json::json!({
+            "id": {"id": "goal-1", "namespace": "ui"},
+            "type": "goal",
+            "status": "accepted",
+            "content": "UI goal",
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u","version":1}
+        });
+        let proposal = serde_json::json!({
+            "id": "p-ui-goal",
+            "status": "accepted",
+            "operations": [{"id":"op1","order":1,"type":"create","node": node}],
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
+        });
+        let create_req = Request::builder()
+            .method("POST")
+            .uri("/proposals")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&proposal).unwrap()))
+            .unwrap();
+        assert_eq!(
+            app.clone().oneshot(create_req).await.unwrap().status(),
+            StatusCode::CREATED
+        );
+        let apply_req = Request::post("/proposals/p-ui-goal/apply")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            app.clone().oneshot(apply_req).await.unwrap().status(),
+            StatusCode::OK
+        );
+        let event = AuditEvent::new(
+            "agent-1",
+            "agent",
+            crate::types::AuditAction::SensitiveRead,
+            "ui:goal-1",
+            crate::types::AuditOutcome::Success,
+        );
+        store.append_audit(event).await.unwrap();
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(get("/nodes/goal-1")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let res = app
+            .clone()
+            .oneshot(get("/nodes/goal-1?namespace=ui"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let got: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(got["content"], "UI goal");
+
+        let res = app
+            .oneshot(get("/nodes/goal-1/provenance?namespace=ui"))
+            .await
+            .unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let provenance: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(provenance["resourceId"], "ui:goal-1");
+        assert_eq!(provenance["events"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn update_sets_any_node_field_and_audits_each() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let app = app_with_store(store.clone(), Default::default());
+        let proposals: Vec<Proposal> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "p-task", "status": "accepted",
+                "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                    "id": { "id": "task-1" }, "type": "task", "status": "accepted",
+                    "content": "Ship it", "assignee": "alice",
+                    "metadata": { "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                                  "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0 }
+                }}],
+                "metadata": { "createdBy": "dev-user" }
+            },
+            {
+                "id": "p-reassign", "status": "accepted",
+                "operations": [{ "id": "op1", "order": 1, "type": "update",
+                    "node_id": { "id": "task-1" },
+                    "changes": { "title": "Ship v2", "assignee": "bob", "dueDate": "2026-02-01",
+                                 "tags": ["release"], "sensitivity": "confidential" } }],
+                "metadata": { "createdBy": "dev-user" }
+            }
+        ]))
+        .unwrap();
+        for proposal in proposals {
+            let id = proposal.id.clone();
+            store.create_proposal(proposal).await.unwrap();
+            let apply = Request::builder()
+                .method("POST")
+                .uri(format!("/proposals/{}/apply", id))
+                .body(Body::empty())
+                .unwrap();
+            assert_eq!(
+                app.clone().oneshot(apply).await.unwrap().status(),
+                StatusCode::OK
+            );
+        }
+
+        let node = store
+            .get_node(&crate::types::NodeId {
+                id: "task-1".to_string(),
+                namespace: None,
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.title.as_deref(), Some("Ship v2"));
+        assert_eq!(node.assignee.as_deref(), Some("bob"));
+        assert_eq!(node.due_date.as_deref(), Some("2026-02-01"));
+        assert_eq!(node.metadata.tags, Some(vec!["release".to_string()]));
+        assert_eq!(
+            node.metadata.sensitivity,
+            Some(crate::sensitivity::Sensitivity::Confidential)
+        );
+        assert_eq!(node.content, "Ship it");
+
+        let applied = store
+            .query_audit(
+                None,
+                Some("proposal_applied"),
+                Some("p-reassign"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let changes = applied.events[0].details.as_ref().unwrap()["fieldChanges"]
+            .as_array()
+            .unwrap()
+            .clone();
+        assert_eq!(changes.len(), 5);
+        let assignee = changes.iter().find(|c| c["field"] == "assignee").unwrap();
+        assert_eq!(assignee["from"], "alice");
+        assert_eq!(assignee["to"], "bob");
+    }
+
     #[tokio::test]
     async fn nodes_query_with_status_filter_and_pagination() {
         let app = app();
@@ -1381,7 +3375,7 @@ mod tests {
             "id": "p-filter",
             "status": "accepted",
             "operations": [{"id":"op1","order":1,"type":"create","node": node}],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1423,7 +3417,7 @@ mod tests {
                 "id": format!("p-page-{}", i),
                 "status": "open",
                 "operations": [],
-                "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+                "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
             });
             let req = Request::builder()
                 .method("POST")
@@ -1446,7 +3440,7 @@ mod tests {
         assert_eq!(result["limit"].as_u64().unwrap(), 2);
         assert_eq!(result["offset"].as_u64().unwrap(), 0);
         assert!(result["proposals"].as_array().unwrap().len() <= 2);
-        assert_eq!(result["hasMore"].as_bool().unwrap(), true);
+        assert!(result["hasMore"].as_bool().unwrap());
 
         // Request second page
         let req2 = Request::builder()
@@ -1457,7 +3451,7 @@ mod tests {
         assert_eq!(res2.status(), StatusCode::OK);
         let body2 = res2.into_body().collect().await.unwrap().to_bytes();
         let result2: serde_json::Value = serde_json::from_slice(&body2).unwrap();
-        assert_eq!(result2["hasMore"].as_bool().unwrap(), false);
+        assert!(!result2["hasMore"].as_bool().unwrap());
     }
 
     #[tokio::test]
@@ -1468,7 +3462,7 @@ mod tests {
             "id": "p-reset-test",
             "status": "open",
             "operations": [],
-            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u"}
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"dev-user","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"dev-user"}
         });
         let create_req = Request::builder()
             .method("POST")
@@ -1495,4 +3489,188 @@ mod tests {
         let get_res = app.clone().oneshot(get_req).await.unwrap();
         assert_eq!(get_res.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn emergency_apply_skips_review_and_opens_a_post_hoc_review() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        // No day is in the change window.
+        let policies = crate::policy::PolicyConfig {
+            rules: vec![crate::policy::PolicyRule::ChangeWindow {
+                allowed_days: vec![],
+                allowed_hour_start: 0,
+                allowed_hour_end: 24,
+            }
+            .into()],
+        };
+        let applier = app_with_policies(
+            store.clone(),
+            Default::default(),
+            policies.clone(),
+            ActorContext {
+                actor_id: "ops".to_string(),
+                actor_type: crate::auth::ActorType::Human,
+                roles: vec![Role::Applier],
+            },
+        );
+        let admin = app_with_policies(
+            store.clone(),
+            Default::default(),
+            policies,
+            ActorContext::dev_default(),
+        );
+        let apply = |app: &Router<()>, body: serde_json::Value| {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/proposals/p-1/apply")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+                (status, json)
+            }
+        };
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-1", "status": "open", "operations": [],
+            "metadata": { "createdBy": "dev-user" }
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+
+        let emergency = serde_json::json!({ "emergency": true, "justification": "outage INC-42" });
+        let (status, _) = apply(&applier, emergency.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = apply(&admin, serde_json::json!({ "emergency": true })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = apply(&admin, serde_json::json!({})).await;
+        assert!(!status.is_success());
+
+        let (status, body) = apply(&admin, emergency).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let applied = store.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(applied.status, crate::types::ProposalStatus::Applied);
+        let follow_up = store
+            .get_proposal(body["followUp"].as_str().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(follow_up.status, crate::types::ProposalStatus::Open);
+        assert_eq!(follow_up.relations, Some(vec!["p-1".to_string()]));
+        match &follow_up.operations[0] {
+            crate::types::Operation::Create { node, .. } => {
+                assert_eq!(node.node_type, crate::types::NodeType::Task);
+                assert!(node.content.contains("outage INC-42"));
+            }
+            other => panic!("unexpected operation {:?}", other),
+        }
+
+        let audit = store
+            .query_audit(
+                None,
+                Some("proposal_applied"),
+                Some("p-1"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let details = audit.events[0].details.as_ref().unwrap();
+        assert_eq!(details["justification"], "outage INC-42");
+        let bypassed: Vec<&str> = details["bypassed"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["rule"].as_str().unwrap())
+            .collect();
+        assert!(bypassed.contains(&"change_window"));
+        assert!(bypassed.contains(&"min_approvals"));
+    }
+
+    #[tokio::test]
+    async fn failed_emergency_apply_leaves_the_proposal_open() {
+        // No room for the node the proposal creates, so its apply fails.
+        let limits = crate::store::limits::MemoryLimits {
+            max_nodes: Some(0),
+            ..Default::default()
+        };
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::with_limits(
+            limits,
+            std::env::temp_dir(),
+        ));
+        let node = serde_json::json!({
+            "id": {"id": "goal-1"}, "type": "goal", "status": "accepted", "content": "A goal",
+            "metadata": {"createdAt":"2026-01-01T00:00:00Z","createdBy":"u","modifiedAt":"2026-01-01T00:00:00Z","modifiedBy":"u","version":1}
+        });
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-1", "status": "open",
+            "operations": [{"id":"op1","order":1,"type":"create","node": node}],
+            "metadata": { "createdBy": "dev-user" }
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let app = app_with_store(store.clone(), Default::default());
+        let req = Request::builder()
+            .method("POST")
+            .uri("/proposals/p-1/apply")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "emergency": true, "justification": "outage INC-42" })
+                    .to_string(),
+            ))
+            .unwrap();
+
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::INSUFFICIENT_STORAGE);
+        let stored = store.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(stored.status, crate::types::ProposalStatus::Open);
+        assert!(store.get_review_history("p-1").await.unwrap().is_empty());
+        // The post-hoc review is written with the apply, so it is not there either.
+        assert_eq!(store.get_open_proposals().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn emergency_apply_of_a_long_id_opens_its_post_hoc_review() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let id = format!("p-{}", "x".repeat(crate::ids::MAX_ID_LEN - 2));
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": id, "status": "open", "operations": [],
+            "metadata": { "createdBy": "dev-user" }
+        }))
+        .unwrap();
+        store.create_proposal(proposal).await.unwrap();
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/proposals/{}/apply", id))
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "emergency": true, "justification": "outage INC-42" })
+                    .to_string(),
+            ))
+            .unwrap();
+
+        let res = app_with_store(store.clone(), Default::default())
+            .oneshot(req)
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let follow_up = store
+            .get_proposal(body["followUp"].as_str().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        match &follow_up.operations[0] {
+            crate::types::Operation::Create { node, .. } => {
+                assert!(crate::ids::is_valid(&node.id.id), "{}", node.id.id);
+            }
+            other => panic!("unexpected operation {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,677 @@
+//! SCIM 2.0 endpoints (`crate::scim`) under `/scim/v2`: `Users` and `Groups` (list with
+//! `filter`, `startIndex`, `count`; create, get, replace, patch, delete), plus
+//! `ServiceProviderConfig` and `ResourceTypes`. The identity provider calls them with an
+//! Admin token; responses and errors use `application/scim+json`.
+//!
+//! Every change is audited (`actor_provisioned`, `actor_deprovisioned`,
+//! `group_provisioned`, `group_deprovisioned`). [`enforce_directory`] applies the
+//! directory to every request: deprovisioned actors and revoked tokens get `401`, and
+//! `scim.group_roles` replaces the roles of provisioned actors. MCP over stdio and the
+//! Slack and forge mappings apply it through `service::apply_directory` as well.
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, Path, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::api::routes::AppState;
+use crate::api::service::{self, actor_type_str};
+use crate::auth::{ActorContext, Role, TokenIssuedAt};
+use crate::rbac;
+use crate::scim::{self, Filter};
+use crate::store::context_store::{StoreError, StoreErrorCode};
+use crate::store::{Directory, DirectoryGroup, DirectoryUser};
+use crate::types::{AuditAction, AuditDetails, AuditEvent, AuditOutcome};
+
+/// Largest page served, whatever `count` asks for.
+const MAX_COUNT: usize = 200;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/scim/v2/ServiceProviderConfig",
+            get(|| async { scim_json(StatusCode::OK, scim::service_provider_config()) }),
+        )
+        .route(
+            "/scim/v2/ResourceTypes",
+            get(|| async { scim_json(StatusCode::OK, scim::resource_types()) }),
+        )
+        .route("/scim/v2/Users", get(list_users).post(create_user))
+        .route(
+            "/scim/v2/Users/:id",
+            get(get_user)
+                .put(replace_user)
+                .patch(patch_user)
+                .delete(delete_user),
+        )
+        .route("/scim/v2/Groups", get(list_groups).post(create_group))
+        .route(
+            "/scim/v2/Groups/:id",
+            get(get_group)
+                .put(replace_group)
+                .patch(patch_group)
+                .delete(delete_group),
+        )
+}
+
+fn scim_json(status: StatusCode, body: Value) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/scim+json")],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+/// A SCIM error response (RFC 7644 §3.12).
+#[derive(Debug)]
+pub struct ScimError {
+    status: StatusCode,
+    scim_type: Option<&'static str>,
+    detail: String,
+}
+
+impl ScimError {
+    fn new(status: StatusCode, scim_type: Option<&'static str>, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            scim_type,
+            detail: detail.into(),
+        }
+    }
+
+    fn invalid(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidValue"), detail)
+    }
+
+    fn not_found(kind: &str, id: &str) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            None,
+            format!("{} {} not found", kind, id),
+        )
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> Response {
+        let mut body = serde_json::json!({
+            "schemas": [scim::ERROR_SCHEMA],
+            "status": self.status.as_u16().to_string(),
+            "detail": self.detail,
+        });
+        if let Some(scim_type) = self.scim_type {
+            body["scimType"] = Value::from(scim_type);
+        }
+        scim_json(self.status, body)
+    }
+}
+
+impl From<StoreError> for ScimError {
+    fn from(e: StoreError) -> Self {
+        match e.code {
+            StoreErrorCode::NotFound => Self::new(StatusCode::NOT_FOUND, None, e.message),
+            StoreErrorCode::Conflict => {
+                Self::new(StatusCode::CONFLICT, Some("uniqueness"), e.message)
+            }
+            StoreErrorCode::Invalid | StoreErrorCode::InvalidTransition => Self::invalid(e.message),
+            _ => Self::new(crate::api::routes::store_status(&e), None, e.to_string()),
+        }
+    }
+}
+
+impl From<rbac::Forbidden> for ScimError {
+    fn from(e: rbac::Forbidden) -> Self {
+        Self::new(StatusCode::FORBIDDEN, None, e.0)
+    }
+}
+
+fn parse_body(body: &Bytes) -> Result<Value, ScimError> {
+    serde_json::from_slice(body).map_err(|e| {
+        ScimError::new(
+            StatusCode::BAD_REQUEST,
+            Some("invalidSyntax"),
+            e.to_string(),
+        )
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListParams {
+    pub filter: Option<String>,
+    /// 1-based, as in SCIM.
+    pub start_index: Option<usize>,
+    pub count: Option<usize>,
+}
+
+/// Filter and page `resources` as a `ListResponse`.
+fn list(resources: Vec<Value>, params: &ListParams) -> Result<Response, ScimError> {
+    let filter = params
+        .filter
+        .as_deref()
+        .map(Filter::parse)
+        .transpose()
+        .map_err(|e| ScimError::new(StatusCode::BAD_REQUEST, Some("invalidFilter"), e))?;
+    let matching: Vec<Value> = resources
+        .into_iter()
+        .filter(|r| filter.as_ref().is_none_or(|f| f.matches(r)))
+        .collect();
+    let total = matching.len();
+    let start_index = params.start_index.unwrap_or(1).max(1);
+    let count = params.count.unwrap_or(MAX_COUNT).min(MAX_COUNT);
+    let page = matching
+        .into_iter()
+        .skip(start_index - 1)
+        .take(count)
+        .collect();
+    Ok(scim_json(
+        StatusCode::OK,
+        scim::list_response(page, total, start_index),
+    ))
+}
+
+async fn audit(
+    state: &AppState,
+    actor: &ActorContext,
+    action: AuditAction,
+    resource_id: &str,
+    details: AuditDetails,
+) -> Result<(), StoreError> {
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        action,
+        resource_id,
+        AuditOutcome::Success,
+    )
+    .with_details(details);
+    service::audit(state, event).await
+}
+
+// --- Users ---
+
+async fn list_users(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<ListParams>,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "GET /scim/v2/Users", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let users = directory
+        .users
+        .values()
+        .map(|u| scim::user_resource(u, &directory))
+        .collect();
+    list(users, &params)
+}
+
+async fn get_user(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "GET /scim/v2/Users/:id", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let user = directory
+        .users
+        .get(&id)
+        .ok_or_else(|| ScimError::not_found("user", &id))?;
+    Ok(scim_json(
+        StatusCode::OK,
+        scim::user_resource(user, &directory),
+    ))
+}
+
+async fn create_user(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    body: Bytes,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "POST /scim/v2/Users", Role::Admin)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut user = DirectoryUser {
+        id: uuid::Uuid::new_v4().to_string(),
+        user_name: String::new(),
+        external_id: None,
+        display_name: None,
+        emails: Vec::new(),
+        active: true,
+        created: now.clone(),
+        last_modified: now,
+    };
+    scim::apply_user(&mut user, &parse_body(&body)?).map_err(ScimError::invalid)?;
+    save_user(&state, &actor, None, user, StatusCode::CREATED).await
+}
+
+async fn replace_user(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "PUT /scim/v2/Users/:id", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let old = directory
+        .users
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| ScimError::not_found("user", &id))?;
+    let mut user = old.clone();
+    scim::apply_user(&mut user, &parse_body(&body)?).map_err(ScimError::invalid)?;
+    save_user(&state, &actor, Some(old), user, StatusCode::OK).await
+}
+
+async fn patch_user(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "PATCH /scim/v2/Users/:id", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let old = directory
+        .users
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| ScimError::not_found("user", &id))?;
+    let mut resource = scim::user_resource(&old, &directory);
+    scim::apply_patch(&mut resource, &parse_body(&body)?).map_err(ScimError::invalid)?;
+    let mut user = old.clone();
+    scim::apply_user(&mut user, &resource).map_err(ScimError::invalid)?;
+    save_user(&state, &actor, Some(old), user, StatusCode::OK).await
+}
+
+/// Store a created or changed user, audit it, and answer with the resource.
+async fn save_user(
+    state: &AppState,
+    actor: &ActorContext,
+    old: Option<DirectoryUser>,
+    mut user: DirectoryUser,
+    status: StatusCode,
+) -> Result<Response, ScimError> {
+    if old.is_some() {
+        user.last_modified = chrono::Utc::now().to_rfc3339();
+    }
+    state.store.save_directory_user(user.clone()).await?;
+    let deactivated = !user.active && old.as_ref().is_none_or(|o| o.active);
+    let (action, operation) = match (&old, deactivated) {
+        (_, true) => (AuditAction::ActorDeprovisioned, "deactivate"),
+        (None, false) => (AuditAction::ActorProvisioned, "create"),
+        (Some(_), false) => (AuditAction::ActorProvisioned, "update"),
+    };
+    let directory = state.store.get_directory().await?;
+    let details = AuditDetails::ScimUser {
+        operation: operation.to_string(),
+        scim_id: user.id.clone(),
+        external_id: user.external_id.clone(),
+        active: Some(user.active),
+        previous_user_name: old
+            .as_ref()
+            .filter(|o| o.user_name != user.user_name)
+            .map(|o| o.user_name.clone()),
+        tokens_revoked_at: directory
+            .revoked
+            .get(&user.user_name)
+            .filter(|_| deactivated)
+            .cloned(),
+    };
+    audit(state, actor, action, &user.user_name, details).await?;
+    Ok(scim_json(status, scim::user_resource(&user, &directory)))
+}
+
+async fn delete_user(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "DELETE /scim/v2/Users/:id", Role::Admin)?;
+    let user = state.store.delete_directory_user(&id).await?;
+    let directory = state.store.get_directory().await?;
+    audit(
+        &state,
+        &actor,
+        AuditAction::ActorDeprovisioned,
+        &user.user_name,
+        AuditDetails::ScimUser {
+            operation: "delete".to_string(),
+            scim_id: user.id.clone(),
+            external_id: user.external_id.clone(),
+            active: None,
+            previous_user_name: None,
+            tokens_revoked_at: directory.revoked.get(&user.user_name).cloned(),
+        },
+    )
+    .await?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+// --- Groups ---
+
+async fn list_groups(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<ListParams>,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "GET /scim/v2/Groups", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let groups = directory
+        .groups
+        .values()
+        .map(|g| scim::group_resource(g, &directory))
+        .collect();
+    list(groups, &params)
+}
+
+async fn get_group(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "GET /scim/v2/Groups/:id", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let group = directory
+        .groups
+        .get(&id)
+        .ok_or_else(|| ScimError::not_found("group", &id))?;
+    Ok(scim_json(
+        StatusCode::OK,
+        scim::group_resource(group, &directory),
+    ))
+}
+
+async fn create_group(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    body: Bytes,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "POST /scim/v2/Groups", Role::Admin)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut group = DirectoryGroup {
+        id: uuid::Uuid::new_v4().to_string(),
+        display_name: String::new(),
+        external_id: None,
+        members: Vec::new(),
+        created: now.clone(),
+        last_modified: now,
+    };
+    scim::apply_group(&mut group, &parse_body(&body)?).map_err(ScimError::invalid)?;
+    save_group(&state, &actor, "create", group, StatusCode::CREATED).await
+}
+
+async fn replace_group(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "PUT /scim/v2/Groups/:id", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let mut group = existing_group(&directory, &id)?;
+    scim::apply_group(&mut group, &parse_body(&body)?).map_err(ScimError::invalid)?;
+    save_group(&state, &actor, "update", group, StatusCode::OK).await
+}
+
+async fn patch_group(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "PATCH /scim/v2/Groups/:id", Role::Admin)?;
+    let directory = state.store.get_directory().await?;
+    let mut group = existing_group(&directory, &id)?;
+    let mut resource = scim::group_resource(&group, &directory);
+    scim::apply_patch(&mut resource, &parse_body(&body)?).map_err(ScimError::invalid)?;
+    scim::apply_group(&mut group, &resource).map_err(ScimError::invalid)?;
+    save_group(&state, &actor, "update", group, StatusCode::OK).await
+}
+
+fn existing_group(directory: &Directory, id: &str) -> Result<DirectoryGroup, ScimError> {
+    directory
+        .groups
+        .get(id)
+        .cloned()
+        .ok_or_else(|| ScimError::not_found("group", id))
+}
+
+async fn save_group(
+    state: &AppState,
+    actor: &ActorContext,
+    operation: &str,
+    mut group: DirectoryGroup,
+    status: StatusCode,
+) -> Result<Response, ScimError> {
+    if operation != "create" {
+        group.last_modified = chrono::Utc::now().to_rfc3339();
+    }
+    state.store.save_directory_group(group.clone()).await?;
+    let directory = state.store.get_directory().await?;
+    let group = existing_group(&directory, &group.id)?;
+    audit(
+        state,
+        actor,
+        AuditAction::GroupProvisioned,
+        &group.id,
+        AuditDetails::ScimGroup {
+            operation: operation.to_string(),
+            display_name: group.display_name.clone(),
+            members: Some(group.members.clone()),
+        },
+    )
+    .await?;
+    Ok(scim_json(status, scim::group_resource(&group, &directory)))
+}
+
+async fn delete_group(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+) -> Result<Response, ScimError> {
+    service::require_route(&state, &actor, "DELETE /scim/v2/Groups/:id", Role::Admin)?;
+    let group = state.store.delete_directory_group(&id).await?;
+    audit(
+        &state,
+        &actor,
+        AuditAction::GroupDeprovisioned,
+        &group.id,
+        AuditDetails::ScimGroup {
+            operation: "delete".to_string(),
+            display_name: group.display_name.clone(),
+            members: None,
+        },
+    )
+    .await?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Middleware applying the actor directory to authenticated requests
+/// ([`service::apply_directory`], which the other entry points call too).
+pub async fn enforce_directory(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let Some(actor) = req.extensions().get::<ActorContext>().cloned() else {
+        return next.run(req).await;
+    };
+    let issued = req.extensions().get::<TokenIssuedAt>().map(|t| t.0);
+    match service::apply_directory(&state, actor, service::Credential::Token(issued)).await {
+        Ok(actor) => {
+            req.extensions_mut().insert(actor);
+            next.run(req).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::ContextStore;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn provisioning_feeds_rbac_and_deprovisioning_revokes() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let config = crate::config::ServerConfig {
+            scim: scim::ScimConfig {
+                group_roles: [("reviewers".to_string(), Role::Reviewer)].into(),
+            },
+            ..Default::default()
+        };
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(config, crate::policy::PolicyConfig::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        );
+        let admin = ActorContext {
+            actor_id: "okta".to_string(),
+            actor_type: crate::auth::ActorType::System,
+            roles: vec![Role::Admin],
+        };
+        // Stands in for `AuthLayer`: the caller's actor and token issue time.
+        let send = |actor: ActorContext, iat: u64, method: &str, uri: &str, body: Value| {
+            let mut req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/scim+json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            req.extensions_mut().insert(actor);
+            req.extensions_mut().insert(TokenIssuedAt(iat));
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, user) = send(
+            admin.clone(),
+            0,
+            "POST",
+            "/scim/v2/Users",
+            serde_json::json!({ "schemas": [scim::USER_SCHEMA], "userName": "alice",
+                                "externalId": "00u1" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let user_id = user["id"].as_str().unwrap().to_string();
+        let (status, dup) = send(
+            admin.clone(),
+            0,
+            "POST",
+            "/scim/v2/Users",
+            serde_json::json!({ "userName": "Alice" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(dup["scimType"], "uniqueness");
+        let (_, found) = send(
+            admin.clone(),
+            0,
+            "GET",
+            "/scim/v2/Users?filter=userName%20eq%20%22ALICE%22",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(found["totalResults"], 1);
+        let (status, _) = send(
+            admin.clone(),
+            0,
+            "POST",
+            "/scim/v2/Groups",
+            serde_json::json!({ "displayName": "reviewers", "members": [{ "value": user_id }] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // The token claims Admin; the directory makes alice a Reviewer.
+        let alice = ActorContext {
+            actor_id: "alice".to_string(),
+            actor_type: crate::auth::ActorType::Human,
+            roles: vec![Role::Admin],
+        };
+        let iat = chrono::Utc::now().timestamp() as u64;
+        let (status, _) = send(alice.clone(), iat, "GET", "/nodes", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(alice.clone(), iat, "GET", "/audit", Value::Null).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, patched) = send(
+            admin.clone(),
+            0,
+            "PATCH",
+            &format!("/scim/v2/Users/{}", user_id),
+            serde_json::json!({ "schemas": [scim::PATCH_SCHEMA], "Operations": [
+                { "op": "replace", "path": "active", "value": false }
+            ]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(patched["active"], false);
+        let (status, _) = send(alice.clone(), iat, "GET", "/nodes", Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // Reactivated, the old token stays revoked; a newer one works.
+        send(
+            admin.clone(),
+            0,
+            "PATCH",
+            &format!("/scim/v2/Users/{}", user_id),
+            serde_json::json!({ "Operations": [{ "op": "replace", "value": { "active": true } }] }),
+        )
+        .await;
+        let (status, _) = send(alice.clone(), iat, "GET", "/nodes", Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send(alice.clone(), iat + 3600, "GET", "/nodes", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(
+            admin.clone(),
+            0,
+            "DELETE",
+            &format!("/scim/v2/Users/{}", user_id),
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(alice, iat + 3600, "GET", "/nodes", Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let deprovisioned = store
+            .query_audit(
+                None,
+                Some("actor_deprovisioned"),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .events;
+        assert_eq!(deprovisioned.len(), 2);
+        assert_eq!(deprovisioned[0].resource_id, "alice");
+        assert!(deprovisioned[1].details.as_ref().unwrap()["tokensRevokedAt"].is_string());
+    }
+}
@@ -0,0 +1,1973 @@
+//! Store operations shared by every API surface (REST routes, gRPC).
+//!
+//! Each operation enforces RBAC, evaluates policies, applies agent sensitivity
+//! redaction, appends audit events and publishes server events exactly once, so the
+//! surfaces only translate requests and responses.
+
+use std::collections::HashMap;
+
+use crate::api::node_locks;
+use crate::api::routes::{ApiError, AppState, AuditQueryParams, ProposalListResponse};
+use crate::api::validate;
+use crate::auth::{ActorContext, ActorType, Role};
+use crate::complexity;
+use crate::content_rules;
+use crate::context_pack::{self, ContextPack};
+use crate::events::{EventBus, EventKind, FieldChange, ServerEvent};
+use crate::forge;
+use crate::ids;
+use crate::policy;
+use crate::rbac;
+use crate::read_only;
+use crate::sensitivity::{self, Sensitivity};
+use crate::store::context_store::StoreError;
+use crate::store::lifecycle::{self, Transition};
+use crate::store::WriteBatch;
+use crate::timestamps::Stamper;
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, AuditQueryResult, Comment, ContextNode,
+    Delegation, FieldBlame, MergeResult, NodeId, NodeQuery, NodeQueryResult, NodeStatus, NodeType,
+    Operation, Proposal, ProposalMetadata, ProposalPatch, ProposalQuery, ProposalStatus, Review,
+    ReviewAction, TaskState, TriageAction, DEFAULT_WORKSPACE, UPDATABLE_METADATA_FIELDS,
+};
+
+/// A server event about `resource_id`, triggered by `actor`.
+pub fn server_event(kind: EventKind, resource_id: &str, actor: &ActorContext) -> ServerEvent {
+    ServerEvent::new(kind, resource_id, &actor.actor_id)
+}
+
+/// `comment_added` audit events for the comments and replies in `after` that are not in
+/// `before` (matched by id), on proposal `proposal_id`.
+pub fn comments_added(
+    actor: &ActorContext,
+    proposal_id: &str,
+    before: &[Comment],
+    after: &[Comment],
+) -> Vec<AuditEvent> {
+    fn flatten<'a>(comments: &'a [Comment], out: &mut Vec<&'a Comment>) {
+        for comment in comments {
+            out.push(comment);
+            flatten(comment.replies.as_deref().unwrap_or_default(), out);
+        }
+    }
+    let (mut old, mut new) = (Vec::new(), Vec::new());
+    flatten(before, &mut old);
+    flatten(after, &mut new);
+    new.into_iter()
+        .filter(|c| !old.iter().any(|o| o.id == c.id))
+        .map(|c| {
+            AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(actor),
+                AuditAction::CommentAdded,
+                proposal_id,
+                AuditOutcome::Success,
+            )
+            .with_details(AuditDetails::Comment {
+                comment_id: c.id.clone(),
+                author: c.author.clone(),
+                operation_id: c.operation_id.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Publish a server event to SSE / gRPC watch subscribers; `workspace` is that of the
+/// resource, None for server-wide events (config, jobs).
+pub fn publish_event(
+    event_bus: &EventBus,
+    kind: EventKind,
+    resource_id: &str,
+    actor: &ActorContext,
+    workspace: Option<&str>,
+) {
+    let mut event = server_event(kind, resource_id, actor);
+    if let Some(workspace) = workspace {
+        event = event.in_workspace(workspace);
+    }
+    event_bus.publish(event);
+}
+
+/// Audit and publish a policy refusal of a write to `resource_id` in `workspace`; returns
+/// the error to answer with.
+pub async fn policy_violation(
+    state: &AppState,
+    actor: &ActorContext,
+    resource_id: &str,
+    workspace: &str,
+    violations: Vec<policy::PolicyViolation>,
+) -> ApiError {
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::PolicyEvaluated,
+        resource_id,
+        AuditOutcome::PolicyViolation,
+    )
+    .in_workspace(workspace)
+    .with_details(AuditDetails::Violations {
+        violations: violations.clone(),
+    });
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(server_event(
+            EventKind::PolicyViolation {
+                violations: violations.clone(),
+            },
+            resource_id,
+            actor,
+        ))
+        .in_workspace(workspace);
+    if let Err(e) = execute(state, batch).await {
+        return e;
+    }
+    ApiError::PolicyViolation(violations)
+}
+
+/// Settle the policy `violations` of a write to `resource_id` in `workspace`. Those of
+/// rules in `warn` or `shadow` enforcement are audited (outcome `policy_warning`; `warn`
+/// ones are also logged) and let through; enforced ones refuse the write (see
+/// [`policy_violation`]).
+pub async fn check_policies(
+    state: &AppState,
+    actor: &ActorContext,
+    resource_id: &str,
+    workspace: &str,
+    violations: Vec<policy::PolicyViolation>,
+) -> Result<(), ApiError> {
+    let (enforced, advisory): (Vec<_>, Vec<_>) = violations
+        .into_iter()
+        .partition(|v| v.enforcement.is_enforce());
+    policy_warnings(state, actor, resource_id, workspace, advisory).await?;
+    if enforced.is_empty() {
+        Ok(())
+    } else {
+        Err(policy_violation(state, actor, resource_id, workspace, enforced).await)
+    }
+}
+
+/// Audit the violations of rules that are not enforced; see [`check_policies`].
+pub async fn policy_warnings(
+    state: &AppState,
+    actor: &ActorContext,
+    resource_id: &str,
+    workspace: &str,
+    violations: Vec<policy::PolicyViolation>,
+) -> Result<(), StoreError> {
+    match policy_warning_event(actor, resource_id, workspace, violations) {
+        Some(event) => audit(state, event).await,
+        None => Ok(()),
+    }
+}
+
+/// The audit event of [`policy_warnings`] (`warn` ones are logged); None without
+/// violations.
+fn policy_warning_event(
+    actor: &ActorContext,
+    resource_id: &str,
+    workspace: &str,
+    violations: Vec<policy::PolicyViolation>,
+) -> Option<AuditEvent> {
+    if violations.is_empty() {
+        return None;
+    }
+    for v in violations
+        .iter()
+        .filter(|v| v.enforcement == policy::Enforcement::Warn)
+    {
+        tracing::warn!(resource = %resource_id, rule = %v.rule, message = %v.message, "policy warning");
+    }
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::PolicyEvaluated,
+        resource_id,
+        AuditOutcome::PolicyWarning,
+    )
+    .in_workspace(workspace)
+    .with_details(AuditDetails::Violations { violations });
+    Some(event)
+}
+
+/// Make a batch of writes (see `store::batch`) and have the outbox dispatcher deliver
+/// its events and audit events.
+pub async fn execute(state: &AppState, batch: WriteBatch) -> Result<(), ApiError> {
+    state.store.execute(batch).await?;
+    state.outbox.wake();
+    Ok(())
+}
+
+/// Append `event` to the audit log as a batch with no writes, so the outbox forwards it
+/// like the audit events of writes (see [`execute`]).
+pub async fn audit(state: &AppState, event: AuditEvent) -> Result<(), StoreError> {
+    state.store.execute(WriteBatch::new().audit(event)).await?;
+    state.outbox.wake();
+    Ok(())
+}
+
+/// Require the role `route` (e.g. `"GET /audit"`) needs: `default`, unless `rbac.routes`
+/// configures another.
+pub fn require_route(
+    state: &AppState,
+    actor: &ActorContext,
+    route: &str,
+    default: Role,
+) -> Result<(), rbac::Forbidden> {
+    rbac::require_route_role(
+        &state.runtime.config.get().route_roles,
+        route,
+        actor,
+        default,
+    )
+}
+
+/// How an actor authenticated, for [`apply_directory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Credential {
+    /// A token or client certificate; the token's issue time (seconds) when it has one.
+    Token(Option<u64>),
+    /// An identity mapping (Slack, forge); revocations invalidate tokens only.
+    Mapping,
+}
+
+/// Apply the actor directory to `actor`, however it reached the server (HTTP requests,
+/// MCP over stdio, Slack and forge mappings): `401` for deprovisioned actors and for
+/// tokens issued before the actor's last revocation (or without an issue time), and the
+/// roles of `scim.group_roles` for provisioned actors. Actors the directory never
+/// provisioned pass unchanged.
+pub async fn apply_directory(
+    state: &AppState,
+    actor: ActorContext,
+    credential: Credential,
+) -> Result<ActorContext, ApiError> {
+    let Some(access) = state.store.directory_access(&actor.actor_id).await? else {
+        return Ok(actor);
+    };
+    if !access.active {
+        return Err(ApiError::Unauthorized(format!(
+            "actor {} is deprovisioned",
+            actor.actor_id
+        )));
+    }
+    if let (Some(revoked_at), Credential::Token(issued)) = (&access.revoked_at, credential) {
+        let revoked = chrono::DateTime::parse_from_rfc3339(revoked_at)
+            .map(|t| t.timestamp())
+            .unwrap_or(i64::MAX);
+        if issued.is_none_or(|iat| iat as i64 <= revoked) {
+            return Err(ApiError::Unauthorized(format!(
+                "token revoked: issued before actor {} was deprovisioned at {}",
+                actor.actor_id, revoked_at
+            )));
+        }
+    }
+    match state.runtime.config.get().scim.roles_for(&access.groups) {
+        Some(roles) => Ok(ActorContext { roles, ..actor }),
+        None => Ok(actor),
+    }
+}
+
+/// The human `actor_id` acting through an identity mapping (Slack, forge) instead of a
+/// token, with the `roles` that integration's config gives it (`slack.roles`,
+/// `forge.workspaces.*.roles`), which the directory then applies to (see
+/// [`apply_directory`]). Roles granted elsewhere, e.g. to a client certificate, do not
+/// carry over.
+pub async fn mapped_actor(
+    state: &AppState,
+    actor_id: &str,
+    roles: Option<&Vec<Role>>,
+) -> Result<ActorContext, ApiError> {
+    let actor = ActorContext {
+        actor_id: actor_id.to_string(),
+        actor_type: ActorType::Human,
+        roles: roles.cloned().unwrap_or_default(),
+    };
+    apply_directory(state, actor, Credential::Mapping).await
+}
+
+/// Timestamp stamping for one write, per `server.trust_client_timestamps`.
+pub fn stamper(state: &AppState) -> Stamper {
+    Stamper::new(state.runtime.config.get().trust_client_timestamps)
+}
+
+pub fn actor_type_str(actor: &ActorContext) -> &'static str {
+    match actor.actor_type {
+        ActorType::Human => "human",
+        ActorType::Agent => "agent",
+        ActorType::System => "system",
+    }
+}
+
+/// Serde string form of a unit enum (e.g. `NodeStatus::Accepted` -> "accepted").
+pub fn enum_str<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+/// A node as the caller may see it.
+pub enum NodeRead {
+    Full(Box<ContextNode>),
+    /// The caller is an agent whose sensitivity clearance is below the node's.
+    Redacted {
+        id: NodeId,
+        node_type: NodeType,
+        status: NodeStatus,
+        sensitivity: Sensitivity,
+    },
+}
+
+impl NodeRead {
+    pub fn node_id(&self) -> &NodeId {
+        match self {
+            NodeRead::Full(node) => &node.id,
+            NodeRead::Redacted { id, .. } => id,
+        }
+    }
+
+    /// JSON as the REST API returns it: the node document, or a redaction stub.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            NodeRead::Full(node) => serde_json::to_value(node).unwrap_or_default(),
+            NodeRead::Redacted {
+                id,
+                node_type,
+                status,
+                sensitivity,
+            } => serde_json::json!({
+                "id": id,
+                "type": node_type,
+                "status": status,
+                "redacted": true,
+                "reason": "sensitivity",
+                "metadata": { "sensitivity": sensitivity.as_str() }
+            }),
+        }
+    }
+}
+
+/// Query nodes. Agents only see nodes within their sensitivity clearance; reads of
+/// confidential+ nodes and redactions are audited.
+pub async fn query_nodes(
+    state: &AppState,
+    actor: &ActorContext,
+    query: NodeQuery,
+) -> Result<NodeQueryResult, ApiError> {
+    require_route(state, actor, "GET /nodes", Role::Reader)?;
+
+    let mut result = state.store.query_nodes(query).await?;
+
+    // Agent sensitivity filtering: redact nodes above agent's allowed sensitivity
+    if actor.actor_type == ActorType::Agent {
+        let max_sensitivity = policy::agent_max_sensitivity(&state.runtime.policies.get());
+        let mut filtered_nodes = Vec::new();
+        let mut redacted_count = 0u64;
+        for node in result.nodes {
+            let node_sensitivity = node.metadata.sensitivity.unwrap_or(Sensitivity::Internal);
+            if sensitivity::agent_can_read(node_sensitivity, max_sensitivity) {
+                // Log agent reads of confidential+ content
+                if node_sensitivity >= Sensitivity::Confidential {
+                    let event = AuditEvent::new(
+                        &actor.actor_id,
+                        actor_type_str(actor),
+                        AuditAction::SensitiveRead,
+                        &node.id.key(),
+                        AuditOutcome::Success,
+                    )
+                    .in_workspace(node.id.workspace());
+                    audit(state, event).await?;
+                }
+                filtered_nodes.push(node);
+            } else {
+                redacted_count += 1;
+            }
+        }
+        if redacted_count > 0 {
+            let event = AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(actor),
+                AuditAction::SensitiveRead,
+                "query_nodes",
+                AuditOutcome::Denied,
+            )
+            .with_details(AuditDetails::Redaction {
+                redacted_count,
+                agent_max_sensitivity: max_sensitivity,
+            });
+            audit(state, event).await?;
+        }
+        result.nodes = filtered_nodes;
+    }
+
+    Ok(result)
+}
+
+/// Page size when collecting all accepted nodes (or all nodes of a type).
+const ACCEPTED_PAGE_SIZE: u32 = 500;
+
+/// Every accepted node the caller may read (sensitivity-filtered like [`query_nodes`]).
+pub async fn accepted_nodes(
+    state: &AppState,
+    actor: &ActorContext,
+) -> Result<Vec<ContextNode>, ApiError> {
+    let mut nodes = Vec::new();
+    let mut offset = 0;
+    loop {
+        let query = NodeQuery {
+            status: Some(vec![NodeStatus::Accepted]),
+            limit: Some(ACCEPTED_PAGE_SIZE),
+            offset: Some(offset),
+            ..Default::default()
+        };
+        let page = query_nodes(state, actor, query).await?;
+        nodes.extend(page.nodes);
+        if !page.has_more {
+            return Ok(nodes);
+        }
+        offset += ACCEPTED_PAGE_SIZE;
+    }
+}
+
+/// Every node of `node_type` (in `namespace`, if given) the caller may read, archived
+/// ones left out (sensitivity-filtered like [`query_nodes`]).
+pub async fn nodes_of_type(
+    state: &AppState,
+    actor: &ActorContext,
+    node_type: NodeType,
+    namespace: Option<String>,
+) -> Result<Vec<ContextNode>, ApiError> {
+    let mut nodes = Vec::new();
+    let mut offset = 0;
+    loop {
+        let query = NodeQuery {
+            r#type: Some(vec![node_type.clone()]),
+            namespace: namespace.clone(),
+            limit: Some(ACCEPTED_PAGE_SIZE),
+            offset: Some(offset),
+            ..Default::default()
+        };
+        let page = query_nodes(state, actor, query).await?;
+        nodes.extend(page.nodes);
+        if !page.has_more {
+            return Ok(nodes);
+        }
+        offset += ACCEPTED_PAGE_SIZE;
+    }
+}
+
+/// Get one node, redacted for agents above their sensitivity clearance.
+pub async fn get_node(
+    state: &AppState,
+    actor: &ActorContext,
+    node_id: &NodeId,
+) -> Result<NodeRead, ApiError> {
+    require_route(state, actor, "GET /nodes/:id", Role::Reader)?;
+
+    let key = node_id.key();
+    let node = state
+        .store
+        .get_node(node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", key)))?;
+
+    Ok(read_node(state, actor, node).await)
+}
+
+/// Apply agent sensitivity redaction (and read logging) to a node the caller already
+/// holds Reader access for, e.g. a node nested in a proposal's operations.
+pub async fn read_node(state: &AppState, actor: &ActorContext, node: ContextNode) -> NodeRead {
+    let key = node.id.key();
+
+    // Agent sensitivity redaction and read logging
+    if actor.actor_type == ActorType::Agent {
+        let node_sensitivity = node.metadata.sensitivity.unwrap_or(Sensitivity::Internal);
+        let max_sensitivity = policy::agent_max_sensitivity(&state.runtime.policies.get());
+
+        if !sensitivity::agent_can_read(node_sensitivity, max_sensitivity) {
+            let event = AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(actor),
+                AuditAction::SensitiveRead,
+                &key,
+                AuditOutcome::Denied,
+            )
+            .in_workspace(node.id.workspace())
+            .with_details(AuditDetails::SensitiveRead {
+                node_sensitivity,
+                agent_max_sensitivity: Some(max_sensitivity),
+            });
+            if let Err(e) = audit(state, event).await {
+                tracing::warn!(node = %key, error = %e, "sensitive read denial not audited");
+            }
+            return NodeRead::Redacted {
+                id: node.id,
+                node_type: node.node_type,
+                status: node.status,
+                sensitivity: node_sensitivity,
+            };
+        }
+
+        // Log agent read (even for non-restricted) of confidential+ content
+        if node_sensitivity >= Sensitivity::Confidential {
+            let event = AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(actor),
+                AuditAction::SensitiveRead,
+                &key,
+                AuditOutcome::Success,
+            )
+            .in_workspace(node.id.workspace())
+            .with_details(AuditDetails::SensitiveRead {
+                node_sensitivity,
+                agent_max_sensitivity: None,
+            });
+            // An unaudited read of confidential content is not served.
+            if let Err(e) = audit(state, event).await {
+                tracing::warn!(node = %key, error = %e, "sensitive read not audited; redacted");
+                return NodeRead::Redacted {
+                    id: node.id,
+                    node_type: node.node_type,
+                    status: node.status,
+                    sensitivity: node_sensitivity,
+                };
+            }
+        }
+    }
+
+    NodeRead::Full(Box::new(node))
+}
+
+/// Highest node sensitivity the caller is served unredacted: agents are capped by the
+/// `egress_control` policy, other actors read everything.
+pub fn sensitivity_clearance(state: &AppState, actor: &ActorContext) -> Sensitivity {
+    if actor.actor_type == ActorType::Agent {
+        policy::agent_max_sensitivity(&state.runtime.policies.get())
+    } else {
+        Sensitivity::Restricted
+    }
+}
+
+/// Label of a node, live or in the trash; None once it has been purged.
+fn node_sensitivity(
+    live: Option<ContextNode>,
+    trash: &[ContextNode],
+    id: &NodeId,
+) -> Option<Sensitivity> {
+    live.as_ref()
+        .or_else(|| trash.iter().find(|n| &n.id == id))
+        .map(|n| n.metadata.sensitivity.unwrap_or_default())
+}
+
+/// Sensitivity of what an audit event is about: the node it names (deleted ones by
+/// their label in the trash), or the most sensitive node a proposal touches (nodes it
+/// creates by their own label, purged ones as restricted). None for other resources.
+async fn resource_sensitivity(
+    state: &AppState,
+    trash: &[ContextNode],
+    resource_id: &str,
+) -> Result<Option<Sensitivity>, ApiError> {
+    let id = NodeId::from_key(resource_id);
+    if let Some(level) = node_sensitivity(state.store.get_node(&id).await?, trash, &id) {
+        return Ok(Some(level));
+    }
+    let Some(proposal) = state.store.get_proposal(resource_id).await? else {
+        return Ok(None);
+    };
+    let mut highest = None;
+    for op in &proposal.operations {
+        let level = match op {
+            Operation::Create { node, .. } => node.metadata.sensitivity.unwrap_or_default(),
+            Operation::Update { node_id, .. }
+            | Operation::Delete { node_id, .. }
+            | Operation::StatusChange { node_id, .. } => {
+                node_sensitivity(state.store.get_node(node_id).await?, trash, node_id)
+                    .unwrap_or(Sensitivity::Restricted)
+            }
+        };
+        highest = highest.max(Some(level));
+    }
+    Ok(highest)
+}
+
+/// Replace the details (content snippets, field changes, …) of audit events about
+/// resources above the caller's sensitivity clearance. Who did what and when stays
+/// visible. Node events whose node has been purged are redacted as restricted: the
+/// label went with it.
+pub async fn redact_audit(
+    state: &AppState,
+    actor: &ActorContext,
+    events: &mut [AuditEvent],
+) -> Result<(), ApiError> {
+    let clearance = sensitivity_clearance(state, actor);
+    if clearance == Sensitivity::Restricted || events.iter().all(|e| e.details.is_none()) {
+        return Ok(());
+    }
+    let trash = state.store.list_trash().await?;
+    let mut levels: HashMap<String, Option<Sensitivity>> = HashMap::new();
+    for event in events.iter_mut().filter(|e| e.details.is_some()) {
+        let level = match levels.get(&event.resource_id) {
+            Some(level) => *level,
+            None => {
+                let level = resource_sensitivity(state, &trash, &event.resource_id).await?;
+                levels.insert(event.resource_id.clone(), level);
+                level
+            }
+        };
+        let about_node = matches!(
+            event.action,
+            AuditAction::NodeCreated
+                | AuditAction::NodeUpdated
+                | AuditAction::NodeDeleted
+                | AuditAction::NodeRestored
+                | AuditAction::SensitiveRead
+        );
+        let level = level.or(about_node.then_some(Sensitivity::Restricted));
+        if let Some(level) = level.filter(|l| !sensitivity::agent_can_read(*l, clearance)) {
+            event.details = serde_json::to_value(AuditDetails::Redacted {
+                redacted: true,
+                sensitivity: level,
+            })
+            .ok();
+        }
+    }
+    Ok(())
+}
+
+/// All audit events for a resource (node key or proposal ID), redacted to the caller's
+/// clearance.
+pub async fn get_provenance(
+    state: &AppState,
+    actor: &ActorContext,
+    resource_id: &str,
+) -> Result<Vec<AuditEvent>, ApiError> {
+    require_route(state, actor, "GET /nodes/:id/provenance", Role::Reader)?;
+    let mut events = state
+        .store
+        .query_audit(None, None, Some(resource_id), None, None, Some(1000), None)
+        .await?
+        .events;
+    redact_audit(state, actor, &mut events).await?;
+    Ok(events)
+}
+
+/// Last change of each field of a node (Reader, like provenance). Fields set before
+/// changes were recorded have no entry.
+pub async fn node_blame(
+    state: &AppState,
+    actor: &ActorContext,
+    node_id: &NodeId,
+) -> Result<NodeBlame, ApiError> {
+    require_route(state, actor, "GET /nodes/:id/blame", Role::Reader)?;
+    let node = state
+        .store
+        .get_node(node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", node_id.key())))?;
+    Ok(NodeBlame {
+        node_id: node.id,
+        version: node.metadata.version,
+        fields: node.metadata.field_changes.unwrap_or_default(),
+    })
+}
+
+/// `GET /nodes/:id/blame` response.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeBlame {
+    pub node_id: NodeId,
+    pub version: u32,
+    pub fields: std::collections::BTreeMap<String, FieldBlame>,
+}
+
+/// Applied proposals that touched a node, oldest first (Reader, like blame). Deleted
+/// nodes keep their history; a node never touched by a proposal is not found.
+pub async fn node_proposals(
+    state: &AppState,
+    actor: &ActorContext,
+    node_id: &NodeId,
+) -> Result<NodeProposals, ApiError> {
+    require_route(state, actor, "GET /nodes/:id/proposals", Role::Reader)?;
+    let proposals = state.store.get_node_proposals(node_id).await?;
+    if proposals.is_empty() && state.store.get_node(node_id).await?.is_none() {
+        return Err(ApiError::NotFound(format!(
+            "node {} not found",
+            node_id.key()
+        )));
+    }
+    Ok(NodeProposals {
+        node_id: node_id.clone(),
+        proposals,
+    })
+}
+
+/// `GET /nodes/:id/proposals` response.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeProposals {
+    pub node_id: NodeId,
+    pub proposals: Vec<crate::store::NodeProposal>,
+}
+
+/// Open proposals (page of), with the total count.
+pub async fn list_open_proposals(
+    state: &AppState,
+    actor: &ActorContext,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<ProposalListResponse, ApiError> {
+    require_route(state, actor, "GET /proposals", Role::Reader)?;
+    let full = state.store.get_open_proposals().await?;
+    Ok(proposal_page(full, limit, offset))
+}
+
+/// One page of `full` (50 by default, at most 1000).
+fn proposal_page(
+    full: Vec<Proposal>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> ProposalListResponse {
+    let total = full.len() as u64;
+    let limit = limit.unwrap_or(50).min(1000);
+    let offset = (offset.unwrap_or(0) as usize).min(full.len());
+    let end = (offset + limit as usize).min(full.len());
+    let has_more = end < full.len();
+    ProposalListResponse {
+        proposals: full[offset..end].to_vec(),
+        total,
+        limit,
+        offset: offset as u32,
+        has_more,
+    }
+}
+
+/// Quarantined proposals awaiting triage, oldest first; only those in `workspace` when
+/// given.
+async fn quarantined(state: &AppState, workspace: Option<&str>) -> Result<Vec<Proposal>, ApiError> {
+    let mut list = state
+        .store
+        .query_proposals(ProposalQuery {
+            status: Some(vec![ProposalStatus::Quarantined]),
+            limit: Some(u32::MAX),
+            ..Default::default()
+        })
+        .await?;
+    list.retain(|p| {
+        p.status == ProposalStatus::Quarantined && workspace.is_none_or(|ws| p.workspace() == ws)
+    });
+    list.sort_by(|a, b| a.metadata.created_at.cmp(&b.metadata.created_at));
+    Ok(list)
+}
+
+/// The quarantine queue (`agent_quarantine` policy): agent proposals no reviewer sees
+/// in `GET /proposals` until they are triaged.
+pub async fn list_quarantined_proposals(
+    state: &AppState,
+    actor: &ActorContext,
+    workspace: Option<&str>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<ProposalListResponse, ApiError> {
+    require_route(state, actor, "GET /proposals/quarantine", Role::Reviewer)?;
+    let full = quarantined(state, workspace).await?;
+    Ok(proposal_page(full, limit, offset))
+}
+
+/// Most proposals one `POST /proposals/triage` names.
+pub const MAX_TRIAGE_PROPOSALS: usize = 100;
+
+/// Outcome of triaging one proposal: the status and body a single request would get.
+#[derive(Debug, serde::Serialize)]
+pub struct TriageResult {
+    pub id: String,
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+/// Triage quarantined proposals (humans only): release them into review or reject them.
+/// `proposal_ids` names them; without it, the whole queue of `workspace` is triaged.
+/// Each proposal is triaged on its own, so one that cannot be does not stop the rest.
+pub async fn triage_proposals(
+    state: &AppState,
+    actor: &ActorContext,
+    proposal_ids: Option<Vec<String>>,
+    workspace: Option<&str>,
+    action: TriageAction,
+    reason: Option<String>,
+) -> Result<Vec<TriageResult>, ApiError> {
+    require_route(state, actor, "POST /proposals/triage", Role::Reviewer)?;
+    rbac::reject_agent(actor, "triage proposals")?;
+    read_only::check_writable(&state.runtime.read_only)?;
+    let ids = match (proposal_ids, workspace) {
+        (Some(ids), _) if ids.len() > MAX_TRIAGE_PROPOSALS => {
+            return Err(ApiError::Invalid(format!(
+                "proposalIds: {} given; at most {} are triaged at once",
+                ids.len(),
+                MAX_TRIAGE_PROPOSALS
+            )))
+        }
+        (Some(ids), _) => ids,
+        (None, Some(workspace)) => quarantined(state, Some(workspace))
+            .await?
+            .into_iter()
+            .map(|p| p.id)
+            .collect(),
+        (None, None) => {
+            return Err(ApiError::Invalid(
+                "give proposalIds, or a workspace whose queue to triage".to_string(),
+            ))
+        }
+    };
+    let reason = reason.filter(|r| !r.trim().is_empty());
+    let mut results = Vec::with_capacity(ids.len());
+    for id in ids {
+        let (status, body) = match triage_proposal(state, actor, &id, action, reason.clone()).await
+        {
+            Ok(proposal) => (200, serde_json::to_value(&proposal).unwrap_or_default()),
+            Err(e) => {
+                let (status, body) = e.status_and_body();
+                (status.as_u16(), body)
+            }
+        };
+        results.push(TriageResult { id, status, body });
+    }
+    Ok(results)
+}
+
+/// Release or reject one quarantined proposal; a released one is announced to Slack
+/// like a new open proposal.
+async fn triage_proposal(
+    state: &AppState,
+    actor: &ActorContext,
+    id: &str,
+    action: TriageAction,
+    reason: Option<String>,
+) -> Result<Proposal, ApiError> {
+    let mut proposal = state
+        .store
+        .get_proposal(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("proposal {} not found", id)))?;
+    let status = lifecycle::next_status(proposal.status, Transition::Triage(action))
+        .map_err(StoreError::from)?;
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ProposalTriaged,
+        id,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::Triage { action, reason });
+    let batch = WriteBatch::new()
+        .triage_proposal(id, action)
+        .audit(event)
+        .publish(server_event(
+            EventKind::ProposalUpdated { status },
+            id,
+            actor,
+        ));
+    execute(state, batch.in_workspace(proposal.workspace())).await?;
+    proposal.status = status;
+    crate::api::slack::request_review(state, &proposal).await;
+    Ok(proposal)
+}
+
+pub async fn get_proposal(
+    state: &AppState,
+    actor: &ActorContext,
+    id: &str,
+) -> Result<Proposal, ApiError> {
+    require_route(state, actor, "GET /proposals/:id", Role::Reader)?;
+
+    state
+        .store
+        .get_proposal(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("proposal {} not found", id)))
+}
+
+/// Create a proposal after evaluating create-time policies. The proposal and the nodes it
+/// creates get a ULID where the id was left empty (`crate::ids`); returns the proposal
+/// as stored.
+pub async fn create_proposal(
+    state: &AppState,
+    actor: &ActorContext,
+    mut proposal: Proposal,
+) -> Result<Proposal, ApiError> {
+    // Only `POST /proposals/auto-merge` records merged authors.
+    proposal.metadata.authors = None;
+    create_server_proposal(state, actor, proposal).await
+}
+
+/// [`create_proposal`] for a proposal the server built, keeping its `metadata.authors`.
+pub(crate) async fn create_server_proposal(
+    state: &AppState,
+    actor: &ActorContext,
+    mut proposal: Proposal,
+) -> Result<Proposal, ApiError> {
+    require_route(state, actor, "POST /proposals", Role::Contributor)?;
+    read_only::check_writable(&state.runtime.read_only)?;
+    rbac::attribute(
+        actor,
+        "metadata.createdBy",
+        &mut proposal.metadata.created_by,
+    )?;
+    rbac::attribute(
+        actor,
+        "metadata.modifiedBy",
+        &mut proposal.metadata.modified_by,
+    )?;
+    ids::assign(&mut proposal).map_err(ApiError::Invalid)?;
+    // Only `POST /proposals/:id/forge` links a request; only an auto-merge supersedes.
+    proposal.metadata.forge = None;
+    proposal.metadata.superseded_by = None;
+    if let Some(issue) = validate::structural_issues(&proposal.operations).first() {
+        return Err(ApiError::Invalid(format!(
+            "operation {}: {}",
+            issue.operation_id, issue.message
+        )));
+    }
+    stamper(state)
+        .proposal(&mut proposal)
+        .map_err(ApiError::Invalid)?;
+    proposal.metadata.complexity = Some(complexity::assess(&*state.store, &proposal).await?);
+
+    // Policy: evaluate on create
+    let mut violations = policy::evaluate_on_create(
+        &proposal,
+        actor_type_str(actor),
+        &state.runtime.policies.get(),
+    );
+    violations.extend(
+        content_rules::violations(
+            &*state.store,
+            &state.runtime.config.get().content_rules,
+            &proposal.operations,
+        )
+        .await?,
+    );
+    check_policies(state, actor, &proposal.id, proposal.workspace(), violations).await?;
+    if policy::quarantines(
+        &proposal,
+        actor_type_str(actor),
+        &state.runtime.policies.get(),
+    ) {
+        proposal.status = ProposalStatus::Quarantined;
+    }
+
+    let proposal_id = proposal.id.clone();
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ProposalCreated,
+        &proposal_id,
+        AuditOutcome::Success,
+    );
+    let mut batch = WriteBatch::new()
+        .create_proposal(proposal.clone())
+        .audit(event)
+        .publish(server_event(
+            EventKind::ProposalUpdated {
+                status: proposal.status,
+            },
+            &proposal_id,
+            actor,
+        ));
+    let comments = proposal.comments.as_deref().unwrap_or_default();
+    for event in comments_added(actor, &proposal_id, &[], comments) {
+        batch = batch.audit(event);
+    }
+    execute(state, batch.in_workspace(proposal.workspace())).await?;
+    crate::api::slack::request_review(state, &proposal).await;
+    Ok(proposal)
+}
+
+/// Propose archiving a node: an open proposal with one `status-change` operation to
+/// `archived`, reviewed and applied like any other. Returns the proposal.
+pub async fn archive_node(
+    state: &AppState,
+    actor: &ActorContext,
+    node_id: &NodeId,
+    reason: Option<String>,
+) -> Result<Proposal, ApiError> {
+    require_route(state, actor, "POST /nodes/:id/archive", Role::Contributor)?;
+    let key = node_id.key();
+    let node = state
+        .store
+        .get_node(node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", key)))?;
+    if node.status == NodeStatus::Archived {
+        return Err(ApiError::Invalid(format!(
+            "node {} is already archived",
+            key
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let proposal = Proposal {
+        id: format!("archive-{}", uuid::Uuid::new_v4()),
+        status: ProposalStatus::Open,
+        operations: vec![Operation::StatusChange {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id: node_id.clone(),
+            new_status: NodeStatus::Archived,
+            old_status: node.status,
+            reason: reason.clone(),
+        }],
+        metadata: ProposalMetadata {
+            created_at: now.clone(),
+            created_by: actor.actor_id.clone(),
+            modified_at: now,
+            modified_by: actor.actor_id.clone(),
+            rationale: reason,
+            required_approvers: None,
+            approved_by: None,
+            base_versions: Some([(key, node.metadata.version)].into()),
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
+        },
+        comments: None,
+        relations: None,
+        applied: None,
+    };
+    create_proposal(state, actor, proposal).await
+}
+
+/// `?include=` names of `GET /proposals/:id`.
+pub const PROPOSAL_INCLUDES: &[&str] = &["reviews", "comments", "conflicts"];
+
+/// Related resources of a proposal the caller already read, by `include` name: its
+/// reviews (as `GET /proposals/:id/reviews`), comments and conflicts with other open
+/// proposals.
+pub async fn proposal_includes(
+    state: &AppState,
+    actor: &ActorContext,
+    proposal_id: &str,
+    include: &[String],
+) -> Result<serde_json::Map<String, serde_json::Value>, ApiError> {
+    let mut included = serde_json::Map::new();
+    for name in include {
+        let value = match name.as_str() {
+            "reviews" => serde_json::to_value(get_review_history(state, actor, proposal_id).await?),
+            "comments" => {
+                serde_json::to_value(state.store.get_proposal_comments(proposal_id).await?)
+            }
+            "conflicts" => {
+                let conflicts = state.store.detect_conflicts(proposal_id).await?;
+                let event = AuditEvent::new(
+                    &actor.actor_id,
+                    actor_type_str(actor),
+                    AuditAction::ConflictsDetected,
+                    proposal_id,
+                    AuditOutcome::Success,
+                )
+                .with_details(AuditDetails::Conflicts {
+                    conflicts: conflicts.conflicts.len(),
+                });
+                audit(state, event).await?;
+                serde_json::to_value(conflicts)
+            }
+            _ => continue,
+        };
+        included.insert(name.clone(), value.unwrap_or_default());
+    }
+    Ok(included)
+}
+
+/// Merge preview of proposals (`POST /proposals/merge`): the field changes that combine
+/// cleanly and the ones that conflict. Nothing is written; the computation is audited.
+pub async fn merge_proposals(
+    state: &AppState,
+    actor: &ActorContext,
+    proposal_ids: &[String],
+) -> Result<MergeResult, ApiError> {
+    require_route(state, actor, "POST /proposals/merge", Role::Reviewer)?;
+    if proposal_ids.len() < 2 {
+        return Err(ApiError::Invalid(
+            "proposalIds: at least two proposals are merged".to_string(),
+        ));
+    }
+    for id in proposal_ids {
+        get_proposal(state, actor, id).await?;
+    }
+    let result = state.store.merge_proposals(proposal_ids).await?;
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ProposalsMerged,
+        &proposal_ids.join(","),
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::Merge {
+        proposal_ids: proposal_ids.to_vec(),
+        merged: result.merged.len(),
+        auto_merged: result.auto_merged.len(),
+        conflicts: result.conflicts.len(),
+    });
+    audit(state, event).await?;
+    Ok(result)
+}
+
+/// Audit an event stream opened over `transport` (`sse`, `websocket`), with its filters.
+pub async fn audit_subscription(
+    state: &AppState,
+    actor: &ActorContext,
+    transport: &str,
+    filters: AuditDetails,
+) -> Result<(), StoreError> {
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::EventsSubscribed,
+        transport,
+        AuditOutcome::Success,
+    )
+    .with_details(filters);
+    audit(state, event).await
+}
+
+/// `?include=` names of `GET /nodes/:id`.
+pub const NODE_INCLUDES: &[&str] = &["relationships.targets", "lock"];
+
+/// Related resources of a node the caller already read, by `include` name: the nodes
+/// its relationships point to, each as `get_node` returns it to the caller (redacted
+/// above clearance), and its edit lock (`null` when unlocked). Targets that no longer
+/// exist are left out.
+pub async fn node_includes(
+    state: &AppState,
+    actor: &ActorContext,
+    read: &NodeRead,
+    include: &[String],
+) -> Result<serde_json::Map<String, serde_json::Value>, ApiError> {
+    let mut included = serde_json::Map::new();
+    for name in include {
+        let value = match name.as_str() {
+            "relationships.targets" => {
+                let mut targets: Vec<serde_json::Value> = Vec::new();
+                if let NodeRead::Full(node) = read {
+                    let mut seen = std::collections::HashSet::new();
+                    for relationship in node.relationships.iter().flatten() {
+                        if !seen.insert(relationship.target.key()) {
+                            continue;
+                        }
+                        match get_node(state, actor, &relationship.target).await {
+                            Ok(target) => targets.push(target.to_json()),
+                            Err(ApiError::NotFound(_)) => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+                serde_json::Value::Array(targets)
+            }
+            "lock" => serde_json::to_value(node_locks::node_lock(state, read.node_id()).await?)
+                .unwrap_or_default(),
+            _ => continue,
+        };
+        included.insert(name.clone(), value);
+    }
+    Ok(included)
+}
+
+pub async fn get_review_history(
+    state: &AppState,
+    actor: &ActorContext,
+    proposal_id: &str,
+) -> Result<Vec<Review>, ApiError> {
+    require_route(state, actor, "GET /proposals/:id/reviews", Role::Reader)?;
+    Ok(state.store.get_review_history(proposal_id).await?)
+}
+
+/// Submit a review (humans only), then let the multi-approval policy settle the
+/// proposal's status.
+pub async fn submit_review(
+    state: &AppState,
+    actor: &ActorContext,
+    proposal_id: &str,
+    mut review: Review,
+) -> Result<Review, ApiError> {
+    require_route(state, actor, "POST /proposals/:id/review", Role::Reviewer)?;
+    rbac::reject_agent(actor, "submit review")?;
+    read_only::check_writable(&state.runtime.read_only)?;
+    stamper(state)
+        .review(&mut review)
+        .map_err(ApiError::Invalid)?;
+
+    if review.proposal_id != proposal_id {
+        return Err(ApiError::Invalid("proposal_id mismatch".to_string()));
+    }
+
+    // Who reviewed, and in what role, comes from the authenticated actor, never the body.
+    review.reviewer = actor.actor_id.clone();
+    review.reviewer_role = actor.highest_role().map(|r| r.as_str().to_string());
+    review.reviewer_groups = state
+        .store
+        .directory_access(&actor.actor_id)
+        .await?
+        .map(|access| access.groups)
+        .filter(|groups| !groups.is_empty());
+    let current = state.store.get_proposal(proposal_id).await?;
+    let delegations = match &current {
+        Some(proposal) => delegations_to(state, proposal, &actor.actor_id).await?,
+        None => Vec::new(),
+    };
+    review.on_behalf_of = (!delegations.is_empty())
+        .then(|| delegations.iter().map(|d| d.delegator.clone()).collect());
+    let mut event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ReviewSubmitted,
+        proposal_id,
+        AuditOutcome::Success,
+    );
+    if let Some(on_behalf_of) = &review.on_behalf_of {
+        event = event.with_details(AuditDetails::Delegated {
+            delegate: actor.actor_id.clone(),
+            on_behalf_of: on_behalf_of.clone(),
+            delegation_ids: delegations.iter().map(|d| d.id.clone()).collect(),
+        });
+    }
+    // The review policies with this review: an approval leaves the proposal open while
+    // they want more (`min_approvals`, required approvers); otherwise they settle its
+    // status (multi-approval) in the same write as the review.
+    let (verdict, violations) = match &current {
+        Some(proposal) => {
+            let mut reviews = state.store.get_review_history(proposal_id).await?;
+            reviews.push(review.clone());
+            policy::evaluate_on_review(proposal, &reviews, &state.runtime.policies.get())
+        }
+        None => (None, Vec::new()),
+    };
+    let pending = current.is_some() && review.action == ReviewAction::Accept && verdict.is_none();
+    // The status the review moves the proposal to; the batch refuses a review that
+    // cannot be made.
+    let workspace = current
+        .as_ref()
+        .map_or(DEFAULT_WORKSPACE, |p| p.workspace())
+        .to_string();
+    let current = current.map_or(ProposalStatus::Open, |p| p.status);
+    let status = match lifecycle::next_status(current, Transition::Review(review.action)) {
+        Ok(_) if pending => current,
+        Ok(next) => next,
+        Err(_) => current,
+    };
+    let reviewed = server_event(
+        EventKind::ReviewSubmitted {
+            action: review.action,
+            status,
+        },
+        proposal_id,
+        actor,
+    );
+    let batch = if pending {
+        WriteBatch::new().submit_pending_review(review.clone())
+    } else {
+        WriteBatch::new().submit_review(review.clone())
+    };
+    let mut batch = batch.audit(event).publish(reviewed);
+    let comments = review.comments.as_deref().unwrap_or_default();
+    for event in comments_added(actor, proposal_id, &[], comments) {
+        batch = batch.audit(event);
+    }
+    if let Some(settled) =
+        verdict.filter(|s| matches!(s, ProposalStatus::Accepted | ProposalStatus::Rejected))
+    {
+        // Accepted: what is left is what rules that are not enforced would have held.
+        if settled == ProposalStatus::Accepted {
+            if let Some(event) = policy_warning_event(actor, proposal_id, &workspace, violations) {
+                batch = batch.audit(event);
+            }
+        }
+        let event = AuditEvent::new(
+            &actor.actor_id,
+            actor_type_str(actor),
+            AuditAction::PolicyEvaluated,
+            proposal_id,
+            AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::StatusTransition {
+            from: status,
+            to: settled,
+        });
+        batch = batch
+            .update_proposal(proposal_id, ProposalPatch::status(settled))
+            .audit(event)
+            .publish(server_event(
+                EventKind::ProposalUpdated { status: settled },
+                proposal_id,
+                actor,
+            ));
+    }
+    execute(state, batch.in_workspace(&workspace)).await?;
+
+    Ok(review)
+}
+
+/// The active delegations `delegate` holds from the proposal's required approvers, one
+/// per approver.
+async fn delegations_to(
+    state: &AppState,
+    proposal: &Proposal,
+    delegate: &str,
+) -> Result<Vec<Delegation>, ApiError> {
+    let now = chrono::Utc::now();
+    let mut held = Vec::new();
+    for approver in proposal.metadata.required_approvers.iter().flatten() {
+        if approver == delegate {
+            continue;
+        }
+        let delegations = state.store.get_delegations(approver).await?;
+        held.extend(
+            delegations
+                .into_iter()
+                .find(|d| d.delegate == delegate && d.is_active(now)),
+        );
+    }
+    Ok(held)
+}
+
+/// Apply an accepted proposal (humans only) after evaluating apply-time policies.
+/// `applied_by` defaults to the calling actor. Holds the `apply:{id}` lease meanwhile, so
+/// a concurrent apply of the same proposal gets a conflict.
+pub async fn apply_proposal(
+    state: &AppState,
+    actor: &ActorContext,
+    id: &str,
+    applied_by: Option<String>,
+) -> Result<(), ApiError> {
+    apply(state, actor, id, applied_by, None).await.map(|_| ())
+}
+
+/// Break-glass apply of an open or accepted proposal (Admins only): skips review
+/// (`min_approvals` and the other review policies) and `change_window`, but needs a
+/// `justification`. The bypassed checks and the justification are audited on
+/// `proposal_applied`, an `emergency_applied` event and a Slack alert go out, and a
+/// follow-up proposal is opened for the post-hoc review. Returns the follow-up.
+pub async fn emergency_apply(
+    state: &AppState,
+    actor: &ActorContext,
+    id: &str,
+    applied_by: Option<String>,
+    justification: &str,
+) -> Result<Proposal, ApiError> {
+    if !actor.has_role(&Role::Admin) {
+        return Err(rbac::Forbidden(format!(
+            "emergency apply needs the admin role: actor {}",
+            actor.actor_id
+        ))
+        .into());
+    }
+    let justification = justification.trim();
+    if justification.is_empty() {
+        return Err(ApiError::Invalid(
+            "emergency apply needs a justification".to_string(),
+        ));
+    }
+    let follow_up = apply(state, actor, id, applied_by, Some(justification)).await?;
+    follow_up.ok_or_else(|| ApiError::NotFound(format!("proposal {} not found", id)))
+}
+
+/// Rules an emergency apply skips.
+const EMERGENCY_BYPASSED_RULES: [&str; 1] = ["change_window"];
+
+/// [`apply_proposal`], and for an emergency (`justification` given) the break-glass
+/// path of [`emergency_apply`]; returns the emergency's follow-up proposal.
+async fn apply(
+    state: &AppState,
+    actor: &ActorContext,
+    id: &str,
+    applied_by: Option<String>,
+    justification: Option<&str>,
+) -> Result<Option<Proposal>, ApiError> {
+    require_route(state, actor, "POST /proposals/:id/apply", Role::Applier)?;
+    rbac::reject_agent(actor, "apply proposal")?;
+    read_only::check_writable(&state.runtime.read_only)?;
+
+    // Policy: evaluate on apply
+    let proposal = state.store.get_proposal(id).await?;
+    let workspace = proposal
+        .as_ref()
+        .map_or(DEFAULT_WORKSPACE, |p| p.workspace())
+        .to_string();
+    let mut bypassed = Vec::new();
+    if let Some(ref proposal) = proposal {
+        let unreviewed = justification.is_some() && proposal.status == ProposalStatus::Open;
+        // Refuse unaccepted proposals before policies can report unrelated violations.
+        if proposal.status != ProposalStatus::Applied && !unreviewed {
+            lifecycle::next_status(proposal.status, Transition::Apply).map_err(StoreError::from)?;
+        }
+        let mut violations = policy::evaluate_on_apply(
+            proposal,
+            actor_type_str(actor),
+            &state.runtime.policies.get(),
+        );
+        if let Some(message) = forge::pipeline_blocker(proposal, &state.runtime.config.get().forge)
+        {
+            violations.push(policy::PolicyViolation::new("forge_pipeline", message));
+        }
+        violations.extend(
+            content_rules::violations(
+                &*state.store,
+                &state.runtime.config.get().content_rules,
+                &proposal.operations,
+            )
+            .await?,
+        );
+        if justification.is_some() {
+            (bypassed, violations) = violations
+                .into_iter()
+                .partition(|v| EMERGENCY_BYPASSED_RULES.contains(&v.rule.as_str()));
+        }
+        if unreviewed {
+            bypassed.push(policy::PolicyViolation::new(
+                "min_approvals",
+                "applied before review accepted it".to_string(),
+            ));
+        }
+        check_policies(state, actor, id, &workspace, violations).await?;
+    }
+
+    let field_changes = match &proposal {
+        Some(proposal) => audited_field_changes(state, proposal).await?,
+        None => Vec::new(),
+    };
+    // Status (and task state) changes outside the transition tables need the proposal to
+    // ask for them and an Admin to apply it.
+    let forced: Vec<serde_json::Value> = field_changes
+        .iter()
+        .filter(|c| is_forced_transition(c))
+        .cloned()
+        .collect();
+    if !forced.is_empty() {
+        let requested = proposal
+            .as_ref()
+            .and_then(|p| p.metadata.force_status_transitions)
+            == Some(true);
+        if !requested {
+            let violations: Vec<policy::PolicyViolation> = forced
+                .iter()
+                .map(|c| {
+                    policy::PolicyViolation::new(
+                        if c["field"] == "state" {
+                            "task_state_transition"
+                        } else {
+                            "node_status_transition"
+                        },
+                        format!(
+                            "node {} cannot go from {} to {} (set metadata.forceStatusTransitions and apply as Admin to force it)",
+                            c["node"].as_str().unwrap_or_default(),
+                            c["from"].as_str().unwrap_or_default(),
+                            c["to"].as_str().unwrap_or_default()
+                        ),
+                    )
+                })
+                .collect();
+            return Err(policy_violation(state, actor, id, &workspace, violations).await);
+        }
+        if !actor.has_role(&Role::Admin) {
+            let event = AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(actor),
+                AuditAction::ProposalApplied,
+                id,
+                AuditOutcome::Denied,
+            )
+            .in_workspace(&workspace)
+            .with_details(AuditDetails::Applied {
+                field_changes,
+                forced_transitions: forced,
+                justification: justification.map(str::to_string),
+                bypassed,
+            });
+            audit(state, event).await?;
+            return Err(rbac::Forbidden(format!(
+                "proposal {} forces node status transitions; only an Admin can apply it",
+                id
+            ))
+            .into());
+        }
+    }
+    let details = AuditDetails::Applied {
+        field_changes: field_changes.clone(),
+        forced_transitions: forced,
+        justification: justification.map(str::to_string),
+        bypassed,
+    };
+    let applied_by = applied_by.unwrap_or_else(|| actor.actor_id.clone());
+    // An emergency apply opens its post-hoc review in the same write: both or neither.
+    let follow_up = match (justification, &proposal) {
+        (Some(justification), Some(proposal)) => {
+            Some(post_hoc_review(state, actor, proposal, justification).await?)
+        }
+        _ => None,
+    };
+    // Another request may be applying it right now.
+    let lease = format!("apply:{}", id);
+    let holder = state.cluster.acquire_exclusive(&lease).await?;
+    let unreviewed = justification.is_some()
+        && proposal
+            .as_ref()
+            .is_some_and(|p| p.status == ProposalStatus::Open);
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ProposalApplied,
+        id,
+        AuditOutcome::Success,
+    )
+    .with_details(details);
+    // Open goes straight to applied: a failed apply leaves it open, not accepted.
+    let batch = if unreviewed {
+        WriteBatch::new().apply_unreviewed(id, &applied_by)
+    } else {
+        WriteBatch::new().apply_proposal(id, &applied_by)
+    };
+    let mut batch = batch.audit(event).publish(server_event(
+        EventKind::ProposalUpdated {
+            status: ProposalStatus::Applied,
+        },
+        id,
+        actor,
+    ));
+    if let (Some(follow_up), Some(justification)) = (&follow_up, justification) {
+        batch = batch
+            .create_proposal(follow_up.clone())
+            .audit(AuditEvent::new(
+                &actor.actor_id,
+                actor_type_str(actor),
+                AuditAction::ProposalCreated,
+                &follow_up.id,
+                AuditOutcome::Success,
+            ))
+            .publish(server_event(
+                EventKind::ProposalUpdated {
+                    status: follow_up.status,
+                },
+                &follow_up.id,
+                actor,
+            ))
+            .publish(server_event(
+                EventKind::EmergencyApplied {
+                    justification: justification.to_string(),
+                    follow_up: follow_up.id.clone(),
+                },
+                id,
+                actor,
+            ));
+    }
+    let applied = execute(state, batch.in_workspace(&workspace)).await;
+    state.cluster.release_held(&lease, &holder).await;
+    applied?;
+    if let Some(proposal) = &proposal {
+        publish_node_events(state, proposal, actor).await;
+    }
+    publish_field_events(state, proposal.as_ref(), &field_changes, actor).await;
+    if let Some(proposal) = &proposal {
+        crate::api::forge::note_applied(state, proposal, &applied_by).await;
+    }
+    match (justification, follow_up) {
+        (Some(justification), Some(follow_up)) => {
+            tracing::warn!(proposal = %id, actor = %actor.actor_id, follow_up = %follow_up.id, "emergency apply");
+            crate::api::slack::request_review(state, &follow_up).await;
+            crate::api::slack::notify_emergency(
+                state,
+                id,
+                &applied_by,
+                justification,
+                &follow_up.id,
+            )
+            .await;
+            Ok(Some(follow_up))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The post-hoc review of an emergency-applied proposal: a proposal creating an open
+/// `task` node that names the change and its justification, in the same workspace.
+/// The apply writes it; it then goes through review like any other proposal. Built by
+/// the server, so the create policies and content rules are not run on it.
+async fn post_hoc_review(
+    state: &AppState,
+    actor: &ActorContext,
+    applied: &Proposal,
+    justification: &str,
+) -> Result<Proposal, ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let namespace = (applied.workspace() != DEFAULT_WORKSPACE).then(|| applied.workspace());
+    let task = serde_json::json!({
+        "id": { "id": format!("post-hoc-review-{}", ids::ulid()), "namespace": namespace },
+        "type": "task",
+        "status": "proposed",
+        "state": "open",
+        "title": format!("Post-hoc review of emergency change {}", applied.id),
+        "content": format!(
+            "Proposal {} was applied by {} as an emergency change, without review. Justification: {}",
+            applied.id, actor.actor_id, justification
+        ),
+        "metadata": {
+            "createdAt": now, "createdBy": actor.actor_id,
+            "modifiedAt": now, "modifiedBy": actor.actor_id, "version": 0
+        }
+    });
+    let task: ContextNode = serde_json::from_value(task)
+        .map_err(|e| ApiError::Invalid(format!("post-hoc review task: {}", e)))?;
+    let mut follow_up = Proposal {
+        id: String::new(),
+        status: ProposalStatus::Open,
+        operations: vec![Operation::Create {
+            id: "op-1".to_string(),
+            order: 1,
+            node: task,
+        }],
+        metadata: ProposalMetadata {
+            created_at: now.clone(),
+            created_by: actor.actor_id.clone(),
+            modified_at: now,
+            modified_by: actor.actor_id.clone(),
+            rationale: Some(format!(
+                "Post-hoc review of emergency change {}: {}",
+                applied.id, justification
+            )),
+            required_approvers: None,
+            approved_by: None,
+            base_versions: None,
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
+        },
+        comments: None,
+        relations: Some(vec![applied.id.clone()]),
+        applied: None,
+    };
+    ids::assign(&mut follow_up).map_err(ApiError::Invalid)?;
+    stamper(state)
+        .proposal(&mut follow_up)
+        .map_err(ApiError::Invalid)?;
+    follow_up.metadata.complexity = Some(complexity::assess(&*state.store, &follow_up).await?);
+    Ok(follow_up)
+}
+
+/// `node_changed` for each node an applied proposal created or changed, with the version
+/// the apply produced. Deleted nodes are left out.
+async fn publish_node_events(state: &AppState, proposal: &Proposal, actor: &ActorContext) {
+    let mut seen = std::collections::HashSet::new();
+    for op in &proposal.operations {
+        let node_id = match op {
+            Operation::Create { node, .. } => &node.id,
+            Operation::Update { node_id, .. } | Operation::StatusChange { node_id, .. } => node_id,
+            Operation::Delete { .. } => continue,
+        };
+        if !seen.insert(node_id.key()) {
+            continue;
+        }
+        if let Ok(Some(node)) = state.store.get_node(node_id).await {
+            publish_event(
+                &state.event_bus,
+                EventKind::NodeChanged {
+                    node_id: node.id.clone(),
+                    version: node.metadata.version,
+                    proposal_id: proposal.id.clone(),
+                },
+                &node.id.key(),
+                actor,
+                Some(node.id.workspace()),
+            );
+        }
+    }
+}
+
+/// Events for the field changes of an applied proposal that someone waits on, with the
+/// field change as data:
+///
+/// - `task_assigned` / `task_state_changed`: a task's assignee or state changed;
+/// - `question_answered`: a question got an answer; `recipient` is the question's
+///   author, who asked it.
+async fn publish_field_events(
+    state: &AppState,
+    proposal: Option<&Proposal>,
+    field_changes: &[serde_json::Value],
+    actor: &ActorContext,
+) {
+    for change in field_changes {
+        if change["from"] == change["to"] {
+            continue;
+        }
+        let Ok(mut data) = serde_json::from_value::<FieldChange>(change.clone()) else {
+            continue;
+        };
+        let kind = match data.field.as_str() {
+            "assignee" => EventKind::TaskAssigned,
+            "state" => EventKind::TaskStateChanged,
+            "answer" => EventKind::QuestionAnswered,
+            _ => continue,
+        };
+        if data.field == "answer" {
+            let node_id =
+                proposal
+                    .into_iter()
+                    .flat_map(|p| &p.operations)
+                    .find_map(|op| match op {
+                        Operation::Update { id, node_id, .. } if *id == data.operation_id => {
+                            Some(node_id)
+                        }
+                        _ => None,
+                    });
+            if let Some(node_id) = node_id {
+                if let Ok(Some(question)) = state.store.get_node(node_id).await {
+                    data.recipient = Some(question.metadata.created_by);
+                }
+            }
+        }
+        let key = data.node.clone();
+        let workspace = NodeId::from_key(&key).workspace().to_string();
+        publish_event(&state.event_bus, kind(data), &key, actor, Some(&workspace));
+    }
+}
+
+/// Whether an audited field change is a status or task state change the transition
+/// tables forbid.
+fn is_forced_transition(change: &serde_json::Value) -> bool {
+    fn parse<T: serde::de::DeserializeOwned>(v: &serde_json::Value) -> Option<T> {
+        serde_json::from_value(v.clone()).ok()
+    }
+    let (from, to) = (&change["from"], &change["to"]);
+    match change["field"].as_str() {
+        Some("status") => match (parse::<NodeStatus>(from), parse::<NodeStatus>(to)) {
+            (Some(from), Some(to)) => !from.can_become(to),
+            _ => false,
+        },
+        Some("state") => match (parse::<TaskState>(from), parse::<TaskState>(to)) {
+            (Some(from), Some(to)) => !from.can_become(to),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// `{ operationId, node, field, from, to }` for every field the proposal's updates and
+/// status changes set, as they stand before the apply (operations see earlier ones).
+async fn audited_field_changes(
+    state: &AppState,
+    proposal: &Proposal,
+) -> Result<Vec<serde_json::Value>, ApiError> {
+    let mut nodes: HashMap<String, Option<ContextNode>> = HashMap::new();
+    let mut changes = Vec::new();
+    for op in &proposal.operations {
+        let (op_id, node_id, fields) = match op {
+            Operation::Update {
+                id,
+                node_id,
+                changes,
+                ..
+            } => (id, node_id, changes.fields()),
+            Operation::StatusChange {
+                id,
+                node_id,
+                new_status,
+                ..
+            } => {
+                let mut fields = serde_json::Map::new();
+                fields.insert("status".to_string(), serde_json::json!(new_status));
+                (id, node_id, fields)
+            }
+            _ => continue,
+        };
+        let key = node_id.key();
+        if !nodes.contains_key(&key) {
+            nodes.insert(key.clone(), state.store.get_node(node_id).await?);
+        }
+        let Some(Some(node)) = nodes.get_mut(&key) else {
+            continue;
+        };
+        let before = serde_json::to_value(&*node).unwrap_or_default();
+        for (field, to) in fields {
+            let from = if UPDATABLE_METADATA_FIELDS.contains(&field.as_str()) {
+                &before["metadata"][&field]
+            } else {
+                &before[&field]
+            };
+            changes.push(serde_json::json!({
+                "operationId": op_id,
+                "node": key,
+                "field": field,
+                "from": from,
+                "to": to,
+            }));
+        }
+        match op {
+            Operation::Update { changes, .. } => changes.apply_to(node),
+            Operation::StatusChange { new_status, .. } => node.status = *new_status,
+            _ => {}
+        }
+    }
+    Ok(changes)
+}
+
+/// Withdraw an open proposal. Only its author may, or an Admin giving a `reason`;
+/// refusals are audited as denied.
+pub async fn withdraw_proposal(
+    state: &AppState,
+    actor: &ActorContext,
+    id: &str,
+    reason: Option<String>,
+) -> Result<(), ApiError> {
+    require_route(
+        state,
+        actor,
+        "POST /proposals/:id/withdraw",
+        Role::Contributor,
+    )?;
+    read_only::check_writable(&state.runtime.read_only)?;
+
+    let proposal = state
+        .store
+        .get_proposal(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("proposal {}", id)))?;
+    lifecycle::next_status(proposal.status, Transition::Withdraw).map_err(StoreError::from)?;
+    let reason = reason.filter(|r| !r.trim().is_empty());
+    let is_author = proposal.metadata.created_by == actor.actor_id;
+    let admin_override = actor.has_role(&Role::Admin) && reason.is_some();
+    if !is_author && !admin_override {
+        let event = AuditEvent::new(
+            &actor.actor_id,
+            actor_type_str(actor),
+            AuditAction::ProposalWithdrawn,
+            id,
+            AuditOutcome::Denied,
+        )
+        .in_workspace(proposal.workspace())
+        .with_details(AuditDetails::Withdrawal {
+            author: proposal.metadata.created_by.clone(),
+            admin_override: false,
+            reason: None,
+        });
+        audit(state, event).await?;
+        return Err(rbac::Forbidden(format!(
+            "only the author ({}) can withdraw proposal {}; an Admin must give a reason",
+            proposal.metadata.created_by, id
+        ))
+        .into());
+    }
+
+    let mut event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ProposalWithdrawn,
+        id,
+        AuditOutcome::Success,
+    );
+    if !is_author {
+        event = event.with_details(AuditDetails::Withdrawal {
+            author: proposal.metadata.created_by.clone(),
+            admin_override: true,
+            reason: reason.clone(),
+        });
+    }
+    let batch = WriteBatch::new()
+        .withdraw_proposal(id)
+        .audit(event)
+        .publish(server_event(
+            EventKind::ProposalUpdated {
+                status: ProposalStatus::Withdrawn,
+            },
+            id,
+            actor,
+        ));
+    execute(state, batch.in_workspace(proposal.workspace())).await?;
+    Ok(())
+}
+
+/// Query the audit log (Admin, or the role `rbac.routes` gives `GET /audit`). Callers
+/// below Admin only get the trail of a proposal they created: `resourceId` must name it.
+/// Details are redacted to the caller's clearance, like provenance.
+pub async fn query_audit(
+    state: &AppState,
+    actor: &ActorContext,
+    filter: &AuditQueryParams,
+) -> Result<AuditQueryResult, ApiError> {
+    require_route(state, actor, "GET /audit", Role::Admin)?;
+    if !actor.has_role(&Role::Admin) {
+        let own = match filter.resource_id.as_deref() {
+            Some(id) => state
+                .store
+                .get_proposal(id)
+                .await?
+                .is_some_and(|p| p.metadata.created_by == actor.actor_id),
+            None => false,
+        };
+        if !own {
+            return Err(rbac::Forbidden(format!(
+                "{} may only read the audit trail of its own proposals (resourceId)",
+                actor.actor_id
+            ))
+            .into());
+        }
+    }
+
+    let mut result = state
+        .store
+        .query_audit(
+            filter.actor.as_deref(),
+            filter.action.as_deref(),
+            filter.resource_id.as_deref(),
+            filter.from.as_deref(),
+            filter.to.as_deref(),
+            filter.limit,
+            filter.offset,
+        )
+        .await?;
+    redact_audit(state, actor, &mut result.events).await?;
+    Ok(result)
+}
+
+/// Build a context pack for `task` from the accepted nodes the caller may read, and
+/// audit exactly which nodes went into it.
+pub async fn context_pack(
+    state: &AppState,
+    actor: &ActorContext,
+    task: &str,
+    tags: &[String],
+    budget_tokens: usize,
+) -> Result<ContextPack, ApiError> {
+    require_route(state, actor, "GET /context-pack", Role::Reader)?;
+
+    if task.trim().is_empty() {
+        return Err(ApiError::Invalid("task is required".to_string()));
+    }
+    if budget_tokens == 0 || budget_tokens > context_pack::MAX_BUDGET_TOKENS {
+        return Err(ApiError::Invalid(format!(
+            "budget_tokens must be between 1 and {}",
+            context_pack::MAX_BUDGET_TOKENS
+        )));
+    }
+
+    let candidates = accepted_nodes(state, actor).await?;
+    let pack = context_pack::build(task, tags, candidates, budget_tokens, chrono::Utc::now());
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(actor),
+        AuditAction::ContextPackGenerated,
+        "context-pack",
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::ContextPack {
+        task: task.to_string(),
+        budget_tokens,
+        estimated_tokens: pack.estimated_tokens,
+        nodes: pack.nodes.iter().map(|n| n.key.clone()).collect(),
+        omitted: pack.omitted,
+    });
+    audit(state, event).await?;
+
+    Ok(pack)
+}
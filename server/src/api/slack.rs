@@ -0,0 +1,442 @@
+//! Slack endpoints (`crate::slack`): `POST /webhooks/slack/interactions` receives
+//! Approve / Reject button presses, `POST /webhooks/slack/commands` the `/truthlayer`
+//! slash command. New proposals are posted to the configured channel by
+//! `request_review`, called from `service::create_proposal`.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::Role;
+use crate::rbac;
+use crate::slack::{self, SlackConfig, SlackRequest, SlackUser};
+use crate::store::lifecycle::{self, Transition};
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, Proposal, ProposalQuery, ProposalStatus,
+    Review, ReviewAction,
+};
+
+/// Open proposals listed by `/truthlayer pending`; Slack caps a message at 50 blocks.
+const PENDING_LIMIT: usize = 10;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/webhooks/slack/interactions", post(interaction))
+        .route("/webhooks/slack/commands", post(command))
+}
+
+/// Queue a review request for a new open proposal in the Slack channel. Best effort: the
+/// proposal is created either way.
+pub(crate) async fn request_review(state: &AppState, proposal: &Proposal) {
+    let review = Transition::Review(ReviewAction::RequestChanges);
+    if lifecycle::next_status(proposal.status, review).is_err() {
+        return;
+    }
+    let config = state.runtime.config.get();
+    let Some(slack) = &config.slack else {
+        return;
+    };
+    if let Err(e) = state
+        .jobs
+        .enqueue(slack::MESSAGE_JOB, slack.review_request_job(proposal))
+        .await
+    {
+        tracing::warn!(proposal = %proposal.id, error = %e, "cannot queue slack review request");
+    }
+}
+
+/// Alert the Slack channel, if configured, to an emergency apply.
+pub(crate) async fn notify_emergency(
+    state: &AppState,
+    proposal_id: &str,
+    applied_by: &str,
+    justification: &str,
+    follow_up: &str,
+) {
+    let config = state.runtime.config.get();
+    let Some(slack) = &config.slack else {
+        return;
+    };
+    let job = slack.emergency_job(proposal_id, applied_by, justification, follow_up);
+    if let Err(e) = state.jobs.enqueue(slack::MESSAGE_JOB, job).await {
+        tracing::warn!(proposal = %proposal_id, error = %e, "cannot queue slack emergency alert");
+    }
+}
+
+/// `POST /webhooks/slack/interactions` — a button press. The outcome goes back to the
+/// message's `response_url` (Slack ignores the response body of block actions) and in
+/// the response.
+async fn interaction(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let config = state.runtime.config.get();
+    let slack = verified(&config.slack, &headers, &body)?;
+    let (user, request) = slack::parse_interaction(&body).map_err(ApiError::Invalid)?;
+    let reply = handle(&state, slack, &user, request).await?;
+    if let Some(url) = &user.response_url {
+        let job = serde_json::json!({ "url": url, "message": reply });
+        if let Err(e) = state.jobs.enqueue(slack::MESSAGE_JOB, job).await {
+            tracing::warn!(error = %e, "cannot queue slack response");
+        }
+    }
+    Ok(Json(reply).into_response())
+}
+
+/// `POST /webhooks/slack/commands` — `/truthlayer [pending | approve <id> [comment] |
+/// reject <id> [comment]]`; the reply is only shown to the caller.
+async fn command(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let config = state.runtime.config.get();
+    let slack = verified(&config.slack, &headers, &body)?;
+    let (user, request) = slack::parse_command(&body).map_err(ApiError::Invalid)?;
+    let reply = handle(&state, slack, &user, request).await?;
+    Ok(Json(reply).into_response())
+}
+
+/// The Slack config, if the request is signed with its secret; otherwise the status and
+/// error: `404` when Slack is not configured, `401` for a missing, stale or wrong
+/// signature.
+fn verified<'a>(
+    slack: &'a Option<SlackConfig>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<&'a SlackConfig, ApiError> {
+    let Some(slack) = slack else {
+        return Err(ApiError::NotFound("slack is not configured".to_string()));
+    };
+    let now = chrono::Utc::now().timestamp();
+    let signed = std::env::var(&slack.signing_secret_env)
+        .is_ok_and(|secret| slack::verify(headers, body, &secret, now));
+    if signed {
+        Ok(slack)
+    } else {
+        Err(ApiError::Unauthorized(
+            "invalid slack request signature".to_string(),
+        ))
+    }
+}
+
+async fn handle(
+    state: &AppState,
+    slack: &SlackConfig,
+    user: &SlackUser,
+    request: SlackRequest,
+) -> Result<serde_json::Value, ApiError> {
+    match request {
+        SlackRequest::Help => Ok(slack::ephemeral(slack::USAGE)),
+        SlackRequest::Pending => {
+            let open = state
+                .store
+                .query_proposals(ProposalQuery {
+                    status: Some(vec![ProposalStatus::Open]),
+                    limit: Some(u32::MAX),
+                    ..Default::default()
+                })
+                .await?;
+            let shown = &open[..open.len().min(PENDING_LIMIT)];
+            Ok(slack::pending_message(shown, open.len()))
+        }
+        SlackRequest::Review {
+            proposal_id,
+            action,
+            comment,
+        } => Ok(slack::ephemeral(
+            &review(state, slack, user, &proposal_id, action, comment).await,
+        )),
+    }
+}
+
+/// Submit a review as the actor `user` is mapped to, audit the mapping, and say what
+/// happened.
+async fn review(
+    state: &AppState,
+    slack: &SlackConfig,
+    user: &SlackUser,
+    proposal_id: &str,
+    action: ReviewAction,
+    comment: Option<String>,
+) -> String {
+    let mapped = slack.identities.get(&user.user_id);
+    let mut error = None;
+    let (outcome, reply) = match mapped {
+        None => {
+            error = Some("slack user is not mapped to an actor".to_string());
+            (
+                AuditOutcome::Denied,
+                "Your Slack account is not mapped to a TruthLayer actor.".to_string(),
+            )
+        }
+        Some(actor_id) => {
+            let review = Review {
+                id: format!("slack-review-{}", uuid::Uuid::new_v4()),
+                proposal_id: proposal_id.to_string(),
+                reviewer: actor_id.clone(),
+                reviewer_role: None,
+                reviewer_groups: None,
+                reviewed_at: chrono::Utc::now().to_rfc3339(),
+                action,
+                comment,
+                comments: None,
+                operation_ids: None,
+                is_approval: None,
+                on_behalf_of: None,
+            };
+            let submitted = async {
+                let reviewer =
+                    service::mapped_actor(state, actor_id, slack.roles.get(actor_id)).await?;
+                rbac::require_role(&reviewer, Role::Reviewer)?;
+                service::submit_review(state, &reviewer, proposal_id, review).await
+            };
+            match submitted.await {
+                Ok(_) => (
+                    AuditOutcome::Success,
+                    format!(
+                        "Recorded your {} of `{}` as {}.",
+                        if action == ReviewAction::Accept {
+                            "approval"
+                        } else {
+                            "rejection"
+                        },
+                        proposal_id,
+                        actor_id
+                    ),
+                ),
+                Err(e) => {
+                    let (status, body) = e.status_and_body();
+                    let message = body["error"]
+                        .as_str()
+                        .unwrap_or("review failed")
+                        .to_string();
+                    error = Some(message.clone());
+                    let outcome =
+                        if matches!(status, StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED) {
+                            AuditOutcome::Denied
+                        } else {
+                            AuditOutcome::Error
+                        };
+                    (
+                        outcome,
+                        format!("Could not review `{}`: {}", proposal_id, message),
+                    )
+                }
+            }
+        }
+    };
+    let event = AuditEvent::new(
+        &slack.service_actor,
+        "system",
+        AuditAction::SlackInteraction,
+        proposal_id,
+        outcome,
+    )
+    .with_details(AuditDetails::Slack {
+        slack_user_id: user.user_id.clone(),
+        slack_user_name: user.user_name.clone(),
+        slack_team_id: user.team_id.clone(),
+        actor_id: mapped.cloned(),
+        action,
+        error,
+    });
+    if let Err(e) = service::audit(state, event).await {
+        tracing::warn!(proposal = %proposal_id, error = %e, "slack interaction not audited");
+    }
+    reply
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::ActorContext;
+    use crate::store::directory::{DirectoryGroup, DirectoryUser};
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::Request;
+    use hmac::{Hmac, Mac};
+    use http_body_util::BodyExt;
+    use sha2::Sha256;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn buttons_review_as_the_mapped_actor() {
+        std::env::set_var("TL_TEST_SLACK_SECRET", "s3cret");
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let config = crate::config::ServerConfig {
+            slack: Some(
+                serde_json::from_value(serde_json::json!({
+                    "bot_token_env": "TL_TEST_SLACK_TOKEN",
+                    "signing_secret_env": "TL_TEST_SLACK_SECRET",
+                    "channel": "C123",
+                    "identities": { "U1": "alice", "U2": "bob", "U3": "carol" }
+                }))
+                .unwrap(),
+            ),
+            scim: crate::scim::ScimConfig {
+                group_roles: [("reviewers".to_string(), Role::Reviewer)].into(),
+            },
+            ..Default::default()
+        };
+        for (name, active) in [("alice", true), ("bob", true), ("carol", false)] {
+            store
+                .save_directory_user(DirectoryUser {
+                    id: name.to_string(),
+                    user_name: name.to_string(),
+                    external_id: None,
+                    display_name: None,
+                    emails: Vec::new(),
+                    active,
+                    created: "2026-01-01T00:00:00Z".to_string(),
+                    last_modified: "2026-01-01T00:00:00Z".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        store
+            .save_directory_group(DirectoryGroup {
+                id: "g-1".to_string(),
+                display_name: "reviewers".to_string(),
+                external_id: None,
+                members: vec!["alice".to_string(), "carol".to_string()],
+                created: "2026-01-01T00:00:00Z".to_string(),
+                last_modified: "2026-01-01T00:00:00Z".to_string(),
+            })
+            .await
+            .unwrap();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(config, crate::policy::PolicyConfig::default()),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(axum::Extension(ActorContext::dev_default()));
+        let send = |uri: &str, body: String, secret: &str| {
+            let timestamp = chrono::Utc::now().timestamp().to_string();
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+            let signature: String = mac
+                .finalize()
+                .into_bytes()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header("x-slack-request-timestamp", timestamp)
+                .header("x-slack-signature", format!("v0={}", signature))
+                .body(Body::from(body))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+
+        let create = Request::builder()
+            .method("POST")
+            .uri("/proposals")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "id": "p-1", "status": "open",
+                    "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                        "id": { "id": "goal-1" }, "type": "goal", "status": "accepted",
+                        "content": "Ship it",
+                        "metadata": {
+                            "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                            "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                        }
+                    }}],
+                    "metadata": { "createdBy": "dev-user" }
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let res = app.clone().oneshot(create).await.unwrap();
+        assert!(res.status().is_success());
+        let queued = store
+            .list_jobs(None, Some(slack::MESSAGE_JOB))
+            .await
+            .unwrap();
+        assert_eq!(queued[0].payload["message"]["channel"], "C123");
+
+        let (status, _) = send(
+            "/webhooks/slack/commands",
+            "user_id=U1&text=pending".to_string(),
+            "wrong",
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (_, pending) = send(
+            "/webhooks/slack/commands",
+            "user_id=U1&text=pending".to_string(),
+            "s3cret",
+        )
+        .await;
+        assert_eq!(pending["blocks"][2]["elements"][0]["value"], "p-1");
+
+        let press = |user: &str| {
+            let payload = serde_json::json!({
+                "type": "block_actions", "user": { "id": user }, "team": { "id": "T1" },
+                "actions": [{ "action_id": slack::APPROVE_ACTION, "value": "p-1" }]
+            });
+            serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap()
+        };
+        let (_, reply) = send("/webhooks/slack/interactions", press("U9"), "s3cret").await;
+        assert!(reply["text"].as_str().unwrap().contains("not mapped"));
+        // Mapping grants no role: bob is only a Reader, carol is deprovisioned.
+        let (_, reply) = send("/webhooks/slack/interactions", press("U2"), "s3cret").await;
+        assert!(reply["text"]
+            .as_str()
+            .unwrap()
+            .contains("insufficient role"));
+        let (_, reply) = send("/webhooks/slack/interactions", press("U3"), "s3cret").await;
+        assert!(reply["text"].as_str().unwrap().contains("deprovisioned"));
+        let (_, reply) = send("/webhooks/slack/interactions", press("U1"), "s3cret").await;
+        assert!(reply["text"].as_str().unwrap().contains("as alice"));
+        let reviews = store.get_review_history("p-1").await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].reviewer, "alice");
+
+        let audit = store
+            .query_audit(
+                None,
+                Some("slack_interaction"),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .events;
+        assert_eq!(audit.len(), 4);
+        assert!(audit.iter().all(|e| e.actor_id == "slack"));
+        let mapped: Vec<&serde_json::Value> = audit
+            .iter()
+            .map(|e| &e.details.as_ref().unwrap()["actorId"])
+            .collect();
+        assert!(mapped.contains(&&serde_json::json!("alice")));
+        assert!(mapped.contains(&&serde_json::Value::Null));
+    }
+}
@@ -0,0 +1,481 @@
+//! Strict request validation (`server.strict_requests`), opt-in.
+//!
+//! REST JSON bodies are read through [`StrictJson`] (or [`OptionalJson`] where the body
+//! may be omitted). With strict mode off they accept what axum's `Json` accepts, but a
+//! body that does not deserialize is refused as `application/problem+json` naming the
+//! field (`path`), what serde expected there (`expected`) and the value sent (`value`),
+//! so clients can correct themselves. With strict mode on, a body is refused the same way
+//! with `400` when:
+//!
+//! - it has a field the request type does not know (e.g. `propsalId`), as if every
+//!   request type were `deny_unknown_fields`;
+//! - a field fails to deserialize (the path is reported instead of just the position;
+//!   inside an operation it stops at the operation, as serde buffers tagged enums);
+//! - a date-time field (`*At`, `*_at`, `dueDate`) is not RFC 3339;
+//! - an id field (`id`, `*Id`, `*_id`) breaks the id grammar (`crate::ids`).
+//!
+//! Unknown fields are found by serializing the parsed value back and looking for input
+//! keys that did not survive; `null` values are not checked.
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{
+        rejection::{BytesRejection, JsonRejection},
+        FromRequest, Request,
+    },
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::api::routes::AppState;
+use crate::ids;
+
+/// A JSON body, validated strictly when `server.strict_requests` is on.
+pub struct StrictJson<T>(pub T);
+
+/// A JSON body that may be omitted. Without strict mode, an unreadable body counts as
+/// omitted (like `Option<Json<T>>`); with it, only an empty body does.
+pub struct OptionalJson<T>(pub Option<T>);
+
+/// Why a body was refused.
+#[derive(Debug)]
+pub enum StrictJsonRejection {
+    /// Not JSON, or not sent as JSON: axum's own rejection, as a problem.
+    Json(JsonRejection),
+    /// The body could not be read (e.g. over the size limit).
+    Body(BytesRejection),
+    /// The field at `path` is invalid: `422` when it does not deserialize, `400` when
+    /// strict mode refuses it. `expected` is what serde wanted, `value` the input there.
+    Invalid {
+        status: StatusCode,
+        path: String,
+        message: String,
+        expected: Option<String>,
+        value: Option<Value>,
+    },
+}
+
+/// A `application/problem+json` response (RFC 9457). `error` repeats `detail`, as every
+/// other REST error carries it.
+fn problem(status: StatusCode, detail: String, extra: serde_json::Map<String, Value>) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, PROBLEM_JSON)],
+        problem_body(status, "about:blank", detail, extra).to_string(),
+    )
+        .into_response()
+}
+
+/// The RFC 9457 body: `type`, `title` (the status's reason), `status`, `detail`, `error`
+/// (repeating `detail`) and `extra`.
+pub fn problem_body(
+    status: StatusCode,
+    problem_type: &str,
+    detail: String,
+    mut extra: serde_json::Map<String, Value>,
+) -> Value {
+    let mut body = serde_json::Map::new();
+    body.insert("type".to_string(), problem_type.into());
+    body.insert(
+        "title".to_string(),
+        status.canonical_reason().unwrap_or("Bad Request").into(),
+    );
+    body.insert("status".to_string(), status.as_u16().into());
+    body.insert("detail".to_string(), detail.clone().into());
+    body.insert("error".to_string(), detail.into());
+    body.append(&mut extra);
+    Value::Object(body)
+}
+
+/// Content type of body rejections and of every other REST error.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// The problem body of a REST error of kind `code`: `type` is
+/// `urn:truthlayer:error:{code}`.
+pub fn error_body(
+    status: StatusCode,
+    code: &str,
+    detail: String,
+    extra: serde_json::Map<String, Value>,
+) -> Value {
+    problem_body(
+        status,
+        &format!("urn:truthlayer:error:{}", code),
+        detail,
+        extra,
+    )
+}
+
+/// [`error_body`] as a response, for errors raised where no `ApiError` can be returned
+/// (middleware, transports, webhook receivers).
+pub fn error_response(
+    status: StatusCode,
+    code: &str,
+    detail: String,
+    extra: serde_json::Map<String, Value>,
+) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, PROBLEM_JSON)],
+        error_body(status, code, detail, extra).to_string(),
+    )
+        .into_response()
+}
+
+impl IntoResponse for StrictJsonRejection {
+    fn into_response(self) -> Response {
+        match self {
+            StrictJsonRejection::Json(rejection) => problem(
+                rejection.status(),
+                rejection.body_text(),
+                Default::default(),
+            ),
+            StrictJsonRejection::Body(rejection) => rejection.into_response(),
+            StrictJsonRejection::Invalid {
+                status,
+                path,
+                message,
+                expected,
+                value,
+            } => {
+                let mut extra = serde_json::Map::new();
+                extra.insert("path".to_string(), path.clone().into());
+                if let Some(expected) = expected {
+                    extra.insert("expected".to_string(), expected.into());
+                }
+                if let Some(value) = value {
+                    extra.insert("value".to_string(), value);
+                }
+                problem(
+                    status,
+                    format!("{}: {}", display_path(&path), message),
+                    extra,
+                )
+            }
+        }
+    }
+}
+
+fn strict(state: &AppState) -> bool {
+    state.runtime.config.get().strict_requests
+}
+
+#[async_trait]
+impl<T> FromRequest<AppState> for StrictJson<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    type Rejection = StrictJsonRejection;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        if !strict(state) {
+            let Json(input) = Json::<Value>::from_request(req, state)
+                .await
+                .map_err(StrictJsonRejection::Json)?;
+            return deserialize(&input, StatusCode::UNPROCESSABLE_ENTITY).map(StrictJson);
+        }
+        let bytes = body_bytes(req, state).await?;
+        parse(&bytes).map(StrictJson)
+    }
+}
+
+#[async_trait]
+impl<T> FromRequest<AppState> for OptionalJson<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    type Rejection = StrictJsonRejection;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        if !strict(state) {
+            let value = Json::<T>::from_request(req, state).await.ok();
+            return Ok(OptionalJson(value.map(|Json(v)| v)));
+        }
+        let bytes = body_bytes(req, state).await?;
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return Ok(OptionalJson(None));
+        }
+        parse(&bytes).map(|v| OptionalJson(Some(v)))
+    }
+}
+
+async fn body_bytes(req: Request, state: &AppState) -> Result<Bytes, StrictJsonRejection> {
+    Bytes::from_request(req, state)
+        .await
+        .map_err(StrictJsonRejection::Body)
+}
+
+/// Deserialize `bytes` as `T`, refusing unknown fields, bad dates and bad ids.
+pub fn parse<T>(bytes: &[u8]) -> Result<T, StrictJsonRejection>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let input: Value = serde_json::from_slice(bytes).map_err(|e| StrictJsonRejection::Invalid {
+        status: StatusCode::BAD_REQUEST,
+        path: String::new(),
+        message: e.to_string(),
+        expected: None,
+        value: None,
+    })?;
+    let parsed: T = deserialize(&input, StatusCode::BAD_REQUEST)?;
+    let known = serde_json::to_value(&parsed).unwrap_or(Value::Null);
+    check(&input, Some(&known), &mut String::new()).map_err(|(path, message)| {
+        StrictJsonRejection::Invalid {
+            status: StatusCode::BAD_REQUEST,
+            value: lookup(&input, &path).cloned(),
+            path,
+            message,
+            expected: None,
+        }
+    })?;
+    Ok(parsed)
+}
+
+/// Deserialize `input` as `T`; a failure names the field's path, what serde expected
+/// there and the value it found. A missing field's path ends with the field.
+fn deserialize<T: DeserializeOwned>(
+    input: &Value,
+    status: StatusCode,
+) -> Result<T, StrictJsonRejection> {
+    serde_path_to_error::deserialize(input).map_err(|e| {
+        let mut path = e.path().to_string();
+        if path == "." {
+            path.clear();
+        }
+        let message = e.into_inner().to_string();
+        if let Some(field) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.strip_suffix('`'))
+        {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(field);
+            return StrictJsonRejection::Invalid {
+                status,
+                path,
+                message: "missing field".to_string(),
+                expected: None,
+                value: None,
+            };
+        }
+        // A type that denies unknown fields refuses them as strict mode does, whatever
+        // `server.strict_requests` says.
+        let status = if message.starts_with("unknown field `") {
+            StatusCode::BAD_REQUEST
+        } else {
+            status
+        };
+        let expected = message
+            .split_once(", expected ")
+            .map(|(_, expected)| expected.to_string());
+        StrictJsonRejection::Invalid {
+            status,
+            value: lookup(input, &path).cloned(),
+            path,
+            message,
+            expected,
+        }
+    })
+}
+
+/// The value at `path` (`a.b[0].c`) in `input`.
+fn lookup<'a>(input: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut value = input;
+    for part in path.split('.').filter(|p| !p.is_empty()) {
+        let (key, indexes) = part.split_once('[').unwrap_or((part, ""));
+        if !key.is_empty() {
+            value = value.get(key)?;
+        }
+        for index in indexes.split('[').filter(|i| !i.is_empty()) {
+            value = value.get(index.trim_end_matches(']').parse::<usize>().ok()?)?;
+        }
+    }
+    Some(value)
+}
+
+/// Walk `input` beside its round-tripped form `known`. Fields kept as raw JSON
+/// round-trip unchanged, so only their formats are checked.
+fn check(input: &Value, known: Option<&Value>, path: &mut String) -> Result<(), (String, String)> {
+    match input {
+        Value::Object(fields) => {
+            let known_fields = known.and_then(Value::as_object);
+            for (key, value) in fields {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                let known_value = known_fields.and_then(|k| k.get(key));
+                if known_fields.is_some() && known_value.is_none() && !value.is_null() {
+                    return Err((path.clone(), "unknown field".to_string()));
+                }
+                check_format(key, value).map_err(|m| (path.clone(), m))?;
+                check(value, known_value, path)?;
+                path.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            let known_items = known.and_then(Value::as_array);
+            for (i, item) in items.iter().enumerate() {
+                let len = path.len();
+                path.push_str(&format!("[{}]", i));
+                check(item, known_items.and_then(|k| k.get(i)), path)?;
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn check_format(key: &str, value: &Value) -> Result<(), String> {
+    let Some(s) = value.as_str() else {
+        return Ok(());
+    };
+    if is_date_field(key) && chrono::DateTime::parse_from_rfc3339(s).is_err() {
+        return Err(format!("'{}' is not an RFC 3339 date-time", s));
+    }
+    if is_id_field(key) && !ids::is_valid(s) {
+        return Err(format!(
+            "'{}' is not a valid id (1-{} of letters, digits, '.', '_', '@', '-')",
+            s,
+            ids::MAX_ID_LEN
+        ));
+    }
+    Ok(())
+}
+
+fn is_date_field(key: &str) -> bool {
+    key.ends_with("At") || key.ends_with("_at") || key == "dueDate" || key == "due_date"
+}
+
+fn is_id_field(key: &str) -> bool {
+    key == "id" || key.ends_with("Id") || key.ends_with("_id")
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "body"
+    } else {
+        path
+    }
+}
+
+/// The body of an [`error_response`], after checking its content type, `type`, `title`
+/// and `status`.
+#[cfg(test)]
+pub(crate) async fn expect_error(res: Response, status: StatusCode, code: &str) -> Value {
+    assert_eq!(res.status(), status);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+    let bytes = http_body_util::BodyExt::collect(res.into_body())
+        .await
+        .unwrap()
+        .to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["type"], format!("urn:truthlayer:error:{}", code));
+    assert_eq!(body["title"], status.canonical_reason().unwrap());
+    assert_eq!(body["status"], status.as_u16());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Proposal, Review};
+
+    fn rejected_path<T: DeserializeOwned + Serialize>(body: serde_json::Value) -> String {
+        match parse::<T>(body.to_string().as_bytes()) {
+            Err(StrictJsonRejection::Invalid { path, .. }) => path,
+            Err(other) => panic!("unexpected rejection {:?}", other),
+            Ok(_) => panic!("accepted {}", body),
+        }
+    }
+
+    fn proposal() -> serde_json::Value {
+        serde_json::json!({
+            "id": "p-1",
+            "status": "open",
+            "operations": [{
+                "type": "status-change", "id": "op-1", "order": 1,
+                "node_id": { "id": "goal-1" },
+                "new_status": "accepted", "old_status": "proposed"
+            }],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "alice",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "alice",
+                "rationale": null
+            }
+        })
+    }
+
+    #[test]
+    fn strict_parse_reports_the_offending_field() {
+        assert!(parse::<Proposal>(proposal().to_string().as_bytes()).is_ok());
+
+        let mut typo = proposal();
+        typo["operations"][0]["propsalId"] = "p-1".into();
+        assert_eq!(rejected_path::<Proposal>(typo), "operations[0].propsalId");
+
+        let mut date = proposal();
+        date["metadata"]["modifiedAt"] = "yesterday".into();
+        assert_eq!(rejected_path::<Proposal>(date), "metadata.modifiedAt");
+
+        let mut id = proposal();
+        id["operations"][0]["node_id"]["id"] = "../goal-1".into();
+        assert_eq!(rejected_path::<Proposal>(id), "operations[0].node_id.id");
+
+        let mut wrong_type = proposal();
+        wrong_type["metadata"]["createdBy"] = 5.into();
+        assert_eq!(rejected_path::<Proposal>(wrong_type), "metadata.createdBy");
+
+        let review = serde_json::json!({
+            "id": "r-1", "proposalId": "p-1", "reviewer": "bob",
+            "reviewedAt": "2026-01-02T00:00:00Z", "action": "accept", "comment": "ok",
+            "aproved": true
+        });
+        assert_eq!(rejected_path::<Review>(review), "aproved");
+    }
+
+    #[tokio::test]
+    async fn type_errors_are_problems_naming_path_expected_type_and_value() {
+        let mut wrong_type = proposal();
+        wrong_type["metadata"]["createdBy"] = 5.into();
+        let rejection = deserialize::<Proposal>(&wrong_type, StatusCode::UNPROCESSABLE_ENTITY)
+            .err()
+            .unwrap();
+        let res = rejection.into_response();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        let bytes = http_body_util::BodyExt::collect(res.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["path"], "metadata.createdBy");
+        assert_eq!(body["expected"], "a string");
+        assert_eq!(body["value"], 5);
+
+        let mut missing = proposal();
+        missing["operations"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("order");
+        match deserialize::<Proposal>(&missing, StatusCode::UNPROCESSABLE_ENTITY) {
+            Err(StrictJsonRejection::Invalid { path, message, .. }) => {
+                assert_eq!(
+                    (path.as_str(), message.as_str()),
+                    ("operations[0].order", "missing field")
+                )
+            }
+            other => panic!("expected a missing field, got {:?}", other.err()),
+        }
+    }
+}
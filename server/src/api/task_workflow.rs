@@ -0,0 +1,338 @@
+//! Task workflow (`/tasks`): listing task nodes by state, assignee and due date, and
+//! proposing task state changes.
+//!
+//! Task states follow `TaskState::can_become` (open → in-progress → completed, with
+//! blocked and cancelled on the side). `POST /tasks/:id/state` refuses a transition the
+//! table does not allow; apply refuses it too, as for node statuses, unless an Admin
+//! applies a proposal with `forceStatusTransitions`. Applying a proposal that changes a
+//! task's assignee or state publishes `task_assigned` / `task_state_changed` events
+//! (`service::apply_proposal`); the `overdue_task_check` scheduled task audits overdue
+//! tasks (`crate::maintenance`).
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::types::{
+    ContextNode, NodeId, NodeQueryResult, NodeType, Operation, Proposal, ProposalMetadata,
+    ProposalStatus, TaskState, UpdateChanges,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/tasks", get(list_tasks))
+        .route("/tasks/:id/state", post(change_task_state))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskListParams {
+    /// Comma-separated task states (`open,in-progress`).
+    pub state: Option<String>,
+    /// Assignee id, or `me` for the caller.
+    pub assignee: Option<String>,
+    /// Only unfinished tasks past their due date.
+    pub overdue: Option<bool>,
+    pub namespace: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// `GET /tasks?state=open&assignee=me&overdue=true` — task nodes, sensitivity-filtered
+/// like `GET /nodes`, oldest due date first (tasks without one last).
+async fn list_tasks(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<TaskListParams>,
+) -> Result<Json<NodeQueryResult>, ApiError> {
+    let states = match params.state.as_deref() {
+        Some(s) => Some(
+            s.split(',')
+                .map(|x| parse_state(x.trim()))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        None => None,
+    };
+    let assignee = match params.assignee.as_deref() {
+        Some("me") => Some(actor.actor_id.clone()),
+        other => other.map(str::to_string),
+    };
+    let now = chrono::Utc::now();
+
+    let mut tasks: Vec<ContextNode> =
+        service::nodes_of_type(&state, &actor, NodeType::Task, params.namespace.clone())
+            .await?
+            .into_iter()
+            .filter(|node| {
+                states
+                    .as_ref()
+                    .is_none_or(|s| node.state.is_some_and(|n| s.contains(&n)))
+                    && assignee
+                        .as_ref()
+                        .is_none_or(|a| node.assignee.as_ref() == Some(a))
+                    && (params.overdue != Some(true) || node.is_overdue(now))
+            })
+            .collect();
+    tasks.sort_by(|a, b| {
+        (a.due_date.is_none(), &a.due_date).cmp(&(b.due_date.is_none(), &b.due_date))
+    });
+
+    let total = tasks.len() as u64;
+    let limit = params.limit.unwrap_or(50);
+    let offset = params.offset.unwrap_or(0);
+    let nodes: Vec<ContextNode> = tasks
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    let has_more = u64::from(offset) + (nodes.len() as u64) < total;
+    Ok(Json(NodeQueryResult {
+        nodes,
+        total,
+        limit,
+        offset,
+        has_more,
+    }))
+}
+
+fn parse_state(s: &str) -> Result<TaskState, ApiError> {
+    serde_json::from_value(serde_json::Value::String(s.to_string())).map_err(|_| {
+        ApiError::Invalid(format!(
+            "unknown task state '{}' (open, in-progress, blocked, completed, cancelled)",
+            s
+        ))
+    })
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TaskStateRequest {
+    pub state: TaskState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// `POST /tasks/:id/state` — propose moving a task to `state` (Contributor). Like
+/// `POST /nodes/:id/archive`, this creates an open proposal (one update operation) that
+/// goes through review; returns it with `201`.
+async fn change_task_state(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    StrictJson(request): StrictJson<TaskStateRequest>,
+) -> Result<(StatusCode, Json<Proposal>), ApiError> {
+    service::require_route(&state, &actor, "POST /tasks/:id/state", Role::Contributor)?;
+    let node_id = NodeId {
+        id,
+        namespace: request.namespace,
+    };
+    let key = node_id.key();
+    let node = state
+        .store
+        .get_node(&node_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("node {} not found", key)))?;
+    if node.node_type != NodeType::Task {
+        return Err(ApiError::Invalid(format!("node {} is not a task", key)));
+    }
+    if let Some(current) = node.state {
+        if current == request.state {
+            return Err(ApiError::Invalid(format!(
+                "task {} is already {}",
+                key,
+                current.as_str()
+            )));
+        }
+        if !current.can_become(request.state) {
+            return Err(ApiError::Invalid(format!(
+                "task {} cannot go from {} to {}",
+                key,
+                current.as_str(),
+                request.state.as_str()
+            )));
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let proposal = Proposal {
+        id: format!("task-state-{}", uuid::Uuid::new_v4()),
+        status: ProposalStatus::Open,
+        operations: vec![Operation::Update {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id,
+            changes: UpdateChanges {
+                state: Some(request.state),
+                ..Default::default()
+            },
+        }],
+        metadata: ProposalMetadata {
+            created_at: now.clone(),
+            created_by: actor.actor_id.clone(),
+            modified_at: now,
+            modified_by: actor.actor_id.clone(),
+            rationale: request.reason,
+            required_approvers: None,
+            approved_by: None,
+            base_versions: Some([(key, node.metadata.version)].into()),
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
+        },
+        comments: None,
+        relations: None,
+        applied: None,
+    };
+    let proposal = service::create_proposal(&state, &actor, proposal).await?;
+    Ok((StatusCode::CREATED, Json(proposal)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::store::ContextStore;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn accepted(id: &str, operation: serde_json::Value) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "status": "accepted",
+            "operations": [operation],
+            "metadata": { "createdBy": "dev-user" }
+        }))
+        .unwrap()
+    }
+
+    fn task(id: &str, state: &str, assignee: &str, due: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "op1", "order": 1, "type": "create",
+            "node": {
+                "id": { "id": id }, "type": "task", "status": "accepted", "content": id,
+                "state": state, "assignee": assignee, "dueDate": due,
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                    "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn tasks_are_listed_moved_and_announced() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        for proposal in [
+            accepted("p-1", task("task-1", "open", "dev-user", "2020-01-01")),
+            accepted("p-2", task("task-2", "completed", "dev-user", "2020-01-01")),
+            accepted("p-3", task("task-3", "open", "alice", "2999-01-01")),
+        ] {
+            let id = proposal.id.clone();
+            store.create_proposal(proposal).await.unwrap();
+            store.apply_proposal(&id, "u").await.unwrap();
+        }
+        let events = EventBus::new();
+        let mut received = events.subscribe();
+        let app = crate::api::routes::router(
+            store.clone(),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            events,
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default(),
+                )
+            }
+        };
+        let ids = |body: &serde_json::Value| -> Vec<String> {
+            body["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["id"]["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let (_, mine) = send(
+            "GET",
+            "/tasks?state=open&assignee=me",
+            serde_json::json!(null),
+        )
+        .await;
+        assert_eq!(ids(&mine), ["task-1"]);
+        let (_, overdue) = send("GET", "/tasks?overdue=true", serde_json::json!(null)).await;
+        assert_eq!(ids(&overdue), ["task-1"]);
+        let (status, _) = send("GET", "/tasks?state=done", serde_json::json!(null)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(
+            "POST",
+            "/tasks/task-1/state",
+            serde_json::json!({ "state": "completed" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, proposal) = send(
+            "POST",
+            "/tasks/task-1/state",
+            serde_json::json!({ "state": "in-progress" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(proposal["operations"][0]["changes"]["state"], "in-progress");
+
+        store
+            .create_proposal(accepted(
+                "p-assign",
+                serde_json::json!({ "id": "op1", "order": 1, "type": "update",
+                    "node_id": { "id": "task-3" }, "changes": { "assignee": "bob" } }),
+            ))
+            .await
+            .unwrap();
+        let (status, _) = send("POST", "/proposals/p-assign/apply", serde_json::json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        let event = loop {
+            let event = received.recv().await.unwrap();
+            if event.event_type() == "task_assigned" {
+                break event;
+            }
+        };
+        assert_eq!(event.resource_id, "task-3");
+        let data = serde_json::to_value(&event).unwrap()["data"].clone();
+        assert_eq!(
+            (&data["from"], &data["to"]),
+            (&"alice".into(), &"bob".into())
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Scheduled task status (`/admin/tasks`): each task's schedule, next run and last-run
+//! job, and on-demand runs. See `crate::scheduler`.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::scheduler::TaskStatus;
+use crate::store::WriteBatch;
+use crate::types::{AuditAction, AuditDetails, AuditEvent, AuditOutcome, JobRecord};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/tasks", get(list_tasks))
+        .route("/admin/tasks/:name/run", post(run_task))
+}
+
+async fn list_tasks(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<Vec<TaskStatus>>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/tasks", Role::Admin)?;
+    Ok(Json(state.scheduler.status().await?))
+}
+
+/// Queue a task's job now (enabled or not); returns the queued job.
+async fn run_task(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(name): Path<String>,
+) -> Result<(StatusCode, Json<JobRecord>), ApiError> {
+    service::require_route(&state, &actor, "POST /admin/tasks/:name/run", Role::Admin)?;
+    let job = state.scheduler.run_now(&name).await?;
+
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::TaskTriggered,
+        &name,
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::TaskTriggered {
+        job_id: job.id.clone(),
+    });
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::TaskTriggered,
+            &name,
+            &actor,
+        ));
+    service::execute(&state, batch).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::scheduler::ScheduledTaskConfig;
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn lists_tasks_and_runs_one_on_demand() {
+        let mut config = crate::config::ServerConfig::default();
+        config.tasks.insert(
+            "stale_proposal_check".to_string(),
+            ScheduledTaskConfig {
+                schedule: "0 9 * * 1-5".to_string(),
+                enabled: true,
+                params: Some(serde_json::json!({ "staleAfterDays": 30 })),
+            },
+        );
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            RuntimeConfig::new(config, crate::policy::PolicyConfig::default()),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+        let get_tasks = || async {
+            let res = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri("/admin/tasks")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            let tasks: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+            tasks
+                .into_iter()
+                .find(|t| t["name"] == "stale_proposal_check")
+                .unwrap()
+        };
+
+        let task = get_tasks().await;
+        assert_eq!(task["enabled"], true);
+        assert!(task["nextRunAt"].as_str().unwrap().contains("T09:00:00"));
+        assert!(task.get("lastRun").is_none());
+
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/tasks/stale_proposal_check/run")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+        let mut task = get_tasks().await;
+        for _ in 0..50 {
+            if task["lastRun"]["status"] == "completed" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            task = get_tasks().await;
+        }
+        assert_eq!(task["lastRun"]["status"], "completed");
+        assert_eq!(task["lastRun"]["result"]["staleAfterDays"], 30);
+
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/tasks/vacuum/run")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
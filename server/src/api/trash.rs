@@ -0,0 +1,191 @@
+//! Node trash (`/admin/trash`): deleted nodes and their restore. Purging is left to
+//! retention (see `store::trash`).
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service::{self, actor_type_str};
+use crate::auth::{ActorContext, Role};
+use crate::events::EventKind;
+use crate::store::WriteBatch;
+use crate::types::{AuditAction, AuditDetails, AuditEvent, AuditOutcome, ContextNode, NodeId};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/trash", get(list_trash))
+        .route("/admin/trash/:id/restore", post(restore_node))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrashParams {
+    pub namespace: Option<String>,
+}
+
+async fn list_trash(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+) -> Result<Json<Vec<ContextNode>>, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/trash", Role::Admin)?;
+    Ok(Json(state.store.list_trash().await?))
+}
+
+/// Restore a deleted node (`?namespace=` for namespaced ids); returns the node.
+async fn restore_node(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Path(id): Path<String>,
+    Query(params): Query<TrashParams>,
+) -> Result<Json<ContextNode>, ApiError> {
+    service::require_route(&state, &actor, "POST /admin/trash/:id/restore", Role::Admin)?;
+    let node_id = NodeId {
+        id,
+        namespace: params.namespace,
+    };
+    let node = state.store.restore_node(&node_id, &actor.actor_id).await?;
+
+    let key = node_id.key();
+    let event = AuditEvent::new(
+        &actor.actor_id,
+        actor_type_str(&actor),
+        AuditAction::NodeRestored,
+        &key,
+        AuditOutcome::Success,
+    )
+    .in_workspace(node.id.workspace())
+    .with_details(AuditDetails::Restored {
+        version: node.metadata.version,
+    });
+    let batch = WriteBatch::new()
+        .audit(event)
+        .publish(service::server_event(
+            EventKind::NodeRestored {
+                node_id: node.id.clone(),
+            },
+            &key,
+            &actor,
+        ))
+        .in_workspace(node.id.workspace());
+    service::execute(&state, batch).await?;
+
+    Ok(Json(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::store::ContextStore;
+    use crate::types::Proposal;
+    use crate::version::ServerInfo;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn accepted(id: &str, operation: serde_json::Value) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "status": "accepted",
+            "operations": [operation],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "u",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "u"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn deleted_nodes_are_listed_and_restored() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let node = serde_json::json!({
+            "id": { "id": "goal-1" },
+            "type": "goal",
+            "status": "accepted",
+            "content": "A goal",
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "u",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "u",
+                "version": 1
+            }
+        });
+        for proposal in [
+            accepted(
+                "p-create",
+                serde_json::json!({ "id": "op1", "order": 1, "type": "create", "node": node }),
+            ),
+            accepted(
+                "p-delete",
+                serde_json::json!({ "id": "op1", "order": 1, "type": "delete", "node_id": { "id": "goal-1" } }),
+            ),
+        ] {
+            let id = proposal.id.clone();
+            store.create_proposal(proposal).await.unwrap();
+            store.apply_proposal(&id, "u").await.unwrap();
+        }
+        let app = crate::api::routes::router(
+            store.clone(),
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            EventBus::new(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+        let send = |method: &str, uri: &str| {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let body = res.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body)
+                        .unwrap_or(serde_json::Value::Null),
+                )
+            }
+        };
+
+        assert_eq!(send("GET", "/nodes/goal-1").await.0, StatusCode::NOT_FOUND);
+        let (status, trash) = send("GET", "/admin/trash").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(trash[0]["metadata"]["deleted"]["proposalId"], "p-delete");
+
+        let (status, restored) = send("POST", "/admin/trash/goal-1/restore").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(restored["status"], "accepted");
+        assert!(restored["metadata"].get("deleted").is_none());
+        assert_eq!(send("GET", "/nodes/goal-1").await.0, StatusCode::OK);
+        assert_eq!(
+            send("POST", "/admin/trash/goal-1/restore").await.0,
+            StatusCode::NOT_FOUND
+        );
+        let audit = store
+            .query_audit(None, Some("node_restored"), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(audit.total, 1);
+    }
+}
@@ -0,0 +1,190 @@
+//! Usage export for chargeback (`crate::usage`): `GET /admin/usage?month=YYYY-MM` returns
+//! the month's daily records per workspace and their totals, as JSON or CSV.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Extension, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+use crate::store::UsageRecord;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/admin/usage", get(usage_report))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsageParams {
+    /// `YYYY-MM`.
+    pub month: String,
+    /// `json` (default) or `csv`.
+    pub format: Option<String>,
+}
+
+/// A workspace's usage over the month.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub api_calls: u64,
+    pub agent_sensitive_reads: u64,
+    /// Largest daily storage measure.
+    pub peak_storage_bytes: u64,
+    /// Days with a record.
+    pub days: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    pub month: String,
+    pub records: Vec<UsageRecord>,
+    /// By workspace.
+    pub totals: BTreeMap<String, UsageTotals>,
+}
+
+fn to_csv(records: &[UsageRecord]) -> String {
+    let mut csv = String::from(
+        "date,workspace,api_calls,storage_bytes,nodes,agent_sensitive_reads,rolled_up_at\n",
+    );
+    for r in records {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            r.date,
+            r.workspace,
+            r.api_calls,
+            r.storage_bytes,
+            r.nodes,
+            r.agent_sensitive_reads,
+            r.rolled_up_at.as_deref().unwrap_or("")
+        ));
+    }
+    csv
+}
+
+/// `GET /admin/usage?month=YYYY-MM[&format=csv]` (Admin).
+async fn usage_report(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<UsageParams>,
+) -> Result<Response, ApiError> {
+    service::require_route(&state, &actor, "GET /admin/usage", Role::Admin)?;
+    if params.month.len() != 7
+        || chrono::NaiveDate::parse_from_str(&format!("{}-01", params.month), "%Y-%m-%d").is_err()
+    {
+        return Err(ApiError::Invalid(format!(
+            "month '{}' is not YYYY-MM",
+            params.month
+        )));
+    }
+    let records = state.store.list_usage(&params.month).await?;
+    match params.format.as_deref().unwrap_or("json") {
+        "json" => {}
+        "csv" => {
+            return Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"usage-{}.csv\"", params.month),
+                    ),
+                ],
+                to_csv(&records),
+            )
+                .into_response())
+        }
+        other => return Err(ApiError::Invalid(format!("unknown format '{}'", other))),
+    }
+    let mut totals: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    for r in &records {
+        let t = totals.entry(r.workspace.clone()).or_default();
+        t.api_calls += r.api_calls;
+        t.agent_sensitive_reads += r.agent_sensitive_reads;
+        t.peak_storage_bytes = t.peak_storage_bytes.max(r.storage_bytes);
+        t.days += 1;
+    }
+    Ok(Json(UsageReport {
+        month: params.month,
+        records,
+        totals,
+    })
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn reports_a_month_with_totals() {
+        let store = Arc::new(crate::store::InMemoryStore::new());
+        let calls = BTreeMap::from([("payments".to_string(), 5)]);
+        for date in ["2026-09-30", "2026-10-01", "2026-10-02"] {
+            crate::store::ContextStore::add_api_calls(store.as_ref(), date, &calls)
+                .await
+                .unwrap();
+        }
+        let app = crate::api::routes::router(
+            store,
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+
+        let res = app
+            .clone()
+            .oneshot(
+                Request::get("/admin/usage?month=2026-10")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["records"].as_array().unwrap().len(), 2);
+        assert_eq!(report["totals"]["payments"]["apiCalls"], 10);
+        assert_eq!(report["totals"]["payments"]["days"], 2);
+
+        let res = app
+            .clone()
+            .oneshot(
+                Request::get("/admin/usage?month=2026-10&format=csv")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains("2026-10-02,payments,5,0,0,0,"));
+
+        let res = app
+            .oneshot(
+                Request::get("/admin/usage?month=2026-13")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,323 @@
+//! Proposal validation (`POST /proposals/validate`): a dry run of a proposal's operations
+//! against the current store, so agents can fix a proposal before submitting it.
+//!
+//! Operations are checked in `order`, each seeing the effect of the ones before it (a node
+//! created by operation 1 can be updated by operation 2). Reported issues:
+//!
+//! - `duplicate_order` / `duplicate_operation_id`: two operations share an `order` / `id`;
+//! - `node_exists`: a create targets a node that exists (or an earlier operation created);
+//! - `node_not_found`: an update, delete or status change targets a missing node;
+//! - `stale_status`: a status change's `old_status` is not the node's status;
+//! - `no_status_change`: a status change to the status the node already has;
+//! - `invalid_transition`: a status (or task state) the transition table does not allow
+//!   from the node's current one (`NodeStatus::can_become`, `TaskState::can_become`; only
+//!   an Admin can force it).
+//!
+//! The structural ones (the first two) can never succeed, so `create_proposal` refuses
+//! them too; the others depend on the store at apply time and are only reported here
+//! (apply itself refuses a `stale_status` with a conflict, see `store::reconcile`).
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{extract::Extension, extract::State, routing::post, Json, Router};
+use serde::Serialize;
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::api::strict::StrictJson;
+use crate::auth::{ActorContext, Role};
+use crate::types::{NodeId, NodeStatus, Operation, Proposal, TaskState};
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/proposals/validate", post(validate_proposal))
+}
+
+/// One problem with one operation.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub operation_id: String,
+    pub order: u32,
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+fn issue(op: &Operation, code: &'static str, message: String) -> ValidationIssue {
+    let (id, order) = op_id_and_order(op);
+    ValidationIssue {
+        operation_id: id.to_string(),
+        order,
+        code,
+        message,
+    }
+}
+
+pub(crate) fn op_id_and_order(op: &Operation) -> (&str, u32) {
+    match op {
+        Operation::Create { id, order, .. }
+        | Operation::Update { id, order, .. }
+        | Operation::Delete { id, order, .. }
+        | Operation::StatusChange { id, order, .. } => (id, *order),
+    }
+}
+
+/// Issues that no store state can fix: repeated operation orders or ids.
+pub fn structural_issues(operations: &[Operation]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut orders = HashSet::new();
+    let mut ids = HashSet::new();
+    for op in operations {
+        let (id, order) = op_id_and_order(op);
+        if !orders.insert(order) {
+            issues.push(issue(
+                op,
+                "duplicate_order",
+                format!("another operation already has order {}", order),
+            ));
+        }
+        if !ids.insert(id) {
+            issues.push(issue(
+                op,
+                "duplicate_operation_id",
+                format!("another operation already has id '{}'", id),
+            ));
+        }
+    }
+    issues
+}
+
+/// Check `operations` against the store, in order.
+pub async fn validate(
+    state: &AppState,
+    operations: &[Operation],
+) -> Result<ValidationReport, ApiError> {
+    let mut issues = structural_issues(operations);
+    let mut sorted: Vec<&Operation> = operations.iter().collect();
+    sorted.sort_by_key(|op| op_id_and_order(op).1);
+
+    // Node key -> status as of the operations checked so far (None: absent).
+    let mut nodes: HashMap<String, Option<NodeStatus>> = HashMap::new();
+    // Node key -> task state, for the nodes whose state an operation sets or creates.
+    let mut task_states: HashMap<String, Option<TaskState>> = HashMap::new();
+    for op in sorted {
+        let node_id = match op {
+            Operation::Create { node, .. } => &node.id,
+            Operation::Update { node_id, .. }
+            | Operation::Delete { node_id, .. }
+            | Operation::StatusChange { node_id, .. } => node_id,
+        };
+        let current = current_status(state, &mut nodes, node_id).await?;
+        let key = node_id.key();
+        match op {
+            Operation::Create { node, .. } => {
+                if current.is_some() {
+                    issues.push(issue(
+                        op,
+                        "node_exists",
+                        format!("node {} already exists", key),
+                    ));
+                } else {
+                    task_states.insert(key.clone(), node.state);
+                    nodes.insert(key, Some(node.status));
+                }
+            }
+            Operation::Update { changes, .. } => match current {
+                None => issues.push(not_found(op, &key)),
+                Some(current) => {
+                    if let Some(to) = changes.state {
+                        let from = match task_states.get(&key) {
+                            Some(state) => *state,
+                            None => state.store.get_node(node_id).await?.and_then(|n| n.state),
+                        };
+                        if let Some(from) = from.filter(|from| !from.can_become(to)) {
+                            issues.push(issue(
+                                op,
+                                "invalid_transition",
+                                format!(
+                                    "task {} cannot go from {} to {}",
+                                    key,
+                                    from.as_str(),
+                                    to.as_str()
+                                ),
+                            ));
+                        }
+                        task_states.insert(key.clone(), Some(to));
+                    }
+                    if let Some(status) = changes.status {
+                        if !current.can_become(status) {
+                            issues.push(invalid_transition(op, &key, current, status));
+                        }
+                        nodes.insert(key, Some(status));
+                    }
+                }
+            },
+            Operation::Delete { .. } => match current {
+                None => issues.push(not_found(op, &key)),
+                Some(_) => {
+                    task_states.insert(key.clone(), None);
+                    nodes.insert(key, None);
+                }
+            },
+            Operation::StatusChange {
+                new_status,
+                old_status,
+                ..
+            } => match current {
+                None => issues.push(not_found(op, &key)),
+                Some(status) if status != *old_status => issues.push(issue(
+                    op,
+                    "stale_status",
+                    format!(
+                        "node {} is {}, not {} (old_status)",
+                        key,
+                        status.as_str(),
+                        old_status.as_str()
+                    ),
+                )),
+                Some(status) if status == *new_status => issues.push(issue(
+                    op,
+                    "no_status_change",
+                    format!("node {} is already {}", key, status.as_str()),
+                )),
+                Some(status) => {
+                    if !status.can_become(*new_status) {
+                        issues.push(invalid_transition(op, &key, status, *new_status));
+                    }
+                    nodes.insert(key, Some(*new_status));
+                }
+            },
+        }
+    }
+    Ok(ValidationReport {
+        valid: issues.is_empty(),
+        issues,
+    })
+}
+
+async fn current_status(
+    state: &AppState,
+    nodes: &mut HashMap<String, Option<NodeStatus>>,
+    node_id: &NodeId,
+) -> Result<Option<NodeStatus>, ApiError> {
+    let key = node_id.key();
+    if let Some(status) = nodes.get(&key) {
+        return Ok(*status);
+    }
+    let status = state.store.get_node(node_id).await?.map(|n| n.status);
+    nodes.insert(key, status);
+    Ok(status)
+}
+
+fn invalid_transition(
+    op: &Operation,
+    key: &str,
+    from: NodeStatus,
+    to: NodeStatus,
+) -> ValidationIssue {
+    issue(
+        op,
+        "invalid_transition",
+        format!(
+            "node {} cannot go from {} to {}",
+            key,
+            from.as_str(),
+            to.as_str()
+        ),
+    )
+}
+
+fn not_found(op: &Operation, key: &str) -> ValidationIssue {
+    issue(op, "node_not_found", format!("node {} does not exist", key))
+}
+
+/// Validate a proposal body without creating it (Contributor).
+async fn validate_proposal(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    StrictJson(proposal): StrictJson<Proposal>,
+) -> Result<Json<ValidationReport>, ApiError> {
+    service::require_route(
+        &state,
+        &actor,
+        "POST /proposals/validate",
+        Role::Contributor,
+    )?;
+    Ok(Json(validate(&state, &proposal.operations).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn reports_each_problem_with_its_operation() {
+        let app = crate::api::routes::router(
+            std::sync::Arc::new(crate::store::InMemoryStore::new()),
+            crate::reload::RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            crate::events::EventBus::new(),
+            crate::version::ServerInfo::default(),
+        )
+        .layer(Extension(ActorContext::dev_default()));
+        let node = serde_json::json!({
+            "id": { "id": "goal-1" }, "type": "goal", "status": "proposed", "content": "c",
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+            }
+        });
+        let body = serde_json::json!({
+            "status": "open",
+            "operations": [
+                { "type": "create", "id": "op-1", "order": 1, "node": node },
+                { "type": "create", "id": "op-2", "order": 2, "node": node },
+                { "type": "status-change", "id": "op-3", "order": 3, "node_id": { "id": "goal-1" },
+                  "old_status": "accepted", "new_status": "rejected" },
+                { "type": "delete", "id": "op-4", "order": 3, "node_id": { "id": "goal-2" } }
+            ]
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/proposals/validate")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let report: serde_json::Value =
+            serde_json::from_slice(&res.into_body().collect().await.unwrap().to_bytes()).unwrap();
+        assert_eq!(report["valid"], false);
+        let codes: Vec<(&str, &str)> = report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| {
+                (
+                    i["operationId"].as_str().unwrap(),
+                    i["code"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("op-4", "duplicate_order"),
+                ("op-2", "node_exists"),
+                ("op-3", "stale_status"),
+                ("op-4", "node_not_found"),
+            ]
+        );
+    }
+}
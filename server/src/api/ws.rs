@@ -0,0 +1,386 @@
+//! `GET /ws`: WebSocket transport for the same ServerEvents as `GET /events` (SSE).
+//!
+//! For clients that handle WebSockets better than SSE (older proxies, Node.js tooling).
+//! Messages are JSON text frames tagged by `type`.
+//!
+//! Client → server:
+//! - `{"type":"subscribe","workspace":"ws-1","eventTypes":["proposal_updated"]}` replaces
+//!   the filters (initial filters come from the `workspace` / `event_types` query params).
+//! - `{"type":"ack","seq":N}` acknowledges every event up to and including `seq`.
+//! - `{"type":"ping"}` → `{"type":"pong"}`.
+//!
+//! Server → client: `subscribed`, `event` (`seq` + `event`), `lagged` (`missed`), `pong`
+//! and `error` (`message`).
+//!
+//! Acknowledgements are flow control: at most `MAX_UNACKED` events are in flight. While
+//! the window is full further events wait in the EventBus receiver; a client that falls
+//! behind by more than the channel capacity gets a `lagged` message, as SSE clients
+//! silently miss events.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Extension, Query, State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::api::routes::{ApiError, AppState};
+use crate::api::service;
+use crate::auth::{ActorContext, Role};
+use crate::events::ServerEvent;
+use crate::types::AuditDetails;
+
+/// Most events delivered but not yet acknowledged.
+pub const MAX_UNACKED: u64 = 64;
+
+/// Interval between WebSocket ping frames (same as the SSE keep-alive).
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Deserialize)]
+pub struct WsParams {
+    pub workspace: Option<String>,
+    /// Comma-separated event types; all types when absent.
+    pub event_types: Option<String>,
+}
+
+/// Messages accepted from the client.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Subscribe {
+        #[serde(default)]
+        workspace: Option<String>,
+        #[serde(default, rename = "eventTypes")]
+        event_types: Vec<String>,
+    },
+    Ack {
+        seq: u64,
+    },
+    Ping,
+}
+
+/// Messages sent to the client.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage<'a> {
+    Subscribed {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace: Option<&'a str>,
+        #[serde(rename = "eventTypes")]
+        event_types: &'a [String],
+    },
+    Event {
+        seq: u64,
+        event: &'a ServerEvent,
+    },
+    Lagged {
+        missed: u64,
+    },
+    Pong,
+    Error {
+        message: String,
+    },
+}
+
+impl ServerMessage<'_> {
+    fn frame(&self) -> Message {
+        Message::Text(serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Per-connection filters and delivery window.
+#[derive(Debug, Default)]
+struct Session {
+    workspace: Option<String>,
+    event_types: Vec<String>,
+    /// Sequence number of the last event sent (0 before the first).
+    sent: u64,
+    /// Highest sequence number acknowledged by the client.
+    acked: u64,
+}
+
+impl Session {
+    fn new(params: WsParams) -> Self {
+        Self {
+            workspace: params.workspace,
+            event_types: params
+                .event_types
+                .map(|s| {
+                    s.split(',')
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn subscribed(&self) -> Message {
+        ServerMessage::Subscribed {
+            workspace: self.workspace.as_deref(),
+            event_types: &self.event_types,
+        }
+        .frame()
+    }
+
+    fn window_open(&self) -> bool {
+        self.sent - self.acked < MAX_UNACKED
+    }
+
+    fn matches(&self, event: &ServerEvent) -> bool {
+        if let Some(ref ws_id) = self.workspace {
+            if event.workspace_id.as_deref() != Some(ws_id.as_str()) {
+                return false;
+            }
+        }
+        self.event_types.is_empty() || self.event_types.iter().any(|t| t == event.event_type())
+    }
+
+    /// The frame for `event`, if it passes the filters (assigns the next sequence number).
+    fn deliver(&mut self, event: &ServerEvent) -> Option<Message> {
+        if !self.matches(event) {
+            return None;
+        }
+        self.sent += 1;
+        Some(
+            ServerMessage::Event {
+                seq: self.sent,
+                event,
+            }
+            .frame(),
+        )
+    }
+
+    /// Apply a client text frame; returns the reply, if any.
+    fn handle_client(&mut self, text: &str) -> Option<Message> {
+        let message = match serde_json::from_str::<ClientMessage>(text) {
+            Ok(m) => m,
+            Err(e) => {
+                return Some(
+                    ServerMessage::Error {
+                        message: format!("invalid message: {}", e),
+                    }
+                    .frame(),
+                )
+            }
+        };
+        match message {
+            ClientMessage::Subscribe {
+                workspace,
+                event_types,
+            } => {
+                self.workspace = workspace;
+                self.event_types = event_types;
+                Some(self.subscribed())
+            }
+            ClientMessage::Ack { seq } if seq > self.sent => Some(
+                ServerMessage::Error {
+                    message: format!("ack {} is beyond last sent event {}", seq, self.sent),
+                }
+                .frame(),
+            ),
+            ClientMessage::Ack { seq } => {
+                self.acked = self.acked.max(seq);
+                None
+            }
+            ClientMessage::Ping => Some(ServerMessage::Pong.frame()),
+        }
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/ws", get(ws_upgrade))
+}
+
+/// `GET /ws?workspace={id}&event_types=a,b` — upgrade to a WebSocket event stream (Reader).
+async fn ws_upgrade(
+    State(state): State<AppState>,
+    Extension(actor): Extension<ActorContext>,
+    Query(params): Query<WsParams>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    service::require_route(&state, &actor, "GET /ws", Role::Reader)?;
+    service::audit_subscription(
+        &state,
+        &actor,
+        "websocket",
+        AuditDetails::Subscription {
+            workspace: params.workspace.clone(),
+            event_types: params.event_types.clone(),
+            resumed: false,
+        },
+    )
+    .await?;
+    let session = Session::new(params);
+    Ok(upgrade.on_upgrade(move |socket| run(socket, state, session)))
+}
+
+async fn run(mut socket: WebSocket, state: AppState, mut session: Session) {
+    // Subscribe before confirming so no event published after `subscribed` is missed.
+    let mut rx = state.event_bus.subscribe();
+    if socket.send(session.subscribed()).await.is_err() {
+        return;
+    }
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+
+    loop {
+        let reply = tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => session.handle_client(&text),
+                // Ping frames are answered by the WebSocket layer; pongs and binary are ignored.
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => None,
+            },
+            received = rx.recv(), if session.window_open() => match received {
+                Ok(event) => session.deliver(&event),
+                Err(RecvError::Lagged(missed)) => Some(ServerMessage::Lagged { missed }.frame()),
+                Err(RecvError::Closed) => break,
+            },
+            _ = keepalive.tick() => Some(Message::Ping(Vec::new())),
+        };
+        if let Some(message) = reply {
+            if socket.send(message).await.is_err() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::test_event as event;
+    use crate::events::EventBus;
+    use crate::reload::RuntimeConfig;
+    use crate::version::ServerInfo;
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::Arc;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    fn text(message: Message) -> serde_json::Value {
+        match message {
+            Message::Text(t) => serde_json::from_str(&t).unwrap(),
+            other => panic!("expected text frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn filters_and_resubscribes() {
+        let mut session = Session::new(WsParams {
+            workspace: Some("ws-1".into()),
+            event_types: Some("proposal_updated, review_submitted".into()),
+        });
+        assert!(session
+            .deliver(&event("proposal_updated", Some("ws-2")))
+            .is_none());
+        assert!(session
+            .deliver(&event("config_changed", Some("ws-1")))
+            .is_none());
+        let first = text(
+            session
+                .deliver(&event("review_submitted", Some("ws-1")))
+                .unwrap(),
+        );
+        assert_eq!(first["type"], "event");
+        assert_eq!(first["seq"], 1);
+        assert_eq!(first["event"]["eventType"], "review_submitted");
+
+        let reply = text(
+            session
+                .handle_client(r#"{"type":"subscribe","eventTypes":["config_changed"]}"#)
+                .unwrap(),
+        );
+        assert_eq!(reply["type"], "subscribed");
+        assert!(session.deliver(&event("config_changed", None)).is_some());
+
+        let error = text(session.handle_client(r#"{"type":"nope"}"#).unwrap());
+        assert_eq!(error["type"], "error");
+    }
+
+    #[test]
+    fn acks_open_the_delivery_window() {
+        let mut session = Session::default();
+        for _ in 0..MAX_UNACKED {
+            session.deliver(&event("proposal_updated", None)).unwrap();
+        }
+        assert!(!session.window_open());
+
+        let error = text(
+            session
+                .handle_client(r#"{"type":"ack","seq":1000}"#)
+                .unwrap(),
+        );
+        assert_eq!(error["type"], "error");
+        assert!(session
+            .handle_client(r#"{"type":"ack","seq":10}"#)
+            .is_none());
+        assert!(session.window_open());
+        // Stale acks never move the window back.
+        session.handle_client(r#"{"type":"ack","seq":3}"#);
+        assert_eq!(session.acked, 10);
+    }
+
+    #[tokio::test]
+    async fn streams_events_over_a_websocket() {
+        let bus = EventBus::new();
+        let app = crate::api::routes::router(
+            Arc::new(crate::store::InMemoryStore::new()),
+            RuntimeConfig::new(
+                crate::config::ServerConfig::default(),
+                crate::policy::PolicyConfig::default(),
+            ),
+            bus.clone(),
+            ServerInfo::default(),
+        )
+        .layer(axum::middleware::from_fn(
+            |mut req: axum::extract::Request, next: axum::middleware::Next| async move {
+                req.extensions_mut().insert(ActorContext::dev_default());
+                next.run(req).await
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!(
+            "ws://{}/ws?event_types=proposal_updated",
+            addr
+        ))
+        .await
+        .unwrap();
+        let next = |msg: Option<Result<WsMessage, _>>| -> serde_json::Value {
+            serde_json::from_str(msg.unwrap().unwrap().to_text().unwrap()).unwrap()
+        };
+        assert_eq!(next(ws.next().await)["type"], "subscribed");
+
+        bus.publish(ServerEvent {
+            resource_id: "p-1".into(),
+            ..crate::events::test_event("review_submitted", None)
+        });
+        bus.publish(ServerEvent {
+            resource_id: "p-2".into(),
+            ..crate::events::test_event("proposal_updated", None)
+        });
+        let delivered = next(ws.next().await);
+        assert_eq!(delivered["seq"], 1);
+        assert_eq!(delivered["event"]["resourceId"], "p-2");
+
+        ws.send(WsMessage::Text(r#"{"type":"ack","seq":1}"#.into()))
+            .await
+            .unwrap();
+        ws.send(WsMessage::Text(r#"{"type":"ping"}"#.into()))
+            .await
+            .unwrap();
+        assert_eq!(next(ws.next().await)["type"], "pong");
+    }
+}
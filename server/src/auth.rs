@@ -1,6 +1,11 @@
 //! Authentication middleware: JWT (HS256) validation and ActorContext extraction.
 //! When AUTH_DISABLED=true (or 1, or not set — default for dev), all requests get a default admin actor.
-//! Otherwise, requires `Authorization: Bearer <token>` with a valid HS256 JWT signed by AUTH_SECRET.
+//! Otherwise, requires `Authorization: Bearer <token>` with a valid HS256 JWT signed by AUTH_SECRET,
+//! or (without a Bearer token) a verified mTLS client certificate mapped to an actor (see `mtls`).
+//! Webhook receivers (`forge::WEBHOOK_PREFIX`) are passed through without an actor; they
+//! verify the sender's signature themselves.
+//! Each request also carries the token's issue time ([`TokenIssuedAt`]), which the SCIM
+//! directory checks against revocations (`crate::api::service::apply_directory`).
 
 use axum::http::{HeaderMap, StatusCode};
 use hmac::{Hmac, Mac};
@@ -8,6 +13,8 @@ use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::sync::Arc;
 
+use crate::mtls::{ClientCertIdentity, ClientIdentityMapping};
+
 type HmacSha256 = Hmac<Sha256>;
 
 /// Actor type: human user, automated agent, or system service.
@@ -44,6 +51,17 @@ impl Role {
         };
         rank(self) >= rank(other)
     }
+
+    /// Lowercase name, as in JWT claims and policy rules.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Contributor => "contributor",
+            Role::Reviewer => "reviewer",
+            Role::Applier => "applier",
+            Role::Admin => "admin",
+        }
+    }
 }
 
 /// Identity and roles extracted from the JWT (or defaults when auth is disabled).
@@ -61,6 +79,14 @@ impl ActorContext {
         self.roles.iter().any(|r| r.includes(role))
     }
 
+    /// The highest role the actor holds (it includes all the others).
+    pub fn highest_role(&self) -> Option<Role> {
+        self.roles
+            .iter()
+            .copied()
+            .reduce(|a, b| if a.includes(&b) { a } else { b })
+    }
+
     /// Default admin actor used when auth is disabled.
     pub fn dev_default() -> Self {
         Self {
@@ -72,7 +98,7 @@ impl ActorContext {
 }
 
 /// JWT claims expected in the Bearer token.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     /// Subject (actor ID).
     pub sub: String,
@@ -85,8 +111,20 @@ pub struct Claims {
     /// Expiration (Unix timestamp). 0 means no expiration.
     #[serde(default)]
     pub exp: u64,
+    /// Issue time (Unix timestamp); 0 when unknown. Tokens issued before the actor's
+    /// deprovisioning are revoked (see `store::directory`).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub iat: u64,
+}
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
 }
 
+/// Request extension: the `iat` of the JWT the request authenticated with, when it had one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenIssuedAt(pub u64);
+
 fn default_actor_type() -> ActorType {
     ActorType::Human
 }
@@ -98,6 +136,8 @@ pub struct AuthConfig {
     pub disabled: bool,
     /// HMAC-SHA256 shared secret for JWT validation.
     pub secret: Option<String>,
+    /// mTLS client certificate identity → actor mappings (from `mtls.identities`).
+    pub client_identities: Vec<ClientIdentityMapping>,
 }
 
 impl AuthConfig {
@@ -107,10 +147,30 @@ impl AuthConfig {
             .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
             .unwrap_or(true); // default: disabled for backward compat
         let secret = std::env::var("AUTH_SECRET").ok();
-        Self { disabled, secret }
+        Self {
+            disabled,
+            secret,
+            client_identities: Vec::new(),
+        }
     }
 }
 
+/// Sign claims as an HS256 JWT (what `truthlayer-server token issue` prints).
+pub fn issue_jwt(claims: &Claims, secret: &str) -> Result<String, String> {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+    let payload = URL_SAFE_NO_PAD
+        .encode(serde_json::to_vec(claims).map_err(|e| format!("cannot encode claims: {}", e))?);
+    let header_payload = format!("{}.{}", header, payload);
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).map_err(|e| format!("hmac error: {}", e))?;
+    mac.update(header_payload.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    Ok(format!("{}.{}", header_payload, signature))
+}
+
 /// Decode and verify an HS256 JWT token. Returns the Claims on success.
 fn decode_jwt(token: &str, secret: &str) -> Result<Claims, String> {
     use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -160,8 +220,44 @@ pub fn extract_actor(
     headers: &HeaderMap,
     config: &AuthConfig,
 ) -> Result<ActorContext, (StatusCode, String)> {
+    extract_actor_with_client_cert(headers, None, config)
+}
+
+/// Like [`extract_actor`], with the token's issue time, for callers that apply the actor
+/// directory themselves (MCP over stdio).
+pub fn extract_token_actor(
+    headers: &HeaderMap,
+    config: &AuthConfig,
+) -> Result<(ActorContext, Option<TokenIssuedAt>), (StatusCode, String)> {
+    authenticate(headers, None, config)
+}
+
+/// Like [`extract_actor`], but a request without an Authorization header may authenticate
+/// with a verified client certificate. Unmapped certificates get 403.
+pub fn extract_actor_with_client_cert(
+    headers: &HeaderMap,
+    client_cert: Option<&ClientCertIdentity>,
+    config: &AuthConfig,
+) -> Result<ActorContext, (StatusCode, String)> {
+    authenticate(headers, client_cert, config).map(|(actor, _)| actor)
+}
+
+/// The actor, and the token's issue time when it authenticated with a JWT carrying one.
+fn authenticate(
+    headers: &HeaderMap,
+    client_cert: Option<&ClientCertIdentity>,
+    config: &AuthConfig,
+) -> Result<(ActorContext, Option<TokenIssuedAt>), (StatusCode, String)> {
     if config.disabled {
-        return Ok(ActorContext::dev_default());
+        return Ok((ActorContext::dev_default(), None));
+    }
+
+    if let (None, Some(cert)) = (headers.get("authorization"), client_cert) {
+        let actor = cert.resolve(&config.client_identities).ok_or((
+            StatusCode::FORBIDDEN,
+            "client certificate not mapped to an actor".to_string(),
+        ))?;
+        return Ok((actor, None));
     }
 
     let auth_header = headers
@@ -193,11 +289,15 @@ pub fn extract_actor(
         roles.push(Role::Reader);
     }
 
-    Ok(ActorContext {
-        actor_id: claims.sub,
-        actor_type: claims.actor_type,
-        roles,
-    })
+    let issued_at = (claims.iat > 0).then_some(TokenIssuedAt(claims.iat));
+    Ok((
+        ActorContext {
+            actor_id: claims.sub,
+            actor_type: claims.actor_type,
+            roles,
+        },
+        issued_at,
+    ))
 }
 
 /// Tower layer that extracts ActorContext from request headers and inserts it as a request extension.
@@ -232,7 +332,7 @@ where
         + 'static,
     S::Future: Send + 'static,
     ReqBody: Send + 'static,
-    ResBody: Default + Send + 'static,
+    ResBody: From<String> + Send + 'static,
 {
     type Response = S::Response;
     type Error = S::Error;
@@ -251,16 +351,33 @@ where
         let config = self.config.clone();
         let mut inner = self.inner.clone();
         Box::pin(async move {
-            match extract_actor(req.headers(), &config) {
-                Ok(actor) => {
+            if req.uri().path().starts_with(crate::forge::WEBHOOK_PREFIX) {
+                return inner.call(req).await;
+            }
+            let client_cert = req.extensions().get::<ClientCertIdentity>();
+            match authenticate(req.headers(), client_cert, &config) {
+                Ok((actor, issued_at)) => {
                     req.extensions_mut().insert(actor);
+                    if let Some(issued_at) = issued_at {
+                        req.extensions_mut().insert(issued_at);
+                    }
                     inner.call(req).await
                 }
-                Err((_status, _msg)) => {
-                    let body = ResBody::default();
+                Err((status, msg)) => {
+                    let code = if status == StatusCode::FORBIDDEN {
+                        "forbidden"
+                    } else {
+                        "unauthorized"
+                    };
+                    let body =
+                        crate::api::strict::error_body(status, code, msg, Default::default());
                     let res = axum::http::Response::builder()
-                        .status(_status)
-                        .body(body)
+                        .status(status)
+                        .header(
+                            axum::http::header::CONTENT_TYPE,
+                            crate::api::strict::PROBLEM_JSON,
+                        )
+                        .body(ResBody::from(body.to_string()))
                         .unwrap();
                     Ok(res)
                 }
@@ -305,20 +422,85 @@ mod tests {
         let config = AuthConfig {
             disabled: true,
             secret: None,
+            client_identities: Vec::new(),
         };
         let headers = HeaderMap::new();
         let actor = extract_actor(&headers, &config).unwrap();
         assert_eq!(actor.actor_id, "dev-user");
     }
 
+    #[tokio::test]
+    async fn layer_refuses_with_a_problem() {
+        use tower::ServiceExt;
+        let app = axum::Router::new()
+            .route("/nodes", axum::routing::get(|| async { "ok" }))
+            .layer(AuthLayer {
+                config: Arc::new(AuthConfig {
+                    disabled: false,
+                    secret: Some("test-secret".to_string()),
+                    client_identities: Vec::new(),
+                }),
+            });
+        let req = axum::http::Request::get("/nodes")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        crate::api::strict::expect_error(res, StatusCode::UNAUTHORIZED, "unauthorized").await;
+    }
+
     #[test]
     fn extract_actor_missing_header() {
         let config = AuthConfig {
             disabled: false,
             secret: Some("test-secret".to_string()),
+            client_identities: Vec::new(),
         };
         let headers = HeaderMap::new();
         let err = extract_actor(&headers, &config).unwrap_err();
         assert_eq!(err.0, StatusCode::UNAUTHORIZED);
     }
+
+    #[test]
+    fn extract_actor_from_client_cert() {
+        let config = AuthConfig {
+            disabled: false,
+            secret: Some("test-secret".to_string()),
+            client_identities: vec![ClientIdentityMapping {
+                subject: "indexer.svc.local".to_string(),
+                actor_id: Some("indexer".to_string()),
+                actor_type: ActorType::Agent,
+                roles: vec![Role::Contributor],
+            }],
+        };
+        let headers = HeaderMap::new();
+        let cert = ClientCertIdentity {
+            names: vec!["indexer.svc.local".to_string()],
+        };
+        let actor = extract_actor_with_client_cert(&headers, Some(&cert), &config).unwrap();
+        assert_eq!(actor.actor_id, "indexer");
+        assert_eq!(actor.actor_type, ActorType::Agent);
+
+        let unmapped = ClientCertIdentity {
+            names: vec!["stranger".to_string()],
+        };
+        let err = extract_actor_with_client_cert(&headers, Some(&unmapped), &config).unwrap_err();
+        assert_eq!(err.0, StatusCode::FORBIDDEN);
+    }
+    #[test]
+    fn issued_jwt_is_accepted() {
+        let claims = Claims {
+            sub: "ci-bot".to_string(),
+            actor_type: ActorType::Agent,
+            roles: vec![Role::Reviewer],
+            exp: 0,
+            iat: 1_700_000_000,
+        };
+        let token = issue_jwt(&claims, "test-secret").unwrap();
+        let decoded = decode_jwt(&token, "test-secret").unwrap();
+        assert_eq!(decoded.sub, "ci-bot");
+        assert_eq!(decoded.actor_type, ActorType::Agent);
+        assert_eq!(decoded.roles, vec![Role::Reviewer]);
+        assert_eq!(decoded.iat, 1_700_000_000);
+        assert!(decode_jwt(&token, "other-secret").is_err());
+    }
 }
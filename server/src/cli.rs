@@ -0,0 +1,575 @@
+//! Command line: `serve` (default) and offline admin subcommands.
+//!
+//! The admin subcommands work directly on the config root and the file store, so routine
+//! operations (issuing a token, exporting the audit log or ADRs, backups, upgrades) do not need
+//! hand-crafted HTTP calls against a running server. `import bundle` and `migrate` write to
+//! the data directory: stop the server first, it does not pick up changes made underneath it.
+//!
+//! `mcp` serves the Model Context Protocol over stdio for IDE assistants that spawn the
+//! server as a subprocess.
+//!
+//! For backward compatibility a bare path argument means `serve <root>`, and the
+//! `--check-config` / `--strict` flags still work without a subcommand.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::adr;
+use crate::api::mcp;
+use crate::api::routes::AppState;
+use crate::auth::{
+    extract_token_actor, issue_jwt, ActorContext, ActorType, AuthConfig, Claims, Role,
+};
+use crate::cluster::Cluster;
+use crate::config::{load_config_checked, validate_config, ServerConfig};
+use crate::events::EventBus;
+use crate::jobs::JobQueue;
+use crate::outbox::OutboxDispatcher;
+use crate::policy::PolicyConfig;
+use crate::reload::RuntimeConfig;
+use crate::scheduler::Scheduler;
+use crate::store::{load_bundles, ContextStore, FileStore, InMemoryStore, StoreBundle};
+use crate::types::{AuditEvent, NodeQuery, NodeType};
+use crate::version::ServerInfo;
+
+pub const USAGE: &str = "\
+Usage: truthlayer-server [COMMAND] [OPTIONS]
+
+Commands:
+  serve [ROOT] [--strict] [--seed PATH]
+                                     Start the server (default); --seed loads a store
+                                     bundle (or a directory of them) at startup
+  check-config [ROOT]                Validate config; exit 1 on any problem
+  token issue --sub ID [--type human|agent|system] [--role ROLE]... [--ttl SECS]
+                                     Print an HS256 JWT signed with AUTH_SECRET
+  export audit [--format json|csv] [--out FILE]
+                                     Write the audit log (file backend)
+  export adr --out DIR               Write decisions as numbered ADR Markdown files
+                                     (file backend)
+  import bundle FILE                 Load a store bundle (file backend; server stopped)
+  migrate                            Rewrite stored records in the current format (server stopped)
+  snapshot [--out FILE]              Write a store bundle of all data (file backend)
+  mcp [--seed PATH]                  Serve MCP over stdio (actor from TRUTHTLAYER_MCP_TOKEN)
+  help                               Show this message
+
+Every command accepts --root DIR (config root; default TRUTHTLAYER_CONFIG_ROOT or '.').";
+
+/// Default token lifetime for `token issue` (24 hours).
+pub const DEFAULT_TOKEN_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Output format for `export audit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditFormat {
+    Json,
+    Csv,
+}
+
+/// Parsed command line.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Serve {
+        config_root: Option<PathBuf>,
+        strict: bool,
+        /// Store bundle file or directory of bundles to import at startup.
+        seed: Option<PathBuf>,
+    },
+    CheckConfig {
+        config_root: Option<PathBuf>,
+    },
+    TokenIssue {
+        subject: String,
+        actor_type: ActorType,
+        roles: Vec<Role>,
+        ttl_secs: u64,
+    },
+    ExportAudit {
+        config_root: Option<PathBuf>,
+        format: AuditFormat,
+        out: Option<PathBuf>,
+    },
+    /// Decision nodes as ADR files (`crate::adr`) in `out`.
+    ExportAdr {
+        config_root: Option<PathBuf>,
+        out: PathBuf,
+    },
+    ImportBundle {
+        config_root: Option<PathBuf>,
+        file: PathBuf,
+    },
+    Migrate {
+        config_root: Option<PathBuf>,
+    },
+    Snapshot {
+        config_root: Option<PathBuf>,
+        out: Option<PathBuf>,
+    },
+    /// MCP over stdio, in-process over the configured store.
+    Mcp {
+        config_root: Option<PathBuf>,
+        seed: Option<PathBuf>,
+    },
+    Help,
+}
+
+/// Parse arguments (without the program name).
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    let command = match args.peek().map(String::as_str) {
+        Some("serve") | Some("check-config") | Some("export") | Some("import")
+        | Some("migrate") | Some("snapshot") | Some("token") | Some("mcp") | Some("help") => {
+            args.next().unwrap_or_default()
+        }
+        Some("-h") | Some("--help") => return Ok(Command::Help),
+        // Legacy `truthlayer-server <root>`: only an existing directory counts, so a
+        // mistyped subcommand is an error instead of a server with an empty config.
+        Some(root) if !root.starts_with('-') && !Path::new(root).is_dir() => {
+            return Err(format!("unknown command or config root '{}'", root))
+        }
+        _ => "serve".to_string(),
+    };
+    // Two-word commands: `token issue`, `export audit` / `export adr`, `import bundle`.
+    let objects: &[&str] = match command.as_str() {
+        "token" => &["issue"],
+        "export" => &["audit", "adr"],
+        "import" => &["bundle"],
+        _ => &[],
+    };
+    let mut object = String::new();
+    if !objects.is_empty() {
+        match args.next() {
+            Some(o) if objects.contains(&o.as_str()) => object = o,
+            Some(o) => return Err(format!("unknown command '{} {}'", command, o)),
+            None => return Err(format!("usage: {} {} ...", command, objects.join("|"))),
+        }
+    }
+
+    let mut config_root = None;
+    let mut positional = Vec::new();
+    let mut strict = false;
+    let mut check_only = false;
+    let mut subject = None;
+    let mut actor_type = ActorType::Human;
+    let mut roles = Vec::new();
+    let mut ttl_secs = DEFAULT_TOKEN_TTL_SECS;
+    let mut format = AuditFormat::Json;
+    let mut out = None;
+    let mut seed = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "--root" => config_root = Some(PathBuf::from(value("--root")?)),
+            "--strict" => strict = true,
+            "--check-config" => check_only = true,
+            "--sub" => subject = Some(value("--sub")?),
+            "--type" => actor_type = parse_enum("--type", &value("--type")?)?,
+            "--role" => {
+                for role in value("--role")?.split(',').filter(|r| !r.is_empty()) {
+                    roles.push(parse_enum("--role", role)?);
+                }
+            }
+            "--ttl" => {
+                let v = value("--ttl")?;
+                ttl_secs = v.parse().map_err(|e| format!("--ttl '{}': {}", v, e))?;
+            }
+            "--format" => {
+                format = match value("--format")?.as_str() {
+                    "json" => AuditFormat::Json,
+                    "csv" => AuditFormat::Csv,
+                    other => {
+                        return Err(format!("--format: expected json or csv, got '{}'", other))
+                    }
+                }
+            }
+            "--out" => out = Some(PathBuf::from(value("--out")?)),
+            "--seed" => seed = Some(PathBuf::from(value("--seed")?)),
+            "-h" | "--help" => return Ok(Command::Help),
+            flag if flag.starts_with("--") => return Err(format!("unknown flag {}", flag)),
+            _ => positional.push(arg),
+        }
+    }
+
+    // serve/check-config keep accepting the config root as a positional argument.
+    let mut positional = positional.into_iter();
+    let take_root = |config_root: Option<PathBuf>, positional: &mut std::vec::IntoIter<String>| {
+        config_root.or_else(|| positional.next().map(PathBuf::from))
+    };
+    let command = match command.as_str() {
+        "serve" if check_only => Command::CheckConfig {
+            config_root: take_root(config_root, &mut positional),
+        },
+        "serve" => Command::Serve {
+            config_root: take_root(config_root, &mut positional),
+            strict,
+            seed,
+        },
+        "check-config" => Command::CheckConfig {
+            config_root: take_root(config_root, &mut positional),
+        },
+        "token" => Command::TokenIssue {
+            subject: subject.ok_or("token issue requires --sub")?,
+            actor_type,
+            roles: if roles.is_empty() {
+                vec![Role::Reader]
+            } else {
+                roles
+            },
+            ttl_secs,
+        },
+        "export" if object == "adr" => Command::ExportAdr {
+            config_root,
+            out: out.ok_or("export adr requires --out DIR")?,
+        },
+        "export" => Command::ExportAudit {
+            config_root,
+            format,
+            out,
+        },
+        "import" => Command::ImportBundle {
+            config_root,
+            file: positional
+                .next()
+                .map(PathBuf::from)
+                .ok_or("import bundle requires a FILE")?,
+        },
+        "migrate" => Command::Migrate { config_root },
+        "snapshot" => Command::Snapshot { config_root, out },
+        "mcp" => Command::Mcp { config_root, seed },
+        _ => Command::Help,
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument {}", extra));
+    }
+    Ok(command)
+}
+
+/// Parse a lowercase serde enum (roles, actor types) from a flag value.
+fn parse_enum<T: serde::de::DeserializeOwned>(flag: &str, value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_ascii_lowercase()))
+        .map_err(|_| format!("{}: invalid value '{}'", flag, value))
+}
+
+/// Load config, reporting (not failing on) problems that do not block admin commands.
+fn load(config_root: Option<PathBuf>) -> ServerConfig {
+    let (config, issues) = load_config_checked(config_root);
+    for issue in issues {
+        eprintln!("warning: {}", issue);
+    }
+    config
+}
+
+fn open_file_store(config: &ServerConfig) -> Result<FileStore, String> {
+    if config.storage_backend != "file" {
+        return Err(format!(
+            "this command needs the file storage backend (configured: '{}'); \
+             the memory backend keeps no data between runs",
+            config.storage_backend
+        ));
+    }
+    FileStore::with_options(config.file_data_path(), config.file_store.clone())
+        .map_err(|e| e.to_string())
+}
+
+fn write_output(out: Option<&Path>, contents: &str) -> Result<(), String> {
+    match out {
+        Some(path) => std::fs::write(path, contents)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e)),
+        None => {
+            use std::io::Write;
+            writeln!(std::io::stdout().lock(), "{}", contents.trim_end())
+                .map_err(|e| format!("cannot write to stdout: {}", e))
+        }
+    }
+}
+
+/// Run an admin command (anything but `serve`). Returns the process exit code.
+pub async fn run(command: Command) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+    match command {
+        Command::Serve { .. } => Err("serve is handled by the binary".into()),
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(0)
+        }
+        Command::CheckConfig { config_root } => {
+            let (config, mut issues) = load_config_checked(config_root);
+            issues.extend(validate_config(&config));
+            if issues.is_empty() {
+                println!("config OK ({})", config.config_root.display());
+                return Ok(0);
+            }
+            for issue in &issues {
+                eprintln!("config error: {}", issue);
+            }
+            Ok(1)
+        }
+        Command::TokenIssue {
+            subject,
+            actor_type,
+            roles,
+            ttl_secs,
+        } => {
+            let secret = std::env::var("AUTH_SECRET")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .ok_or("AUTH_SECRET must be set to sign tokens")?;
+            let now = chrono::Utc::now().timestamp().max(0) as u64;
+            let exp = if ttl_secs == 0 { 0 } else { now + ttl_secs };
+            let claims = Claims {
+                sub: subject,
+                actor_type,
+                roles,
+                exp,
+                iat: now,
+            };
+            println!("{}", issue_jwt(&claims, &secret)?);
+            Ok(0)
+        }
+        Command::ExportAudit {
+            config_root,
+            format,
+            out,
+        } => {
+            let store = open_file_store(&load(config_root))?;
+            let events = store
+                .query_audit(None, None, None, None, None, Some(u32::MAX), None)
+                .await?
+                .events;
+            let contents = match format {
+                AuditFormat::Csv => AuditEvent::to_csv(&events),
+                AuditFormat::Json => serde_json::to_string_pretty(&events)?,
+            };
+            write_output(out.as_deref(), &contents)?;
+            eprintln!("exported {} audit events", events.len());
+            Ok(0)
+        }
+        Command::ExportAdr { config_root, out } => {
+            let store = open_file_store(&load(config_root))?;
+            let mut decisions = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page = store
+                    .query_nodes(NodeQuery {
+                        r#type: Some(vec![NodeType::Decision]),
+                        limit: Some(1000),
+                        offset: Some(offset),
+                        ..Default::default()
+                    })
+                    .await?;
+                decisions.extend(page.nodes);
+                if !page.has_more {
+                    break;
+                }
+                offset += 1000;
+            }
+            std::fs::create_dir_all(&out)
+                .map_err(|e| format!("cannot create {}: {}", out.display(), e))?;
+            let files = adr::render_all(&decisions);
+            for file in &files {
+                let path = out.join(&file.path);
+                std::fs::write(&path, &file.content)
+                    .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+            }
+            eprintln!(
+                "exported {} decision records to {}",
+                files.len(),
+                out.display()
+            );
+            Ok(0)
+        }
+        Command::ImportBundle { config_root, file } => {
+            let store = open_file_store(&load(config_root))?;
+            let contents = std::fs::read_to_string(&file)
+                .map_err(|e| format!("cannot read {}: {}", file.display(), e))?;
+            let bundle: StoreBundle = serde_json::from_str(&contents)
+                .map_err(|e| format!("malformed bundle {}: {}", file.display(), e))?;
+            let summary = store.import_bundle(bundle).await?;
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+            Ok(0)
+        }
+        Command::Migrate { config_root } => {
+            let store = open_file_store(&load(config_root))?;
+            let report = store.migrate()?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            Ok(if report.failed.is_empty() { 0 } else { 1 })
+        }
+        Command::Snapshot { config_root, out } => {
+            let store = open_file_store(&load(config_root))?;
+            let bundle = store.export_bundle().await?;
+            write_output(out.as_deref(), &serde_json::to_string_pretty(&bundle)?)?;
+            eprintln!(
+                "snapshot: {} nodes, {} proposals, {} audit events (revision {})",
+                bundle.nodes.len(),
+                bundle.proposals.len(),
+                bundle.audit.len(),
+                bundle.revision
+            );
+            Ok(0)
+        }
+        Command::Mcp { config_root, seed } => {
+            let config = load(config_root);
+            let store: Arc<dyn ContextStore> = if config.storage_backend == "file" {
+                Arc::new(open_file_store(&config)?)
+            } else {
+                Arc::new(InMemoryStore::with_limits(
+                    config.memory_limits.clone(),
+                    config.audit_spill_path(),
+                ))
+            };
+            if let Some(seed) = &seed {
+                for (_, bundle) in load_bundles(seed)? {
+                    store.import_bundle(bundle).await?;
+                }
+            }
+            let (actor, issued_at) = mcp_actor()?;
+            eprintln!(
+                "truthlayer MCP server on stdio (actor {}, {} storage)",
+                actor.actor_id, config.storage_backend
+            );
+            let policies = PolicyConfig::load_from_file(&config.policies_file());
+            let jobs = JobQueue::standard(store.clone(), config.jobs.clone());
+            jobs.start();
+            let cluster = Cluster::new(store.clone(), &config.cluster);
+            let scheduler = Scheduler::new(
+                jobs.clone(),
+                store.clone(),
+                &config.tasks,
+                config.retention_file(),
+            );
+            let event_bus = EventBus::new();
+            let outbox = OutboxDispatcher::default();
+            outbox.start(store.clone(), event_bus.clone(), config.audit_sink.clone());
+            let state = AppState {
+                store,
+                server_info: Arc::new(ServerInfo {
+                    transports: vec!["mcp-stdio".to_string()],
+                    storage_backend: config.storage_backend.clone(),
+                }),
+                runtime: RuntimeConfig::new(config, policies),
+                event_bus,
+                jobs,
+                scheduler,
+                cluster,
+                outbox,
+            };
+            mcp::serve_stdio(state, actor, issued_at).await?;
+            Ok(0)
+        }
+    }
+}
+
+/// The stdio MCP caller: the JWT in `TRUTHTLAYER_MCP_TOKEN`, validated like an
+/// `Authorization` header (dev default actor when auth is disabled).
+fn mcp_actor() -> Result<(ActorContext, Option<u64>), String> {
+    let mut headers = axum::http::HeaderMap::new();
+    if let Ok(token) = std::env::var("TRUTHTLAYER_MCP_TOKEN") {
+        let value = format!("Bearer {}", token.trim())
+            .parse()
+            .map_err(|_| "TRUTHTLAYER_MCP_TOKEN is not a valid header value".to_string())?;
+        headers.insert(axum::http::header::AUTHORIZATION, value);
+    }
+    extract_token_actor(&headers, &AuthConfig::from_env())
+        .map(|(actor, issued_at)| (actor, issued_at.map(|t| t.0)))
+        .map_err(|(_, e)| format!("MCP actor: {} (set TRUTHTLAYER_MCP_TOKEN)", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn bare_root_and_legacy_flags_still_serve() {
+        assert_eq!(
+            parse_args(&[]).unwrap(),
+            Command::Serve {
+                config_root: None,
+                strict: false,
+                seed: None,
+            }
+        );
+        assert_eq!(
+            parse_args(&[".", "--strict", "--seed", "fixtures/demo"]).unwrap(),
+            Command::Serve {
+                config_root: Some(PathBuf::from(".")),
+                strict: true,
+                seed: Some(PathBuf::from("fixtures/demo")),
+            }
+        );
+        assert_eq!(
+            parse_args(&["--check-config", "/etc/ctx"]).unwrap(),
+            Command::CheckConfig {
+                config_root: Some(PathBuf::from("/etc/ctx"))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(
+            parse_args(&[
+                "token",
+                "issue",
+                "--sub",
+                "ci",
+                "--type",
+                "agent",
+                "--role",
+                "reviewer,applier"
+            ])
+            .unwrap(),
+            Command::TokenIssue {
+                subject: "ci".to_string(),
+                actor_type: ActorType::Agent,
+                roles: vec![Role::Reviewer, Role::Applier],
+                ttl_secs: DEFAULT_TOKEN_TTL_SECS,
+            }
+        );
+        assert_eq!(
+            parse_args(&["export", "audit", "--format", "csv", "--root", "cfg"]).unwrap(),
+            Command::ExportAudit {
+                config_root: Some(PathBuf::from("cfg")),
+                format: AuditFormat::Csv,
+                out: None,
+            }
+        );
+        assert_eq!(
+            parse_args(&["export", "adr", "--out", "docs/adr"]).unwrap(),
+            Command::ExportAdr {
+                config_root: None,
+                out: PathBuf::from("docs/adr"),
+            }
+        );
+        assert_eq!(
+            parse_args(&["mcp", "--seed", "fixtures/demo"]).unwrap(),
+            Command::Mcp {
+                config_root: None,
+                seed: Some(PathBuf::from("fixtures/demo")),
+            }
+        );
+        assert_eq!(
+            parse_args(&["import", "bundle", "b.json"]).unwrap(),
+            Command::ImportBundle {
+                config_root: None,
+                file: PathBuf::from("b.json"),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parse_args(&["token", "issue"]).is_err());
+        assert!(parse_args(&["token", "revoke"]).is_err());
+        assert!(parse_args(&["token", "issue", "--sub", "x", "--role", "owner"]).is_err());
+        assert!(parse_args(&["export", "audit", "--format", "xml"]).is_err());
+        assert!(parse_args(&["export", "adr"]).is_err());
+        assert!(parse_args(&["migrate", "extra"]).is_err());
+        assert!(parse_args(&["--bogus"]).is_err());
+        assert!(parse_args(&["snapshto"]).is_err());
+    }
+}
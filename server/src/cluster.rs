@@ -0,0 +1,247 @@
+//! Multi-instance coordination (`cluster` in config.json).
+//!
+//! Keeps servers from doing the same work twice at once by taking leases
+//! (`crate::store::lease`) under a per-process instance id:
+//!
+//! - `apply:{proposal id}` while a proposal is applied (released afterwards), held per
+//!   call ([`Cluster::acquire_exclusive`]) so two requests, on one replica or two,
+//!   cannot apply the same proposal concurrently;
+//! - `task:{name}:{due time}` when a scheduled task is due, so one replica queues it;
+//! - `retention_sweep`, renewed by its holder at each retention check, so one replica
+//!   queues the sweeps while it is alive.
+//!
+//! With `cluster.lease_dir` the leases are kept in that directory
+//! (`crate::store::lease_dir`), shared by every replica that mounts it; without it they
+//! are the store's, which the built-in backends keep in process memory. Job execution
+//! needs no lease: workers claim jobs from the store atomically.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::context_store::StoreError;
+use crate::store::{ContextStore, Lease, LeaseDir};
+
+/// `cluster` in config.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    /// This server's name in leases and logs. Default: `{host}-{pid}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    /// How long a lease outlives its holder when the holder dies mid-work. Default: 60.
+    #[serde(default = "default_lease_ttl_secs")]
+    pub lease_ttl_secs: u64,
+    /// Directory the replicas share their leases in (a volume every one mounts).
+    /// Default: the store's leases, which only coordinate this process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_dir: Option<PathBuf>,
+}
+
+fn default_lease_ttl_secs() -> u64 {
+    60
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            instance_id: None,
+            lease_ttl_secs: default_lease_ttl_secs(),
+            lease_dir: None,
+        }
+    }
+}
+
+impl ClusterConfig {
+    /// Problems in a `cluster` config.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.lease_ttl_secs == 0 {
+            issues.push("cluster.lease_ttl_secs: must be at least 1".to_string());
+        }
+        if self
+            .instance_id
+            .as_deref()
+            .is_some_and(|id| id.trim().is_empty())
+        {
+            issues.push("cluster.instance_id: must not be empty".to_string());
+        }
+        if self
+            .lease_dir
+            .as_ref()
+            .is_some_and(|dir| dir.as_os_str().is_empty())
+        {
+            issues.push("cluster.lease_dir: must not be empty".to_string());
+        }
+        issues
+    }
+}
+
+/// This instance's handle on the leases it shares with other instances. Cheap to clone.
+#[derive(Clone)]
+pub struct Cluster {
+    store: Arc<dyn ContextStore>,
+    lease_dir: Option<LeaseDir>,
+    instance_id: Arc<str>,
+    lease_ttl: Duration,
+}
+
+impl Cluster {
+    pub fn new(store: Arc<dyn ContextStore>, config: &ClusterConfig) -> Self {
+        let instance_id = config.instance_id.clone().unwrap_or_else(|| {
+            format!(
+                "{}-{}",
+                crate::store::dir_lock::hostname(),
+                std::process::id()
+            )
+        });
+        Self {
+            store,
+            lease_dir: config.lease_dir.clone().map(LeaseDir::new),
+            instance_id: instance_id.into(),
+            lease_ttl: Duration::from_secs(config.lease_ttl_secs.max(1)),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn lease_ttl(&self) -> Duration {
+        self.lease_ttl
+    }
+
+    /// Take or renew `name` for the configured TTL; `StoreErrorCode::Locked` while another
+    /// instance holds it.
+    pub async fn acquire(&self, name: &str) -> Result<Lease, StoreError> {
+        self.acquire_for(name, self.lease_ttl).await
+    }
+
+    pub async fn acquire_for(&self, name: &str, ttl: Duration) -> Result<Lease, StoreError> {
+        self.acquire_as(name, &self.instance_id, ttl).await
+    }
+
+    async fn acquire_as(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<Lease, StoreError> {
+        let Some(dir) = &self.lease_dir else {
+            return self.store.acquire_lease(name, holder, ttl).await;
+        };
+        let (dir, name, holder) = (dir.clone(), name.to_string(), holder.to_string());
+        tokio::task::spawn_blocking(move || dir.acquire(&name, &holder, ttl))
+            .await
+            .map_err(|e| StoreError::internal(e.to_string()))?
+    }
+
+    /// Take `name` for one call: the holder is this instance's id plus a fresh call id,
+    /// so a second call on this instance is refused like one on another instance. Returns
+    /// the holder, to pass to [`Cluster::release_held`].
+    pub async fn acquire_exclusive(&self, name: &str) -> Result<String, StoreError> {
+        let holder = format!("{}/{}", self.instance_id, uuid::Uuid::new_v4());
+        self.acquire_as(name, &holder, self.lease_ttl).await?;
+        Ok(holder)
+    }
+
+    /// Release `name`. Failures are logged: the lease expires on its own.
+    pub async fn release(&self, name: &str) {
+        self.release_held(name, &self.instance_id).await
+    }
+
+    /// Release `name` if `holder` holds it.
+    pub async fn release_held(&self, name: &str, holder: &str) {
+        let released = match &self.lease_dir {
+            None => self.store.release_lease(name, holder).await,
+            Some(dir) => {
+                let (dir, lease, holder) = (dir.clone(), name.to_string(), holder.to_string());
+                tokio::task::spawn_blocking(move || dir.release(&lease, &holder))
+                    .await
+                    .map_err(|e| StoreError::internal(e.to_string()))
+                    .and_then(|released| released)
+            }
+        };
+        if let Err(e) = released {
+            tracing::warn!(lease = %name, error = %e, "cannot release lease");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    #[tokio::test]
+    async fn instances_sharing_a_store_exclude_each_other() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let instance = |id: &str| {
+            Cluster::new(
+                store.clone(),
+                &ClusterConfig {
+                    instance_id: Some(id.to_string()),
+                    ..Default::default()
+                },
+            )
+        };
+        let (a, b) = (instance("a"), instance("b"));
+
+        a.acquire("apply:p-1").await.unwrap();
+        assert!(matches!(
+            b.acquire("apply:p-1").await,
+            Err(StoreError {
+                code: StoreErrorCode::Locked,
+                ..
+            })
+        ));
+        // Releasing someone else's lease does nothing.
+        b.release("apply:p-1").await;
+        assert!(b.acquire("apply:p-1").await.is_err());
+        a.release("apply:p-1").await;
+        assert_eq!(b.acquire("apply:p-1").await.unwrap().holder, "b");
+
+        // Exclusive leases also exclude other calls on the same instance.
+        let held = a.acquire_exclusive("apply:p-2").await.unwrap();
+        assert!(held.starts_with("a/"));
+        assert!(a.acquire_exclusive("apply:p-2").await.is_err());
+        a.release_held("apply:p-2", &held).await;
+        a.acquire_exclusive("apply:p-2").await.unwrap();
+
+        let default = Cluster::new(store.clone(), &ClusterConfig::default());
+        assert!(default
+            .instance_id()
+            .ends_with(&format!("-{}", std::process::id())));
+    }
+
+    #[tokio::test]
+    async fn replicas_with_their_own_stores_share_a_lease_dir() {
+        let dir = std::env::temp_dir().join(format!("tl-cluster-{}", uuid::Uuid::new_v4()));
+        let replica = |id: &str| {
+            let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+            Cluster::new(
+                store,
+                &ClusterConfig {
+                    instance_id: Some(id.to_string()),
+                    lease_dir: Some(dir.clone()),
+                    ..Default::default()
+                },
+            )
+        };
+        let (a, b) = (replica("a"), replica("b"));
+
+        let held = a.acquire_exclusive("apply:p-1").await.unwrap();
+        let err = b.acquire_exclusive("apply:p-1").await.unwrap_err();
+        assert_eq!(err.code, StoreErrorCode::Locked);
+        assert!(err.message.contains(&held), "{}", err);
+        a.release_held("apply:p-1", &held).await;
+        b.acquire_exclusive("apply:p-1").await.unwrap();
+
+        a.acquire("retention_sweep").await.unwrap();
+        assert!(b.acquire("retention_sweep").await.is_err());
+        // The stores keep no leases of their own.
+        assert!(a.store.list_leases("").await.unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
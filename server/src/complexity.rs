@@ -0,0 +1,157 @@
+//! Proposal size and complexity scoring, so reviewers can triage a large change to
+//! sensitive nodes differently from a typo fix, and `min_approvals` rules can ask more of
+//! high-scoring proposals (`min_score`, see `crate::policy`).
+//!
+//! The score adds up:
+//! - 1 per operation;
+//! - 1 per started KiB of content, titles and descriptions the operations write;
+//! - 2 per distinct node touched;
+//! - per touched node, its sensitivity weight: public 0, internal 1, confidential 3,
+//!   restricted 5. A node counts at the higher of its stored and its new sensitivity.
+//!
+//! The server scores a proposal when it is created and keeps the result in
+//! `metadata.complexity`. Proposals stored without one (created before scoring, or
+//! imported) are scored from their operations alone when a rule needs the score.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::sensitivity::Sensitivity;
+use crate::store::context_store::StoreError;
+use crate::store::ContextStore;
+use crate::types::{Complexity, NodeId, Operation, Proposal};
+
+fn weight(sensitivity: Sensitivity) -> u32 {
+    match sensitivity {
+        Sensitivity::Public => 0,
+        Sensitivity::Internal => 1,
+        Sensitivity::Confidential => 3,
+        Sensitivity::Restricted => 5,
+    }
+}
+
+/// Score `proposal`; `stored` holds the current sensitivity of existing nodes by key
+/// (nodes missing from it count as their new sensitivity, or the default).
+pub fn score(proposal: &Proposal, stored: &HashMap<String, Sensitivity>) -> Complexity {
+    let mut content_bytes = 0u64;
+    let mut nodes: BTreeMap<String, Sensitivity> = BTreeMap::new();
+    let mut touch = |id: &NodeId, new: Option<Sensitivity>| {
+        let key = id.key();
+        let level = new
+            .into_iter()
+            .chain(stored.get(&key).copied())
+            .max()
+            .unwrap_or_default();
+        let entry = nodes.entry(key).or_insert(level);
+        *entry = (*entry).max(level);
+    };
+    for op in &proposal.operations {
+        match op {
+            Operation::Create { node, .. } => {
+                content_bytes += [
+                    Some(&node.content),
+                    node.title.as_ref(),
+                    node.description.as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|s| s.len() as u64)
+                .sum::<u64>();
+                touch(&node.id, node.metadata.sensitivity);
+            }
+            Operation::Update {
+                node_id, changes, ..
+            } => {
+                content_bytes += [&changes.content, &changes.title, &changes.description]
+                    .into_iter()
+                    .flatten()
+                    .map(|s| s.len() as u64)
+                    .sum::<u64>();
+                touch(node_id, changes.sensitivity);
+            }
+            Operation::Delete { node_id, .. } | Operation::StatusChange { node_id, .. } => {
+                touch(node_id, None);
+            }
+        }
+    }
+
+    let mut sensitivity = BTreeMap::new();
+    for level in nodes.values() {
+        *sensitivity.entry(*level).or_insert(0) += 1;
+    }
+    let operations = proposal.operations.len() as u32;
+    let score = operations
+        + content_bytes.div_ceil(1024) as u32
+        + 2 * nodes.len() as u32
+        + nodes.values().map(|l| weight(*l)).sum::<u32>();
+    Complexity {
+        score,
+        operations,
+        content_bytes,
+        nodes: nodes.len() as u32,
+        sensitivity,
+    }
+}
+
+/// Score `proposal` against the stored nodes it touches.
+pub async fn assess(
+    store: &dyn ContextStore,
+    proposal: &Proposal,
+) -> Result<Complexity, StoreError> {
+    let mut stored = HashMap::new();
+    for op in &proposal.operations {
+        let id = match op {
+            Operation::Create { node, .. } => &node.id,
+            Operation::Update { node_id, .. }
+            | Operation::Delete { node_id, .. }
+            | Operation::StatusChange { node_id, .. } => node_id,
+        };
+        if stored.contains_key(&id.key()) {
+            continue;
+        }
+        if let Some(node) = store.get_node(id).await? {
+            stored.insert(id.key(), node.metadata.sensitivity.unwrap_or_default());
+        }
+    }
+    Ok(score(proposal, &stored))
+}
+
+/// The proposal's score: the one recorded on create, else from its operations alone.
+pub fn score_of(proposal: &Proposal) -> u32 {
+    proposal
+        .metadata
+        .complexity
+        .as_ref()
+        .map_or_else(|| score(proposal, &HashMap::new()).score, |c| c.score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_weighs_size_nodes_and_sensitivity() {
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-1", "status": "open",
+            "operations": [
+                { "type": "update", "id": "op-1", "order": 1, "node_id": { "id": "n1" },
+                  "changes": { "content": "x".repeat(1500) } },
+                { "type": "status-change", "id": "op-2", "order": 2, "node_id": { "id": "n1" },
+                  "new_status": "accepted", "old_status": "proposed" },
+                { "type": "delete", "id": "op-3", "order": 3, "node_id": { "id": "n2" } }
+            ]
+        }))
+        .unwrap();
+        let stored = HashMap::from([("n1".to_string(), Sensitivity::Restricted)]);
+        let complexity = score(&proposal, &stored);
+        assert_eq!(complexity.operations, 3);
+        assert_eq!(complexity.content_bytes, 1500);
+        assert_eq!(complexity.nodes, 2);
+        assert_eq!(
+            complexity.sensitivity,
+            BTreeMap::from([(Sensitivity::Internal, 1), (Sensitivity::Restricted, 1)])
+        );
+        // 3 operations + 2 KiB + 2 × 2 nodes + restricted 5 + internal 1.
+        assert_eq!(complexity.score, 15);
+        assert_eq!(score_of(&proposal), 11);
+    }
+}
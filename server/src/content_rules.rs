@@ -0,0 +1,274 @@
+//! Content rules per node type (`content_rules` in config.json, reloadable): a cap on
+//! content size, the content formats allowed and whether a title is required, so one
+//! pasted log dump does not become a multi-megabyte node every list response carries.
+//!
+//! Keys are node types (`note`, `decision`, …) or `*` for every type; a type's rule takes
+//! each setting it leaves unset from `*`. Proposals are checked when created and again
+//! when applied (the rules, or the proposal, may have changed in between), on the nodes
+//! as the proposal would leave them. A create is checked in full; an update only on what
+//! it sets, so a node stored before a rule existed can still be retagged. A breach is a
+//! `content_rule` policy violation (`422`, audited as `policy_evaluated`).
+//!
+//! Formats are recognised from the content: `json` (an object or array that parses),
+//! `html` (starts with `<` and ends with `>`), otherwise `markdown`, plain text included.
+//! Content with control characters other than tab, CR and LF is in no format, so any
+//! `formats` list refuses it.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::policy::PolicyViolation;
+use crate::store::context_store::StoreError;
+use crate::store::ContextStore;
+use crate::types::{ContextNode, NodeType, Operation};
+
+/// Rule name of content rule violations.
+pub const RULE: &str = "content_rule";
+
+/// `content_rules` in config.json: node type (or `*`) → rule.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContentRules(pub BTreeMap<String, ContentRule>);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContentRule {
+    /// Largest `content`, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_content_bytes: Option<usize>,
+    /// Formats `content` may be in; any when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formats: Option<Vec<ContentFormat>>,
+    /// Nodes need a non-blank `title`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_title: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl ContentFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentFormat::Markdown => "markdown",
+            ContentFormat::Json => "json",
+            ContentFormat::Html => "html",
+        }
+    }
+
+    /// The format `content` is in; None for binary-looking content.
+    pub fn detect(content: &str) -> Option<Self> {
+        if content
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r'))
+        {
+            return None;
+        }
+        let trimmed = content.trim();
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+        {
+            Some(ContentFormat::Json)
+        } else if trimmed.starts_with('<') && trimmed.ends_with('>') {
+            Some(ContentFormat::Html)
+        } else {
+            Some(ContentFormat::Markdown)
+        }
+    }
+}
+
+impl ContentRules {
+    /// The rule for `node_type`: its own settings, then `*`'s.
+    pub fn rule_for(&self, node_type: &NodeType) -> ContentRule {
+        let own = self.0.get(node_type.as_str()).cloned().unwrap_or_default();
+        let all = self.0.get("*").cloned().unwrap_or_default();
+        ContentRule {
+            max_content_bytes: own.max_content_bytes.or(all.max_content_bytes),
+            formats: own.formats.or(all.formats),
+            require_title: own.require_title.or(all.require_title),
+        }
+    }
+
+    /// What `node` breaks: its content rules when `content`, its title rule when `title`.
+    pub fn check(&self, node: &ContextNode, content: bool, title: bool) -> Vec<String> {
+        let rule = self.rule_for(&node.node_type);
+        let name = format!("node {} ({})", node.id.key(), node.node_type.as_str());
+        let mut problems = Vec::new();
+        if content {
+            if let Some(max) = rule
+                .max_content_bytes
+                .filter(|max| node.content.len() > *max)
+            {
+                problems.push(format!(
+                    "{}: content is {} bytes, at most {}",
+                    name,
+                    node.content.len(),
+                    max
+                ));
+            }
+            if let Some(formats) = &rule.formats {
+                let format = ContentFormat::detect(&node.content);
+                if !format.is_some_and(|f| formats.contains(&f)) {
+                    let allowed: Vec<&str> = formats.iter().map(|f| f.as_str()).collect();
+                    problems.push(format!(
+                        "{}: content is {}, allowed: {}",
+                        name,
+                        format.map_or("binary", |f| f.as_str()),
+                        allowed.join(", ")
+                    ));
+                }
+            }
+        }
+        let untitled = node.title.as_deref().is_none_or(|t| t.trim().is_empty());
+        if title && rule.require_title == Some(true) && untitled {
+            problems.push(format!("{}: a title is required", name));
+        }
+        problems
+    }
+}
+
+/// The content rules `operations` break on the nodes they would leave; nodes they
+/// update are read from `store`.
+pub async fn violations(
+    store: &dyn ContextStore,
+    rules: &ContentRules,
+    operations: &[Operation],
+) -> Result<Vec<PolicyViolation>, StoreError> {
+    if rules.0.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut sorted: Vec<&Operation> = operations.iter().collect();
+    sorted.sort_by_key(|op| crate::api::validate::op_id_and_order(op).1);
+    // Node key -> the node as left so far (None: deleted), and whether its content and
+    // title are to be checked.
+    let mut nodes: HashMap<String, (Option<ContextNode>, bool, bool)> = HashMap::new();
+    let mut keys: Vec<String> = Vec::new();
+    for op in sorted {
+        match op {
+            Operation::Create { node, .. } => {
+                let key = node.id.key();
+                if !nodes.contains_key(&key) {
+                    keys.push(key.clone());
+                }
+                nodes.insert(key, (Some(node.clone()), true, true));
+            }
+            Operation::Update {
+                node_id, changes, ..
+            } => {
+                let key = node_id.key();
+                if !nodes.contains_key(&key) {
+                    let stored = store.get_node(node_id).await?;
+                    nodes.insert(key.clone(), (stored, false, false));
+                    keys.push(key.clone());
+                }
+                if let Some((Some(node), content, title)) = nodes.get_mut(&key) {
+                    changes.apply_to(node);
+                    *content |= changes.content.is_some();
+                    *title |= changes.title.is_some();
+                }
+            }
+            Operation::Delete { node_id, .. } => {
+                if let Some(entry) = nodes.get_mut(&node_id.key()) {
+                    entry.0 = None;
+                }
+            }
+            Operation::StatusChange { .. } => {}
+        }
+    }
+    let mut violations = Vec::new();
+    for key in keys {
+        if let Some((Some(node), content, title)) = nodes.get(&key) {
+            for problem in rules.check(node, *content, *title) {
+                violations.push(PolicyViolation::new(RULE, problem));
+            }
+        }
+    }
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(title: Option<&str>, content: &str) -> ContextNode {
+        serde_json::from_value(serde_json::json!({
+            "id": { "id": "n1" }, "type": "note", "status": "proposed",
+            "title": title, "content": content,
+            "metadata": { "createdAt": "2026-01-01T00:00:00Z", "createdBy": "x",
+                "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "x", "version": 1 }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn type_rules_fall_back_to_the_wildcard() {
+        let rules: ContentRules = serde_json::from_value(serde_json::json!({
+            "*": { "max_content_bytes": 10 },
+            "note": { "formats": ["markdown"], "require_title": true }
+        }))
+        .unwrap();
+        assert!(rules
+            .check(&note(Some("T"), "# Short"), true, true)
+            .is_empty());
+        assert_eq!(
+            rules.check(&note(None, "{\"log\": \"a long dump\"}"), true, true),
+            [
+                "node n1 (note): content is 22 bytes, at most 10",
+                "node n1 (note): content is json, allowed: markdown",
+                "node n1 (note): a title is required",
+            ]
+        );
+        assert_eq!(
+            ContentFormat::detect("bin\u{0}ary"),
+            None,
+            "control characters"
+        );
+        // Only what an update sets is checked.
+        assert!(rules.check(&note(None, "x"), true, false).is_empty());
+        assert!(rules.rule_for(&NodeType::Decision).formats.is_none());
+    }
+
+    #[tokio::test]
+    async fn proposals_are_checked_on_the_nodes_they_leave() {
+        let store = crate::store::InMemoryStore::new();
+        store
+            .import_bundle(
+                serde_json::from_value(serde_json::json!({ "nodes": [note(None, "old")] }))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let rules: ContentRules = serde_json::from_value(serde_json::json!({
+            "note": { "max_content_bytes": 5, "require_title": true }
+        }))
+        .unwrap();
+        let update = |changes: serde_json::Value| -> Vec<Operation> {
+            serde_json::from_value(serde_json::json!([{ "type": "update", "id": "op-1",
+                "order": 1, "node_id": { "id": "n1" }, "changes": changes }]))
+            .unwrap()
+        };
+        let broken = |ops: Vec<Operation>| {
+            let (store, rules) = (&store, &rules);
+            async move {
+                violations(store, rules, &ops)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|v| v.message)
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert!(broken(update(serde_json::json!({ "tags": ["a"] })))
+            .await
+            .is_empty());
+        assert_eq!(
+            broken(update(serde_json::json!({ "content": "longer" }))).await,
+            ["node n1 (note): content is 6 bytes, at most 5"]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Context packs: the accepted nodes most relevant to a task, formatted for an LLM
+//! prompt under a token budget (`GET /context-pack`).
+//!
+//! Scoring (higher first):
+//! - **Task terms** matched in title and tags (3 points each) and description/content
+//!   (1 point each); requested `tags` add 5 points each.
+//! - **Relationship proximity**: nodes one hop from a matching node get half that node's
+//!   score, two hops a quarter (relationships count in both directions).
+//! - **Recency** breaks ties: up to 1 point, halving every 30 days since `modifiedAt`.
+//!
+//! When nothing matches the task, the most recent nodes are packed instead. Nodes are
+//! added greedily in score order while they fit the budget. Tokens are estimated at four
+//! characters per token, which is close for English prose with common tokenizers.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::types::ContextNode;
+
+/// Default `budget_tokens`.
+pub const DEFAULT_BUDGET_TOKENS: usize = 2_000;
+/// Largest accepted `budget_tokens`.
+pub const MAX_BUDGET_TOKENS: usize = 100_000;
+
+const TITLE_TAG_WEIGHT: f64 = 3.0;
+const BODY_WEIGHT: f64 = 1.0;
+const REQUESTED_TAG_WEIGHT: f64 = 5.0;
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
+/// Words too common to say anything about relevance.
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "are", "was", "were", "will",
+    "should", "would", "can", "could", "how", "what", "why", "when", "which", "who", "our", "your",
+    "their", "its", "has", "have", "not", "but", "all", "any", "use", "using",
+];
+
+/// Estimated prompt tokens for `text` (four characters per token, rounded up).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Lowercase words of at least three characters, without stop words.
+fn terms(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// A node in the pack, with why and how much it costs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackedNode {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub score: f64,
+    pub estimated_tokens: usize,
+    pub markdown: String,
+}
+
+/// The selected context, ready for a prompt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextPack {
+    pub task: String,
+    pub budget_tokens: usize,
+    pub estimated_tokens: usize,
+    pub nodes: Vec<PackedNode>,
+    /// Candidate nodes left out because they did not fit the budget.
+    pub omitted: usize,
+}
+
+impl ContextPack {
+    /// The whole pack as one Markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut out = pack_header(&self.task);
+        for node in &self.nodes {
+            out.push_str(&node.markdown);
+        }
+        out
+    }
+}
+
+fn pack_header(task: &str) -> String {
+    format!("# Context for: {}\n", task)
+}
+
+/// Compact Markdown section for one node (also used for MCP resources).
+pub fn node_markdown(node: &ContextNode) -> String {
+    let mut out = format!(
+        "\n## {}\n\n`{}` · {} · v{}\n",
+        node.title.as_deref().unwrap_or(&node.id.id),
+        node.id.key(),
+        node.node_type.as_str(),
+        node.metadata.version,
+    );
+    if let Some(description) = node.description.as_deref().filter(|d| !d.is_empty()) {
+        out.push_str(&format!("\n{}\n", description));
+    }
+    if !node.content.is_empty() {
+        out.push_str(&format!("\n{}\n", node.content.trim_end()));
+    }
+    out
+}
+
+fn text_score(node: &ContextNode, task_terms: &HashSet<String>, tags: &HashSet<String>) -> f64 {
+    let node_tags: Vec<String> = node
+        .metadata
+        .tags
+        .iter()
+        .flatten()
+        .map(|t| t.to_lowercase())
+        .collect();
+    let mut heading = terms(node.title.as_deref().unwrap_or(&node.id.id));
+    for tag in &node_tags {
+        heading.extend(terms(tag));
+    }
+    let mut body = terms(&node.content);
+    if let Some(description) = &node.description {
+        body.extend(terms(description));
+    }
+    let mut score = 0.0;
+    for term in task_terms {
+        if heading.contains(term) {
+            score += TITLE_TAG_WEIGHT;
+        }
+        if body.contains(term) {
+            score += BODY_WEIGHT;
+        }
+    }
+    score + REQUESTED_TAG_WEIGHT * node_tags.iter().filter(|t| tags.contains(*t)).count() as f64
+}
+
+fn recency_score(node: &ContextNode, now: chrono::DateTime<chrono::Utc>) -> f64 {
+    chrono::DateTime::parse_from_rfc3339(&node.metadata.modified_at)
+        .map(|t| {
+            let age_days =
+                (now - t.with_timezone(&chrono::Utc)).num_seconds().max(0) as f64 / 86_400.0;
+            0.5f64.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+        })
+        .unwrap_or(0.0)
+}
+
+/// Select and format the nodes for `task` (candidates: accepted nodes the caller may
+/// read) within `budget_tokens`.
+pub fn build(
+    task: &str,
+    tags: &[String],
+    candidates: Vec<ContextNode>,
+    budget_tokens: usize,
+    now: chrono::DateTime<chrono::Utc>,
+) -> ContextPack {
+    let task_terms = terms(task);
+    let tags: HashSet<String> = tags.iter().map(|t| t.to_lowercase()).collect();
+    let index: HashMap<String, usize> = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.id.key(), i))
+        .collect();
+
+    // Undirected relationship graph over the candidates.
+    let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); candidates.len()];
+    for (i, node) in candidates.iter().enumerate() {
+        for rel in node.relationships.iter().flatten() {
+            if let Some(&j) = index.get(&rel.target.key()) {
+                if i != j {
+                    neighbours[i].push(j);
+                    neighbours[j].push(i);
+                }
+            }
+        }
+    }
+
+    let direct: Vec<f64> = candidates
+        .iter()
+        .map(|n| text_score(n, &task_terms, &tags))
+        .collect();
+    let mut relevance = direct.clone();
+    for (seed, &score) in direct.iter().enumerate().filter(|(_, s)| **s > 0.0) {
+        // Breadth-first up to two hops; each node gets the best path's share once.
+        let mut seen = HashSet::from([seed]);
+        let mut queue = VecDeque::from([(seed, 0u32)]);
+        while let Some((i, hops)) = queue.pop_front() {
+            if hops == 2 {
+                continue;
+            }
+            for &j in &neighbours[i] {
+                if seen.insert(j) {
+                    relevance[j] += score / f64::from(2u32.pow(hops + 1));
+                    queue.push_back((j, hops + 1));
+                }
+            }
+        }
+    }
+    let any_match = relevance.iter().any(|&s| s > 0.0);
+
+    let mut ranked: Vec<(f64, ContextNode)> = candidates
+        .into_iter()
+        .zip(relevance)
+        .filter(|(_, relevance)| !any_match || *relevance > 0.0)
+        .map(|(node, relevance)| (relevance + recency_score(&node, now), node))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| a.1.id.key().cmp(&b.1.id.key()))
+    });
+
+    let mut used = estimate_tokens(&pack_header(task));
+    let mut nodes = Vec::new();
+    let mut omitted = 0;
+    for (score, node) in ranked {
+        let markdown = node_markdown(&node);
+        let tokens = estimate_tokens(&markdown);
+        if used + tokens > budget_tokens {
+            omitted += 1;
+            continue;
+        }
+        used += tokens;
+        nodes.push(PackedNode {
+            key: node.id.key(),
+            node_type: node.node_type.as_str().to_string(),
+            title: node.title.clone(),
+            score: (score * 100.0).round() / 100.0,
+            estimated_tokens: tokens,
+            markdown,
+        });
+    }
+
+    ContextPack {
+        task: task.to_string(),
+        budget_tokens,
+        estimated_tokens: used,
+        nodes,
+        omitted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::StoreBundle;
+
+    fn demo_nodes() -> Vec<ContextNode> {
+        let bundle: StoreBundle =
+            serde_json::from_str(include_str!("../fixtures/demo/demo.json")).unwrap();
+        bundle.nodes
+    }
+
+    fn keys(pack: &ContextPack) -> Vec<&str> {
+        pack.nodes.iter().map(|n| n.key.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_matches_then_related_nodes() {
+        let nodes = demo_nodes();
+        let decision = nodes
+            .iter()
+            .find(|n| n.id.id == "decision-001")
+            .unwrap()
+            .clone();
+        let task = decision.title.clone().unwrap();
+        let pack = build(&task, &[], nodes, MAX_BUDGET_TOKENS, chrono::Utc::now());
+        let keys = keys(&pack);
+        assert_eq!(keys[0], "decision-001");
+        // goal-001 (implemented by) and risk-001 (related to) are one hop away.
+        assert!(keys.contains(&"goal-001"));
+        assert!(keys.contains(&"risk-001"));
+        assert_eq!(pack.omitted, 0);
+        assert!(pack
+            .to_markdown()
+            .starts_with(&format!("# Context for: {}", task)));
+    }
+
+    #[test]
+    fn respects_budget_and_counts_omitted() {
+        let nodes = demo_nodes();
+        let total = nodes.len();
+        let pack = build("anything at all", &[], nodes, 60, chrono::Utc::now());
+        assert!(pack.estimated_tokens <= 60);
+        assert_eq!(pack.nodes.len() + pack.omitted, total);
+        assert!(pack.omitted > 0);
+
+        let empty = build("x", &[], demo_nodes(), 1, chrono::Utc::now());
+        assert!(empty.nodes.is_empty());
+    }
+
+    #[test]
+    fn estimates_four_chars_per_token() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+}
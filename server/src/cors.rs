@@ -0,0 +1,120 @@
+//! CORS: allowed origins from config (`cors` in config.json), reloadable at runtime.
+//!
+//! An empty `allowed_origins` keeps the historical permissive policy (any origin), which
+//! the VS Code extension and local tooling rely on during development.
+
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use tower::{Layer, ServiceExt};
+use tower_http::cors::{Any, CorsLayer};
+
+use crate::reload::Reloadable;
+
+/// CORS configuration (`cors` in config.json).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Exact origins allowed to call the API (e.g. `https://ctx.example.com`).
+    /// Empty = any origin.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    /// Build the tower-http layer for this config. Invalid origins are skipped with a warning.
+    pub fn to_layer(&self) -> CorsLayer {
+        if self.allowed_origins.is_empty() {
+            return CorsLayer::permissive();
+        }
+        let origins: Vec<HeaderValue> = self
+            .allowed_origins
+            .iter()
+            .filter_map(|o| match HeaderValue::from_str(o) {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    tracing::warn!(origin = %o, "ignoring invalid CORS origin");
+                    None
+                }
+            })
+            .collect();
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(Any)
+            .allow_headers(Any)
+            // Browser clients read ETag for conditional GETs.
+            .expose_headers([axum::http::header::ETAG])
+    }
+}
+
+/// Axum middleware: apply the current CORS layer (swapped on config reload).
+pub async fn reloadable_cors(
+    State(cors): State<Reloadable<CorsLayer>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    // CORS calls the inner service at most once (never for preflight requests).
+    let mut next = Some(next);
+    let inner = tower::service_fn(move |req: Request| {
+        let next = next.take();
+        async move {
+            Ok::<_, Infallible>(match next {
+                Some(next) => next.run(req).await,
+                None => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            })
+        }
+    });
+    match cors.get().layer(inner).oneshot(req).await {
+        Ok(res) => res,
+        Err(never) => match never {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::Router;
+
+    fn app(cors: Reloadable<CorsLayer>) -> Router {
+        Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(cors, reloadable_cors))
+    }
+
+    fn request(origin: &str) -> Request {
+        Request::builder()
+            .uri("/health")
+            .header("origin", origin)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn allowed_origins_apply_and_reload() {
+        let cors = Reloadable::new(
+            CorsConfig {
+                allowed_origins: vec!["https://a.example".to_string()],
+            }
+            .to_layer(),
+        );
+        let res = app(cors.clone())
+            .oneshot(request("https://b.example"))
+            .await
+            .unwrap();
+        assert!(res.headers().get("access-control-allow-origin").is_none());
+
+        cors.set(CorsConfig::default().to_layer());
+        let res = app(cors)
+            .oneshot(request("https://b.example"))
+            .await
+            .unwrap();
+        assert_eq!(res.headers()["access-control-allow-origin"], "*");
+    }
+}
@@ -1,24 +1,126 @@
 //! Server-Sent Events broadcast system for real-time notifications.
 //!
-//! Extensions subscribe to `GET /events?workspace={id}` to receive live updates:
-//! `proposal_updated`, `review_submitted`, `config_changed`, `audit_event`.
+//! Extensions subscribe to `GET /events?workspace={id}` to receive live updates. Each
+//! event's `eventType` names an [`EventKind`] and `data` holds its fields, so the schema
+//! is the enum: clients and server share the names below.
 //!
 //! Uses `tokio::sync::broadcast` — late subscribers that fall behind by more than
 //! `EVENT_CHANNEL_CAPACITY` events will miss older events (acceptable for
 //! notification-style SSE where clients can refresh on reconnect).
+//!
+//! The bus also keeps the last [`EVENT_LOG_CAPACITY`] events, numbered in publish order,
+//! for clients that come back: SSE resumes from `Last-Event-ID` and `GET /changes`
+//! long-polls from a [`Cursor`]. The log lives in memory, per instance; a cursor from
+//! before a restart, or older than the log, is answered with what is left and `reset`,
+//! so the client knows to refresh.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 
+use crate::policy::PolicyViolation;
+use crate::types::{NodeId, ProposalStatus, ReviewAction};
+
 /// Capacity of the event broadcast channel.
 const EVENT_CHANNEL_CAPACITY: usize = 256;
 
+/// Events kept for resuming.
+pub const EVENT_LOG_CAPACITY: usize = 1000;
+
+/// What happened. Serialized as `eventType` (the snake_case variant name) and `data`
+/// (the variant's fields, camelCase; absent for kinds without any).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(
+    tag = "eventType",
+    content = "data",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum EventKind {
+    /// A proposal was created, patched, withdrawn or applied; `status` is its new status.
+    ProposalUpdated { status: ProposalStatus },
+    /// A review was submitted; `status` is the proposal's status after it.
+    ReviewSubmitted {
+        action: ReviewAction,
+        status: ProposalStatus,
+    },
+    /// An applied proposal created or changed a node; `version` is the node's new version.
+    NodeChanged {
+        node_id: NodeId,
+        version: u32,
+        proposal_id: String,
+    },
+    /// A node was restored from the trash.
+    NodeRestored { node_id: NodeId },
+    /// An Admin applied a proposal as an emergency change; `follow_up` is the proposal
+    /// opened for its post-hoc review.
+    EmergencyApplied {
+        justification: String,
+        follow_up: String,
+    },
+    /// A write was refused by policy; the resource is the proposal.
+    PolicyViolation { violations: Vec<PolicyViolation> },
+    /// An applied proposal changed a task's assignee.
+    TaskAssigned(FieldChange),
+    /// An applied proposal changed a task's state.
+    TaskStateChanged(FieldChange),
+    /// An applied proposal answered a question; `recipient` is its author.
+    QuestionAnswered(FieldChange),
+    /// Server configuration or store state changed (`target`: `read_only`, `store`).
+    ConfigChanged { target: String },
+    /// A failed job was queued again.
+    JobRetried,
+    /// An export job was requested.
+    ExportRequested,
+    /// A scheduled task was run by hand.
+    TaskTriggered,
+}
+
+impl EventKind {
+    /// The `eventType` name, as used by the stream filters.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::ProposalUpdated { .. } => "proposal_updated",
+            EventKind::ReviewSubmitted { .. } => "review_submitted",
+            EventKind::NodeChanged { .. } => "node_changed",
+            EventKind::NodeRestored { .. } => "node_restored",
+            EventKind::EmergencyApplied { .. } => "emergency_applied",
+            EventKind::PolicyViolation { .. } => "policy_violation",
+            EventKind::TaskAssigned(_) => "task_assigned",
+            EventKind::TaskStateChanged(_) => "task_state_changed",
+            EventKind::QuestionAnswered(_) => "question_answered",
+            EventKind::ConfigChanged { .. } => "config_changed",
+            EventKind::JobRetried => "job_retried",
+            EventKind::ExportRequested => "export_requested",
+            EventKind::TaskTriggered => "task_triggered",
+        }
+    }
+}
+
+/// One field an applied proposal changed (as in the `proposal_applied` audit event).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub operation_id: String,
+    /// Node key.
+    pub node: String,
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+    /// Who should act on the change (a question's author, for `question_answered`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+}
+
 /// A server event broadcast to SSE subscribers.
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerEvent {
-    /// Event type: `proposal_updated`, `review_submitted`, `config_changed`, `audit_event`.
-    pub event_type: String,
+    /// `eventType` and `data`.
+    #[serde(flatten)]
+    pub kind: EventKind,
     /// Workspace ID this event belongs to (for filtering).
     pub workspace_id: Option<String>,
     /// The resource that changed (proposal ID, node ID, etc.).
@@ -27,26 +129,103 @@ pub struct ServerEvent {
     pub actor_id: String,
     /// ISO 8601 timestamp.
     pub timestamp: String,
-    /// Optional additional data.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<serde_json::Value>,
 }
 
-/// Broadcast channel for server events. Cheaply cloneable (Arc-wrapped internally by broadcast).
+impl ServerEvent {
+    /// An event about `resource_id`, now, outside any workspace.
+    pub fn new(kind: EventKind, resource_id: &str, actor_id: &str) -> Self {
+        Self {
+            kind,
+            workspace_id: None,
+            resource_id: resource_id.to_string(),
+            actor_id: actor_id.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// The event with the workspace of the resource it is about.
+    pub fn in_workspace(mut self, workspace: &str) -> Self {
+        self.workspace_id = Some(workspace.to_string());
+        self
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        self.kind.name()
+    }
+}
+
+/// Position in the event log: the events numbered `seq` and later are still to be read.
+/// `epoch` names the log (one per server run), so cursors from a previous run are told
+/// apart. Clients see it as an opaque `epoch.seq` token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub epoch: String,
+    pub seq: u64,
+}
+
+impl Cursor {
+    pub fn token(&self) -> String {
+        format!("{}.{}", self.epoch, self.seq)
+    }
+
+    /// None for a malformed token.
+    pub fn parse(token: &str) -> Option<Self> {
+        let (epoch, seq) = token.split_once('.')?;
+        Some(Self {
+            epoch: epoch.to_string(),
+            seq: seq.parse().ok()?,
+        })
+    }
+}
+
+/// Logged events after a cursor.
+#[derive(Debug)]
+pub struct LogRead {
+    /// Oldest first, each with its number.
+    pub events: Vec<(u64, ServerEvent)>,
+    /// Where the next read continues.
+    pub cursor: Cursor,
+    /// Events between the given cursor and `events` are gone (older than the log, or
+    /// from another run); the client should refresh its state.
+    pub reset: bool,
+}
+
+#[derive(Default)]
+struct EventLog {
+    events: VecDeque<(u64, ServerEvent)>,
+    /// Number of the next event published.
+    next: u64,
+}
+
+/// Broadcast channel for server events, plus the recent-events log. Cheaply cloneable.
 #[derive(Clone)]
 pub struct EventBus {
     tx: broadcast::Sender<ServerEvent>,
+    log: Arc<Mutex<EventLog>>,
+    epoch: Arc<str>,
 }
 
 impl EventBus {
     pub fn new() -> Self {
         let (tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
-        Self { tx }
+        Self {
+            tx,
+            log: Arc::default(),
+            epoch: crate::ids::ulid().into(),
+        }
     }
 
-    /// Publish an event to all active SSE subscribers.
-    /// If no subscribers are listening, the event is silently dropped.
+    /// Publish an event to all active SSE subscribers and log it.
+    /// If no subscribers are listening, only the log keeps it.
     pub fn publish(&self, event: ServerEvent) {
+        // Logged and sent under the lock, so the log and the channel agree on order.
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let seq = log.next;
+        log.next += 1;
+        log.events.push_back((seq, event.clone()));
+        if log.events.len() > EVENT_LOG_CAPACITY {
+            log.events.pop_front();
+        }
         // send() returns Err only when there are zero receivers — that's fine.
         let _ = self.tx.send(event);
     }
@@ -55,6 +234,39 @@ impl EventBus {
     pub fn subscribe(&self) -> broadcast::Receiver<ServerEvent> {
         self.tx.subscribe()
     }
+
+    /// Cursor after the last event published.
+    pub fn cursor(&self) -> Cursor {
+        let log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        Cursor {
+            epoch: self.epoch.to_string(),
+            seq: log.next,
+        }
+    }
+
+    /// Logged events from `since` on. A cursor of another run gets every logged event.
+    pub fn read_since(&self, since: &Cursor) -> LogRead {
+        let log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let oldest = log.events.front().map_or(log.next, |(seq, _)| *seq);
+        let (from, reset) = if *since.epoch == *self.epoch && since.seq <= log.next {
+            (since.seq, since.seq < oldest)
+        } else {
+            (0, true)
+        };
+        LogRead {
+            events: log
+                .events
+                .iter()
+                .filter(|(seq, _)| *seq >= from)
+                .cloned()
+                .collect(),
+            cursor: Cursor {
+                epoch: self.epoch.to_string(),
+                seq: log.next,
+            },
+            reset,
+        }
+    }
 }
 
 impl Default for EventBus {
@@ -63,39 +275,111 @@ impl Default for EventBus {
     }
 }
 
+/// An event of the named kind in `workspace`, for stream filter tests.
+#[cfg(test)]
+pub(crate) fn test_event(event_type: &str, workspace: Option<&str>) -> ServerEvent {
+    let kind = match event_type {
+        "proposal_updated" => EventKind::ProposalUpdated {
+            status: ProposalStatus::Open,
+        },
+        "review_submitted" => EventKind::ReviewSubmitted {
+            action: ReviewAction::Accept,
+            status: ProposalStatus::Accepted,
+        },
+        "config_changed" => EventKind::ConfigChanged {
+            target: "store".into(),
+        },
+        other => panic!("no test event for {}", other),
+    };
+    ServerEvent {
+        workspace_id: workspace.map(String::from),
+        ..ServerEvent::new(kind, "p-1", "a")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn updated() -> EventKind {
+        EventKind::ProposalUpdated {
+            status: ProposalStatus::Open,
+        }
+    }
+
     #[tokio::test]
     async fn publish_and_receive() {
         let bus = EventBus::new();
         let mut rx = bus.subscribe();
 
         bus.publish(ServerEvent {
-            event_type: "proposal_updated".into(),
             workspace_id: Some("ws-1".into()),
-            resource_id: "p-1".into(),
-            actor_id: "user-1".into(),
-            timestamp: "2026-01-01T00:00:00Z".into(),
-            data: None,
+            ..ServerEvent::new(updated(), "p-1", "user-1")
         });
 
         let event = rx.recv().await.unwrap();
-        assert_eq!(event.event_type, "proposal_updated");
+        assert_eq!(event.event_type(), "proposal_updated");
         assert_eq!(event.resource_id, "p-1");
     }
 
+    #[test]
+    fn the_log_resumes_from_a_cursor() {
+        let bus = EventBus::new();
+        let event = |id: &str| ServerEvent::new(updated(), id, "a");
+        bus.publish(event("p-1"));
+        let cursor = bus.cursor();
+        bus.publish(event("p-2"));
+
+        let read = bus.read_since(&Cursor::parse(&cursor.token()).unwrap());
+        assert!(!read.reset);
+        assert_eq!(read.events.len(), 1);
+        assert_eq!(read.events[0].1.resource_id, "p-2");
+        assert!(bus.read_since(&read.cursor).events.is_empty());
+
+        // A cursor from another run sees what is left, flagged.
+        let stale = Cursor {
+            epoch: "old".into(),
+            seq: 7,
+        };
+        let read = bus.read_since(&stale);
+        assert!(read.reset);
+        assert_eq!(read.events.len(), 2);
+
+        for i in 0..EVENT_LOG_CAPACITY {
+            bus.publish(event(&format!("p-{}", i)));
+        }
+        assert!(bus.read_since(&cursor).reset);
+    }
+
     #[test]
     fn publish_with_no_subscribers_does_not_panic() {
         let bus = EventBus::new();
-        bus.publish(ServerEvent {
-            event_type: "test".into(),
-            workspace_id: None,
-            resource_id: "x".into(),
-            actor_id: "a".into(),
-            timestamp: "2026-01-01T00:00:00Z".into(),
-            data: None,
-        });
+        bus.publish(ServerEvent::new(EventKind::JobRetried, "x", "a"));
+    }
+
+    #[test]
+    fn events_serialize_as_event_type_and_data() {
+        let review = ServerEvent::new(
+            EventKind::ReviewSubmitted {
+                action: ReviewAction::Accept,
+                status: ProposalStatus::Accepted,
+            },
+            "p-1",
+            "alice",
+        );
+        let json = serde_json::to_value(&review).unwrap();
+        assert_eq!(json["eventType"], "review_submitted");
+        assert_eq!(
+            json["data"],
+            serde_json::json!({ "action": "accept", "status": "accepted" })
+        );
+        assert_eq!(json["resourceId"], "p-1");
+        let back: ServerEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(back.event_type(), "review_submitted");
+
+        let json =
+            serde_json::to_value(ServerEvent::new(EventKind::JobRetried, "j-1", "a")).unwrap();
+        assert_eq!(json["eventType"], "job_retried");
+        assert!(json.get("data").is_none());
     }
 }
@@ -0,0 +1,519 @@
+//! Forge integration: proposals mirrored as GitHub pull request / GitLab merge request
+//! checks, configured per workspace (`forge.workspaces` in config.json).
+//!
+//! - `POST /proposals/:id/forge` links a proposal to a request and posts a summary of
+//!   the proposal there as a comment (the `forge_comment` background job, retried like
+//!   any job); applying the proposal posts a short note.
+//! - The forge's webhook (`POST /webhooks/forge/:workspace`, authenticated by its
+//!   signature rather than a JWT) brings approvals back as reviews, with forge logins
+//!   mapped to actors by `identities` (and their roles by `roles`), and records
+//!   pipeline results on the link.
+//! - With `require_green_pipeline` (the default), a linked proposal cannot be applied
+//!   until the last pipeline reported for its request succeeded (`forge_pipeline` policy
+//!   violation).
+//!
+//! A proposal's workspace is [`Proposal::workspace`]: the namespace of the node its first
+//! operation touches, `default` without one.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::auth::Role;
+use crate::jobs::JobHandler;
+use crate::store::ContextStore;
+use crate::types::{JobRecord, Operation, PipelineStatus, Proposal, ReviewAction};
+
+/// Paths under this prefix skip JWT authentication (see `crate::auth`); their handlers
+/// verify the caller themselves.
+pub const WEBHOOK_PREFIX: &str = "/webhooks/";
+
+/// Job kind that posts a comment on a pull / merge request.
+pub const COMMENT_JOB: &str = "forge_comment";
+
+/// `forge` in config.json.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForgeConfig {
+    /// Workspace name (`default` for nodes without a namespace) → its forge project.
+    #[serde(default)]
+    pub workspaces: BTreeMap<String, ForgeWorkspace>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeProvider {
+    Github,
+    Gitlab,
+}
+
+/// One workspace's forge project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeWorkspace {
+    pub provider: ForgeProvider,
+    /// API base URL. Default: `https://api.github.com` / `https://gitlab.com/api/v4`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// `owner/repo` (GitHub) or the project path or id (GitLab).
+    pub project: String,
+    /// Environment variable holding the API token used to post comments.
+    pub token_env: String,
+    /// Environment variable holding the webhook secret.
+    pub webhook_secret_env: String,
+    /// Forge login → actor id; approvals by unmapped logins are ignored.
+    #[serde(default)]
+    pub identities: BTreeMap<String, String>,
+    /// Mapped actor id → roles its forge reviews carry; an actor without an entry has
+    /// none. The roles of its SCIM groups (`scim.group_roles`) take precedence.
+    #[serde(default)]
+    pub roles: BTreeMap<String, Vec<Role>>,
+    /// Refuse to apply a linked proposal until its pipeline succeeded. Default: true.
+    #[serde(default = "default_true")]
+    pub require_green_pipeline: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ForgeConfig {
+    /// Problems in a `forge` config.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for (name, ws) in &self.workspaces {
+            if ws.project.trim().is_empty() {
+                issues.push(format!(
+                    "forge.workspaces.{}.project: must not be empty",
+                    name
+                ));
+            }
+            for (field, value) in [
+                ("token_env", &ws.token_env),
+                ("webhook_secret_env", &ws.webhook_secret_env),
+            ] {
+                if value.trim().is_empty() {
+                    issues.push(format!(
+                        "forge.workspaces.{}.{}: must name an environment variable",
+                        name, field
+                    ));
+                }
+            }
+            for actor in ws.roles.keys() {
+                if !ws.identities.values().any(|a| a == actor) {
+                    issues.push(format!(
+                        "forge.workspaces.{}.roles.{}: no login is mapped to this actor",
+                        name, actor
+                    ));
+                }
+            }
+            if let Some(url) = &ws.api_url {
+                if !url.starts_with("https://") && !url.starts_with("http://") {
+                    issues.push(format!(
+                        "forge.workspaces.{}.api_url: must be an http(s) URL",
+                        name
+                    ));
+                }
+            }
+        }
+        issues
+    }
+}
+
+impl ForgeWorkspace {
+    pub fn api_url(&self) -> String {
+        let url = self.api_url.as_deref().unwrap_or(match self.provider {
+            ForgeProvider::Github => "https://api.github.com",
+            ForgeProvider::Gitlab => "https://gitlab.com/api/v4",
+        });
+        url.trim_end_matches('/').to_string()
+    }
+
+    /// Job payload posting `body` on request `number`. Carries the token's variable
+    /// name, not the token.
+    pub fn comment_job(&self, number: u64, body: String) -> serde_json::Value {
+        serde_json::json!({
+            "provider": self.provider,
+            "apiUrl": self.api_url(),
+            "project": self.project,
+            "number": number,
+            "tokenEnv": self.token_env,
+            "body": body,
+        })
+    }
+}
+
+/// Why a linked proposal may not be applied yet, if it may not.
+pub fn pipeline_blocker(proposal: &Proposal, config: &ForgeConfig) -> Option<String> {
+    let link = proposal.metadata.forge.as_ref()?;
+    let workspace = config.workspaces.get(&link.workspace)?;
+    if !workspace.require_green_pipeline || link.pipeline == Some(PipelineStatus::Success) {
+        return None;
+    }
+    let state = match link.pipeline {
+        Some(status) => serde_json::to_value(status)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default(),
+        None => "not reported".to_string(),
+    };
+    Some(format!(
+        "the pipeline of linked request #{} is {}; it must succeed before apply",
+        link.number, state
+    ))
+}
+
+/// Markdown summary of a proposal, posted on its linked request.
+pub fn summary(proposal: &Proposal) -> String {
+    let mut out = String::new();
+    writeln!(out, "### TruthLayer proposal `{}`", proposal.id).ok();
+    out.push('\n');
+    writeln!(out, "- Author: {}", proposal.metadata.created_by).ok();
+    writeln!(
+        out,
+        "- Status: {}",
+        serde_json::to_value(proposal.status)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    )
+    .ok();
+    if let Some(rationale) = proposal.metadata.rationale.as_deref() {
+        writeln!(out, "- Rationale: {}", rationale).ok();
+    }
+    writeln!(out, "\n| Operation | Node | Change |\n| --- | --- | --- |").ok();
+    for op in &proposal.operations {
+        let (kind, key, change) = match op {
+            Operation::Create { node, .. } => ("create", node.id.key(), node.content.clone()),
+            Operation::Update {
+                node_id, changes, ..
+            } => {
+                let fields: Vec<String> = changes.fields().keys().cloned().collect();
+                ("update", node_id.key(), fields.join(", "))
+            }
+            Operation::Delete { node_id, .. } => ("delete", node_id.key(), String::new()),
+            Operation::StatusChange {
+                node_id,
+                old_status,
+                new_status,
+                ..
+            } => (
+                "status-change",
+                node_id.key(),
+                format!("{:?} → {:?}", old_status, new_status).to_lowercase(),
+            ),
+        };
+        let change: String = change.replace(['\n', '|'], " ").chars().take(80).collect();
+        writeln!(out, "| {} | `{}` | {} |", kind, key, change).ok();
+    }
+    out.push_str("\nApprovals here are recorded as reviews of the proposal.\n");
+    out
+}
+
+/// What a webhook delivery means for linked proposals.
+#[derive(Debug, PartialEq)]
+pub enum ForgeEvent {
+    /// A review of request `number` by forge user `login`.
+    Review {
+        number: u64,
+        login: String,
+        action: ReviewAction,
+        comment: Option<String>,
+    },
+    /// A pipeline / check suite result for the requests `numbers`.
+    Pipeline {
+        numbers: Vec<u64>,
+        sha: Option<String>,
+        status: PipelineStatus,
+    },
+    /// Nothing to do (other event types and actions), with the reason.
+    Ignored(String),
+}
+
+/// Whether a delivery carries the workspace's secret: GitHub signs the body
+/// (`X-Hub-Signature-256`), GitLab sends the secret (`X-Gitlab-Token`).
+pub fn verify(provider: ForgeProvider, headers: &HeaderMap, body: &[u8], secret: &str) -> bool {
+    match provider {
+        ForgeProvider::Github => {
+            let Some(signature) = headers
+                .get("x-hub-signature-256")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("sha256="))
+            else {
+                return false;
+            };
+            let Some(signature) = decode_hex(signature) else {
+                return false;
+            };
+            let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+                return false;
+            };
+            mac.update(body);
+            mac.verify_slice(&signature).is_ok()
+        }
+        ForgeProvider::Gitlab => headers
+            .get("x-gitlab-token")
+            .is_some_and(|v| constant_time_eq(v.as_bytes(), secret.as_bytes())),
+    }
+}
+
+/// Lowercase or uppercase hex to bytes (webhook signatures).
+pub(crate) fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Parse a webhook delivery (`X-GitHub-Event` / `X-Gitlab-Event` and the JSON body).
+pub fn parse_event(
+    provider: ForgeProvider,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<ForgeEvent, String> {
+    let header = match provider {
+        ForgeProvider::Github => "x-github-event",
+        ForgeProvider::Gitlab => "x-gitlab-event",
+    };
+    let kind = headers
+        .get(header)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| format!("missing {} header", header))?;
+    let payload: serde_json::Value =
+        serde_json::from_slice(body).map_err(|e| format!("invalid JSON payload: {}", e))?;
+    let str_at = |pointer: &str| payload.pointer(pointer).and_then(|v| v.as_str());
+    let number_at = |pointer: &str| {
+        payload
+            .pointer(pointer)
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| format!("payload has no {}", pointer))
+    };
+
+    match (provider, kind) {
+        (ForgeProvider::Github, "pull_request_review") => {
+            if str_at("/action") != Some("submitted") {
+                return Ok(ForgeEvent::Ignored("review not submitted".to_string()));
+            }
+            let action = match str_at("/review/state") {
+                Some("approved") => ReviewAction::Accept,
+                Some("changes_requested") => ReviewAction::RequestChanges,
+                other => {
+                    return Ok(ForgeEvent::Ignored(format!(
+                        "review state {}",
+                        other.unwrap_or("missing")
+                    )))
+                }
+            };
+            Ok(ForgeEvent::Review {
+                number: number_at("/pull_request/number")?,
+                login: str_at("/review/user/login")
+                    .ok_or("payload has no /review/user/login")?
+                    .to_string(),
+                action,
+                comment: str_at("/review/body")
+                    .filter(|b| !b.is_empty())
+                    .map(str::to_string),
+            })
+        }
+        (ForgeProvider::Github, "check_suite") => {
+            let status = match (str_at("/action"), str_at("/check_suite/conclusion")) {
+                (Some("requested" | "rerequested"), _) => PipelineStatus::Pending,
+                (Some("completed"), Some("success" | "neutral" | "skipped")) => {
+                    PipelineStatus::Success
+                }
+                (Some("completed"), Some("cancelled" | "stale")) => PipelineStatus::Canceled,
+                (Some("completed"), _) => PipelineStatus::Failed,
+                _ => return Ok(ForgeEvent::Ignored("check suite action".to_string())),
+            };
+            let numbers = payload
+                .pointer("/check_suite/pull_requests")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|pr| pr["number"].as_u64())
+                .collect();
+            Ok(ForgeEvent::Pipeline {
+                numbers,
+                sha: str_at("/check_suite/head_sha").map(str::to_string),
+                status,
+            })
+        }
+        (ForgeProvider::Gitlab, "Merge Request Hook") => {
+            if str_at("/object_attributes/action") != Some("approved") {
+                return Ok(ForgeEvent::Ignored("merge request action".to_string()));
+            }
+            Ok(ForgeEvent::Review {
+                number: number_at("/object_attributes/iid")?,
+                login: str_at("/user/username")
+                    .ok_or("payload has no /user/username")?
+                    .to_string(),
+                action: ReviewAction::Accept,
+                comment: None,
+            })
+        }
+        (ForgeProvider::Gitlab, "Pipeline Hook") => {
+            let status = match str_at("/object_attributes/status") {
+                Some("success") => PipelineStatus::Success,
+                Some("failed") => PipelineStatus::Failed,
+                Some("canceled") => PipelineStatus::Canceled,
+                Some("running") => PipelineStatus::Running,
+                Some(
+                    "created" | "pending" | "preparing" | "scheduled" | "waiting_for_resource",
+                ) => PipelineStatus::Pending,
+                other => {
+                    return Ok(ForgeEvent::Ignored(format!(
+                        "pipeline status {}",
+                        other.unwrap_or("missing")
+                    )))
+                }
+            };
+            Ok(ForgeEvent::Pipeline {
+                numbers: payload
+                    .pointer("/merge_request/iid")
+                    .and_then(|v| v.as_u64())
+                    .into_iter()
+                    .collect(),
+                sha: str_at("/object_attributes/sha").map(str::to_string),
+                status,
+            })
+        }
+        (_, other) => Ok(ForgeEvent::Ignored(format!("event {}", other))),
+    }
+}
+
+/// Posts comments on pull / merge requests (`forge_comment` jobs).
+pub struct ForgeCommentHandler;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentJob {
+    provider: ForgeProvider,
+    api_url: String,
+    project: String,
+    number: u64,
+    token_env: String,
+    body: String,
+}
+
+#[async_trait::async_trait]
+impl JobHandler for ForgeCommentHandler {
+    fn kind(&self) -> &'static str {
+        COMMENT_JOB
+    }
+
+    async fn run(
+        &self,
+        _store: std::sync::Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let comment: CommentJob = serde_json::from_value(job.payload.clone())
+            .map_err(|e| format!("invalid payload: {}", e))?;
+        let token = std::env::var(&comment.token_env)
+            .map_err(|_| format!("{} is not set", comment.token_env))?;
+        let (url, auth) = match comment.provider {
+            ForgeProvider::Github => (
+                format!(
+                    "{}/repos/{}/issues/{}/comments",
+                    comment.api_url, comment.project, comment.number
+                ),
+                ("authorization", format!("Bearer {}", token)),
+            ),
+            ForgeProvider::Gitlab => (
+                format!(
+                    "{}/projects/{}/merge_requests/{}/notes",
+                    comment.api_url,
+                    comment.project.replace('/', "%2F"),
+                    comment.number
+                ),
+                ("private-token", token),
+            ),
+        };
+        let (status, _) =
+            crate::outbound::post_json(&url, &[auth], &serde_json::json!({ "body": comment.body }))
+                .await?;
+        if !status.is_success() {
+            return Err(format!("POST {}: {}", url, status));
+        }
+        Ok(Some(serde_json::json!({ "url": url })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn github_deliveries_are_verified_and_parsed() {
+        let body = br#"{"action":"submitted","review":{"state":"approved","body":"",
+            "user":{"login":"octocat"}},"pull_request":{"number":42}}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(body);
+        let signature: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-github-event", "pull_request_review".parse().unwrap());
+        headers.insert(
+            "x-hub-signature-256",
+            format!("sha256={}", signature).parse().unwrap(),
+        );
+        assert!(verify(ForgeProvider::Github, &headers, body, "s3cret"));
+        assert!(!verify(ForgeProvider::Github, &headers, body, "other"));
+        assert_eq!(
+            parse_event(ForgeProvider::Github, &headers, body).unwrap(),
+            ForgeEvent::Review {
+                number: 42,
+                login: "octocat".to_string(),
+                action: ReviewAction::Accept,
+                comment: None,
+            }
+        );
+
+        headers.insert("x-github-event", "check_suite".parse().unwrap());
+        let suite = br#"{"action":"completed","check_suite":{"conclusion":"timed_out",
+            "head_sha":"abc123","pull_requests":[{"number":42}]}}"#;
+        assert_eq!(
+            parse_event(ForgeProvider::Github, &headers, suite).unwrap(),
+            ForgeEvent::Pipeline {
+                numbers: vec![42],
+                sha: Some("abc123".to_string()),
+                status: PipelineStatus::Failed,
+            }
+        );
+    }
+
+    #[test]
+    fn gitlab_pipelines_map_to_statuses() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-gitlab-event", "Pipeline Hook".parse().unwrap());
+        headers.insert("x-gitlab-token", "s3cret".parse().unwrap());
+        assert!(verify(ForgeProvider::Gitlab, &headers, b"{}", "s3cret"));
+        let body = br#"{"object_attributes":{"status":"success","sha":"def"},
+            "merge_request":{"iid":7}}"#;
+        assert_eq!(
+            parse_event(ForgeProvider::Gitlab, &headers, body).unwrap(),
+            ForgeEvent::Pipeline {
+                numbers: vec![7],
+                sha: Some("def".to_string()),
+                status: PipelineStatus::Success,
+            }
+        );
+        headers.insert("x-gitlab-event", "Push Hook".parse().unwrap());
+        assert!(matches!(
+            parse_event(ForgeProvider::Gitlab, &headers, b"{}").unwrap(),
+            ForgeEvent::Ignored(_)
+        ));
+    }
+}
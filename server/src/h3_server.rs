@@ -15,41 +15,272 @@
 //! giving full stream multiplexing with no head-of-line blocking.
 //! SSE responses (infinite streaming bodies) are handled naturally: the body streaming
 //! loop runs until either the body ends or the client disconnects.
+//!
+//! Resource limits ([`QuicLimits`]): concurrent connections are capped at accept time
+//! (excess handshakes are refused), concurrent request streams per connection are capped
+//! with a semaphore (new streams wait for a slot), and each connection has a request rate
+//! budget (excess requests get `429` without reaching the router).
+//!
+//! Address validation: with `stateless_retry` (default on) every new client must echo a
+//! Retry token before the server allocates handshake state, which blocks reflection and
+//! amplification from spoofed source addresses; the handshake queue and its buffers are
+//! bounded.
+//!
+//! 0-RTT: early data is enabled for fast reconnects, but it is replayable. On streams
+//! accepted before the handshake completes only safe methods are routed; POST, PATCH, PUT
+//! and DELETE get `425 Too Early`, so a replayed `POST /proposals/:id/apply` can never
+//! double-submit. The `425` and `429` bodies are `application/problem+json`, like every
+//! other REST error.
+//!
+//! WebTransport: connections advertise WebTransport support. An extended CONNECT to
+//! `/webtransport` is authorized through the router, then the connection is handed over to
+//! [`crate::webtransport::serve_session`] (requests already in flight keep running).
 
 use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use axum::Router;
 use bytes::{Buf, Bytes};
 use http_body_util::BodyExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch, Semaphore};
 use tower::ServiceExt;
 
+use crate::events::EventBus;
+use crate::limits::{self, BodyLimitConfig};
+use crate::mtls::ClientCertIdentity;
+use crate::reload::Reloadable;
+use crate::webtransport::{self, H3RequestStream};
+
+/// Connection and stream limits for the QUIC endpoint (`quic` in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuicLimits {
+    /// Maximum concurrent QUIC connections; further handshakes are refused.
+    #[serde(default = "default_max_connections")]
+    pub max_connections: usize,
+    /// Maximum concurrent request streams handled per connection.
+    #[serde(default = "default_max_streams_per_connection")]
+    pub max_streams_per_connection: usize,
+    /// Maximum requests per second per connection (0 = unlimited). Excess → 429.
+    #[serde(default = "default_max_requests_per_sec")]
+    pub max_requests_per_sec: u32,
+    /// Require address validation via stateless Retry before any handshake state is kept.
+    #[serde(default = "default_stateless_retry")]
+    pub stateless_retry: bool,
+    /// Lifetime of Retry tokens in seconds.
+    #[serde(default = "default_retry_token_lifetime_secs")]
+    pub retry_token_lifetime_secs: u64,
+    /// Maximum handshakes pending in the accept queue.
+    #[serde(default = "default_max_incoming")]
+    pub max_incoming: usize,
+    /// Bytes buffered per pending handshake before its packets are dropped.
+    #[serde(default = "default_incoming_buffer_bytes")]
+    pub incoming_buffer_bytes: u64,
+    /// Bytes buffered across all pending handshakes before packets are dropped.
+    #[serde(default = "default_incoming_buffer_bytes_total")]
+    pub incoming_buffer_bytes_total: u64,
+}
+
+fn default_max_connections() -> usize {
+    1024
+}
+
+fn default_max_streams_per_connection() -> usize {
+    100
+}
+
+fn default_max_requests_per_sec() -> u32 {
+    200
+}
+
+fn default_stateless_retry() -> bool {
+    true
+}
+
+fn default_retry_token_lifetime_secs() -> u64 {
+    15
+}
+
+fn default_max_incoming() -> usize {
+    4096
+}
+
+fn default_incoming_buffer_bytes() -> u64 {
+    64 * 1024
+}
+
+fn default_incoming_buffer_bytes_total() -> u64 {
+    16 * 1024 * 1024
+}
+
+impl Default for QuicLimits {
+    fn default() -> Self {
+        Self {
+            max_connections: default_max_connections(),
+            max_streams_per_connection: default_max_streams_per_connection(),
+            max_requests_per_sec: default_max_requests_per_sec(),
+            stateless_retry: default_stateless_retry(),
+            retry_token_lifetime_secs: default_retry_token_lifetime_secs(),
+            max_incoming: default_max_incoming(),
+            incoming_buffer_bytes: default_incoming_buffer_bytes(),
+            incoming_buffer_bytes_total: default_incoming_buffer_bytes_total(),
+        }
+    }
+}
+
+impl QuicLimits {
+    /// Apply the handshake-queue and Retry token settings to a quinn server config.
+    pub fn apply_to(&self, server_config: &mut quinn::ServerConfig) {
+        server_config
+            .retry_token_lifetime(Duration::from_secs(self.retry_token_lifetime_secs))
+            .max_incoming(self.max_incoming)
+            .incoming_buffer_size(self.incoming_buffer_bytes)
+            .incoming_buffer_size_total(self.incoming_buffer_bytes_total);
+    }
+}
+
+/// Fixed one-second window request counter for a single connection.
+/// Reads `max_requests_per_sec` on every request so config reloads apply immediately.
+struct RequestRateLimiter {
+    limits: Reloadable<QuicLimits>,
+    window: Mutex<(Instant, u32)>,
+}
+
+impl RequestRateLimiter {
+    fn new(limits: Reloadable<QuicLimits>) -> Self {
+        Self {
+            limits,
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Count one request; false when the current window's budget is exhausted.
+    fn try_acquire(&self) -> bool {
+        let max_per_sec = self.limits.get().max_requests_per_sec;
+        if max_per_sec == 0 {
+            return true;
+        }
+        let mut window = match self.window.lock() {
+            Ok(w) => w,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let now = Instant::now();
+        if now.duration_since(window.0) >= Duration::from_secs(1) {
+            *window = (now, 0);
+        }
+        if window.1 >= max_per_sec {
+            return false;
+        }
+        window.1 += 1;
+        true
+    }
+}
+
 /// Start the HTTP/3 server on a QUIC endpoint and bridge all requests to the axum router.
 ///
 /// This function runs until the endpoint is closed or the process is shut down.
 /// All axum middleware (auth, RBAC, policy, OTEL, CORS) applies to every request —
 /// the router is invoked identically to how `axum::serve` would invoke it over TCP.
+/// Request bodies are capped per `body_limits` while reading from the stream;
+/// connections, streams and request rate are capped per `quic_limits` (only the request
+/// rate follows config reloads; the other limits are fixed at startup).
+/// With `client_auth` (mTLS configured), each request carries the peer's verified
+/// [`ClientCertIdentity`] as an extension for `AuthLayer`.
+/// WebTransport sessions stream events from `event_bus`.
+#[allow(clippy::too_many_arguments)]
 pub async fn serve_h3(
-    server_config: quinn::ServerConfig,
+    mut server_config: quinn::ServerConfig,
+    endpoint_config: quinn::EndpointConfig,
     addr: SocketAddr,
     app: Router,
+    body_limits: Arc<BodyLimitConfig>,
+    quic_limits: Reloadable<QuicLimits>,
+    client_auth: bool,
+    event_bus: EventBus,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let endpoint = quinn::Endpoint::server(server_config, addr)?;
-    tracing::info!(%addr, protocol = "HTTP/3 (QUIC)", "listening");
+    let limits = quic_limits.get();
+    limits.apply_to(&mut server_config);
+    let socket = std::net::UdpSocket::bind(addr)?;
+    let endpoint = quinn::Endpoint::new(
+        endpoint_config,
+        Some(server_config),
+        socket,
+        Arc::new(quinn::TokioRuntime),
+    )?;
+    tracing::info!(
+        %addr,
+        protocol = "HTTP/3 (QUIC)",
+        max_connections = limits.max_connections,
+        stateless_retry = limits.stateless_retry,
+        "listening"
+    );
+
+    let connection_slots = Arc::new(Semaphore::new(limits.max_connections));
 
     while let Some(incoming) = endpoint.accept().await {
+        let remote = incoming.remote_address();
+        // Stateless retry: answer unvalidated Initials with a Retry token and keep no state,
+        // so spoofed sources never get a handshake (or amplified response) from us.
+        if limits.stateless_retry && !incoming.remote_address_validated() {
+            if let Err(e) = incoming.retry() {
+                tracing::debug!(%remote, error = %e, "QUIC retry failed");
+            }
+            continue;
+        }
+        let Ok(permit) = connection_slots.clone().try_acquire_owned() else {
+            tracing::warn!(%remote, "QUIC connection limit reached; refusing connection");
+            incoming.refuse();
+            continue;
+        };
         let app = app.clone();
+        let body_limits = body_limits.clone();
+        let quic_limits = quic_limits.clone();
+        let event_bus = event_bus.clone();
         tokio::spawn(async move {
-            let remote = incoming.remote_address();
-            match incoming.await {
-                Ok(conn) => {
-                    tracing::debug!(%remote, "QUIC connection established");
-                    handle_connection(conn, app).await;
-                    tracing::debug!(%remote, "QUIC connection closed");
-                }
+            // Held for the connection's lifetime; released on drop.
+            let _permit = permit;
+            let connecting = match incoming.accept() {
+                Ok(c) => c,
                 Err(e) => {
-                    tracing::warn!(%remote, error = %e, "QUIC handshake failed");
+                    tracing::warn!(%remote, error = %e, "QUIC accept failed");
+                    return;
                 }
-            }
+            };
+            // Accept 0-RTT/0.5-RTT streams before the handshake completes, tracking completion
+            // so non-idempotent requests in replayable early data can be rejected.
+            let (conn, handshake_done) = match connecting.into_0rtt() {
+                Ok((conn, zero_rtt_accepted)) => {
+                    let (tx, rx) = watch::channel(false);
+                    let watched = conn.clone();
+                    tokio::spawn(async move {
+                        zero_rtt_accepted.await;
+                        if watched.close_reason().is_none() {
+                            let _ = tx.send(true);
+                        }
+                    });
+                    (conn, rx)
+                }
+                Err(connecting) => match connecting.await {
+                    Ok(conn) => (conn, watch::channel(true).1),
+                    Err(e) => {
+                        tracing::warn!(%remote, error = %e, "QUIC handshake failed");
+                        return;
+                    }
+                },
+            };
+            tracing::debug!(%remote, "QUIC connection established");
+            handle_connection(
+                conn,
+                app,
+                body_limits,
+                quic_limits,
+                handshake_done,
+                client_auth,
+                event_bus,
+            )
+            .await;
+            tracing::debug!(%remote, "QUIC connection closed");
         });
     }
 
@@ -57,9 +288,31 @@ pub async fn serve_h3(
 }
 
 /// Handle a single QUIC connection: upgrade to HTTP/3 and accept request streams.
-async fn handle_connection(conn: quinn::Connection, app: Router) {
+/// At most `max_streams_per_connection` requests run concurrently; further streams wait.
+/// `handshake_done` flips to true once the TLS handshake completes; streams accepted before
+/// that may be replayed 0-RTT data and only safe methods (GET, HEAD, OPTIONS, TRACE) are
+/// served on them.
+/// An authorized WebTransport CONNECT ends the loop and hands the connection to the session.
+async fn handle_connection(
+    conn: quinn::Connection,
+    app: Router,
+    body_limits: Arc<BodyLimitConfig>,
+    quic_limits: Reloadable<QuicLimits>,
+    handshake_done: watch::Receiver<bool>,
+    client_auth: bool,
+    event_bus: EventBus,
+) {
+    let quic_conn = conn.clone();
     let h3_conn = h3_quinn::Connection::new(conn);
-    let mut server_conn = match h3::server::Connection::new(h3_conn).await {
+    let mut server_conn = match h3::server::builder()
+        .enable_webtransport(true)
+        .enable_extended_connect(true)
+        .enable_datagram(true)
+        .max_webtransport_sessions(1)
+        .send_grease(true)
+        .build(h3_conn)
+        .await
+    {
         Ok(c) => c,
         Err(e) => {
             tracing::warn!(error = %e, "HTTP/3 connection setup failed");
@@ -67,20 +320,90 @@ async fn handle_connection(conn: quinn::Connection, app: Router) {
         }
     };
 
+    let stream_slots = Arc::new(Semaphore::new(quic_limits.get().max_streams_per_connection));
+    let rate_limiter = Arc::new(RequestRateLimiter::new(quic_limits));
+    // Authorized WebTransport CONNECTs, sent back by their request task for the handover.
+    let (session_tx, mut session_rx) = mpsc::channel::<(http::Request<()>, H3RequestStream)>(1);
+
     loop {
-        match server_conn.accept().await {
+        // Wait for a free stream slot before accepting the next request stream.
+        let Ok(permit) = stream_slots.clone().acquire_owned().await else {
+            break;
+        };
+        let accepted = tokio::select! {
+            accepted = server_conn.accept() => accepted,
+            Some((req, stream)) = session_rx.recv() => {
+                drop(permit);
+                match h3_webtransport::server::WebTransportSession::accept(req, stream, server_conn).await {
+                    Ok(session) => {
+                        let client_cert = if client_auth { peer_identity(&quic_conn) } else { None };
+                        webtransport::serve_session(session, event_bus, move |req, stream| {
+                            let app = app.clone();
+                            let body_limits = body_limits.clone();
+                            let client_cert = client_cert.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = handle_request(req, stream, app, &body_limits, client_cert).await {
+                                    tracing::debug!(error = %e, "request handling error");
+                                }
+                            });
+                        })
+                        .await;
+                    }
+                    Err(e) => tracing::debug!(error = %e, "WebTransport session setup failed"),
+                }
+                return;
+            }
+        };
+        match accepted {
             Ok(Some(resolver)) => {
+                // Tagged now: the handshake may complete while the headers are still
+                // being read, but the stream's data was sent early all the same.
+                let early_data = !*handshake_done.borrow();
                 let app = app.clone();
+                let body_limits = body_limits.clone();
+                let rate_limiter = rate_limiter.clone();
+                let mut handshake_done = handshake_done.clone();
+                let quic_conn = quic_conn.clone();
+                let session_tx = session_tx.clone();
                 tokio::spawn(async move {
+                    let _permit = permit;
                     // Resolve the request (reads HTTP/3 headers from the stream)
-                    let (req, stream) = match resolver.resolve_request().await {
+                    let (req, mut stream) = match resolver.resolve_request().await {
                         Ok(pair) => pair,
                         Err(e) => {
                             tracing::debug!(error = %e, "HTTP/3 request resolution failed");
                             return;
                         }
                     };
-                    if let Err(e) = handle_request(req, stream, app).await {
+                    if let Some(response) = early_data_refusal(req.method(), early_data) {
+                        tracing::debug!(method = %req.method(), "rejecting non-idempotent request in 0-RTT early data");
+                        let _ = send_response(&mut stream, response).await;
+                        return;
+                    }
+                    if !rate_limiter.try_acquire() {
+                        tracing::debug!("per-connection request rate exceeded");
+                        let _ = send_response(&mut stream, too_many_requests()).await;
+                        return;
+                    }
+                    let client_cert = if client_auth {
+                        // The client certificate is only known once the handshake completes.
+                        let _ = handshake_done.wait_for(|done| *done).await;
+                        peer_identity(&quic_conn)
+                    } else {
+                        None
+                    };
+                    if webtransport::is_webtransport_connect(&req) {
+                        let response = authorize_connect(&req, app, client_cert).await;
+                        if response.status().is_success() {
+                            let _ = session_tx.send((req, stream)).await;
+                        } else {
+                            let _ = send_response(&mut stream, response).await;
+                        }
+                        return;
+                    }
+                    if let Err(e) =
+                        handle_request(req, stream, app, &body_limits, client_cert).await
+                    {
                         // Debug level: most errors are client disconnects, not server bugs
                         tracing::debug!(error = %e, "request handling error");
                     }
@@ -96,10 +419,32 @@ async fn handle_connection(conn: quinn::Connection, app: Router) {
     }
 }
 
+/// Route a WebTransport CONNECT (without its stream) through the router so auth, RBAC and
+/// CORS decide whether the session is accepted (`CONNECT /webtransport`).
+async fn authorize_connect(
+    req: &http::Request<()>,
+    app: Router,
+    client_cert: Option<ClientCertIdentity>,
+) -> axum::response::Response {
+    let mut probe = http::Request::new(axum::body::Body::empty());
+    *probe.method_mut() = req.method().clone();
+    *probe.uri_mut() = req.uri().clone();
+    *probe.headers_mut() = req.headers().clone();
+    probe
+        .extensions_mut()
+        .insert(h3::ext::Protocol::WEB_TRANSPORT);
+    if let Some(identity) = client_cert {
+        probe.extensions_mut().insert(identity);
+    }
+    // Router<()> error type is Infallible, so unwrap is safe
+    app.oneshot(probe).await.unwrap()
+}
+
 /// Bridge a single HTTP/3 request to the axum router and stream the response back.
 ///
 /// Flow:
-/// 1. Read the request body from the h3 stream (collected for JSON endpoints)
+/// 1. Read the request body from the h3 stream (collected for JSON endpoints);
+///    reply 413 without calling the router if it exceeds the route's body limit
 /// 2. Construct an `http::Request<axum::body::Body>` that axum understands
 /// 3. Call the router (auth, RBAC, policy, OTEL all apply)
 /// 4. Send response headers through h3
@@ -107,25 +452,86 @@ async fn handle_connection(conn: quinn::Connection, app: Router) {
 /// 6. Finish the h3 stream
 async fn handle_request(
     req: http::Request<()>,
-    mut stream: h3::server::RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>,
+    mut stream: H3RequestStream,
     app: Router,
+    body_limits: &BodyLimitConfig,
+    client_cert: Option<ClientCertIdentity>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // 1. Read request body from h3 stream
+    // 1. Read request body from h3 stream, stopping at the body limit
+    let limit = body_limits.limit_for(req.uri().path());
+    if limits::content_length(req.headers()).is_some_and(|len| len > limit) {
+        return send_response(&mut stream, limits::payload_too_large(limit)).await;
+    }
     let mut body_data = Vec::new();
     while let Some(mut chunk) = stream.recv_data().await? {
         let data = chunk.copy_to_bytes(chunk.remaining());
+        if body_data.len() + data.len() > limit {
+            return send_response(&mut stream, limits::payload_too_large(limit)).await;
+        }
         body_data.extend_from_slice(&data);
     }
 
     // 2. Convert to axum-compatible request (preserves method, URI, headers, extensions)
     let (parts, _) = req.into_parts();
     let body = axum::body::Body::from(Bytes::from(body_data));
-    let axum_req = http::Request::from_parts(parts, body);
+    let mut axum_req = http::Request::from_parts(parts, body);
+    if let Some(identity) = client_cert {
+        axum_req.extensions_mut().insert(identity);
+    }
 
     // 3. Route through axum — all middleware applies (auth, RBAC, OTEL, CORS)
     // Router<()> error type is Infallible, so unwrap is safe
     let response = app.oneshot(axum_req).await.unwrap();
 
+    send_response(&mut stream, response).await
+}
+
+/// Verified client certificate identity of a QUIC connection (mTLS), if any.
+fn peer_identity(conn: &quinn::Connection) -> Option<ClientCertIdentity> {
+    let chain = conn
+        .peer_identity()?
+        .downcast::<Vec<rustls::pki_types::CertificateDer<'static>>>()
+        .ok()?;
+    ClientCertIdentity::from_chain(&chain)
+}
+
+/// The `425` for a `method` request on a stream accepted in early data; None when it may
+/// be served.
+fn early_data_refusal(method: &http::Method, early_data: bool) -> Option<axum::response::Response> {
+    (early_data && !method.is_safe()).then(too_early)
+}
+
+/// 425 response (RFC 8470) for non-idempotent requests received before the handshake
+/// completed: 0-RTT data can be replayed, so the client must retry after the handshake.
+fn too_early() -> axum::response::Response {
+    crate::api::strict::error_response(
+        http::StatusCode::TOO_EARLY,
+        "too_early",
+        "non-idempotent request received in 0-RTT early data; retry after handshake".to_string(),
+        Default::default(),
+    )
+}
+
+/// 429 response for requests over the per-connection rate budget.
+fn too_many_requests() -> axum::response::Response {
+    let mut response = crate::api::strict::error_response(
+        http::StatusCode::TOO_MANY_REQUESTS,
+        "rate_limited",
+        "request rate limit exceeded for connection".to_string(),
+        Default::default(),
+    );
+    response.headers_mut().insert(
+        http::header::RETRY_AFTER,
+        http::HeaderValue::from_static("1"),
+    );
+    response
+}
+
+/// Send an axum response through the h3 stream: headers, body frames, then FIN.
+async fn send_response(
+    stream: &mut H3RequestStream,
+    response: axum::response::Response,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // 4. Split response and send headers
     let (resp_parts, resp_body) = response.into_parts();
     let h3_resp = http::Response::from_parts(resp_parts, ());
@@ -160,3 +566,94 @@ async fn handle_request(
     stream.finish().await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits_with_rate(max_requests_per_sec: u32) -> Reloadable<QuicLimits> {
+        Reloadable::new(QuicLimits {
+            max_requests_per_sec,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn rate_limiter_caps_requests_per_window() {
+        let limiter = RequestRateLimiter::new(limits_with_rate(2));
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+    }
+
+    #[test]
+    fn rate_limiter_follows_reloaded_limit() {
+        let limits = limits_with_rate(1);
+        let limiter = RequestRateLimiter::new(limits.clone());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        limits.set(QuicLimits {
+            max_requests_per_sec: 0,
+            ..Default::default()
+        });
+        assert!(limiter.try_acquire());
+    }
+
+    #[test]
+    fn rate_limiter_zero_is_unlimited() {
+        let limiter = RequestRateLimiter::new(limits_with_rate(0));
+        for _ in 0..1000 {
+            assert!(limiter.try_acquire());
+        }
+    }
+
+    #[test]
+    fn quic_limits_defaults_fill_missing_fields() {
+        let limits: QuicLimits = serde_json::from_str(r#"{ "max_connections": 8 }"#).unwrap();
+        assert_eq!(limits.max_connections, 8);
+        assert_eq!(limits.max_streams_per_connection, 100);
+        assert_eq!(limits.max_requests_per_sec, 200);
+        assert!(limits.stateless_retry);
+        assert_eq!(limits.retry_token_lifetime_secs, 15);
+    }
+
+    #[test]
+    fn quic_limits_allow_disabling_retry() {
+        let limits: QuicLimits =
+            serde_json::from_str(r#"{ "stateless_retry": false, "max_incoming": 16 }"#).unwrap();
+        assert!(!limits.stateless_retry);
+        assert_eq!(limits.max_incoming, 16);
+        let (certs, key) = crate::tls::generate_dev_cert().unwrap();
+        let mut server_config = crate::tls::build_quinn_server_config(certs, key, None).unwrap();
+        limits.apply_to(&mut server_config);
+    }
+
+    #[test]
+    fn early_data_streams_refuse_unsafe_methods() {
+        let (tx, rx) = watch::channel(false);
+        // Tagged when accepted, before the handshake completes...
+        let early_data = !*rx.borrow();
+        tx.send(true).unwrap();
+        // ...so completing it while the headers are read changes nothing.
+        for method in [
+            http::Method::POST,
+            http::Method::PATCH,
+            http::Method::PUT,
+            http::Method::DELETE,
+        ] {
+            let response = early_data_refusal(&method, early_data).expect("refused");
+            assert_eq!(response.status(), http::StatusCode::TOO_EARLY, "{}", method);
+        }
+        assert!(early_data_refusal(&http::Method::GET, early_data).is_none());
+        assert!(early_data_refusal(&http::Method::POST, !*rx.borrow()).is_none());
+    }
+
+    #[tokio::test]
+    async fn refusals_are_problems() {
+        use crate::api::strict::expect_error;
+        expect_error(too_early(), http::StatusCode::TOO_EARLY, "too_early").await;
+        let res = too_many_requests();
+        assert_eq!(res.headers()[http::header::RETRY_AFTER], "1");
+        expect_error(res, http::StatusCode::TOO_MANY_REQUESTS, "rate_limited").await;
+    }
+}
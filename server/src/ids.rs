@@ -0,0 +1,165 @@
+//! Proposal and node ids: the grammar every id must follow, and server-assigned ids.
+//!
+//! Ids end up in URLs and file names (the file backend stores `{id}.json`), so they are
+//! 1–200 characters of ASCII letters, digits and `.` `_` `@` `-`, not starting with
+//! `.`. No `:`: it separates namespace and id in node keys (`NodeId::key`), so
+//! `{namespace: "ui", id: "goal-1"}` is the only node keyed `ui:goal-1`. A proposal or
+//! created node sent without an id gets a ULID: 26 Crockford base32 characters,
+//! sortable by creation time.
+
+use crate::types::{NodeId, Operation, Proposal};
+
+/// Longest id accepted.
+pub const MAX_ID_LEN: usize = 200;
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// A new ULID for the current time.
+pub fn ulid() -> String {
+    let ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    let random = uuid::Uuid::new_v4().into_bytes();
+    let mut tail = [0u8; 10];
+    tail.copy_from_slice(&random[..10]);
+    ulid_from_parts(ms, tail)
+}
+
+/// ULID of a millisecond timestamp (48 bits) and 80 random bits.
+fn ulid_from_parts(ms: u64, random: [u8; 10]) -> String {
+    let mut value = u128::from(ms & 0xFFFF_FFFF_FFFF) << 80;
+    for (i, byte) in random.iter().enumerate() {
+        value |= u128::from(*byte) << (72 - 8 * i);
+    }
+    (0..26)
+        .map(|i| CROCKFORD[((value >> (125 - 5 * i)) & 31) as usize] as char)
+        .collect()
+}
+
+/// Whether `id` follows the id grammar.
+pub fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '@' | '-'))
+}
+
+/// Check `id` (a `what`, e.g. "proposal id") against the grammar.
+pub fn validate(what: &str, id: &str) -> Result<(), String> {
+    if is_valid(id) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid {} '{}': use 1-{} of letters, digits, '.', '_', '@', '-' (not starting with '.')",
+            what, id, MAX_ID_LEN
+        ))
+    }
+}
+
+fn validate_node_id(id: &NodeId) -> Result<(), String> {
+    validate("node id", &id.id)?;
+    match &id.namespace {
+        Some(namespace) => validate("namespace", namespace),
+        None => Ok(()),
+    }
+}
+
+/// Give `proposal` and the nodes it creates a ULID where the client left the id empty,
+/// then check every id in it against the grammar.
+pub fn assign(proposal: &mut Proposal) -> Result<(), String> {
+    if proposal.id.is_empty() {
+        proposal.id = ulid();
+    }
+    validate("proposal id", &proposal.id)?;
+    for op in &mut proposal.operations {
+        match op {
+            Operation::Create { node, .. } => {
+                if node.id.id.is_empty() {
+                    node.id.id = ulid();
+                }
+                validate_node_id(&node.id)?;
+            }
+            Operation::Update { node_id, .. }
+            | Operation::Delete { node_id, .. }
+            | Operation::StatusChange { node_id, .. } => validate_node_id(node_id)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ulids_encode_time_first() {
+        assert_eq!(ulid_from_parts(0, [0; 10]), "00000000000000000000000000");
+        assert_eq!(
+            ulid_from_parts(0xFFFF_FFFF_FFFF, [0xFF; 10]),
+            "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+        );
+        // Reference vector from the ULID spec.
+        assert!(ulid_from_parts(1_469_918_176_385, [0; 10]).starts_with("01ARYZ6S41"));
+        let (a, b) = (ulid(), ulid());
+        assert_eq!(a.len(), 26);
+        assert!(is_valid(&a) && a != b);
+    }
+
+    #[test]
+    fn assigns_missing_ids_and_refuses_bad_ones() {
+        let mut proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "status": "open",
+            "operations": [{
+                "type": "create", "id": "op-1", "order": 1,
+                "node": {
+                    "type": "goal", "status": "proposed", "content": "c",
+                    "metadata": {
+                        "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                        "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                    }
+                }
+            }],
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u"
+            }
+        }))
+        .unwrap();
+        assign(&mut proposal).unwrap();
+        assert_eq!(proposal.id.len(), 26);
+        let Operation::Create { node, .. } = &proposal.operations[0] else {
+            unreachable!()
+        };
+        assert_eq!(node.id.id.len(), 26);
+
+        proposal.id = "../p-1".to_string();
+        assert!(assign(&mut proposal).unwrap_err().contains("proposal id"));
+        proposal.id = "p".repeat(MAX_ID_LEN + 1);
+        assert!(assign(&mut proposal).is_err());
+        proposal.id = "p-1".to_string();
+        assert!(assign(&mut proposal).is_ok());
+    }
+
+    #[test]
+    fn node_keys_name_one_node() {
+        // `ui:goal-1` as an id would share its key with goal-1 in namespace ui.
+        let namespaced = NodeId {
+            id: "goal-1".to_string(),
+            namespace: Some("ui".to_string()),
+        };
+        assert_eq!(NodeId::from_key(&namespaced.key()), namespaced);
+        assert!(validate_node_id(&namespaced).is_ok());
+        for colliding in [
+            NodeId {
+                id: "ui:goal-1".to_string(),
+                namespace: None,
+            },
+            NodeId {
+                id: "goal-1".to_string(),
+                namespace: Some("a:ui".to_string()),
+            },
+        ] {
+            assert!(validate_node_id(&colliding).is_err(), "{:?}", colliding);
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Background job subsystem: a persistent queue in the store, a worker pool and
+//! retry with exponential backoff.
+//!
+//! Features that need work done outside a request (exports, retention sweeps) register a
+//! [`JobHandler`] for their job kind and enqueue [`JobRecord`]s instead of spawning their
+//! own tokio tasks. Workers claim due jobs from the store, so queued work survives a
+//! restart with the file backend. A failed attempt is retried after
+//! `backoff_base_secs * 2^(attempt - 1)` (capped) until `max_attempts`, then the job is
+//! marked failed. Status is served at `/admin/jobs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::store::context_store::StoreError;
+use crate::store::ContextStore;
+use crate::types::{JobRecord, JobStatus};
+
+/// Work for one job kind.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    /// Job kind this handler runs (the `kind` of its records).
+    fn kind(&self) -> &'static str;
+
+    /// Run one attempt. `Ok` carries an optional result stored on the job; `Err` is
+    /// retried with backoff while attempts remain.
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String>;
+}
+
+/// Worker pool and retry settings (`jobs` in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobsConfig {
+    /// Concurrent workers. Default: 2.
+    #[serde(default = "default_workers")]
+    pub workers: usize,
+    /// How often idle workers look for due jobs (retries, jobs enqueued by other tasks).
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Attempts per job before it is marked failed.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles per attempt.
+    #[serde(default = "default_backoff_base_secs")]
+    pub backoff_base_secs: u64,
+    /// Upper bound on the retry delay.
+    #[serde(default = "default_backoff_max_secs")]
+    pub backoff_max_secs: u64,
+}
+
+fn default_workers() -> usize {
+    2
+}
+
+fn default_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_max_attempts() -> u32 {
+    crate::types::DEFAULT_MAX_ATTEMPTS
+}
+
+fn default_backoff_base_secs() -> u64 {
+    5
+}
+
+fn default_backoff_max_secs() -> u64 {
+    600
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            workers: default_workers(),
+            poll_interval_ms: default_poll_interval_ms(),
+            max_attempts: default_max_attempts(),
+            backoff_base_secs: default_backoff_base_secs(),
+            backoff_max_secs: default_backoff_max_secs(),
+        }
+    }
+}
+
+/// Delay before retrying after failed attempt number `attempt` (1-based).
+pub fn backoff(config: &JobsConfig, attempt: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+    Duration::from_secs(
+        config
+            .backoff_base_secs
+            .saturating_mul(factor)
+            .min(config.backoff_max_secs),
+    )
+}
+
+/// Handle to the job queue: enqueue work and run the workers. Cheap to clone.
+#[derive(Clone)]
+pub struct JobQueue {
+    store: Arc<dyn ContextStore>,
+    handlers: Arc<HashMap<&'static str, Arc<dyn JobHandler>>>,
+    notify: Arc<Notify>,
+    config: JobsConfig,
+}
+
+impl JobQueue {
+    /// A queue with no handlers; add them with [`with_handler`](Self::with_handler).
+    pub fn new(store: Arc<dyn ContextStore>, config: JobsConfig) -> Self {
+        Self {
+            store,
+            handlers: Arc::new(HashMap::new()),
+            notify: Arc::new(Notify::new()),
+            config,
+        }
+    }
+
+    /// A queue with the server's built-in handlers (exports and snapshots, retention
+    /// sweeps, maintenance checks, usage rollups).
+    pub fn standard(store: Arc<dyn ContextStore>, config: JobsConfig) -> Self {
+        Self::new(store, config)
+            .with_handler(Arc::new(crate::api::exports::ExportJobHandler))
+            .with_handler(Arc::new(crate::api::exports::SnapshotJobHandler))
+            .with_handler(Arc::new(crate::retention::RetentionSweepHandler))
+            .with_handler(Arc::new(crate::maintenance::StaleProposalCheckHandler))
+            .with_handler(Arc::new(crate::maintenance::HashVerificationHandler))
+            .with_handler(Arc::new(crate::maintenance::OverdueTaskCheckHandler))
+            .with_handler(Arc::new(crate::maintenance::RiskReviewReminderHandler))
+            .with_handler(Arc::new(crate::maintenance::StoreCompactionHandler))
+            .with_handler(Arc::new(crate::forge::ForgeCommentHandler))
+            .with_handler(Arc::new(crate::slack::SlackMessageHandler))
+            .with_handler(Arc::new(crate::usage::UsageRollupHandler))
+    }
+
+    pub fn with_handler(mut self, handler: Arc<dyn JobHandler>) -> Self {
+        Arc::make_mut(&mut self.handlers).insert(handler.kind(), handler);
+        self
+    }
+
+    /// Queue a job of a registered kind and wake a worker.
+    pub async fn enqueue(
+        &self,
+        kind: &str,
+        payload: serde_json::Value,
+    ) -> Result<JobRecord, StoreError> {
+        if !self.handlers.contains_key(kind) {
+            return Err(StoreError::invalid(format!("unknown job kind '{}'", kind)));
+        }
+        let mut job = JobRecord::new(kind, payload);
+        job.max_attempts = self.config.max_attempts.max(1);
+        self.store.save_job(job.clone()).await?;
+        self.notify.notify_one();
+        Ok(job)
+    }
+
+    /// Put a failed job back in the queue with a fresh set of attempts.
+    pub async fn retry(&self, job_id: &str) -> Result<JobRecord, StoreError> {
+        let mut job = self
+            .store
+            .get_job(job_id)
+            .await?
+            .ok_or_else(|| StoreError::not_found(format!("job {}", job_id)))?;
+        if job.status != JobStatus::Failed {
+            return Err(StoreError::conflict(format!(
+                "job {} has not failed (status: {})",
+                job_id,
+                crate::api::service::enum_str(&job.status)
+            )));
+        }
+        job.status = JobStatus::Queued;
+        job.attempts = 0;
+        job.run_after = None;
+        job.finished_at = None;
+        self.store.save_job(job.clone()).await?;
+        self.notify.notify_one();
+        Ok(job)
+    }
+
+    /// Spawn the worker pool. Workers run until the runtime shuts down.
+    pub fn start(&self) -> Vec<tokio::task::JoinHandle<()>> {
+        let workers = self.config.workers.max(1);
+        tracing::debug!(workers, "job workers started");
+        (0..workers)
+            .map(|_| {
+                let queue = self.clone();
+                tokio::spawn(async move { queue.work().await })
+            })
+            .collect()
+    }
+
+    async fn work(&self) {
+        let idle = Duration::from_millis(self.config.poll_interval_ms.max(10));
+        loop {
+            match self.run_next().await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => tracing::warn!(error = %e, "job queue unavailable"),
+            }
+            tokio::select! {
+                _ = self.notify.notified() => {}
+                _ = tokio::time::sleep(idle) => {}
+            }
+        }
+    }
+
+    /// Claim and run one due job. Returns false when nothing was due.
+    pub async fn run_next(&self) -> Result<bool, StoreError> {
+        let kinds: Vec<&str> = self.handlers.keys().copied().collect();
+        let now = chrono::Utc::now();
+        let Some(mut job) = self.store.claim_job(&kinds, &now.to_rfc3339()).await? else {
+            return Ok(false);
+        };
+        let Some(handler) = self.handlers.get(job.kind.as_str()).cloned() else {
+            return Ok(false);
+        };
+
+        // Run in its own task so a panicking handler fails the attempt, not the worker.
+        let store = self.store.clone();
+        let claimed = job.clone();
+        let outcome = tokio::spawn(async move { handler.run(store, &claimed).await })
+            .await
+            .unwrap_or_else(|e| Err(format!("job handler panicked: {}", e)));
+
+        let finished = chrono::Utc::now();
+        match outcome {
+            Ok(result) => {
+                job.status = JobStatus::Completed;
+                job.result = result;
+                job.last_error = None;
+                job.run_after = None;
+                job.finished_at = Some(finished.to_rfc3339());
+            }
+            Err(e) if job.attempts < job.max_attempts => {
+                let delay = backoff(&self.config, job.attempts);
+                tracing::warn!(job = %job.id, kind = %job.kind, attempt = job.attempts, error = %e, "job attempt failed; retrying");
+                job.status = JobStatus::Queued;
+                job.last_error = Some(e);
+                job.run_after = Some(
+                    (finished + chrono::Duration::from_std(delay).unwrap_or_default()).to_rfc3339(),
+                );
+            }
+            Err(e) => {
+                tracing::warn!(job = %job.id, kind = %job.kind, error = %e, "job failed");
+                job.status = JobStatus::Failed;
+                job.last_error = Some(e);
+                job.run_after = None;
+                job.finished_at = Some(finished.to_rfc3339());
+            }
+        }
+        self.store.save_job(job).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails until its `n`th attempt.
+    struct Flaky(AtomicU32, u32);
+
+    #[async_trait]
+    impl JobHandler for Flaky {
+        fn kind(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn run(
+            &self,
+            _store: Arc<dyn ContextStore>,
+            job: &JobRecord,
+        ) -> Result<Option<serde_json::Value>, String> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            if n < self.1 {
+                return Err(format!("attempt {} failed", n));
+            }
+            Ok(Some(job.payload.clone()))
+        }
+    }
+
+    fn queue(succeed_on: u32, max_attempts: u32) -> JobQueue {
+        let config = JobsConfig {
+            max_attempts,
+            backoff_base_secs: 0,
+            ..Default::default()
+        };
+        JobQueue::new(Arc::new(crate::store::InMemoryStore::new()), config)
+            .with_handler(Arc::new(Flaky(AtomicU32::new(0), succeed_on)))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let config = JobsConfig {
+            backoff_base_secs: 5,
+            backoff_max_secs: 30,
+            ..Default::default()
+        };
+        let secs: Vec<u64> = (1..=5).map(|a| backoff(&config, a).as_secs()).collect();
+        assert_eq!(secs, vec![5, 10, 20, 30, 30]);
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_are_retried_until_success() {
+        let q = queue(2, 3);
+        let job = q
+            .enqueue("flaky", serde_json::json!({ "x": 1 }))
+            .await
+            .unwrap();
+        assert!(q.run_next().await.unwrap());
+        let after_first = q.store.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(after_first.status, JobStatus::Queued);
+        assert_eq!(after_first.last_error.as_deref(), Some("attempt 1 failed"));
+
+        assert!(q.run_next().await.unwrap());
+        let done = q.store.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(done.status, JobStatus::Completed);
+        assert_eq!(done.attempts, 2);
+        assert_eq!(done.result, Some(serde_json::json!({ "x": 1 })));
+        assert!(!q.run_next().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn job_fails_after_max_attempts_and_can_be_retried() {
+        let q = queue(3, 2);
+        let job = q.enqueue("flaky", serde_json::Value::Null).await.unwrap();
+        while q.run_next().await.unwrap() {}
+        let failed = q.store.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(failed.attempts, 2);
+
+        q.retry(&job.id).await.unwrap();
+        assert!(q.run_next().await.unwrap());
+        let done = q.store.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(done.status, JobStatus::Completed);
+        assert!(matches!(
+            q.retry(&job.id).await,
+            Err(StoreError {
+                code: StoreErrorCode::Conflict,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn unknown_kinds_are_rejected() {
+        let q = queue(1, 1);
+        assert!(matches!(
+            q.enqueue("nope", serde_json::Value::Null).await,
+            Err(StoreError {
+                code: StoreErrorCode::Invalid,
+                ..
+            })
+        ));
+    }
+}
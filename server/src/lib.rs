@@ -1,20 +1,46 @@
 //! TruthLayer server: server-centric config, storage, RBAC, policy, audit, sensitivity.
-//! HTTP/3 (QUIC) transport with SSE for real-time notifications.
+//! HTTP/3 (QUIC) transport with SSE and WebTransport for real-time notifications.
 //! Rust port: types, ContextStore trait, in-memory store, HTTP API, governance enforcement.
 
+pub mod acme;
+pub mod adr;
 pub mod api;
 pub mod auth;
+pub mod cli;
+pub mod cluster;
+pub mod complexity;
 pub mod config;
+pub mod content_rules;
+pub mod context_pack;
+pub mod cors;
 pub mod events;
+pub mod forge;
 pub mod h3_server;
+pub mod ids;
+pub mod jobs;
+pub mod limits;
+pub mod maintenance;
+pub mod mtls;
+pub mod outbound;
+pub mod outbox;
 pub mod policy;
 pub mod rbac;
+pub mod read_only;
+pub mod reload;
 pub mod retention;
+pub mod scheduler;
+pub mod scim;
 pub mod sensitivity;
+pub mod slack;
 pub mod store;
 pub mod telemetry;
+pub mod timestamps;
 pub mod tls;
+pub mod tls_tcp_server;
 pub mod types;
+pub mod usage;
+pub mod version;
+pub mod webtransport;
 
 pub use auth::{ActorContext, ActorType, AuthConfig, AuthLayer, Role};
 pub use config::{load_config, ServerConfig};
@@ -0,0 +1,203 @@
+//! Request body size limits: global cap plus per-route overrides, from config.
+//!
+//! Enforced on both transports: as axum middleware (TCP listeners, and everything routed
+//! through the router) and in the h3 bridge before the body is buffered. Oversized
+//! requests get `413 Payload Too Large` with an `application/problem+json` body.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use serde::{Deserialize, Serialize};
+
+use crate::api::strict::error_response;
+
+/// Default global body cap (2 MiB, same as axum's extractor default).
+pub const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+fn default_max_body_bytes() -> usize {
+    DEFAULT_MAX_BODY_BYTES
+}
+
+/// Body limit configuration (`limits` in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyLimitConfig {
+    /// Global cap applied when no route override matches.
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Per-route overrides; first match wins.
+    #[serde(default)]
+    pub routes: Vec<RouteBodyLimit>,
+}
+
+/// Body limit for one route pattern, e.g. `/proposals/:id/review`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteBodyLimit {
+    /// Route pattern; `:name` segments match any single path segment.
+    pub path: String,
+    pub max_body_bytes: usize,
+}
+
+impl Default for BodyLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl BodyLimitConfig {
+    /// Effective limit for a request path (query string excluded).
+    pub fn limit_for(&self, path: &str) -> usize {
+        self.routes
+            .iter()
+            .find(|r| path_matches(&r.path, path))
+            .map(|r| r.max_body_bytes)
+            .unwrap_or(self.max_body_bytes)
+    }
+}
+
+/// Match a route pattern against a path segment-by-segment (`:param` matches any segment).
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pat: Vec<&str> = pattern.trim_end_matches('/').split('/').collect();
+    let segs: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    pat.len() == segs.len()
+        && pat
+            .iter()
+            .zip(segs.iter())
+            .all(|(p, s)| p.starts_with(':') || p == s)
+}
+
+/// 413 response with a problem body naming the limit.
+pub fn payload_too_large(limit: usize) -> Response {
+    let mut extra = serde_json::Map::new();
+    extra.insert("limit".to_string(), limit.into());
+    error_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "payload_too_large",
+        format!("request body exceeds limit of {} bytes", limit),
+        extra,
+    )
+}
+
+/// Declared `Content-Length`, if present and valid.
+pub fn content_length(headers: &axum::http::HeaderMap) -> Option<usize> {
+    headers
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Axum middleware: reject bodies over the route's limit with 413.
+/// Checks `Content-Length` up front, then buffers at most `limit` bytes (chunked bodies).
+pub async fn enforce_body_limit(
+    State(config): State<Arc<BodyLimitConfig>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let limit = config.limit_for(req.uri().path());
+    if content_length(req.headers()).is_some_and(|len| len > limit) {
+        return payload_too_large(limit);
+    }
+
+    let (parts, body) = req.into_parts();
+    let bytes = match Limited::new(body, limit).collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(e) if e.downcast_ref::<LengthLimitError>().is_some() => {
+            return payload_too_large(limit);
+        }
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "invalid",
+                format!("failed to read body: {}", e),
+                Default::default(),
+            );
+        }
+    };
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn config() -> BodyLimitConfig {
+        BodyLimitConfig {
+            max_body_bytes: 16,
+            routes: vec![RouteBodyLimit {
+                path: "/proposals/:id/review".to_string(),
+                max_body_bytes: 4,
+            }],
+        }
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/proposals", post(|| async { "ok" }))
+            .route("/proposals/:id/review", post(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(config()),
+                enforce_body_limit,
+            ))
+    }
+
+    #[test]
+    fn limit_for_uses_route_override_then_global() {
+        let cfg = config();
+        assert_eq!(cfg.limit_for("/proposals/p-1/review"), 4);
+        assert_eq!(cfg.limit_for("/proposals/p-1/review/"), 4);
+        assert_eq!(cfg.limit_for("/proposals"), 16);
+        assert_eq!(cfg.limit_for("/proposals/p-1"), 16);
+    }
+
+    #[tokio::test]
+    async fn oversized_body_returns_413() {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/proposals")
+            .body(Body::from(vec![b'x'; 17]))
+            .unwrap();
+        let res = app().oneshot(req).await.unwrap();
+        let json = crate::api::strict::expect_error(
+            res,
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+        )
+        .await;
+        assert_eq!(json["limit"], 16);
+    }
+
+    #[tokio::test]
+    async fn route_override_applies() {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/proposals/p-1/review")
+            .body(Body::from("hello"))
+            .unwrap();
+        let res = app().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn body_within_limit_passes() {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/proposals")
+            .body(Body::from("small"))
+            .unwrap();
+        let res = app().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
@@ -4,36 +4,91 @@
 //! TLS: loads PEM certs from config, or generates self-signed for development.
 //! All axum middleware (auth, RBAC, policy, OTEL, CORS) applies through the h3→axum bridge.
 //!
+//! TLS TCP fallback: set `TRUTHTLAYER_TLS_TCP_LISTEN` (or `server.tls_tcp_listen_addr`) to also
+//! serve HTTP/1.1 + HTTP/2 over TLS for clients without QUIC. Supported in production.
+//!
+//! mTLS: set `TRUTHTLAYER_MTLS_CLIENT_CA` (or `mtls` in config.json) to verify client
+//! certificates on both TLS listeners; `mtls.identities` maps them to actors.
+//!
+//! Admin subcommands (`token issue`, `export audit`, `import bundle`, `check-config`,
+//! `migrate`, `snapshot`) run offline and exit; see [`truthlayer_server::cli`].
+//!
+//! Config validation: `truthlayer-server check-config [root]` reports every config
+//! problem and exits non-zero if there are any. `--strict` (or `TRUTHTLAYER_STRICT_CONFIG=true`)
+//! refuses to start on the same problems; otherwise they are logged as warnings and the
+//! historical fallbacks apply (defaults for a malformed file, memory for an unknown backend).
+//!
 //! Dev mode: set `TRUTHTLAYER_DEV_TCP=true` to also start a plain TCP/HTTP listener
 //! on the same port for Node.js tooling (fetch, integration tests, smoke scripts).
 //! Node.js does not yet support HTTP/3/QUIC clients. The TCP dev listener must NEVER
-//! be enabled in production — QUIC is the only production transport.
+//! be enabled in production — use the TLS TCP fallback instead.
 
 use std::sync::Arc;
 
-use tower_http::cors::CorsLayer;
+use axum::extract::DefaultBodyLimit;
+use rustls::server::danger::ClientCertVerifier;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use truthlayer_server::{
+    acme,
     api::routes,
     auth::{AuthConfig, AuthLayer},
-    config::load_config,
+    cli::{self, Command},
+    cluster::Cluster,
+    config::{load_config_checked, validate_config, ServerConfig},
+    cors,
     events::EventBus,
-    h3_server,
+    h3_server, limits, mtls,
     policy::PolicyConfig,
+    reload::{self, RuntimeConfig},
     retention::RetentionConfig,
-    store::InMemoryStore,
+    store::{self, InMemoryStore},
     telemetry::{
         init_meter_provider, init_tracer, HttpServerMetricsLayer, RequestSpanLayer,
         TraceContextLayer,
     },
-    tls,
+    tls, tls_tcp_server,
+    version::ServerInfo,
 };
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let config_root = std::env::args().nth(1).map(std::path::PathBuf::from);
-    let config = load_config(config_root);
+    let (config_root, strict, seed) = match cli::parse(std::env::args().skip(1)) {
+        Ok(Command::Serve {
+            config_root,
+            strict,
+            seed,
+        }) => (config_root, strict, seed),
+        Ok(command) => std::process::exit(cli::run(command).await?),
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let strict = strict
+        || std::env::var("TRUTHTLAYER_STRICT_CONFIG")
+            .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+    let (config, mut config_issues) = load_config_checked(config_root);
+    // An RBAC override no route reads would leave the route at its default role.
+    let rbac_issues = config.route_roles.validate();
+    if !rbac_issues.is_empty() {
+        for issue in &rbac_issues {
+            eprintln!("config error: {}", issue);
+        }
+        return Err("unknown rbac.routes key(s); refusing to start".into());
+    }
+    config_issues.extend(validate_config(&config));
+
+    if strict && !config_issues.is_empty() {
+        for issue in &config_issues {
+            eprintln!("config error: {}", issue);
+        }
+        return Err(format!(
+            "{} config error(s); refusing to start (strict mode)",
+            config_issues.len()
+        )
+        .into());
+    }
 
     // --- OpenTelemetry (optional) ---
     let enable_console = std::env::var("OTEL_CONSOLE_SPANS")
@@ -83,8 +138,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         None
     };
 
-    let env_filter =
-        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into());
+    // RUST_LOG wins at startup; `server.log_level` is the default and can be reloaded (SIGHUP).
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        tracing_subscriber::EnvFilter::try_new(config.log_level.as_deref().unwrap_or("info"))
+            .unwrap_or_else(|_| "info".into())
+    });
+    let (env_filter, log_filter_handle) = tracing_subscriber::reload::Layer::new(env_filter);
+    let set_log_level: reload::LogLevelSetter = Arc::new(move |level: &str| {
+        let filter = tracing_subscriber::EnvFilter::try_new(level).map_err(|e| e.to_string())?;
+        log_filter_handle.reload(filter).map_err(|e| e.to_string())
+    });
 
     if _tracer_provider.is_some() {
         tracing_subscriber::registry()
@@ -100,85 +163,197 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     }
 
     tracing::info!(config_root = ?config.config_root, backend = %config.storage_backend, "config loaded");
+    for issue in &config_issues {
+        tracing::warn!(issue = %issue, "config problem (use --strict to fail startup)");
+    }
 
     // --- Auth ---
-    let auth_config = AuthConfig::from_env();
+    let mut auth_config = AuthConfig::from_env();
+    if let Some(mtls) = &config.mtls {
+        auth_config.client_identities = mtls.identities.clone();
+    }
     if auth_config.disabled {
         tracing::warn!("authentication DISABLED (AUTH_DISABLED=true or default). Set AUTH_SECRET and AUTH_DISABLED=false for production.");
     }
 
     // --- Policy ---
-    let policies_path = config.config_root.join("policies.json");
-    let policies = Arc::new(PolicyConfig::load_from_file(&policies_path));
+    let policies = PolicyConfig::load_from_file(&config.policies_file());
     if !policies.rules.is_empty() {
         tracing::info!(rules = policies.rules.len(), "policy engine loaded");
     }
 
     // --- Storage ---
-    let store: Arc<dyn truthlayer_server::ContextStore> = match config.storage_backend.as_str() {
-        "memory" | "mem" => Arc::new(InMemoryStore::new()),
-        "file" => {
-            let data_dir = config.file_data_dir.as_deref().unwrap_or("data");
-            let data_path = config.config_root.join(data_dir);
-            tracing::info!(path = ?data_path, "using file-based storage");
-            Arc::new(
-                truthlayer_server::store::FileStore::new(data_path)
-                    .expect("failed to initialize file store"),
-            )
-        }
-        _ => {
-            tracing::warn!(
-                "unknown storage backend '{}', using memory",
-                config.storage_backend
+    let (storage_backend, store): (&str, Arc<dyn truthlayer_server::ContextStore>) =
+        match config.storage_backend.as_str() {
+            "memory" | "mem" => (
+                "memory",
+                Arc::new(InMemoryStore::with_limits(
+                    config.memory_limits.clone(),
+                    config.audit_spill_path(),
+                )),
+            ),
+            "file" => {
+                let data_path = config.file_data_path();
+                tracing::info!(path = ?data_path, "using file-based storage");
+                (
+                    "file",
+                    Arc::new(
+                        truthlayer_server::store::FileStore::with_options(
+                            data_path,
+                            config.file_store.clone(),
+                        )
+                        .map_err(|e| format!("cannot open file store: {}", e))?,
+                    ),
+                )
+            }
+            _ => {
+                tracing::warn!(
+                    "unknown storage backend '{}', using memory",
+                    config.storage_backend
+                );
+                (
+                    "memory",
+                    Arc::new(InMemoryStore::with_limits(
+                        config.memory_limits.clone(),
+                        config.audit_spill_path(),
+                    )),
+                )
+            }
+        };
+
+    // --- Seed data (demo / dev / test) ---
+    if let Some(seed) = &seed {
+        for (file, bundle) in store::load_bundles(seed)? {
+            let summary = store.import_bundle(bundle).await?;
+            tracing::info!(
+                file = %file.display(),
+                nodes = summary.nodes,
+                proposals = summary.proposals,
+                reviews = summary.reviews,
+                skipped = summary.skipped,
+                "seed data loaded"
             );
-            Arc::new(InMemoryStore::new())
         }
-    };
+    }
 
     // --- Retention (background task) ---
-    let retention_path = config.config_root.join("retention.json");
+    let retention_path = config.retention_file();
     let retention_config = RetentionConfig::load_from_file(&retention_path);
     if !retention_config.rules.is_empty() {
         tracing::info!(rules = retention_config.rules.len(), "retention engine loaded");
-        truthlayer_server::retention::spawn_retention_task(store.clone(), retention_config);
+        // A cron schedule under `tasks.retention_sweep` replaces the interval timer.
+        if config.tasks.contains_key("retention_sweep") {
+            tracing::info!("retention sweeps run on the task schedule");
+        } else {
+            truthlayer_server::retention::spawn_retention_task(
+                store.clone(),
+                retention_config,
+                Cluster::new(store.clone(), &config.cluster),
+            );
+        }
     }
 
     // --- Event bus (SSE notifications) ---
     let event_bus = EventBus::new();
 
+    // --- Optional dev-mode TCP listener (flag) ---
+    // Node.js (v24) does not support HTTP/3/QUIC clients yet. To allow `fetch()`-based
+    // tools (integration tests, smoke scripts, VS Code extension host) to reach the
+    // server during development, set TRUTHTLAYER_DEV_TCP=true.
+    let dev_tcp = std::env::var("TRUTHTLAYER_DEV_TCP")
+        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+
+    // --- Deployment info (GET /version) ---
+    let mut transports = vec!["h3".to_string()];
+    if config.tls_tcp_listen_addr.is_some() {
+        transports.push("tls-tcp".to_string());
+    }
+    if dev_tcp {
+        transports.push("dev-tcp".to_string());
+    }
+    let server_info = ServerInfo {
+        transports,
+        storage_backend: storage_backend.to_string(),
+    };
+
     // --- Axum router + middleware ---
-    let app = routes::router(store, policies, event_bus);
+    // --- Runtime config (reloadable on SIGHUP: policies, CORS, QUIC request rate, log level,
+    // read-only mode) ---
+    let runtime = RuntimeConfig::new(config.clone(), policies).with_log_level_setter(set_log_level);
+    reload::spawn_sighup_reload(runtime.clone(), config.config_root.clone(), store.clone());
+    if config.read_only {
+        tracing::warn!("starting in read-only mode (server.read_only); writes return 503");
+    }
+
+    let app = routes::router(store, runtime.clone(), event_bus.clone(), server_info);
 
     let app = app.layer(AuthLayer {
         config: Arc::new(auth_config),
     });
 
     let app = if _tracer_provider.is_some() {
-        app.layer(HttpServerMetricsLayer::default())
-            .layer(TraceContextLayer::default())
-            .layer(RequestSpanLayer::default())
+        app.layer(HttpServerMetricsLayer)
+            .layer(TraceContextLayer)
+            .layer(RequestSpanLayer)
     } else {
         app
     };
-    let app = app.layer(CorsLayer::permissive());
+    // --- Body size limits (413 on oversized requests; replaces axum's 2 MiB extractor cap) ---
+    let body_limits = Arc::new(config.body_limits.clone());
+    let app = app
+        .layer(axum::middleware::from_fn_with_state(
+            body_limits.clone(),
+            limits::enforce_body_limit,
+        ))
+        .layer(DefaultBodyLimit::disable());
+    let app = app.layer(axum::middleware::from_fn_with_state(
+        runtime.cors.clone(),
+        cors::reloadable_cors,
+    ));
 
-    // --- TLS certificates ---
-    let (certs, key) = if let (Some(cert_path), Some(key_path)) =
-        (&config.tls_cert_path, &config.tls_key_path)
-    {
-        tracing::info!(cert = %cert_path, key = %key_path, "loading TLS certificates from disk");
-        tls::load_certs_from_pem(
-            std::path::Path::new(cert_path),
-            std::path::Path::new(key_path),
-        )?
+    // --- mTLS client certificate verification (optional) ---
+    let client_verifier = match &config.mtls {
+        Some(mtls) => {
+            tracing::info!(
+                ca = %mtls.client_ca_path,
+                required = mtls.required,
+                identities = mtls.identities.len(),
+                "mTLS client certificate authentication enabled"
+            );
+            Some(mtls::build_client_verifier(mtls)?)
+        }
+        None => None,
+    };
+
+    // --- TLS: ACME automatic certificates, or static/self-signed certificates ---
+    let (server_config, tcp_tls_config) = if let Some(acme_config) = config.acme.clone() {
+        if config.tls_cert_path.is_some() {
+            return Err("both ACME and TRUTHTLAYER_TLS_CERT are configured; choose one".into());
+        }
+        if config.tls_tcp_listen_addr.is_none() {
+            tracing::warn!(
+                "ACME TLS-ALPN-01 validation needs the TLS TCP listener on :443; set TRUTHTLAYER_TLS_TCP_LISTEN"
+            );
+        }
+        tracing::info!(domains = ?acme_config.domains, directory = %acme_config.directory_url, "ACME enabled");
+        let resolver = acme::start(acme_config, &config.config_root)?;
+        (
+            tls::build_quinn_server_config_with_resolver(
+                resolver.clone(),
+                client_verifier.clone(),
+            )?,
+            config.tls_tcp_listen_addr.as_ref().map(|_| {
+                tls::build_tcp_tls_config_with_resolver(resolver, client_verifier.clone())
+            }),
+        )
     } else {
-        tracing::warn!("no TLS cert configured — generating self-signed dev certificate (NOT for production)");
-        tracing::warn!("set TRUTHTLAYER_TLS_CERT and TRUTHTLAYER_TLS_KEY for production");
-        tls::generate_dev_cert()?
+        static_tls_configs(&config, client_verifier.clone())?
     };
 
-    // --- QUIC server config ---
-    let server_config = tls::build_quinn_server_config(certs, key)?;
+    // --- QUIC transport tuning (keep-alive, idle timeout, UDP payload, congestion control) ---
+    let mut server_config = server_config;
+    server_config.transport_config(Arc::new(config.quic_transport.transport_config()?));
+    let endpoint_config = config.quic_transport.endpoint_config()?;
 
     // --- Start HTTP/3 server ---
     let addr: std::net::SocketAddr = config
@@ -186,14 +361,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .parse()
         .map_err(|e| format!("invalid listen address '{}': {}", config.listen_addr, e))?;
 
+    // --- Optional TLS TCP fallback listener (HTTP/1.1 + HTTP/2) ---
+    // Production-supported transport for clients without QUIC; independent of TRUTHTLAYER_DEV_TCP.
+    if let (Some(tls_addr), Some(tcp_tls_config)) = (&config.tls_tcp_listen_addr, tcp_tls_config) {
+        let tls_addr: std::net::SocketAddr = tls_addr
+            .parse()
+            .map_err(|e| format!("invalid TLS TCP listen address '{}': {}", tls_addr, e))?;
+        if dev_tcp && tls_addr == addr {
+            return Err(format!(
+                "TLS TCP listener and dev TCP listener both bind {}; use a different TRUTHTLAYER_TLS_TCP_LISTEN",
+                addr
+            )
+            .into());
+        }
+        let tls_app = app.clone();
+        tokio::spawn(async move {
+            if let Err(e) = tls_tcp_server::serve_tls_tcp(tcp_tls_config, tls_addr, tls_app).await {
+                tracing::error!(error = %e, "TLS TCP listener error");
+            }
+        });
+    }
+
     // --- Optional dev-mode TCP listener ---
-    // Node.js (v24) does not support HTTP/3/QUIC clients yet. To allow `fetch()`-based
-    // tools (integration tests, smoke scripts, VS Code extension host) to reach the
-    // server during development, set TRUTHTLAYER_DEV_TCP=true.
     // TCP and UDP ports are independent, so both can bind to the same port.
-    let dev_tcp = std::env::var("TRUTHTLAYER_DEV_TCP")
-        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
-
     if dev_tcp {
         let tcp_app = app.clone();
         let tcp_addr = addr;
@@ -216,7 +406,55 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         });
     }
 
-    h3_server::serve_h3(server_config, addr, app).await?;
+    h3_server::serve_h3(
+        server_config,
+        endpoint_config,
+        addr,
+        app,
+        body_limits,
+        runtime.quic_limits.clone(),
+        client_verifier.is_some(),
+        event_bus,
+    )
+    .await?;
 
     Ok(())
 }
+
+/// QUIC server config plus the TLS TCP config (when that listener is enabled).
+type TlsConfigs = (quinn::ServerConfig, Option<Arc<rustls::ServerConfig>>);
+
+/// QUIC and TLS TCP configs from PEM files (production) or a generated dev certificate.
+fn static_tls_configs(
+    config: &ServerConfig,
+    client_verifier: Option<Arc<dyn ClientCertVerifier>>,
+) -> Result<TlsConfigs, Box<dyn std::error::Error + Send + Sync>> {
+    let (certs, key) = if let (Some(cert_path), Some(key_path)) =
+        (&config.tls_cert_path, &config.tls_key_path)
+    {
+        tracing::info!(cert = %cert_path, key = %key_path, "loading TLS certificates from disk");
+        tls::load_certs_from_pem(
+            std::path::Path::new(cert_path),
+            std::path::Path::new(key_path),
+        )?
+    } else {
+        tracing::warn!("no TLS cert configured — generating self-signed dev certificate (NOT for production)");
+        tracing::warn!("set TRUTHTLAYER_TLS_CERT and TRUTHTLAYER_TLS_KEY for production");
+        tls::generate_dev_cert()?
+    };
+
+    // TLS TCP fallback uses the same certificates as QUIC
+    let tcp_tls_config = if config.tls_tcp_listen_addr.is_some() {
+        Some(tls::build_tcp_tls_config(
+            certs.clone(),
+            key.clone_key(),
+            client_verifier.clone(),
+        )?)
+    } else {
+        None
+    };
+    Ok((
+        tls::build_quinn_server_config(certs, key, client_verifier)?,
+        tcp_tls_config,
+    ))
+}
@@ -0,0 +1,318 @@
+//! Maintenance job handlers run by the task scheduler (`crate::scheduler`): stale-proposal
+//! check, content hash verification, overdue task check, risk review reminder and store
+//! compaction. Findings go in the job result (shown by `/admin/jobs` and `/admin/tasks`)
+//! and the server log. The checks change nothing; overdue tasks, risks needing review and
+//! compaction are also audited.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::jobs::JobHandler;
+use crate::store::{CompactOptions, ContextStore, WriteBatch};
+use crate::types::{
+    AuditAction, AuditDetails, AuditEvent, AuditOutcome, ContextNode, JobRecord, NodeQuery,
+    NodeType, RiskSeverity,
+};
+
+/// Job kind that lists open proposals with no activity for `staleAfterDays` (payload,
+/// default 14) or whose base versions are outdated.
+pub const STALE_PROPOSAL_CHECK_JOB: &str = "stale_proposal_check";
+
+/// Job kind that recomputes the content hash of every accepted node and reports mismatches.
+pub const HASH_VERIFICATION_JOB: &str = "hash_verification";
+
+/// Job kind that lists unfinished tasks past their `dueDate` (`ContextNode::is_overdue`)
+/// and audits them as `tasks_overdue`.
+pub const OVERDUE_TASK_CHECK_JOB: &str = "overdue_task_check";
+
+/// Job kind that lists high and critical risks with no `mitigation`, or unchanged for
+/// `reviewAfterDays` (payload, default 30), and audits them as `risks_need_review`.
+pub const RISK_REVIEW_REMINDER_JOB: &str = "risk_review_reminder";
+
+/// Job kind that compacts the store; the payload is a `CompactOptions` object (`{}` for
+/// the defaults).
+pub const STORE_COMPACTION_JOB: &str = "store_compaction";
+
+const DEFAULT_STALE_AFTER_DAYS: i64 = 14;
+
+pub struct StaleProposalCheckHandler;
+
+#[async_trait]
+impl JobHandler for StaleProposalCheckHandler {
+    fn kind(&self) -> &'static str {
+        STALE_PROPOSAL_CHECK_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let days = job
+            .payload
+            .get("staleAfterDays")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_STALE_AFTER_DAYS);
+        let cutoff = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+
+        let open = store
+            .get_open_proposals()
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut inactive = Vec::new();
+        let mut outdated = Vec::new();
+        for proposal in &open {
+            if proposal.metadata.modified_at < cutoff {
+                inactive.push(proposal.id.clone());
+            }
+            if store
+                .is_proposal_stale(&proposal.id)
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                outdated.push(proposal.id.clone());
+            }
+        }
+        if !inactive.is_empty() || !outdated.is_empty() {
+            tracing::info!(
+                inactive = inactive.len(),
+                outdated = outdated.len(),
+                "stale proposals found"
+            );
+        }
+        Ok(Some(serde_json::json!({
+            "open": open.len(),
+            "staleAfterDays": days,
+            "inactive": inactive,
+            "outdated": outdated,
+        })))
+    }
+}
+
+/// Page size when scanning nodes of one type.
+const NODE_PAGE_SIZE: u32 = 500;
+
+/// Every node of `node_type` (archived ones left out).
+async fn nodes_of_type(
+    store: &Arc<dyn ContextStore>,
+    node_type: NodeType,
+) -> Result<Vec<ContextNode>, String> {
+    let mut nodes = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = store
+            .query_nodes(NodeQuery {
+                r#type: Some(vec![node_type.clone()]),
+                limit: Some(NODE_PAGE_SIZE),
+                offset: Some(offset),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        nodes.extend(page.nodes);
+        if !page.has_more {
+            return Ok(nodes);
+        }
+        offset += NODE_PAGE_SIZE;
+    }
+}
+
+pub struct OverdueTaskCheckHandler;
+
+#[async_trait]
+impl JobHandler for OverdueTaskCheckHandler {
+    fn kind(&self) -> &'static str {
+        OVERDUE_TASK_CHECK_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        _job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let now = chrono::Utc::now();
+        let tasks = nodes_of_type(&store, NodeType::Task).await?;
+        let overdue: Vec<serde_json::Value> = tasks
+            .iter()
+            .filter(|n| n.is_overdue(now))
+            .map(|node| {
+                serde_json::json!({
+                    "node": node.id.key(),
+                    "assignee": node.assignee,
+                    "dueDate": node.due_date,
+                    "state": node.state,
+                })
+            })
+            .collect();
+        let found = overdue.len();
+        let details = AuditDetails::TasksOverdue {
+            checked: tasks.len(),
+            overdue,
+        };
+        if found > 0 {
+            tracing::info!(overdue = found, "overdue tasks found");
+            let event = AuditEvent::new(
+                "system",
+                "system",
+                AuditAction::TasksOverdue,
+                "tasks",
+                AuditOutcome::Success,
+            )
+            .with_details(details.clone());
+            store
+                .execute(WriteBatch::new().audit(event))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(serde_json::to_value(details).ok())
+    }
+}
+
+const DEFAULT_RISK_REVIEW_AFTER_DAYS: i64 = 30;
+
+pub struct RiskReviewReminderHandler;
+
+#[async_trait]
+impl JobHandler for RiskReviewReminderHandler {
+    fn kind(&self) -> &'static str {
+        RISK_REVIEW_REMINDER_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let days = job
+            .payload
+            .get("reviewAfterDays")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_RISK_REVIEW_AFTER_DAYS);
+        let cutoff = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+
+        let risks = nodes_of_type(&store, NodeType::Risk).await?;
+        let mut reminders = Vec::new();
+        for node in &risks {
+            if !matches!(
+                node.severity,
+                Some(RiskSeverity::High | RiskSeverity::Critical)
+            ) {
+                continue;
+            }
+            let unmitigated = node
+                .mitigation
+                .as_deref()
+                .is_none_or(|m| m.trim().is_empty());
+            let unreviewed = node.metadata.modified_at < cutoff;
+            if unmitigated || unreviewed {
+                reminders.push(serde_json::json!({
+                    "node": node.id.key(),
+                    "severity": node.severity,
+                    "score": node.risk_score(),
+                    "unmitigated": unmitigated,
+                    "lastReviewedAt": node.metadata.modified_at,
+                }));
+            }
+        }
+        let found = reminders.len();
+        let details = AuditDetails::RisksNeedReview {
+            checked: risks.len(),
+            review_after_days: days,
+            risks: reminders,
+        };
+        if found > 0 {
+            tracing::info!(risks = found, "risks need review");
+            let event = AuditEvent::new(
+                "system",
+                "system",
+                AuditAction::RisksNeedReview,
+                "risks",
+                AuditOutcome::Success,
+            )
+            .with_details(details.clone());
+            store
+                .execute(WriteBatch::new().audit(event))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(serde_json::to_value(details).ok())
+    }
+}
+
+pub struct HashVerificationHandler;
+
+#[async_trait]
+impl JobHandler for HashVerificationHandler {
+    fn kind(&self) -> &'static str {
+        HASH_VERIFICATION_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        _job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let nodes = store
+            .get_accepted_nodes()
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut checked = 0usize;
+        let mut mismatched = Vec::new();
+        for node in &nodes {
+            let Some(expected) = &node.metadata.content_hash else {
+                continue;
+            };
+            checked += 1;
+            if *expected != crate::sensitivity::content_hash(&node.content) {
+                mismatched.push(node.id.id.clone());
+            }
+        }
+        if !mismatched.is_empty() {
+            tracing::warn!(nodes = ?mismatched, "content hash mismatch");
+        }
+        Ok(Some(serde_json::json!({
+            "checked": checked,
+            "mismatched": mismatched,
+        })))
+    }
+}
+
+pub struct StoreCompactionHandler;
+
+#[async_trait]
+impl JobHandler for StoreCompactionHandler {
+    fn kind(&self) -> &'static str {
+        STORE_COMPACTION_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let options: CompactOptions = serde_json::from_value(job.payload.clone())
+            .map_err(|e| format!("invalid compaction options: {}", e))?;
+        let report = store.compact(&options).await.map_err(|e| e.to_string())?;
+        let details = serde_json::to_value(&report).unwrap_or_default();
+        tracing::info!(
+            pruned_proposals = report.pruned_proposals.len(),
+            orphaned_files = report.orphaned_files.len(),
+            reclaimed_bytes = report.reclaimed_bytes,
+            "store compacted"
+        );
+        let event = AuditEvent::new(
+            "system",
+            "system",
+            AuditAction::StoreCompacted,
+            "store",
+            AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::Compacted(report));
+        store
+            .execute(WriteBatch::new().audit(event))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Some(details))
+    }
+}
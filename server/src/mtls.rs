@@ -0,0 +1,192 @@
+//! Mutual TLS: client certificate verification and certificate → ActorContext mapping.
+//!
+//! Optional alternative to JWTs for service-to-service and agent callers. When `mtls`
+//! (config.json) or `TRUTHTLAYER_MTLS_CLIENT_CA` is set, the QUIC and TLS TCP listeners
+//! verify client certificates against the configured CA. The verified leaf's identities
+//! (SAN DNS/URI/email, then subject CN) are attached to each request as
+//! [`ClientCertIdentity`]; `AuthLayer` maps them to an actor via `identities` when the
+//! request carries no Bearer token.
+
+use std::sync::Arc;
+
+use rustls::pki_types::CertificateDer;
+use rustls::server::danger::ClientCertVerifier;
+use rustls::server::WebPkiClientVerifier;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::{ActorContext, ActorType, Role};
+
+/// mTLS configuration (`mtls` in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtlsConfig {
+    /// PEM bundle of CA certificates that issue client certificates.
+    pub client_ca_path: String,
+    /// Reject handshakes without a client certificate. When false, certificates are
+    /// optional and callers without one fall back to JWT auth.
+    #[serde(default = "default_required")]
+    pub required: bool,
+    /// Certificate identity → actor mappings; first match wins.
+    #[serde(default)]
+    pub identities: Vec<ClientIdentityMapping>,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// Maps one certificate identity (SAN or CN) to an actor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientIdentityMapping {
+    /// SAN (DNS name, URI or email) or subject CN to match exactly.
+    pub subject: String,
+    /// Actor ID; defaults to `subject`.
+    #[serde(default)]
+    pub actor_id: Option<String>,
+    #[serde(default = "default_actor_type")]
+    pub actor_type: ActorType,
+    #[serde(default)]
+    pub roles: Vec<Role>,
+}
+
+fn default_actor_type() -> ActorType {
+    ActorType::System
+}
+
+/// Identities of a verified client certificate, inserted as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertIdentity {
+    /// SAN entries (DNS, URI, email) followed by the subject CN, in certificate order.
+    pub names: Vec<String>,
+}
+
+impl ClientCertIdentity {
+    /// Extract identities from the leaf certificate. None if it cannot be parsed.
+    pub fn from_leaf(cert: &CertificateDer<'_>) -> Option<Self> {
+        use x509_parser::extensions::GeneralName;
+
+        let (_, parsed) = x509_parser::parse_x509_certificate(cert.as_ref()).ok()?;
+        let mut names = Vec::new();
+        if let Ok(Some(san)) = parsed.subject_alternative_name() {
+            for name in &san.value.general_names {
+                match name {
+                    GeneralName::DNSName(s) | GeneralName::URI(s) | GeneralName::RFC822Name(s) => {
+                        names.push(s.to_string())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(cn) = parsed
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+        {
+            names.push(cn.to_string());
+        }
+        Some(Self { names })
+    }
+
+    /// Identity of a peer certificate chain (leaf first), as reported by the TLS session.
+    pub fn from_chain(chain: &[CertificateDer<'_>]) -> Option<Self> {
+        chain.first().and_then(Self::from_leaf)
+    }
+
+    /// Actor for the first mapping whose subject matches one of this certificate's names.
+    pub fn resolve(&self, mappings: &[ClientIdentityMapping]) -> Option<ActorContext> {
+        let mapping = mappings
+            .iter()
+            .find(|m| self.names.iter().any(|n| n == &m.subject))?;
+        let mut roles = mapping.roles.clone();
+        if roles.is_empty() {
+            roles.push(Role::Reader);
+        }
+        Some(ActorContext {
+            actor_id: mapping
+                .actor_id
+                .clone()
+                .unwrap_or_else(|| mapping.subject.clone()),
+            actor_type: mapping.actor_type,
+            roles,
+        })
+    }
+}
+
+/// Build the client certificate verifier from the configured CA bundle.
+pub fn build_client_verifier(
+    config: &MtlsConfig,
+) -> Result<Arc<dyn ClientCertVerifier>, Box<dyn std::error::Error + Send + Sync>> {
+    let pem = std::fs::read(&config.client_ca_path).map_err(|e| {
+        format!(
+            "failed to read mTLS client CA {}: {}",
+            config.client_ca_path, e
+        )
+    })?;
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in rustls_pemfile::certs(&mut &pem[..]) {
+        let cert = cert.map_err(|e| format!("invalid PEM client CA: {}", e))?;
+        roots
+            .add(cert)
+            .map_err(|e| format!("invalid client CA certificate: {}", e))?;
+    }
+    if roots.is_empty() {
+        return Err("no certificates found in mTLS client CA file".into());
+    }
+    let builder = WebPkiClientVerifier::builder(Arc::new(roots));
+    let builder = if config.required {
+        builder
+    } else {
+        builder.allow_unauthenticated()
+    };
+    Ok(builder
+        .build()
+        .map_err(|e| format!("mTLS verifier error: {}", e))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cert_for(names: &[&str]) -> CertificateDer<'static> {
+        let names: Vec<String> = names.iter().map(|s| s.to_string()).collect();
+        let certified = rcgen::generate_simple_self_signed(names).unwrap();
+        certified.cert.der().clone()
+    }
+
+    fn mapping(subject: &str, roles: Vec<Role>) -> ClientIdentityMapping {
+        ClientIdentityMapping {
+            subject: subject.to_string(),
+            actor_id: None,
+            actor_type: ActorType::Agent,
+            roles,
+        }
+    }
+
+    #[test]
+    fn extracts_san_names() {
+        let identity = ClientCertIdentity::from_leaf(&cert_for(&["agent-1.svc.local"])).unwrap();
+        assert!(identity.names.contains(&"agent-1.svc.local".to_string()));
+    }
+
+    #[test]
+    fn resolve_maps_first_matching_identity() {
+        let identity = ClientCertIdentity::from_leaf(&cert_for(&["indexer.svc.local"])).unwrap();
+        let mappings = vec![
+            mapping("other.svc.local", vec![Role::Admin]),
+            mapping("indexer.svc.local", vec![Role::Contributor]),
+        ];
+        let actor = identity.resolve(&mappings).unwrap();
+        assert_eq!(actor.actor_id, "indexer.svc.local");
+        assert_eq!(actor.actor_type, ActorType::Agent);
+        assert!(actor.has_role(&Role::Contributor));
+        assert!(!actor.has_role(&Role::Admin));
+    }
+
+    #[test]
+    fn resolve_unmapped_identity_is_none() {
+        let identity = ClientCertIdentity::from_leaf(&cert_for(&["stranger.example"])).unwrap();
+        assert!(identity
+            .resolve(&[mapping("indexer.svc.local", vec![])])
+            .is_none());
+    }
+}
@@ -0,0 +1,43 @@
+//! Outbound HTTPS calls to third-party APIs (forge comments, Slack messages), made from
+//! background jobs so a slow or failing service only delays a retry.
+
+use serde_json::Value;
+
+/// POST `body` as JSON to `url` with extra `headers`; returns the status and the response
+/// body. Native roots, HTTP/1.1; plain `http://` is allowed for self-hosted endpoints.
+pub async fn post_json(
+    url: &str,
+    headers: &[(&str, String)],
+    body: &Value,
+) -> Result<(http::StatusCode, bytes::Bytes), String> {
+    let mut request = http::Request::post(url)
+        .header("content-type", "application/json")
+        .header("accept", "application/json")
+        .header("user-agent", "truthlayer-server");
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    let request = request
+        .body(http_body_util::Full::new(bytes::Bytes::from(
+            body.to_string(),
+        )))
+        .map_err(|e| e.to_string())?;
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_provider_and_native_roots(rustls::crypto::ring::default_provider())
+        .map_err(|e| format!("TLS roots: {}", e))?
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+        .build(connector);
+    let response = client
+        .request(request)
+        .await
+        .map_err(|e| format!("POST {}: {}", url, e))?;
+    let status = response.status();
+    let bytes = http_body_util::BodyExt::collect(response.into_body())
+        .await
+        .map_err(|e| format!("POST {}: {}", url, e))?
+        .to_bytes();
+    Ok((status, bytes))
+}
@@ -0,0 +1,247 @@
+//! Outbox dispatcher: delivers what `ContextStore::execute` stored in the outbox (see
+//! `store::outbox`) with its write.
+//!
+//! - **Server events** are published to the [`EventBus`] (SSE, WebSocket, WebTransport,
+//!   gRPC watch) and cleared from the entry.
+//! - **Audit events** are POSTed as `{ "events": [...] }` to the `audit_sink` in
+//!   config.json, when one is configured. A failed delivery is retried after
+//!   `2^attempts` seconds (capped at [`MAX_BACKOFF`]), keeping the entry and its last
+//!   error; without a sink there is nothing to forward.
+//!
+//! An entry is removed once both are done, so a restart with the file backend resumes
+//! where delivery stopped. Handlers [`wake`](OutboxDispatcher::wake) the dispatcher after
+//! a write; it also polls every [`POLL_INTERVAL`] for retries.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, Notify};
+
+use crate::events::EventBus;
+use crate::store::context_store::StoreError;
+use crate::store::{ContextStore, OutboxEntry};
+
+/// How often pending entries are retried without a wake-up.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Upper bound on the delay between audit sink attempts.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Entries handled per pass.
+const BATCH_SIZE: usize = 100;
+
+/// Where audit events are forwarded (`audit_sink` in config.json); e.g. a SIEM's HTTP
+/// collector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditSinkConfig {
+    /// Receives `POST { "events": [AuditEvent...] }`; any 2xx is a delivery.
+    pub url: String,
+    /// Environment variable holding a bearer token, if the sink wants one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+impl AuditSinkConfig {
+    /// Problems in an `audit_sink` config.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+            issues.push(format!(
+                "audit_sink.url: '{}' is not an http(s) URL",
+                self.url
+            ));
+        }
+        if self
+            .token_env
+            .as_deref()
+            .is_some_and(|v| v.trim().is_empty())
+        {
+            issues.push("audit_sink.token_env: must not be empty".to_string());
+        }
+        issues
+    }
+
+    async fn deliver(&self, entry: &OutboxEntry) -> Result<(), String> {
+        let mut headers = Vec::new();
+        if let Some(var) = &self.token_env {
+            let token = std::env::var(var).map_err(|_| format!("{} is not set", var))?;
+            headers.push(("authorization", format!("Bearer {}", token)));
+        }
+        let body = serde_json::json!({ "events": entry.audit });
+        let (status, _) = crate::outbound::post_json(&self.url, &headers, &body).await?;
+        if !status.is_success() {
+            return Err(format!("POST {}: {}", self.url, status));
+        }
+        Ok(())
+    }
+}
+
+/// Delay before the attempt after `attempts` failed ones.
+fn backoff(attempts: u32) -> Duration {
+    Duration::from_secs(1u64 << attempts.min(16)).min(MAX_BACKOFF)
+}
+
+/// Delivers outbox entries; clones share the wake-up signal.
+#[derive(Clone, Default)]
+pub struct OutboxDispatcher {
+    wake: Arc<Notify>,
+    /// One pass at a time, so an entry is never delivered twice.
+    pass: Arc<Mutex<()>>,
+}
+
+impl OutboxDispatcher {
+    /// Deliver new entries now rather than at the next poll.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// One delivery pass over the due entries; returns how many were finished.
+    pub async fn run_once(
+        &self,
+        store: &Arc<dyn ContextStore>,
+        event_bus: &EventBus,
+        sink: Option<&AuditSinkConfig>,
+    ) -> Result<usize, StoreError> {
+        let _pass = self.pass.lock().await;
+        let now = chrono::Utc::now();
+        let now_str = now.to_rfc3339();
+        let mut finished = 0;
+        for mut entry in store.list_outbox(BATCH_SIZE).await? {
+            let mut changed = !entry.events.is_empty();
+            for event in entry.events.drain(..) {
+                event_bus.publish(event);
+            }
+            if !entry.audit.is_empty() {
+                match sink {
+                    None => entry.audit.clear(),
+                    Some(sink) if entry.is_due(&now_str) => match sink.deliver(&entry).await {
+                        Ok(()) => entry.audit.clear(),
+                        Err(e) => {
+                            tracing::warn!(entry = %entry.id, error = %e, "audit sink delivery failed");
+                            let retry = chrono::Duration::from_std(backoff(entry.attempts))
+                                .unwrap_or_else(|_| chrono::Duration::seconds(1));
+                            entry.attempts += 1;
+                            entry.next_attempt_at = Some((now + retry).to_rfc3339());
+                            entry.last_error = Some(e);
+                            changed = true;
+                        }
+                    },
+                    Some(_) => {}
+                }
+            }
+            if entry.is_done() {
+                store.remove_outbox_entry(&entry.id).await?;
+                finished += 1;
+            } else if changed {
+                store.save_outbox_entry(entry).await?;
+            }
+        }
+        Ok(finished)
+    }
+
+    /// Deliver on every wake-up and [`POLL_INTERVAL`] until the runtime stops.
+    pub fn start(
+        &self,
+        store: Arc<dyn ContextStore>,
+        event_bus: EventBus,
+        sink: Option<AuditSinkConfig>,
+    ) -> tokio::task::JoinHandle<()> {
+        let dispatcher = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = dispatcher.run_once(&store, &event_bus, sink.as_ref()).await {
+                    tracing::warn!(error = %e, "outbox delivery failed");
+                }
+                tokio::select! {
+                    _ = dispatcher.wake.notified() => {}
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventKind, ServerEvent};
+    use crate::store::WriteBatch;
+    use crate::types::{AuditAction, AuditEvent, AuditOutcome, Proposal, ProposalStatus};
+
+    fn proposal(id: &str) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "status": "open", "operations": []
+        }))
+        .unwrap()
+    }
+
+    fn batch(id: &str) -> WriteBatch {
+        WriteBatch::new()
+            .create_proposal(proposal(id))
+            .audit(AuditEvent::new(
+                "alice",
+                "human",
+                AuditAction::ProposalCreated,
+                id,
+                AuditOutcome::Success,
+            ))
+            .publish(ServerEvent::new(
+                EventKind::ProposalUpdated {
+                    status: ProposalStatus::Open,
+                },
+                id,
+                "alice",
+            ))
+    }
+
+    #[tokio::test]
+    async fn events_survive_a_restart_and_failed_audit_is_retried() {
+        let dir = std::env::temp_dir().join(format!("tl-outbox-{}", uuid::Uuid::new_v4()));
+        let file = crate::store::FileStore::new(&dir).unwrap();
+        file.execute(batch("p-1")).await.unwrap();
+        drop(file);
+
+        // Nothing was published before the restart; the entry is still owed.
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::FileStore::new(&dir).unwrap());
+        assert_eq!(store.list_outbox(10).await.unwrap().len(), 1);
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        let dispatcher = OutboxDispatcher::default();
+        let unreachable = AuditSinkConfig {
+            url: "http://127.0.0.1:9/audit".to_string(),
+            token_env: None,
+        };
+        let finished = dispatcher
+            .run_once(&store, &bus, Some(&unreachable))
+            .await
+            .unwrap();
+        assert_eq!(finished, 0);
+        assert_eq!(rx.try_recv().unwrap().resource_id, "p-1");
+
+        // The events are not published twice; the audit forward waits for its retry.
+        let pending = store.list_outbox(10).await.unwrap();
+        assert!(pending[0].events.is_empty());
+        assert_eq!(pending[0].attempts, 1);
+        assert!(pending[0].last_error.is_some());
+        dispatcher
+            .run_once(&store, &bus, Some(&unreachable))
+            .await
+            .unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(store.list_outbox(10).await.unwrap()[0].attempts, 1);
+
+        // Without a sink the entry is done.
+        assert_eq!(dispatcher.run_once(&store, &bus, None).await.unwrap(), 1);
+        assert!(store.list_outbox(10).await.unwrap().is_empty());
+        drop(store);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(0), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(40), MAX_BACKOFF);
+    }
+}
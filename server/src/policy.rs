@@ -1,8 +1,15 @@
 //! Policy engine: configurable rules that validate and gate proposals.
-//! Policies are evaluated at create, review, and apply time.
+//! Policies are evaluated at create, update (PATCH), review, and apply time.
+//!
+//! Each rule has an `enforcement`: `enforce` (the default) refuses what it flags, `warn`
+//! and `shadow` let it through and have the would-be violations audited (`warn` also
+//! logs them), so a stricter rule can be trialled against real traffic first. Violations
+//! carry the enforcement of the rule that raised them.
 
 use serde::{Deserialize, Serialize};
 
+use crate::auth::Role;
+use crate::complexity;
 use crate::types::proposal::{Proposal, ProposalStatus, Review, ReviewAction};
 
 /// A single policy violation returned when a rule is not satisfied.
@@ -11,18 +18,85 @@ use crate::types::proposal::{Proposal, ProposalStatus, Review, ReviewAction};
 pub struct PolicyViolation {
     pub rule: String,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Enforcement::is_enforce")]
+    pub enforcement: Enforcement,
+}
+
+impl PolicyViolation {
+    /// An enforced violation of `rule`.
+    pub fn new(rule: &str, message: String) -> Self {
+        Self {
+            rule: rule.to_string(),
+            message,
+            enforcement: Enforcement::Enforce,
+        }
+    }
+}
+
+/// What a rule's violations do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Enforcement {
+    /// Refuse the write.
+    #[default]
+    Enforce,
+    /// Let it through; audit and log the violations.
+    Warn,
+    /// Let it through; only audit the violations.
+    Shadow,
+}
+
+impl Enforcement {
+    pub fn is_enforce(&self) -> bool {
+        *self == Enforcement::Enforce
+    }
+}
+
+/// A rule as configured: the rule and its enforcement (`{ "type": ..., "enforcement":
+/// "shadow", ... }`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfiguredRule {
+    #[serde(flatten)]
+    pub rule: PolicyRule,
+    #[serde(default, skip_serializing_if = "Enforcement::is_enforce")]
+    pub enforcement: Enforcement,
+}
+
+impl From<PolicyRule> for ConfiguredRule {
+    fn from(rule: PolicyRule) -> Self {
+        Self {
+            rule,
+            enforcement: Enforcement::Enforce,
+        }
+    }
+}
+
+/// Approvals a `min_approvals` rule needs from members of one SCIM group (matched by
+/// display name against the groups recorded on each review).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupQuorum {
+    pub group: String,
+    pub min: u32,
 }
 
 /// Policy rules loaded from configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum PolicyRule {
-    /// Require a minimum number of approvals before a proposal can be accepted.
+    /// Require a minimum number of approvals before a proposal can be accepted, and
+    /// optionally a quorum from each of some directory groups.
     MinApprovals {
         /// Node types this rule applies to (empty = all).
         #[serde(default)]
         node_types: Vec<String>,
+        #[serde(default)]
         min: u32,
+        /// Only proposals whose complexity score (`crate::complexity`) is at least this.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_score: Option<u32>,
+        /// All must be met, e.g. 2 of `security-team` and 1 of `architects`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        quorums: Vec<GroupQuorum>,
     },
     /// Require at least one reviewer with a specific role.
     RequiredReviewerRole {
@@ -38,6 +112,13 @@ pub enum PolicyRule {
         allowed_hour_start: u8,
         allowed_hour_end: u8,
     },
+    /// Refuse applies from `start` until `end` (RFC 3339), e.g. over a release freeze.
+    ChangeFreeze {
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
     /// Block agents from specific actions.
     AgentRestriction { blocked_actions: Vec<String> },
     /// Limit proposal size for agents.
@@ -55,6 +136,12 @@ pub enum PolicyRule {
         #[serde(default)]
         destinations: Vec<String>,
     },
+    /// Hold agent proposals as `quarantined` until a human triages them into review.
+    AgentQuarantine {
+        /// Workspaces this rule applies to (empty = all).
+        #[serde(default)]
+        workspaces: Vec<String>,
+    },
 }
 
 fn default_max_content_length() -> u32 {
@@ -65,20 +152,40 @@ fn default_max_content_length() -> u32 {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PolicyConfig {
     #[serde(default)]
-    pub rules: Vec<PolicyRule>,
+    pub rules: Vec<ConfiguredRule>,
 }
 
 impl PolicyConfig {
     /// Load from a JSON file path, or return empty config if file doesn't exist.
     pub fn load_from_file(path: &std::path::Path) -> Self {
-        if path.exists() {
-            if let Ok(s) = std::fs::read_to_string(path) {
-                if let Ok(config) = serde_json::from_str::<PolicyConfig>(&s) {
-                    return config;
-                }
-            }
+        Self::try_load_from_file(path).unwrap_or_default()
+    }
+
+    /// Like [`load_from_file`](Self::load_from_file), but unreadable or malformed files are
+    /// errors (with serde's line/column) instead of an empty config. Missing file = empty.
+    pub fn try_load_from_file(path: &std::path::Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
         }
-        Self::default()
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str::<PolicyConfig>(&s)
+            .map_err(|e| format!("malformed {}: {}", path.display(), e))
+    }
+
+    /// The rules that are enforced.
+    pub fn enforced(&self) -> impl Iterator<Item = &PolicyRule> {
+        self.rules
+            .iter()
+            .filter(|r| r.enforcement.is_enforce())
+            .map(|r| &r.rule)
+    }
+}
+
+/// Give the violations raised from `from` on the enforcement of the rule raising them.
+fn tag(violations: &mut [PolicyViolation], from: usize, enforcement: Enforcement) {
+    for violation in violations.iter_mut().skip(from) {
+        violation.enforcement = enforcement;
     }
 }
 
@@ -91,21 +198,22 @@ pub fn evaluate_on_create(
 ) -> Vec<PolicyViolation> {
     let mut violations = Vec::new();
 
-    for rule in &policies.rules {
-        match rule {
+    for entry in &policies.rules {
+        let from = violations.len();
+        match &entry.rule {
             PolicyRule::AgentProposalLimit {
                 max_operations,
                 max_content_length,
             } if actor_type == "agent" => {
                 if proposal.operations.len() as u32 > *max_operations {
-                    violations.push(PolicyViolation {
-                        rule: "agent_proposal_limit".to_string(),
-                        message: format!(
+                    violations.push(PolicyViolation::new(
+                        "agent_proposal_limit",
+                        format!(
                             "agent proposals limited to {} operations, got {}",
                             max_operations,
                             proposal.operations.len()
                         ),
-                    });
+                    ));
                 }
                 let total_content: u32 = proposal
                     .operations
@@ -121,17 +229,32 @@ pub fn evaluate_on_create(
                     })
                     .sum();
                 if total_content > *max_content_length {
-                    violations.push(PolicyViolation {
-                        rule: "agent_proposal_limit".to_string(),
-                        message: format!(
+                    violations.push(PolicyViolation::new(
+                        "agent_proposal_limit",
+                        format!(
                             "agent proposal content limited to {} bytes, got {}",
                             max_content_length, total_content
                         ),
-                    });
+                    ));
                 }
             }
+            // An enforced rule quarantines the proposal instead (see `quarantines`).
+            PolicyRule::AgentQuarantine { workspaces }
+                if actor_type == "agent"
+                    && !entry.enforcement.is_enforce()
+                    && covers(workspaces, proposal) =>
+            {
+                violations.push(PolicyViolation::new(
+                    "agent_quarantine",
+                    format!(
+                        "agent proposals in workspace '{}' are quarantined for triage",
+                        proposal.workspace()
+                    ),
+                ));
+            }
             _ => {}
         }
+        tag(&mut violations, from, entry.enforcement);
     }
 
     // Check if agent is trying to modify restricted-sensitivity nodes
@@ -158,47 +281,212 @@ pub fn evaluate_on_review(
         return (Some(ProposalStatus::Rejected), violations);
     }
 
-    let accept_count = all_reviews
-        .iter()
-        .filter(|r| r.action == ReviewAction::Accept)
-        .count() as u32;
-
-    let mut min_approvals_needed: u32 = 1; // default: 1 approval needed
+    let accept_count = accept_count(all_reviews);
+    let min_approvals_needed = min_approvals(proposal, policies.enforced());
 
-    for rule in &policies.rules {
-        match rule {
-            PolicyRule::MinApprovals { node_types, min } => {
-                if node_types.is_empty() || proposal_touches_node_types(proposal, node_types) {
-                    min_approvals_needed = min_approvals_needed.max(*min);
-                }
-            }
+    for entry in &policies.rules {
+        let from = violations.len();
+        match &entry.rule {
             PolicyRule::RequiredReviewerRole {
                 node_types, role, ..
-            } => {
-                if node_types.is_empty() || proposal_touches_node_types(proposal, node_types) {
-                    let has_role_reviewer = all_reviews.iter().any(|r| {
-                        r.action == ReviewAction::Accept
-                            && r.reviewer_role.as_deref() == Some(role.as_str())
-                    });
-                    if !has_role_reviewer {
-                        violations.push(PolicyViolation {
-                            rule: "required_reviewer_role".to_string(),
-                            message: format!("requires reviewer with role '{}'", role),
-                        });
+            } if node_types.is_empty() || proposal_touches_node_types(proposal, node_types) => {
+                let has_role_reviewer = all_reviews
+                    .iter()
+                    .any(|r| r.action == ReviewAction::Accept && reviewer_has_role(r, role));
+                if !has_role_reviewer {
+                    violations.push(PolicyViolation::new(
+                        "required_reviewer_role",
+                        format!("requires reviewer with role '{}'", role),
+                    ));
+                }
+            }
+            PolicyRule::MinApprovals {
+                node_types,
+                min_score,
+                quorums,
+                ..
+            } if min_approvals_apply(proposal, node_types, *min_score) => {
+                for quorum in quorums {
+                    let approvers = group_approvers(all_reviews, &quorum.group);
+                    if approvers < quorum.min {
+                        violations.push(PolicyViolation::new(
+                            "min_approvals",
+                            format!(
+                                "requires {} approval(s) from group '{}', got {}",
+                                quorum.min, quorum.group, approvers
+                            ),
+                        ));
                     }
                 }
             }
             _ => {}
         }
+        tag(&mut violations, from, entry.enforcement);
     }
 
-    if accept_count >= min_approvals_needed && violations.is_empty() {
+    let missing = missing_approvers(proposal, all_reviews);
+    if !missing.is_empty() {
+        violations.push(PolicyViolation::new(
+            "required_approvers",
+            format!("awaiting approval from {}", missing.join(", ")),
+        ));
+    }
+
+    if accept_count >= min_approvals_needed
+        && violations.iter().all(|v| !v.enforcement.is_enforce())
+    {
+        // What the rules that are not enforced would have held it back for.
+        for entry in policies
+            .rules
+            .iter()
+            .filter(|r| !r.enforcement.is_enforce())
+        {
+            let needed = min_approvals(proposal, std::iter::once(&entry.rule));
+            if accept_count < needed {
+                let mut violation = PolicyViolation::new(
+                    "min_approvals",
+                    format!(
+                        "accepting requires {} approving review(s), got {}",
+                        needed, accept_count
+                    ),
+                );
+                violation.enforcement = entry.enforcement;
+                violations.push(violation);
+            }
+        }
         (Some(ProposalStatus::Accepted), violations)
     } else {
         (None, violations) // still pending more reviews
     }
 }
 
+/// Approving reviews among `reviews`.
+fn accept_count(reviews: &[Review]) -> u32 {
+    reviews
+        .iter()
+        .filter(|r| r.action == ReviewAction::Accept)
+        .count() as u32
+}
+
+/// The proposal's required approvers (`metadata.requiredApprovers`) with no approving
+/// review of their own, nor one a delegate submitted on their behalf.
+pub fn missing_approvers(proposal: &Proposal, reviews: &[Review]) -> Vec<String> {
+    let required = proposal.metadata.required_approvers.as_deref();
+    required
+        .unwrap_or_default()
+        .iter()
+        .filter(|approver| {
+            !reviews.iter().any(|r| {
+                r.action == ReviewAction::Accept
+                    && (&r.reviewer == *approver
+                        || r.on_behalf_of
+                            .as_deref()
+                            .unwrap_or_default()
+                            .contains(*approver))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Distinct reviewers in `group` among the approving reviews.
+fn group_approvers(reviews: &[Review], group: &str) -> u32 {
+    reviews
+        .iter()
+        .filter(|r| r.action == ReviewAction::Accept)
+        .filter(|r| r.reviewer_groups.iter().flatten().any(|g| g == group))
+        .map(|r| r.reviewer.as_str())
+        .collect::<std::collections::BTreeSet<_>>()
+        .len() as u32
+}
+
+/// Approvals the proposal needs under `rules`: the largest applicable `min_approvals`,
+/// at least 1.
+fn min_approvals<'a>(proposal: &Proposal, rules: impl Iterator<Item = &'a PolicyRule>) -> u32 {
+    rules
+        .filter_map(|rule| match rule {
+            PolicyRule::MinApprovals {
+                node_types,
+                min,
+                min_score,
+                ..
+            } if min_approvals_apply(proposal, node_types, *min_score) => Some(*min),
+            _ => None,
+        })
+        .fold(1, u32::max)
+}
+
+/// Whether a `min_approvals` rule with these filters covers `proposal`.
+fn min_approvals_apply(proposal: &Proposal, node_types: &[String], min_score: Option<u32>) -> bool {
+    (node_types.is_empty() || proposal_touches_node_types(proposal, node_types))
+        && min_score.is_none_or(|min| complexity::score_of(proposal) >= min)
+}
+
+/// Evaluate policies when a proposal is patched. `patched` is the proposal with the
+/// patch applied and `previous` its status before. The create-time rules are checked
+/// again for the patching actor, agents blocked from `update` cannot patch, a patch to
+/// `accepted` needs the reviews that would have accepted it (see [`evaluate_on_review`])
+/// and a patch to `rejected` as many reviews, of any kind, as accepting needs
+/// approvals. Returns violations (empty = pass).
+pub fn evaluate_on_update(
+    patched: &Proposal,
+    previous: ProposalStatus,
+    all_reviews: &[Review],
+    actor_type: &str,
+    policies: &PolicyConfig,
+) -> Vec<PolicyViolation> {
+    let mut violations = evaluate_on_create(patched, actor_type, policies);
+
+    let update_blocked = policies.rules.iter().find(|entry| {
+        matches!(&entry.rule, PolicyRule::AgentRestriction { blocked_actions }
+            if blocked_actions.iter().any(|a| a == "update"))
+    });
+    if let (true, Some(entry)) = (actor_type == "agent", update_blocked) {
+        let mut violation = PolicyViolation::new(
+            "agent_restriction",
+            "agents cannot update proposals".to_string(),
+        );
+        violation.enforcement = entry.enforcement;
+        violations.push(violation);
+    }
+
+    if patched.status == ProposalStatus::Accepted && previous != ProposalStatus::Accepted {
+        let (outcome, review_violations) = evaluate_on_review(patched, all_reviews, policies);
+        if outcome != Some(ProposalStatus::Accepted) {
+            let needed = min_approvals(patched, policies.enforced());
+            let accepted = accept_count(all_reviews);
+            if outcome == Some(ProposalStatus::Rejected) {
+                violations.push(PolicyViolation::new(
+                    "min_approvals",
+                    "cannot accept a proposal a review rejected".to_string(),
+                ));
+            } else if accepted < needed {
+                violations.push(PolicyViolation::new(
+                    "min_approvals",
+                    format!(
+                        "accepting requires {} approving review(s), got {}",
+                        needed, accepted
+                    ),
+                ));
+            }
+        }
+        violations.extend(review_violations);
+    }
+
+    if patched.status == ProposalStatus::Rejected && previous != ProposalStatus::Rejected {
+        let needed = min_approvals(patched, policies.enforced());
+        let reviewed = all_reviews.len() as u32;
+        if reviewed < needed {
+            violations.push(PolicyViolation::new(
+                "min_approvals",
+                format!("rejecting requires {} review(s), got {}", needed, reviewed),
+            ));
+        }
+    }
+
+    violations
+}
+
 /// Evaluate policies at apply time.
 /// Returns violations (empty = allow apply).
 pub fn evaluate_on_apply(
@@ -208,8 +496,9 @@ pub fn evaluate_on_apply(
 ) -> Vec<PolicyViolation> {
     let mut violations = Vec::new();
 
-    for rule in &policies.rules {
-        match rule {
+    for entry in &policies.rules {
+        let from = violations.len();
+        match &entry.rule {
             PolicyRule::ChangeWindow {
                 allowed_days,
                 allowed_hour_start,
@@ -219,34 +508,52 @@ pub fn evaluate_on_apply(
                 let weekday = now.format("%u").to_string().parse::<u8>().unwrap_or(1) - 1; // 0=Mon
                 let hour = now.format("%H").to_string().parse::<u8>().unwrap_or(0);
                 if !allowed_days.contains(&weekday) {
-                    violations.push(PolicyViolation {
-                        rule: "change_window".to_string(),
-                        message: format!(
+                    violations.push(PolicyViolation::new(
+                        "change_window",
+                        format!(
                             "apply not allowed on day {} (allowed: {:?})",
                             weekday, allowed_days
                         ),
-                    });
+                    ));
                 }
                 if hour < *allowed_hour_start || hour >= *allowed_hour_end {
-                    violations.push(PolicyViolation {
-                        rule: "change_window".to_string(),
-                        message: format!(
+                    violations.push(PolicyViolation::new(
+                        "change_window",
+                        format!(
                             "apply not allowed at hour {} (allowed: {}–{})",
                             hour, allowed_hour_start, allowed_hour_end
                         ),
-                    });
+                    ));
                 }
             }
-            PolicyRule::AgentRestriction { blocked_actions } if actor_type == "agent" => {
-                if blocked_actions.contains(&"apply".to_string()) {
-                    violations.push(PolicyViolation {
-                        rule: "agent_restriction".to_string(),
-                        message: "agents cannot apply proposals".to_string(),
-                    });
+            PolicyRule::ChangeFreeze { start, end, reason } => {
+                let now = chrono::Utc::now();
+                if *start <= now && now < *end {
+                    violations.push(PolicyViolation::new(
+                        "change_freeze",
+                        format!(
+                            "apply frozen from {} until {}{}",
+                            start.to_rfc3339(),
+                            end.to_rfc3339(),
+                            reason
+                                .as_deref()
+                                .map(|r| format!(": {}", r))
+                                .unwrap_or_default()
+                        ),
+                    ));
                 }
             }
+            PolicyRule::AgentRestriction { blocked_actions }
+                if actor_type == "agent" && blocked_actions.contains(&"apply".to_string()) =>
+            {
+                violations.push(PolicyViolation::new(
+                    "agent_restriction",
+                    "agents cannot apply proposals".to_string(),
+                ));
+            }
             _ => {}
         }
+        tag(&mut violations, from, entry.enforcement);
     }
 
     violations
@@ -255,10 +562,10 @@ pub fn evaluate_on_apply(
 /// Get the maximum sensitivity level an agent is allowed to read, based on EgressControl policies.
 /// Defaults to `Internal` if no EgressControl rule is configured.
 pub fn agent_max_sensitivity(policies: &PolicyConfig) -> crate::sensitivity::Sensitivity {
-    for rule in &policies.rules {
+    for entry in &policies.rules {
         if let PolicyRule::EgressControl {
             max_sensitivity, ..
-        } = rule
+        } = &entry.rule
         {
             return *max_sensitivity;
         }
@@ -267,6 +574,20 @@ pub fn agent_max_sensitivity(policies: &PolicyConfig) -> crate::sensitivity::Sen
     crate::sensitivity::Sensitivity::Internal
 }
 
+/// Whether a new proposal by `actor_type` starts quarantined: an agent's, in a workspace
+/// an `agent_quarantine` rule covers.
+pub fn quarantines(proposal: &Proposal, actor_type: &str, policies: &PolicyConfig) -> bool {
+    actor_type == "agent"
+        && policies.enforced().any(|rule| {
+            matches!(rule, PolicyRule::AgentQuarantine { workspaces } if covers(workspaces, proposal))
+        })
+}
+
+/// Whether `workspaces` (empty = all) include the proposal's.
+fn covers(workspaces: &[String], proposal: &Proposal) -> bool {
+    workspaces.is_empty() || workspaces.iter().any(|w| w == proposal.workspace())
+}
+
 /// Check if a proposal's operations touch any restricted-sensitivity nodes.
 /// Returns violations if an agent tries to modify restricted nodes.
 pub fn check_agent_restricted_node_modification(
@@ -280,24 +601,29 @@ pub fn check_agent_restricted_node_modification(
     }
     let max_sens = agent_max_sensitivity(policies);
     for op in &proposal.operations {
-        match op {
-            crate::types::proposal::Operation::Create { node, .. } => {
-                if let Some(ref sens) = node.metadata.sensitivity {
-                    if *sens > max_sens {
-                        violations.push(PolicyViolation {
-                            rule: "agent_restricted_modification".to_string(),
-                            message: format!(
-                                "agents cannot create nodes with sensitivity '{}' (max allowed: '{}')",
-                                sens.as_str(),
-                                max_sens.as_str()
-                            ),
-                        });
-                    }
+        if let crate::types::proposal::Operation::Create { node, .. } = op {
+            if let Some(ref sens) = node.metadata.sensitivity {
+                if *sens > max_sens {
+                    violations.push(PolicyViolation::new(
+                        "agent_restricted_modification",
+                        format!(
+                            "agents cannot create nodes with sensitivity '{}' (max allowed: '{}')",
+                            sens.as_str(),
+                            max_sens.as_str()
+                        ),
+                    ));
                 }
             }
-            _ => {}
         }
     }
+    // Under the enforcement of the `egress_control` rule that set the clearance.
+    let egress = policies
+        .rules
+        .iter()
+        .find(|r| matches!(r.rule, PolicyRule::EgressControl { .. }));
+    if let Some(entry) = egress {
+        tag(&mut violations, 0, entry.enforcement);
+    }
     violations
 }
 
@@ -314,6 +640,19 @@ fn proposal_touches_node_types(proposal: &Proposal, node_types: &[String]) -> bo
     })
 }
 
+/// Whether a review's server-derived `reviewer_role` satisfies `role`. Known roles follow
+/// the RBAC hierarchy (an admin satisfies "reviewer"); other names must match exactly.
+fn reviewer_has_role(review: &Review, role: &str) -> bool {
+    let Some(held) = review.reviewer_role.as_deref() else {
+        return false;
+    };
+    let parse = |s: &str| serde_json::from_value::<Role>(serde_json::Value::String(s.to_string()));
+    match (parse(held), parse(role)) {
+        (Ok(held), Ok(required)) => held.includes(&required),
+        _ => held == role,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,6 +673,12 @@ mod tests {
                 required_approvers: None,
                 approved_by: None,
                 base_versions: None,
+                force_status_transitions: None,
+                forge: None,
+                complexity: None,
+                authors: None,
+                superseded_by: None,
+                conflict_resolutions: None,
             },
             comments: None,
             relations: None,
@@ -341,6 +686,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn agent_quarantine_covers_agents_in_its_workspaces() {
+        let policies: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [{ "type": "agent_quarantine", "workspaces": ["default"] }]
+        }))
+        .unwrap();
+        let proposal = empty_proposal();
+        assert!(quarantines(&proposal, "agent", &policies));
+        assert!(!quarantines(&proposal, "human", &policies));
+        assert!(!quarantines(&proposal, "agent", &PolicyConfig::default()));
+        let elsewhere = PolicyConfig {
+            rules: vec![PolicyRule::AgentQuarantine {
+                workspaces: vec!["ui".to_string()],
+            }
+            .into()],
+        };
+        assert!(!quarantines(&proposal, "agent", &elsewhere));
+    }
+
     #[test]
     fn agent_max_sensitivity_default_is_internal() {
         let policies = PolicyConfig::default();
@@ -353,7 +717,8 @@ mod tests {
             rules: vec![PolicyRule::EgressControl {
                 max_sensitivity: Sensitivity::Confidential,
                 destinations: vec![],
-            }],
+            }
+            .into()],
         };
         assert_eq!(agent_max_sensitivity(&policies), Sensitivity::Confidential);
     }
@@ -364,7 +729,8 @@ mod tests {
             rules: vec![PolicyRule::AgentProposalLimit {
                 max_operations: 1,
                 max_content_length: 10,
-            }],
+            }
+            .into()],
         };
         let mut proposal = empty_proposal();
         proposal.operations = vec![
@@ -396,6 +762,8 @@ mod tests {
                         source_attribution: None,
                         ip_classification: None,
                         license: None,
+                        deleted: None,
+                        field_changes: None,
                     },
                     relationships: None,
                     relations: None,
@@ -447,6 +815,8 @@ mod tests {
                         source_attribution: None,
                         ip_classification: None,
                         license: None,
+                        deleted: None,
+                        field_changes: None,
                     },
                     relationships: None,
                     relations: None,
@@ -485,7 +855,8 @@ mod tests {
             rules: vec![PolicyRule::AgentProposalLimit {
                 max_operations: 0,
                 max_content_length: 0,
-            }],
+            }
+            .into()],
         };
         let proposal = empty_proposal();
         let violations = evaluate_on_create(&proposal, "human", &policies);
@@ -503,7 +874,8 @@ mod tests {
                 allowed_days: vec![], // no days allowed
                 allowed_hour_start: 0,
                 allowed_hour_end: 0,
-            }],
+            }
+            .into()],
         };
         let proposal = empty_proposal();
         let violations = evaluate_on_apply(&proposal, "human", &policies);
@@ -513,12 +885,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn evaluate_on_apply_change_freeze_blocks_while_it_lasts() {
+        let now = chrono::Utc::now();
+        let freeze = |start, end| PolicyConfig {
+            rules: vec![PolicyRule::ChangeFreeze {
+                start,
+                end,
+                reason: Some("year-end close".to_string()),
+            }
+            .into()],
+        };
+        let proposal = empty_proposal();
+        let hour = chrono::Duration::hours(1);
+        let during = evaluate_on_apply(&proposal, "human", &freeze(now - hour, now + hour));
+        assert_eq!(during[0].rule, "change_freeze");
+        assert!(during[0].message.ends_with(": year-end close"));
+        let over = evaluate_on_apply(&proposal, "human", &freeze(now - hour * 2, now - hour));
+        assert!(over.is_empty());
+    }
+
     #[test]
     fn evaluate_on_apply_agent_restriction() {
         let policies = PolicyConfig {
             rules: vec![PolicyRule::AgentRestriction {
                 blocked_actions: vec!["apply".to_string()],
-            }],
+            }
+            .into()],
         };
         let proposal = empty_proposal();
         let violations = evaluate_on_apply(&proposal, "agent", &policies);
@@ -535,7 +928,8 @@ mod tests {
             rules: vec![PolicyRule::EgressControl {
                 max_sensitivity: Sensitivity::Internal,
                 destinations: vec![],
-            }],
+            }
+            .into()],
         };
         let mut proposal = empty_proposal();
         proposal.operations = vec![crate::types::proposal::Operation::Create {
@@ -566,6 +960,8 @@ mod tests {
                     source_attribution: None,
                     ip_classification: None,
                     license: None,
+                    deleted: None,
+                    field_changes: None,
                 },
                 relationships: None,
                 relations: None,
@@ -606,4 +1002,158 @@ mod tests {
             "human should not be restricted"
         );
     }
+
+    #[test]
+    fn rules_that_are_not_enforced_report_without_blocking() {
+        let policies: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [
+                { "type": "min_approvals", "min": 2, "enforcement": "shadow" },
+                { "type": "agent_proposal_limit", "max_operations": 0, "enforcement": "warn" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(policies.rules[0].enforcement, Enforcement::Shadow);
+        assert_eq!(
+            serde_json::to_value(&policies.rules[1]).unwrap()["enforcement"],
+            "warn"
+        );
+        let approval: Review = serde_json::from_value(serde_json::json!({
+            "id": "r-1", "proposalId": "p-test", "reviewer": "bob",
+            "reviewedAt": "2026-01-02T00:00:00Z", "action": "accept"
+        }))
+        .unwrap();
+        let (outcome, violations) = evaluate_on_review(&empty_proposal(), &[approval], &policies);
+        assert_eq!(outcome, Some(ProposalStatus::Accepted));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, "min_approvals");
+        assert_eq!(violations[0].enforcement, Enforcement::Shadow);
+
+        let mut proposal = empty_proposal();
+        proposal.operations = serde_json::from_value(serde_json::json!([
+            { "type": "delete", "id": "op-1", "order": 1, "node_id": { "id": "n1" } }
+        ]))
+        .unwrap();
+        let violations = evaluate_on_create(&proposal, "agent", &policies);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].enforcement, Enforcement::Warn);
+    }
+
+    #[test]
+    fn min_score_rules_cover_complex_proposals_only() {
+        let policies: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [{ "type": "min_approvals", "min": 3, "min_score": 10 }]
+        }))
+        .unwrap();
+        let mut proposal = empty_proposal();
+        assert_eq!(min_approvals(&proposal, policies.enforced()), 1);
+        proposal.metadata.complexity = Some(crate::types::Complexity {
+            score: 12,
+            ..Default::default()
+        });
+        assert_eq!(min_approvals(&proposal, policies.enforced()), 3);
+    }
+
+    #[test]
+    fn group_quorums_count_distinct_members() {
+        let policies: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "rules": [{ "type": "min_approvals", "quorums": [
+                { "group": "security-team", "min": 2 },
+                { "group": "architects", "min": 1 }
+            ] }]
+        }))
+        .unwrap();
+        let approval = |id: &str, reviewer: &str, groups: &[&str]| -> Review {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "proposalId": "p-test", "reviewer": reviewer,
+                "reviewedAt": "2026-01-02T00:00:00Z", "action": "accept",
+                "reviewerGroups": groups
+            }))
+            .unwrap()
+        };
+        let mut reviews = vec![
+            approval("r-1", "sam", &["security-team", "architects"]),
+            approval("r-2", "sam", &["security-team"]),
+        ];
+        let (outcome, violations) = evaluate_on_review(&empty_proposal(), &reviews, &policies);
+        assert_eq!(outcome, None);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("'security-team', got 1"));
+
+        reviews.push(approval("r-3", "sue", &["security-team"]));
+        let (outcome, violations) = evaluate_on_review(&empty_proposal(), &reviews, &policies);
+        assert_eq!(outcome, Some(ProposalStatus::Accepted));
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn patching_to_accepted_needs_the_approvals() {
+        let policies = PolicyConfig {
+            rules: vec![
+                PolicyRule::MinApprovals {
+                    node_types: vec![],
+                    min: 2,
+                    min_score: None,
+                    quorums: vec![],
+                }
+                .into(),
+                PolicyRule::AgentRestriction {
+                    blocked_actions: vec!["update".to_string()],
+                }
+                .into(),
+            ],
+        };
+        let mut patched = empty_proposal();
+        patched.status = ProposalStatus::Accepted;
+        let approval: Review = serde_json::from_value(serde_json::json!({
+            "id": "r-1", "proposalId": "p-test", "reviewer": "bob",
+            "reviewedAt": "2026-01-02T00:00:00Z", "action": "accept"
+        }))
+        .unwrap();
+
+        let violations = evaluate_on_update(
+            &patched,
+            ProposalStatus::Open,
+            std::slice::from_ref(&approval),
+            "human",
+            &policies,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, "min_approvals");
+
+        let second = Review {
+            id: "r-2".to_string(),
+            ..approval.clone()
+        };
+        let reviews = [approval, second];
+        let ok = evaluate_on_update(&patched, ProposalStatus::Open, &reviews, "human", &policies);
+        assert!(ok.is_empty());
+
+        // Rejecting by PATCH is gated the same way, on reviews of any kind.
+        patched.status = ProposalStatus::Rejected;
+        let violations = evaluate_on_update(
+            &patched,
+            ProposalStatus::Open,
+            &reviews[..1],
+            "human",
+            &policies,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message,
+            "rejecting requires 2 review(s), got 1"
+        );
+        let ok = evaluate_on_update(&patched, ProposalStatus::Open, &reviews, "human", &policies);
+        assert!(ok.is_empty());
+
+        // Rationale edits need no reviews, but agents are blocked from updating.
+        let agent = evaluate_on_update(
+            &empty_proposal(),
+            ProposalStatus::Open,
+            &[],
+            "agent",
+            &policies,
+        );
+        assert_eq!(agent.len(), 1);
+        assert_eq!(agent[0].rule, "agent_restriction");
+    }
 }
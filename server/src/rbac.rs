@@ -1,8 +1,12 @@
 //! RBAC enforcement: Axum extractors that check ActorContext roles.
-//! Used in route handlers to gate access.
+//! Used in route handlers to gate access. Each REST route has a default required role in
+//! its handler; [`RouteRoles`] (`rbac.routes` in config.json) overrides it per route.
+
+use std::collections::BTreeMap;
 
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
 
 use crate::auth::{ActorContext, ActorType, Role};
 
@@ -11,11 +15,12 @@ pub struct Forbidden(pub String);
 
 impl IntoResponse for Forbidden {
     fn into_response(self) -> Response {
-        (
+        crate::api::strict::error_response(
             StatusCode::FORBIDDEN,
-            axum::Json(serde_json::json!({ "error": self.0 })),
+            "forbidden",
+            self.0,
+            Default::default(),
         )
-            .into_response()
     }
 }
 
@@ -31,6 +36,137 @@ pub fn require_role(actor: &ActorContext, role: Role) -> Result<(), Forbidden> {
     }
 }
 
+/// Required role by route, overriding the handlers' defaults (`rbac.routes` in
+/// config.json, reloadable). Keys are the method and the route as registered, e.g.
+/// `"GET /audit"` or `"POST /proposals/:id/apply"`. Service calls behind GraphQL, gRPC
+/// and MCP follow the key of the REST route they serve.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RouteRoles(pub BTreeMap<String, Role>);
+
+/// Keys `rbac.routes` may set: the routes whose handlers (or the service calls behind
+/// them) require their role through [`require_route_role`], by path.
+pub const ROUTES: [&str; 73] = [
+    "GET /actors",
+    "GET /admin/config",
+    "GET /admin/conflicts",
+    "POST /admin/dsar/erase",
+    "GET /admin/dsar/export",
+    "GET /admin/exports",
+    "POST /admin/exports",
+    "GET /admin/exports/:id",
+    "GET /admin/exports/:id/download",
+    "GET /admin/jobs",
+    "GET /admin/jobs/:id",
+    "POST /admin/jobs/:id/retry",
+    "GET /admin/policy/violations",
+    "GET /admin/read-only",
+    "PUT /admin/read-only",
+    "POST /admin/seed",
+    "POST /admin/store/compact",
+    "GET /admin/store/status",
+    "GET /admin/tasks",
+    "POST /admin/tasks/:name/run",
+    "GET /admin/trash",
+    "POST /admin/trash/:id/restore",
+    "GET /admin/usage",
+    "GET /audit",
+    "GET /audit/export",
+    "GET /calendar",
+    "GET /changes",
+    "GET /context-pack",
+    "GET /events",
+    "GET /nodes",
+    "GET /nodes/:id",
+    "POST /nodes/:id/answer",
+    "POST /nodes/:id/archive",
+    "GET /nodes/:id/blame",
+    "POST /nodes/:id/commits",
+    "POST /nodes/:id/lock",
+    "DELETE /nodes/:id/lock",
+    "GET /nodes/:id/proposals",
+    "POST /nodes/:id/propose-update",
+    "GET /nodes/:id/provenance",
+    "GET /nodes/locks",
+    "GET /proposals",
+    "POST /proposals",
+    "GET /proposals/:id",
+    "PATCH /proposals/:id",
+    "POST /proposals/:id/apply",
+    "POST /proposals/:id/forge",
+    "PATCH /proposals/:id/operations",
+    "POST /proposals/:id/resolve-conflicts",
+    "POST /proposals/:id/review",
+    "GET /proposals/:id/reviews",
+    "POST /proposals/:id/withdraw",
+    "POST /proposals/auto-merge",
+    "POST /proposals/merge",
+    "GET /proposals/quarantine",
+    "POST /proposals/triage",
+    "POST /proposals/validate",
+    "POST /reset",
+    "GET /scim/v2/Groups",
+    "POST /scim/v2/Groups",
+    "GET /scim/v2/Groups/:id",
+    "PUT /scim/v2/Groups/:id",
+    "PATCH /scim/v2/Groups/:id",
+    "DELETE /scim/v2/Groups/:id",
+    "GET /scim/v2/Users",
+    "POST /scim/v2/Users",
+    "GET /scim/v2/Users/:id",
+    "PUT /scim/v2/Users/:id",
+    "PATCH /scim/v2/Users/:id",
+    "DELETE /scim/v2/Users/:id",
+    "POST /tasks/:id/state",
+    "CONNECT /webtransport",
+    "GET /ws",
+];
+
+impl RouteRoles {
+    /// The role `route` requires: its configured one, else `default`.
+    pub fn required(&self, route: &str, default: Role) -> Role {
+        self.0.get(route).copied().unwrap_or(default)
+    }
+
+    /// Problems in `rbac.routes`: keys that are not in [`ROUTES`]. An override that no
+    /// route would read is refused rather than silently ignored.
+    pub fn validate(&self) -> Vec<String> {
+        self.0
+            .keys()
+            .filter(|key| !ROUTES.contains(&key.as_str()))
+            .map(|key| {
+                format!(
+                    "rbac.routes: unknown route '{}' (keys are 'METHOD /path' as registered, e.g. 'POST /proposals/:id/apply')",
+                    key
+                )
+            })
+            .collect()
+    }
+}
+
+/// Require the role `route` needs per `routes`, `default` unless configured.
+pub fn require_route_role(
+    routes: &RouteRoles,
+    route: &str,
+    actor: &ActorContext,
+    default: Role,
+) -> Result<(), Forbidden> {
+    require_role(actor, routes.required(route, default))
+}
+
+/// Attribute `field` (e.g. "metadata.createdBy") to the actor: fill it in when empty,
+/// refuse it when it names someone else, so no caller can write as another identity.
+pub fn attribute(actor: &ActorContext, field: &str, value: &mut String) -> Result<(), Forbidden> {
+    if !value.is_empty() && *value != actor.actor_id {
+        return Err(Forbidden(format!(
+            "{} '{}' does not match the authenticated actor {}",
+            field, value, actor.actor_id
+        )));
+    }
+    *value = actor.actor_id.clone();
+    Ok(())
+}
+
 /// Reject if actor_type is Agent (agents cannot review or apply).
 pub fn reject_agent(actor: &ActorContext, action: &str) -> Result<(), Forbidden> {
     if actor.actor_type == ActorType::Agent {
@@ -42,3 +178,54 @@ pub fn reject_agent(actor: &ActorContext, action: &str) -> Result<(), Forbidden>
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn forbidden_is_a_problem() {
+        let res = Forbidden("insufficient role".to_string()).into_response();
+        let body = crate::api::strict::expect_error(res, StatusCode::FORBIDDEN, "forbidden").await;
+        assert_eq!(body["detail"], "insufficient role");
+    }
+
+    #[test]
+    fn route_keys_must_be_registered() {
+        let routes: RouteRoles = serde_json::from_value(serde_json::json!({
+            "GET /audit": "reviewer",
+            "CONNECT /webtransport": "admin",
+            "POST /proposals/:id/aply": "admin",
+            "get /audit": "admin"
+        }))
+        .unwrap();
+        let issues = routes.validate();
+        assert_eq!(issues.len(), 2, "{:?}", issues);
+        assert!(issues[0].contains("'POST /proposals/:id/aply'"));
+        assert!(issues[1].contains("'get /audit'"));
+    }
+
+    /// Every key the API passes to `require_route` is in [`ROUTES`], and every entry is used.
+    #[test]
+    fn routes_list_every_require_route_key() {
+        let mut used = std::collections::BTreeSet::new();
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/api");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let file = entry.unwrap().path();
+            let source = std::fs::read_to_string(&file).unwrap();
+            for (at, _) in source.match_indices("require_route(") {
+                if source[..at].ends_with("fn ") {
+                    continue;
+                }
+                let rest = &source[at..];
+                let start = rest.find('"').unwrap() + 1;
+                let key = &rest[start..start + rest[start..].find('"').unwrap()];
+                assert!(ROUTES.contains(&key), "{}: '{}'", file.display(), key);
+                used.insert(key.to_string());
+            }
+        }
+        for key in ROUTES {
+            assert!(used.contains(key), "'{}' is not required anywhere", key);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Read-only mode for migrations, restores and incident response.
+//!
+//! Turned on by `server.read_only` in config.json (applied at startup and on SIGHUP when
+//! the value changes) or at runtime with `PUT /admin/read-only`; the latest change wins.
+//! While it is on, REST requests with a mutating method get `503 Service Unavailable`
+//! with the reason, and the write operations shared by gRPC and MCP (create proposal,
+//! review, apply) are refused the same way. Reads, SSE, WebSocket and WebTransport
+//! streams keep working. Every change is recorded as a `read_only_changed` audit event.
+//!
+//! Background jobs and scheduled tasks are not paused; disable their tasks if they must
+//! not write during the maintenance window.
+
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::api::routes::ApiError;
+use crate::reload::Reloadable;
+use crate::types::{AuditAction, AuditDetails, AuditEvent, AuditOutcome};
+
+/// Why and since when the server is read-only.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyMode {
+    pub reason: String,
+    pub since: String,
+    /// Actor that turned it on, or `config` for `server.read_only`.
+    pub changed_by: String,
+}
+
+impl ReadOnlyMode {
+    pub fn new(reason: &str, changed_by: &str) -> Self {
+        Self {
+            reason: reason.to_string(),
+            since: chrono::Utc::now().to_rfc3339(),
+            changed_by: changed_by.to_string(),
+        }
+    }
+}
+
+/// Current read-only state; None while writable.
+pub type ReadOnlyState = Reloadable<Option<ReadOnlyMode>>;
+
+/// Reason recorded when `server.read_only` turns the mode on.
+pub const CONFIG_REASON: &str = "server.read_only is set in config.json";
+
+/// Refuse a write while the server is read-only.
+pub fn check_writable(state: &ReadOnlyState) -> Result<(), ApiError> {
+    match &*state.get() {
+        Some(mode) => Err(ApiError::ReadOnly(format!(
+            "server is read-only for maintenance since {}: {}",
+            mode.since, mode.reason
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Paths whose mutating-method requests are not refused here: the toggle itself, and
+/// transports that carry reads over POST (their writes go through `check_writable`).
+fn exempt(path: &str) -> bool {
+    matches!(
+        path,
+        "/admin/read-only" | "/graphql" | "/agent/batch" | "/mcp"
+    ) || path.starts_with("/truthlayer.v1.ContextService/")
+}
+
+/// Axum middleware: 503 for POST / PUT / PATCH / DELETE while read-only.
+pub async fn guard(State(state): State<ReadOnlyState>, req: Request, next: Next) -> Response {
+    let mutating = matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    if mutating && !exempt(req.uri().path()) {
+        if let Err(e) = check_writable(&state) {
+            return e.into_response();
+        }
+    }
+    next.run(req).await
+}
+
+/// Audit event for a change of the flag.
+pub fn audit_event(
+    actor_id: &str,
+    actor_type: &str,
+    mode: Option<&ReadOnlyMode>,
+    source: &str,
+) -> AuditEvent {
+    AuditEvent::new(
+        actor_id,
+        actor_type,
+        AuditAction::ReadOnlyChanged,
+        "server",
+        AuditOutcome::Success,
+    )
+    .with_details(AuditDetails::ReadOnly {
+        enabled: mode.is_some(),
+        reason: mode.map(|m| m.reason.clone()),
+        source: source.to_string(),
+    })
+}
@@ -0,0 +1,299 @@
+//! Runtime config reload (SIGHUP) for the settings that can change without a restart.
+//!
+//! Reloadable: `server.policies_path` (and the policies file itself), `cors`,
+//! `quic.max_requests_per_sec`, `server.log_level`, `server.read_only`, `content_rules`. Everything else (listen addresses, TLS,
+//! storage, connection/stream caps, body limits) keeps its startup value until restart;
+//! `GET /admin/config` shows the effective configuration either way.
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use tower_http::cors::CorsLayer;
+
+use crate::config::{load_config_checked, ServerConfig};
+use crate::h3_server::QuicLimits;
+use crate::policy::PolicyConfig;
+use crate::read_only::{self, ReadOnlyMode, ReadOnlyState};
+use crate::store::{ContextStore, WriteBatch};
+
+/// Hot-swappable value: readers take a cheap `Arc` snapshot, reloads replace it whole.
+pub struct Reloadable<T>(Arc<RwLock<Arc<T>>>);
+
+impl<T> Clone for Reloadable<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Reloadable<T> {
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(value))))
+    }
+
+    /// Current value.
+    pub fn get(&self) -> Arc<T> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the value; in-flight readers keep their snapshot.
+    pub fn set(&self, value: T) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(value);
+    }
+}
+
+/// Applies a new log filter directive (e.g. `info,truthlayer_server=debug`).
+pub type LogLevelSetter = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Reloadable runtime state shared by the router, middleware and the QUIC server.
+#[derive(Clone)]
+pub struct RuntimeConfig {
+    /// Effective configuration (startup values plus reloaded settings).
+    pub config: Reloadable<ServerConfig>,
+    pub policies: Reloadable<PolicyConfig>,
+    pub quic_limits: Reloadable<QuicLimits>,
+    pub cors: Reloadable<CorsLayer>,
+    /// RFC 3339 time of the last successful reload; None until the first SIGHUP.
+    pub reloaded_at: Reloadable<Option<String>>,
+    /// Read-only mode; None while writable.
+    pub read_only: ReadOnlyState,
+    log_level: Option<LogLevelSetter>,
+}
+
+impl RuntimeConfig {
+    pub fn new(config: ServerConfig, policies: PolicyConfig) -> Self {
+        Self {
+            policies: Reloadable::new(policies),
+            quic_limits: Reloadable::new(config.quic_limits.clone()),
+            cors: Reloadable::new(config.cors.to_layer()),
+            read_only: Reloadable::new(
+                config
+                    .read_only
+                    .then(|| ReadOnlyMode::new(read_only::CONFIG_REASON, "config")),
+            ),
+            config: Reloadable::new(config),
+            reloaded_at: Reloadable::new(None),
+            log_level: None,
+        }
+    }
+
+    /// Enable log level reloads through the given setter.
+    pub fn with_log_level_setter(mut self, setter: LogLevelSetter) -> Self {
+        self.log_level = Some(setter);
+        self
+    }
+
+    /// Apply the reloadable settings from a freshly loaded config.
+    /// A malformed policies file or unknown `rbac.routes` keys (the current rules stay in
+    /// effect) or an invalid log level is reported as an error after the other settings are
+    /// applied.
+    pub fn reload(&self, fresh: ServerConfig) -> Result<(), String> {
+        let mut effective = (*self.config.get()).clone();
+        effective.policies_path = fresh.policies_path;
+        effective.cors = fresh.cors;
+        effective.content_rules = fresh.content_rules;
+        effective.quic_limits.max_requests_per_sec = fresh.quic_limits.max_requests_per_sec;
+
+        let mut errors = Vec::new();
+        // Keep the current overrides rather than load ones that name unknown routes.
+        let route_issues = fresh.route_roles.validate();
+        if route_issues.is_empty() {
+            effective.route_roles = fresh.route_roles;
+        } else {
+            errors.push(format!(
+                "rbac.routes not reloaded: {}",
+                route_issues.join("; ")
+            ));
+        }
+        match PolicyConfig::try_load_from_file(&effective.policies_file()) {
+            Ok(policies) => {
+                tracing::info!(
+                    path = %effective.policies_file().display(),
+                    rules = policies.rules.len(),
+                    "policies reloaded"
+                );
+                self.policies.set(policies);
+            }
+            // Keep enforcing the current rules rather than silently dropping them.
+            Err(e) => errors.push(format!("policies not reloaded: {}", e)),
+        }
+        self.cors.set(effective.cors.to_layer());
+        self.quic_limits.set(effective.quic_limits.clone());
+        // Only a change of the config value toggles the mode, so a reload does not undo
+        // `PUT /admin/read-only`.
+        if fresh.read_only != effective.read_only {
+            effective.read_only = fresh.read_only;
+            self.read_only.set(
+                fresh
+                    .read_only
+                    .then(|| ReadOnlyMode::new(read_only::CONFIG_REASON, "config")),
+            );
+        }
+
+        if let (Some(level), Some(setter)) = (&fresh.log_level, &self.log_level) {
+            match setter(level) {
+                Ok(()) => effective.log_level = Some(level.clone()),
+                Err(e) => errors.push(format!("invalid log level '{}': {}", level, e)),
+            }
+        }
+
+        self.config.set(effective);
+        self.reloaded_at.set(Some(chrono::Utc::now().to_rfc3339()));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Reload config from `config_root` whenever the process receives SIGHUP (Unix only).
+/// A read-only mode change is audited to `store`.
+pub fn spawn_sighup_reload(
+    runtime: RuntimeConfig,
+    config_root: PathBuf,
+    store: Arc<dyn ContextStore>,
+) {
+    #[cfg(unix)]
+    tokio::spawn(async move {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::warn!(error = %e, "cannot listen for SIGHUP; config reload disabled");
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            tracing::info!("SIGHUP received; reloading config");
+            let (fresh, issues) = load_config_checked(Some(config_root.clone()));
+            for issue in &issues {
+                tracing::warn!(issue = %issue, "config problem during reload");
+            }
+            let read_only_before = runtime.read_only.get();
+            if let Err(e) = runtime.reload(fresh) {
+                tracing::warn!(error = %e, "config reload incomplete");
+            }
+            let read_only_after = runtime.read_only.get();
+            if !Arc::ptr_eq(&read_only_before, &read_only_after) {
+                tracing::warn!(
+                    enabled = read_only_after.is_some(),
+                    "read-only mode changed by config reload"
+                );
+                let event = read_only::audit_event(
+                    "config",
+                    "system",
+                    read_only_after.as_ref().as_ref(),
+                    "config",
+                );
+                if let Err(e) = store.execute(WriteBatch::new().audit(event)).await {
+                    tracing::warn!(error = %e, "read-only change not audited");
+                }
+            }
+        }
+    });
+    #[cfg(not(unix))]
+    {
+        let _ = (runtime, config_root, store);
+        tracing::debug!("config reload via SIGHUP is not supported on this platform");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reload_applies_reloadable_settings_only() {
+        let runtime = RuntimeConfig::new(ServerConfig::default(), PolicyConfig::default());
+        let mut fresh = ServerConfig {
+            listen_addr: "0.0.0.0:9999".to_string(),
+            ..Default::default()
+        };
+        fresh.quic_limits.max_requests_per_sec = 7;
+        fresh.quic_limits.max_connections = 1;
+        fresh.cors.allowed_origins = vec!["https://a.example".to_string()];
+
+        runtime.reload(fresh).unwrap();
+
+        let effective = runtime.config.get();
+        assert_eq!(effective.listen_addr, "127.0.0.1:3080");
+        assert_eq!(effective.cors.allowed_origins.len(), 1);
+        assert_eq!(runtime.quic_limits.get().max_requests_per_sec, 7);
+        assert_eq!(runtime.quic_limits.get().max_connections, 1024);
+        assert!(runtime.reloaded_at.get().is_some());
+    }
+
+    #[test]
+    fn reload_keeps_route_roles_with_unknown_keys() {
+        let runtime = RuntimeConfig::new(ServerConfig::default(), PolicyConfig::default());
+        let mut fresh = ServerConfig::default();
+        fresh
+            .route_roles
+            .0
+            .insert("GET /audit".to_string(), crate::auth::Role::Reviewer);
+        runtime.reload(fresh.clone()).unwrap();
+        fresh
+            .route_roles
+            .0
+            .insert("GET /audits".to_string(), crate::auth::Role::Admin);
+        assert!(runtime.reload(fresh).unwrap_err().contains("GET /audits"));
+        assert_eq!(runtime.config.get().route_roles.0.len(), 1);
+    }
+
+    #[test]
+    fn reload_reports_invalid_log_level() {
+        let setter: LogLevelSetter = Arc::new(|level: &str| {
+            if level == "bogus[" {
+                Err("parse error".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        let runtime = RuntimeConfig::new(ServerConfig::default(), PolicyConfig::default())
+            .with_log_level_setter(setter);
+        let fresh = ServerConfig {
+            log_level: Some("bogus[".to_string()),
+            ..Default::default()
+        };
+        assert!(runtime.reload(fresh).is_err());
+        assert!(runtime.config.get().log_level.is_none());
+    }
+
+    #[test]
+    fn reload_toggles_read_only_only_when_the_config_value_changes() {
+        let runtime = RuntimeConfig::new(ServerConfig::default(), PolicyConfig::default());
+        assert!(runtime.read_only.get().is_none());
+
+        let read_only = || ServerConfig {
+            read_only: true,
+            ..Default::default()
+        };
+        runtime.reload(read_only()).unwrap();
+        assert_eq!(
+            runtime
+                .read_only
+                .get()
+                .as_ref()
+                .as_ref()
+                .unwrap()
+                .changed_by,
+            "config"
+        );
+
+        // Turned off through the API: reloading the unchanged config keeps it off.
+        runtime.read_only.set(None);
+        runtime.reload(read_only()).unwrap();
+        assert!(runtime.read_only.get().is_none());
+
+        runtime.reload(ServerConfig::default()).unwrap();
+        runtime
+            .read_only
+            .set(Some(ReadOnlyMode::new("restore", "alice")));
+        runtime.reload(ServerConfig::default()).unwrap();
+        assert_eq!(
+            runtime.read_only.get().as_ref().as_ref().unwrap().reason,
+            "restore"
+        );
+    }
+}
@@ -1,11 +1,16 @@
 //! Retention policy engine: configurable rules for data lifecycle management.
-//! Runs as a background tokio task that periodically enforces retention on proposals and audit logs.
+//! A timer task periodically queues a sweep job (see `crate::jobs`) that enforces retention
+//! on proposals and audit logs. A `node` rule with the `delete` action purges nodes that
+//! have been in the trash longer than `retention_days` (see `store::trash`).
 
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::store::ContextStore;
-use crate::types::{AuditAction, AuditEvent, AuditOutcome};
+use crate::cluster::Cluster;
+use crate::jobs::JobHandler;
+use crate::store::{ContextStore, WriteBatch};
+use crate::types::{AuditAction, AuditDetails, AuditEvent, AuditOutcome, JobRecord};
 
 /// Action to take when retention period expires.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -42,22 +47,111 @@ fn default_interval() -> u64 {
 
 impl RetentionConfig {
     pub fn load_from_file(path: &std::path::Path) -> Self {
-        if path.exists() {
-            if let Ok(s) = std::fs::read_to_string(path) {
-                if let Ok(config) = serde_json::from_str::<RetentionConfig>(&s) {
-                    return config;
-                }
+        Self::try_load_from_file(path).unwrap_or_default()
+    }
+
+    /// Like [`load_from_file`](Self::load_from_file), but unreadable or malformed files are
+    /// errors instead of an empty config. Missing file = empty.
+    pub fn try_load_from_file(path: &std::path::Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str::<RetentionConfig>(&s)
+            .map_err(|e| format!("malformed {}: {}", path.display(), e))
+    }
+}
+
+/// Job kind of a retention sweep; the payload is the list of rules to enforce.
+pub const RETENTION_SWEEP_JOB: &str = "retention_sweep";
+
+/// Enforce each rule once.
+pub async fn sweep(store: &dyn ContextStore, rules: &[RetentionRule]) {
+    for rule in rules {
+        tracing::debug!(
+            resource_type = %rule.resource_type,
+            retention_days = rule.retention_days,
+            "checking retention"
+        );
+        // Deleted nodes are purged for good; other rules only record the check so far.
+        if rule.resource_type == "node" && rule.action == RetentionAction::Delete {
+            purge_trash(store, rule).await;
+        }
+        let event = AuditEvent::new(
+            "system",
+            "system",
+            AuditAction::PolicyEvaluated,
+            &format!("retention:{}", rule.resource_type),
+            AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::Retention {
+            retention_rule: rule.resource_type.clone(),
+            retention_days: rule.retention_days,
+            action: rule.action.clone(),
+        });
+        if let Err(e) = store.execute(WriteBatch::new().audit(event)).await {
+            tracing::warn!(error = %e, "retention check not audited");
+        }
+    }
+}
+
+/// Remove nodes that have been in the trash longer than the rule's retention period.
+async fn purge_trash(store: &dyn ContextStore, rule: &RetentionRule) {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(rule.retention_days));
+    match store.purge_trash(&cutoff.to_rfc3339()).await {
+        Ok(purged) if purged.is_empty() => {}
+        Ok(purged) => {
+            tracing::info!(nodes = purged.len(), "purged deleted nodes past retention");
+            let keys: Vec<String> = purged.iter().map(|id| id.key()).collect();
+            let event = AuditEvent::new(
+                "system",
+                "system",
+                AuditAction::NodesPurged,
+                "retention:node",
+                AuditOutcome::Success,
+            )
+            .with_details(AuditDetails::Purged {
+                nodes: keys,
+                retention_days: rule.retention_days,
+            });
+            if let Err(e) = store.execute(WriteBatch::new().audit(event)).await {
+                tracing::warn!(error = %e, "purge of deleted nodes not audited");
             }
         }
-        Self::default()
+        Err(e) => tracing::warn!(error = %e, "could not purge deleted nodes"),
+    }
+}
+
+/// Runs [`RETENTION_SWEEP_JOB`] jobs on the job queue.
+pub struct RetentionSweepHandler;
+
+#[async_trait]
+impl JobHandler for RetentionSweepHandler {
+    fn kind(&self) -> &'static str {
+        RETENTION_SWEEP_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let rules: Vec<RetentionRule> = serde_json::from_value(job.payload.clone())
+            .map_err(|e| format!("invalid retention rules: {}", e))?;
+        sweep(store.as_ref(), &rules).await;
+        Ok(Some(serde_json::json!({ "rules": rules.len() })))
     }
 }
 
-/// Spawn a background retention task (non-blocking).
+/// Spawn the retention timer (non-blocking): every `check_interval_secs` it queues a
+/// [`RETENTION_SWEEP_JOB`] for the job workers to run. With several instances on one
+/// store, only the holder of the `retention_sweep` lease queues sweeps.
 /// Returns a JoinHandle that can be used to monitor or abort the task.
 pub fn spawn_retention_task(
     store: Arc<dyn ContextStore>,
     config: RetentionConfig,
+    cluster: Cluster,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         if config.rules.is_empty() {
@@ -74,27 +168,18 @@ pub fn spawn_retention_task(
 
         loop {
             tokio::time::sleep(interval).await;
-            for rule in &config.rules {
-                tracing::debug!(
-                    resource_type = %rule.resource_type,
-                    retention_days = rule.retention_days,
-                    "checking retention"
-                );
-                // Log a retention check event (actual deletion logic would go here
-                // once we have created_at timestamps queryable on proposals/nodes).
-                let event = AuditEvent::new(
-                    "system",
-                    "system",
-                    AuditAction::PolicyEvaluated,
-                    &format!("retention:{}", rule.resource_type),
-                    AuditOutcome::Success,
-                )
-                .with_details(serde_json::json!({
-                    "retention_rule": rule.resource_type,
-                    "retention_days": rule.retention_days,
-                    "action": format!("{:?}", rule.action),
-                }));
-                let _ = store.append_audit(event).await;
+            // Held across ticks (renewed each time), so a standby instance takes over
+            // only once the holder has missed a check.
+            if let Err(e) = cluster
+                .acquire_for("retention_sweep", interval + cluster.lease_ttl())
+                .await
+            {
+                tracing::debug!(reason = %e, "retention sweep left to another instance");
+                continue;
+            }
+            let job = JobRecord::new(RETENTION_SWEEP_JOB, serde_json::json!(config.rules));
+            if let Err(e) = store.save_job(job).await {
+                tracing::warn!(error = %e, "could not queue retention sweep");
             }
         }
     })
@@ -0,0 +1,350 @@
+//! Cron-style scheduled tasks: retention sweep, stale-proposal check, hash verification,
+//! snapshot, store compaction and usage rollup.
+//!
+//! Each task is configured under `tasks` in config.json with a cron expression (UTC) and
+//! an `enabled` flag; unconfigured tasks do not run. When a task is due the scheduler
+//! queues a job of its kind on the job queue (`crate::jobs`), which does the work with
+//! the usual retries. A task's last run is its newest job, so `/admin/tasks` shows
+//! last-run status from the job records, across restarts with the file backend.
+//!
+//! With a [`Cluster`] handle, each due run is queued by whichever instance first takes
+//! its `task:{name}:{due time}` lease, so it is queued once.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::exports::SNAPSHOT_JOB;
+use crate::cluster::Cluster;
+use crate::jobs::JobQueue;
+use crate::maintenance::{
+    HASH_VERIFICATION_JOB, OVERDUE_TASK_CHECK_JOB, RISK_REVIEW_REMINDER_JOB,
+    STALE_PROPOSAL_CHECK_JOB, STORE_COMPACTION_JOB,
+};
+use crate::retention::{RetentionConfig, RETENTION_SWEEP_JOB};
+use crate::store::context_store::StoreError;
+use crate::store::ContextStore;
+use crate::types::JobRecord;
+use crate::usage::USAGE_ROLLUP_JOB;
+
+/// Built-in tasks: task name and the job kind it queues.
+pub const TASKS: &[(&str, &str)] = &[
+    ("retention_sweep", RETENTION_SWEEP_JOB),
+    ("stale_proposal_check", STALE_PROPOSAL_CHECK_JOB),
+    ("hash_verification", HASH_VERIFICATION_JOB),
+    ("overdue_task_check", OVERDUE_TASK_CHECK_JOB),
+    ("risk_review_reminder", RISK_REVIEW_REMINDER_JOB),
+    ("snapshot", SNAPSHOT_JOB),
+    ("store_compaction", STORE_COMPACTION_JOB),
+    ("usage_rollup", USAGE_ROLLUP_JOB),
+];
+
+/// One entry of `tasks` in config.json, keyed by task name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTaskConfig {
+    /// Cron expression in UTC: 5 fields (`min hour day month weekday`) or 6 with
+    /// leading seconds.
+    pub schedule: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Job payload for the task (e.g. `{ "staleAfterDays": 30 }`, or
+    /// `{ "proposalRetentionDays": 30 }` for `store_compaction`). The retention sweep
+    /// ignores it and reads `retention.json` at each run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Parse a 5- or 6-field cron expression.
+pub fn parse_schedule(expr: &str) -> Result<cron::Schedule, String> {
+    let normalized = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr.trim())
+    } else {
+        expr.trim().to_string()
+    };
+    cron::Schedule::from_str(&normalized)
+        .map_err(|e| format!("invalid cron expression '{}': {}", expr, e))
+}
+
+/// Problems in a `tasks` config (unknown task names, unparseable schedules).
+pub fn validate(tasks: &BTreeMap<String, ScheduledTaskConfig>) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (name, task) in tasks {
+        if !TASKS.iter().any(|(n, _)| n == name) {
+            let known: Vec<&str> = TASKS.iter().map(|(n, _)| *n).collect();
+            issues.push(format!(
+                "tasks.{}: unknown task (known: {})",
+                name,
+                known.join(", ")
+            ));
+        }
+        if let Err(e) = parse_schedule(&task.schedule) {
+            issues.push(format!("tasks.{}.schedule: {}", name, e));
+        }
+    }
+    issues
+}
+
+/// A task as reported by `GET /admin/tasks`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatus {
+    pub name: String,
+    pub job_kind: String,
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<String>,
+    /// Newest job of this task's kind.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<JobRecord>,
+}
+
+struct Task {
+    name: &'static str,
+    job_kind: &'static str,
+    config: Option<ScheduledTaskConfig>,
+    /// Parsed schedule; None when the task is unconfigured or its expression is invalid.
+    schedule: Option<cron::Schedule>,
+}
+
+impl Task {
+    fn next_run(
+        &self,
+        after: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let enabled = self.config.as_ref().is_some_and(|c| c.enabled);
+        if !enabled {
+            return None;
+        }
+        self.schedule.as_ref()?.after(&after).next()
+    }
+}
+
+/// The task registry and its timer. Cheap to clone.
+#[derive(Clone)]
+pub struct Scheduler {
+    jobs: JobQueue,
+    store: Arc<dyn ContextStore>,
+    tasks: Arc<Vec<Task>>,
+    retention_file: PathBuf,
+    cluster: Option<Cluster>,
+}
+
+impl Scheduler {
+    /// Build the registry from the `tasks` config. Invalid schedules are logged and the
+    /// task stays idle (`validate` reports them at startup).
+    pub fn new(
+        jobs: JobQueue,
+        store: Arc<dyn ContextStore>,
+        config: &BTreeMap<String, ScheduledTaskConfig>,
+        retention_file: PathBuf,
+    ) -> Self {
+        let tasks = TASKS
+            .iter()
+            .map(|(name, job_kind)| {
+                let config = config.get(*name).cloned();
+                let schedule = config
+                    .as_ref()
+                    .and_then(|c| match parse_schedule(&c.schedule) {
+                        Ok(s) => Some(s),
+                        Err(e) => {
+                            tracing::warn!(task = %name, error = %e, "scheduled task disabled");
+                            None
+                        }
+                    });
+                Task {
+                    name,
+                    job_kind,
+                    config,
+                    schedule,
+                }
+            })
+            .collect();
+        Self {
+            jobs,
+            store,
+            tasks: Arc::new(tasks),
+            retention_file,
+            cluster: None,
+        }
+    }
+
+    /// Coordinate timed runs with other instances through `cluster` leases.
+    pub fn with_cluster(mut self, cluster: Cluster) -> Self {
+        self.cluster = Some(cluster);
+        self
+    }
+
+    /// Whether a task is configured and enabled.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.tasks
+            .iter()
+            .any(|t| t.name == name && t.config.as_ref().is_some_and(|c| c.enabled))
+    }
+
+    /// Spawn the timer: sleeps until the next due task, queues its job, repeats.
+    /// Returns immediately (idle) when no task is enabled.
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let now = chrono::Utc::now();
+                let Some((at, task)) = scheduler
+                    .tasks
+                    .iter()
+                    .filter_map(|t| t.next_run(now).map(|at| (at, t)))
+                    .min_by_key(|(at, _)| *at)
+                else {
+                    tracing::debug!("no scheduled tasks enabled; scheduler idle");
+                    return;
+                };
+                tokio::time::sleep((at - now).to_std().unwrap_or_default()).await;
+                if let Some(cluster) = &scheduler.cluster {
+                    let lease = format!("task:{}:{}", task.name, at.to_rfc3339());
+                    if let Err(e) = cluster.acquire(&lease).await {
+                        tracing::debug!(task = %task.name, reason = %e, "scheduled task queued by another instance");
+                        continue;
+                    }
+                }
+                match scheduler.run_now(task.name).await {
+                    Ok(job) => {
+                        tracing::info!(task = %task.name, job = %job.id, "scheduled task queued")
+                    }
+                    Err(e) => {
+                        tracing::warn!(task = %task.name, error = %e, "scheduled task not queued")
+                    }
+                }
+            }
+        })
+    }
+
+    /// Queue a task's job now, whatever its schedule.
+    pub async fn run_now(&self, name: &str) -> Result<JobRecord, StoreError> {
+        let task = self
+            .tasks
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| StoreError::not_found(format!("task {}", name)))?;
+        let payload = if task.job_kind == RETENTION_SWEEP_JOB {
+            let retention = RetentionConfig::try_load_from_file(&self.retention_file)
+                .map_err(StoreError::invalid)?;
+            serde_json::json!(retention.rules)
+        } else {
+            task.config
+                .as_ref()
+                .and_then(|c| c.params.clone())
+                .unwrap_or_else(|| serde_json::json!({}))
+        };
+        self.jobs.enqueue(task.job_kind, payload).await
+    }
+
+    /// Every task with its schedule, next run and last run.
+    pub async fn status(&self) -> Result<Vec<TaskStatus>, StoreError> {
+        let now = chrono::Utc::now();
+        let mut out = Vec::with_capacity(self.tasks.len());
+        for task in self.tasks.iter() {
+            let last_run = self
+                .store
+                .list_jobs(None, Some(task.job_kind))
+                .await?
+                .into_iter()
+                .next();
+            out.push(TaskStatus {
+                name: task.name.to_string(),
+                job_kind: task.job_kind.to_string(),
+                enabled: task.config.as_ref().is_some_and(|c| c.enabled),
+                schedule: task.config.as_ref().map(|c| c.schedule.clone()),
+                next_run_at: task.next_run(now).map(|t| t.to_rfc3339()),
+                last_run,
+            });
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::JobStatus;
+
+    fn tasks(entries: &[(&str, &str, bool)]) -> BTreeMap<String, ScheduledTaskConfig> {
+        entries
+            .iter()
+            .map(|(name, schedule, enabled)| {
+                (
+                    name.to_string(),
+                    ScheduledTaskConfig {
+                        schedule: schedule.to_string(),
+                        enabled: *enabled,
+                        params: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn accepts_five_and_six_field_expressions() {
+        assert!(parse_schedule("0 3 * * *").is_ok());
+        assert!(parse_schedule("30 0 3 * * *").is_ok());
+        assert!(parse_schedule("every day").is_err());
+
+        let issues = validate(&tasks(&[
+            ("snapshot", "0 3 * * *", true),
+            ("vacuum", "0 3 * * *", true),
+            ("hash_verification", "61 * * * *", true),
+        ]));
+        assert_eq!(issues.len(), 2, "{:?}", issues);
+        assert!(issues[0].starts_with("tasks.hash_verification.schedule"));
+        assert!(issues[1].starts_with("tasks.vacuum: unknown task"));
+    }
+
+    #[tokio::test]
+    async fn reports_schedule_and_last_run() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let jobs = JobQueue::standard(store.clone(), Default::default());
+        let scheduler = Scheduler::new(
+            jobs.clone(),
+            store,
+            &tasks(&[
+                ("hash_verification", "0 4 * * *", true),
+                ("snapshot", "0 3 * * *", false),
+            ]),
+            PathBuf::from("retention.json"),
+        );
+        assert!(scheduler.is_enabled("hash_verification"));
+        assert!(!scheduler.is_enabled("snapshot"));
+
+        let before = scheduler.status().await.unwrap();
+        let hash = before
+            .iter()
+            .find(|t| t.name == "hash_verification")
+            .unwrap();
+        assert!(hash.next_run_at.as_deref().unwrap().contains("T04:00:00"));
+        assert!(hash.last_run.is_none());
+        let snapshot = before.iter().find(|t| t.name == "snapshot").unwrap();
+        assert!(snapshot.next_run_at.is_none());
+
+        scheduler.run_now("hash_verification").await.unwrap();
+        while jobs.run_next().await.unwrap() {}
+        let after = scheduler.status().await.unwrap();
+        let hash = after
+            .iter()
+            .find(|t| t.name == "hash_verification")
+            .unwrap();
+        let last_run = hash.last_run.as_ref().unwrap();
+        assert_eq!(last_run.status, JobStatus::Completed);
+        assert_eq!(
+            last_run.result,
+            Some(serde_json::json!({ "checked": 0, "mismatched": [] }))
+        );
+    }
+}
@@ -0,0 +1,468 @@
+//! SCIM 2.0 (RFC 7643 / 7644) provisioning: the identity provider creates, updates and
+//! removes actors (`Users`) and `Groups` at `/scim/v2` (see `crate::api::scim`), and the
+//! server keeps them in the store's actor directory (`store::directory`).
+//!
+//! This module maps between SCIM resources and directory records: resource JSON, the
+//! `eq` filters IdPs use to look users up, and PATCH operations. Only the core User and
+//! Group schemas are supported; unknown attributes are ignored.
+//!
+//! With `scim.group_roles` configured, the directory feeds RBAC: a provisioned actor's
+//! roles are those of its mapped groups (Reader when none), whatever its token says.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::auth::Role;
+use crate::store::{Directory, DirectoryGroup, DirectoryUser};
+
+pub const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+pub const LIST_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const PATCH_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+pub const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+
+/// Path prefix of the SCIM endpoints.
+pub const BASE_PATH: &str = "/scim/v2";
+
+/// `scim` in config.json.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScimConfig {
+    /// Group displayName → role granted to its members. When set, provisioned actors get
+    /// exactly the roles of their groups.
+    #[serde(default)]
+    pub group_roles: BTreeMap<String, Role>,
+}
+
+impl ScimConfig {
+    /// Roles for an actor in `groups`; None when the directory does not decide roles.
+    pub fn roles_for(&self, groups: &[String]) -> Option<Vec<Role>> {
+        if self.group_roles.is_empty() {
+            return None;
+        }
+        let mut roles: Vec<Role> = groups
+            .iter()
+            .filter_map(|name| {
+                self.group_roles
+                    .iter()
+                    .find(|(group, _)| group.eq_ignore_ascii_case(name))
+                    .map(|(_, role)| *role)
+            })
+            .collect();
+        roles.dedup();
+        if roles.is_empty() {
+            roles.push(Role::Reader);
+        }
+        Some(roles)
+    }
+}
+
+/// A user as a SCIM resource, with its groups.
+pub fn user_resource(user: &DirectoryUser, directory: &Directory) -> Value {
+    let groups: Vec<Value> = directory
+        .groups
+        .values()
+        .filter(|g| g.members.contains(&user.id))
+        .map(|g| {
+            json!({
+                "value": g.id,
+                "display": g.display_name,
+                "$ref": format!("{}/Groups/{}", BASE_PATH, g.id),
+            })
+        })
+        .collect();
+    let mut resource = json!({
+        "schemas": [USER_SCHEMA],
+        "id": user.id,
+        "userName": user.user_name,
+        "active": user.active,
+        "emails": user.emails.iter().enumerate().map(|(i, e)| json!({
+            "value": e, "primary": i == 0,
+        })).collect::<Vec<_>>(),
+        "groups": groups,
+        "meta": meta("User", &user.id, &user.created, &user.last_modified),
+    });
+    if let Some(external_id) = &user.external_id {
+        resource["externalId"] = json!(external_id);
+    }
+    if let Some(display_name) = &user.display_name {
+        resource["displayName"] = json!(display_name);
+    }
+    resource
+}
+
+/// A group as a SCIM resource; member display names come from `directory`.
+pub fn group_resource(group: &DirectoryGroup, directory: &Directory) -> Value {
+    let members: Vec<Value> = group
+        .members
+        .iter()
+        .map(|id| {
+            json!({
+                "value": id,
+                "display": directory.users.get(id).map(|u| u.user_name.clone()),
+                "$ref": format!("{}/Users/{}", BASE_PATH, id),
+            })
+        })
+        .collect();
+    let mut resource = json!({
+        "schemas": [GROUP_SCHEMA],
+        "id": group.id,
+        "displayName": group.display_name,
+        "members": members,
+        "meta": meta("Group", &group.id, &group.created, &group.last_modified),
+    });
+    if let Some(external_id) = &group.external_id {
+        resource["externalId"] = json!(external_id);
+    }
+    resource
+}
+
+fn meta(kind: &str, id: &str, created: &str, last_modified: &str) -> Value {
+    json!({
+        "resourceType": kind,
+        "created": created,
+        "lastModified": last_modified,
+        "location": format!("{}/{}s/{}", BASE_PATH, kind, id),
+        "version": format!("W/\"{}\"", last_modified),
+    })
+}
+
+/// A `ListResponse` page.
+pub fn list_response(resources: Vec<Value>, total: usize, start_index: usize) -> Value {
+    json!({
+        "schemas": [LIST_SCHEMA],
+        "totalResults": total,
+        "startIndex": start_index,
+        "itemsPerPage": resources.len(),
+        "Resources": resources,
+    })
+}
+
+/// Attribute `name` of a resource body, matched case-insensitively as SCIM requires.
+fn attr<'a>(body: &'a Value, name: &str) -> Option<&'a Value> {
+    body.as_object()?
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v)
+}
+
+fn string_attr(body: &Value, name: &str) -> Option<String> {
+    attr(body, name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Booleans arrive as JSON booleans or, from some IdPs, as `"True"` / `"False"`.
+fn bool_value(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) if s.eq_ignore_ascii_case("true") => Some(true),
+        Value::String(s) if s.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    }
+}
+
+/// Apply a User resource body (POST / PUT) to `user`: `userName` is required, `active`
+/// defaults to true.
+pub fn apply_user(user: &mut DirectoryUser, body: &Value) -> Result<(), String> {
+    user.user_name = string_attr(body, "userName").ok_or("userName is required")?;
+    user.external_id = string_attr(body, "externalId");
+    user.display_name = string_attr(body, "displayName").or_else(|| {
+        let name = attr(body, "name")?;
+        string_attr(name, "formatted").or_else(|| {
+            let parts: Vec<String> = ["givenName", "familyName"]
+                .iter()
+                .filter_map(|p| string_attr(name, p))
+                .collect();
+            Some(parts.join(" ")).filter(|n| !n.is_empty())
+        })
+    });
+    user.emails = attr(body, "emails")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| string_attr(e, "value"))
+        .collect();
+    user.active = match attr(body, "active") {
+        None | Some(Value::Null) => true,
+        Some(v) => bool_value(v).ok_or("active must be a boolean")?,
+    };
+    Ok(())
+}
+
+/// Apply a Group resource body (POST / PUT) to `group`.
+pub fn apply_group(group: &mut DirectoryGroup, body: &Value) -> Result<(), String> {
+    group.display_name = string_attr(body, "displayName").ok_or("displayName is required")?;
+    group.external_id = string_attr(body, "externalId");
+    group.members = attr(body, "members")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|m| string_attr(m, "value"))
+        .collect();
+    Ok(())
+}
+
+/// A `filter` query: `<attribute> eq "<value>"`, the form IdPs use to find existing
+/// resources. Other operators are refused (`invalidFilter`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub attribute: String,
+    pub value: String,
+}
+
+impl Filter {
+    pub fn parse(filter: &str) -> Result<Self, String> {
+        let mut parts = filter.trim().splitn(3, char::is_whitespace);
+        let (Some(attribute), Some(op), Some(value)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!("unsupported filter '{}'", filter));
+        };
+        let value = value.trim();
+        if !op.eq_ignore_ascii_case("eq") || !value.starts_with('"') || !value.ends_with('"') {
+            return Err(format!(
+                "unsupported filter '{}': only attribute eq \"value\"",
+                filter
+            ));
+        }
+        Ok(Self {
+            attribute: attribute.to_string(),
+            value: value[1..value.len().saturating_sub(1).max(1)].to_string(),
+        })
+    }
+
+    /// Whether a resource matches; string comparison is case-insensitive except for ids.
+    pub fn matches(&self, resource: &Value) -> bool {
+        let mut current = resource;
+        for segment in self.attribute.split('.') {
+            match attr(current, segment) {
+                Some(v) => current = v,
+                None => return false,
+            }
+        }
+        match current {
+            Value::String(s) if self.attribute.eq_ignore_ascii_case("id") => *s == self.value,
+            Value::String(s) => s.eq_ignore_ascii_case(&self.value),
+            Value::Bool(b) => bool_value(&json!(self.value)) == Some(*b),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PatchRequest {
+    #[serde(rename = "Operations", alias = "operations")]
+    operations: Vec<PatchOperation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PatchOperation {
+    op: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    value: Option<Value>,
+}
+
+/// Apply a `PatchOp` request to a resource's JSON; the caller parses the result back
+/// with [`apply_user`] / [`apply_group`]. Supports attribute paths (`active`,
+/// `name.formatted`), path-less objects, and `members[value eq "…"]` removals.
+pub fn apply_patch(resource: &mut Value, body: &Value) -> Result<(), String> {
+    let request: PatchRequest =
+        serde_json::from_value(body.clone()).map_err(|e| format!("invalid PatchOp: {}", e))?;
+    for operation in request.operations {
+        let op = operation.op.to_ascii_lowercase();
+        let object = resource
+            .as_object_mut()
+            .ok_or("resource is not an object")?;
+        match (op.as_str(), operation.path.as_deref()) {
+            ("add" | "replace", None) => {
+                let Some(Value::Object(values)) = operation.value else {
+                    return Err(format!("{} without a path needs an object value", op));
+                };
+                for (name, value) in values {
+                    set(object, &name, value, op == "add");
+                }
+            }
+            ("add" | "replace", Some(path)) => {
+                let value = operation
+                    .value
+                    .ok_or_else(|| format!("{} {} needs a value", op, path))?;
+                match path.split_once('.') {
+                    Some((parent, child)) if !path.contains('[') => {
+                        let key = key(object, parent);
+                        let entry = object
+                            .entry(key)
+                            .or_insert_with(|| Value::Object(Map::new()));
+                        if let Some(inner) = entry.as_object_mut() {
+                            set(inner, child, value, op == "add");
+                        }
+                    }
+                    _ => set(object, path, value, op == "add"),
+                }
+            }
+            ("remove", Some(path)) => {
+                if let Some((name, filter)) = path.split_once('[') {
+                    let filter = Filter::parse(filter.trim_end_matches(']'))?;
+                    let key = key(object, name);
+                    if let Some(Value::Array(items)) = object.get_mut(&key) {
+                        items.retain(|item| !filter.matches(item));
+                    }
+                } else if let Some(Value::Array(remove)) = operation.value {
+                    // `remove members` with a list of values removes just those.
+                    let key = key(object, path);
+                    let values: Vec<Option<&str>> =
+                        remove.iter().map(|v| v["value"].as_str()).collect();
+                    if let Some(Value::Array(items)) = object.get_mut(&key) {
+                        items.retain(|item| !values.contains(&item["value"].as_str()));
+                    }
+                } else {
+                    let key = key(object, path);
+                    object.remove(&key);
+                }
+            }
+            ("remove", None) => return Err("remove needs a path".to_string()),
+            (other, _) => return Err(format!("unsupported op '{}'", other)),
+        }
+    }
+    Ok(())
+}
+
+/// The existing key matching `name` case-insensitively, else `name`.
+fn key(object: &Map<String, Value>, name: &str) -> String {
+    object
+        .keys()
+        .find(|k| k.eq_ignore_ascii_case(name))
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Set an attribute; `add` appends to multi-valued attributes instead of replacing them.
+fn set(object: &mut Map<String, Value>, name: &str, value: Value, add: bool) {
+    let key = key(object, name);
+    match (object.get_mut(&key), value) {
+        (Some(Value::Array(items)), Value::Array(new)) if add => {
+            for item in new {
+                if !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+        }
+        (_, value) => {
+            object.insert(key, value);
+        }
+    }
+}
+
+/// `GET /scim/v2/ServiceProviderConfig`: what this server supports.
+pub fn service_provider_config() -> Value {
+    json!({
+        "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig"],
+        "patch": { "supported": true },
+        "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+        "filter": { "supported": true, "maxResults": 200 },
+        "changePassword": { "supported": false },
+        "sort": { "supported": false },
+        "etag": { "supported": false },
+        "authenticationSchemes": [{
+            "type": "oauthbearertoken",
+            "name": "OAuth Bearer Token",
+            "description": "An Admin JWT in the Authorization header",
+        }],
+    })
+}
+
+/// `GET /scim/v2/ResourceTypes`.
+pub fn resource_types() -> Value {
+    let types = vec![
+        json!({ "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ResourceType"],
+                "id": "User", "name": "User", "endpoint": "/Users", "schema": USER_SCHEMA }),
+        json!({ "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ResourceType"],
+                "id": "Group", "name": "Group", "endpoint": "/Groups", "schema": GROUP_SCHEMA }),
+    ];
+    list_response(types, 2, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alice() -> DirectoryUser {
+        let mut user = DirectoryUser {
+            id: "u1".to_string(),
+            user_name: String::new(),
+            external_id: None,
+            display_name: None,
+            emails: Vec::new(),
+            active: true,
+            created: "t".to_string(),
+            last_modified: "t".to_string(),
+        };
+        apply_user(
+            &mut user,
+            &json!({
+                "schemas": [USER_SCHEMA], "UserName": "alice@example.com",
+                "name": { "givenName": "Alice", "familyName": "Liddell" },
+                "emails": [{ "value": "alice@example.com", "primary": true }],
+            }),
+        )
+        .unwrap();
+        user
+    }
+
+    #[test]
+    fn patches_and_filters_follow_idp_conventions() {
+        let directory = Directory::default();
+        let mut user = alice();
+        assert_eq!(user.display_name.as_deref(), Some("Alice Liddell"));
+        let mut resource = user_resource(&user, &directory);
+        assert!(Filter::parse(r#"userName eq "ALICE@example.com""#)
+            .unwrap()
+            .matches(&resource));
+        assert!(Filter::parse("userName sw \"a\"").is_err());
+
+        apply_patch(
+            &mut resource,
+            &json!({ "schemas": [PATCH_SCHEMA], "Operations": [
+                { "op": "Replace", "path": "active", "value": "False" },
+                { "op": "replace", "path": "name.formatted", "value": "A. Liddell" },
+            ]}),
+        )
+        .unwrap();
+        apply_user(&mut user, &resource).unwrap();
+        assert!(!user.active);
+        // `displayName` from the resource wins over `name`.
+        assert_eq!(user.display_name.as_deref(), Some("Alice Liddell"));
+
+        let mut group = json!({ "displayName": "reviewers",
+                                "members": [{ "value": "u1" }, { "value": "u2" }] });
+        apply_patch(
+            &mut group,
+            &json!({ "Operations": [
+                { "op": "remove", "path": "members[value eq \"u1\"]" },
+                { "op": "add", "path": "members", "value": [{ "value": "u3" }] },
+            ]}),
+        )
+        .unwrap();
+        assert_eq!(
+            group["members"],
+            json!([{ "value": "u2" }, { "value": "u3" }])
+        );
+    }
+
+    #[test]
+    fn group_roles_decide_only_when_configured() {
+        assert_eq!(ScimConfig::default().roles_for(&["x".to_string()]), None);
+        let config = ScimConfig {
+            group_roles: [("TruthLayer Reviewers".to_string(), Role::Reviewer)].into(),
+        };
+        assert_eq!(
+            config.roles_for(&["truthlayer reviewers".to_string()]),
+            Some(vec![Role::Reviewer])
+        );
+        assert_eq!(config.roles_for(&[]), Some(vec![Role::Reader]));
+    }
+}
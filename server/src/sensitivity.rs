@@ -4,21 +4,16 @@
 use serde::{Deserialize, Serialize};
 
 /// Sensitivity level for content classification (ordered low→high).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Sensitivity {
     Public,
+    #[default]
     Internal,
     Confidential,
     Restricted,
 }
 
-impl Default for Sensitivity {
-    fn default() -> Self {
-        Sensitivity::Internal
-    }
-}
-
 impl Sensitivity {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -0,0 +1,455 @@
+//! Slack integration: review requests posted to a channel with Approve / Reject buttons,
+//! and a `/truthlayer` slash command, both answered at `POST /webhooks/slack/*`.
+//!
+//! Slack calls back as the app, not as a TruthLayer actor, so the app is bound to a
+//! service identity (`service_actor`) and each Slack user who may review is mapped to an
+//! actor (`identities`). A button press or command becomes a review by the mapped actor,
+//! with the roles `roles` gives it (or its directory groups, which take precedence), and
+//! every callback — mapped, unmapped or failed — is audited as `slack_interaction`
+//! by the service identity with the Slack user, team and mapped actor in the details.
+//! Requests are authenticated by Slack's signing secret (`X-Slack-Signature`), not a JWT.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::auth::Role;
+use crate::jobs::JobHandler;
+use crate::store::ContextStore;
+use crate::types::{JobRecord, Proposal, ReviewAction};
+
+/// Job kind that posts a message (`chat.postMessage` or an interaction's `response_url`).
+pub const MESSAGE_JOB: &str = "slack_message";
+
+/// Requests signed longer ago than this are refused (replay protection, as Slack advises).
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+/// Button action ids; the button's value is the proposal id.
+pub const APPROVE_ACTION: &str = "truthlayer_approve";
+pub const REJECT_ACTION: &str = "truthlayer_reject";
+
+/// `slack` in config.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// Web API base URL. Default: `https://slack.com/api`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// Environment variable holding the bot token (`xoxb-…`) used to post messages.
+    pub bot_token_env: String,
+    /// Environment variable holding the app's signing secret.
+    pub signing_secret_env: String,
+    /// Channel id that review requests are posted to.
+    pub channel: String,
+    /// Actor id the app acts as in the audit log. Default: `slack`.
+    #[serde(default = "default_service_actor")]
+    pub service_actor: String,
+    /// Slack user id (`U…`) → actor id; actions by unmapped users are refused.
+    #[serde(default)]
+    pub identities: BTreeMap<String, String>,
+    /// Mapped actor id → roles its Slack reviews carry; an actor without an entry has
+    /// none. The roles of its SCIM groups (`scim.group_roles`) take precedence.
+    #[serde(default)]
+    pub roles: BTreeMap<String, Vec<Role>>,
+}
+
+fn default_service_actor() -> String {
+    "slack".to_string()
+}
+
+impl SlackConfig {
+    /// Problems in a `slack` config.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for (field, value) in [
+            ("bot_token_env", &self.bot_token_env),
+            ("signing_secret_env", &self.signing_secret_env),
+        ] {
+            if value.trim().is_empty() {
+                issues.push(format!(
+                    "slack.{}: must name an environment variable",
+                    field
+                ));
+            }
+        }
+        if self.channel.trim().is_empty() {
+            issues.push("slack.channel: must not be empty".to_string());
+        }
+        if self.service_actor.trim().is_empty() {
+            issues.push("slack.service_actor: must not be empty".to_string());
+        }
+        if self.identities.values().any(|a| a == &self.service_actor) {
+            issues
+                .push("slack.identities: users cannot be mapped to the service actor".to_string());
+        }
+        for actor in self.roles.keys() {
+            if !self.identities.values().any(|a| a == actor) {
+                issues.push(format!(
+                    "slack.roles.{}: no Slack user is mapped to this actor",
+                    actor
+                ));
+            }
+        }
+        issues
+    }
+
+    pub fn api_url(&self) -> String {
+        self.api_url
+            .as_deref()
+            .unwrap_or("https://slack.com/api")
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Job payload posting a review request for `proposal` to the channel. Carries the
+    /// token's variable name, not the token.
+    pub fn review_request_job(&self, proposal: &Proposal) -> serde_json::Value {
+        let mut message = review_message(proposal);
+        message["channel"] = serde_json::json!(self.channel);
+        serde_json::json!({
+            "url": format!("{}/chat.postMessage", self.api_url()),
+            "tokenEnv": self.bot_token_env,
+            "message": message,
+        })
+    }
+
+    /// Job payload alerting the channel (`@channel`) to an emergency apply.
+    pub fn emergency_job(
+        &self,
+        proposal_id: &str,
+        applied_by: &str,
+        justification: &str,
+        follow_up: &str,
+    ) -> serde_json::Value {
+        let text = format!(
+            "<!channel> Emergency change: *`{}`* was applied by {} without the usual review and change window checks.\n>{}\nPost-hoc review: *`{}`*",
+            proposal_id,
+            applied_by,
+            justification.replace('\n', " "),
+            follow_up
+        );
+        serde_json::json!({
+            "url": format!("{}/chat.postMessage", self.api_url()),
+            "tokenEnv": self.bot_token_env,
+            "message": {
+                "channel": self.channel,
+                "text": format!("Emergency change {} applied", proposal_id),
+                "blocks": [{ "type": "section", "text": { "type": "mrkdwn", "text": text } }],
+            },
+        })
+    }
+}
+
+/// Whether a request carries a valid signature from the app's signing secret: the
+/// `v0=` HMAC-SHA256 of `v0:<timestamp>:<body>`, signed within the tolerance of `now`.
+pub fn verify(headers: &HeaderMap, body: &[u8], secret: &str, now: i64) -> bool {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let Some(timestamp) = header("x-slack-request-timestamp") else {
+        return false;
+    };
+    if !timestamp
+        .parse::<i64>()
+        .is_ok_and(|t| (now - t).abs() <= SIGNATURE_TOLERANCE_SECS)
+    {
+        return false;
+    }
+    let Some(signature) = header("x-slack-signature")
+        .and_then(|v| v.strip_prefix("v0="))
+        .and_then(crate::forge::decode_hex)
+    else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("v0:{}:", timestamp).as_bytes());
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// One line describing a proposal: id, author, rationale.
+fn describe(proposal: &Proposal) -> String {
+    let mut line = format!(
+        "*`{}`* by {} ({} operation{})",
+        proposal.id,
+        proposal.metadata.created_by,
+        proposal.operations.len(),
+        if proposal.operations.len() == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+    if let Some(rationale) = proposal.metadata.rationale.as_deref() {
+        let rationale: String = rationale.replace('\n', " ").chars().take(200).collect();
+        write!(line, "\n>{}", rationale).ok();
+    }
+    line
+}
+
+fn buttons(proposal_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "type": "actions",
+        "elements": [
+            { "type": "button", "action_id": APPROVE_ACTION, "style": "primary",
+              "text": { "type": "plain_text", "text": "Approve" }, "value": proposal_id },
+            { "type": "button", "action_id": REJECT_ACTION, "style": "danger",
+              "text": { "type": "plain_text", "text": "Reject" }, "value": proposal_id },
+        ]
+    })
+}
+
+/// Block Kit message asking for a review of `proposal`.
+pub fn review_message(proposal: &Proposal) -> serde_json::Value {
+    serde_json::json!({
+        "text": format!("Proposal {} is waiting for review", proposal.id),
+        "blocks": [
+            { "type": "section",
+              "text": { "type": "mrkdwn", "text": format!("Review requested: {}", describe(proposal)) } },
+            buttons(&proposal.id),
+        ]
+    })
+}
+
+/// Ephemeral reply to `/truthlayer pending`: each open proposal with its buttons.
+pub fn pending_message(proposals: &[Proposal], total: usize) -> serde_json::Value {
+    if proposals.is_empty() {
+        return ephemeral("No proposals are waiting for review.");
+    }
+    let mut blocks = vec![serde_json::json!({
+        "type": "section",
+        "text": { "type": "mrkdwn",
+                  "text": format!("{} proposal(s) waiting for review:", total) }
+    })];
+    for proposal in proposals {
+        blocks.push(serde_json::json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": describe(proposal) }
+        }));
+        blocks.push(buttons(&proposal.id));
+    }
+    serde_json::json!({
+        "response_type": "ephemeral",
+        "text": format!("{} proposal(s) waiting for review", total),
+        "blocks": blocks,
+    })
+}
+
+/// A plain message only the caller sees.
+pub fn ephemeral(text: &str) -> serde_json::Value {
+    serde_json::json!({ "response_type": "ephemeral", "text": text })
+}
+
+/// Who acted in Slack, and where to answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlackUser {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub team_id: Option<String>,
+    pub response_url: Option<String>,
+}
+
+/// What a callback asks for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlackRequest {
+    /// Review a proposal (button or `approve` / `reject` command).
+    Review {
+        proposal_id: String,
+        action: ReviewAction,
+        comment: Option<String>,
+    },
+    /// List the open proposals (`/truthlayer` or `/truthlayer pending`).
+    Pending,
+    /// Anything else: answer with usage.
+    Help,
+}
+
+pub const USAGE: &str = "Usage: `/truthlayer pending`, `/truthlayer approve <proposal-id> [comment]`, `/truthlayer reject <proposal-id> [comment]`";
+
+/// Parse an interactivity callback (`payload=<json>` form); only `block_actions` from
+/// our buttons are requests.
+pub fn parse_interaction(body: &[u8]) -> Result<(SlackUser, SlackRequest), String> {
+    let form: BTreeMap<String, String> =
+        serde_urlencoded::from_bytes(body).map_err(|e| format!("invalid form body: {}", e))?;
+    let payload: serde_json::Value = serde_json::from_str(
+        form.get("payload")
+            .ok_or("interaction has no payload field")?,
+    )
+    .map_err(|e| format!("invalid payload JSON: {}", e))?;
+    let str_at = |pointer: &str| payload.pointer(pointer).and_then(|v| v.as_str());
+    let user = SlackUser {
+        user_id: str_at("/user/id")
+            .ok_or("payload has no /user/id")?
+            .to_string(),
+        user_name: str_at("/user/username").map(str::to_string),
+        team_id: str_at("/team/id").map(str::to_string),
+        response_url: str_at("/response_url").map(str::to_string),
+    };
+    if str_at("/type") != Some("block_actions") {
+        return Ok((user, SlackRequest::Help));
+    }
+    let request = match (str_at("/actions/0/action_id"), str_at("/actions/0/value")) {
+        (Some(APPROVE_ACTION), Some(id)) => SlackRequest::Review {
+            proposal_id: id.to_string(),
+            action: ReviewAction::Accept,
+            comment: None,
+        },
+        (Some(REJECT_ACTION), Some(id)) => SlackRequest::Review {
+            proposal_id: id.to_string(),
+            action: ReviewAction::Reject,
+            comment: None,
+        },
+        _ => SlackRequest::Help,
+    };
+    Ok((user, request))
+}
+
+/// Parse a slash command (form fields `user_id`, `text`, …).
+pub fn parse_command(body: &[u8]) -> Result<(SlackUser, SlackRequest), String> {
+    let form: BTreeMap<String, String> =
+        serde_urlencoded::from_bytes(body).map_err(|e| format!("invalid form body: {}", e))?;
+    let user = SlackUser {
+        user_id: form
+            .get("user_id")
+            .cloned()
+            .ok_or("command has no user_id")?,
+        user_name: form.get("user_name").cloned(),
+        team_id: form.get("team_id").cloned(),
+        response_url: form.get("response_url").cloned(),
+    };
+    let text = form.get("text").map(String::as_str).unwrap_or_default();
+    let mut words = text.split_whitespace();
+    let request = match (words.next(), words.next()) {
+        (None | Some("pending"), None) => SlackRequest::Pending,
+        (Some(verb @ ("approve" | "reject")), Some(id)) => {
+            let comment = words.collect::<Vec<_>>().join(" ");
+            SlackRequest::Review {
+                proposal_id: id.to_string(),
+                action: if verb == "approve" {
+                    ReviewAction::Accept
+                } else {
+                    ReviewAction::Reject
+                },
+                comment: Some(comment).filter(|c| !c.is_empty()),
+            }
+        }
+        _ => SlackRequest::Help,
+    };
+    Ok((user, request))
+}
+
+/// Posts Slack messages (`slack_message` jobs): to the Web API with the bot token, or to
+/// an interaction's `response_url` without one.
+pub struct SlackMessageHandler;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageJob {
+    url: String,
+    #[serde(default)]
+    token_env: Option<String>,
+    message: serde_json::Value,
+}
+
+#[async_trait::async_trait]
+impl JobHandler for SlackMessageHandler {
+    fn kind(&self) -> &'static str {
+        MESSAGE_JOB
+    }
+
+    async fn run(
+        &self,
+        _store: std::sync::Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let message: MessageJob = serde_json::from_value(job.payload.clone())
+            .map_err(|e| format!("invalid payload: {}", e))?;
+        let mut headers = Vec::new();
+        if let Some(env) = &message.token_env {
+            let token = std::env::var(env).map_err(|_| format!("{} is not set", env))?;
+            headers.push(("authorization", format!("Bearer {}", token)));
+        }
+        let (status, body) =
+            crate::outbound::post_json(&message.url, &headers, &message.message).await?;
+        if !status.is_success() {
+            return Err(format!("POST {}: {}", message.url, status));
+        }
+        // The Web API answers 200 with `{ "ok": false, "error": … }` on failure.
+        if message.token_env.is_some() {
+            let reply: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+            if reply["ok"] != true {
+                return Err(format!(
+                    "POST {}: {}",
+                    message.url,
+                    reply["error"].as_str().unwrap_or("unexpected response")
+                ));
+            }
+        }
+        Ok(Some(serde_json::json!({ "url": message.url })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_are_checked_with_a_replay_window() {
+        let body = b"token=x&team_id=T1&user_id=U1&text=pending";
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(b"v0:1700000000:");
+        mac.update(body);
+        let signature: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-slack-request-timestamp", "1700000000".parse().unwrap());
+        headers.insert(
+            "x-slack-signature",
+            format!("v0={}", signature).parse().unwrap(),
+        );
+        assert!(verify(&headers, body, "s3cret", 1_700_000_100));
+        assert!(!verify(&headers, body, "other", 1_700_000_100));
+        assert!(!verify(&headers, body, "s3cret", 1_700_001_000));
+        assert!(!verify(&headers, b"tampered", "s3cret", 1_700_000_100));
+    }
+
+    #[test]
+    fn commands_and_buttons_parse_to_requests() {
+        let (user, request) =
+            parse_command(b"user_id=U1&team_id=T1&text=reject+p-1+needs+a+source").unwrap();
+        assert_eq!(user.user_id, "U1");
+        assert_eq!(
+            request,
+            SlackRequest::Review {
+                proposal_id: "p-1".to_string(),
+                action: ReviewAction::Reject,
+                comment: Some("needs a source".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_command(b"user_id=U1&text=").unwrap().1,
+            SlackRequest::Pending
+        );
+
+        let payload = serde_json::json!({
+            "type": "block_actions", "user": { "id": "U2", "username": "bob" },
+            "team": { "id": "T1" }, "response_url": "https://hooks.slack.test/r",
+            "actions": [{ "action_id": APPROVE_ACTION, "value": "p-2" }]
+        });
+        let form = serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap();
+        let (user, request) = parse_interaction(form.as_bytes()).unwrap();
+        assert_eq!(user.user_name.as_deref(), Some("bob"));
+        assert!(matches!(
+            request,
+            SlackRequest::Review { action: ReviewAction::Accept, ref proposal_id, .. }
+                if proposal_id == "p-2"
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+//! Indexed audit log for `InMemoryStore`.
+//!
+//! Events keep their append order and get a sequence number. Indexes by actor, by
+//! resource and by hour (timestamp prefix `YYYY-MM-DDTHH`) hold sequence numbers in
+//! ascending order, so a filtered query only visits candidate events instead of the
+//! whole log. Dropping the oldest events (audit spill) trims the index prefixes.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ops::Bound;
+
+use crate::types::AuditEvent;
+
+/// Audit query filters. Timestamps compare as strings (RFC 3339), bounds inclusive.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct AuditFilter<'a> {
+    pub actor: Option<&'a str>,
+    pub action: Option<&'a str>,
+    pub resource_id: Option<&'a str>,
+    pub from: Option<&'a str>,
+    pub to: Option<&'a str>,
+}
+
+impl AuditFilter<'_> {
+    pub fn matches(&self, e: &AuditEvent) -> bool {
+        if self.actor.is_some_and(|a| e.actor_id != a) {
+            return false;
+        }
+        if self.resource_id.is_some_and(|rid| e.resource_id != rid) {
+            return false;
+        }
+        if self.from.is_some_and(|f| e.timestamp.as_str() < f) {
+            return false;
+        }
+        if self.to.is_some_and(|t| e.timestamp.as_str() > t) {
+            return false;
+        }
+        if let Some(act) = self.action {
+            let action_str = serde_json::to_string(&e.action)
+                .unwrap_or_default()
+                .replace('"', "");
+            if action_str != act {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Hour bucket of a timestamp (`YYYY-MM-DDTHH`).
+fn hour(timestamp: &str) -> &str {
+    timestamp.get(..13).unwrap_or(timestamp)
+}
+
+#[derive(Default)]
+pub(crate) struct AuditLog {
+    events: VecDeque<AuditEvent>,
+    /// Sequence number of `events[0]`.
+    first_seq: u64,
+    by_actor: HashMap<String, Vec<u64>>,
+    by_resource: HashMap<String, Vec<u64>>,
+    by_hour: BTreeMap<String, Vec<u64>>,
+}
+
+impl AuditLog {
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// All events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &AuditEvent> {
+        self.events.iter()
+    }
+
+    pub fn push(&mut self, event: AuditEvent) {
+        let seq = self.first_seq + self.events.len() as u64;
+        self.by_actor
+            .entry(event.actor_id.clone())
+            .or_default()
+            .push(seq);
+        self.by_resource
+            .entry(event.resource_id.clone())
+            .or_default()
+            .push(seq);
+        self.by_hour
+            .entry(hour(&event.timestamp).to_string())
+            .or_default()
+            .push(seq);
+        self.events.push_back(event);
+    }
+
+    /// Drop the `count` oldest events.
+    pub fn drop_oldest(&mut self, count: usize) {
+        let count = count.min(self.events.len());
+        self.events.drain(..count);
+        self.first_seq += count as u64;
+        let first = self.first_seq;
+        let trim = |seqs: &mut Vec<u64>| {
+            let cut = seqs.partition_point(|&s| s < first);
+            seqs.drain(..cut);
+            !seqs.is_empty()
+        };
+        self.by_actor.retain(|_, seqs| trim(seqs));
+        self.by_resource.retain(|_, seqs| trim(seqs));
+        self.by_hour.retain(|_, seqs| trim(seqs));
+    }
+
+    /// Events matching `filter`, oldest first. Actor, resource and time range narrow the
+    /// candidates through the indexes; every candidate is then checked in full.
+    pub fn matching<'a>(
+        &'a self,
+        filter: &'a AuditFilter<'a>,
+    ) -> Box<dyn Iterator<Item = &'a AuditEvent> + 'a> {
+        let mut lists: Vec<Vec<u64>> = Vec::new();
+        if let Some(actor) = filter.actor {
+            lists.push(self.by_actor.get(actor).cloned().unwrap_or_default());
+        }
+        if let Some(rid) = filter.resource_id {
+            lists.push(self.by_resource.get(rid).cloned().unwrap_or_default());
+        }
+        if filter.from.is_some() || filter.to.is_some() {
+            let lower = match filter.from {
+                Some(f) => Bound::Included(hour(f).to_string()),
+                None => Bound::Unbounded,
+            };
+            let upper = match filter.to {
+                Some(t) => Bound::Included(t.to_string()),
+                None => Bound::Unbounded,
+            };
+            // Imported events can be out of time order, so merge and sort the buckets.
+            let mut seqs: Vec<u64> = self
+                .by_hour
+                .range((lower, upper))
+                .flat_map(|(_, seqs)| seqs.iter().copied())
+                .collect();
+            seqs.sort_unstable();
+            lists.push(seqs);
+        }
+
+        lists.sort_by_key(Vec::len);
+        let mut lists = lists.into_iter();
+        let Some(mut candidates) = lists.next() else {
+            return Box::new(self.events.iter().filter(move |e| filter.matches(e)));
+        };
+        for other in lists {
+            candidates.retain(|s| other.binary_search(s).is_ok());
+        }
+        Box::new(
+            candidates
+                .into_iter()
+                .filter_map(move |seq| self.events.get((seq - self.first_seq) as usize))
+                .filter(move |e| filter.matches(e)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AuditAction, AuditOutcome};
+
+    fn event(actor: &str, resource: &str, timestamp: &str) -> AuditEvent {
+        let mut e = AuditEvent::new(
+            actor,
+            "human",
+            AuditAction::ProposalCreated,
+            resource,
+            AuditOutcome::Success,
+        );
+        e.timestamp = timestamp.to_string();
+        e
+    }
+
+    #[test]
+    fn indexed_filters_match_a_full_scan() {
+        let mut log = AuditLog::default();
+        let times = [
+            "2026-03-01T09:15:00Z",
+            "2026-03-01T10:05:00Z",
+            "2026-03-02T08:00:00Z",
+            // Imported out of order.
+            "2026-02-28T23:59:00Z",
+            "2026-03-02T10:30:00Z",
+        ];
+        for (i, ts) in times.iter().enumerate() {
+            log.push(event(
+                &format!("actor-{}", i % 2),
+                &format!("p-{}", i % 3),
+                ts,
+            ));
+        }
+        log.drop_oldest(1);
+
+        let filters = [
+            AuditFilter {
+                actor: Some("actor-1"),
+                ..Default::default()
+            },
+            AuditFilter {
+                resource_id: Some("p-0"),
+                from: Some("2026-03-01"),
+                ..Default::default()
+            },
+            AuditFilter {
+                from: Some("2026-03-01T10:00:00Z"),
+                to: Some("2026-03-02T09:00:00Z"),
+                ..Default::default()
+            },
+            AuditFilter {
+                to: Some("2026-03-01"),
+                ..Default::default()
+            },
+            AuditFilter {
+                actor: Some("nobody"),
+                ..Default::default()
+            },
+        ];
+        for filter in &filters {
+            let indexed: Vec<&str> = log.matching(filter).map(|e| e.timestamp.as_str()).collect();
+            let scanned: Vec<&str> = log
+                .iter()
+                .filter(|e| filter.matches(e))
+                .map(|e| e.timestamp.as_str())
+                .collect();
+            assert_eq!(indexed, scanned, "{:?}", filter);
+        }
+        assert_eq!(log.matching(&filters[3]).count(), 1);
+    }
+}
@@ -0,0 +1,433 @@
+//! Grouped writes (`ContextStore::execute`): proposal mutations and the audit events that
+//! record them, applied all-or-nothing. Every operation is checked against the stored
+//! state (and the batch's earlier operations) before anything changes, so a refused
+//! operation leaves no partial write behind and no audit event for a write that did not
+//! happen. The writes and their audit events become visible together.
+//!
+//! The batch's audit events and server events are also stored as one
+//! [`OutboxEntry`](crate::store::OutboxEntry) in the same write, for the dispatcher
+//! (`crate::outbox`) to deliver.
+//!
+//! An apply ([`BatchOp::ApplyProposal`]) also writes the proposal's operations to the
+//! nodes, in the same write as its status change.
+//!
+//! The memory backend swaps the staged proposals in under its locks; the file backend
+//! also writes their files in one journaled commit (see `store::journal`).
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::events::ServerEvent;
+use crate::store::context_store::StoreError;
+use crate::store::lifecycle;
+use crate::store::outbox::OutboxEntry;
+use crate::types::{
+    AuditEvent, Complexity, Operation, Proposal, ProposalPatch, Review, TriageAction,
+};
+
+/// One write in a [`WriteBatch`].
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum BatchOp {
+    CreateProposal(Proposal),
+    UpdateProposal {
+        id: String,
+        patch: ProposalPatch,
+    },
+    /// `PATCH /proposals/:id/operations`: the open proposal's new operations.
+    EditOperations {
+        id: String,
+        operations: Vec<Operation>,
+        complexity: Complexity,
+    },
+    SubmitReview(Review),
+    /// An approval the review policies want more approvals beside: recorded, while the
+    /// proposal stays open.
+    SubmitPendingReview(Review),
+    WithdrawProposal(String),
+    /// `POST /proposals/auto-merge`: the open proposal `id` is replaced by `by`.
+    SupersedeProposal {
+        id: String,
+        by: String,
+    },
+    TriageProposal {
+        id: String,
+        action: TriageAction,
+    },
+    /// `POST /proposals/:id/apply`: the accepted proposal's operations are written to the
+    /// nodes and it becomes Applied; `unreviewed` (break-glass) also applies an open one.
+    /// Applying an applied proposal again does nothing.
+    ApplyProposal {
+        id: String,
+        applied_by: String,
+        unreviewed: bool,
+    },
+}
+
+/// Writes applied together, in order, with the audit events appended on success.
+#[derive(Debug, Clone, Default)]
+pub struct WriteBatch {
+    pub ops: Vec<BatchOp>,
+    pub audit: Vec<AuditEvent>,
+    /// Published by the outbox dispatcher once the writes are made.
+    pub events: Vec<ServerEvent>,
+}
+
+impl WriteBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn create_proposal(mut self, proposal: Proposal) -> Self {
+        self.ops.push(BatchOp::CreateProposal(proposal));
+        self
+    }
+
+    pub fn update_proposal(mut self, id: &str, patch: ProposalPatch) -> Self {
+        self.ops.push(BatchOp::UpdateProposal {
+            id: id.to_string(),
+            patch,
+        });
+        self
+    }
+
+    pub fn edit_operations(
+        mut self,
+        id: &str,
+        operations: Vec<Operation>,
+        complexity: Complexity,
+    ) -> Self {
+        self.ops.push(BatchOp::EditOperations {
+            id: id.to_string(),
+            operations,
+            complexity,
+        });
+        self
+    }
+
+    pub fn submit_review(mut self, review: Review) -> Self {
+        self.ops.push(BatchOp::SubmitReview(review));
+        self
+    }
+
+    pub fn submit_pending_review(mut self, review: Review) -> Self {
+        self.ops.push(BatchOp::SubmitPendingReview(review));
+        self
+    }
+
+    pub fn withdraw_proposal(mut self, id: &str) -> Self {
+        self.ops.push(BatchOp::WithdrawProposal(id.to_string()));
+        self
+    }
+
+    pub fn supersede_proposal(mut self, id: &str, by: &str) -> Self {
+        self.ops.push(BatchOp::SupersedeProposal {
+            id: id.to_string(),
+            by: by.to_string(),
+        });
+        self
+    }
+
+    pub fn triage_proposal(mut self, id: &str, action: TriageAction) -> Self {
+        self.ops.push(BatchOp::TriageProposal {
+            id: id.to_string(),
+            action,
+        });
+        self
+    }
+
+    pub fn apply_proposal(mut self, id: &str, applied_by: &str) -> Self {
+        self.ops.push(BatchOp::ApplyProposal {
+            id: id.to_string(),
+            applied_by: applied_by.to_string(),
+            unreviewed: false,
+        });
+        self
+    }
+
+    /// Break-glass [`WriteBatch::apply_proposal`]: an open proposal goes straight to
+    /// Applied, so a refused apply leaves it open rather than accepted without a review.
+    pub fn apply_unreviewed(mut self, id: &str, applied_by: &str) -> Self {
+        self.ops.push(BatchOp::ApplyProposal {
+            id: id.to_string(),
+            applied_by: applied_by.to_string(),
+            unreviewed: true,
+        });
+        self
+    }
+
+    /// Audit event appended once the writes are made.
+    pub fn audit(mut self, event: AuditEvent) -> Self {
+        self.audit.push(event);
+        self
+    }
+
+    /// Server event published once the writes are made.
+    pub fn publish(mut self, event: ServerEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Put the audit and server events that name no workspace in `workspace`.
+    pub fn in_workspace(mut self, workspace: &str) -> Self {
+        for event in self.audit.iter_mut().filter(|e| e.workspace_id.is_none()) {
+            event.workspace_id = Some(workspace.to_string());
+        }
+        for event in self.events.iter_mut().filter(|e| e.workspace_id.is_none()) {
+            event.workspace_id = Some(workspace.to_string());
+        }
+        self
+    }
+
+    /// The outbox entry recording this batch's deliveries; None when it has none.
+    pub(crate) fn outbox_entry(&self) -> Option<OutboxEntry> {
+        if self.events.is_empty() && self.audit.is_empty() {
+            return None;
+        }
+        Some(OutboxEntry::new(self.events.clone(), self.audit.clone()))
+    }
+}
+
+/// The proposals and review lists a batch changed, in their new state.
+#[derive(Debug, Default)]
+pub(crate) struct Staged {
+    pub proposals: BTreeMap<String, Proposal>,
+    pub reviews: BTreeMap<String, Vec<Review>>,
+    /// Proposals the batch creates.
+    pub created: usize,
+    /// Proposals to apply, with who applies them: the backend writes their operations to
+    /// the nodes and marks them applied (`lifecycle::mark_applied`).
+    pub applies: Vec<(String, String)>,
+}
+
+impl Staged {
+    fn proposal<'a>(
+        &'a mut self,
+        stored: &HashMap<String, Proposal>,
+        id: &str,
+    ) -> Result<&'a mut Proposal, StoreError> {
+        if !self.proposals.contains_key(id) {
+            let current = stored
+                .get(id)
+                .ok_or_else(|| StoreError::not_found(format!("proposal {}", id)))?;
+            self.proposals.insert(id.to_string(), current.clone());
+        }
+        Ok(self.proposals.get_mut(id).expect("staged above"))
+    }
+}
+
+/// Apply `ops` to copies of the proposals and reviews they touch. The stored maps are
+/// not changed; on error nothing is staged.
+pub(crate) fn stage(
+    ops: &[BatchOp],
+    proposals: &HashMap<String, Proposal>,
+    reviews: &HashMap<String, Vec<Review>>,
+) -> Result<Staged, StoreError> {
+    let mut staged = Staged::default();
+    for op in ops {
+        match op {
+            BatchOp::CreateProposal(proposal) => {
+                if proposals.contains_key(&proposal.id)
+                    || staged.proposals.contains_key(&proposal.id)
+                {
+                    return Err(StoreError::conflict(format!(
+                        "proposal {} already exists",
+                        proposal.id
+                    )));
+                }
+                staged
+                    .proposals
+                    .insert(proposal.id.clone(), proposal.clone());
+                staged.created += 1;
+            }
+            BatchOp::UpdateProposal { id, patch } => {
+                lifecycle::apply_update(staged.proposal(proposals, id)?, patch)?;
+            }
+            BatchOp::EditOperations {
+                id,
+                operations,
+                complexity,
+            } => {
+                lifecycle::edit_operations(
+                    staged.proposal(proposals, id)?,
+                    operations,
+                    complexity,
+                )?;
+            }
+            BatchOp::SubmitReview(review) | BatchOp::SubmitPendingReview(review) => {
+                let proposal = staged.proposal(proposals, &review.proposal_id)?;
+                if matches!(op, BatchOp::SubmitReview(_)) {
+                    lifecycle::apply_review(proposal, review)?;
+                } else {
+                    lifecycle::check_review(proposal, review)?;
+                }
+                staged
+                    .reviews
+                    .entry(review.proposal_id.clone())
+                    .or_insert_with(|| {
+                        reviews
+                            .get(&review.proposal_id)
+                            .cloned()
+                            .unwrap_or_default()
+                    })
+                    .push(review.clone());
+            }
+            BatchOp::WithdrawProposal(id) => {
+                lifecycle::withdraw(staged.proposal(proposals, id)?)?;
+            }
+            BatchOp::SupersedeProposal { id, by } => {
+                lifecycle::supersede(staged.proposal(proposals, id)?, by)?;
+            }
+            BatchOp::TriageProposal { id, action } => {
+                lifecycle::triage(staged.proposal(proposals, id)?, *action)?;
+            }
+            BatchOp::ApplyProposal {
+                id,
+                applied_by,
+                unreviewed,
+            } => {
+                if lifecycle::check_apply(staged.proposal(proposals, id)?, *unreviewed)? {
+                    staged.applies.push((id.clone(), applied_by.clone()));
+                }
+            }
+        }
+    }
+    Ok(staged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::{ContextStore, StoreErrorCode};
+    use crate::store::{FileStore, InMemoryStore};
+    use crate::types::{AuditAction, AuditOutcome, ProposalStatus};
+
+    fn proposal(id: &str) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "status": "open", "operations": []
+        }))
+        .unwrap()
+    }
+
+    fn created(id: &str) -> AuditEvent {
+        AuditEvent::new(
+            "alice",
+            "human",
+            AuditAction::ProposalCreated,
+            id,
+            AuditOutcome::Success,
+        )
+    }
+
+    #[tokio::test]
+    async fn a_refused_op_writes_nothing() {
+        let dir = std::env::temp_dir().join(format!("tl-batch-{}", uuid::Uuid::new_v4()));
+        let file = FileStore::new(&dir).unwrap();
+        let memory = InMemoryStore::new();
+        for store in [&file as &dyn ContextStore, &memory] {
+            store.create_proposal(proposal("p-1")).await.unwrap();
+
+            // The withdrawal after p-2's creation fails: neither is kept, nor audited.
+            let refused = WriteBatch::new()
+                .create_proposal(proposal("p-2"))
+                .withdraw_proposal("p-missing")
+                .audit(created("p-2"));
+            let err = store.execute(refused).await.unwrap_err();
+            assert_eq!(err.code, StoreErrorCode::NotFound);
+            assert!(store.get_proposal("p-2").await.unwrap().is_none());
+            let audit = store
+                .query_audit(None, None, Some("p-2"), None, None, None, None)
+                .await
+                .unwrap();
+            assert!(audit.events.is_empty());
+
+            let batch = WriteBatch::new()
+                .create_proposal(proposal("p-2"))
+                .withdraw_proposal("p-1")
+                .audit(created("p-2"));
+            store.execute(batch).await.unwrap();
+            assert!(store.get_proposal("p-2").await.unwrap().is_some());
+            let p1 = store.get_proposal("p-1").await.unwrap().unwrap();
+            assert_eq!(p1.status, ProposalStatus::Withdrawn);
+            let audit = store
+                .query_audit(None, None, Some("p-2"), None, None, None, None)
+                .await
+                .unwrap();
+            assert_eq!(audit.events.len(), 1);
+        }
+        drop(file);
+        let reopened = FileStore::new(&dir).unwrap();
+        let p1 = reopened.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(p1.status, ProposalStatus::Withdrawn);
+        assert!(reopened.get_proposal("p-2").await.unwrap().is_some());
+        drop(reopened);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn an_apply_commits_with_its_audit() {
+        let dir = std::env::temp_dir().join(format!("tl-batch-{}", uuid::Uuid::new_v4()));
+        let file = FileStore::new(&dir).unwrap();
+        let memory = InMemoryStore::new();
+        let open: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-1", "status": "open",
+            "operations": [{ "id": "op1", "order": 1, "type": "create", "node": {
+                "id": { "id": "goal-1" }, "type": "goal", "status": "accepted",
+                "content": "v1",
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z", "createdBy": "u",
+                    "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "u", "version": 0
+                }
+            }}],
+            "metadata": { "createdBy": "u" }
+        }))
+        .unwrap();
+        let goal = crate::types::NodeId {
+            id: "goal-1".to_string(),
+            namespace: None,
+        };
+        let applied = |id: &str| {
+            AuditEvent::new(
+                "alice",
+                "human",
+                AuditAction::ProposalApplied,
+                id,
+                AuditOutcome::Success,
+            )
+        };
+        for store in [&file as &dyn ContextStore, &memory] {
+            store.create_proposal(open.clone()).await.unwrap();
+
+            // Open is not accepted: nothing is applied, audited or queued for delivery.
+            let refused = WriteBatch::new()
+                .apply_proposal("p-1", "alice")
+                .audit(applied("p-1"));
+            let err = store.execute(refused).await.unwrap_err();
+            assert_eq!(err.code, StoreErrorCode::InvalidTransition);
+            assert!(store.get_node(&goal).await.unwrap().is_none());
+            assert!(store.list_outbox(10).await.unwrap().is_empty());
+
+            let batch = WriteBatch::new()
+                .apply_unreviewed("p-1", "alice")
+                .audit(applied("p-1"));
+            store.execute(batch).await.unwrap();
+            assert!(store.get_node(&goal).await.unwrap().is_some());
+            let p1 = store.get_proposal("p-1").await.unwrap().unwrap();
+            assert_eq!(p1.status, ProposalStatus::Applied);
+            let audit = store
+                .query_audit(None, None, Some("p-1"), None, None, None, None)
+                .await
+                .unwrap();
+            assert_eq!(audit.events.len(), 1);
+            let outbox = store.list_outbox(10).await.unwrap();
+            assert_eq!(outbox.len(), 1);
+            assert_eq!(outbox[0].audit.len(), 1);
+        }
+        drop(file);
+        let reopened = FileStore::new(&dir).unwrap();
+        assert!(reopened.get_node(&goal).await.unwrap().is_some());
+        let p1 = reopened.get_proposal("p-1").await.unwrap().unwrap();
+        assert_eq!(p1.status, ProposalStatus::Applied);
+        drop(reopened);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,131 @@
+//! Per-field change history, shared by the store backends.
+//!
+//! When a proposal is applied, every field an operation sets is stamped in the node's
+//! `metadata.fieldChanges` with the proposal, its author, who applied it and when. Only
+//! the last change of each field is kept, like `git blame` for the current version;
+//! the full history is in the audit log and the applied proposals.
+
+use crate::types::{ContextNode, FieldBlame, Operation, UPDATABLE_METADATA_FIELDS};
+
+/// Fields `op` sets on its node.
+pub(crate) fn changed_fields(op: &Operation) -> Vec<String> {
+    match op {
+        Operation::Create { node, .. } => {
+            let value = serde_json::to_value(node).unwrap_or_default();
+            let mut fields: Vec<String> = value
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(k, v)| k.as_str() != "id" && k.as_str() != "metadata" && !v.is_null())
+                .map(|(k, _)| k.clone())
+                .collect();
+            let metadata = &value["metadata"];
+            fields.extend(
+                UPDATABLE_METADATA_FIELDS
+                    .iter()
+                    .filter(|f| !metadata[**f].is_null())
+                    .map(|f| f.to_string()),
+            );
+            fields
+        }
+        Operation::Update { changes, .. } => changes.fields().keys().cloned().collect(),
+        Operation::StatusChange { .. } => vec!["status".to_string()],
+        Operation::Delete { .. } => Vec::new(),
+    }
+}
+
+/// Stamp the fields `op` set on `node`, at the node's current version.
+pub(crate) fn record(node: &mut ContextNode, op: &Operation, change: &FieldBlame) {
+    if matches!(op, Operation::Create { .. }) {
+        // A new node starts its history here, whatever the payload carried.
+        node.metadata.field_changes = None;
+    }
+    let fields = changed_fields(op);
+    if fields.is_empty() {
+        return;
+    }
+    let change = FieldBlame {
+        version: node.metadata.version,
+        ..change.clone()
+    };
+    let changes = node
+        .metadata
+        .field_changes
+        .get_or_insert_with(Default::default);
+    for field in fields {
+        changes.insert(field, change.clone());
+    }
+}
+
+/// The change to stamp for `proposal_id` (`version` is filled in per node).
+pub(crate) fn change(proposal_id: &str, author: &str, applied_by: &str, at: &str) -> FieldBlame {
+    FieldBlame {
+        proposal_id: proposal_id.to_string(),
+        author: author.to_string(),
+        applied_by: applied_by.to_string(),
+        changed_at: at.to_string(),
+        version: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{NodeStatus, UpdateChanges};
+
+    #[test]
+    fn later_operations_take_over_only_their_fields() {
+        let mut node: ContextNode = serde_json::from_value(serde_json::json!({
+            "id": { "id": "c-1" },
+            "type": "constraint",
+            "status": "proposed",
+            "content": "No PII in logs",
+            "constraint": "No PII in logs",
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "alice",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "alice",
+                "version": 1,
+                "sensitivity": "internal"
+            }
+        }))
+        .unwrap();
+        let create = Operation::Create {
+            id: "op-1".to_string(),
+            order: 1,
+            node: node.clone(),
+        };
+        record(
+            &mut node,
+            &create,
+            &change("p-1", "alice", "bob", "2026-01-02T00:00:00Z"),
+        );
+        node.metadata.version = 2;
+        let update = Operation::Update {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id: node.id.clone(),
+            changes: UpdateChanges {
+                status: Some(NodeStatus::Accepted),
+                ..Default::default()
+            },
+        };
+        record(
+            &mut node,
+            &update,
+            &change("p-2", "carol", "bob", "2026-01-03T00:00:00Z"),
+        );
+
+        let changes = node.metadata.field_changes.unwrap();
+        let fields: Vec<&str> = changes.keys().map(String::as_str).collect();
+        assert_eq!(
+            fields,
+            ["constraint", "content", "sensitivity", "status", "type"]
+        );
+        assert_eq!(changes["constraint"].author, "alice");
+        assert_eq!(changes["constraint"].version, 1);
+        assert_eq!(changes["status"].proposal_id, "p-2");
+        assert_eq!(changes["status"].version, 2);
+    }
+}
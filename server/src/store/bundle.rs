@@ -0,0 +1,79 @@
+//! Store bundles: a portable JSON dump of a whole store (nodes, proposals, reviews,
+//! audit log, revision counter). Written by `truthlayer-server snapshot`, loaded by
+//! `truthlayer-server import bundle`, `serve --seed` and `POST /admin/seed`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AuditEvent, ContextNode, Proposal, Review};
+
+/// Full contents of a store. Every section is optional when deserializing, so a
+/// hand-written bundle may contain only nodes or only proposals.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreBundle {
+    /// Revision counter (last applied revision number).
+    #[serde(default)]
+    pub revision: u64,
+    #[serde(default)]
+    pub nodes: Vec<ContextNode>,
+    #[serde(default)]
+    pub proposals: Vec<Proposal>,
+    /// Review history keyed by proposal ID.
+    #[serde(default)]
+    pub reviews: BTreeMap<String, Vec<Review>>,
+    #[serde(default)]
+    pub audit: Vec<AuditEvent>,
+}
+
+/// Read the bundle at `path`, or every `*.json` bundle in it (sorted by name) when it is a
+/// directory. Used by `serve --seed`.
+pub fn load_bundles(path: &Path) -> Result<Vec<(PathBuf, StoreBundle)>, String> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+    files
+        .into_iter()
+        .map(|file| {
+            let s = std::fs::read_to_string(&file)
+                .map_err(|e| format!("cannot read {}: {}", file.display(), e))?;
+            let bundle = serde_json::from_str(&s)
+                .map_err(|e| format!("malformed bundle {}: {}", file.display(), e))?;
+            Ok((file, bundle))
+        })
+        .collect()
+}
+
+/// What an import added. Records whose ID already exists are left untouched and
+/// counted in `skipped`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub nodes: usize,
+    pub proposals: usize,
+    pub reviews: usize,
+    pub audit_events: usize,
+    pub skipped: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demo_fixture_parses() {
+        let bundle: StoreBundle =
+            serde_json::from_str(include_str!("../../fixtures/demo/demo.json")).unwrap();
+        assert_eq!(bundle.nodes.len(), 4);
+    }
+}
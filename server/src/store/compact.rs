@@ -0,0 +1,106 @@
+//! Store compaction (`POST /admin/store/compact`, scheduled task `store_compaction`).
+//!
+//! Compaction removes what no store operation reads any more:
+//!
+//! - withdrawn and rejected proposals last modified more than `proposalRetentionDays`
+//!   ago, with their reviews (open, accepted and applied proposals are kept whatever
+//!   their age: they are pending work or the provenance of accepted truth);
+//! - orphaned files: record files the store does not know (left by an interrupted write
+//!   or a crash, or reviews whose proposal is gone);
+//! - audit segments: the file backend rewrites `audit.jsonl` without unreadable lines,
+//!   the memory backend merges its spilled audit pages into one. No event is dropped.
+//!
+//! The report lists what was removed and the bytes reclaimed on disk (file backend) or in
+//! serialized record size (memory backend, see [`json_size`](super::limits::json_size)).
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Proposal, ProposalStatus};
+
+/// Settings of one compaction run (request body or task `params`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactOptions {
+    /// Age, by `metadata.modifiedAt`, after which withdrawn and rejected proposals are
+    /// removed.
+    #[serde(default = "default_proposal_retention_days")]
+    pub proposal_retention_days: u32,
+}
+
+fn default_proposal_retention_days() -> u32 {
+    90
+}
+
+impl Default for CompactOptions {
+    fn default() -> Self {
+        Self {
+            proposal_retention_days: default_proposal_retention_days(),
+        }
+    }
+}
+
+impl CompactOptions {
+    /// Whether `proposal` is closed and past retention at `now`. Proposals with an
+    /// unparseable timestamp are kept.
+    pub(crate) fn prunable(&self, proposal: &Proposal, now: chrono::DateTime<chrono::Utc>) -> bool {
+        if !matches!(
+            proposal.status,
+            ProposalStatus::Withdrawn | ProposalStatus::Rejected
+        ) {
+            return false;
+        }
+        let cutoff = now - chrono::Duration::days(i64::from(self.proposal_retention_days));
+        chrono::DateTime::parse_from_rfc3339(&proposal.metadata.modified_at)
+            .is_ok_and(|modified| modified < cutoff)
+    }
+}
+
+/// What a compaction run removed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactReport {
+    /// Ids of the removed proposals, sorted.
+    pub pruned_proposals: Vec<String>,
+    /// Reviews removed with them or as orphans.
+    pub pruned_reviews: usize,
+    /// Removed files, relative to the data (or spill) directory, sorted.
+    pub orphaned_files: Vec<String>,
+    /// Whether the audit segments were rewritten.
+    pub audit_defragmented: bool,
+    pub reclaimed_bytes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_closed_proposals_past_retention_are_prunable() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let options = CompactOptions {
+            proposal_retention_days: 30,
+        };
+        let proposal = |status, modified_at: &str| -> Proposal {
+            serde_json::from_value(serde_json::json!({
+                "id": "p-1",
+                "status": status,
+                "operations": [],
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z",
+                    "createdBy": "alice",
+                    "modifiedAt": modified_at,
+                    "modifiedBy": "alice"
+                }
+            }))
+            .unwrap()
+        };
+        assert!(options.prunable(&proposal("withdrawn", "2026-04-01T00:00:00Z"), now));
+        assert!(options.prunable(&proposal("rejected", "2026-04-01T00:00:00+02:00"), now));
+        assert!(!options.prunable(&proposal("rejected", "2026-05-15T00:00:00Z"), now));
+        assert!(!options.prunable(&proposal("applied", "2025-01-01T00:00:00Z"), now));
+        assert!(!options.prunable(&proposal("open", "2025-01-01T00:00:00Z"), now));
+        assert!(!options.prunable(&proposal("withdrawn", "last spring"), now));
+    }
+}
@@ -1,11 +1,24 @@
 //! Context store trait: source of truth for nodes, proposals, reviews.
 //! Mirrors src/types/context-store.ts.
 
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
 
+use crate::store::batch::WriteBatch;
+use crate::store::bundle::{ImportSummary, StoreBundle};
+use crate::store::compact::{CompactOptions, CompactReport};
+use crate::store::directory::{ActorAccess, Directory, DirectoryGroup, DirectoryUser};
+use crate::store::lease::Lease;
+use crate::store::limits::StoreStatus;
+use crate::store::outbox::OutboxEntry;
+use crate::store::trace::NodeProposal;
+use crate::store::usage::UsageRecord;
 use crate::types::{
-    AuditEvent, Comment, ConflictDetectionResult, ContextNode, MergeResult, NodeId, NodeQuery,
-    NodeQueryResult, Proposal, ProposalQuery, Review,
+    AuditEvent, AuditQueryResult, Comment, ConflictDetectionResult, ContextNode, Delegation,
+    ExportJob, JobRecord, JobStatus, MergeResult, NodeId, NodeQuery, NodeQueryResult, Proposal,
+    ProposalPatch, ProposalQuery, Review, UserPreferences,
 };
 
 #[async_trait]
@@ -23,19 +36,28 @@ pub trait ContextStore: Send + Sync {
     async fn update_proposal(
         &self,
         proposal_id: &str,
-        updates: serde_json::Value,
+        patch: ProposalPatch,
     ) -> Result<(), StoreError>;
 
     async fn submit_review(&self, review: Review) -> Result<(), StoreError>;
 
     /// Apply an accepted proposal to the store. Records AppliedMetadata and sets status to Applied.
     /// Idempotent: if the proposal is already Applied, returns Ok without mutating.
-    async fn apply_proposal(&self, proposal_id: &str, applied_by: &str) -> Result<(), StoreError>;
+    /// A batch of one [`WriteBatch::apply_proposal`].
+    async fn apply_proposal(&self, proposal_id: &str, applied_by: &str) -> Result<(), StoreError> {
+        self.execute(WriteBatch::new().apply_proposal(proposal_id, applied_by))
+            .await
+    }
 
     /// Withdraw a proposal (author only). Allowed only from Open (DRAFT/SUBMITTED/CHANGES_REQUESTED); status → Withdrawn.
     /// Returns error if proposal is already Accepted, Rejected, Withdrawn, or Applied.
     async fn withdraw_proposal(&self, proposal_id: &str) -> Result<(), StoreError>;
 
+    /// Make a batch of proposal writes and append its audit events, all or nothing (see
+    /// `store::batch`): when one write is refused, none is made and nothing is audited.
+    /// The batch's events and audit events are stored with the writes as an outbox entry.
+    async fn execute(&self, batch: WriteBatch) -> Result<(), StoreError>;
+
     async fn get_review_history(&self, proposal_id: &str) -> Result<Vec<Review>, StoreError>;
 
     async fn get_proposal_comments(&self, proposal_id: &str) -> Result<Vec<Comment>, StoreError>;
@@ -50,6 +72,10 @@ pub trait ContextStore: Send + Sync {
 
     async fn get_open_proposals(&self) -> Result<Vec<Proposal>, StoreError>;
 
+    /// Applied proposals that touched `node_id`, in apply order (see `store::trace`).
+    /// Empty for unknown nodes.
+    async fn get_node_proposals(&self, node_id: &NodeId) -> Result<Vec<NodeProposal>, StoreError>;
+
     /// Compare proposal's operations (by node and field) with other open proposals.
     /// Returns conflicts, mergeable (proposal IDs), needsResolution (proposal IDs).
     /// Per AGENT_API § Conflict detection and merge; RECONCILIATION_STRATEGIES.
@@ -69,12 +95,23 @@ pub trait ContextStore: Send + Sync {
     /// Reset store state (for dev/demo only). In-memory clears all; other backends may return error.
     async fn reset(&self) -> Result<(), StoreError>;
 
+    /// Dump everything (nodes, proposals, reviews, audit log, revision counter) as a bundle.
+    async fn export_bundle(&self) -> Result<StoreBundle, StoreError>;
+
+    /// Load a bundle into the store (restore, migration between backends, fixtures).
+    /// Records are inserted as-is, bypassing the proposal workflow; IDs that already exist
+    /// are skipped. Audit events are appended unless their event ID is already present.
+    /// The revision counter becomes the larger of the current and bundled values.
+    async fn import_bundle(&self, bundle: StoreBundle) -> Result<ImportSummary, StoreError>;
+
     // --- Audit log ---
 
     /// Append an audit event to the immutable log.
     async fn append_audit(&self, event: AuditEvent) -> Result<(), StoreError>;
 
-    /// Query audit events with optional filters.
+    /// Query audit events with optional filters: one page, oldest first, with the total
+    /// number of matches.
+    #[allow(clippy::too_many_arguments)]
     async fn query_audit(
         &self,
         actor: Option<&str>,
@@ -84,26 +121,307 @@ pub trait ContextStore: Send + Sync {
         to: Option<&str>,
         limit: Option<u32>,
         offset: Option<u32>,
-    ) -> Result<Vec<AuditEvent>, StoreError>;
+    ) -> Result<AuditQueryResult, StoreError>;
+
+    // --- Export jobs ---
+
+    /// Insert or replace an export job record. Jobs survive `reset` (like the audit log).
+    async fn save_export_job(&self, job: ExportJob) -> Result<(), StoreError>;
+
+    async fn get_export_job(&self, job_id: &str) -> Result<Option<ExportJob>, StoreError>;
+
+    /// All export jobs, newest first.
+    async fn list_export_jobs(&self) -> Result<Vec<ExportJob>, StoreError>;
+
+    /// Store the finished artifact of an export job.
+    async fn put_export_artifact(&self, job_id: &str, data: Vec<u8>) -> Result<(), StoreError>;
+
+    async fn get_export_artifact(&self, job_id: &str) -> Result<Option<Vec<u8>>, StoreError>;
+
+    // --- Background jobs ---
+
+    /// Insert or replace a job record. Jobs survive `reset` (like the audit log).
+    async fn save_job(&self, job: JobRecord) -> Result<(), StoreError>;
+
+    async fn get_job(&self, job_id: &str) -> Result<Option<JobRecord>, StoreError>;
+
+    /// Jobs, newest first, optionally filtered by status and kind.
+    async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        kind: Option<&str>,
+    ) -> Result<Vec<JobRecord>, StoreError>;
+
+    /// Claim the oldest job of one of `kinds` that is due at `now` (RFC 3339): mark it
+    /// running and count the attempt, atomically, so two workers never get the same job.
+    async fn claim_job(&self, kinds: &[&str], now: &str) -> Result<Option<JobRecord>, StoreError>;
+
+    // --- Status ---
+
+    /// Record counts and approximate memory use.
+    async fn status(&self) -> Result<StoreStatus, StoreError>;
+
+    /// Remove closed proposals past retention, orphaned files and audit fragmentation
+    /// (see `store::compact`). Accepted truth and audit events are never removed.
+    async fn compact(&self, options: &CompactOptions) -> Result<CompactReport, StoreError>;
+
+    // --- Trash ---
+
+    /// Deleted nodes (see `store::trash`), most recently deleted first.
+    async fn list_trash(&self) -> Result<Vec<ContextNode>, StoreError>;
+
+    /// Bring a deleted node back with its content and status; NotFound unless it is in
+    /// the trash.
+    async fn restore_node(
+        &self,
+        node_id: &NodeId,
+        restored_by: &str,
+    ) -> Result<ContextNode, StoreError>;
+
+    /// Permanently remove nodes deleted before `deleted_before` (RFC 3339) and return
+    /// their ids. Only retention sweeps call this.
+    async fn purge_trash(&self, deleted_before: &str) -> Result<Vec<NodeId>, StoreError>;
+
+    // --- Leases ---
+
+    /// Take or renew the advisory lease `name` for `holder` for `ttl` (see `store::lease`).
+    /// Fails with [`StoreErrorCode::Locked`] while another holder's lease is live.
+    async fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<Lease, StoreError>;
+
+    /// Give up the lease `name` if `holder` holds it.
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), StoreError>;
+
+    /// Live leases whose name starts with `prefix` (node locks: `lease::NODE_LOCK_PREFIX`).
+    async fn list_leases(&self, prefix: &str) -> Result<Vec<Lease>, StoreError>;
+
+    // --- Actor directory ---
+
+    /// Users, groups and token revocations provisioned over SCIM (see `store::directory`).
+    /// The directory survives `reset` (like the audit log).
+    async fn get_directory(&self) -> Result<Directory, StoreError>;
+
+    /// Insert or replace a user; [`StoreError::Conflict`] when another user has its
+    /// `userName`. Deactivating or renaming a user revokes its tokens.
+    async fn save_directory_user(&self, user: DirectoryUser) -> Result<(), StoreError>;
+
+    /// Remove a user (and its group memberships) and revoke its tokens.
+    async fn delete_directory_user(&self, id: &str) -> Result<DirectoryUser, StoreError>;
+
+    /// Insert or replace a group; members must be existing users.
+    async fn save_directory_group(&self, group: DirectoryGroup) -> Result<(), StoreError>;
+
+    async fn delete_directory_group(&self, id: &str) -> Result<DirectoryGroup, StoreError>;
+
+    /// What the directory says about an actor id; None for actors it never provisioned.
+    async fn directory_access(&self, actor_id: &str) -> Result<Option<ActorAccess>, StoreError>;
+
+    // --- User preferences ---
+
+    /// The actor's saved preferences; None when it never saved any. Kept across `reset`.
+    async fn get_preferences(&self, actor_id: &str) -> Result<Option<UserPreferences>, StoreError>;
+
+    /// Replace the actor's preferences.
+    async fn save_preferences(
+        &self,
+        actor_id: &str,
+        preferences: UserPreferences,
+    ) -> Result<(), StoreError>;
+
+    // --- Review delegations ---
+
+    /// The delegations `delegator` has made (any window). Kept across `reset`.
+    async fn get_delegations(&self, delegator: &str) -> Result<Vec<Delegation>, StoreError>;
+
+    /// Replace the delegations `delegator` has made.
+    async fn save_delegations(
+        &self,
+        delegator: &str,
+        delegations: Vec<Delegation>,
+    ) -> Result<(), StoreError>;
+
+    // --- Usage metering ---
+
+    /// Add API calls (workspace → count) to the usage of `date` (`YYYY-MM-DD`).
+    async fn add_api_calls(
+        &self,
+        date: &str,
+        calls: &BTreeMap<String, u64>,
+    ) -> Result<(), StoreError>;
+
+    /// Store rolled-up usage records, keeping the API calls already counted for them.
+    async fn save_usage_rollup(&self, records: Vec<UsageRecord>) -> Result<(), StoreError>;
+
+    /// Usage records of a month (`YYYY-MM`), by date then workspace. Kept across `reset`.
+    async fn list_usage(&self, month: &str) -> Result<Vec<UsageRecord>, StoreError>;
+
+    // --- Outbox ---
+
+    /// Undelivered outbox entries (see `store::outbox`), oldest first, at most `limit`.
+    /// [`execute`](Self::execute) adds them; they survive `reset`.
+    async fn list_outbox(&self, limit: usize) -> Result<Vec<OutboxEntry>, StoreError>;
+
+    /// Replace an entry after a delivery attempt.
+    async fn save_outbox_entry(&self, entry: OutboxEntry) -> Result<(), StoreError>;
+
+    /// Remove a delivered entry; missing entries are fine.
+    async fn remove_outbox_entry(&self, id: &str) -> Result<(), StoreError>;
+}
+
+/// What kind of failure a [`StoreError`] is. Callers branch on this (and on
+/// [`StoreError::retryable`]) rather than on messages; the API surfaces map it to their
+/// status codes (REST, gRPC, GraphQL, SCIM).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreErrorCode {
+    NotFound,
+    /// The write clashes with the stored state (duplicate, taken name, stale update).
+    Conflict,
+    Invalid,
+    /// The proposal's status does not allow it (see `store::lifecycle`).
+    InvalidTransition,
+    /// A configured capacity limit would be exceeded; the write was not made.
+    CapacityExceeded,
+    /// Another process or instance holds the data directory or a lease.
+    Locked,
+    /// Reading or writing storage failed.
+    Io,
+    /// Stored data could not be parsed.
+    Corrupt,
+    Internal,
+}
+
+impl StoreErrorCode {
+    /// Stable machine-readable name (`not_found`, `locked`, …), as in REST error bodies.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StoreErrorCode::NotFound => "not_found",
+            StoreErrorCode::Conflict => "conflict",
+            StoreErrorCode::Invalid => "invalid",
+            StoreErrorCode::InvalidTransition => "invalid_transition",
+            StoreErrorCode::CapacityExceeded => "capacity_exceeded",
+            StoreErrorCode::Locked => "locked",
+            StoreErrorCode::Io => "io",
+            StoreErrorCode::Corrupt => "corrupt",
+            StoreErrorCode::Internal => "internal",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StoreErrorCode::NotFound => "not found",
+            StoreErrorCode::InvalidTransition => "invalid transition",
+            StoreErrorCode::CapacityExceeded => "capacity exceeded",
+            StoreErrorCode::Io => "i/o error",
+            other => other.as_str(),
+        }
+    }
 }
 
 #[derive(Debug)]
-pub enum StoreError {
-    NotFound(String),
-    Conflict(String),
-    Invalid(String),
-    Internal(String),
+pub struct StoreError {
+    pub code: StoreErrorCode,
+    pub message: String,
+    /// What the error is about (`proposal p-1`, a file path), when known.
+    pub resource: Option<String>,
+    /// Whether the same call may succeed later unchanged: true for locks, and for I/O
+    /// errors that are usually transient (interrupted, timed out, would block).
+    pub retryable: bool,
+    /// `message` and `resource` name server-side details (file paths, OS errors) that
+    /// belong in the log only; API responses carry a generic detail instead.
+    pub redacted: bool,
+    pub source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl StoreError {
+    pub fn new(code: StoreErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            resource: None,
+            retryable: code == StoreErrorCode::Locked,
+            redacted: matches!(
+                code,
+                StoreErrorCode::Io | StoreErrorCode::Corrupt | StoreErrorCode::Internal
+            ),
+            source: None,
+        }
+    }
+
+    /// `resource` (`proposal p-1`) does not exist.
+    pub fn not_found(resource: impl Into<String>) -> Self {
+        let resource = resource.into();
+        Self::new(StoreErrorCode::NotFound, resource.clone()).with_resource(resource)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(StoreErrorCode::Conflict, message)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(StoreErrorCode::Invalid, message)
+    }
+
+    pub fn capacity_exceeded(message: impl Into<String>) -> Self {
+        Self::new(StoreErrorCode::CapacityExceeded, message)
+    }
+
+    pub fn locked(message: impl Into<String>) -> Self {
+        Self::new(StoreErrorCode::Locked, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StoreErrorCode::Internal, message)
+    }
+
+    /// `what` (`write tmp`, `read …`) failed with `e`.
+    pub fn io(what: impl std::fmt::Display, e: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        let mut err = Self::new(StoreErrorCode::Io, format!("{}: {}", what, e));
+        err.retryable = matches!(
+            e.kind(),
+            ErrorKind::Interrupted | ErrorKind::TimedOut | ErrorKind::WouldBlock
+        );
+        err.with_source(e)
+    }
+
+    /// The data stored at `resource` does not parse.
+    pub fn corrupt(resource: impl std::fmt::Display, e: serde_json::Error) -> Self {
+        Self::new(StoreErrorCode::Corrupt, format!("{}: {}", resource, e))
+            .with_resource(resource.to_string())
+            .with_source(e)
+    }
+
+    /// Keep `message` and `resource` out of API responses (see `redacted`).
+    pub fn redacted(mut self) -> Self {
+        self.redacted = true;
+        self
+    }
+
+    pub fn with_resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+
+    pub fn with_source(mut self, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
 }
 
 impl std::fmt::Display for StoreError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            StoreError::NotFound(msg) => write!(f, "not found: {}", msg),
-            StoreError::Conflict(msg) => write!(f, "conflict: {}", msg),
-            StoreError::Invalid(msg) => write!(f, "invalid: {}", msg),
-            StoreError::Internal(msg) => write!(f, "internal: {}", msg),
-        }
+        write!(f, "{}: {}", self.code.label(), self.message)
     }
 }
 
-impl std::error::Error for StoreError {}
+impl std::error::Error for StoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
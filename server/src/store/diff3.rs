@@ -0,0 +1,162 @@
+//! Line-level three-way text merge (diff3), used by `reconcile::merge` for node content
+//! so that edits to different paragraphs of one node do not conflict.
+//!
+//! Both sides are diffed against the base (longest common subsequence of lines). Base
+//! lines both sides kept are anchors; between anchors, a hunk only one side changed takes
+//! that side, a hunk both changed the same way takes it once, and a hunk they changed
+//! differently is a conflict, written with diff3 markers:
+//!
+//! ```text
+//! <<<<<<< p-a
+//! their lines
+//! ||||||| base
+//! base lines
+//! =======
+//! other lines
+//! >>>>>>> p-b
+//! ```
+
+/// Longest inputs (lines of base × lines of a side, after the common prefix and suffix)
+/// diffed; larger texts are merged as a whole.
+const MAX_CELLS: usize = 4_000_000;
+
+/// Outcome of [`merge`]: the merged text, with markers around `conflicts` hunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextMerge {
+    pub text: String,
+    pub conflicts: usize,
+}
+
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// For each line of `base`, the line of `side` it is matched to in a longest common
+/// subsequence; None when the texts are too large to diff.
+fn matches(base: &[&str], side: &[&str]) -> Option<Vec<Option<usize>>> {
+    let prefix = base.iter().zip(side).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(side[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (o, s) = (
+        &base[prefix..base.len() - suffix],
+        &side[prefix..side.len() - suffix],
+    );
+    if o.len().saturating_mul(s.len()) > MAX_CELLS {
+        return None;
+    }
+    // lcs[i][j]: LCS length of o[i..] and s[j..].
+    let mut lcs = vec![vec![0u32; s.len() + 1]; o.len() + 1];
+    for i in (0..o.len()).rev() {
+        for j in (0..s.len()).rev() {
+            lcs[i][j] = if o[i] == s[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut matched: Vec<Option<usize>> = (0..prefix).map(Some).collect();
+    matched.resize(base.len(), None);
+    let (mut i, mut j) = (0, 0);
+    while i < o.len() && j < s.len() {
+        if o[i] == s[j] {
+            matched[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    for k in 0..suffix {
+        matched[base.len() - suffix + k] = Some(side.len() - suffix + k);
+    }
+    Some(matched)
+}
+
+/// Push `hunk` onto `out`, ending it with a newline so a marker can follow.
+fn push_block(out: &mut String, hunk: &[&str]) {
+    for line in hunk {
+        out.push_str(line);
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Merge `a` and `b`, both edited from `base`; `labels` name them in conflict markers.
+/// None when the texts are too large to diff.
+pub fn merge(base: &str, a: &str, b: &str, labels: (&str, &str)) -> Option<TextMerge> {
+    let (o, sa, sb) = (lines(base), lines(a), lines(b));
+    let (ma, mb) = (matches(&o, &sa)?, matches(&o, &sb)?);
+    // Anchors: base lines both sides kept, then a sentinel past the ends.
+    let mut anchors: Vec<(usize, usize, usize)> = (0..o.len())
+        .filter_map(|i| Some((i, ma[i]?, mb[i]?)))
+        .collect();
+    anchors.push((o.len(), sa.len(), sb.len()));
+
+    let mut text = String::new();
+    let mut conflicts = 0;
+    let (mut oi, mut ai, mut bi) = (0, 0, 0);
+    for (oe, ae, be) in anchors {
+        let (ho, ha, hb) = (&o[oi..oe], &sa[ai..ae], &sb[bi..be]);
+        if ha == ho {
+            hb.iter().for_each(|l| text.push_str(l));
+        } else if hb == ho || ha == hb {
+            ha.iter().for_each(|l| text.push_str(l));
+        } else {
+            conflicts += 1;
+            push_block(&mut text, &[]);
+            text.push_str(&format!("<<<<<<< {}\n", labels.0));
+            push_block(&mut text, ha);
+            text.push_str("||||||| base\n");
+            push_block(&mut text, ho);
+            text.push_str("=======\n");
+            push_block(&mut text, hb);
+            text.push_str(&format!(">>>>>>> {}\n", labels.1));
+        }
+        if oe < o.len() {
+            text.push_str(o[oe]);
+        }
+        (oi, ai, bi) = (oe + 1, ae + 1, be + 1);
+    }
+    Some(TextMerge { text, conflicts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_to_different_paragraphs_merge_and_overlaps_are_marked() {
+        let base = "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n";
+        let a = "# Title\n\nFirst paragraph, edited.\n\nSecond paragraph.\n";
+        let b = "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n\nThird.\n";
+        assert_eq!(
+            merge(base, a, b, ("p-a", "p-b")),
+            Some(TextMerge {
+                text: "# Title\n\nFirst paragraph, edited.\n\nSecond paragraph.\n\nThird.\n"
+                    .to_string(),
+                conflicts: 0,
+            })
+        );
+
+        let c = "# Title\n\nFirst paragraph, rewritten.\n\nSecond paragraph.\n";
+        let merged = merge(base, a, c, ("p-a", "p-c")).unwrap();
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.text,
+            "# Title\n\n<<<<<<< p-a\nFirst paragraph, edited.\n||||||| base\nFirst paragraph.\n\
+             =======\nFirst paragraph, rewritten.\n>>>>>>> p-c\n\nSecond paragraph.\n"
+        );
+
+        // The same edit on both sides, and a last line without a newline.
+        let merged = merge("x\ny", "x\nz", "x\nz", ("a", "b")).unwrap();
+        assert_eq!((merged.text.as_str(), merged.conflicts), ("x\nz", 0));
+    }
+}
@@ -0,0 +1,165 @@
+//! Single-writer lock for a `FileStore` data directory.
+//!
+//! `FileStore::new` takes an exclusive advisory lock (`flock` on Unix, `LockFileEx` on
+//! Windows) on `{data_dir}/.lock` and holds it until the store is dropped, so a second
+//! server or CLI process on the same directory fails at startup instead of interleaving
+//! writes. The file records the holder (pid, host, start time) for the error message.
+//!
+//! The OS releases the lock when its process exits, however it exits, so a lock file
+//! left by a crashed process is stale by construction: the next process gets the lock,
+//! logs the previous holder and takes over. A clean shutdown empties the file.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::context_store::StoreError;
+
+/// Contents of the lock file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockOwner {
+    pid: u32,
+    host: String,
+    acquired_at: String,
+}
+
+impl std::fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pid {} on {} since {}",
+            self.pid, self.host, self.acquired_at
+        )
+    }
+}
+
+/// Held lock on a data directory; released on drop.
+pub(crate) struct DataDirLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl DataDirLock {
+    /// Lock `dir`. Fails with a retryable `StoreErrorCode::Locked` naming the holder
+    /// when another process has it.
+    pub fn acquire(dir: &Path) -> Result<Self, StoreError> {
+        let path = dir.join(".lock");
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| StoreError::io(format!("open {}", path.display()), e))?;
+
+        let previous = read_owner(&mut file);
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(std::fs::TryLockError::WouldBlock) => {
+                let holder = previous.map(|o| format!(" by {}", o)).unwrap_or_default();
+                return Err(StoreError::locked(format!(
+                    "data directory {} is locked{}; another server or CLI command is using it",
+                    dir.display(),
+                    holder
+                ))
+                .with_resource(path.display().to_string())
+                .redacted());
+            }
+            Err(std::fs::TryLockError::Error(e)) => {
+                // Some network filesystems do not support locking; run unprotected.
+                tracing::warn!(path = %path.display(), error = %e, "cannot lock data directory; concurrent writers will not be detected");
+            }
+        }
+        if let Some(stale) = previous {
+            tracing::warn!(previous = %stale, "recovered stale data directory lock (previous process did not shut down cleanly)");
+        }
+
+        let owner = LockOwner {
+            pid: std::process::id(),
+            host: hostname(),
+            acquired_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let json = serde_json::to_vec(&owner).map_err(|e| StoreError::internal(e.to_string()))?;
+        file.set_len(0)
+            .and_then(|_| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| file.write_all(&json))
+            .map_err(|e| StoreError::io(format!("write {}", path.display()), e))?;
+        Ok(Self { file, path })
+    }
+}
+
+impl Drop for DataDirLock {
+    fn drop(&mut self) {
+        // Emptied, not deleted: removing the file would let a waiting process lock an
+        // inode that a newer process no longer sees.
+        if let Err(e) = self.file.set_len(0) {
+            tracing::warn!(path = %self.path.display(), error = %e, "cannot clear data directory lock");
+        }
+        let _ = self.file.unlock();
+    }
+}
+
+/// Holder recorded in the lock file; None when empty (clean shutdown) or unreadable.
+fn read_owner(file: &mut File) -> Option<LockOwner> {
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Host name from the environment or `/etc/hostname`.
+pub(crate) fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|h| h.trim().to_string())
+        })
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown host".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    #[test]
+    fn second_holder_is_refused_and_stale_locks_are_taken_over() {
+        let dir = std::env::temp_dir().join(format!("tl-dir-lock-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let lock = DataDirLock::acquire(&dir).unwrap();
+        match DataDirLock::acquire(&dir) {
+            Err(e) => {
+                assert_eq!(e.code, StoreErrorCode::Locked);
+                assert!(e.retryable);
+                assert!(
+                    e.message.contains(&format!("pid {}", std::process::id())),
+                    "{}",
+                    e
+                )
+            }
+            Ok(_) => panic!("expected the directory to be locked"),
+        }
+        drop(lock);
+        assert_eq!(std::fs::read_to_string(dir.join(".lock")).unwrap(), "");
+
+        // Left behind by a process that died without unlocking.
+        std::fs::write(
+            dir.join(".lock"),
+            r#"{"pid":1,"host":"old","acquiredAt":"2026-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let lock = DataDirLock::acquire(&dir).unwrap();
+        assert!(std::fs::read_to_string(dir.join(".lock"))
+            .unwrap()
+            .contains(&format!("\"pid\":{}", std::process::id())));
+        drop(lock);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
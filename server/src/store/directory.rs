@@ -0,0 +1,234 @@
+//! Actor directory, shared by the store backends: the users and groups an identity
+//! provider provisions over SCIM (`/scim/v2`, see `crate::api::scim`), and the actors
+//! whose tokens were revoked by deprovisioning.
+//!
+//! A user's `userName` is its actor id (the JWT `sub`). Deactivating, deleting or
+//! renaming a user revokes the tokens issued to that actor id until then; the
+//! revocation outlives the user, so reactivating it needs a fresh token. The directory
+//! survives `reset`, like the audit log.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::context_store::StoreError;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryUser {
+    pub id: String,
+    /// The actor id.
+    pub user_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emails: Vec<String>,
+    pub active: bool,
+    pub created: String,
+    pub last_modified: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryGroup {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// User ids.
+    #[serde(default)]
+    pub members: Vec<String>,
+    pub created: String,
+    pub last_modified: String,
+}
+
+/// What the directory says about an actor id, for request authentication.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorAccess {
+    /// False for deactivated and deleted users.
+    pub active: bool,
+    /// Display names of the user's groups.
+    pub groups: Vec<String>,
+    /// When the actor's tokens were last revoked (RFC 3339).
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Directory {
+    /// By user id.
+    #[serde(default)]
+    pub users: BTreeMap<String, DirectoryUser>,
+    /// By group id.
+    #[serde(default)]
+    pub groups: BTreeMap<String, DirectoryGroup>,
+    /// Actor id → when its tokens were revoked.
+    #[serde(default)]
+    pub revoked: BTreeMap<String, String>,
+}
+
+impl Directory {
+    /// The user whose `userName` is `user_name` (case-insensitive, as SCIM specifies).
+    pub fn user_by_name(&self, user_name: &str) -> Option<&DirectoryUser> {
+        self.users
+            .values()
+            .find(|u| u.user_name.eq_ignore_ascii_case(user_name))
+    }
+
+    /// Insert or replace a user. Deactivating or renaming one revokes its old actor id's
+    /// tokens as of `now`.
+    pub(crate) fn put_user(&mut self, user: DirectoryUser, now: &str) -> Result<(), StoreError> {
+        if let Some(other) = self.user_by_name(&user.user_name) {
+            if other.id != user.id {
+                return Err(StoreError::conflict(format!(
+                    "userName {} is taken by user {}",
+                    user.user_name, other.id
+                )));
+            }
+        }
+        match self.users.get(&user.id) {
+            Some(old) if old.user_name != user.user_name => {
+                self.revoked.insert(old.user_name.clone(), now.to_string());
+            }
+            Some(old) if old.active && !user.active => {
+                self.revoked.insert(user.user_name.clone(), now.to_string());
+            }
+            None if !user.active => {
+                self.revoked.insert(user.user_name.clone(), now.to_string());
+            }
+            _ => {}
+        }
+        self.users.insert(user.id.clone(), user);
+        Ok(())
+    }
+
+    /// Remove a user from the directory and its groups, and revoke its tokens.
+    pub(crate) fn remove_user(&mut self, id: &str, now: &str) -> Result<DirectoryUser, StoreError> {
+        let user = self
+            .users
+            .remove(id)
+            .ok_or_else(|| StoreError::not_found(format!("user {}", id)))?;
+        for group in self.groups.values_mut() {
+            group.members.retain(|m| m != id);
+        }
+        self.revoked.insert(user.user_name.clone(), now.to_string());
+        Ok(user)
+    }
+
+    /// Insert or replace a group; its members must be known users.
+    pub(crate) fn put_group(&mut self, mut group: DirectoryGroup) -> Result<(), StoreError> {
+        if let Some(other) = self
+            .groups
+            .values()
+            .find(|g| g.id != group.id && g.display_name.eq_ignore_ascii_case(&group.display_name))
+        {
+            return Err(StoreError::conflict(format!(
+                "displayName {} is taken by group {}",
+                group.display_name, other.id
+            )));
+        }
+        if let Some(unknown) = group.members.iter().find(|m| !self.users.contains_key(*m)) {
+            return Err(StoreError::invalid(format!(
+                "member {} is not a user",
+                unknown
+            )));
+        }
+        group.members.sort();
+        group.members.dedup();
+        self.groups.insert(group.id.clone(), group);
+        Ok(())
+    }
+
+    pub(crate) fn remove_group(&mut self, id: &str) -> Result<DirectoryGroup, StoreError> {
+        self.groups
+            .remove(id)
+            .ok_or_else(|| StoreError::not_found(format!("group {}", id)))
+    }
+
+    /// What the directory knows about `actor_id`; None for actors it never provisioned.
+    pub fn access(&self, actor_id: &str) -> Option<ActorAccess> {
+        let revoked_at = self.revoked.get(actor_id).cloned();
+        match self.user_by_name(actor_id) {
+            Some(user) => Some(ActorAccess {
+                active: user.active,
+                groups: self
+                    .groups
+                    .values()
+                    .filter(|g| g.members.contains(&user.id))
+                    .map(|g| g.display_name.clone())
+                    .collect(),
+                revoked_at,
+            }),
+            None => revoked_at.map(|at| ActorAccess {
+                active: false,
+                groups: Vec::new(),
+                revoked_at: Some(at),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    fn user(id: &str, name: &str, active: bool) -> DirectoryUser {
+        DirectoryUser {
+            id: id.to_string(),
+            user_name: name.to_string(),
+            external_id: None,
+            display_name: None,
+            emails: Vec::new(),
+            active,
+            created: "2026-01-01T00:00:00Z".to_string(),
+            last_modified: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn deprovisioning_revokes_and_outlives_the_user() {
+        let mut dir = Directory::default();
+        dir.put_user(user("u1", "alice", true), "t1").unwrap();
+        assert!(matches!(
+            dir.put_user(user("u2", "ALICE", true), "t1"),
+            Err(StoreError {
+                code: StoreErrorCode::Conflict,
+                ..
+            })
+        ));
+        dir.put_group(DirectoryGroup {
+            id: "g1".to_string(),
+            display_name: "reviewers".to_string(),
+            external_id: None,
+            members: vec!["u1".to_string(), "u1".to_string()],
+            created: "t1".to_string(),
+            last_modified: "t1".to_string(),
+        })
+        .unwrap();
+        let access = dir.access("alice").unwrap();
+        assert!(access.active);
+        assert_eq!(access.groups, ["reviewers"]);
+        assert_eq!(access.revoked_at, None);
+
+        dir.put_user(user("u1", "alice", false), "t2").unwrap();
+        assert_eq!(
+            dir.access("alice").unwrap().revoked_at.as_deref(),
+            Some("t2")
+        );
+        dir.put_user(user("u1", "alice", true), "t3").unwrap();
+        assert_eq!(
+            dir.access("alice").unwrap().revoked_at.as_deref(),
+            Some("t2")
+        );
+
+        dir.remove_user("u1", "t4").unwrap();
+        assert!(dir.groups["g1"].members.is_empty());
+        let gone = dir.access("alice").unwrap();
+        assert!(!gone.active);
+        assert_eq!(gone.revoked_at.as_deref(), Some("t4"));
+        assert_eq!(dir.access("bob"), None);
+    }
+}
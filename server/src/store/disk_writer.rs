@@ -0,0 +1,306 @@
+//! Disk writer for `FileStore` (`storage.file` in config.json).
+//!
+//! Every write goes to one dedicated thread, so blocking filesystem calls stay off the
+//! async runtime and files change in the order the store issued them (the store enqueues
+//! while holding its cache locks). Record files are replaced atomically (temp file, then
+//! rename) and the caller awaits the result; writes spanning several files go through the
+//! write-ahead [`Journal`]. Audit events are appended to `audit.jsonl`
+//! in batches: when `audit_batch_size` events are pending, after `audit_flush_interval_ms`,
+//! on [`DiskWriter::flush`] and when the store is dropped.
+//!
+//! With `durability: "fsync"` each record file and audit batch is synced to disk before
+//! it counts as written; `"buffered"` (the default) leaves that to the OS.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::store::context_store::{StoreError, StoreErrorCode};
+use crate::store::journal::{FileOp, Journal};
+use crate::types::AuditEvent;
+
+/// When a write counts as done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Durability {
+    /// Written to the OS page cache.
+    #[default]
+    Buffered,
+    /// Synced to disk (`fsync`), including the directory entry of renamed files.
+    Fsync,
+}
+
+/// Write settings for the file backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStoreOptions {
+    #[serde(default)]
+    pub durability: Durability,
+    /// Longest time an audit event waits in the batch. 0 = append each event before
+    /// `append_audit` returns.
+    #[serde(default = "default_audit_flush_interval_ms")]
+    pub audit_flush_interval_ms: u64,
+    /// Pending audit events that trigger an immediate flush.
+    #[serde(default = "default_audit_batch_size")]
+    pub audit_batch_size: usize,
+}
+
+fn default_audit_flush_interval_ms() -> u64 {
+    1000
+}
+
+fn default_audit_batch_size() -> usize {
+    256
+}
+
+impl Default for FileStoreOptions {
+    fn default() -> Self {
+        Self {
+            durability: Durability::default(),
+            audit_flush_interval_ms: default_audit_flush_interval_ms(),
+            audit_batch_size: default_audit_batch_size(),
+        }
+    }
+}
+
+impl FileStoreOptions {
+    /// Problems in a `storage.file` config.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.audit_batch_size == 0 {
+            issues.push("storage.file.audit_batch_size: must be at least 1".to_string());
+        }
+        issues
+    }
+}
+
+type Ack = oneshot::Sender<Result<(), String>>;
+
+enum Op {
+    Commit { ops: Vec<FileOp>, ack: Ack },
+    Audit(Box<AuditEvent>),
+    Flush(Ack),
+}
+
+/// Writes the caller may wait for. Dropping it without waiting does not cancel them.
+#[must_use]
+#[derive(Default)]
+pub(crate) struct Pending(Vec<oneshot::Receiver<Result<(), String>>>);
+
+impl Pending {
+    /// Wait for every write; the first failure is returned.
+    pub async fn wait(self) -> Result<(), StoreError> {
+        let mut result = Ok(());
+        for rx in self.0 {
+            let outcome = rx
+                .await
+                .unwrap_or_else(|_| Err("disk writer stopped".to_string()));
+            if let (Ok(()), Err(e)) = (&result, outcome) {
+                result = Err(StoreError::new(StoreErrorCode::Io, e));
+            }
+        }
+        result
+    }
+}
+
+/// Handle to the writer thread. Dropping it flushes pending audit events and joins the
+/// thread.
+pub(crate) struct DiskWriter {
+    tx: Option<mpsc::Sender<Op>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DiskWriter {
+    /// Start the writer for the data directory `root`.
+    pub fn start(root: PathBuf, options: FileStoreOptions) -> Result<Self, StoreError> {
+        let (tx, rx) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name("file-store-writer".to_string())
+            .spawn(move || run(rx, root, &options))
+            .map_err(|e| StoreError::internal(format!("cannot start disk writer: {}", e)))?;
+        Ok(Self {
+            tx: Some(tx),
+            thread: Some(thread),
+        })
+    }
+
+    fn send(&self, op: impl FnOnce(Ack) -> Op) -> Pending {
+        let (ack, rx) = oneshot::channel();
+        if let Some(tx) = &self.tx {
+            // On a send error the ack is dropped with the op, so `wait` reports it.
+            let _ = tx.send(op(ack));
+        }
+        Pending(vec![rx])
+    }
+
+    /// Apply `ops` as one operation: all of them, in order, or (after a crash) replayed
+    /// from the journal at the next start.
+    pub fn commit(&self, ops: Vec<FileOp>) -> Pending {
+        if ops.is_empty() {
+            return Pending::default();
+        }
+        self.send(|ack| Op::Commit { ops, ack })
+    }
+
+    /// Replace `path` with `data` atomically.
+    pub fn write(&self, path: PathBuf, data: Vec<u8>) -> Pending {
+        self.commit(vec![FileOp::Write { path, data }])
+    }
+
+    /// Queue an audit event for the next batch.
+    pub fn append_audit(&self, event: AuditEvent) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(Op::Audit(Box::new(event)));
+        }
+    }
+
+    /// Append every queued audit event now.
+    pub fn flush(&self) -> Pending {
+        self.send(Op::Flush)
+    }
+}
+
+impl Drop for DiskWriter {
+    fn drop(&mut self) {
+        drop(self.tx.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(rx: mpsc::Receiver<Op>, root: PathBuf, options: &FileStoreOptions) {
+    let audit_file = &root.join("audit.jsonl");
+    let mut journal = Journal::new(root.clone(), options.durability);
+    let interval = Duration::from_millis(options.audit_flush_interval_ms);
+    let batch_size = options.audit_batch_size.max(1);
+    let durability = options.durability;
+    let mut batch: Vec<AuditEvent> = Vec::new();
+    let mut deadline: Option<Instant> = None;
+
+    loop {
+        let op = match deadline {
+            None => match rx.recv() {
+                Ok(op) => op,
+                Err(_) => break,
+            },
+            Some(at) => match rx.recv_timeout(at.saturating_duration_since(Instant::now())) {
+                Ok(op) => op,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    deadline = flush_audit(audit_file, &mut batch, durability, interval).err();
+                    continue;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            },
+        };
+        match op {
+            Op::Commit { ops, ack } => {
+                let _ = ack.send(journal.commit(ops));
+            }
+            Op::Audit(event) => {
+                batch.push(*event);
+                if batch.len() >= batch_size || interval.is_zero() {
+                    deadline = flush_audit(audit_file, &mut batch, durability, interval).err();
+                } else if deadline.is_none() {
+                    deadline = Some(Instant::now() + interval);
+                }
+            }
+            Op::Flush(ack) => {
+                let result = flush_audit(audit_file, &mut batch, durability, interval);
+                deadline = result.as_ref().err().copied();
+                let _ = ack.send(result.map_err(|_| "audit flush failed".to_string()));
+            }
+        }
+    }
+
+    if flush_audit(audit_file, &mut batch, durability, interval).is_err() {
+        tracing::error!(
+            lost = batch.len(),
+            "audit events could not be written at shutdown"
+        );
+    }
+}
+
+/// Append the batch to the audit file. On failure the events stay queued and the error
+/// carries the time of the next attempt.
+fn flush_audit(
+    audit_file: &Path,
+    batch: &mut Vec<AuditEvent>,
+    durability: Durability,
+    interval: Duration,
+) -> Result<(), Instant> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    let mut lines = Vec::new();
+    for event in batch.iter() {
+        if let Ok(line) = serde_json::to_vec(event) {
+            lines.extend_from_slice(&line);
+            lines.push(b'\n');
+        }
+    }
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(audit_file)
+        .and_then(|mut file| {
+            file.write_all(&lines)?;
+            if durability == Durability::Fsync {
+                file.sync_data()?;
+            }
+            Ok(())
+        });
+    match written {
+        Ok(()) => {
+            batch.clear();
+            Ok(())
+        }
+        Err(e) => {
+            tracing::error!(error = %e, pending = batch.len(), "audit append failed; will retry");
+            Err(Instant::now() + interval.max(Duration::from_secs(1)))
+        }
+    }
+}
+
+/// Atomic write: write to a temp file, then rename over `path`. With `Fsync` the temp
+/// file and the directory entry are synced as well.
+pub(crate) fn write_atomic(
+    path: &Path,
+    content: &[u8],
+    durability: Durability,
+) -> Result<(), StoreError> {
+    let dir = path.parent().unwrap_or(path);
+    std::fs::create_dir_all(dir).map_err(|e| StoreError::io("mkdir", e))?;
+    let tmp = path.with_extension("tmp");
+    let mut file = std::fs::File::create(&tmp).map_err(|e| StoreError::io("write tmp", e))?;
+    file.write_all(content)
+        .map_err(|e| StoreError::io("write tmp", e))?;
+    if durability == Durability::Fsync {
+        file.sync_all().map_err(|e| StoreError::io("fsync", e))?;
+    }
+    drop(file);
+    std::fs::rename(&tmp, path).map_err(|e| StoreError::io("rename", e))?;
+    if durability == Durability::Fsync {
+        sync_dir(dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> Result<(), StoreError> {
+    std::fs::File::open(dir)
+        .and_then(|d| d.sync_all())
+        .map_err(|e| StoreError::io("fsync dir", e))
+}
+
+/// Directories cannot be opened for syncing on this platform; the rename is durable
+/// once the file itself is.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> Result<(), StoreError> {
+    Ok(())
+}
@@ -1,45 +1,132 @@
 //! File-based implementation of ContextStore.
 //! Stores data as JSON files under `data/workspaces/{workspaceId}/` with atomic writes.
 //! Git-friendly format for versioned truth.
+//!
+//! Reads are served from an in-memory cache. Writes go through a [`DiskWriter`] thread
+//! (no blocking IO on the async runtime), with a write-ahead [`journal`](super::journal)
+//! for changes spanning several files; the audit log is an append-only `audit.jsonl`
+//! written in batches. A legacy `audit.json` is converted on startup. One process at a
+//! time may open a data directory (see [`DataDirLock`]).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
+use crate::store::audit_index::AuditFilter;
+use crate::store::batch::{self, WriteBatch};
+use crate::store::blame;
+use crate::store::bundle::{ImportSummary, StoreBundle};
+use crate::store::compact::{CompactOptions, CompactReport};
 use crate::store::context_store::{ContextStore, StoreError};
+use crate::store::dir_lock::DataDirLock;
+use crate::store::directory::{ActorAccess, Directory, DirectoryGroup, DirectoryUser};
+use crate::store::disk_writer::{write_atomic, DiskWriter, Durability, FileStoreOptions};
+use crate::store::journal::{self, FileOp, Recovery};
+use crate::store::lease::{Lease, LeaseTable, NODE_LOCK_PREFIX};
+use crate::store::lifecycle;
+use crate::store::limits::{json_size, StoreStatus};
+use crate::store::node_index::NodeTable;
+use crate::store::outbox::OutboxEntry;
+use crate::store::reconcile;
+use crate::store::trace::{NodeProposal, TraceIndex};
+use crate::store::trash;
+use crate::store::usage::{UsageLedger, UsageRecord};
 use crate::types::{
-    AppliedMetadata, AuditEvent, Comment, ConflictDetectionResult, ContextNode, MergeResult,
-    NodeId, NodeQuery, NodeQueryResult, Proposal, ProposalQuery, ProposalStatus, Review,
+    AuditEvent, AuditQueryResult, Comment, ConflictDetectionResult, ContextNode, Delegation,
+    ExportJob, JobRecord, JobStatus, MergeResult, NodeId, NodeQuery, NodeQueryResult, Proposal,
+    ProposalPatch, ProposalQuery, ProposalStatus, Review, UserPreferences,
 };
 
+/// Outcome of [`FileStore::migrate`].
+#[derive(Debug, Default, serde::Serialize)]
+pub struct MigrationReport {
+    /// Files rewritten in the current format.
+    pub rewritten: usize,
+    /// Files already in the current format.
+    pub unchanged: usize,
+    /// Files that could not be parsed (path and error).
+    pub failed: Vec<String>,
+}
+
 /// File-based ContextStore: persists all data as JSON files.
 pub struct FileStore {
     root: PathBuf,
+    options: FileStoreOptions,
     /// In-memory cache synchronized with disk.
-    nodes: RwLock<HashMap<String, ContextNode>>,
+    nodes: RwLock<NodeTable>,
     proposals: RwLock<HashMap<String, Proposal>>,
     reviews: RwLock<HashMap<String, Vec<Review>>>,
+    /// Node key -> applied proposals that touched it (see `store::trace`); rebuilt on load.
+    trace: RwLock<TraceIndex>,
     audit_log: RwLock<Vec<AuditEvent>>,
     revision_counter: RwLock<u64>,
+    export_jobs: RwLock<HashMap<String, ExportJob>>,
+    jobs: RwLock<HashMap<String, JobRecord>>,
+    /// Not persisted: only this process can hold the data directory.
+    leases: RwLock<LeaseTable>,
+    /// SCIM users and groups (see `store::directory`); kept across `reset`.
+    directory: RwLock<Directory>,
+    /// Actor id -> preferences; kept across `reset`.
+    /// Daily usage per workspace (see `store::usage`); kept across `reset`.
+    usage: RwLock<UsageLedger>,
+    preferences: RwLock<BTreeMap<String, UserPreferences>>,
+    /// Delegator id -> review delegations; kept across `reset`.
+    delegations: RwLock<BTreeMap<String, Vec<Delegation>>>,
+    /// Undelivered outbox entries by id (see `store::outbox`); kept across `reset`.
+    outbox: RwLock<BTreeMap<String, OutboxEntry>>,
+    writer: DiskWriter,
+    /// Declared last: released only after the writer has flushed.
+    _lock: DataDirLock,
 }
 
 impl FileStore {
-    /// Create a new FileStore rooted at the given data directory.
-    /// Loads existing data from disk if present.
+    /// Create a new FileStore rooted at the given data directory, with default write
+    /// options. Loads existing data from disk if present. Fails with
+    /// `StoreErrorCode::Locked` when another process holds the directory.
     pub fn new(root: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        Self::with_options(root, FileStoreOptions::default())
+    }
+
+    /// Create a FileStore with the given durability and audit batching settings.
+    pub fn with_options(
+        root: impl Into<PathBuf>,
+        options: FileStoreOptions,
+    ) -> Result<Self, StoreError> {
         let root = root.into();
-        std::fs::create_dir_all(&root)
-            .map_err(|e| StoreError::Internal(format!("cannot create data dir: {}", e)))?;
+        std::fs::create_dir_all(&root).map_err(|e| StoreError::io("cannot create data dir", e))?;
+        let lock = DataDirLock::acquire(&root)?;
+        match journal::recover(&root, options.durability)? {
+            Recovery::Clean => {}
+            Recovery::Replayed(steps) => {
+                tracing::warn!(steps, "replayed an interrupted write from the journal")
+            }
+            Recovery::RolledBack => {
+                tracing::warn!("discarded a write interrupted before it was journaled")
+            }
+        }
 
         let store = Self {
             root: root.clone(),
-            nodes: RwLock::new(HashMap::new()),
+            nodes: RwLock::new(NodeTable::default()),
             proposals: RwLock::new(HashMap::new()),
             reviews: RwLock::new(HashMap::new()),
+            trace: RwLock::new(TraceIndex::default()),
             audit_log: RwLock::new(Vec::new()),
             revision_counter: RwLock::new(0),
+            export_jobs: RwLock::new(HashMap::new()),
+            jobs: RwLock::new(HashMap::new()),
+            leases: RwLock::new(LeaseTable::default()),
+            directory: RwLock::new(Directory::default()),
+            usage: RwLock::new(UsageLedger::default()),
+            preferences: RwLock::new(BTreeMap::new()),
+            delegations: RwLock::new(BTreeMap::new()),
+            outbox: RwLock::new(BTreeMap::new()),
+            writer: DiskWriter::start(root.clone(), options.clone())?,
+            options,
+            _lock: lock,
         };
 
         // Load existing data
@@ -59,7 +146,13 @@ impl FileStore {
         self.root.join("reviews")
     }
 
+    /// Append-only audit log, one JSON event per line.
     fn audit_file(&self) -> PathBuf {
+        self.root.join("audit.jsonl")
+    }
+
+    /// Audit log of older versions (a single JSON array), converted on load.
+    fn legacy_audit_file(&self) -> PathBuf {
         self.root.join("audit.json")
     }
 
@@ -67,15 +160,39 @@ impl FileStore {
         self.root.join("revision.json")
     }
 
-    /// Atomic write: write to temp file then rename.
-    fn atomic_write(path: &Path, content: &[u8]) -> Result<(), StoreError> {
-        let dir = path.parent().unwrap_or(path);
-        std::fs::create_dir_all(dir).map_err(|e| StoreError::Internal(format!("mkdir: {}", e)))?;
-        let tmp = path.with_extension("tmp");
-        std::fs::write(&tmp, content)
-            .map_err(|e| StoreError::Internal(format!("write tmp: {}", e)))?;
-        std::fs::rename(&tmp, path).map_err(|e| StoreError::Internal(format!("rename: {}", e)))?;
-        Ok(())
+    /// Background job records (`{id}.json`).
+    fn jobs_dir(&self) -> PathBuf {
+        self.root.join("jobs")
+    }
+
+    /// SCIM users, groups and token revocations, as one document.
+    fn directory_file(&self) -> PathBuf {
+        self.root.join("directory.json")
+    }
+
+    /// Daily usage per workspace, as one document.
+    fn usage_file(&self) -> PathBuf {
+        self.root.join("usage.json")
+    }
+
+    /// Undelivered outbox entries (`{id}.json`).
+    fn outbox_dir(&self) -> PathBuf {
+        self.root.join("outbox")
+    }
+
+    /// Every actor's preferences, as one document.
+    fn preferences_file(&self) -> PathBuf {
+        self.root.join("preferences.json")
+    }
+
+    /// Every actor's review delegations, as one document.
+    fn delegations_file(&self) -> PathBuf {
+        self.root.join("delegations.json")
+    }
+
+    /// Export job records (`{id}.json`) and finished artifacts (`{id}.data`).
+    fn exports_dir(&self) -> PathBuf {
+        self.root.join("exports")
     }
 
     fn load_from_disk(&self) -> Result<(), StoreError> {
@@ -84,14 +201,14 @@ impl FileStore {
             let mut nodes = self
                 .nodes
                 .write()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
+                .map_err(|e| StoreError::internal(e.to_string()))?;
             for entry in std::fs::read_dir(self.nodes_dir())
-                .map_err(|e| StoreError::Internal(e.to_string()))?
+                .map_err(|e| StoreError::io(self.nodes_dir().display(), e))?
             {
-                let entry = entry.map_err(|e| StoreError::Internal(e.to_string()))?;
-                if entry.path().extension().map_or(false, |ext| ext == "json") {
+                let entry = entry.map_err(|e| StoreError::io("read_dir", e))?;
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
                     let content = std::fs::read_to_string(entry.path())
-                        .map_err(|e| StoreError::Internal(e.to_string()))?;
+                        .map_err(|e| StoreError::io(entry.path().display(), e))?;
                     if let Ok(node) = serde_json::from_str::<ContextNode>(&content) {
                         let key = node.id.key();
                         nodes.insert(key, node);
@@ -105,19 +222,24 @@ impl FileStore {
             let mut proposals = self
                 .proposals
                 .write()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
+                .map_err(|e| StoreError::internal(e.to_string()))?;
             for entry in std::fs::read_dir(self.proposals_dir())
-                .map_err(|e| StoreError::Internal(e.to_string()))?
+                .map_err(|e| StoreError::io(self.proposals_dir().display(), e))?
             {
-                let entry = entry.map_err(|e| StoreError::Internal(e.to_string()))?;
-                if entry.path().extension().map_or(false, |ext| ext == "json") {
+                let entry = entry.map_err(|e| StoreError::io("read_dir", e))?;
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
                     let content = std::fs::read_to_string(entry.path())
-                        .map_err(|e| StoreError::Internal(e.to_string()))?;
+                        .map_err(|e| StoreError::io(entry.path().display(), e))?;
                     if let Ok(proposal) = serde_json::from_str::<Proposal>(&content) {
                         proposals.insert(proposal.id.clone(), proposal);
                     }
                 }
             }
+            *self
+                .trace
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? =
+                TraceIndex::rebuild(proposals.values());
         }
 
         // Load reviews
@@ -125,14 +247,14 @@ impl FileStore {
             let mut reviews = self
                 .reviews
                 .write()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
+                .map_err(|e| StoreError::internal(e.to_string()))?;
             for entry in std::fs::read_dir(self.reviews_dir())
-                .map_err(|e| StoreError::Internal(e.to_string()))?
+                .map_err(|e| StoreError::io(self.reviews_dir().display(), e))?
             {
-                let entry = entry.map_err(|e| StoreError::Internal(e.to_string()))?;
-                if entry.path().extension().map_or(false, |ext| ext == "json") {
+                let entry = entry.map_err(|e| StoreError::io("read_dir", e))?;
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
                     let content = std::fs::read_to_string(entry.path())
-                        .map_err(|e| StoreError::Internal(e.to_string()))?;
+                        .map_err(|e| StoreError::io(entry.path().display(), e))?;
                     if let Ok(review_list) = serde_json::from_str::<Vec<Review>>(&content) {
                         let stem = entry
                             .path()
@@ -147,27 +269,137 @@ impl FileStore {
         }
 
         // Load audit log
-        if self.audit_file().exists() {
-            let content = std::fs::read_to_string(self.audit_file())
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            if let Ok(events) = serde_json::from_str::<Vec<AuditEvent>>(&content) {
-                let mut log = self
-                    .audit_log
-                    .write()
-                    .map_err(|e| StoreError::Internal(e.to_string()))?;
-                *log = events;
+        {
+            let mut log = self
+                .audit_log
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            *log = self.load_audit_log()?;
+        }
+
+        // Load export jobs (their queued background jobs resume them)
+        if self.exports_dir().exists() {
+            let mut jobs = self
+                .export_jobs
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for entry in std::fs::read_dir(self.exports_dir())
+                .map_err(|e| StoreError::io(self.exports_dir().display(), e))?
+            {
+                let entry = entry.map_err(|e| StoreError::io("read_dir", e))?;
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
+                    let content = std::fs::read_to_string(entry.path())
+                        .map_err(|e| StoreError::io(entry.path().display(), e))?;
+                    if let Ok(job) = serde_json::from_str::<ExportJob>(&content) {
+                        jobs.insert(job.id.clone(), job);
+                    }
+                }
             }
         }
 
+        // Load background jobs; one cut off by a restart goes back to the queue
+        if self.jobs_dir().exists() {
+            let mut jobs = self
+                .jobs
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for entry in std::fs::read_dir(self.jobs_dir())
+                .map_err(|e| StoreError::io(self.jobs_dir().display(), e))?
+            {
+                let entry = entry.map_err(|e| StoreError::io("read_dir", e))?;
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
+                    let content = std::fs::read_to_string(entry.path())
+                        .map_err(|e| StoreError::io(entry.path().display(), e))?;
+                    if let Ok(mut job) = serde_json::from_str::<JobRecord>(&content) {
+                        if job.status == JobStatus::Running {
+                            job.status = JobStatus::Queued;
+                            let json = serde_json::to_string_pretty(&job)
+                                .map_err(|e| StoreError::internal(e.to_string()))?;
+                            write_atomic(&entry.path(), json.as_bytes(), self.options.durability)?;
+                        }
+                        jobs.insert(job.id.clone(), job);
+                    }
+                }
+            }
+        }
+
+        // Load undelivered outbox entries
+        if self.outbox_dir().exists() {
+            let mut outbox = self
+                .outbox
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for entry in std::fs::read_dir(self.outbox_dir())
+                .map_err(|e| StoreError::io(self.outbox_dir().display(), e))?
+            {
+                let entry = entry.map_err(|e| StoreError::io("read_dir", e))?;
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
+                    let content = std::fs::read_to_string(entry.path())
+                        .map_err(|e| StoreError::io(entry.path().display(), e))?;
+                    let loaded: OutboxEntry = serde_json::from_str(&content)
+                        .map_err(|e| StoreError::corrupt(entry.path().display(), e))?;
+                    outbox.insert(loaded.id.clone(), loaded);
+                }
+            }
+        }
+
+        // Load the actor directory
+        if self.directory_file().exists() {
+            let content = std::fs::read_to_string(self.directory_file())
+                .map_err(|e| StoreError::io(self.directory_file().display(), e))?;
+            let loaded: Directory = serde_json::from_str(&content)
+                .map_err(|e| StoreError::corrupt(self.directory_file().display(), e))?;
+            *self
+                .directory
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? = loaded;
+        }
+
+        // Load the usage ledger
+        if self.usage_file().exists() {
+            let content = std::fs::read_to_string(self.usage_file())
+                .map_err(|e| StoreError::io(self.usage_file().display(), e))?;
+            let loaded: UsageLedger = serde_json::from_str(&content)
+                .map_err(|e| StoreError::corrupt(self.usage_file().display(), e))?;
+            *self
+                .usage
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? = loaded;
+        }
+
+        // Load user preferences
+        if self.preferences_file().exists() {
+            let content = std::fs::read_to_string(self.preferences_file())
+                .map_err(|e| StoreError::io(self.preferences_file().display(), e))?;
+            let loaded: BTreeMap<String, UserPreferences> = serde_json::from_str(&content)
+                .map_err(|e| StoreError::corrupt(self.preferences_file().display(), e))?;
+            *self
+                .preferences
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? = loaded;
+        }
+
+        // Load review delegations
+        if self.delegations_file().exists() {
+            let content = std::fs::read_to_string(self.delegations_file())
+                .map_err(|e| StoreError::io(self.delegations_file().display(), e))?;
+            let loaded: BTreeMap<String, Vec<Delegation>> = serde_json::from_str(&content)
+                .map_err(|e| StoreError::corrupt(self.delegations_file().display(), e))?;
+            *self
+                .delegations
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? = loaded;
+        }
+
         // Load revision counter
         if self.revision_file().exists() {
             let content = std::fs::read_to_string(self.revision_file())
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
+                .map_err(|e| StoreError::io(self.revision_file().display(), e))?;
             if let Ok(rev) = serde_json::from_str::<u64>(&content) {
                 let mut counter = self
                     .revision_counter
                     .write()
-                    .map_err(|e| StoreError::Internal(e.to_string()))?;
+                    .map_err(|e| StoreError::internal(e.to_string()))?;
                 *counter = rev;
             }
         }
@@ -175,219 +407,305 @@ impl FileStore {
         Ok(())
     }
 
-    fn save_node(&self, node: &ContextNode) -> Result<(), StoreError> {
+    /// Read `audit.jsonl`, converting a legacy `audit.json` first. Lines that do not
+    /// parse (e.g. one cut short by a crash) are skipped with a warning.
+    fn load_audit_log(&self) -> Result<Vec<AuditEvent>, StoreError> {
+        let legacy = self.legacy_audit_file();
+        if legacy.exists() {
+            let content = std::fs::read_to_string(&legacy)
+                .map_err(|e| StoreError::io(legacy.display(), e))?;
+            let mut events: Vec<AuditEvent> = serde_json::from_str(&content)
+                .map_err(|e| StoreError::corrupt(legacy.display(), e))?;
+            events.extend(read_audit_lines(&self.audit_file())?);
+            write_atomic(
+                &self.audit_file(),
+                &audit_lines(&events)?,
+                self.options.durability,
+            )?;
+            std::fs::remove_file(&legacy).map_err(|e| StoreError::io(legacy.display(), e))?;
+            tracing::info!(events = events.len(), "converted audit.json to audit.jsonl");
+            return Ok(events);
+        }
+        read_audit_lines(&self.audit_file())
+    }
+
+    fn node_file(&self, node: &ContextNode) -> Result<FileOp, StoreError> {
         let path = self.nodes_dir().join(format!("{}.json", node.id.key()));
         let json =
-            serde_json::to_string_pretty(node).map_err(|e| StoreError::Internal(e.to_string()))?;
-        Self::atomic_write(&path, json.as_bytes())
+            serde_json::to_string_pretty(node).map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path,
+            data: json.into_bytes(),
+        })
     }
 
-    fn save_proposal(&self, proposal: &Proposal) -> Result<(), StoreError> {
+    fn proposal_file(&self, proposal: &Proposal) -> Result<FileOp, StoreError> {
         let path = self.proposals_dir().join(format!("{}.json", proposal.id));
         let json = serde_json::to_string_pretty(proposal)
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Self::atomic_write(&path, json.as_bytes())
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path,
+            data: json.into_bytes(),
+        })
     }
 
-    fn save_reviews(&self, proposal_id: &str, reviews: &[Review]) -> Result<(), StoreError> {
+    fn reviews_file(&self, proposal_id: &str, reviews: &[Review]) -> Result<FileOp, StoreError> {
         let path = self.reviews_dir().join(format!("{}.json", proposal_id));
         let json = serde_json::to_string_pretty(reviews)
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Self::atomic_write(&path, json.as_bytes())
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path,
+            data: json.into_bytes(),
+        })
     }
 
-    fn save_audit_log(&self) -> Result<(), StoreError> {
-        let log = self
-            .audit_log
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+    fn export_job_file(&self, job: &ExportJob) -> Result<FileOp, StoreError> {
+        let path = self.exports_dir().join(format!("{}.json", job.id));
         let json =
-            serde_json::to_string_pretty(&*log).map_err(|e| StoreError::Internal(e.to_string()))?;
-        Self::atomic_write(&self.audit_file(), json.as_bytes())
+            serde_json::to_string_pretty(job).map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path,
+            data: json.into_bytes(),
+        })
     }
 
-    fn save_revision(&self) -> Result<(), StoreError> {
-        let rev = self
-            .revision_counter
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let json = serde_json::to_string(&*rev).map_err(|e| StoreError::Internal(e.to_string()))?;
-        Self::atomic_write(&self.revision_file(), json.as_bytes())
+    fn outbox_file(&self, entry: &OutboxEntry) -> Result<FileOp, StoreError> {
+        let json =
+            serde_json::to_string_pretty(entry).map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path: self.outbox_dir().join(format!("{}.json", entry.id)),
+            data: json.into_bytes(),
+        })
     }
-}
-
-// Helper to generate node key from NodeId
-fn node_key(id: &NodeId) -> String {
-    id.key()
-}
 
-#[async_trait]
-impl ContextStore for FileStore {
-    async fn get_node(&self, node_id: &NodeId) -> Result<Option<ContextNode>, StoreError> {
-        let nodes = self
-            .nodes
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Ok(nodes.get(&node_key(node_id)).cloned())
+    fn job_file(&self, job: &JobRecord) -> Result<FileOp, StoreError> {
+        let path = self.jobs_dir().join(format!("{}.json", job.id));
+        let json =
+            serde_json::to_string_pretty(job).map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path,
+            data: json.into_bytes(),
+        })
     }
 
-    async fn query_nodes(&self, query: NodeQuery) -> Result<NodeQueryResult, StoreError> {
-        let nodes = self
-            .nodes
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-
-        let mut filtered: Vec<&ContextNode> = nodes.values().collect();
-        if let Some(ref statuses) = query.status {
-            filtered.retain(|n| statuses.contains(&n.status));
-        }
-        let total = filtered.len() as u64;
-        let limit = query.limit.unwrap_or(50).min(1000);
-        let offset = query.offset.unwrap_or(0);
-        let start = (offset as usize).min(filtered.len());
-        let end = (start + limit as usize).min(filtered.len());
-        let page = filtered[start..end].iter().cloned().cloned().collect();
-
-        Ok(NodeQueryResult {
-            nodes: page,
-            total,
-            limit,
-            offset,
-            has_more: end < filtered.len(),
+    fn directory_write(&self, directory: &Directory) -> Result<FileOp, StoreError> {
+        let json = serde_json::to_string_pretty(directory)
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path: self.directory_file(),
+            data: json.into_bytes(),
         })
     }
 
-    async fn get_proposal(&self, proposal_id: &str) -> Result<Option<Proposal>, StoreError> {
-        let proposals = self
-            .proposals
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Ok(proposals.get(proposal_id).cloned())
+    /// Apply `change` to the directory and write it out.
+    async fn update_directory<T>(
+        &self,
+        change: impl FnOnce(&mut Directory) -> Result<T, StoreError>,
+    ) -> Result<T, StoreError> {
+        let (value, written) = {
+            let mut directory = self
+                .directory
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let value = change(&mut directory)?;
+            (
+                value,
+                self.writer.commit(vec![self.directory_write(&directory)?]),
+            )
+        };
+        written.wait().await?;
+        Ok(value)
     }
 
-    async fn query_proposals(&self, _query: ProposalQuery) -> Result<Vec<Proposal>, StoreError> {
-        let proposals = self
-            .proposals
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Ok(proposals.values().cloned().collect())
+    /// Apply `change` to the usage ledger and write it out.
+    async fn update_usage(&self, change: impl FnOnce(&mut UsageLedger)) -> Result<(), StoreError> {
+        let written = {
+            let mut usage = self
+                .usage
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            change(&mut usage);
+            let json = serde_json::to_string_pretty(&*usage)
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            self.writer.commit(vec![FileOp::Write {
+                path: self.usage_file(),
+                data: json.into_bytes(),
+            }])
+        };
+        written.wait().await
     }
 
-    async fn create_proposal(&self, proposal: Proposal) -> Result<(), StoreError> {
-        let mut proposals = self
-            .proposals
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        if proposals.contains_key(&proposal.id) {
-            return Err(StoreError::Conflict(format!(
-                "proposal {} already exists",
-                proposal.id
-            )));
-        }
-        self.save_proposal(&proposal)?;
-        proposals.insert(proposal.id.clone(), proposal);
-        Ok(())
+    fn revision_write(&self, rev: u64) -> Result<FileOp, StoreError> {
+        let json = serde_json::to_string(&rev).map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(FileOp::Write {
+            path: self.revision_file(),
+            data: json.into_bytes(),
+        })
     }
 
-    async fn update_proposal(
-        &self,
-        proposal_id: &str,
-        updates: serde_json::Value,
-    ) -> Result<(), StoreError> {
-        let mut proposals = self
-            .proposals
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let proposal = proposals
-            .get_mut(proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-
-        if let Some(status) = updates.get("status").and_then(|v| v.as_str()) {
-            match status {
-                "open" => proposal.status = ProposalStatus::Open,
-                "accepted" => proposal.status = ProposalStatus::Accepted,
-                "rejected" => proposal.status = ProposalStatus::Rejected,
-                "withdrawn" => proposal.status = ProposalStatus::Withdrawn,
-                "applied" => proposal.status = ProposalStatus::Applied,
-                _ => {}
+    /// Rewrite every record on disk in the current format (new fields get their defaults,
+    /// removed fields are dropped). Files that no longer parse are left untouched and
+    /// reported; `FileStore::new` silently skips them, so run this after upgrading.
+    pub fn migrate(&self) -> Result<MigrationReport, StoreError> {
+        fn rewrite<T: serde::de::DeserializeOwned + serde::Serialize>(
+            path: &Path,
+            durability: Durability,
+            report: &mut MigrationReport,
+        ) -> Result<(), StoreError> {
+            let content =
+                std::fs::read_to_string(path).map_err(|e| StoreError::internal(e.to_string()))?;
+            match serde_json::from_str::<T>(&content) {
+                Ok(value) => {
+                    let json = serde_json::to_string_pretty(&value)
+                        .map_err(|e| StoreError::internal(e.to_string()))?;
+                    if json != content {
+                        write_atomic(path, json.as_bytes(), durability)?;
+                        report.rewritten += 1;
+                    } else {
+                        report.unchanged += 1;
+                    }
+                }
+                Err(e) => report.failed.push(format!("{}: {}", path.display(), e)),
             }
+            Ok(())
         }
-        self.save_proposal(proposal)?;
-        Ok(())
-    }
-
-    async fn submit_review(&self, review: Review) -> Result<(), StoreError> {
-        let mut reviews = self
-            .reviews
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let list = reviews.entry(review.proposal_id.clone()).or_default();
-        list.push(review.clone());
-        self.save_reviews(&review.proposal_id, list)?;
-        Ok(())
-    }
-
-    async fn apply_proposal(&self, proposal_id: &str, applied_by: &str) -> Result<(), StoreError> {
-        let mut proposals = self
-            .proposals
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut nodes = self
-            .nodes
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut rev = self
-            .revision_counter
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
 
-        let proposal = proposals
-            .get_mut(proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
+        fn json_files(dir: &Path) -> Result<Vec<PathBuf>, StoreError> {
+            if !dir.exists() {
+                return Ok(Vec::new());
+            }
+            let mut files = Vec::new();
+            for entry in std::fs::read_dir(dir).map_err(|e| StoreError::internal(e.to_string()))? {
+                let path = entry
+                    .map_err(|e| StoreError::internal(e.to_string()))?
+                    .path();
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    files.push(path);
+                }
+            }
+            files.sort();
+            Ok(files)
+        }
 
-        if proposal.status == ProposalStatus::Applied {
-            return Ok(()); // idempotent
+        let durability = self.options.durability;
+        let mut report = MigrationReport::default();
+        for path in json_files(&self.nodes_dir())? {
+            rewrite::<ContextNode>(&path, durability, &mut report)?;
+        }
+        for path in json_files(&self.proposals_dir())? {
+            rewrite::<Proposal>(&path, durability, &mut report)?;
+        }
+        for path in json_files(&self.reviews_dir())? {
+            rewrite::<Vec<Review>>(&path, durability, &mut report)?;
+        }
+        // The audit log is rewritten as a whole; one bad line leaves it untouched.
+        let audit = self.audit_file();
+        if audit.exists() {
+            let content =
+                std::fs::read_to_string(&audit).map_err(|e| StoreError::internal(e.to_string()))?;
+            let parsed: Result<Vec<AuditEvent>, String> = content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(n, line)| {
+                    serde_json::from_str(line).map_err(|e| format!("line {}: {}", n + 1, e))
+                })
+                .collect();
+            match parsed {
+                Ok(events) => {
+                    let lines = audit_lines(&events)?;
+                    if lines != content.as_bytes() {
+                        write_atomic(&audit, &lines, durability)?;
+                        report.rewritten += 1;
+                    } else {
+                        report.unchanged += 1;
+                    }
+                }
+                Err(e) => report.failed.push(format!("{}: {}", audit.display(), e)),
+            }
+        }
+        if self.revision_file().exists() {
+            rewrite::<u64>(&self.revision_file(), durability, &mut report)?;
         }
+        Ok(report)
+    }
 
-        let prev_rev = *rev;
-        *rev += 1;
-        let new_rev = *rev;
+    /// Refuse an apply of `proposal` the nodes do not allow: operations on trashed nodes
+    /// or status transitions it does not force.
+    fn check_apply(nodes: &NodeTable, proposal: &Proposal) -> Result<(), StoreError> {
+        trash::check_operations(nodes, &proposal.operations)?;
+        reconcile::check_status_transitions(
+            &proposal.operations,
+            |key| nodes.get(key).map(|n| n.status),
+            proposal.metadata.force_status_transitions == Some(true),
+        )?;
+        reconcile::check_task_transitions(
+            &proposal.operations,
+            |key| nodes.get(key).and_then(|n| n.state),
+            proposal.metadata.force_status_transitions == Some(true),
+        )
+    }
 
-        // Apply operations
+    /// Write `proposal`'s operations to the nodes and mark it applied at store revision
+    /// `previous + 1`; returns the node files to commit.
+    fn apply(
+        &self,
+        nodes: &mut NodeTable,
+        proposal: &mut Proposal,
+        applied_by: &str,
+        review_id: Option<String>,
+        previous: u64,
+    ) -> Result<Vec<FileOp>, StoreError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let change = blame::change(
+            &proposal.id,
+            &proposal.metadata.created_by,
+            applied_by,
+            &now,
+        );
+        let mut ops = Vec::new();
         for op in &proposal.operations {
             match op {
                 crate::types::Operation::Create { node, .. } => {
                     let key = node_key(&node.id);
                     let mut node = node.clone();
+                    node.metadata.modified_at = now.clone();
+                    node.metadata.modified_by = applied_by.to_string();
                     // Content fingerprinting: SHA-256 hash for IP protection
                     node.metadata.content_hash =
                         Some(crate::sensitivity::content_hash(&node.content));
-                    self.save_node(&node)?;
+                    blame::record(&mut node, op, &change);
+                    ops.push(self.node_file(&node)?);
                     nodes.insert(key, node);
                 }
                 crate::types::Operation::Update {
                     node_id, changes, ..
                 } => {
                     let key = node_key(node_id);
-                    if let Some(existing) = nodes.get_mut(&key) {
+                    nodes.modify(&key, |existing| {
+                        existing.metadata.modified_at = now.clone();
+                        existing.metadata.modified_by = applied_by.to_string();
+                        changes.apply_to(existing);
                         if let Some(ref c) = changes.content {
-                            existing.content = c.clone();
                             // Recompute content hash on content change
                             existing.metadata.content_hash =
                                 Some(crate::sensitivity::content_hash(c));
                         }
-                        if let Some(s) = changes.status {
-                            existing.status = s;
-                        }
                         existing.metadata.version += 1;
-                        self.save_node(existing)?;
+                        blame::record(existing, op, &change);
+                    });
+                    if let Some(existing) = nodes.get(&key) {
+                        ops.push(self.node_file(existing)?);
                     }
                 }
                 crate::types::Operation::Delete { node_id, .. } => {
                     let key = node_key(node_id);
-                    nodes.remove(&key);
-                    let path = self.nodes_dir().join(format!("{}.json", key));
-                    let _ = std::fs::remove_file(path);
+                    nodes.modify(&key, |existing| {
+                        trash::tombstone(existing, applied_by, &now, &proposal.id)
+                    });
+                    if let Some(deleted) = nodes.get_deleted(&key) {
+                        ops.push(self.node_file(deleted)?);
+                    }
                 }
                 crate::types::Operation::StatusChange {
                     node_id,
@@ -395,154 +713,577 @@ impl ContextStore for FileStore {
                     ..
                 } => {
                     let key = node_key(node_id);
-                    if let Some(existing) = nodes.get_mut(&key) {
+                    nodes.modify(&key, |existing| {
                         existing.status = *new_status;
-                        self.save_node(existing)?;
+                        existing.metadata.modified_at = now.clone();
+                        existing.metadata.modified_by = applied_by.to_string();
+                        blame::record(existing, op, &change);
+                    });
+                    if let Some(existing) = nodes.get(&key) {
+                        ops.push(self.node_file(existing)?);
                     }
                 }
             }
         }
 
-        proposal.status = ProposalStatus::Applied;
-        proposal.applied = Some(AppliedMetadata {
-            applied_at: chrono::Utc::now().to_rfc3339(),
-            applied_by: applied_by.to_string(),
-            applied_from_review_id: None,
-            applied_from_proposal_id: proposal_id.to_string(),
-            applied_to_revision_id: format!("rev-{}", new_rev),
-            previous_revision_id: format!("rev-{}", prev_rev),
-        });
-        self.save_proposal(proposal)?;
-        self.save_revision()?;
-        Ok(())
+        lifecycle::mark_applied(proposal, applied_by, review_id, previous);
+        Ok(ops)
     }
+}
 
-    async fn withdraw_proposal(&self, proposal_id: &str) -> Result<(), StoreError> {
-        let mut proposals = self
-            .proposals
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let proposal = proposals
-            .get_mut(proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-
-        match proposal.status {
-            ProposalStatus::Open => {
-                proposal.status = ProposalStatus::Withdrawn;
-                self.save_proposal(proposal)?;
-                Ok(())
-            }
-            _ => Err(StoreError::Invalid(format!(
-                "cannot withdraw proposal in status {:?}",
-                proposal.status
-            ))),
+/// Events of an audit JSON Lines file, oldest first; empty when the file is missing.
+fn read_audit_lines(path: &Path) -> Result<Vec<AuditEvent>, StoreError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(StoreError::io(path.display(), e)),
+    };
+    let mut events = Vec::new();
+    for (n, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditEvent>(line) {
+            Ok(event) => events.push(event),
+            Err(e) => tracing::warn!(line = n + 1, error = %e, "skipping unreadable audit line"),
         }
     }
-
-    async fn get_review_history(&self, proposal_id: &str) -> Result<Vec<Review>, StoreError> {
-        let reviews = self
-            .reviews
-            .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Ok(reviews.get(proposal_id).cloned().unwrap_or_default())
+    // A line cut short by a crash must not swallow the next append.
+    if !content.is_empty() && !content.ends_with('\n') {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|mut f| std::io::Write::write_all(&mut f, b"\n"))
+            .map_err(|e| StoreError::io(path.display(), e))?;
     }
+    Ok(events)
+}
 
-    async fn get_proposal_comments(&self, _proposal_id: &str) -> Result<Vec<Comment>, StoreError> {
-        Ok(vec![])
+fn audit_lines(events: &[AuditEvent]) -> Result<Vec<u8>, StoreError> {
+    let mut out = Vec::new();
+    for event in events {
+        serde_json::to_writer(&mut out, event).map_err(|e| StoreError::internal(e.to_string()))?;
+        out.push(b'\n');
     }
+    Ok(out)
+}
 
-    async fn add_proposal_comment(
-        &self,
-        _proposal_id: &str,
-        _comment: Comment,
-    ) -> Result<(), StoreError> {
-        Ok(())
+// Helper to generate node key from NodeId
+fn node_key(id: &NodeId) -> String {
+    id.key()
+}
+
+#[async_trait]
+impl ContextStore for FileStore {
+    async fn get_node(&self, node_id: &NodeId) -> Result<Option<ContextNode>, StoreError> {
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(nodes.get(&node_key(node_id)).cloned())
     }
 
-    async fn get_accepted_nodes(&self) -> Result<Vec<ContextNode>, StoreError> {
+    async fn query_nodes(&self, query: NodeQuery) -> Result<NodeQueryResult, StoreError> {
         let nodes = self
             .nodes
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Ok(nodes
-            .values()
-            .filter(|n| n.status == crate::types::NodeStatus::Accepted)
-            .cloned()
-            .collect())
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(nodes.query(&query))
     }
 
-    async fn get_open_proposals(&self) -> Result<Vec<Proposal>, StoreError> {
+    async fn get_proposal(&self, proposal_id: &str) -> Result<Option<Proposal>, StoreError> {
         let proposals = self
             .proposals
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        Ok(proposals
-            .values()
-            .filter(|p| p.status == ProposalStatus::Open)
-            .cloned()
-            .collect())
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(proposals.get(proposal_id).cloned())
     }
 
-    async fn detect_conflicts(
-        &self,
-        _proposal_id: &str,
-    ) -> Result<ConflictDetectionResult, StoreError> {
-        Ok(ConflictDetectionResult {
-            conflicts: vec![],
-            mergeable: vec![],
-            needs_resolution: vec![],
-        })
+    async fn query_proposals(&self, _query: ProposalQuery) -> Result<Vec<Proposal>, StoreError> {
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(proposals.values().cloned().collect())
     }
 
-    async fn is_proposal_stale(&self, _proposal_id: &str) -> Result<bool, StoreError> {
-        Ok(false)
+    async fn create_proposal(&self, proposal: Proposal) -> Result<(), StoreError> {
+        let written = {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            if proposals.contains_key(&proposal.id) {
+                return Err(StoreError::conflict(format!(
+                    "proposal {} already exists",
+                    proposal.id
+                )));
+            }
+            let written = self.writer.commit(vec![self.proposal_file(&proposal)?]);
+            proposals.insert(proposal.id.clone(), proposal);
+            written
+        };
+        written.wait().await
     }
 
-    async fn merge_proposals(&self, _proposal_ids: &[String]) -> Result<MergeResult, StoreError> {
-        Err(StoreError::Internal(
-            "merge not yet implemented for file store".to_string(),
-        ))
+    async fn update_proposal(
+        &self,
+        proposal_id: &str,
+        patch: ProposalPatch,
+    ) -> Result<(), StoreError> {
+        let written = {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let proposal = proposals
+                .get_mut(proposal_id)
+                .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+            lifecycle::apply_update(proposal, &patch)?;
+            self.writer.commit(vec![self.proposal_file(proposal)?])
+        };
+        written.wait().await
     }
 
-    async fn reset(&self) -> Result<(), StoreError> {
-        let mut nodes = self
-            .nodes
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut proposals = self
-            .proposals
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut reviews = self
-            .reviews
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut rev = self
-            .revision_counter
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        nodes.clear();
-        proposals.clear();
-        reviews.clear();
-        *rev = 0;
-
-        // Clear files on disk (but not audit log)
-        let _ = std::fs::remove_dir_all(self.nodes_dir());
-        let _ = std::fs::remove_dir_all(self.proposals_dir());
-        let _ = std::fs::remove_dir_all(self.reviews_dir());
-        let _ = std::fs::remove_file(self.revision_file());
-        Ok(())
+    async fn submit_review(&self, review: Review) -> Result<(), StoreError> {
+        let written = {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let proposal = proposals
+                .get_mut(&review.proposal_id)
+                .ok_or_else(|| StoreError::not_found(format!("proposal {}", review.proposal_id)))?;
+            lifecycle::apply_review(proposal, &review)?;
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let list = reviews.entry(review.proposal_id.clone()).or_default();
+            list.push(review.clone());
+            self.writer.commit(vec![
+                self.proposal_file(proposal)?,
+                self.reviews_file(&review.proposal_id, list)?,
+            ])
+        };
+        written.wait().await
     }
 
-    async fn append_audit(&self, event: AuditEvent) -> Result<(), StoreError> {
-        let mut log = self
-            .audit_log
-            .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        log.push(event);
-        // Persist immediately
-        drop(log);
-        self.save_audit_log()
+    async fn withdraw_proposal(&self, proposal_id: &str) -> Result<(), StoreError> {
+        let written = {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let proposal = proposals
+                .get_mut(proposal_id)
+                .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+            lifecycle::withdraw(proposal)?;
+            self.writer.commit(vec![self.proposal_file(proposal)?])
+        };
+        written.wait().await
+    }
+
+    async fn execute(&self, batch: WriteBatch) -> Result<(), StoreError> {
+        let written = {
+            // Same lock order as reset and import_bundle.
+            let mut nodes = self
+                .nodes
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut staged = batch::stage(&batch.ops, &proposals, &reviews)?;
+            for (id, _) in &staged.applies {
+                FileStore::check_apply(&nodes, &staged.proposals[id])?;
+            }
+            let mut ops = Vec::new();
+            if !staged.applies.is_empty() {
+                let mut rev = self
+                    .revision_counter
+                    .write()
+                    .map_err(|e| StoreError::internal(e.to_string()))?;
+                let mut trace = self
+                    .trace
+                    .write()
+                    .map_err(|e| StoreError::internal(e.to_string()))?;
+                for (id, applied_by) in &staged.applies {
+                    let review_id = staged
+                        .reviews
+                        .get(id)
+                        .or_else(|| reviews.get(id))
+                        .and_then(|v| lifecycle::accepting_review(v));
+                    let proposal = staged.proposals.get_mut(id).expect("staged with its apply");
+                    ops.extend(self.apply(&mut nodes, proposal, applied_by, review_id, *rev)?);
+                    *rev += 1;
+                    trace.record(proposal);
+                }
+                ops.push(self.revision_write(*rev)?);
+            }
+            for proposal in staged.proposals.values() {
+                ops.push(self.proposal_file(proposal)?);
+            }
+            for (proposal_id, list) in &staged.reviews {
+                ops.push(self.reviews_file(proposal_id, list)?);
+            }
+            let entry = batch.outbox_entry();
+            if let Some(entry) = &entry {
+                ops.push(self.outbox_file(entry)?);
+            }
+            let mut log = self
+                .audit_log
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut outbox = self
+                .outbox
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            // One journaled commit (with the outbox entry), the audit events queued right
+            // behind it.
+            let written = self.writer.commit(ops);
+            for event in batch.audit {
+                self.writer.append_audit(event.clone());
+                log.push(event);
+            }
+            proposals.extend(staged.proposals);
+            reviews.extend(staged.reviews);
+            if let Some(entry) = entry {
+                outbox.insert(entry.id.clone(), entry);
+            }
+            written
+        };
+        written.wait().await?;
+        if self.options.audit_flush_interval_ms == 0 {
+            self.writer.flush().wait().await?;
+        }
+        Ok(())
+    }
+
+    async fn get_review_history(&self, proposal_id: &str) -> Result<Vec<Review>, StoreError> {
+        let reviews = self
+            .reviews
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(reviews.get(proposal_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_proposal_comments(&self, proposal_id: &str) -> Result<Vec<Comment>, StoreError> {
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(proposals
+            .get(proposal_id)
+            .and_then(|p| p.comments.as_ref())
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Comments live in the proposal file, so they are rewritten with it.
+    async fn add_proposal_comment(
+        &self,
+        proposal_id: &str,
+        comment: Comment,
+    ) -> Result<(), StoreError> {
+        let written = {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let proposal = proposals
+                .get_mut(proposal_id)
+                .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+            proposal.comments.get_or_insert_with(Vec::new).push(comment);
+            self.writer.commit(vec![self.proposal_file(proposal)?])
+        };
+        written.wait().await
+    }
+
+    async fn get_accepted_nodes(&self) -> Result<Vec<ContextNode>, StoreError> {
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(nodes.with_status(crate::types::NodeStatus::Accepted))
+    }
+
+    async fn get_open_proposals(&self) -> Result<Vec<Proposal>, StoreError> {
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(proposals
+            .values()
+            .filter(|p| p.status == ProposalStatus::Open)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_node_proposals(&self, node_id: &NodeId) -> Result<Vec<NodeProposal>, StoreError> {
+        Ok(self
+            .trace
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .get(&node_key(node_id)))
+    }
+
+    async fn detect_conflicts(
+        &self,
+        proposal_id: &str,
+    ) -> Result<ConflictDetectionResult, StoreError> {
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let proposal = proposals
+            .get(proposal_id)
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        let open: Vec<Proposal> = proposals
+            .values()
+            .filter(|p| p.status == ProposalStatus::Open)
+            .cloned()
+            .collect();
+        let mut result = reconcile::detect_conflicts(proposal, &open);
+        let locks = self
+            .leases
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .live(NODE_LOCK_PREFIX, chrono::Utc::now());
+        result.locked_nodes = reconcile::locked_nodes(proposal, &locks);
+        Ok(result)
+    }
+
+    async fn is_proposal_stale(&self, proposal_id: &str) -> Result<bool, StoreError> {
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let proposal = proposals
+            .get(proposal_id)
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(reconcile::is_stale(proposal, |key| {
+            nodes.get(key).map(|n| n.metadata.version)
+        }))
+    }
+
+    async fn merge_proposals(&self, proposal_ids: &[String]) -> Result<MergeResult, StoreError> {
+        let proposals: Vec<Proposal> = {
+            let p = self
+                .proposals
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            proposal_ids
+                .iter()
+                .filter_map(|id| p.get(id).cloned())
+                .collect()
+        };
+        if proposals.len() != proposal_ids.len() {
+            return Err(StoreError::not_found(
+                "one or more proposal ids not found".to_string(),
+            ));
+        }
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(reconcile::merge(&proposals, |key| nodes.get(key)))
+    }
+
+    async fn reset(&self) -> Result<(), StoreError> {
+        let written = {
+            let mut nodes = self
+                .nodes
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut trace = self
+                .trace
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut rev = self
+                .revision_counter
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            nodes.clear();
+            proposals.clear();
+            reviews.clear();
+            *trace = TraceIndex::default();
+            *rev = 0;
+
+            // Clear files on disk (but not audit log)
+            self.writer.commit(vec![
+                FileOp::Remove {
+                    path: self.nodes_dir(),
+                    dir: true,
+                },
+                FileOp::Remove {
+                    path: self.proposals_dir(),
+                    dir: true,
+                },
+                FileOp::Remove {
+                    path: self.reviews_dir(),
+                    dir: true,
+                },
+                FileOp::Remove {
+                    path: self.revision_file(),
+                    dir: false,
+                },
+            ])
+        };
+        written.wait().await
+    }
+
+    async fn export_bundle(&self) -> Result<StoreBundle, StoreError> {
+        let mut nodes: Vec<ContextNode> = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .values()
+            .cloned()
+            .collect();
+        nodes.sort_by_key(|n| node_key(&n.id));
+        let mut proposals: Vec<Proposal> = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .values()
+            .cloned()
+            .collect();
+        proposals.sort_by(|a, b| a.id.cmp(&b.id));
+        let reviews = self
+            .reviews
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let audit = self
+            .audit_log
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .clone();
+        let revision = *self
+            .revision_counter
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(StoreBundle {
+            revision,
+            nodes,
+            proposals,
+            reviews,
+            audit,
+        })
+    }
+
+    async fn import_bundle(&self, bundle: StoreBundle) -> Result<ImportSummary, StoreError> {
+        let mut summary = ImportSummary::default();
+        let mut ops = Vec::new();
+        // Guards are held until the files are queued, so later writes land after them.
+        let (written, flushed) = {
+            let mut nodes = self
+                .nodes
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for node in bundle.nodes {
+                let key = node_key(&node.id);
+                if nodes.contains_key(&key) {
+                    summary.skipped += 1;
+                    continue;
+                }
+                ops.push(self.node_file(&node)?);
+                nodes.insert(key, node);
+                summary.nodes += 1;
+            }
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for proposal in bundle.proposals {
+                if proposals.contains_key(&proposal.id) {
+                    summary.skipped += 1;
+                    continue;
+                }
+                ops.push(self.proposal_file(&proposal)?);
+                proposals.insert(proposal.id.clone(), proposal);
+                summary.proposals += 1;
+            }
+            *self
+                .trace
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? =
+                TraceIndex::rebuild(proposals.values());
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for (proposal_id, list) in bundle.reviews {
+                if reviews.get(&proposal_id).is_some_and(|r| !r.is_empty()) {
+                    summary.skipped += list.len();
+                    continue;
+                }
+                summary.reviews += list.len();
+                ops.push(self.reviews_file(&proposal_id, &list)?);
+                reviews.insert(proposal_id, list);
+            }
+            let mut log = self
+                .audit_log
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let known: std::collections::HashSet<String> =
+                log.iter().map(|e| e.event_id.clone()).collect();
+            for event in bundle.audit {
+                if known.contains(&event.event_id) {
+                    summary.skipped += 1;
+                    continue;
+                }
+                self.writer.append_audit(event.clone());
+                log.push(event);
+                summary.audit_events += 1;
+            }
+            let mut rev = self
+                .revision_counter
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            *rev = (*rev).max(bundle.revision);
+            ops.push(self.revision_write(*rev)?);
+            (self.writer.commit(ops), self.writer.flush())
+        };
+        written.wait().await?;
+        flushed.wait().await?;
+        Ok(summary)
+    }
+
+    async fn append_audit(&self, event: AuditEvent) -> Result<(), StoreError> {
+        {
+            let mut log = self
+                .audit_log
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            // Queued under the lock so the file keeps the log's order.
+            self.writer.append_audit(event.clone());
+            log.push(event);
+        }
+        if self.options.audit_flush_interval_ms == 0 {
+            self.writer.flush().wait().await?;
+        }
+        Ok(())
     }
 
     async fn query_audit(
@@ -554,48 +1295,1019 @@ impl ContextStore for FileStore {
         to: Option<&str>,
         limit: Option<u32>,
         offset: Option<u32>,
-    ) -> Result<Vec<AuditEvent>, StoreError> {
+    ) -> Result<AuditQueryResult, StoreError> {
         let log = self
             .audit_log
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let filtered: Vec<&AuditEvent> = log
-            .iter()
-            .filter(|e| {
-                if let Some(a) = actor {
-                    if e.actor_id != a {
-                        return false;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let filter = AuditFilter {
+            actor,
+            action,
+            resource_id,
+            from,
+            to,
+        };
+        let limit = limit.unwrap_or(100);
+        let offset = offset.unwrap_or(0) as usize;
+        let filtered: Vec<&AuditEvent> = log.iter().filter(|e| filter.matches(e)).collect();
+        let total = filtered.len();
+        let events: Vec<AuditEvent> = filtered
+            .into_iter()
+            .skip(offset)
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        let has_more = offset + events.len() < total;
+        Ok(AuditQueryResult {
+            events,
+            total: total as u64,
+            limit,
+            offset: offset as u32,
+            has_more,
+        })
+    }
+
+    async fn save_export_job(&self, job: ExportJob) -> Result<(), StoreError> {
+        let written = {
+            let mut jobs = self
+                .export_jobs
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let written = self.writer.commit(vec![self.export_job_file(&job)?]);
+            jobs.insert(job.id.clone(), job);
+            written
+        };
+        written.wait().await
+    }
+
+    async fn get_export_job(&self, job_id: &str) -> Result<Option<ExportJob>, StoreError> {
+        let jobs = self
+            .export_jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(jobs.get(job_id).cloned())
+    }
+
+    async fn list_export_jobs(&self) -> Result<Vec<ExportJob>, StoreError> {
+        let jobs = self
+            .export_jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut list: Vec<ExportJob> = jobs.values().cloned().collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(list)
+    }
+
+    async fn put_export_artifact(&self, job_id: &str, data: Vec<u8>) -> Result<(), StoreError> {
+        // Artifacts can be large: written straight to disk, never cached in memory.
+        let path = self.exports_dir().join(format!("{}.data", job_id));
+        self.writer.write(path, data).wait().await
+    }
+
+    async fn get_export_artifact(&self, job_id: &str) -> Result<Option<Vec<u8>>, StoreError> {
+        let path = self.exports_dir().join(format!("{}.data", job_id));
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StoreError::io("read export artifact", e)),
+        }
+    }
+
+    async fn save_job(&self, job: JobRecord) -> Result<(), StoreError> {
+        let written = {
+            let mut jobs = self
+                .jobs
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let written = self.writer.commit(vec![self.job_file(&job)?]);
+            jobs.insert(job.id.clone(), job);
+            written
+        };
+        written.wait().await
+    }
+
+    async fn get_job(&self, job_id: &str) -> Result<Option<JobRecord>, StoreError> {
+        let jobs = self
+            .jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(jobs.get(job_id).cloned())
+    }
+
+    async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        kind: Option<&str>,
+    ) -> Result<Vec<JobRecord>, StoreError> {
+        let jobs = self
+            .jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut list: Vec<JobRecord> = jobs
+            .values()
+            .filter(|j| status.is_none_or(|s| j.status == s))
+            .filter(|j| kind.is_none_or(|k| j.kind == k))
+            .cloned()
+            .collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(list)
+    }
+
+    async fn claim_job(&self, kinds: &[&str], now: &str) -> Result<Option<JobRecord>, StoreError> {
+        let (claimed, written) = {
+            let mut jobs = self
+                .jobs
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let Some(job) = jobs
+                .values_mut()
+                .filter(|j| kinds.contains(&j.kind.as_str()) && j.is_due(now))
+                .min_by(|a, b| a.created_at.cmp(&b.created_at))
+            else {
+                return Ok(None);
+            };
+            job.status = JobStatus::Running;
+            job.attempts += 1;
+            job.started_at = Some(now.to_string());
+            let claimed = job.clone();
+            let written = self.writer.commit(vec![self.job_file(&claimed)?]);
+            (claimed, written)
+        };
+        written.wait().await?;
+        Ok(Some(claimed))
+    }
+    /// Counts and size of the in-memory cache; export artifacts stay on disk and are not
+    /// counted.
+    async fn status(&self) -> Result<StoreStatus, StoreError> {
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let reviews = self
+            .reviews
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let log = self
+            .audit_log
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let export_jobs = self
+            .export_jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let jobs = self
+            .jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+
+        let approx_bytes = nodes.values().map(json_size).sum::<usize>()
+            + proposals.values().map(json_size).sum::<usize>()
+            + reviews.values().flatten().map(json_size).sum::<usize>()
+            + log.iter().map(json_size).sum::<usize>()
+            + export_jobs.values().map(json_size).sum::<usize>()
+            + jobs.values().map(json_size).sum::<usize>();
+        Ok(StoreStatus {
+            nodes: nodes.len(),
+            proposals: proposals.len(),
+            reviews: reviews.values().map(Vec::len).sum(),
+            audit_events: log.len(),
+            audit_events_spilled: 0,
+            export_jobs: export_jobs.len(),
+            jobs: jobs.len(),
+            approx_bytes,
+            limits: None,
+        })
+    }
+
+    async fn compact(&self, options: &CompactOptions) -> Result<CompactReport, StoreError> {
+        let now = chrono::Utc::now();
+        let mut report = CompactReport::default();
+        let mut reclaimed = 0u64;
+        let file_len = |path: &Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+        let written = {
+            // Same lock order as reset and import_bundle.
+            let nodes = self
+                .nodes
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut ops = Vec::new();
+
+            // Files no record is stored under. Writes to these directories are queued
+            // under the locks held here, so none is in flight for a record we do not know.
+            let known: [(&str, PathBuf, &dyn Fn(&str) -> bool); 3] = [
+                ("nodes", self.nodes_dir(), &|key| nodes.contains_key(key)),
+                ("proposals", self.proposals_dir(), &|id| {
+                    proposals.contains_key(id)
+                }),
+                ("reviews", self.reviews_dir(), &|id| {
+                    reviews.contains_key(id)
+                }),
+            ];
+            for (name, dir, is_known) in known {
+                for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+                    let path = entry.path();
+                    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                    let is_record = path.extension().is_some_and(|ext| ext == "json");
+                    if !path.is_file() || (is_record && is_known(&stem)) {
+                        continue;
                     }
+                    reclaimed += file_len(&path);
+                    report.orphaned_files.push(format!(
+                        "{}/{}",
+                        name,
+                        entry.file_name().to_string_lossy()
+                    ));
+                    ops.push(FileOp::Remove { path, dir: false });
                 }
-                if let Some(act) = action {
-                    let action_str = serde_json::to_string(&e.action)
-                        .unwrap_or_default()
-                        .replace('"', "");
-                    if action_str != act {
-                        return false;
-                    }
+            }
+            report.orphaned_files.sort();
+
+            proposals.retain(|id, p| {
+                if !options.prunable(p, now) {
+                    return true;
                 }
-                if let Some(rid) = resource_id {
-                    if e.resource_id != rid {
-                        return false;
-                    }
+                report.pruned_proposals.push(id.clone());
+                false
+            });
+            report.pruned_proposals.sort();
+            for id in &report.pruned_proposals {
+                let path = self.proposals_dir().join(format!("{}.json", id));
+                reclaimed += file_len(&path);
+                ops.push(FileOp::Remove { path, dir: false });
+            }
+            // Reviews of pruned (or never stored) proposals.
+            reviews.retain(|id, list| {
+                if proposals.contains_key(id) {
+                    return true;
                 }
-                if let Some(f) = from {
-                    if e.timestamp.as_str() < f {
-                        return false;
-                    }
+                let path = self.reviews_dir().join(format!("{}.json", id));
+                reclaimed += file_len(&path);
+                report.pruned_reviews += list.len();
+                ops.push(FileOp::Remove { path, dir: false });
+                false
+            });
+            self.writer.commit(ops)
+        };
+        written.wait().await?;
+
+        // Rewrite the audit log when the file holds more than its events (unreadable
+        // lines, a torn tail). Queued batches are flushed first, so the rewrite holds
+        // them exactly once; the log lock keeps new events out until it is queued.
+        let rewritten = {
+            let log = self
+                .audit_log
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let lines = audit_lines(&log)?;
+            let on_disk = file_len(&self.audit_file());
+            if on_disk > lines.len() as u64 {
+                reclaimed += on_disk - lines.len() as u64;
+                report.audit_defragmented = true;
+                Some((
+                    self.writer.flush(),
+                    self.writer.write(self.audit_file(), lines),
+                ))
+            } else {
+                None
+            }
+        };
+        if let Some((flushed, written)) = rewritten {
+            flushed.wait().await?;
+            written.wait().await?;
+        }
+
+        report.reclaimed_bytes = reclaimed;
+        Ok(report)
+    }
+
+    async fn list_trash(&self) -> Result<Vec<ContextNode>, StoreError> {
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(trash::list(&nodes))
+    }
+
+    async fn restore_node(
+        &self,
+        node_id: &NodeId,
+        restored_by: &str,
+    ) -> Result<ContextNode, StoreError> {
+        let (restored, written) = {
+            let mut nodes = self
+                .nodes
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let restored = trash::restore(&mut nodes, node_id, restored_by)?;
+            let written = self.writer.commit(vec![self.node_file(&restored)?]);
+            (restored, written)
+        };
+        written.wait().await?;
+        Ok(restored)
+    }
+
+    async fn purge_trash(&self, deleted_before: &str) -> Result<Vec<NodeId>, StoreError> {
+        let (purged, written) = {
+            let mut nodes = self
+                .nodes
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut purged = Vec::new();
+            let mut ops = Vec::new();
+            for key in trash::purgeable(&nodes, deleted_before)? {
+                if let Some(node) = nodes.remove(&key) {
+                    let path = self.nodes_dir().join(format!("{}.json", key));
+                    ops.push(FileOp::Remove { path, dir: false });
+                    purged.push(node.id);
                 }
-                if let Some(t) = to {
-                    if e.timestamp.as_str() > t {
-                        return false;
-                    }
+            }
+            (purged, self.writer.commit(ops))
+        };
+        written.wait().await?;
+        Ok(purged)
+    }
+
+    async fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<Lease, StoreError> {
+        let mut leases = self
+            .leases
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        leases.acquire(name, holder, ttl, chrono::Utc::now())
+    }
+
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), StoreError> {
+        let mut leases = self
+            .leases
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        leases.release(name, holder);
+        Ok(())
+    }
+
+    async fn list_leases(&self, prefix: &str) -> Result<Vec<Lease>, StoreError> {
+        let leases = self
+            .leases
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(leases.live(prefix, chrono::Utc::now()))
+    }
+
+    async fn get_directory(&self) -> Result<Directory, StoreError> {
+        let directory = self
+            .directory
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(directory.clone())
+    }
+
+    async fn save_directory_user(&self, user: DirectoryUser) -> Result<(), StoreError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.update_directory(|d| d.put_user(user, &now)).await
+    }
+
+    async fn delete_directory_user(&self, id: &str) -> Result<DirectoryUser, StoreError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.update_directory(|d| d.remove_user(id, &now)).await
+    }
+
+    async fn save_directory_group(&self, group: DirectoryGroup) -> Result<(), StoreError> {
+        self.update_directory(|d| d.put_group(group)).await
+    }
+
+    async fn delete_directory_group(&self, id: &str) -> Result<DirectoryGroup, StoreError> {
+        self.update_directory(|d| d.remove_group(id)).await
+    }
+
+    async fn directory_access(&self, actor_id: &str) -> Result<Option<ActorAccess>, StoreError> {
+        let directory = self
+            .directory
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(directory.access(actor_id))
+    }
+
+    async fn get_preferences(&self, actor_id: &str) -> Result<Option<UserPreferences>, StoreError> {
+        let preferences = self
+            .preferences
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(preferences.get(actor_id).cloned())
+    }
+
+    async fn save_preferences(
+        &self,
+        actor_id: &str,
+        preferences: UserPreferences,
+    ) -> Result<(), StoreError> {
+        let written = {
+            let mut all = self
+                .preferences
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            all.insert(actor_id.to_string(), preferences);
+            let json = serde_json::to_string_pretty(&*all)
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            self.writer.commit(vec![FileOp::Write {
+                path: self.preferences_file(),
+                data: json.into_bytes(),
+            }])
+        };
+        written.wait().await
+    }
+
+    async fn get_delegations(&self, delegator: &str) -> Result<Vec<Delegation>, StoreError> {
+        let delegations = self
+            .delegations
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(delegations.get(delegator).cloned().unwrap_or_default())
+    }
+
+    async fn save_delegations(
+        &self,
+        delegator: &str,
+        delegations: Vec<Delegation>,
+    ) -> Result<(), StoreError> {
+        let written = {
+            let mut all = self
+                .delegations
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            all.insert(delegator.to_string(), delegations);
+            let json = serde_json::to_string_pretty(&*all)
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            self.writer.commit(vec![FileOp::Write {
+                path: self.delegations_file(),
+                data: json.into_bytes(),
+            }])
+        };
+        written.wait().await
+    }
+
+    async fn add_api_calls(
+        &self,
+        date: &str,
+        calls: &BTreeMap<String, u64>,
+    ) -> Result<(), StoreError> {
+        self.update_usage(|u| u.add_api_calls(date, calls)).await
+    }
+
+    async fn save_usage_rollup(&self, records: Vec<UsageRecord>) -> Result<(), StoreError> {
+        self.update_usage(|u| u.apply_rollup(records)).await
+    }
+
+    async fn list_usage(&self, month: &str) -> Result<Vec<UsageRecord>, StoreError> {
+        let usage = self
+            .usage
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(usage.month(month))
+    }
+
+    async fn list_outbox(&self, limit: usize) -> Result<Vec<OutboxEntry>, StoreError> {
+        let outbox = self
+            .outbox
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(outbox.values().take(limit).cloned().collect())
+    }
+
+    async fn save_outbox_entry(&self, entry: OutboxEntry) -> Result<(), StoreError> {
+        let written = {
+            let mut outbox = self
+                .outbox
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let written = self.writer.commit(vec![self.outbox_file(&entry)?]);
+            outbox.insert(entry.id.clone(), entry);
+            written
+        };
+        written.wait().await
+    }
+
+    async fn remove_outbox_entry(&self, id: &str) -> Result<(), StoreError> {
+        let written = {
+            let mut outbox = self
+                .outbox
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            if outbox.remove(id).is_none() {
+                return Ok(());
+            }
+            self.writer.commit(vec![FileOp::Remove {
+                path: self.outbox_dir().join(format!("{}.json", id)),
+                dir: false,
+            }])
+        };
+        written.wait().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+    use crate::types::{
+        AuditAction, AuditOutcome, Operation, ProposalMetadata, ReviewAction, UpdateChanges,
+    };
+
+    fn event(actor: &str) -> AuditEvent {
+        AuditEvent::new(
+            actor,
+            "human",
+            AuditAction::ProposalCreated,
+            "p-1",
+            AuditOutcome::Success,
+        )
+    }
+
+    #[tokio::test]
+    async fn audit_batches_reach_disk_and_legacy_log_is_converted() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("audit.json"),
+            serde_json::to_string(&vec![event("legacy")]).unwrap(),
+        )
+        .unwrap();
+        let options = FileStoreOptions {
+            audit_flush_interval_ms: 60_000,
+            ..Default::default()
+        };
+
+        let store = FileStore::with_options(&dir, options.clone()).unwrap();
+        assert!(!dir.join("audit.json").exists());
+        store.append_audit(event("a")).await.unwrap();
+        store.append_audit(event("b")).await.unwrap();
+        // Still batched: only the converted legacy event is on disk.
+        assert_eq!(read_audit_lines(&store.audit_file()).unwrap().len(), 1);
+        store.writer.flush().wait().await.unwrap();
+        assert_eq!(read_audit_lines(&store.audit_file()).unwrap().len(), 3);
+
+        let job = JobRecord::new("export", serde_json::Value::Null);
+        store.save_job(job.clone()).await.unwrap();
+        assert!(dir.join("jobs").join(format!("{}.json", job.id)).exists());
+        store.append_audit(event("c")).await.unwrap();
+        drop(store);
+
+        let reopened = FileStore::with_options(&dir, options).unwrap();
+        let actors: Vec<String> = reopened
+            .query_audit(None, None, None, None, None, None, None)
+            .await
+            .unwrap()
+            .events
+            .into_iter()
+            .map(|e| e.actor_id)
+            .collect();
+        assert_eq!(actors, ["legacy", "a", "b", "c"]);
+        assert!(reopened.get_job(&job.id).await.unwrap().is_some());
+        drop(reopened);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn proposal(id: &str, operations: Vec<Operation>) -> Proposal {
+        Proposal {
+            id: id.to_string(),
+            status: ProposalStatus::Open,
+            operations,
+            metadata: ProposalMetadata {
+                created_at: "2026-03-01T00:00:00Z".to_string(),
+                created_by: "alice".to_string(),
+                modified_at: "2026-03-01T00:00:00Z".to_string(),
+                modified_by: "alice".to_string(),
+                rationale: None,
+                required_approvers: None,
+                approved_by: None,
+                base_versions: None,
+                force_status_transitions: None,
+                forge: None,
+                complexity: None,
+                authors: None,
+                superseded_by: None,
+                conflict_resolutions: None,
+            },
+            comments: None,
+            relations: None,
+            applied: None,
+        }
+    }
+
+    fn update(node: &str, content: &str) -> Operation {
+        Operation::Update {
+            id: format!("op-{}", content),
+            order: 1,
+            node_id: NodeId {
+                id: node.to_string(),
+                namespace: None,
+            },
+            changes: UpdateChanges {
+                content: Some(content.to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn reconciliation_matches_the_memory_backend() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        let file = FileStore::new(&dir).unwrap();
+        let memory = crate::store::InMemoryStore::new();
+        let node: ContextNode = serde_json::from_value(serde_json::json!({
+            "id": { "id": "n1" },
+            "type": "goal",
+            "status": "accepted",
+            "content": "v1",
+            "metadata": {
+                "createdAt": "2026-03-01T00:00:00Z",
+                "createdBy": "alice",
+                "modifiedAt": "2026-03-01T00:00:00Z",
+                "modifiedBy": "alice",
+                "version": 1
+            }
+        }))
+        .unwrap();
+        let mut stale = proposal("p-a", vec![update("n1", "a")]);
+        stale.metadata.base_versions = Some([("n1".to_string(), 1)].into());
+
+        for store in [&file as &dyn ContextStore, &memory] {
+            let mut seed = proposal(
+                "p-seed",
+                vec![Operation::Create {
+                    id: "op-seed".to_string(),
+                    order: 1,
+                    node: node.clone(),
+                }],
+            );
+            seed.status = ProposalStatus::Accepted;
+            store.create_proposal(seed).await.unwrap();
+            store.apply_proposal("p-seed", "alice").await.unwrap();
+            store.create_proposal(stale.clone()).await.unwrap();
+            store
+                .create_proposal(proposal("p-b", vec![update("n1", "b")]))
+                .await
+                .unwrap();
+            store
+                .create_proposal(proposal("p-c", vec![update("n2", "c")]))
+                .await
+                .unwrap();
+            let mut newer = proposal("p-d", vec![update("n1", "d")]);
+            newer.status = ProposalStatus::Accepted;
+            store.create_proposal(newer).await.unwrap();
+            store.apply_proposal("p-d", "alice").await.unwrap();
+        }
+
+        let ids = ["p-a".to_string(), "p-b".to_string(), "p-c".to_string()];
+        let mut results = Vec::new();
+        for store in [&file as &dyn ContextStore, &memory] {
+            let conflicts = store.detect_conflicts("p-a").await.unwrap();
+            assert_eq!(conflicts.needs_resolution, ["p-b"]);
+            assert_eq!(conflicts.mergeable, ["p-c"]);
+            assert!(store.is_proposal_stale("p-a").await.unwrap());
+            assert!(!store.is_proposal_stale("p-b").await.unwrap());
+            let merge = store.merge_proposals(&ids).await.unwrap();
+            assert_eq!((merge.conflicts.len(), merge.auto_merged.len()), (1, 1));
+            results.push(serde_json::to_value((conflicts, merge)).unwrap());
+        }
+        assert_eq!(results[0], results[1]);
+        drop(file);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn node_queries_match_the_memory_backend() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        let file = FileStore::new(&dir).unwrap();
+        let memory = crate::store::InMemoryStore::new();
+        let node = |id: &str,
+                    namespace: Option<&str>,
+                    ty: &str,
+                    content: &str,
+                    by: &str,
+                    tags: &[&str]| {
+            let node: ContextNode = serde_json::from_value(serde_json::json!({
+                "id": { "id": id, "namespace": namespace },
+                "type": ty,
+                "status": "accepted",
+                "content": content,
+                "metadata": {
+                    "createdAt": "2026-03-01T00:00:00Z",
+                    "createdBy": by,
+                    "modifiedAt": "2026-03-01T00:00:00Z",
+                    "modifiedBy": by,
+                    "tags": tags,
+                    "version": 1
                 }
-                true
+            }))
+            .unwrap();
+            Operation::Create {
+                id: format!("op-{}", id),
+                order: 1,
+                node,
+            }
+        };
+        let mut seed = proposal(
+            "p-seed",
+            vec![
+                node(
+                    "g1",
+                    None,
+                    "goal",
+                    "Ship the Rust server",
+                    "alice",
+                    &["server"],
+                ),
+                node(
+                    "d1",
+                    None,
+                    "decision",
+                    "Use axum for HTTP",
+                    "bob",
+                    &["server", "http"],
+                ),
+                node(
+                    "d2",
+                    Some("ops"),
+                    "decision",
+                    "Deploy with rust images",
+                    "alice",
+                    &["ops"],
+                ),
+                node("r1", Some("ops"), "risk", "Disk fills up", "bob", &[]),
+            ],
+        );
+        seed.status = ProposalStatus::Accepted;
+        for store in [&file as &dyn ContextStore, &memory] {
+            store.create_proposal(seed.clone()).await.unwrap();
+            store.apply_proposal("p-seed", "alice").await.unwrap();
+        }
+
+        let cases = [
+            (
+                serde_json::json!({ "type": ["decision"] }),
+                vec!["d1", "d2"],
+            ),
+            (serde_json::json!({ "search": "RUST" }), vec!["g1", "d2"]),
+            (
+                serde_json::json!({ "tags": ["server", "http"] }),
+                vec!["d1"],
+            ),
+            (serde_json::json!({ "namespace": "ops" }), vec!["d2", "r1"]),
+            (serde_json::json!({ "created_by": "bob" }), vec!["d1", "r1"]),
+            (
+                serde_json::json!({ "type": ["decision", "risk"], "namespace": "ops", "created_by": "bob" }),
+                vec!["r1"],
+            ),
+            (
+                serde_json::json!({ "limit": 2, "offset": 1 }),
+                vec!["g1", "d2"],
+            ),
+        ];
+        for (query, expected) in cases {
+            let query: NodeQuery = serde_json::from_value(query).unwrap();
+            for store in [&file as &dyn ContextStore, &memory] {
+                let result = store.query_nodes(query.clone()).await.unwrap();
+                let ids: Vec<&str> = result.nodes.iter().map(|n| n.id.id.as_str()).collect();
+                assert_eq!(ids, expected, "{:?}", query);
+            }
+        }
+        drop(file);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn compaction_prunes_orphans_closed_proposals_and_audit_garbage() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(&dir).unwrap();
+        let mut closed = proposal("p-closed", Vec::new());
+        closed.status = ProposalStatus::Withdrawn;
+        closed.metadata.modified_at = "2025-01-01T00:00:00Z".to_string();
+        store.create_proposal(closed).await.unwrap();
+        store
+            .create_proposal(proposal("p-open", Vec::new()))
+            .await
+            .unwrap();
+        store.append_audit(event("a")).await.unwrap();
+        store.writer.flush().wait().await.unwrap();
+        drop(store);
+
+        std::fs::create_dir_all(dir.join("nodes")).unwrap();
+        std::fs::create_dir_all(dir.join("reviews")).unwrap();
+        std::fs::write(dir.join("nodes/ghost.json"), "{}").unwrap();
+        std::fs::write(dir.join("proposals/p-open.tmp"), "{").unwrap();
+        std::fs::write(
+            dir.join("reviews/p-gone.json"),
+            r#"[{"id":"r-1","proposalId":"p-gone","reviewer":"bob","reviewedAt":"2026-01-01T00:00:00Z","action":"accept"}]"#,
+        )
+        .unwrap();
+        let mut audit = std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join("audit.jsonl"))
+            .unwrap();
+        std::io::Write::write_all(&mut audit, b"not json\n").unwrap();
+        drop(audit);
+
+        let store = FileStore::new(&dir).unwrap();
+        let report = store.compact(&CompactOptions::default()).await.unwrap();
+        assert_eq!(report.pruned_proposals, ["p-closed"]);
+        assert_eq!(report.pruned_reviews, 1);
+        assert_eq!(
+            report.orphaned_files,
+            ["nodes/ghost.json", "proposals/p-open.tmp"]
+        );
+        assert!(report.audit_defragmented);
+        assert!(report.reclaimed_bytes > 0);
+        assert!(!dir.join("proposals/p-closed.json").exists());
+        assert!(!dir.join("reviews/p-gone.json").exists());
+        assert!(dir.join("proposals/p-open.json").exists());
+        assert_eq!(read_audit_lines(&store.audit_file()).unwrap().len(), 1);
+        assert!(!std::fs::read_to_string(dir.join("audit.jsonl"))
+            .unwrap()
+            .contains("not json"));
+
+        let again = store.compact(&CompactOptions::default()).await.unwrap();
+        assert!(again.pruned_proposals.is_empty() && again.orphaned_files.is_empty());
+        assert_eq!(again.reclaimed_bytes, 0);
+        drop(store);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn comments_survive_a_restart() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(&dir).unwrap();
+        store
+            .create_proposal(proposal("p-1", Vec::new()))
+            .await
+            .unwrap();
+        let comment: Comment = serde_json::from_value(serde_json::json!({
+            "id": "c-1",
+            "content": "Needs a rationale",
+            "author": "bob",
+            "createdAt": "2026-03-02T00:00:00Z"
+        }))
+        .unwrap();
+        assert!(matches!(
+            store.add_proposal_comment("missing", comment.clone()).await,
+            Err(StoreError {
+                code: StoreErrorCode::NotFound,
+                ..
             })
-            .collect();
-        let off = offset.unwrap_or(0) as usize;
-        let lim = limit.unwrap_or(100) as usize;
-        let page = filtered.into_iter().skip(off).take(lim).cloned().collect();
-        Ok(page)
+        ));
+        store.add_proposal_comment("p-1", comment).await.unwrap();
+        drop(store);
+
+        let reopened = FileStore::new(&dir).unwrap();
+        let comments = reopened.get_proposal_comments("p-1").await.unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].content, "Needs a rationale");
+        drop(reopened);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn node_proposals_are_rebuilt_on_restart() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        let store = FileStore::new(&dir).unwrap();
+        let node: ContextNode = serde_json::from_value(serde_json::json!({
+            "id": { "id": "n1" }, "type": "goal", "status": "accepted", "content": "v1",
+            "metadata": {
+                "createdAt": "2026-03-01T00:00:00Z", "createdBy": "alice",
+                "modifiedAt": "2026-03-01T00:00:00Z", "modifiedBy": "alice", "version": 1
+            }
+        }))
+        .unwrap();
+        let seed = proposal(
+            "p-seed",
+            vec![Operation::Create {
+                id: "op-seed".to_string(),
+                order: 1,
+                node,
+            }],
+        );
+        for mut p in [seed, proposal("p-edit", vec![update("n1", "v2")])] {
+            p.status = ProposalStatus::Accepted;
+            let id = p.id.clone();
+            store.create_proposal(p).await.unwrap();
+            store.apply_proposal(&id, "bob").await.unwrap();
+        }
+        let n1 = NodeId {
+            id: "n1".to_string(),
+            namespace: None,
+        };
+        let before = store.get_node_proposals(&n1).await.unwrap();
+        assert_eq!(before.len(), 2);
+        drop(store);
+
+        let reopened = FileStore::new(&dir).unwrap();
+        let after = reopened.get_node_proposals(&n1).await.unwrap();
+        let ids: Vec<&str> = after.iter().map(|p| p.proposal_id.as_str()).collect();
+        assert_eq!(ids, ["p-seed", "p-edit"]);
+        assert_eq!(after[1].operations[0].operation_id, "op-v2");
+        drop(reopened);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn both_backends_enforce_the_proposal_lifecycle() {
+        let dir = std::env::temp_dir().join(format!("tl-file-store-{}", uuid::Uuid::new_v4()));
+        let file = FileStore::new(&dir).unwrap();
+        let memory = crate::store::InMemoryStore::new();
+        let review = |action| Review {
+            id: "r-1".to_string(),
+            proposal_id: "p-1".to_string(),
+            reviewer: "bob".to_string(),
+            reviewer_role: None,
+            reviewer_groups: None,
+            reviewed_at: "2026-03-02T00:00:00Z".to_string(),
+            action,
+            comment: None,
+            comments: None,
+            operation_ids: None,
+            is_approval: None,
+            on_behalf_of: None,
+        };
+
+        for store in [&file as &dyn ContextStore, &memory] {
+            store
+                .create_proposal(proposal("p-1", Vec::new()))
+                .await
+                .unwrap();
+            let patch = ProposalPatch::status(ProposalStatus::Applied);
+            assert!(matches!(
+                store.update_proposal("p-1", patch).await,
+                Err(StoreError {
+                    code: StoreErrorCode::InvalidTransition,
+                    ..
+                })
+            ));
+            assert!(matches!(
+                store.apply_proposal("p-1", "bob").await,
+                Err(StoreError {
+                    code: StoreErrorCode::InvalidTransition,
+                    ..
+                })
+            ));
+
+            store
+                .submit_review(review(ReviewAction::Accept))
+                .await
+                .unwrap();
+            let accepted = store.get_proposal("p-1").await.unwrap().unwrap();
+            assert_eq!(accepted.status, ProposalStatus::Accepted);
+            assert!(matches!(
+                store.submit_review(review(ReviewAction::Reject)).await,
+                Err(StoreError {
+                    code: StoreErrorCode::InvalidTransition,
+                    ..
+                })
+            ));
+            assert!(matches!(
+                store.withdraw_proposal("p-1").await,
+                Err(StoreError {
+                    code: StoreErrorCode::InvalidTransition,
+                    ..
+                })
+            ));
+
+            store.apply_proposal("p-1", "bob").await.unwrap();
+            let applied = store.get_proposal("p-1").await.unwrap().unwrap();
+            let meta = applied.applied.unwrap();
+            assert_eq!(meta.applied_from_review_id.as_deref(), Some("r-1"));
+            assert_eq!(
+                (
+                    meta.previous_revision_id.as_str(),
+                    meta.applied_to_revision_id.as_str()
+                ),
+                ("rev_0", "rev_1")
+            );
+            let reopen = ProposalPatch::status(ProposalStatus::Open);
+            assert!(matches!(
+                store.update_proposal("p-1", reopen).await,
+                Err(StoreError {
+                    code: StoreErrorCode::InvalidTransition,
+                    ..
+                })
+            ));
+        }
+        drop(file);
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -1,61 +1,110 @@
 //! In-memory implementation of ContextStore.
 //! Mirrors src/store/in-memory-store.ts (subset).
+//!
+//! Unbounded by default; [`InMemoryStore::with_limits`] caps nodes, proposals and audit
+//! events (see `crate::store::limits`).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
 use std::sync::RwLock;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
+use crate::store::audit_index::{AuditFilter, AuditLog};
+use crate::store::batch::{self, WriteBatch};
+use crate::store::blame;
+use crate::store::bundle::{ImportSummary, StoreBundle};
+use crate::store::compact::{CompactOptions, CompactReport};
 use crate::store::context_store::{ContextStore, StoreError};
+use crate::store::directory::{ActorAccess, Directory, DirectoryGroup, DirectoryUser};
+use crate::store::lease::{Lease, LeaseTable, NODE_LOCK_PREFIX};
+use crate::store::lifecycle;
+use crate::store::limits::{json_size, AuditOverflow, MemoryLimits, StoreStatus};
+use crate::store::node_index::NodeTable;
+use crate::store::outbox::OutboxEntry;
+use crate::store::reconcile;
+use crate::store::trace::{NodeProposal, TraceIndex};
+use crate::store::trash;
+use crate::store::usage::{UsageLedger, UsageRecord};
 use crate::types::{
-    AppliedMetadata, AuditEvent, Comment, ConflictDetectionResult, ConflictSeverity, ContextNode,
-    FieldChange, MergeConflictField, MergeResult, NodeId, NodeQuery, NodeQueryResult, NodeStatus,
-    Operation, Proposal, ProposalConflict, ProposalQuery, ProposalStatus, Review, ReviewAction,
+    AuditEvent, AuditQueryResult, Comment, ConflictDetectionResult, ContextNode, Delegation,
+    ExportJob, FieldBlame, JobRecord, JobStatus, MergeResult, NodeId, NodeQuery, NodeQueryResult,
+    NodeStatus, Operation, Proposal, ProposalPatch, ProposalQuery, ProposalStatus, Review,
+    UserPreferences,
 };
 
 fn node_key(id: &NodeId) -> String {
     id.key()
 }
 
-fn operations_node_keys(ops: &[Operation]) -> std::collections::HashSet<String> {
-    let mut keys = std::collections::HashSet::new();
-    for op in ops {
-        match op {
-            Operation::Create { node, .. } => {
-                keys.insert(node_key(&node.id));
-            }
-            Operation::Update { node_id, .. }
-            | Operation::Delete { node_id, .. }
-            | Operation::StatusChange { node_id, .. } => {
-                keys.insert(node_key(node_id));
-            }
-        }
-    }
-    keys
-}
-
-fn key_to_node_id(key: &str) -> Option<NodeId> {
-    if key.contains(':') {
-        let mut it = key.splitn(2, ':');
-        let namespace = it.next().map(String::from);
-        let id = it.next().map(String::from)?;
-        Some(NodeId { id, namespace })
-    } else {
-        Some(NodeId {
-            id: key.to_string(),
-            namespace: None,
-        })
-    }
-}
-
 pub struct InMemoryStore {
-    nodes: RwLock<HashMap<String, ContextNode>>,
+    /// Nodes with secondary indexes (status, type, namespace, tag).
+    nodes: RwLock<NodeTable>,
     proposals: RwLock<HashMap<String, Proposal>>,
     reviews: RwLock<HashMap<String, Vec<Review>>>,
+    /// Node key -> applied proposals that touched it (see `store::trace`).
+    trace: RwLock<TraceIndex>,
     /// Incremented on each apply; used for appliedToRevisionId / previousRevisionId.
     revision_counter: RwLock<u64>,
     /// Immutable audit log (append-only).
-    audit_log: RwLock<Vec<AuditEvent>>,
+    audit_log: RwLock<AuditLog>,
+    export_jobs: RwLock<HashMap<String, ExportJob>>,
+    export_artifacts: RwLock<HashMap<String, Vec<u8>>>,
+    jobs: RwLock<HashMap<String, JobRecord>>,
+    leases: RwLock<LeaseTable>,
+    /// SCIM users and groups (see `store::directory`); kept across `reset`.
+    directory: RwLock<Directory>,
+    /// Actor id -> preferences; kept across `reset`.
+    /// Daily usage per workspace (see `store::usage`); kept across `reset`.
+    usage: RwLock<UsageLedger>,
+    preferences: RwLock<HashMap<String, UserPreferences>>,
+    /// Delegator id -> review delegations; kept across `reset`.
+    delegations: RwLock<HashMap<String, Vec<Delegation>>>,
+    /// Undelivered outbox entries by id (see `store::outbox`); kept across `reset`.
+    outbox: RwLock<BTreeMap<String, OutboxEntry>>,
+    limits: MemoryLimits,
+    /// Where this instance spills audit pages; None = spilling unavailable.
+    spill_dir: Option<PathBuf>,
+    audit_spill: RwLock<AuditSpill>,
+}
+
+/// Audit pages written to disk by this instance, oldest first.
+#[derive(Default)]
+struct AuditSpill {
+    pages: Vec<PathBuf>,
+    events: usize,
+    /// Pages written so far, including ones since merged by `compact`; numbers the next.
+    written: usize,
+}
+
+/// Events of spilled audit pages, oldest first. Unreadable pages and lines are logged
+/// and skipped.
+fn read_spilled(pages: &[PathBuf]) -> impl Iterator<Item = AuditEvent> + '_ {
+    pages.iter().flat_map(|path| {
+        let file = std::fs::File::open(path)
+            .inspect_err(|e| tracing::warn!(page = ?path, error = %e, "cannot read audit page"));
+        file.into_iter()
+            .flat_map(|f| std::io::BufReader::new(f).lines())
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str(&line).ok())
+    })
+}
+
+fn check_capacity(
+    what: &str,
+    max: Option<usize>,
+    current: usize,
+    adding: usize,
+) -> Result<(), StoreError> {
+    match max {
+        Some(max) if current + adding > max => Err(StoreError::capacity_exceeded(format!(
+            "{} limit is {} ({} stored, {} more requested)",
+            what, max, current, adding
+        ))),
+        _ => Ok(()),
+    }
 }
 
 impl Default for InMemoryStore {
@@ -67,19 +116,159 @@ impl Default for InMemoryStore {
 impl InMemoryStore {
     pub fn new() -> Self {
         Self {
-            nodes: RwLock::new(HashMap::new()),
+            nodes: RwLock::new(NodeTable::default()),
             proposals: RwLock::new(HashMap::new()),
-            audit_log: RwLock::new(Vec::new()),
+            audit_log: RwLock::new(AuditLog::default()),
             reviews: RwLock::new(HashMap::new()),
+            trace: RwLock::new(TraceIndex::default()),
             revision_counter: RwLock::new(0),
+            export_jobs: RwLock::new(HashMap::new()),
+            export_artifacts: RwLock::new(HashMap::new()),
+            jobs: RwLock::new(HashMap::new()),
+            leases: RwLock::new(LeaseTable::default()),
+            directory: RwLock::new(Directory::default()),
+            usage: RwLock::new(UsageLedger::default()),
+            preferences: RwLock::new(HashMap::new()),
+            delegations: RwLock::new(HashMap::new()),
+            outbox: RwLock::new(BTreeMap::new()),
+            limits: MemoryLimits::default(),
+            spill_dir: None,
+            audit_spill: RwLock::new(AuditSpill::default()),
         }
     }
 
+    /// A store bounded by `limits`. Spilled audit pages go to a new subdirectory of
+    /// `spill_root`, created on the first spill.
+    pub fn with_limits(limits: MemoryLimits, spill_root: PathBuf) -> Self {
+        let instance = format!(
+            "{}-{}",
+            chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        Self {
+            limits,
+            spill_dir: Some(spill_root.join(instance)),
+            ..Self::new()
+        }
+    }
+
+    /// Append to the audit log, applying `max_audit_events` and the overflow policy.
+    fn push_audit(&self, log: &mut AuditLog, event: AuditEvent) -> Result<(), StoreError> {
+        let Some(max) = self.limits.max_audit_events else {
+            log.push(event);
+            return Ok(());
+        };
+        let spill_dir = match (self.limits.audit_overflow, &self.spill_dir) {
+            (AuditOverflow::Spill, Some(dir)) => dir,
+            _ => {
+                check_capacity("audit event", Some(max), log.len(), 1).inspect_err(
+                    |e| tracing::warn!(action = ?event.action, error = %e, "audit event refused"),
+                )?;
+                log.push(event);
+                return Ok(());
+            }
+        };
+        if log.len() < max {
+            log.push(event);
+            return Ok(());
+        }
+
+        // Move the oldest page (at least enough to make room) to disk, then append.
+        let count = self
+            .limits
+            .audit_page_size
+            .max(log.len() + 1 - max)
+            .min(log.len());
+        let mut spill = self
+            .audit_spill
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let path = spill_dir.join(format!("page-{:06}.jsonl", spill.written + 1));
+        let write_page = || -> std::io::Result<()> {
+            std::fs::create_dir_all(spill_dir)?;
+            let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            for event in log.iter().take(count) {
+                serde_json::to_writer(&mut out, event)?;
+                out.write_all(b"\n")?;
+            }
+            out.flush()
+        };
+        write_page().map_err(|e| {
+            StoreError::internal(format!("cannot spill audit page to {:?}: {}", path, e))
+        })?;
+        log.drop_oldest(count);
+        log.push(event);
+        spill.pages.push(path);
+        spill.events += count;
+        spill.written += 1;
+        tracing::info!(
+            events = count,
+            pages = spill.pages.len(),
+            "audit events spilled to disk"
+        );
+        Ok(())
+    }
+
+    /// Remove files in the spill directory that are not pages (left by a failed spill)
+    /// and merge the pages into one. Returns the bytes reclaimed.
+    fn compact_spill(&self, report: &mut CompactReport) -> Result<u64, StoreError> {
+        let Some(spill_dir) = &self.spill_dir else {
+            return Ok(0);
+        };
+        let mut spill = self
+            .audit_spill
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut reclaimed = 0;
+        for entry in std::fs::read_dir(spill_dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if spill.pages.contains(&path) || !path.is_file() {
+                continue;
+            }
+            let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            std::fs::remove_file(&path)
+                .map_err(|e| StoreError::internal(format!("remove {:?}: {}", path, e)))?;
+            reclaimed += len;
+            report
+                .orphaned_files
+                .push(entry.file_name().to_string_lossy().to_string());
+        }
+        report.orphaned_files.sort();
+
+        if spill.pages.len() < 2 {
+            return Ok(reclaimed);
+        }
+        let file_len = |path: &PathBuf| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let before: u64 = spill.pages.iter().map(file_len).sum();
+        let path = spill_dir.join(format!("page-{:06}.jsonl", spill.written + 1));
+        let mut events = 0;
+        let mut write_page = || -> std::io::Result<()> {
+            let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            for event in read_spilled(&spill.pages) {
+                serde_json::to_writer(&mut out, &event)?;
+                out.write_all(b"\n")?;
+                events += 1;
+            }
+            out.flush()
+        };
+        write_page().map_err(|e| {
+            StoreError::internal(format!("cannot merge audit pages into {:?}: {}", path, e))
+        })?;
+        for page in std::mem::replace(&mut spill.pages, vec![path.clone()]) {
+            let _ = std::fs::remove_file(page);
+        }
+        spill.events = events;
+        spill.written += 1;
+        report.audit_defragmented = true;
+        Ok(reclaimed + before.saturating_sub(file_len(&path)))
+    }
+
     fn apply_operation(
-        nodes: &mut HashMap<String, ContextNode>,
+        nodes: &mut NodeTable,
         op: &Operation,
         modified_at: &str,
         modified_by: &str,
+        change: &FieldBlame,
     ) -> Result<(), StoreError> {
         match op {
             Operation::Create { node, .. } => {
@@ -90,36 +279,36 @@ impl InMemoryStore {
                 node.metadata.version += 1;
                 // Content fingerprinting: SHA-256 hash for IP protection
                 node.metadata.content_hash = Some(crate::sensitivity::content_hash(&node.content));
+                blame::record(&mut node, op, change);
                 nodes.insert(key, node);
             }
             Operation::Update {
                 node_id, changes, ..
             } => {
                 let key = node_key(node_id);
-                let existing = nodes
-                    .get_mut(&key)
-                    .ok_or_else(|| StoreError::NotFound(format!("node {}", key)))?;
-                existing.metadata.modified_at = modified_at.to_string();
-                existing.metadata.modified_by = modified_by.to_string();
-                existing.metadata.version += 1;
-                if let Some(ref c) = changes.content {
-                    existing.content = c.clone();
-                    existing.description = Some(c.clone());
-                    // Recompute content hash on content change
-                    existing.metadata.content_hash = Some(crate::sensitivity::content_hash(c));
-                }
-                if let Some(s) = changes.status {
-                    existing.status = s;
+                let found = nodes.modify(&key, |existing| {
+                    existing.metadata.modified_at = modified_at.to_string();
+                    existing.metadata.modified_by = modified_by.to_string();
+                    existing.metadata.version += 1;
+                    changes.apply_to(existing);
+                    if let Some(ref c) = changes.content {
+                        if changes.description.is_none() {
+                            existing.description = Some(c.clone());
+                        }
+                        // Recompute content hash on content change
+                        existing.metadata.content_hash = Some(crate::sensitivity::content_hash(c));
+                    }
+                    blame::record(existing, op, change);
+                });
+                if !found {
+                    return Err(StoreError::not_found(format!("node {}", key)));
                 }
             }
             Operation::Delete { node_id, .. } => {
                 let key = node_key(node_id);
-                if let Some(n) = nodes.get_mut(&key) {
-                    n.status = NodeStatus::Rejected;
-                    n.metadata.modified_at = modified_at.to_string();
-                    n.metadata.modified_by = modified_by.to_string();
-                    n.metadata.version += 1;
-                }
+                nodes.modify(&key, |n| {
+                    trash::tombstone(n, modified_by, modified_at, &change.proposal_id)
+                });
             }
             Operation::StatusChange {
                 node_id,
@@ -127,16 +316,77 @@ impl InMemoryStore {
                 ..
             } => {
                 let key = node_key(node_id);
-                if let Some(n) = nodes.get_mut(&key) {
+                nodes.modify(&key, |n| {
                     n.status = *new_status;
                     n.metadata.modified_at = modified_at.to_string();
                     n.metadata.modified_by = modified_by.to_string();
                     n.metadata.version += 1;
-                }
+                    blame::record(n, op, change);
+                });
             }
         }
         Ok(())
     }
+
+    /// Refuse an apply of `proposal` the nodes do not allow: operations on trashed nodes,
+    /// status transitions it does not force, or more nodes than `max_nodes`.
+    fn check_apply(&self, nodes: &NodeTable, proposal: &Proposal) -> Result<(), StoreError> {
+        let forced = proposal.metadata.force_status_transitions == Some(true);
+        trash::check_operations(nodes, &proposal.operations)?;
+        reconcile::check_status_transitions(
+            &proposal.operations,
+            |key| nodes.get(key).map(|n| n.status),
+            forced,
+        )?;
+        reconcile::check_task_transitions(
+            &proposal.operations,
+            |key| nodes.get(key).and_then(|n| n.state),
+            forced,
+        )?;
+        if self.limits.max_nodes.is_some() {
+            let created: std::collections::HashSet<String> = proposal
+                .operations
+                .iter()
+                .filter_map(|op| match op {
+                    Operation::Create { node, .. } => Some(node_key(&node.id)),
+                    _ => None,
+                })
+                .filter(|key| !nodes.contains_key(key))
+                .collect();
+            check_capacity("node", self.limits.max_nodes, nodes.len(), created.len())?;
+        }
+        Ok(())
+    }
+
+    /// Write `proposal`'s operations to the nodes, in their order, and mark it applied at
+    /// store revision `previous + 1`.
+    fn apply(
+        nodes: &mut NodeTable,
+        proposal: &mut Proposal,
+        applied_by: &str,
+        review_id: Option<String>,
+        previous: u64,
+    ) -> Result<(), StoreError> {
+        let mut sorted_ops = proposal.operations.clone();
+        sorted_ops.sort_by_key(|o| match o {
+            Operation::Create { order, .. }
+            | Operation::Update { order, .. }
+            | Operation::Delete { order, .. }
+            | Operation::StatusChange { order, .. } => *order,
+        });
+        let now = chrono::Utc::now().to_rfc3339();
+        let change = blame::change(
+            &proposal.id,
+            &proposal.metadata.created_by,
+            applied_by,
+            &now,
+        );
+        for op in &sorted_ops {
+            InMemoryStore::apply_operation(nodes, op, &now, applied_by, &change)?;
+        }
+        lifecycle::mark_applied(proposal, applied_by, review_id, previous);
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -146,7 +396,7 @@ impl ContextStore for InMemoryStore {
         let nodes = self
             .nodes
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         Ok(nodes.get(&key).cloned())
     }
 
@@ -154,51 +404,15 @@ impl ContextStore for InMemoryStore {
         let nodes = self
             .nodes
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut list: Vec<ContextNode> = nodes.values().cloned().collect();
-
-        if let Some(ref statuses) = query.status {
-            list.retain(|n| statuses.contains(&n.status));
-        }
-        if let Some(ref types) = query.r#type {
-            list.retain(|n| types.contains(&n.node_type));
-        }
-        if let Some(ref search) = query.search {
-            let s = search.to_lowercase();
-            list.retain(|n| {
-                n.content.to_lowercase().contains(&s)
-                    || n.title
-                        .as_ref()
-                        .map(|t| t.to_lowercase().contains(&s))
-                        .unwrap_or(false)
-                    || n.description
-                        .as_ref()
-                        .map(|d| d.to_lowercase().contains(&s))
-                        .unwrap_or(false)
-            });
-        }
-
-        let total = list.len() as u64;
-        let limit = query.limit.unwrap_or(50).min(1000);
-        let offset = query.offset.unwrap_or(0) as usize;
-        let end = (offset + limit as usize).min(list.len());
-        list = list[offset..end].to_vec();
-        let has_more = (offset + list.len()) < total as usize;
-
-        Ok(NodeQueryResult {
-            nodes: list,
-            total,
-            limit,
-            offset: offset as u32,
-            has_more,
-        })
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(nodes.query(&query))
     }
 
     async fn get_proposal(&self, proposal_id: &str) -> Result<Option<Proposal>, StoreError> {
         let proposals = self
             .proposals
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         Ok(proposals.get(proposal_id).cloned())
     }
 
@@ -206,7 +420,7 @@ impl ContextStore for InMemoryStore {
         let proposals = self
             .proposals
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         let mut list: Vec<Proposal> = proposals.values().cloned().collect();
         if let Some(ref statuses) = query.status {
             list.retain(|p| statuses.contains(&p.status));
@@ -222,13 +436,14 @@ impl ContextStore for InMemoryStore {
         let mut proposals = self
             .proposals
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         if proposals.contains_key(&id) {
-            return Err(StoreError::Conflict(format!(
+            return Err(StoreError::conflict(format!(
                 "proposal {} already exists",
                 id
             )));
         }
+        check_capacity("proposal", self.limits.max_proposals, proposals.len(), 1)?;
         proposals.insert(id, proposal);
         Ok(())
     }
@@ -236,45 +451,16 @@ impl ContextStore for InMemoryStore {
     async fn update_proposal(
         &self,
         proposal_id: &str,
-        updates: serde_json::Value,
+        patch: ProposalPatch,
     ) -> Result<(), StoreError> {
         let mut proposals = self
             .proposals
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         let p = proposals
             .get_mut(proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-        if let Some(s) = updates.get("status").and_then(|v| v.as_str()) {
-            // Only apply_proposal may set status to Applied (it also sets applied metadata).
-            if s == "applied" {
-                return Err(StoreError::Invalid(
-                    "cannot set status to applied via PATCH; use POST /proposals/:id/apply"
-                        .to_string(),
-                ));
-            }
-            p.status = match s {
-                "open" => ProposalStatus::Open,
-                "accepted" => ProposalStatus::Accepted,
-                "rejected" => ProposalStatus::Rejected,
-                "withdrawn" => ProposalStatus::Withdrawn,
-                _ => return Err(StoreError::Invalid(format!("unknown status {}", s))),
-            };
-        }
-        if let Some(m) = updates.get("metadata").and_then(|v| v.as_object()) {
-            if let Some(v) = m.get("modified_at").and_then(|v| v.as_str()) {
-                p.metadata.modified_at = v.to_string();
-            }
-            if let Some(v) = m.get("modified_by").and_then(|v| v.as_str()) {
-                p.metadata.modified_by = v.to_string();
-            }
-        }
-        if let Some(arr) = updates.get("comments").and_then(|v| v.as_array()) {
-            if let Ok(comments) = serde_json::from_value(serde_json::Value::Array(arr.clone())) {
-                p.comments = Some(comments);
-            }
-        }
-        Ok(())
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        lifecycle::apply_update(p, &patch)
     }
 
     async fn submit_review(&self, review: Review) -> Result<(), StoreError> {
@@ -282,136 +468,100 @@ impl ContextStore for InMemoryStore {
         let mut proposals = self
             .proposals
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         let p = proposals
             .get_mut(&proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-        if p.status != ProposalStatus::Open {
-            return Err(StoreError::Invalid(
-                "proposal is not open for review".to_string(),
-            ));
-        }
-        if review.action == ReviewAction::Accept {
-            p.status = ProposalStatus::Accepted;
-        } else if review.action == ReviewAction::Reject {
-            p.status = ProposalStatus::Rejected;
-        }
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        lifecycle::apply_review(p, &review)?;
 
         let mut reviews = self
             .reviews
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         reviews.entry(proposal_id).or_default().push(review);
         Ok(())
     }
 
-    async fn apply_proposal(&self, proposal_id: &str, applied_by: &str) -> Result<(), StoreError> {
-        // Idempotent: if already Applied, return Ok without mutating.
-        {
-            let proposals = self
-                .proposals
-                .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            if let Some(p) = proposals.get(proposal_id) {
-                if p.status == ProposalStatus::Applied {
-                    return Ok(());
-                }
-            }
-        }
-
-        let (ops, _modified_at, _modified_by, last_review_id) = {
-            let proposals = self
-                .proposals
-                .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            let proposal = proposals
-                .get(proposal_id)
-                .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-            if proposal.status != ProposalStatus::Accepted {
-                return Err(StoreError::Invalid(
-                    "only accepted proposals can be applied".to_string(),
-                ));
-            }
-            let reviews = self
-                .reviews
-                .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            let last_review_id = reviews
-                .get(proposal_id)
-                .and_then(|v| v.last())
-                .map(|r| r.id.clone());
-            (
-                proposal.operations.clone(),
-                proposal.metadata.modified_at.clone(),
-                proposal.metadata.modified_by.clone(),
-                last_review_id,
-            )
-        };
-
-        let mut sorted_ops = ops;
-        sorted_ops.sort_by_key(|o| match o {
-            Operation::Create { order, .. }
-            | Operation::Update { order, .. }
-            | Operation::Delete { order, .. }
-            | Operation::StatusChange { order, .. } => *order,
-        });
-
-        let now = chrono::Utc::now().to_rfc3339();
-        let (previous_revision_id, applied_to_revision_id) = {
-            let mut rev = self
-                .revision_counter
-                .write()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            let prev = format!("rev_{}", *rev);
-            *rev += 1;
-            let applied_to = format!("rev_{}", *rev);
-            (prev, applied_to)
-        };
-
-        {
-            let mut nodes = self
-                .nodes
-                .write()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            for op in &sorted_ops {
-                InMemoryStore::apply_operation(&mut nodes, op, &now, applied_by)?;
-            }
-        }
-        {
-            let mut proposals = self
-                .proposals
-                .write()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            if let Some(p) = proposals.get_mut(proposal_id) {
-                p.status = ProposalStatus::Applied;
-                p.applied = Some(AppliedMetadata {
-                    applied_at: now,
-                    applied_by: applied_by.to_string(),
-                    applied_from_review_id: last_review_id,
-                    applied_from_proposal_id: proposal_id.to_string(),
-                    applied_to_revision_id: applied_to_revision_id.clone(),
-                    previous_revision_id: previous_revision_id.clone(),
-                });
-            }
-        }
-        Ok(())
-    }
-
     async fn withdraw_proposal(&self, proposal_id: &str) -> Result<(), StoreError> {
         let mut proposals = self
             .proposals
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         let p = proposals
             .get_mut(proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-        if p.status != ProposalStatus::Open {
-            return Err(StoreError::Invalid(
-                "only open proposals (draft/submitted/changes_requested) can be withdrawn"
-                    .to_string(),
-            ));
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        lifecycle::withdraw(p)
+    }
+
+    async fn execute(&self, batch: WriteBatch) -> Result<(), StoreError> {
+        // Lock order: nodes, proposals, reviews, trace, revision, audit log (as in
+        // `import_bundle`).
+        let mut nodes = self
+            .nodes
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut proposals = self
+            .proposals
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut reviews = self
+            .reviews
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut staged = batch::stage(&batch.ops, &proposals, &reviews)?;
+        check_capacity(
+            "proposal",
+            self.limits.max_proposals,
+            proposals.len(),
+            staged.created,
+        )?;
+        for (id, _) in &staged.applies {
+            self.check_apply(&nodes, &staged.proposals[id])?;
+        }
+        let mut trace = self
+            .trace
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut rev = self
+            .revision_counter
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut log = self
+            .audit_log
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        if self.limits.audit_overflow == AuditOverflow::Reject || self.spill_dir.is_none() {
+            check_capacity(
+                "audit event",
+                self.limits.max_audit_events,
+                log.len(),
+                batch.audit.len(),
+            )?;
+        }
+        let entry = batch.outbox_entry();
+        let mut outbox = self
+            .outbox
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        for (id, applied_by) in &staged.applies {
+            let review_id = staged
+                .reviews
+                .get(id)
+                .or_else(|| reviews.get(id))
+                .and_then(|v| lifecycle::accepting_review(v));
+            let proposal = staged.proposals.get_mut(id).expect("staged with its apply");
+            InMemoryStore::apply(&mut nodes, proposal, applied_by, review_id, *rev)?;
+            *rev += 1;
+            trace.record(proposal);
+        }
+        for event in batch.audit {
+            self.push_audit(&mut log, event)?;
+        }
+        proposals.extend(staged.proposals);
+        reviews.extend(staged.reviews);
+        if let Some(entry) = entry {
+            outbox.insert(entry.id.clone(), entry);
         }
-        p.status = ProposalStatus::Withdrawn;
         Ok(())
     }
 
@@ -419,7 +569,7 @@ impl ContextStore for InMemoryStore {
         let reviews = self
             .reviews
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         Ok(reviews.get(proposal_id).cloned().unwrap_or_default())
     }
 
@@ -427,7 +577,7 @@ impl ContextStore for InMemoryStore {
         let proposals = self
             .proposals
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         Ok(proposals
             .get(proposal_id)
             .and_then(|p| p.comments.as_ref())
@@ -443,21 +593,20 @@ impl ContextStore for InMemoryStore {
         let mut proposals = self
             .proposals
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
+            .map_err(|e| StoreError::internal(e.to_string()))?;
         let p = proposals
             .get_mut(proposal_id)
-            .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
         p.comments.get_or_insert_with(Vec::new).push(comment);
         Ok(())
     }
 
     async fn get_accepted_nodes(&self) -> Result<Vec<ContextNode>, StoreError> {
-        self.query_nodes(NodeQuery {
-            status: Some(vec![NodeStatus::Accepted]),
-            ..Default::default()
-        })
-        .await
-        .map(|r| r.nodes)
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(nodes.with_status(NodeStatus::Accepted))
     }
 
     async fn get_open_proposals(&self) -> Result<Vec<Proposal>, StoreError> {
@@ -468,102 +617,55 @@ impl ContextStore for InMemoryStore {
         .await
     }
 
+    async fn get_node_proposals(&self, node_id: &NodeId) -> Result<Vec<NodeProposal>, StoreError> {
+        Ok(self
+            .trace
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .get(&node_key(node_id)))
+    }
+
     async fn detect_conflicts(
         &self,
         proposal_id: &str,
     ) -> Result<ConflictDetectionResult, StoreError> {
-        let (proposal, open) = {
-            let proposals = self
-                .proposals
-                .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            let proposal = proposals
-                .get(proposal_id)
-                .cloned()
-                .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-            let open: Vec<Proposal> = proposals
-                .values()
-                .filter(|p| p.status == ProposalStatus::Open && p.id != proposal_id)
-                .cloned()
-                .collect();
-            (proposal, open)
-        };
-        let node_ids_self: std::collections::HashSet<String> =
-            operations_node_keys(&proposal.operations);
-        let mut conflicts = Vec::new();
-        let mut needs_resolution = Vec::new();
-        for other in &open {
-            let node_ids_other = operations_node_keys(&other.operations);
-            let conflicting_nodes: Vec<NodeId> = node_ids_self
-                .intersection(&node_ids_other)
-                .filter_map(|k| key_to_node_id(k))
-                .collect();
-            if conflicting_nodes.is_empty() {
-                continue;
-            }
-            let severity = if conflicting_nodes.len() > 1 {
-                ConflictSeverity::Critical
-            } else {
-                ConflictSeverity::Node
-            };
-            conflicts.push(ProposalConflict {
-                proposals: vec![proposal_id.to_string(), other.id.clone()],
-                conflicting_nodes: conflicting_nodes.clone(),
-                conflicting_fields: None,
-                severity,
-                auto_resolvable: false,
-            });
-            if !needs_resolution.contains(&other.id) {
-                needs_resolution.push(other.id.clone());
-            }
-        }
-        let mergeable: Vec<String> = open
-            .iter()
-            .map(|p| p.id.clone())
-            .filter(|id| !needs_resolution.contains(id))
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let proposal = proposals
+            .get(proposal_id)
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        let open: Vec<Proposal> = proposals
+            .values()
+            .filter(|p| p.status == ProposalStatus::Open)
+            .cloned()
             .collect();
-        Ok(ConflictDetectionResult {
-            conflicts,
-            mergeable,
-            needs_resolution,
-        })
+        let mut result = reconcile::detect_conflicts(proposal, &open);
+        let locks = self
+            .leases
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .live(NODE_LOCK_PREFIX, chrono::Utc::now());
+        result.locked_nodes = reconcile::locked_nodes(proposal, &locks);
+        Ok(result)
     }
 
     async fn is_proposal_stale(&self, proposal_id: &str) -> Result<bool, StoreError> {
-        let (proposal, nodes) = {
-            let proposals = self
-                .proposals
-                .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            let proposal = proposals
-                .get(proposal_id)
-                .cloned()
-                .ok_or_else(|| StoreError::NotFound(format!("proposal {}", proposal_id)))?;
-            let nodes = self
-                .nodes
-                .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
-            let node_keys: Vec<String> = operations_node_keys(&proposal.operations)
-                .into_iter()
-                .collect();
-            let current_versions: std::collections::HashMap<String, u32> = node_keys
-                .iter()
-                .filter_map(|k| nodes.get(k).map(|n| (k.clone(), n.metadata.version)))
-                .collect();
-            (proposal, current_versions)
-        };
-        let base = match &proposal.metadata.base_versions {
-            Some(b) => b,
-            None => return Ok(false),
-        };
-        for (key, &current_v) in &nodes {
-            if let Some(&base_v) = base.get(key) {
-                if current_v > base_v {
-                    return Ok(true);
-                }
-            }
-        }
-        Ok(false)
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let proposal = proposals
+            .get(proposal_id)
+            .ok_or_else(|| StoreError::not_found(format!("proposal {}", proposal_id)))?;
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(reconcile::is_stale(proposal, |key| {
+            nodes.get(key).map(|n| n.metadata.version)
+        }))
     }
 
     async fn merge_proposals(&self, proposal_ids: &[String]) -> Result<MergeResult, StoreError> {
@@ -571,183 +673,678 @@ impl ContextStore for InMemoryStore {
             let p = self
                 .proposals
                 .read()
-                .map_err(|e| StoreError::Internal(e.to_string()))?;
+                .map_err(|e| StoreError::internal(e.to_string()))?;
             proposal_ids
                 .iter()
                 .filter_map(|id| p.get(id).cloned())
                 .collect()
         };
         if proposals.len() != proposal_ids.len() {
-            return Err(StoreError::NotFound(
+            return Err(StoreError::not_found(
                 "one or more proposal ids not found".to_string(),
             ));
         }
-        // Field-level: (node_key, field) -> (proposal_id, value)
-        let mut by_field: std::collections::HashMap<
-            (String, String),
-            Vec<(String, serde_json::Value)>,
-        > = std::collections::HashMap::new();
-        for prop in &proposals {
-            for op in &prop.operations {
-                match op {
-                    Operation::Update {
-                        node_id, changes, ..
-                    } => {
-                        let key = node_id.key();
-                        if let Some(ref c) = changes.content {
-                            by_field
-                                .entry((key.clone(), "content".to_string()))
-                                .or_default()
-                                .push((prop.id.clone(), serde_json::json!(c)));
-                        }
-                        if let Some(s) = &changes.status {
-                            by_field
-                                .entry((key.clone(), "status".to_string()))
-                                .or_default()
-                                .push((
-                                    prop.id.clone(),
-                                    serde_json::to_value(s).unwrap_or(serde_json::Value::Null),
-                                ));
-                        }
-                    }
-                    _ => {}
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(reconcile::merge(&proposals, |key| nodes.get(key)))
+    }
+
+    async fn reset(&self) -> Result<(), StoreError> {
+        let mut nodes = self
+            .nodes
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut proposals = self
+            .proposals
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut reviews = self
+            .reviews
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut trace = self
+            .trace
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut rev = self
+            .revision_counter
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        nodes.clear();
+        proposals.clear();
+        reviews.clear();
+        *trace = TraceIndex::default();
+        *rev = 0;
+        // Note: audit log is NOT cleared on reset (intentional — audit is immutable).
+        Ok(())
+    }
+
+    async fn export_bundle(&self) -> Result<StoreBundle, StoreError> {
+        let mut nodes: Vec<ContextNode> = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .values()
+            .cloned()
+            .collect();
+        nodes.sort_by_key(|n| node_key(&n.id));
+        let mut proposals: Vec<Proposal> = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .values()
+            .cloned()
+            .collect();
+        proposals.sort_by(|a, b| a.id.cmp(&b.id));
+        let reviews = self
+            .reviews
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let audit = {
+            let log = self
+                .audit_log
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let spill = self
+                .audit_spill
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            read_spilled(&spill.pages)
+                .chain(log.iter().cloned())
+                .collect()
+        };
+        let revision = *self
+            .revision_counter
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(StoreBundle {
+            revision,
+            nodes,
+            proposals,
+            reviews,
+            audit,
+        })
+    }
+
+    async fn import_bundle(&self, bundle: StoreBundle) -> Result<ImportSummary, StoreError> {
+        // Refuse the whole bundle rather than import part of it.
+        {
+            let nodes = self
+                .nodes
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let new_nodes = bundle
+                .nodes
+                .iter()
+                .filter(|n| !nodes.contains_key(&node_key(&n.id)))
+                .count();
+            check_capacity("node", self.limits.max_nodes, nodes.len(), new_nodes)?;
+            let proposals = self
+                .proposals
+                .read()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let new_proposals = bundle
+                .proposals
+                .iter()
+                .filter(|p| !proposals.contains_key(&p.id))
+                .count();
+            check_capacity(
+                "proposal",
+                self.limits.max_proposals,
+                proposals.len(),
+                new_proposals,
+            )?;
+            if self.limits.audit_overflow == AuditOverflow::Reject || self.spill_dir.is_none() {
+                let log = self
+                    .audit_log
+                    .read()
+                    .map_err(|e| StoreError::internal(e.to_string()))?;
+                check_capacity(
+                    "audit event",
+                    self.limits.max_audit_events,
+                    log.len(),
+                    bundle.audit.len(),
+                )?;
+            }
+        }
+
+        let mut summary = ImportSummary::default();
+        {
+            let mut nodes = self
+                .nodes
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for node in bundle.nodes {
+                let key = node_key(&node.id);
+                if nodes.contains_key(&key) {
+                    summary.skipped += 1;
+                    continue;
                 }
+                nodes.insert(key, node);
+                summary.nodes += 1;
             }
         }
-        let mut merged = Vec::new();
-        let mut conflicts = Vec::new();
-        let mut auto_merged = Vec::new();
-        for ((node_key, field), values) in by_field {
-            let node_id = key_to_node_id(&node_key).unwrap_or_else(|| NodeId {
-                id: node_key.clone(),
-                namespace: None,
+        {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for proposal in bundle.proposals {
+                if proposals.contains_key(&proposal.id) {
+                    summary.skipped += 1;
+                    continue;
+                }
+                proposals.insert(proposal.id.clone(), proposal);
+                summary.proposals += 1;
+            }
+            *self
+                .trace
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))? =
+                TraceIndex::rebuild(proposals.values());
+        }
+        {
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            for (proposal_id, list) in bundle.reviews {
+                if reviews.get(&proposal_id).is_some_and(|r| !r.is_empty()) {
+                    summary.skipped += list.len();
+                    continue;
+                }
+                summary.reviews += list.len();
+                reviews.insert(proposal_id, list);
+            }
+        }
+        {
+            let mut log = self
+                .audit_log
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let known: std::collections::HashSet<String> =
+                log.iter().map(|e| e.event_id.clone()).collect();
+            for event in bundle.audit {
+                if known.contains(&event.event_id) {
+                    summary.skipped += 1;
+                    continue;
+                }
+                self.push_audit(&mut log, event)?;
+                summary.audit_events += 1;
+            }
+        }
+        {
+            let mut rev = self
+                .revision_counter
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            *rev = (*rev).max(bundle.revision);
+        }
+        Ok(summary)
+    }
+
+    async fn append_audit(&self, event: AuditEvent) -> Result<(), StoreError> {
+        let mut log = self
+            .audit_log
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        self.push_audit(&mut log, event)
+    }
+
+    async fn query_audit(
+        &self,
+        actor: Option<&str>,
+        action: Option<&str>,
+        resource_id: Option<&str>,
+        from: Option<&str>,
+        to: Option<&str>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<AuditQueryResult, StoreError> {
+        // Lock order: audit log, then spill state (as in `push_audit`).
+        let log = self
+            .audit_log
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let spill = self
+            .audit_spill
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let filter = AuditFilter {
+            actor,
+            action,
+            resource_id,
+            from,
+            to,
+        };
+        let limit = limit.unwrap_or(100);
+        let offset = offset.unwrap_or(0) as usize;
+        let mut total = 0usize;
+        let mut events = Vec::new();
+        let mut visit = |e: &AuditEvent| {
+            if total >= offset && events.len() < limit as usize {
+                events.push(e.clone());
+            }
+            total += 1;
+        };
+        // Spilled pages hold the oldest events, so they come first. They are not indexed.
+        for e in read_spilled(&spill.pages).filter(|e| filter.matches(e)) {
+            visit(&e);
+        }
+        for e in log.matching(&filter) {
+            visit(e);
+        }
+        let has_more = offset + events.len() < total;
+        Ok(AuditQueryResult {
+            events,
+            total: total as u64,
+            limit,
+            offset: offset as u32,
+            has_more,
+        })
+    }
+
+    async fn save_export_job(&self, job: ExportJob) -> Result<(), StoreError> {
+        let mut jobs = self
+            .export_jobs
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        jobs.insert(job.id.clone(), job);
+        Ok(())
+    }
+
+    async fn get_export_job(&self, job_id: &str) -> Result<Option<ExportJob>, StoreError> {
+        let jobs = self
+            .export_jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(jobs.get(job_id).cloned())
+    }
+
+    async fn list_export_jobs(&self) -> Result<Vec<ExportJob>, StoreError> {
+        let jobs = self
+            .export_jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut list: Vec<ExportJob> = jobs.values().cloned().collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(list)
+    }
+
+    async fn put_export_artifact(&self, job_id: &str, data: Vec<u8>) -> Result<(), StoreError> {
+        let mut artifacts = self
+            .export_artifacts
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        artifacts.insert(job_id.to_string(), data);
+        Ok(())
+    }
+
+    async fn get_export_artifact(&self, job_id: &str) -> Result<Option<Vec<u8>>, StoreError> {
+        let artifacts = self
+            .export_artifacts
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(artifacts.get(job_id).cloned())
+    }
+
+    async fn save_job(&self, job: JobRecord) -> Result<(), StoreError> {
+        let mut jobs = self
+            .jobs
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        jobs.insert(job.id.clone(), job);
+        Ok(())
+    }
+
+    async fn get_job(&self, job_id: &str) -> Result<Option<JobRecord>, StoreError> {
+        let jobs = self
+            .jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(jobs.get(job_id).cloned())
+    }
+
+    async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        kind: Option<&str>,
+    ) -> Result<Vec<JobRecord>, StoreError> {
+        let jobs = self
+            .jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let mut list: Vec<JobRecord> = jobs
+            .values()
+            .filter(|j| status.is_none_or(|s| j.status == s))
+            .filter(|j| kind.is_none_or(|k| j.kind == k))
+            .cloned()
+            .collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(list)
+    }
+
+    async fn claim_job(&self, kinds: &[&str], now: &str) -> Result<Option<JobRecord>, StoreError> {
+        let mut jobs = self
+            .jobs
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let Some(job) = jobs
+            .values_mut()
+            .filter(|j| kinds.contains(&j.kind.as_str()) && j.is_due(now))
+            .min_by(|a, b| a.created_at.cmp(&b.created_at))
+        else {
+            return Ok(None);
+        };
+        job.status = JobStatus::Running;
+        job.attempts += 1;
+        job.started_at = Some(now.to_string());
+        Ok(Some(job.clone()))
+    }
+
+    async fn status(&self) -> Result<StoreStatus, StoreError> {
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let proposals = self
+            .proposals
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let reviews = self
+            .reviews
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let log = self
+            .audit_log
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let spill = self
+            .audit_spill
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let export_jobs = self
+            .export_jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let artifacts = self
+            .export_artifacts
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        let jobs = self
+            .jobs
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+
+        let approx_bytes = nodes.values().map(json_size).sum::<usize>()
+            + proposals.values().map(json_size).sum::<usize>()
+            + reviews.values().flatten().map(json_size).sum::<usize>()
+            + log.iter().map(json_size).sum::<usize>()
+            + export_jobs.values().map(json_size).sum::<usize>()
+            + artifacts.values().map(Vec::len).sum::<usize>()
+            + jobs.values().map(json_size).sum::<usize>();
+        Ok(StoreStatus {
+            nodes: nodes.len(),
+            proposals: proposals.len(),
+            reviews: reviews.values().map(Vec::len).sum(),
+            audit_events: log.len(),
+            audit_events_spilled: spill.events,
+            export_jobs: export_jobs.len(),
+            jobs: jobs.len(),
+            approx_bytes,
+            limits: Some(self.limits.clone()),
+        })
+    }
+
+    async fn compact(&self, options: &CompactOptions) -> Result<CompactReport, StoreError> {
+        let now = chrono::Utc::now();
+        let mut report = CompactReport::default();
+        let mut reclaimed = 0usize;
+        {
+            let mut proposals = self
+                .proposals
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            let mut reviews = self
+                .reviews
+                .write()
+                .map_err(|e| StoreError::internal(e.to_string()))?;
+            proposals.retain(|id, p| {
+                if !options.prunable(p, now) {
+                    return true;
+                }
+                reclaimed += json_size(p);
+                report.pruned_proposals.push(id.clone());
+                false
+            });
+            // Reviews of pruned (or never stored) proposals.
+            reviews.retain(|id, list| {
+                if proposals.contains_key(id) {
+                    return true;
+                }
+                reclaimed += list.iter().map(json_size).sum::<usize>();
+                report.pruned_reviews += list.len();
+                false
             });
-            if values.len() == 1 {
-                let (_pid, v) = &values[0];
-                auto_merged.push(FieldChange {
-                    node_id: node_id.clone(),
-                    field: field.clone(),
-                    old_value: serde_json::Value::Null,
-                    new_value: v.clone(),
-                });
-                continue;
-            }
-            let uniq: std::collections::HashSet<_> = values.iter().map(|(_, v)| v).collect();
-            if uniq.len() > 1 {
-                conflicts.push(MergeConflictField {
-                    field: field.clone(),
-                    node_id: node_id.clone(),
-                    proposal1_value: values[0].1.clone(),
-                    proposal2_value: values[1].1.clone(),
-                });
-            } else {
-                merged.push(FieldChange {
-                    node_id: node_id.clone(),
-                    field: field.clone(),
-                    old_value: serde_json::Value::Null,
-                    new_value: values[0].1.clone(),
-                });
-            }
         }
-        Ok(MergeResult {
-            merged,
-            conflicts,
-            auto_merged,
-        })
+        report.pruned_proposals.sort();
+        let spill_reclaimed = self.compact_spill(&mut report)?;
+        report.reclaimed_bytes = reclaimed as u64 + spill_reclaimed;
+        Ok(report)
     }
 
-    async fn reset(&self) -> Result<(), StoreError> {
+    async fn list_trash(&self) -> Result<Vec<ContextNode>, StoreError> {
+        let nodes = self
+            .nodes
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(trash::list(&nodes))
+    }
+
+    async fn restore_node(
+        &self,
+        node_id: &NodeId,
+        restored_by: &str,
+    ) -> Result<ContextNode, StoreError> {
         let mut nodes = self
             .nodes
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut proposals = self
-            .proposals
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        trash::restore(&mut nodes, node_id, restored_by)
+    }
+
+    async fn purge_trash(&self, deleted_before: &str) -> Result<Vec<NodeId>, StoreError> {
+        let mut nodes = self
+            .nodes
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut reviews = self
-            .reviews
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(trash::purgeable(&nodes, deleted_before)?
+            .iter()
+            .filter_map(|key| nodes.remove(key))
+            .map(|node| node.id)
+            .collect())
+    }
+
+    async fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<Lease, StoreError> {
+        let mut leases = self
+            .leases
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let mut rev = self
-            .revision_counter
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        leases.acquire(name, holder, ttl, chrono::Utc::now())
+    }
+
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), StoreError> {
+        let mut leases = self
+            .leases
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        nodes.clear();
-        proposals.clear();
-        reviews.clear();
-        *rev = 0;
-        // Note: audit log is NOT cleared on reset (intentional — audit is immutable).
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        leases.release(name, holder);
         Ok(())
     }
 
-    async fn append_audit(&self, event: AuditEvent) -> Result<(), StoreError> {
-        let mut log = self
-            .audit_log
+    async fn list_leases(&self, prefix: &str) -> Result<Vec<Lease>, StoreError> {
+        let leases = self
+            .leases
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(leases.live(prefix, chrono::Utc::now()))
+    }
+
+    async fn get_directory(&self) -> Result<Directory, StoreError> {
+        let directory = self
+            .directory
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(directory.clone())
+    }
+
+    async fn save_directory_user(&self, user: DirectoryUser) -> Result<(), StoreError> {
+        let mut directory = self
+            .directory
             .write()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        log.push(event);
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        directory.put_user(user, &chrono::Utc::now().to_rfc3339())
+    }
+
+    async fn delete_directory_user(&self, id: &str) -> Result<DirectoryUser, StoreError> {
+        let mut directory = self
+            .directory
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        directory.remove_user(id, &chrono::Utc::now().to_rfc3339())
+    }
+
+    async fn save_directory_group(&self, group: DirectoryGroup) -> Result<(), StoreError> {
+        let mut directory = self
+            .directory
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        directory.put_group(group)
+    }
+
+    async fn delete_directory_group(&self, id: &str) -> Result<DirectoryGroup, StoreError> {
+        let mut directory = self
+            .directory
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        directory.remove_group(id)
+    }
+
+    async fn directory_access(&self, actor_id: &str) -> Result<Option<ActorAccess>, StoreError> {
+        let directory = self
+            .directory
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(directory.access(actor_id))
+    }
+
+    async fn get_preferences(&self, actor_id: &str) -> Result<Option<UserPreferences>, StoreError> {
+        let preferences = self
+            .preferences
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(preferences.get(actor_id).cloned())
+    }
+
+    async fn save_preferences(
+        &self,
+        actor_id: &str,
+        preferences: UserPreferences,
+    ) -> Result<(), StoreError> {
+        self.preferences
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .insert(actor_id.to_string(), preferences);
         Ok(())
     }
 
-    async fn query_audit(
+    async fn get_delegations(&self, delegator: &str) -> Result<Vec<Delegation>, StoreError> {
+        let delegations = self
+            .delegations
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(delegations.get(delegator).cloned().unwrap_or_default())
+    }
+
+    async fn save_delegations(
         &self,
-        actor: Option<&str>,
-        action: Option<&str>,
-        resource_id: Option<&str>,
-        from: Option<&str>,
-        to: Option<&str>,
-        limit: Option<u32>,
-        offset: Option<u32>,
-    ) -> Result<Vec<AuditEvent>, StoreError> {
-        let log = self
-            .audit_log
+        delegator: &str,
+        delegations: Vec<Delegation>,
+    ) -> Result<(), StoreError> {
+        self.delegations
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .insert(delegator.to_string(), delegations);
+        Ok(())
+    }
+
+    async fn add_api_calls(
+        &self,
+        date: &str,
+        calls: &BTreeMap<String, u64>,
+    ) -> Result<(), StoreError> {
+        self.usage
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .add_api_calls(date, calls);
+        Ok(())
+    }
+
+    async fn save_usage_rollup(&self, records: Vec<UsageRecord>) -> Result<(), StoreError> {
+        self.usage
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .apply_rollup(records);
+        Ok(())
+    }
+
+    async fn list_usage(&self, month: &str) -> Result<Vec<UsageRecord>, StoreError> {
+        let usage = self
+            .usage
             .read()
-            .map_err(|e| StoreError::Internal(e.to_string()))?;
-        let filtered: Vec<&AuditEvent> = log
-            .iter()
-            .filter(|e| {
-                if let Some(a) = actor {
-                    if e.actor_id != a {
-                        return false;
-                    }
-                }
-                if let Some(act) = action {
-                    let action_str = serde_json::to_string(&e.action)
-                        .unwrap_or_default()
-                        .replace('"', "");
-                    if action_str != act {
-                        return false;
-                    }
-                }
-                if let Some(rid) = resource_id {
-                    if e.resource_id != rid {
-                        return false;
-                    }
-                }
-                if let Some(f) = from {
-                    if e.timestamp.as_str() < f {
-                        return false;
-                    }
-                }
-                if let Some(t) = to {
-                    if e.timestamp.as_str() > t {
-                        return false;
-                    }
-                }
-                true
-            })
-            .collect();
-        let off = offset.unwrap_or(0) as usize;
-        let lim = limit.unwrap_or(100) as usize;
-        let page = filtered.into_iter().skip(off).take(lim).cloned().collect();
-        Ok(page)
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(usage.month(month))
+    }
+
+    async fn list_outbox(&self, limit: usize) -> Result<Vec<OutboxEntry>, StoreError> {
+        let outbox = self
+            .outbox
+            .read()
+            .map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(outbox.values().take(limit).cloned().collect())
+    }
+
+    async fn save_outbox_entry(&self, entry: OutboxEntry) -> Result<(), StoreError> {
+        self.outbox
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .insert(entry.id.clone(), entry);
+        Ok(())
+    }
+
+    async fn remove_outbox_entry(&self, id: &str) -> Result<(), StoreError> {
+        self.outbox
+            .write()
+            .map_err(|e| StoreError::internal(e.to_string()))?
+            .remove(id);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::store::context_store::StoreErrorCode;
     use crate::types::{NodeMetadata, NodeStatus, NodeType, ProposalMetadata, ProposalStatus};
 
     fn meta() -> NodeMetadata {
@@ -765,6 +1362,8 @@ mod tests {
             source_attribution: None,
             ip_classification: None,
             license: None,
+            deleted: None,
+            field_changes: None,
         }
     }
 
@@ -778,9 +1377,52 @@ mod tests {
             required_approvers: None,
             approved_by: None,
             base_versions: None,
+            force_status_transitions: None,
+            forge: None,
+            complexity: None,
+            authors: None,
+            superseded_by: None,
+            conflict_resolutions: None,
         }
     }
 
+    #[tokio::test]
+    async fn bundle_round_trip_skips_existing() {
+        let source = InMemoryStore::new();
+        let proposal = Proposal {
+            id: "p-1".to_string(),
+            status: ProposalStatus::Open,
+            operations: vec![],
+            metadata: proposal_meta(),
+            comments: None,
+            relations: None,
+            applied: None,
+        };
+        source.create_proposal(proposal).await.unwrap();
+        source
+            .append_audit(AuditEvent::new(
+                "test",
+                "human",
+                crate::types::AuditAction::ProposalCreated,
+                "p-1",
+                crate::types::AuditOutcome::Success,
+            ))
+            .await
+            .unwrap();
+        let bundle = source.export_bundle().await.unwrap();
+
+        let target = InMemoryStore::new();
+        let summary = target.import_bundle(bundle.clone()).await.unwrap();
+        assert_eq!(summary.proposals, 1);
+        assert_eq!(summary.audit_events, 1);
+        assert!(target.get_proposal("p-1").await.unwrap().is_some());
+
+        let again = target.import_bundle(bundle).await.unwrap();
+        assert_eq!(again.proposals, 0);
+        assert_eq!(again.audit_events, 0);
+        assert_eq!(again.skipped, 2);
+    }
+
     #[tokio::test]
     async fn create_and_get_proposal() {
         let store = InMemoryStore::new();
@@ -966,6 +1608,294 @@ mod tests {
             .query_audit(None, None, None, None, None, None, None)
             .await
             .unwrap();
-        assert!(!events.is_empty(), "audit log should survive reset");
+        assert!(!events.events.is_empty(), "audit log should survive reset");
+    }
+
+    fn tagged_node(
+        id: &str,
+        namespace: Option<&str>,
+        node_type: NodeType,
+        tags: &[&str],
+    ) -> ContextNode {
+        let mut metadata = meta();
+        metadata.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+        ContextNode {
+            id: NodeId {
+                id: id.to_string(),
+                namespace: namespace.map(str::to_string),
+            },
+            node_type,
+            status: NodeStatus::Accepted,
+            title: None,
+            description: None,
+            content: format!("content of {}", id),
+            text_range: None,
+            metadata,
+            relationships: None,
+            relations: None,
+            referenced_by: None,
+            source_files: None,
+            decision: None,
+            rationale: None,
+            alternatives: None,
+            decided_at: None,
+            state: None,
+            assignee: None,
+            due_date: None,
+            dependencies: None,
+            severity: None,
+            likelihood: None,
+            mitigation: None,
+            question: None,
+            answer: None,
+            answered_at: None,
+            constraint: None,
+            reason: None,
+        }
+    }
+
+    async fn apply_ops(store: &InMemoryStore, id: &str, operations: Vec<Operation>) {
+        let proposal = Proposal {
+            id: id.to_string(),
+            status: ProposalStatus::Accepted,
+            operations,
+            metadata: proposal_meta(),
+            comments: None,
+            relations: None,
+            applied: None,
+        };
+        store.create_proposal(proposal).await.unwrap();
+        store.apply_proposal(id, "test-user").await.unwrap();
+    }
+
+    fn ids(result: &NodeQueryResult) -> Vec<&str> {
+        result.nodes.iter().map(|n| n.id.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn query_uses_indexes_and_pages_in_key_order() {
+        let store = InMemoryStore::new();
+        let nodes = [
+            tagged_node("a", None, NodeType::Goal, &["auth", "api"]),
+            tagged_node("b", None, NodeType::Decision, &["auth"]),
+            tagged_node("c", Some("team"), NodeType::Goal, &["api"]),
+            tagged_node("d", Some("team"), NodeType::Goal, &["auth", "api"]),
+        ];
+        let ops = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| Operation::Create {
+                id: format!("op-{}", i),
+                order: i as u32,
+                node: node.clone(),
+            })
+            .collect();
+        apply_ops(&store, "p-create", ops).await;
+
+        let all_tags = store
+            .query_nodes(NodeQuery {
+                tags: Some(vec!["auth".to_string(), "api".to_string()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(&all_tags), vec!["a", "d"]);
+
+        let team_goals = store
+            .query_nodes(NodeQuery {
+                namespace: Some("team".to_string()),
+                r#type: Some(vec![NodeType::Goal]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(&team_goals), vec!["c", "d"]);
+
+        let page = store
+            .query_nodes(NodeQuery {
+                limit: Some(2),
+                offset: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.total, 4);
+        assert_eq!(page.nodes.len(), 2);
+        assert!(page.has_more);
+
+        let past_end = store
+            .query_nodes(NodeQuery {
+                offset: Some(10),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(past_end.nodes.is_empty());
+        assert_eq!(past_end.total, 4);
+
+        // Status changes move the node between index entries.
+        apply_ops(
+            &store,
+            "p-supersede",
+            vec![Operation::StatusChange {
+                id: "op-status".to_string(),
+                order: 1,
+                node_id: nodes[0].id.clone(),
+                new_status: NodeStatus::Superseded,
+                old_status: NodeStatus::Accepted,
+                reason: None,
+            }],
+        )
+        .await;
+        let accepted = store
+            .query_nodes(NodeQuery {
+                status: Some(vec![NodeStatus::Accepted]),
+                tags: Some(vec!["auth".to_string()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(&accepted), vec!["b", "d"]);
+        assert_eq!(store.get_accepted_nodes().await.unwrap().len(), 3);
+    }
+
+    fn audit_event(n: usize) -> AuditEvent {
+        AuditEvent::new(
+            &format!("actor-{}", n),
+            "human",
+            crate::types::AuditAction::StoreReset,
+            "store",
+            crate::types::AuditOutcome::Success,
+        )
+    }
+
+    #[tokio::test]
+    async fn limits_reject_nodes_and_proposals_over_the_cap() {
+        let limits = MemoryLimits {
+            max_nodes: Some(1),
+            max_proposals: Some(2),
+            ..Default::default()
+        };
+        let store = InMemoryStore::with_limits(limits, std::env::temp_dir());
+        let create = |id: &str| Operation::Create {
+            id: format!("op-{}", id),
+            order: 1,
+            node: tagged_node(id, None, NodeType::Goal, &[]),
+        };
+        apply_ops(&store, "p-1", vec![create("a")]).await;
+
+        let proposal = Proposal {
+            id: "p-2".to_string(),
+            status: ProposalStatus::Accepted,
+            operations: vec![create("b")],
+            metadata: proposal_meta(),
+            comments: None,
+            relations: None,
+            applied: None,
+        };
+        store.create_proposal(proposal.clone()).await.unwrap();
+        assert!(matches!(
+            store.apply_proposal("p-2", "test-user").await,
+            Err(StoreError {
+                code: StoreErrorCode::CapacityExceeded,
+                ..
+            })
+        ));
+        assert!(matches!(
+            store
+                .create_proposal(Proposal {
+                    id: "p-3".to_string(),
+                    ..proposal
+                })
+                .await,
+            Err(StoreError {
+                code: StoreErrorCode::CapacityExceeded,
+                ..
+            })
+        ));
+
+        let status = store.status().await.unwrap();
+        assert_eq!((status.nodes, status.proposals), (1, 2));
+        assert!(status.approx_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn audit_overflow_spills_oldest_pages_or_rejects() {
+        let dir = std::env::temp_dir().join(format!("tl-spill-{}", uuid::Uuid::new_v4()));
+        let limits = MemoryLimits {
+            max_audit_events: Some(3),
+            audit_page_size: 2,
+            ..Default::default()
+        };
+        let store = InMemoryStore::with_limits(limits.clone(), dir.clone());
+        for n in 0..6 {
+            store.append_audit(audit_event(n)).await.unwrap();
+        }
+        let status = store.status().await.unwrap();
+        assert_eq!((status.audit_events, status.audit_events_spilled), (2, 4));
+        // Spilled events are still queried, oldest first.
+        let all = store
+            .query_audit(None, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        let actors: Vec<&str> = all.events.iter().map(|e| e.actor_id.as_str()).collect();
+        assert_eq!(
+            actors,
+            vec!["actor-0", "actor-1", "actor-2", "actor-3", "actor-4", "actor-5"]
+        );
+        let one = store
+            .query_audit(Some("actor-1"), None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(one.events.len(), 1);
+        let page = store
+            .query_audit(None, None, None, None, None, Some(2), Some(3))
+            .await
+            .unwrap();
+        assert_eq!((page.events.len(), page.total, page.has_more), (2, 6, true));
+        assert_eq!(page.events[0].actor_id, "actor-3");
+        assert_eq!(store.export_bundle().await.unwrap().audit.len(), 6);
+
+        // Compaction merges the two pages and drops a partial page from a failed spill.
+        let spill_dir = std::fs::read_dir(&dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        std::fs::write(spill_dir.join("page-000009.jsonl"), "{\"eventId\":").unwrap();
+        let report = store.compact(&Default::default()).await.unwrap();
+        assert_eq!(report.orphaned_files, ["page-000009.jsonl"]);
+        assert!(report.audit_defragmented);
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 1);
+        let all = store
+            .query_audit(None, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.total, 6);
+        store.append_audit(audit_event(6)).await.unwrap();
+        store.append_audit(audit_event(7)).await.unwrap();
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 2);
+        assert_eq!(store.status().await.unwrap().audit_events_spilled, 6);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let rejecting = InMemoryStore::with_limits(
+            MemoryLimits {
+                audit_overflow: AuditOverflow::Reject,
+                ..limits
+            },
+            dir.clone(),
+        );
+        for n in 0..3 {
+            rejecting.append_audit(audit_event(n)).await.unwrap();
+        }
+        assert!(matches!(
+            rejecting.append_audit(audit_event(3)).await,
+            Err(StoreError {
+                code: StoreErrorCode::CapacityExceeded,
+                ..
+            })
+        ));
+        assert!(!dir.exists());
     }
 }
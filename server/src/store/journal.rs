@@ -0,0 +1,263 @@
+//! Write-ahead journal for `FileStore`.
+//!
+//! A store operation that touches several files (applying a proposal writes its nodes,
+//! the proposal and `revision.json`; imports and resets are similar) is first written
+//! whole to `journal.json`: every file's new content, or its removal. Only then are the
+//! files changed, and the journal is deleted once all of them are. Writing the journal is
+//! itself atomic, so after a crash there are two cases:
+//!
+//! - `journal.json` exists: the operation was committed but may be half applied. Startup
+//!   replays it; every step replaces or removes a whole file, so replaying is idempotent.
+//! - only `journal.tmp` exists: the crash hit while recording, no data file was touched
+//!   yet. Startup discards it, which rolls the operation back.
+//!
+//! Single-file writes are atomic renames already and skip the journal.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::context_store::{StoreError, StoreErrorCode};
+use crate::store::disk_writer::{write_atomic, Durability};
+
+/// One step of a store write.
+#[derive(Debug, Clone)]
+pub(crate) enum FileOp {
+    /// Replace the file with `data`.
+    Write { path: PathBuf, data: Vec<u8> },
+    /// Remove a file, or a directory tree when `dir`. Missing paths are fine.
+    Remove { path: PathBuf, dir: bool },
+}
+
+impl FileOp {
+    fn apply(&self, durability: Durability) -> Result<(), String> {
+        match self {
+            FileOp::Write { path, data } => {
+                write_atomic(path, data, durability).map_err(|e| e.to_string())
+            }
+            FileOp::Remove { path, dir } => {
+                let result = if *dir {
+                    std::fs::remove_dir_all(path)
+                } else {
+                    std::fs::remove_file(path)
+                };
+                match result {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                        Err(format!("remove {}: {}", path.display(), e))
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// On-disk form of a journaled operation. Paths are relative to the data directory.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalEntry {
+    recorded_at: String,
+    ops: Vec<JournalOp>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalOp {
+    Write { path: PathBuf, content: String },
+    Remove { path: PathBuf, dir: bool },
+}
+
+/// What [`recover`] found at startup.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) enum Recovery {
+    #[default]
+    Clean,
+    /// A committed operation was replayed (number of steps).
+    Replayed(usize),
+    /// An operation that never finished recording was discarded.
+    RolledBack,
+}
+
+fn journal_file(root: &Path) -> PathBuf {
+    root.join("journal.json")
+}
+
+/// Applies store writes through the journal. Owned by the disk writer thread.
+pub(crate) struct Journal {
+    root: PathBuf,
+    durability: Durability,
+    /// Operation whose steps failed part way; finished before anything else is written,
+    /// so later writes cannot be overtaken by its replay.
+    unfinished: Option<Vec<FileOp>>,
+}
+
+impl Journal {
+    pub fn new(root: PathBuf, durability: Durability) -> Self {
+        Self {
+            root,
+            durability,
+            unfinished: None,
+        }
+    }
+
+    /// Apply `ops` in order, journaling them first when there is more than one.
+    pub fn commit(&mut self, ops: Vec<FileOp>) -> Result<(), String> {
+        if let Some(unfinished) = self.unfinished.take() {
+            self.run(unfinished)
+                .map_err(|e| format!("earlier write still failing: {}", e))?;
+        }
+        if ops.len() > 1 {
+            self.record(&ops)?;
+        }
+        self.run(ops)
+    }
+
+    fn record(&self, ops: &[FileOp]) -> Result<(), String> {
+        let entry = JournalEntry {
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            ops: ops
+                .iter()
+                .map(|op| self.to_journal(op))
+                .collect::<Result<_, _>>()?,
+        };
+        let json = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+        write_atomic(&journal_file(&self.root), &json, self.durability).map_err(|e| e.to_string())
+    }
+
+    fn run(&mut self, ops: Vec<FileOp>) -> Result<(), String> {
+        let journaled = ops.len() > 1;
+        if let Err(e) = ops.iter().try_for_each(|op| op.apply(self.durability)) {
+            if journaled {
+                self.unfinished = Some(ops);
+            }
+            return Err(e);
+        }
+        if journaled {
+            std::fs::remove_file(journal_file(&self.root))
+                .map_err(|e| format!("remove journal: {}", e))?;
+        }
+        Ok(())
+    }
+
+    fn to_journal(&self, op: &FileOp) -> Result<JournalOp, String> {
+        let relative = |path: &Path| {
+            path.strip_prefix(&self.root)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| path.to_path_buf())
+        };
+        Ok(match op {
+            FileOp::Write { path, data } => JournalOp::Write {
+                path: relative(path),
+                content: String::from_utf8(data.clone())
+                    .map_err(|_| format!("{}: only text files can be journaled", path.display()))?,
+            },
+            FileOp::Remove { path, dir } => JournalOp::Remove {
+                path: relative(path),
+                dir: *dir,
+            },
+        })
+    }
+}
+
+/// Finish or discard an operation interrupted by a crash. Runs before the store loads.
+pub(crate) fn recover(root: &Path, durability: Durability) -> Result<Recovery, StoreError> {
+    let file = journal_file(root);
+    let tmp = file.with_extension("tmp");
+    let content = match std::fs::read_to_string(&file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            if tmp.exists() {
+                std::fs::remove_file(&tmp).map_err(|e| StoreError::internal(e.to_string()))?;
+                return Ok(Recovery::RolledBack);
+            }
+            return Ok(Recovery::Clean);
+        }
+        Err(e) => return Err(StoreError::io("read journal", e)),
+    };
+    let entry: JournalEntry = serde_json::from_str(&content).map_err(|e| {
+        StoreError::new(
+            StoreErrorCode::Corrupt,
+            format!(
+                "{} is unreadable ({}); restore the data directory from a backup",
+                file.display(),
+                e
+            ),
+        )
+        .with_resource(file.display().to_string())
+        .with_source(e)
+    })?;
+    let steps = entry.ops.len();
+    for op in entry.ops {
+        let op = match op {
+            JournalOp::Write { path, content } => FileOp::Write {
+                path: root.join(path),
+                data: content.into_bytes(),
+            },
+            JournalOp::Remove { path, dir } => FileOp::Remove {
+                path: root.join(path),
+                dir,
+            },
+        };
+        op.apply(durability)
+            .map_err(|e| StoreError::new(StoreErrorCode::Io, format!("journal replay: {}", e)))?;
+    }
+    std::fs::remove_file(&file).map_err(|e| StoreError::internal(e.to_string()))?;
+    let _ = std::fs::remove_file(&tmp);
+    Ok(Recovery::Replayed(steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_entries_are_replayed_and_torn_ones_discarded() {
+        let root = std::env::temp_dir().join(format!("tl-journal-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("nodes")).unwrap();
+        std::fs::write(root.join("nodes/old.json"), "{}").unwrap();
+        let journal = Journal::new(root.clone(), Durability::Buffered);
+        let ops = vec![
+            FileOp::Write {
+                path: root.join("nodes/a.json"),
+                data: b"{\"a\":1}".to_vec(),
+            },
+            FileOp::Remove {
+                path: root.join("nodes/old.json"),
+                dir: false,
+            },
+            FileOp::Write {
+                path: root.join("revision.json"),
+                data: b"7".to_vec(),
+            },
+        ];
+
+        // Crash after recording, before any file was touched.
+        journal.record(&ops).unwrap();
+        assert_eq!(
+            recover(&root, Durability::Buffered).unwrap(),
+            Recovery::Replayed(3)
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("nodes/a.json")).unwrap(),
+            "{\"a\":1}"
+        );
+        assert!(!root.join("nodes/old.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(root.join("revision.json")).unwrap(),
+            "7"
+        );
+        assert!(!journal_file(&root).exists());
+
+        // Crash while recording.
+        std::fs::write(root.join("journal.tmp"), "{\"recordedAt\":").unwrap();
+        assert_eq!(
+            recover(&root, Durability::Buffered).unwrap(),
+            Recovery::RolledBack
+        );
+        assert_eq!(
+            recover(&root, Durability::Buffered).unwrap(),
+            Recovery::Clean
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,149 @@
+//! Advisory leases: named, expiring claims that let one holder at a time run a piece of
+//! work (apply a proposal, queue a scheduled task).
+//!
+//! A lease is granted when nobody holds it, when the current lease has expired, or to
+//! its current holder (a renewal). Holders are instance ids (see `crate::cluster`).
+//! Leases live with the store; both built-in backends keep them in process memory, so
+//! they serialize work within one process. Servers coordinate through a lease directory
+//! instead (`store::lease_dir`, `cluster.lease_dir`), which keeps the same table on disk.
+//!
+//! Node locks (`POST /nodes/:id/lock`) are leases too, named `node:{key}` and held by an
+//! actor id: the same expiry and renewal rules, surfaced to other authors as warnings.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::store::context_store::StoreError;
+use crate::types::{NodeId, NodeLock};
+
+/// Name prefix of node locks.
+pub const NODE_LOCK_PREFIX: &str = "node:";
+
+/// The lease name of the lock on `node`.
+pub fn node_lock_name(node: &NodeId) -> String {
+    format!("{}{}", NODE_LOCK_PREFIX, node.key())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lease {
+    pub name: String,
+    pub holder: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Lease {
+    /// The node lock this lease is, when it is one.
+    pub fn node_lock(&self) -> Option<NodeLock> {
+        let key = self.name.strip_prefix(NODE_LOCK_PREFIX)?;
+        Some(NodeLock {
+            node_id: NodeId::from_key(key),
+            holder: self.holder.clone(),
+            acquired_at: self.acquired_at.to_rfc3339(),
+            expires_at: self.expires_at.to_rfc3339(),
+        })
+    }
+}
+
+/// Lease records of one store (or lease directory), by name.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct LeaseTable(HashMap<String, Lease>);
+
+impl LeaseTable {
+    /// Grant or renew `name` for `holder` until `now + ttl`. Fails with
+    /// `StoreErrorCode::Locked` naming the holder while another holder's lease is live.
+    /// Expired leases are dropped on the way.
+    pub fn acquire(
+        &mut self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<Lease, StoreError> {
+        self.0.retain(|_, lease| lease.expires_at > now);
+        let ttl = chrono::Duration::from_std(ttl)
+            .map_err(|e| StoreError::invalid(format!("lease ttl: {}", e)))?;
+        let acquired_at = match self.0.get(name) {
+            Some(current) if current.holder != holder => {
+                return Err(StoreError::locked(format!(
+                    "lease {} is held by {} until {}",
+                    name,
+                    current.holder,
+                    current.expires_at.to_rfc3339()
+                ))
+                .with_resource(format!("lease {}", name)));
+            }
+            Some(current) => current.acquired_at,
+            None => now,
+        };
+        let lease = Lease {
+            name: name.to_string(),
+            holder: holder.to_string(),
+            acquired_at,
+            expires_at: now + ttl,
+        };
+        self.0.insert(name.to_string(), lease.clone());
+        Ok(lease)
+    }
+
+    /// Release `name` if `holder` holds it; a lease held by someone else is left alone.
+    pub fn release(&mut self, name: &str, holder: &str) {
+        if self.0.get(name).is_some_and(|l| l.holder == holder) {
+            self.0.remove(name);
+        }
+    }
+
+    /// Live leases whose name starts with `prefix`, by name.
+    pub fn live(&self, prefix: &str, now: DateTime<Utc>) -> Vec<Lease> {
+        let mut leases: Vec<Lease> = self
+            .0
+            .values()
+            .filter(|l| l.name.starts_with(prefix) && l.expires_at > now)
+            .cloned()
+            .collect();
+        leases.sort_by(|a, b| a.name.cmp(&b.name));
+        leases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    #[test]
+    fn leases_are_exclusive_until_released_or_expired() {
+        let now = Utc::now();
+        let ttl = Duration::from_secs(30);
+        let mut table = LeaseTable::default();
+
+        let first = table.acquire("apply:p-1", "a", ttl, now).unwrap();
+        match table.acquire("apply:p-1", "b", ttl, now) {
+            Err(e) if e.code == StoreErrorCode::Locked => {
+                assert!(e.message.contains("held by a"), "{}", e)
+            }
+            other => panic!("expected a held lease, got {:?}", other),
+        }
+        let renewed = table
+            .acquire("apply:p-1", "a", ttl, now + chrono::Duration::seconds(10))
+            .unwrap();
+        assert_eq!(renewed.acquired_at, first.acquired_at);
+        assert!(renewed.expires_at > first.expires_at);
+
+        table.release("apply:p-1", "b");
+        assert!(table.acquire("apply:p-1", "b", ttl, now).is_err());
+        table.release("apply:p-1", "a");
+        assert!(table.acquire("apply:p-1", "b", ttl, now).is_ok());
+
+        let later = now + chrono::Duration::seconds(31);
+        assert_eq!(
+            table.acquire("apply:p-1", "a", ttl, later).unwrap().holder,
+            "a"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Leases shared between processes through a directory (`cluster.lease_dir`).
+//!
+//! The lease table is `{dir}/leases.json`. Every acquire or release takes an exclusive
+//! advisory lock on `{dir}/leases.lock` (`flock` on Unix, `LockFileEx` on Windows),
+//! reads the table, changes it and writes it back (temp file, then rename) before
+//! unlocking: a compare-and-swap, so servers sharing the directory, e.g. on a volume
+//! every replica mounts, see one holder per lease. The rules are those of
+//! [`LeaseTable`]; a holder that dies keeps its leases until they expire.
+//!
+//! The calls block on the file lock and the disk; async callers run them on the
+//! blocking pool (see `crate::cluster`).
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::store::context_store::StoreError;
+use crate::store::disk_writer::{write_atomic, Durability};
+use crate::store::lease::{Lease, LeaseTable};
+
+/// A lease table in a directory; cheap to open, every call goes to disk.
+#[derive(Debug, Clone)]
+pub struct LeaseDir {
+    dir: PathBuf,
+}
+
+impl LeaseDir {
+    /// The lease table in `dir`, created with its first lease.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// [`LeaseTable::acquire`] on the shared table.
+    pub fn acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<Lease, StoreError> {
+        self.update(|table| table.acquire(name, holder, ttl, chrono::Utc::now()))
+    }
+
+    /// [`LeaseTable::release`] on the shared table.
+    pub fn release(&self, name: &str, holder: &str) -> Result<(), StoreError> {
+        self.update(|table| {
+            table.release(name, holder);
+            Ok(())
+        })
+    }
+
+    /// [`LeaseTable::live`] on the shared table.
+    pub fn live(&self, prefix: &str) -> Result<Vec<Lease>, StoreError> {
+        let _lock = self.lock()?;
+        Ok(self.read()?.live(prefix, chrono::Utc::now()))
+    }
+
+    /// Read, change and write back the table under the directory lock. A refused
+    /// change writes nothing.
+    fn update<T>(
+        &self,
+        change: impl FnOnce(&mut LeaseTable) -> Result<T, StoreError>,
+    ) -> Result<T, StoreError> {
+        let _lock = self.lock()?;
+        let mut table = self.read()?;
+        let result = change(&mut table)?;
+        let json = serde_json::to_vec(&table).map_err(|e| StoreError::internal(e.to_string()))?;
+        write_atomic(&self.table_file(), &json, Durability::Fsync)?;
+        Ok(result)
+    }
+
+    fn read(&self) -> Result<LeaseTable, StoreError> {
+        let path = self.table_file();
+        match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                StoreError::internal(format!("unreadable lease table {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(LeaseTable::default()),
+            Err(e) => Err(StoreError::io(path.display(), e)),
+        }
+    }
+
+    /// The exclusive lock on the directory, released when the file is dropped.
+    fn lock(&self) -> Result<File, StoreError> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| StoreError::io(format!("mkdir {}", self.dir.display()), e))?;
+        let path = self.dir.join("leases.lock");
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| StoreError::io(format!("open {}", path.display()), e))?;
+        file.lock()
+            .map_err(|e| StoreError::io(format!("lock {}", path.display()), e))?;
+        Ok(file)
+    }
+
+    fn table_file(&self) -> PathBuf {
+        self.dir.join("leases.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    #[test]
+    fn handles_on_one_directory_share_their_leases() {
+        let dir = std::env::temp_dir().join(format!("tl-leases-{}", uuid::Uuid::new_v4()));
+        // Two handles, as two servers would open the directory.
+        let (a, b) = (LeaseDir::new(&dir), LeaseDir::new(&dir));
+        let ttl = Duration::from_secs(30);
+
+        a.acquire("apply:p-1", "a", ttl).unwrap();
+        let err = b.acquire("apply:p-1", "b", ttl).unwrap_err();
+        assert_eq!(err.code, StoreErrorCode::Locked);
+        assert!(err.message.contains("held by a"), "{}", err);
+        assert_eq!(b.live("apply:").unwrap().len(), 1);
+
+        b.release("apply:p-1", "b").unwrap();
+        assert!(b.acquire("apply:p-1", "b", ttl).is_err());
+        a.release("apply:p-1", "a").unwrap();
+        assert_eq!(b.acquire("apply:p-1", "b", ttl).unwrap().holder, "b");
+
+        // Expired leases are taken over.
+        a.acquire("task:t", "a", Duration::from_millis(1)).unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(b.acquire("task:t", "b", ttl).unwrap().holder, "b");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
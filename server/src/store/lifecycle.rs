@@ -0,0 +1,584 @@
+//! Proposal state machine, shared by the store backends and the API handlers.
+//!
+//! ```text
+//! open ──review accept──▶ accepted ──apply──▶ applied (final)
+//!   │ ──review reject──▶ rejected
+//!   │ ──request changes─▶ open
+//!   └ ──withdraw──────▶ withdrawn
+//!
+//! quarantined ──triage release──▶ open
+//!   │ ──triage reject──▶ rejected
+//!   └ ──withdraw──────▶ withdrawn
+//!
+//! open ──auto-merge──▶ superseded (final)
+//! ```
+//!
+//! Reviews need an open proposal and withdrawals an open or quarantined one; only
+//! accepted proposals are applied, and only `apply_proposal` reaches `applied` (it also
+//! records the applied metadata). Agent proposals start `quarantined` under the
+//! `agent_quarantine` policy, and only triage lets them out. Only open proposals have
+//! their operations edited or are superseded. PATCH only settles an open proposal as
+//! `accepted` or `rejected`, or keeps the status it has: [`PATCH_TRANSITIONS`] lists
+//! them, and nothing is reopened. Settling one by PATCH still needs the reviews that
+//! would have settled it (`policy::evaluate_on_update`).
+//! Only `POST /proposals/:id/withdraw` withdraws, after its author check.
+//! [`next_status`] is the whole table: every refused transition comes back as a
+//! [`Rejection`] saying why. Backends run it on the proposal they hold under their
+//! lock; handlers run it to refuse early (before policies, forge or Slack calls).
+
+use std::fmt;
+
+use crate::store::context_store::{StoreError, StoreErrorCode};
+use crate::types::{
+    AppliedMetadata, Complexity, Operation, Proposal, ProposalPatch, ProposalStatus, Review,
+    ReviewAction, TriageAction,
+};
+
+/// Something done to a proposal that may change its status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Review(ReviewAction),
+    Withdraw,
+    Apply,
+    /// Break-glass apply (`service::emergency_apply`): an open proposal too.
+    EmergencyApply,
+    /// `POST /proposals/triage` on a quarantined proposal.
+    Triage(TriageAction),
+    /// `status` in a PATCH body.
+    SetStatus(ProposalStatus),
+    /// `PATCH /proposals/:id/operations`; the status stays.
+    EditOperations,
+    /// `POST /proposals/auto-merge` on each proposal it combines.
+    Supersede,
+}
+
+impl Transition {
+    fn verb(&self) -> &'static str {
+        match self {
+            Transition::Review(_) => "review",
+            Transition::Withdraw => "withdraw",
+            Transition::Apply | Transition::EmergencyApply => "apply",
+            Transition::Triage(_) => "triage",
+            Transition::SetStatus(_) => "change the status of",
+            Transition::EditOperations => "edit the operations of",
+            Transition::Supersede => "supersede",
+        }
+    }
+}
+
+/// A transition the state machine refuses, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub from: ProposalStatus,
+    pub transition: Transition,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let from = serde_json::to_value(self.from)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        write!(
+            f,
+            "cannot {} a proposal that is {}: {}",
+            self.transition.verb(),
+            from,
+            self.reason
+        )
+    }
+}
+
+impl From<Rejection> for StoreError {
+    fn from(r: Rejection) -> Self {
+        StoreError::new(StoreErrorCode::InvalidTransition, r.to_string())
+    }
+}
+
+/// Why a proposal in `from` no longer takes reviews, withdrawals or applies.
+fn closed_reason(from: ProposalStatus) -> &'static str {
+    match from {
+        ProposalStatus::Open => "it is still open",
+        ProposalStatus::Accepted => "it was already accepted and only awaits apply",
+        ProposalStatus::Rejected => "it was rejected",
+        ProposalStatus::Withdrawn => "it was withdrawn",
+        ProposalStatus::Applied => "it was applied, which is final",
+        ProposalStatus::Quarantined => "it is quarantined until a human triages it",
+        ProposalStatus::Superseded => {
+            "it was superseded by an auto-merged proposal, which is final"
+        }
+    }
+}
+
+/// The status changes `PATCH /proposals/:id` makes (from, to); any other is refused.
+pub const PATCH_TRANSITIONS: [(ProposalStatus, ProposalStatus); 5] = [
+    (ProposalStatus::Open, ProposalStatus::Open),
+    (ProposalStatus::Open, ProposalStatus::Accepted),
+    (ProposalStatus::Open, ProposalStatus::Rejected),
+    (ProposalStatus::Accepted, ProposalStatus::Accepted),
+    (ProposalStatus::Rejected, ProposalStatus::Rejected),
+];
+
+/// The status `transition` leads to from `from`, or why it is refused.
+pub fn next_status(
+    from: ProposalStatus,
+    transition: Transition,
+) -> Result<ProposalStatus, Rejection> {
+    let reject = |reason| {
+        Err(Rejection {
+            from,
+            transition,
+            reason,
+        })
+    };
+    match (from, transition) {
+        (ProposalStatus::Open, Transition::Review(action)) => Ok(match action {
+            ReviewAction::Accept => ProposalStatus::Accepted,
+            ReviewAction::Reject => ProposalStatus::Rejected,
+            ReviewAction::RequestChanges => ProposalStatus::Open,
+        }),
+        (ProposalStatus::Open | ProposalStatus::Quarantined, Transition::Withdraw) => {
+            Ok(ProposalStatus::Withdrawn)
+        }
+        (ProposalStatus::Quarantined, Transition::Triage(action)) => Ok(match action {
+            TriageAction::Release => ProposalStatus::Open,
+            TriageAction::Reject => ProposalStatus::Rejected,
+        }),
+        (_, Transition::Triage(_)) => reject("only quarantined proposals are triaged"),
+        (ProposalStatus::Accepted, Transition::Apply) => Ok(ProposalStatus::Applied),
+        (ProposalStatus::Open, Transition::Apply) => reject("it has not been accepted yet"),
+        (ProposalStatus::Open | ProposalStatus::Accepted, Transition::EmergencyApply) => {
+            Ok(ProposalStatus::Applied)
+        }
+        (ProposalStatus::Open, Transition::Supersede) => Ok(ProposalStatus::Superseded),
+        (
+            _,
+            Transition::Review(_)
+            | Transition::Withdraw
+            | Transition::Apply
+            | Transition::EmergencyApply,
+        ) => reject(closed_reason(from)),
+        (_, Transition::Supersede) => reject(closed_reason(from)),
+        (_, Transition::SetStatus(ProposalStatus::Applied)) => {
+            reject("only POST /proposals/:id/apply makes a proposal applied")
+        }
+        (_, Transition::SetStatus(ProposalStatus::Superseded)) => {
+            reject("only POST /proposals/auto-merge supersedes a proposal")
+        }
+        (_, Transition::SetStatus(ProposalStatus::Withdrawn)) => {
+            reject("only POST /proposals/:id/withdraw withdraws a proposal")
+        }
+        (
+            ProposalStatus::Applied | ProposalStatus::Superseded | ProposalStatus::Withdrawn,
+            Transition::SetStatus(_),
+        ) => reject(closed_reason(from)),
+        (ProposalStatus::Quarantined, Transition::SetStatus(_)) => {
+            reject("only POST /proposals/triage takes a proposal out of quarantine")
+        }
+        (_, Transition::SetStatus(ProposalStatus::Quarantined)) => {
+            reject("only agent proposals are quarantined, when they are created")
+        }
+        (_, Transition::SetStatus(to)) if PATCH_TRANSITIONS.contains(&(from, to)) => Ok(to),
+        (_, Transition::SetStatus(_)) => reject(closed_reason(from)),
+        (ProposalStatus::Open, Transition::EditOperations) => Ok(ProposalStatus::Open),
+        (_, Transition::EditOperations) => reject(closed_reason(from)),
+    }
+}
+
+/// True when no review, withdrawal or apply can follow.
+pub fn is_closed(status: ProposalStatus) -> bool {
+    [
+        Transition::Review(ReviewAction::RequestChanges),
+        Transition::Withdraw,
+        Transition::Apply,
+    ]
+    .into_iter()
+    .all(|t| next_status(status, t).is_err())
+}
+
+/// Apply a patch; its status must be reachable from the current one.
+/// `metadata.forge` and `metadata.conflictResolutions` come from the server only; the
+/// REST PATCH refuses them.
+pub(crate) fn apply_update(
+    proposal: &mut Proposal,
+    patch: &ProposalPatch,
+) -> Result<(), StoreError> {
+    if let Some(status) = patch.status {
+        proposal.status = next_status(proposal.status, Transition::SetStatus(status))?;
+    }
+    if let Some(m) = &patch.metadata {
+        if let Some(v) = &m.modified_at {
+            proposal.metadata.modified_at = v.clone();
+        }
+        if let Some(v) = &m.modified_by {
+            proposal.metadata.modified_by = v.clone();
+        }
+        if let Some(v) = &m.rationale {
+            proposal.metadata.rationale = Some(v.clone());
+        }
+        if let Some(v) = &m.forge {
+            proposal.metadata.forge = Some(v.clone());
+        }
+        if let Some(v) = &m.conflict_resolutions {
+            proposal.metadata.conflict_resolutions = Some(v.clone());
+        }
+    }
+    if let Some(comments) = &patch.comments {
+        proposal.comments = Some(comments.clone());
+    }
+    Ok(())
+}
+
+/// Replace the operations of an open proposal, with their new complexity score.
+pub(crate) fn edit_operations(
+    proposal: &mut Proposal,
+    operations: &[Operation],
+    complexity: &Complexity,
+) -> Result<(), StoreError> {
+    next_status(proposal.status, Transition::EditOperations)?;
+    proposal.operations = operations.to_vec();
+    proposal.metadata.complexity = Some(complexity.clone());
+    Ok(())
+}
+
+/// Record the outcome of a review: accept / reject close the proposal, a change request
+/// leaves it open.
+pub(crate) fn apply_review(proposal: &mut Proposal, review: &Review) -> Result<(), StoreError> {
+    proposal.status = next_status(proposal.status, Transition::Review(review.action))?;
+    Ok(())
+}
+
+/// Refuse a review `proposal` cannot take, without changing its status.
+pub(crate) fn check_review(proposal: &Proposal, review: &Review) -> Result<(), StoreError> {
+    next_status(proposal.status, Transition::Review(review.action))?;
+    Ok(())
+}
+
+/// Whether `proposal` should be applied: false when it already was (apply is
+/// idempotent), an error unless it is accepted (or, `unreviewed`, open).
+pub(crate) fn check_apply(proposal: &Proposal, unreviewed: bool) -> Result<bool, StoreError> {
+    if proposal.status == ProposalStatus::Applied {
+        return Ok(false);
+    }
+    let transition = if unreviewed {
+        Transition::EmergencyApply
+    } else {
+        Transition::Apply
+    };
+    next_status(proposal.status, transition)?;
+    Ok(true)
+}
+
+/// Id of the review that accepted the proposal (the latest accepting one).
+pub(crate) fn accepting_review(reviews: &[Review]) -> Option<String> {
+    reviews
+        .iter()
+        .rev()
+        .find(|r| r.action == ReviewAction::Accept)
+        .map(|r| r.id.clone())
+}
+
+/// Mark an accepted proposal applied, moving the store from revision `previous` to
+/// `previous + 1`. `review_id` is the review that accepted it, when there is one.
+pub(crate) fn mark_applied(
+    proposal: &mut Proposal,
+    applied_by: &str,
+    review_id: Option<String>,
+    previous: u64,
+) {
+    proposal.status = ProposalStatus::Applied;
+    proposal.applied = Some(AppliedMetadata {
+        applied_at: chrono::Utc::now().to_rfc3339(),
+        applied_by: applied_by.to_string(),
+        applied_from_review_id: review_id,
+        applied_from_proposal_id: proposal.id.clone(),
+        applied_to_revision_id: format!("rev_{}", previous + 1),
+        previous_revision_id: format!("rev_{}", previous),
+    });
+}
+
+pub(crate) fn withdraw(proposal: &mut Proposal) -> Result<(), StoreError> {
+    proposal.status = next_status(proposal.status, Transition::Withdraw)?;
+    Ok(())
+}
+
+/// Mark an open proposal replaced by the auto-merged proposal `by`.
+pub(crate) fn supersede(proposal: &mut Proposal, by: &str) -> Result<(), StoreError> {
+    proposal.status = next_status(proposal.status, Transition::Supersede)?;
+    proposal.metadata.superseded_by = Some(by.to_string());
+    Ok(())
+}
+
+/// Release a quarantined proposal into review, or reject it.
+pub(crate) fn triage(proposal: &mut Proposal, action: TriageAction) -> Result<(), StoreError> {
+    proposal.status = next_status(proposal.status, Transition::Triage(action))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ProposalMetadata;
+
+    const ALL: [ProposalStatus; 7] = [
+        ProposalStatus::Open,
+        ProposalStatus::Accepted,
+        ProposalStatus::Rejected,
+        ProposalStatus::Withdrawn,
+        ProposalStatus::Applied,
+        ProposalStatus::Quarantined,
+        ProposalStatus::Superseded,
+    ];
+
+    fn proposal(status: ProposalStatus) -> Proposal {
+        Proposal {
+            id: "p-1".to_string(),
+            status,
+            operations: Vec::new(),
+            metadata: ProposalMetadata {
+                created_at: "2026-03-01T00:00:00Z".to_string(),
+                created_by: "alice".to_string(),
+                modified_at: "2026-03-01T00:00:00Z".to_string(),
+                modified_by: "alice".to_string(),
+                rationale: None,
+                required_approvers: None,
+                approved_by: None,
+                base_versions: None,
+                force_status_transitions: None,
+                forge: None,
+                complexity: None,
+                authors: None,
+                superseded_by: None,
+                conflict_resolutions: None,
+            },
+            comments: None,
+            relations: None,
+            applied: None,
+        }
+    }
+
+    fn review(id: &str, action: ReviewAction) -> Review {
+        Review {
+            id: id.to_string(),
+            proposal_id: "p-1".to_string(),
+            reviewer: "bob".to_string(),
+            reviewer_role: None,
+            reviewer_groups: None,
+            reviewed_at: "2026-03-02T00:00:00Z".to_string(),
+            action,
+            comment: None,
+            comments: None,
+            operation_ids: None,
+            is_approval: None,
+            on_behalf_of: None,
+        }
+    }
+
+    #[test]
+    fn patch_transitions() {
+        for from in ALL {
+            for target in ALL {
+                let mut p = proposal(from);
+                let result = apply_update(&mut p, &ProposalPatch::status(target));
+                if PATCH_TRANSITIONS.contains(&(from, target)) {
+                    assert!(result.is_ok(), "{:?} -> {:?}", from, target);
+                    assert_eq!(p.status, target);
+                } else {
+                    assert!(
+                        matches!(
+                            result,
+                            Err(StoreError {
+                                code: StoreErrorCode::InvalidTransition,
+                                ..
+                            })
+                        ),
+                        "{:?} -> {:?}",
+                        from,
+                        target
+                    );
+                    assert_eq!(p.status, from);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn patch_refuses_reopening_and_skipping_endpoints() {
+        use ProposalStatus::*;
+        for (from, to, reason) in [
+            (Rejected, Open, "it was rejected"),
+            (Withdrawn, Open, "it was withdrawn"),
+            (Accepted, Open, "only awaits apply"),
+            (Accepted, Rejected, "only awaits apply"),
+            (Rejected, Accepted, "it was rejected"),
+            (Open, Withdrawn, "POST /proposals/:id/withdraw"),
+            (Open, Applied, "POST /proposals/:id/apply"),
+            (Open, Superseded, "POST /proposals/auto-merge"),
+            (Open, Quarantined, "when they are created"),
+            (Quarantined, Open, "POST /proposals/triage"),
+        ] {
+            let mut p = proposal(from);
+            let err = apply_update(&mut p, &ProposalPatch::status(to)).unwrap_err();
+            assert_eq!(
+                err.code,
+                StoreErrorCode::InvalidTransition,
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+            assert!(
+                err.message.contains(reason),
+                "{:?} -> {:?}: {}",
+                from,
+                to,
+                err
+            );
+            assert_eq!(p.status, from);
+        }
+    }
+
+    #[test]
+    fn review_apply_and_withdraw_transitions() {
+        for from in ALL {
+            let open = from == ProposalStatus::Open;
+            for (action, to) in [
+                (ReviewAction::Accept, ProposalStatus::Accepted),
+                (ReviewAction::Reject, ProposalStatus::Rejected),
+                (ReviewAction::RequestChanges, ProposalStatus::Open),
+            ] {
+                let mut p = proposal(from);
+                assert_eq!(apply_review(&mut p, &review("r", action)).is_ok(), open);
+                assert_eq!(p.status, if open { to } else { from });
+            }
+
+            let withdrawable = open || from == ProposalStatus::Quarantined;
+            let mut p = proposal(from);
+            assert_eq!(withdraw(&mut p).is_ok(), withdrawable);
+            assert_eq!(
+                p.status,
+                if withdrawable {
+                    ProposalStatus::Withdrawn
+                } else {
+                    from
+                }
+            );
+
+            match (from, check_apply(&proposal(from), false)) {
+                (ProposalStatus::Accepted, Ok(true)) | (ProposalStatus::Applied, Ok(false)) => {}
+                (ProposalStatus::Accepted | ProposalStatus::Applied, other) => {
+                    panic!("{:?}: {:?}", from, other)
+                }
+                (_, result) => assert!(matches!(
+                    result,
+                    Err(StoreError {
+                        code: StoreErrorCode::InvalidTransition,
+                        ..
+                    })
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn transition_table() {
+        use ProposalStatus::*;
+        let transitions = [
+            Transition::Review(ReviewAction::Accept),
+            Transition::Review(ReviewAction::Reject),
+            Transition::Review(ReviewAction::RequestChanges),
+            Transition::Withdraw,
+            Transition::Apply,
+        ];
+        // Rows follow ALL, columns follow `transitions`; None = refused.
+        let expected: [[Option<ProposalStatus>; 5]; 7] = [
+            [
+                Some(Accepted),
+                Some(Rejected),
+                Some(Open),
+                Some(Withdrawn),
+                None,
+            ],
+            [None, None, None, None, Some(Applied)],
+            [None; 5],
+            [None; 5],
+            [None; 5],
+            [None, None, None, Some(Withdrawn), None],
+            [None; 5],
+        ];
+        for (from, row) in ALL.into_iter().zip(expected) {
+            for (transition, to) in transitions.into_iter().zip(row) {
+                match (next_status(from, transition), to) {
+                    (Ok(got), Some(to)) => assert_eq!(got, to, "{:?} {:?}", from, transition),
+                    (Err(rejection), None) => {
+                        assert_eq!((rejection.from, rejection.transition), (from, transition));
+                        assert!(!rejection.reason.is_empty());
+                    }
+                    (got, _) => panic!("{:?} {:?}: {:?}", from, transition, got),
+                }
+            }
+            for to in ALL {
+                let allowed = PATCH_TRANSITIONS.contains(&(from, to));
+                assert_eq!(
+                    next_status(from, Transition::SetStatus(to)).is_ok(),
+                    allowed,
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+            assert_eq!(
+                is_closed(from),
+                matches!(from, Rejected | Withdrawn | Applied | Superseded)
+            );
+        }
+        assert_eq!(
+            next_status(Withdrawn, Transition::Review(ReviewAction::Accept))
+                .unwrap_err()
+                .to_string(),
+            "cannot review a proposal that is withdrawn: it was withdrawn"
+        );
+    }
+
+    #[test]
+    fn quarantine_is_left_by_triage_or_withdrawal_only() {
+        use ProposalStatus::*;
+        for (action, to) in [
+            (TriageAction::Release, Open),
+            (TriageAction::Reject, Rejected),
+        ] {
+            let mut p = proposal(Quarantined);
+            triage(&mut p, action).unwrap();
+            assert_eq!(p.status, to);
+            assert!(triage(&mut p, action).is_err());
+        }
+        let mut p = proposal(Quarantined);
+        assert!(apply_review(&mut p, &review("r", ReviewAction::Accept)).is_err());
+        assert!(check_apply(&p, false).is_err());
+        for to in ALL {
+            assert!(apply_update(&mut p, &ProposalPatch::status(to)).is_err());
+            assert!(next_status(to, Transition::SetStatus(Quarantined)).is_err());
+        }
+        assert_eq!(p.status, Quarantined);
+        assert!(!is_closed(Quarantined));
+        withdraw(&mut p).unwrap();
+        assert_eq!(p.status, Withdrawn);
+    }
+
+    #[test]
+    fn applied_metadata_names_the_accepting_review() {
+        let reviews = [
+            review("r-1", ReviewAction::Reject),
+            review("r-2", ReviewAction::Accept),
+            review("r-3", ReviewAction::Reject),
+        ];
+        let mut p = proposal(ProposalStatus::Accepted);
+        mark_applied(&mut p, "carol", accepting_review(&reviews), 4);
+        assert_eq!(p.status, ProposalStatus::Applied);
+        let applied = p.applied.unwrap();
+        assert_eq!(applied.applied_from_review_id.as_deref(), Some("r-2"));
+        assert_eq!(applied.applied_from_proposal_id, "p-1");
+        assert_eq!(applied.previous_revision_id, "rev_4");
+        assert_eq!(applied.applied_to_revision_id, "rev_5");
+    }
+}
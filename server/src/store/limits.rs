@@ -0,0 +1,109 @@
+//! Memory bounds for `InMemoryStore` (`storage.memory` in config.json) and the store
+//! status report served at `GET /admin/store/status`.
+//!
+//! Nodes and proposals are the accepted truth and its pending changes, so they are never
+//! evicted: writes that would exceed `max_nodes` / `max_proposals` are rejected with
+//! [`StoreErrorCode::CapacityExceeded`](super::context_store::StoreErrorCode::CapacityExceeded).
+//! The audit log either rejects appends too (`audit_overflow: "reject"`) or moves its
+//! oldest page of events to a JSON Lines file on disk (`"spill"`, the default); spilled
+//! events are still returned by audit queries and exports.
+
+use serde::{Deserialize, Serialize};
+
+/// What happens when the in-memory audit log reaches `max_audit_events`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOverflow {
+    /// Refuse new audit events.
+    Reject,
+    /// Write the oldest `audit_page_size` events to disk and drop them from memory.
+    #[default]
+    Spill,
+}
+
+/// Limits for the memory backend. Unset maxima are unbounded (the default).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_nodes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_proposals: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_audit_events: Option<usize>,
+    #[serde(default)]
+    pub audit_overflow: AuditOverflow,
+    /// Events written per spilled page.
+    #[serde(default = "default_audit_page_size")]
+    pub audit_page_size: usize,
+    /// Spill directory, relative to the config root. Each store instance writes to its
+    /// own subdirectory, so pages from earlier runs are kept but not read back.
+    #[serde(default = "default_audit_spill_dir")]
+    pub audit_spill_dir: String,
+}
+
+fn default_audit_page_size() -> usize {
+    1000
+}
+
+fn default_audit_spill_dir() -> String {
+    "audit-spill".to_string()
+}
+
+impl Default for MemoryLimits {
+    fn default() -> Self {
+        Self {
+            max_nodes: None,
+            max_proposals: None,
+            max_audit_events: None,
+            audit_overflow: AuditOverflow::default(),
+            audit_page_size: default_audit_page_size(),
+            audit_spill_dir: default_audit_spill_dir(),
+        }
+    }
+}
+
+impl MemoryLimits {
+    /// Problems in a `storage.memory` config.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for (name, value) in [
+            ("max_nodes", self.max_nodes),
+            ("max_proposals", self.max_proposals),
+            ("max_audit_events", self.max_audit_events),
+        ] {
+            if value == Some(0) {
+                issues.push(format!("storage.memory.{}: must be at least 1", name));
+            }
+        }
+        if self.audit_page_size == 0 {
+            issues.push("storage.memory.audit_page_size: must be at least 1".to_string());
+        }
+        issues
+    }
+}
+
+/// Record counts and approximate memory use of a store.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreStatus {
+    pub nodes: usize,
+    pub proposals: usize,
+    pub reviews: usize,
+    /// Audit events held in memory.
+    pub audit_events: usize,
+    /// Audit events moved to disk by the spill policy.
+    pub audit_events_spilled: usize,
+    pub export_jobs: usize,
+    pub jobs: usize,
+    /// Approximate bytes held, measured as the serialized JSON size of the records plus
+    /// export artifacts. Allocator overhead and indexes are not counted.
+    pub approx_bytes: usize,
+    /// Configured limits (memory backend only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<MemoryLimits>,
+}
+
+/// Serialized JSON size of a record, the unit of [`StoreStatus::approx_bytes`].
+pub(crate) fn json_size<T: Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).map(|v| v.len()).unwrap_or(0)
+}
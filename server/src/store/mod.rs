@@ -1,7 +1,38 @@
+mod audit_index;
+pub mod batch;
+mod blame;
+pub mod bundle;
+pub mod compact;
 pub mod context_store;
+mod diff3;
+pub(crate) mod dir_lock;
+pub mod directory;
+pub mod disk_writer;
 pub mod file_store;
 pub mod in_memory;
+mod journal;
+pub mod lease;
+pub mod lease_dir;
+pub mod lifecycle;
+pub mod limits;
+mod node_index;
+pub mod outbox;
+pub(crate) mod reconcile;
+pub mod trace;
+mod trash;
+pub mod usage;
 
+pub use batch::{BatchOp, WriteBatch};
+pub use bundle::{load_bundles, ImportSummary, StoreBundle};
+pub use compact::{CompactOptions, CompactReport};
 pub use context_store::ContextStore;
+pub use directory::{ActorAccess, Directory, DirectoryGroup, DirectoryUser};
+pub use disk_writer::{Durability, FileStoreOptions};
 pub use file_store::FileStore;
 pub use in_memory::InMemoryStore;
+pub use lease::Lease;
+pub use lease_dir::LeaseDir;
+pub use limits::{AuditOverflow, MemoryLimits, StoreStatus};
+pub use outbox::OutboxEntry;
+pub use trace::NodeProposal;
+pub use usage::{UsageLedger, UsageRecord};
@@ -0,0 +1,292 @@
+//! Node table with secondary indexes, used by both store backends.
+//!
+//! Nodes are keyed by `NodeId::key()`. Indexes by status, type, namespace, tag and commit are
+//! kept in step with every insert and change, so [`NodeTable::query`] narrows to candidate
+//! keys without scanning the whole map, then walks them in key order and clones only the
+//! nodes on the requested page. Both backends answer `query_nodes` through it, so a
+//! filter means the same thing whichever one is configured.
+//!
+//! Deleted nodes (tombstones, see `store::trash`) stay in the table but not in the
+//! indexes: queries and [`NodeTable::get`] skip them, [`NodeTable::trash`] lists them.
+//! Archived nodes are indexed as usual but left out of queries that do not ask for them
+//! (`NodeQuery::shows_archived`).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::types::{ContextNode, NodeQuery, NodeQueryResult, NodeStatus, NodeType};
+
+#[derive(Default)]
+pub(crate) struct NodeTable {
+    nodes: HashMap<String, ContextNode>,
+    /// All keys, in order (paging order for unfiltered queries).
+    keys: BTreeSet<String>,
+    by_status: HashMap<NodeStatus, BTreeSet<String>>,
+    by_type: HashMap<NodeType, BTreeSet<String>>,
+    /// Namespace (`None` = default namespace) → keys.
+    by_namespace: HashMap<Option<String>, BTreeSet<String>>,
+    by_tag: HashMap<String, BTreeSet<String>>,
+    /// Lowercased commit SHA (`implementedInCommit`, `referencedInCommits`) → keys; ordered
+    /// so a SHA prefix is a range.
+    by_commit: BTreeMap<String, BTreeSet<String>>,
+    /// Keys of deleted nodes; these are in no other index.
+    trash: BTreeSet<String>,
+}
+
+impl NodeTable {
+    /// A live node; None for unknown and deleted keys.
+    pub fn get(&self, key: &str) -> Option<&ContextNode> {
+        self.nodes.get(key).filter(|n| n.metadata.deleted.is_none())
+    }
+
+    pub fn is_deleted(&self, key: &str) -> bool {
+        self.trash.contains(key)
+    }
+
+    /// A deleted node; None for unknown and live keys.
+    pub fn get_deleted(&self, key: &str) -> Option<&ContextNode> {
+        self.nodes.get(key).filter(|n| n.metadata.deleted.is_some())
+    }
+
+    /// Deleted nodes, in key order.
+    pub fn trash(&self) -> impl Iterator<Item = &ContextNode> {
+        self.trash.iter().filter_map(|k| self.nodes.get(k))
+    }
+
+    /// Live and deleted nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    /// Live and deleted nodes (bundles keep the trash).
+    pub fn values(&self) -> impl Iterator<Item = &ContextNode> {
+        self.nodes.values()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Insert or replace a node, updating the indexes.
+    pub fn insert(&mut self, key: String, node: ContextNode) {
+        if let Some(old) = self.nodes.remove(&key) {
+            self.unindex(&key, &old);
+        }
+        self.index(&key, &node);
+        self.nodes.insert(key, node);
+    }
+
+    /// Remove a node and its index entries.
+    pub fn remove(&mut self, key: &str) -> Option<ContextNode> {
+        let node = self.nodes.remove(key)?;
+        self.unindex(key, &node);
+        Some(node)
+    }
+
+    /// Change a node in place, live or deleted, re-indexing it afterwards. False when the
+    /// key is unknown.
+    pub fn modify(&mut self, key: &str, f: impl FnOnce(&mut ContextNode)) -> bool {
+        let Some(mut node) = self.nodes.remove(key) else {
+            return false;
+        };
+        self.unindex(key, &node);
+        f(&mut node);
+        self.index(key, &node);
+        self.nodes.insert(key.to_string(), node);
+        true
+    }
+
+    /// All nodes with the given status, in key order.
+    pub fn with_status(&self, status: NodeStatus) -> Vec<ContextNode> {
+        self.by_status
+            .get(&status)
+            .into_iter()
+            .flatten()
+            .filter_map(|k| self.nodes.get(k).cloned())
+            .collect()
+    }
+
+    /// Keys that pass the indexed filters of `query` (status, type, namespace, all
+    /// tags, commit), in key order. Filters without an index (search, creator) are not applied.
+    pub fn candidates<'a>(&'a self, query: &NodeQuery) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        let mut sets: Vec<BTreeSet<&str>> = Vec::new();
+        if let Some(statuses) = &query.status {
+            sets.push(union(statuses.iter().filter_map(|s| self.by_status.get(s))));
+        }
+        if let Some(types) = &query.r#type {
+            sets.push(union(types.iter().filter_map(|t| self.by_type.get(t))));
+        }
+        if let Some(namespace) = &query.namespace {
+            sets.push(union(self.by_namespace.get(&Some(namespace.clone()))));
+        }
+        for tag in query.tags.iter().flatten() {
+            sets.push(union(self.by_tag.get(tag)));
+        }
+        if let Some(commit) = &query.commit {
+            let prefix = commit.trim().to_lowercase();
+            sets.push(union(
+                self.by_commit
+                    .range(prefix.clone()..)
+                    .take_while(|(sha, _)| sha.starts_with(&prefix))
+                    .map(|(_, keys)| keys),
+            ));
+        }
+
+        // Intersect starting from the smallest set.
+        sets.sort_by_key(|s| s.len());
+        let mut sets = sets.into_iter();
+        let Some(mut result) = sets.next() else {
+            return Box::new(self.keys.iter().map(String::as_str));
+        };
+        for set in sets {
+            result.retain(|k| set.contains(k));
+        }
+        Box::new(result.into_iter())
+    }
+
+    /// One page of the nodes matching every filter of `query`, in key order.
+    pub fn query(&self, query: &NodeQuery) -> NodeQueryResult {
+        let limit = query.limit.unwrap_or(50).min(1000);
+        let offset = query.offset.unwrap_or(0) as usize;
+        let search = query.search.as_ref().map(|s| s.to_lowercase());
+        let mut total = 0usize;
+        let mut page = Vec::new();
+        // Indexed filters narrow the candidates; the rest are checked per node.
+        for key in self.candidates(query) {
+            let Some(node) = self.nodes.get(key) else {
+                continue;
+            };
+            if !matches_unindexed(node, query, search.as_deref()) {
+                continue;
+            }
+            if total >= offset && page.len() < limit as usize {
+                page.push(node.clone());
+            }
+            total += 1;
+        }
+        let has_more = offset + page.len() < total;
+
+        NodeQueryResult {
+            nodes: page,
+            total: total as u64,
+            limit,
+            offset: offset as u32,
+            has_more,
+        }
+    }
+
+    fn index(&mut self, key: &str, node: &ContextNode) {
+        if node.metadata.deleted.is_some() {
+            self.trash.insert(key.to_string());
+            return;
+        }
+        self.keys.insert(key.to_string());
+        self.by_status
+            .entry(node.status)
+            .or_default()
+            .insert(key.to_string());
+        self.by_type
+            .entry(node.node_type.clone())
+            .or_default()
+            .insert(key.to_string());
+        self.by_namespace
+            .entry(node.id.namespace.clone())
+            .or_default()
+            .insert(key.to_string());
+        for tag in node.metadata.tags.iter().flatten() {
+            self.by_tag
+                .entry(tag.clone())
+                .or_default()
+                .insert(key.to_string());
+        }
+        for sha in commits(node) {
+            self.by_commit
+                .entry(sha)
+                .or_default()
+                .insert(key.to_string());
+        }
+    }
+
+    fn unindex(&mut self, key: &str, node: &ContextNode) {
+        if node.metadata.deleted.is_some() {
+            self.trash.remove(key);
+            return;
+        }
+        self.keys.remove(key);
+        remove_from(&mut self.by_status, &node.status, key);
+        remove_from(&mut self.by_type, &node.node_type, key);
+        remove_from(&mut self.by_namespace, &node.id.namespace, key);
+        for tag in node.metadata.tags.iter().flatten() {
+            remove_from(&mut self.by_tag, tag, key);
+        }
+        for sha in commits(node) {
+            if let Some(keys) = self.by_commit.get_mut(&sha) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.by_commit.remove(&sha);
+                }
+            }
+        }
+    }
+}
+
+/// Commits a node is linked to, lowercased.
+fn commits(node: &ContextNode) -> impl Iterator<Item = String> + '_ {
+    node.metadata
+        .implemented_in_commit
+        .iter()
+        .chain(node.metadata.referenced_in_commits.iter().flatten())
+        .map(|sha| sha.to_lowercase())
+}
+
+/// Filters [`NodeTable::candidates`] leaves out: case-insensitive `search` (already
+/// lowercased) over content, title and description, and creator / last modifier.
+fn matches_unindexed(node: &ContextNode, query: &NodeQuery, search: Option<&str>) -> bool {
+    if node.status == NodeStatus::Archived && !query.shows_archived() {
+        return false;
+    }
+    if let Some(s) = search {
+        let found = node.content.to_lowercase().contains(s)
+            || node
+                .title
+                .as_ref()
+                .is_some_and(|t| t.to_lowercase().contains(s))
+            || node
+                .description
+                .as_ref()
+                .is_some_and(|d| d.to_lowercase().contains(s));
+        if !found {
+            return false;
+        }
+    }
+    query
+        .created_by
+        .as_ref()
+        .is_none_or(|c| node.metadata.created_by == *c)
+        && query
+            .modified_by
+            .as_ref()
+            .is_none_or(|m| node.metadata.modified_by == *m)
+}
+
+fn union<'a>(sets: impl IntoIterator<Item = &'a BTreeSet<String>>) -> BTreeSet<&'a str> {
+    sets.into_iter()
+        .flat_map(|s| s.iter().map(String::as_str))
+        .collect()
+}
+
+fn remove_from<K: std::hash::Hash + Eq>(
+    index: &mut HashMap<K, BTreeSet<String>>,
+    value: &K,
+    key: &str,
+) {
+    if let Some(set) = index.get_mut(value) {
+        set.remove(key);
+        if set.is_empty() {
+            index.remove(value);
+        }
+    }
+}
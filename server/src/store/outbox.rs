@@ -0,0 +1,57 @@
+//! Outbox entries, shared by the store backends: the server events and audit events of a
+//! [`WriteBatch`](crate::store::WriteBatch), stored in the same write as its mutations so
+//! they cannot be lost between the write and their delivery. The dispatcher
+//! (`crate::outbox`) publishes the events, forwards the audit events to the configured
+//! sink and removes the entry once both are done. Entries survive `reset`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::ServerEvent;
+use crate::types::AuditEvent;
+
+/// Deliveries still owed for one committed write.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxEntry {
+    /// Sortable (ULID): entries are delivered in commit order.
+    pub id: String,
+    pub created_at: String,
+    /// Not yet published; cleared once they are.
+    #[serde(default)]
+    pub events: Vec<ServerEvent>,
+    /// Not yet forwarded to the audit sink. The audit log itself already has them.
+    #[serde(default)]
+    pub audit: Vec<AuditEvent>,
+    /// Failed forwarding attempts.
+    #[serde(default)]
+    pub attempts: u32,
+    /// RFC 3339; not retried before then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl OutboxEntry {
+    pub fn new(events: Vec<ServerEvent>, audit: Vec<AuditEvent>) -> Self {
+        Self {
+            id: crate::ids::ulid(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            events,
+            audit,
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
+        }
+    }
+
+    /// Nothing left to deliver.
+    pub fn is_done(&self) -> bool {
+        self.events.is_empty() && self.audit.is_empty()
+    }
+
+    /// Whether an attempt may be made at `now` (RFC 3339).
+    pub fn is_due(&self, now: &str) -> bool {
+        self.next_attempt_at.as_deref().is_none_or(|t| t <= now)
+    }
+}
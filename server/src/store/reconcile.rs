@@ -0,0 +1,590 @@
+//! Conflict detection, staleness and merge for proposals, shared by the store backends
+//! and the conflict matrix (`GET /admin/conflicts`).
+//!
+//! Backends read what these need under their own locks (the proposal, the other open
+//! proposals, current node versions) and call in; the rules live here once.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::store::context_store::StoreError;
+use crate::store::diff3;
+use crate::store::lease::Lease;
+use crate::types::{
+    ConflictDetectionResult, ConflictSeverity, ContextNode, FieldChange, MergeConflictField,
+    MergeResult, NodeId, NodeLock, NodeStatus, Operation, Proposal, ProposalConflict, TaskState,
+};
+
+/// Keys of the nodes a proposal's operations touch.
+fn operations_node_keys(ops: &[Operation]) -> HashSet<String> {
+    let mut keys = HashSet::new();
+    for op in ops {
+        match op {
+            Operation::Create { node, .. } => {
+                keys.insert(node.id.key());
+            }
+            Operation::Update { node_id, .. }
+            | Operation::Delete { node_id, .. }
+            | Operation::StatusChange { node_id, .. } => {
+                keys.insert(node_id.key());
+            }
+        }
+    }
+    keys
+}
+
+/// Conflicts between `proposal` and the other open proposals: any shared node is a
+/// conflict (critical when more than one is shared). Open proposals without one are
+/// mergeable.
+pub(crate) fn detect_conflicts(proposal: &Proposal, open: &[Proposal]) -> ConflictDetectionResult {
+    let mut open: Vec<&Proposal> = open.iter().filter(|p| p.id != proposal.id).collect();
+    open.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let node_ids_self = operations_node_keys(&proposal.operations);
+    let mut conflicts = Vec::new();
+    let mut needs_resolution = Vec::new();
+    for other in &open {
+        let node_ids_other = operations_node_keys(&other.operations);
+        let mut shared: Vec<&String> = node_ids_self.intersection(&node_ids_other).collect();
+        if shared.is_empty() {
+            continue;
+        }
+        shared.sort();
+        let conflicting_nodes: Vec<NodeId> =
+            shared.into_iter().map(|k| NodeId::from_key(k)).collect();
+        let severity = if conflicting_nodes.len() > 1 {
+            ConflictSeverity::Critical
+        } else {
+            ConflictSeverity::Node
+        };
+        conflicts.push(ProposalConflict {
+            proposals: vec![proposal.id.clone(), other.id.clone()],
+            conflicting_nodes,
+            conflicting_fields: None,
+            severity,
+            auto_resolvable: false,
+        });
+        needs_resolution.push(other.id.clone());
+    }
+    let mergeable = open
+        .iter()
+        .map(|p| p.id.clone())
+        .filter(|id| !needs_resolution.contains(id))
+        .collect();
+    ConflictDetectionResult {
+        conflicts,
+        mergeable,
+        needs_resolution,
+        locked_nodes: Vec::new(),
+    }
+}
+
+/// The node locks among `locks` on nodes `proposal` touches, held by someone other than
+/// its author.
+pub(crate) fn locked_nodes(proposal: &Proposal, locks: &[Lease]) -> Vec<NodeLock> {
+    let keys = operations_node_keys(&proposal.operations);
+    locks
+        .iter()
+        .filter(|l| l.holder != proposal.metadata.created_by)
+        .filter_map(Lease::node_lock)
+        .filter(|lock| keys.contains(&lock.node_id.key()))
+        .collect()
+}
+
+/// Whether a node the proposal touches has moved past the version recorded in its
+/// `base_versions`. Proposals without base versions are never stale.
+pub(crate) fn is_stale(proposal: &Proposal, current_version: impl Fn(&str) -> Option<u32>) -> bool {
+    let Some(base) = &proposal.metadata.base_versions else {
+        return false;
+    };
+    operations_node_keys(&proposal.operations)
+        .iter()
+        .any(|key| match (base.get(key), current_version(key)) {
+            (Some(&base_v), Some(current_v)) => current_v > base_v,
+            _ => false,
+        })
+}
+
+/// Value of `field` on `node` as of version `base`, when the field has not changed
+/// since: the node is still at `base`, or the field's last change (`fieldChanges`) is
+/// no later. Fields an update sets live on the node or in its metadata.
+fn base_value(node: &ContextNode, field: &str, base: u32) -> Option<serde_json::Value> {
+    let unchanged = node.metadata.version <= base
+        || node
+            .metadata
+            .field_changes
+            .as_ref()
+            .and_then(|changes| changes.get(field))
+            .is_some_and(|change| change.version <= base);
+    if !unchanged {
+        return None;
+    }
+    let value = serde_json::to_value(node).ok()?;
+    Some(
+        value
+            .get(field)
+            .or_else(|| value["metadata"].get(field))
+            .cloned()
+            .unwrap_or_default(),
+    )
+}
+
+/// One proposal's value for a field in [`merge`], and the field's value at its base.
+struct Side {
+    proposal_id: String,
+    value: serde_json::Value,
+    base: Option<serde_json::Value>,
+}
+
+/// Line-level diff3 of the changed sides of a text field (`crate::store::diff3`), when
+/// they all have the same known base: Ok with the merged text, Err with the text marked
+/// up around the first conflicting merge. None when there is no common base or the text
+/// is too large.
+fn merge_text(changed: &[&Side]) -> Option<Result<String, String>> {
+    let base = changed[0].base.as_ref()?;
+    if changed.iter().any(|s| s.base.as_ref() != Some(base)) {
+        return None;
+    }
+    let base = base.as_str()?;
+    let mut text = changed[0].value.as_str()?.to_string();
+    let mut label = changed[0].proposal_id.clone();
+    for side in &changed[1..] {
+        let merged = diff3::merge(
+            base,
+            &text,
+            side.value.as_str()?,
+            (&label, &side.proposal_id),
+        )?;
+        if merged.conflicts > 0 {
+            return Some(Err(merged.text));
+        }
+        text = merged.text;
+        label = format!("{}+{}", label, side.proposal_id);
+    }
+    Some(Ok(text))
+}
+
+/// Field-level three-way merge of the updates in `proposals`. A proposal's side of a
+/// field is unchanged when it sets the value the field had at the proposal's base
+/// version of the node (`metadata.baseVersions`, see [`base_value`]); sides without a
+/// known base count as changed. A field one proposal sets, or only one side changed, is
+/// auto-merged; several changed sides with the same value are merged; with different
+/// values it is a conflict between the changed sides only. Different `content` edits
+/// from a common base are merged line by line first ([`merge_text`]); only overlapping
+/// hunks conflict, and the conflict carries the content with diff3 markers. `node`
+/// gives a stored node by key.
+pub(crate) fn merge<'a>(
+    proposals: &[Proposal],
+    node: impl Fn(&str) -> Option<&'a ContextNode>,
+) -> MergeResult {
+    // (node key, field) -> sides, in key order for a stable result.
+    let mut by_field: BTreeMap<(String, String), Vec<Side>> = BTreeMap::new();
+    for prop in proposals {
+        for op in &prop.operations {
+            if let Operation::Update {
+                node_id, changes, ..
+            } = op
+            {
+                let key = node_id.key();
+                let base_version = prop
+                    .metadata
+                    .base_versions
+                    .as_ref()
+                    .and_then(|b| b.get(&key));
+                for (field, value) in changes.fields() {
+                    let base = base_version
+                        .zip(node(&key))
+                        .and_then(|(v, n)| base_value(n, &field, *v));
+                    by_field
+                        .entry((key.clone(), field))
+                        .or_default()
+                        .push(Side {
+                            proposal_id: prop.id.clone(),
+                            value,
+                            base,
+                        });
+                }
+            }
+        }
+    }
+    let mut merged = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut auto_merged = Vec::new();
+    for ((node_key, field), sides) in by_field {
+        let node_id = NodeId::from_key(&node_key);
+        let old_value = sides
+            .iter()
+            .find_map(|s| s.base.clone())
+            .unwrap_or_default();
+        let changed: Vec<&Side> = sides
+            .iter()
+            .filter(|s| s.base.as_ref() != Some(&s.value))
+            .collect();
+        let change = |value: &serde_json::Value| FieldChange {
+            node_id: node_id.clone(),
+            field: field.clone(),
+            old_value: old_value.clone(),
+            new_value: value.clone(),
+        };
+        if sides.len() == 1 || changed.len() <= 1 {
+            let side = changed.first().copied().unwrap_or(&sides[0]);
+            auto_merged.push(change(&side.value));
+            continue;
+        }
+        let uniq: HashSet<&serde_json::Value> = changed.iter().map(|s| &s.value).collect();
+        let text = if field == "content" && uniq.len() > 1 {
+            merge_text(&changed)
+        } else {
+            None
+        };
+        if let Some(Ok(text)) = text {
+            merged.push(change(&serde_json::Value::String(text)));
+        } else if uniq.len() > 1 {
+            conflicts.push(MergeConflictField {
+                field,
+                node_id,
+                proposal1_value: changed[0].value.clone(),
+                proposal2_value: changed[1].value.clone(),
+                base_value: sides.iter().find_map(|s| s.base.clone()),
+                proposal_ids: changed.iter().map(|s| s.proposal_id.clone()).collect(),
+                marked_content: text.and_then(Result::err),
+            });
+        } else {
+            merged.push(change(&changed[0].value));
+        }
+    }
+    MergeResult {
+        merged,
+        conflicts,
+        auto_merged,
+    }
+}
+
+/// Status checks for `ops` (in apply order), counting the operations before each:
+///
+/// - compare-and-swap: every `status-change` must find its node in `old_status`;
+/// - every status an update or status change sets must follow the transition table
+///   (`NodeStatus::can_become`), unless `forced` (an Admin-applied proposal with
+///   `forceStatusTransitions`).
+///
+/// `current_status` gives a node's stored status by key; missing nodes are left to the
+/// apply itself.
+pub(crate) fn check_status_transitions(
+    ops: &[Operation],
+    current_status: impl Fn(&str) -> Option<NodeStatus>,
+    forced: bool,
+) -> Result<(), StoreError> {
+    let transition = |op_id: &str, key: &str, from: NodeStatus, to: NodeStatus| {
+        if forced || from.can_become(to) {
+            Ok(())
+        } else {
+            Err(StoreError::conflict(format!(
+                "operation {}: node {} cannot go from {} to {}",
+                op_id,
+                key,
+                from.as_str(),
+                to.as_str()
+            )))
+        }
+    };
+    let mut pending: HashMap<String, Option<NodeStatus>> = HashMap::new();
+    for op in ops {
+        match op {
+            Operation::Create { node, .. } => {
+                pending.insert(node.id.key(), Some(node.status));
+            }
+            Operation::Update {
+                id,
+                node_id,
+                changes,
+                ..
+            } => {
+                if let Some(status) = changes.status {
+                    let key = node_id.key();
+                    let before = match pending.get(&key) {
+                        Some(status) => *status,
+                        None => current_status(&key),
+                    };
+                    if let Some(before) = before {
+                        transition(id, &key, before, status)?;
+                    }
+                    pending.insert(key, Some(status));
+                }
+            }
+            Operation::Delete { node_id, .. } => {
+                pending.insert(node_id.key(), None);
+            }
+            Operation::StatusChange {
+                id,
+                node_id,
+                new_status,
+                old_status,
+                ..
+            } => {
+                let key = node_id.key();
+                let status = match pending.get(&key) {
+                    Some(status) => *status,
+                    None => current_status(&key),
+                };
+                if let Some(status) = status {
+                    if status != *old_status {
+                        return Err(StoreError::conflict(format!(
+                            "operation {}: node {} is {}, not {} (old_status); it changed since the proposal was made",
+                            id,
+                            key,
+                            status.as_str(),
+                            old_status.as_str()
+                        )));
+                    }
+                    transition(id, &key, status, *new_status)?;
+                }
+                pending.insert(key, Some(*new_status));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Task state checks for `ops` (in apply order): every task `state` an update sets must
+/// follow the task transition table (`TaskState::can_become`) from the state before it,
+/// unless `forced` (as for [`check_status_transitions`]). A task with no state yet may
+/// take any.
+///
+/// `current_state` gives a node's stored task state by key.
+pub(crate) fn check_task_transitions(
+    ops: &[Operation],
+    current_state: impl Fn(&str) -> Option<TaskState>,
+    forced: bool,
+) -> Result<(), StoreError> {
+    let mut pending: HashMap<String, Option<TaskState>> = HashMap::new();
+    for op in ops {
+        match op {
+            Operation::Create { node, .. } => {
+                pending.insert(node.id.key(), node.state);
+            }
+            Operation::Update {
+                id,
+                node_id,
+                changes,
+                ..
+            } => {
+                let Some(to) = changes.state else {
+                    continue;
+                };
+                let key = node_id.key();
+                let before = match pending.get(&key) {
+                    Some(state) => *state,
+                    None => current_state(&key),
+                };
+                if let Some(from) = before {
+                    if !forced && !from.can_become(to) {
+                        return Err(StoreError::conflict(format!(
+                            "operation {}: task {} cannot go from {} to {}",
+                            id,
+                            key,
+                            from.as_str(),
+                            to.as_str()
+                        )));
+                    }
+                }
+                pending.insert(key, Some(to));
+            }
+            Operation::Delete { node_id, .. } => {
+                pending.insert(node_id.key(), None);
+            }
+            Operation::StatusChange { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    fn status_change(id: &str, old: NodeStatus, new: NodeStatus) -> Operation {
+        Operation::StatusChange {
+            id: id.to_string(),
+            order: 1,
+            node_id: NodeId::from_key("goal-1"),
+            new_status: new,
+            old_status: old,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn three_way_merge_keeps_only_the_sides_that_changed() {
+        let blame = |version: u32| {
+            serde_json::json!({ "proposalId": "p-0", "author": "a", "appliedBy": "a",
+                "changedAt": "2026-01-01T00:00:00Z", "version": version })
+        };
+        let node: ContextNode = serde_json::from_value(serde_json::json!({
+            "id": { "id": "n1" }, "type": "goal", "status": "accepted",
+            "title": "Old", "content": "Body",
+            "metadata": { "createdAt": "", "createdBy": "a", "modifiedAt": "",
+                "modifiedBy": "a", "version": 2,
+                "fieldChanges": { "title": blame(2), "content": blame(1) } }
+        }))
+        .unwrap();
+        let proposal = |id: &str, base: u32, changes: serde_json::Value| -> Proposal {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "status": "open",
+                "operations": [{ "type": "update", "id": "op-1", "order": 1,
+                    "node_id": { "id": "n1" }, "changes": changes }],
+                "metadata": { "baseVersions": { "n1": base } }
+            }))
+            .unwrap()
+        };
+        let a = proposal(
+            "p-a",
+            1,
+            serde_json::json!({ "title": "A", "content": "Body" }),
+        );
+        let b = proposal(
+            "p-b",
+            2,
+            serde_json::json!({ "title": "Old", "content": "B" }),
+        );
+        let stored = |key: &str| (key == "n1").then_some(&node);
+
+        // p-a changed the title (its base predates the last title change), p-b the content.
+        let result = merge(&[a.clone(), b.clone()], stored);
+        assert!(result.conflicts.is_empty());
+        let values: Vec<_> = result
+            .auto_merged
+            .iter()
+            .map(|c| (c.field.as_str(), c.new_value.clone(), c.old_value.clone()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("content", serde_json::json!("B"), serde_json::json!("Body")),
+                ("title", serde_json::json!("A"), serde_json::json!("Old")),
+            ]
+        );
+
+        // Without base versions both sides count as changed.
+        let result = merge(&[a.clone(), b.clone()], |_| None);
+        assert_eq!(result.conflicts.len(), 2);
+
+        let c = proposal("p-c", 2, serde_json::json!({ "title": "C" }));
+        let result = merge(&[a, b, c], stored);
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.field, "title");
+        assert_eq!(conflict.proposal_ids, ["p-a", "p-c"]);
+        assert_eq!(conflict.base_value, Some(serde_json::json!("Old")));
+    }
+
+    #[test]
+    fn content_edits_merge_line_by_line() {
+        let node: ContextNode = serde_json::from_value(serde_json::json!({
+            "id": { "id": "n1" }, "type": "goal", "status": "accepted",
+            "content": "one\ntwo\nthree\n",
+            "metadata": { "createdAt": "", "createdBy": "a", "modifiedAt": "",
+                "modifiedBy": "a", "version": 1 }
+        }))
+        .unwrap();
+        let proposal = |id: &str, content: &str| -> Proposal {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "status": "open",
+                "operations": [{ "type": "update", "id": "op-1", "order": 1,
+                    "node_id": { "id": "n1" }, "changes": { "content": content } }],
+                "metadata": { "baseVersions": { "n1": 1 } }
+            }))
+            .unwrap()
+        };
+        let stored = |_: &str| Some(&node);
+        let a = proposal("p-a", "ONE\ntwo\nthree\n");
+        let b = proposal("p-b", "one\ntwo\nTHREE\n");
+        let result = merge(&[a.clone(), b], stored);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.merged[0].new_value, "ONE\ntwo\nTHREE\n");
+
+        let c = proposal("p-c", "uno\ntwo\nthree\n");
+        let result = merge(&[a, c], stored);
+        let marked = result.conflicts[0].marked_content.as_deref().unwrap();
+        assert!(marked.starts_with("<<<<<<< p-a\nONE\n||||||| base\none\n=======\nuno\n"));
+    }
+
+    #[test]
+    fn status_changes_must_find_their_old_status() {
+        let stored = |_: &str| Some(NodeStatus::Proposed);
+        let ok = [status_change(
+            "op-1",
+            NodeStatus::Proposed,
+            NodeStatus::Accepted,
+        )];
+        assert!(check_status_transitions(&ok, stored, false).is_ok());
+
+        let stale = [status_change(
+            "op-1",
+            NodeStatus::Accepted,
+            NodeStatus::Archived,
+        )];
+        assert!(matches!(
+            check_status_transitions(&stale, stored, false),
+            Err(StoreError {
+                code: StoreErrorCode::Conflict,
+                ..
+            })
+        ));
+
+        // A later operation sees the status an earlier one set.
+        let chained = [
+            status_change("op-1", NodeStatus::Proposed, NodeStatus::Accepted),
+            status_change("op-2", NodeStatus::Accepted, NodeStatus::Archived),
+        ];
+        assert!(check_status_transitions(&chained, stored, false).is_ok());
+    }
+
+    #[test]
+    fn transitions_follow_the_table_unless_forced() {
+        let rejected = |_: &str| Some(NodeStatus::Rejected);
+        let revive = [status_change(
+            "op-1",
+            NodeStatus::Rejected,
+            NodeStatus::Accepted,
+        )];
+        assert!(check_status_transitions(&revive, rejected, false).is_err());
+        assert!(check_status_transitions(&revive, rejected, true).is_ok());
+
+        let update = [Operation::Update {
+            id: "op-1".to_string(),
+            order: 1,
+            node_id: NodeId::from_key("goal-1"),
+            changes: crate::types::UpdateChanges {
+                status: Some(NodeStatus::Accepted),
+                ..Default::default()
+            },
+        }];
+        assert!(check_status_transitions(&update, rejected, false).is_err());
+        assert!(NodeStatus::Rejected.can_become(NodeStatus::Proposed));
+    }
+
+    #[test]
+    fn task_states_follow_their_table() {
+        let set_state = |id: &str, state: TaskState| Operation::Update {
+            id: id.to_string(),
+            order: 1,
+            node_id: NodeId::from_key("task-1"),
+            changes: crate::types::UpdateChanges {
+                state: Some(state),
+                ..Default::default()
+            },
+        };
+        let open = |_: &str| Some(TaskState::Open);
+        let finish = [set_state("op-1", TaskState::Completed)];
+        assert!(check_task_transitions(&finish, open, false).is_err());
+        assert!(check_task_transitions(&finish, open, true).is_ok());
+
+        let worked = [
+            set_state("op-1", TaskState::InProgress),
+            set_state("op-2", TaskState::Completed),
+        ];
+        assert!(check_task_transitions(&worked, open, false).is_ok());
+        // A task without a state may start anywhere.
+        assert!(check_task_transitions(&finish, |_: &str| None, false).is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+//! Proposal traceability, shared by the store backends: for each node, the applied
+//! proposals that touched it, oldest first (`GET /nodes/:id/proposals`).
+//!
+//! Backends record a proposal when they apply it and rebuild the index from the applied
+//! proposals when they load or import. Applied proposals are never pruned (see
+//! `store::compact`), so the index always covers the node's whole history, including
+//! changes made before field blame (`store::blame`) was recorded.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Operation, Proposal};
+
+/// An applied proposal that touched a node, with the operations that touched it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeProposal {
+    pub proposal_id: String,
+    pub author: String,
+    pub applied_by: String,
+    pub applied_at: String,
+    pub revision_id: String,
+    pub operations: Vec<TracedOperation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedOperation {
+    pub operation_id: String,
+    /// `create`, `update`, `delete` or `status-change`.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// Node key -> proposals that touched it, in apply order.
+#[derive(Debug, Default)]
+pub(crate) struct TraceIndex {
+    by_node: HashMap<String, Vec<NodeProposal>>,
+}
+
+impl TraceIndex {
+    /// Index built from `proposals`; those not applied are skipped.
+    pub(crate) fn rebuild<'a>(proposals: impl IntoIterator<Item = &'a Proposal>) -> Self {
+        let mut applied: Vec<&Proposal> = proposals
+            .into_iter()
+            .filter(|p| p.applied.is_some())
+            .collect();
+        applied.sort_by_key(|p| {
+            let applied = p.applied.as_ref().expect("filtered above");
+            (
+                applied.applied_at.clone(),
+                revision_number(&applied.applied_to_revision_id),
+            )
+        });
+        let mut index = Self::default();
+        for proposal in applied {
+            index.record(proposal);
+        }
+        index
+    }
+
+    /// Add an applied proposal under every node its operations touch. Recording the same
+    /// proposal twice is a no-op.
+    pub(crate) fn record(&mut self, proposal: &Proposal) {
+        let Some(applied) = &proposal.applied else {
+            return;
+        };
+        let mut operations: Vec<&Operation> = proposal.operations.iter().collect();
+        operations.sort_by_key(|op| match op {
+            Operation::Create { order, .. }
+            | Operation::Update { order, .. }
+            | Operation::Delete { order, .. }
+            | Operation::StatusChange { order, .. } => *order,
+        });
+        let touched = operations.into_iter().map(|op| {
+            let (id, key, kind) = match op {
+                Operation::Create { id, node, .. } => (id, node.id.key(), "create"),
+                Operation::Update { id, node_id, .. } => (id, node_id.key(), "update"),
+                Operation::Delete { id, node_id, .. } => (id, node_id.key(), "delete"),
+                Operation::StatusChange { id, node_id, .. } => (id, node_id.key(), "status-change"),
+            };
+            let op = TracedOperation {
+                operation_id: id.clone(),
+                kind: kind.to_string(),
+            };
+            (key, op)
+        });
+
+        for (key, op) in touched {
+            let entries = self.by_node.entry(key).or_default();
+            match entries.iter_mut().find(|e| e.proposal_id == proposal.id) {
+                Some(entry) => {
+                    if !entry
+                        .operations
+                        .iter()
+                        .any(|o| o.operation_id == op.operation_id)
+                    {
+                        entry.operations.push(op);
+                    }
+                }
+                None => entries.push(NodeProposal {
+                    proposal_id: proposal.id.clone(),
+                    author: proposal.metadata.created_by.clone(),
+                    applied_by: applied.applied_by.clone(),
+                    applied_at: applied.applied_at.clone(),
+                    revision_id: applied.applied_to_revision_id.clone(),
+                    operations: vec![op],
+                }),
+            }
+        }
+    }
+
+    /// Proposals that touched the node `key`, oldest first.
+    pub(crate) fn get(&self, key: &str) -> Vec<NodeProposal> {
+        self.by_node.get(key).cloned().unwrap_or_default()
+    }
+}
+
+/// `rev_12` -> 12, so revisions order numerically.
+fn revision_number(revision: &str) -> u64 {
+    revision
+        .rsplit('_')
+        .next()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(id: &str, revision: u64, operations: serde_json::Value) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "status": "applied",
+            "operations": operations,
+            "metadata": { "createdBy": "alice" },
+            "applied": {
+                "appliedAt": "2026-03-01T00:00:00Z", "appliedBy": "bob",
+                "appliedFromProposalId": id,
+                "appliedToRevisionId": format!("rev_{}", revision),
+                "previousRevisionId": format!("rev_{}", revision - 1)
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn proposals_are_listed_per_node_in_apply_order() {
+        let create = applied(
+            "p-create",
+            1,
+            serde_json::json!([{ "id": "op1", "order": 1, "type": "create", "node": {
+                "id": { "id": "goal-1" }, "type": "goal", "status": "accepted", "content": "x",
+                "metadata": {
+                    "createdAt": "2026-01-01T00:00:00Z", "createdBy": "alice",
+                    "modifiedAt": "2026-01-01T00:00:00Z", "modifiedBy": "alice", "version": 0
+                }
+            }}]),
+        );
+        let update = applied(
+            "p-update",
+            10,
+            serde_json::json!([
+                { "id": "op2", "order": 2, "type": "update",
+                  "node_id": { "id": "goal-1" }, "changes": { "content": "y" } },
+                { "id": "op1", "order": 1, "type": "update",
+                  "node_id": { "id": "goal-1" }, "changes": { "title": "Goal" } },
+                { "id": "op3", "order": 3, "type": "delete", "node_id": { "id": "goal-2" } }
+            ]),
+        );
+        let open: Proposal = serde_json::from_value(serde_json::json!({
+            "id": "p-open", "status": "open",
+            "operations": [{ "id": "op1", "order": 1, "type": "delete",
+                             "node_id": { "id": "goal-1" } }]
+        }))
+        .unwrap();
+
+        let mut index = TraceIndex::rebuild([&update, &open, &create]);
+        index.record(&update);
+        let trace = index.get("goal-1");
+        let ids: Vec<&str> = trace.iter().map(|t| t.proposal_id.as_str()).collect();
+        assert_eq!(ids, ["p-create", "p-update"]);
+        let ops: Vec<&str> = trace[1]
+            .operations
+            .iter()
+            .map(|o| o.operation_id.as_str())
+            .collect();
+        assert_eq!(ops, ["op1", "op2"]);
+        assert_eq!(trace[1].applied_by, "bob");
+        assert_eq!(index.get("goal-2")[0].operations[0].kind, "delete");
+        assert!(index.get("goal-3").is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! Node trash, shared by the store backends.
+//!
+//! A delete operation does not remove a node: it sets `metadata.deleted` (a tombstone)
+//! and the node drops out of every read (`get_node`, queries, context packs). Deleted
+//! nodes are listed by `GET /admin/trash` and can be restored with their content and
+//! status. They are removed for good only by a retention sweep with a `node` rule and
+//! the `delete` action (`purge_trash`).
+//!
+//! Operations on a deleted node are refused, and so is creating a node under a deleted
+//! node's id: restore it, or wait for retention to purge it.
+
+use crate::store::context_store::StoreError;
+use crate::store::node_index::NodeTable;
+use crate::types::{ContextNode, DeletedMetadata, NodeId, Operation};
+
+/// Refuse `operations` that target a deleted node.
+pub(crate) fn check_operations(
+    nodes: &NodeTable,
+    operations: &[Operation],
+) -> Result<(), StoreError> {
+    for op in operations {
+        let (key, verb) = match op {
+            Operation::Create { node, .. } => (node.id.key(), "create"),
+            Operation::Update { node_id, .. } => (node_id.key(), "update"),
+            Operation::Delete { node_id, .. } => (node_id.key(), "delete"),
+            Operation::StatusChange { node_id, .. } => (node_id.key(), "change status of"),
+        };
+        if nodes.is_deleted(&key) {
+            return Err(StoreError::conflict(format!(
+                "cannot {} node {}: it is in the trash (restore it first)",
+                verb, key
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Move a node to the trash.
+pub(crate) fn tombstone(
+    node: &mut ContextNode,
+    deleted_by: &str,
+    deleted_at: &str,
+    proposal_id: &str,
+) {
+    node.metadata.deleted = Some(DeletedMetadata {
+        deleted_at: deleted_at.to_string(),
+        deleted_by: deleted_by.to_string(),
+        proposal_id: Some(proposal_id.to_string()),
+    });
+    node.metadata.modified_at = deleted_at.to_string();
+    node.metadata.modified_by = deleted_by.to_string();
+    node.metadata.version += 1;
+}
+
+/// Bring a deleted node back as it was before the delete.
+pub(crate) fn restore(
+    nodes: &mut NodeTable,
+    node_id: &NodeId,
+    restored_by: &str,
+) -> Result<ContextNode, StoreError> {
+    let key = node_id.key();
+    if !nodes.is_deleted(&key) {
+        return Err(StoreError::not_found(format!(
+            "node {} is not in the trash",
+            key
+        )));
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut restored = None;
+    nodes.modify(&key, |node| {
+        node.metadata.deleted = None;
+        node.metadata.modified_at = now;
+        node.metadata.modified_by = restored_by.to_string();
+        node.metadata.version += 1;
+        restored = Some(node.clone());
+    });
+    restored.ok_or_else(|| StoreError::not_found(format!("node {}", key)))
+}
+
+/// Deleted nodes, most recently deleted first.
+pub(crate) fn list(nodes: &NodeTable) -> Vec<ContextNode> {
+    let mut list: Vec<ContextNode> = nodes.trash().cloned().collect();
+    list.sort_by(|a, b| deleted_at(b).cmp(deleted_at(a)));
+    list
+}
+
+fn deleted_at(node: &ContextNode) -> &str {
+    node.metadata
+        .deleted
+        .as_ref()
+        .map(|d| d.deleted_at.as_str())
+        .unwrap_or_default()
+}
+
+/// Keys of nodes deleted before `before` (RFC 3339). Tombstones with an unparseable
+/// time are kept.
+pub(crate) fn purgeable(nodes: &NodeTable, before: &str) -> Result<Vec<String>, StoreError> {
+    let cutoff = chrono::DateTime::parse_from_rfc3339(before)
+        .map_err(|e| StoreError::invalid(format!("purge cutoff '{}': {}", before, e)))?;
+    Ok(nodes
+        .trash()
+        .filter(|n| chrono::DateTime::parse_from_rfc3339(deleted_at(n)).is_ok_and(|at| at < cutoff))
+        .map(|n| n.id.key())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::context_store::StoreErrorCode;
+
+    fn node(id: &str) -> ContextNode {
+        serde_json::from_value(serde_json::json!({
+            "id": { "id": id },
+            "type": "goal",
+            "status": "accepted",
+            "content": "c",
+            "metadata": {
+                "createdAt": "2026-01-01T00:00:00Z",
+                "createdBy": "u",
+                "modifiedAt": "2026-01-01T00:00:00Z",
+                "modifiedBy": "u",
+                "version": 1
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn deleted_nodes_leave_reads_until_restored_or_purged() {
+        let mut nodes = NodeTable::default();
+        for id in ["a", "b"] {
+            nodes.insert(id.to_string(), node(id));
+        }
+        nodes.modify("a", |n| {
+            tombstone(n, "alice", "2026-02-01T00:00:00Z", "p-1")
+        });
+        nodes.modify("b", |n| tombstone(n, "bob", "2026-03-01T00:00:00Z", "p-2"));
+
+        assert!(nodes.get("a").is_none());
+        assert_eq!(nodes.query(&Default::default()).total, 0);
+        let trash: Vec<String> = list(&nodes).iter().map(|n| n.id.key()).collect();
+        assert_eq!(trash, ["b", "a"]);
+        let create = Operation::Create {
+            id: "op".to_string(),
+            order: 1,
+            node: node("a"),
+        };
+        assert!(matches!(
+            check_operations(&nodes, &[create]),
+            Err(StoreError {
+                code: StoreErrorCode::Conflict,
+                ..
+            })
+        ));
+
+        let restored = restore(&mut nodes, &node("a").id, "carol").unwrap();
+        assert_eq!(restored.metadata.version, 3);
+        assert_eq!(
+            nodes.get("a").unwrap().status,
+            crate::types::NodeStatus::Accepted
+        );
+        assert!(restore(&mut nodes, &node("a").id, "carol").is_err());
+
+        assert_eq!(
+            purgeable(&nodes, "2026-02-15T00:00:00Z").unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(purgeable(&nodes, "2026-04-01T00:00:00Z").unwrap(), ["b"]);
+    }
+}
@@ -0,0 +1,106 @@
+//! Usage ledger, shared by the store backends: one [`UsageRecord`] per day and workspace
+//! for chargeback (`crate::usage`, `GET /admin/usage`). API calls accumulate as
+//! instances flush their counters; the daily rollup sets the other measures. The ledger
+//! survives `reset`, like the audit log.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A workspace's usage on one day (UTC).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRecord {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub workspace: String,
+    /// REST, GraphQL and MCP requests naming the workspace.
+    #[serde(default)]
+    pub api_calls: u64,
+    /// Serialized size of the workspace's nodes when the day was rolled up.
+    #[serde(default)]
+    pub storage_bytes: u64,
+    #[serde(default)]
+    pub nodes: u64,
+    /// Confidential or restricted nodes served to agents.
+    #[serde(default)]
+    pub agent_sensitive_reads: u64,
+    /// When the day was last rolled up; None until then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rolled_up_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageLedger {
+    /// By `{date}/{workspace}`.
+    #[serde(default)]
+    pub records: BTreeMap<String, UsageRecord>,
+}
+
+impl UsageLedger {
+    fn record(&mut self, date: &str, workspace: &str) -> &mut UsageRecord {
+        self.records
+            .entry(format!("{}/{}", date, workspace))
+            .or_insert_with(|| UsageRecord {
+                date: date.to_string(),
+                workspace: workspace.to_string(),
+                ..Default::default()
+            })
+    }
+
+    /// Add API calls (workspace → count) to `date`.
+    pub(crate) fn add_api_calls(&mut self, date: &str, calls: &BTreeMap<String, u64>) {
+        for (workspace, n) in calls {
+            self.record(date, workspace).api_calls += n;
+        }
+    }
+
+    /// Set the rolled-up measures of each record, keeping the API calls counted so far.
+    pub(crate) fn apply_rollup(&mut self, records: Vec<UsageRecord>) {
+        for rolled in records {
+            let record = self.record(&rolled.date, &rolled.workspace);
+            *record = UsageRecord {
+                api_calls: record.api_calls,
+                ..rolled
+            };
+        }
+    }
+
+    /// Records of the days starting with `month` (`YYYY-MM`), by date then workspace.
+    pub fn month(&self, month: &str) -> Vec<UsageRecord> {
+        self.records
+            .values()
+            .filter(|r| r.date.starts_with(month))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollup_keeps_counted_api_calls() {
+        let mut ledger = UsageLedger::default();
+        let calls = BTreeMap::from([("payments".to_string(), 3)]);
+        ledger.add_api_calls("2026-10-01", &calls);
+        ledger.add_api_calls("2026-10-01", &calls);
+        ledger.apply_rollup(vec![UsageRecord {
+            date: "2026-10-01".to_string(),
+            workspace: "payments".to_string(),
+            storage_bytes: 2048,
+            nodes: 4,
+            agent_sensitive_reads: 1,
+            rolled_up_at: Some("2026-10-02T00:05:00Z".to_string()),
+            ..Default::default()
+        }]);
+        ledger.add_api_calls("2026-11-01", &calls);
+
+        let october = ledger.month("2026-10");
+        assert_eq!(october.len(), 1);
+        assert_eq!(october[0].api_calls, 6);
+        assert_eq!(october[0].storage_bytes, 2048);
+        assert_eq!(october[0].agent_sensitive_reads, 1);
+    }
+}
@@ -0,0 +1,169 @@
+//! Server-managed timestamps.
+//!
+//! Clients (and agents with skewed clocks) used to set `createdAt`, `modifiedAt` and
+//! `reviewedAt` themselves. The write paths shared by every API surface now stamp them
+//! with the server clock instead, ignoring client values:
+//!
+//! - creating a proposal: its `createdAt` / `modifiedAt`, the `createdAt` /
+//!   `modifiedAt` of nodes in its create operations, and its comments' times;
+//! - submitting a review: `reviewedAt` and its comments' times;
+//! - `PATCH /proposals/:id`: `metadata.modifiedAt`, and the times of comments it adds
+//!   or resolves (comments already stored keep theirs).
+//!
+//! Applying a proposal stamps node `modifiedAt` in the store. With
+//! `server.trust_client_timestamps` (trusted-importer mode, e.g. a migration replaying
+//! history) client values are kept when present and refused unless they are RFC 3339.
+
+use crate::types::{Comment, Operation, Proposal, ProposalPatch, Review};
+
+/// Stamps times for one request.
+pub struct Stamper {
+    now: String,
+    trust_client: bool,
+}
+
+impl Stamper {
+    pub fn new(trust_client: bool) -> Self {
+        Self {
+            now: chrono::Utc::now().to_rfc3339(),
+            trust_client,
+        }
+    }
+
+    /// Set `field` to now, or in trusted mode keep a valid client value.
+    fn stamp(&self, what: &str, field: &mut String) -> Result<(), String> {
+        if self.trust_client && !field.is_empty() {
+            return chrono::DateTime::parse_from_rfc3339(field)
+                .map(|_| ())
+                .map_err(|e| format!("{} '{}' is not an RFC 3339 date-time: {}", what, field, e));
+        }
+        *field = self.now.clone();
+        Ok(())
+    }
+
+    fn stamp_comments(&self, comments: &mut [Comment]) -> Result<(), String> {
+        for comment in comments {
+            self.stamp("comment createdAt", &mut comment.created_at)?;
+            if let Some(resolved_at) = comment.resolved_at.as_mut() {
+                self.stamp("comment resolvedAt", resolved_at)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stamp a proposal being created and the nodes it creates.
+    pub fn proposal(&self, proposal: &mut Proposal) -> Result<(), String> {
+        self.stamp("createdAt", &mut proposal.metadata.created_at)?;
+        self.stamp("modifiedAt", &mut proposal.metadata.modified_at)?;
+        self.operations(&mut proposal.operations)?;
+        if let Some(comments) = proposal.comments.as_mut() {
+            self.stamp_comments(comments)?;
+        }
+        Ok(())
+    }
+
+    /// Stamp the nodes `operations` create.
+    pub fn operations(&self, operations: &mut [Operation]) -> Result<(), String> {
+        for op in operations {
+            if let Operation::Create { node, .. } = op {
+                self.stamp("node createdAt", &mut node.metadata.created_at)?;
+                self.stamp("node modifiedAt", &mut node.metadata.modified_at)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stamp a review being submitted.
+    pub fn review(&self, review: &mut Review) -> Result<(), String> {
+        self.stamp("reviewedAt", &mut review.reviewed_at)?;
+        if let Some(comments) = review.comments.as_mut() {
+            self.stamp_comments(comments)?;
+        }
+        Ok(())
+    }
+
+    /// Stamp a patch of `existing`: `metadata.modifiedAt`, and the comments it adds or
+    /// resolves. Comments `existing` already has keep their stored times.
+    pub fn update(&self, patch: &mut ProposalPatch, existing: &Proposal) -> Result<(), String> {
+        let metadata = patch.metadata.get_or_insert_with(Default::default);
+        let mut modified_at = metadata.modified_at.take().unwrap_or_default();
+        self.stamp("metadata.modifiedAt", &mut modified_at)?;
+        metadata.modified_at = Some(modified_at);
+
+        let stored = existing.comments.as_deref().unwrap_or_default();
+        for comment in patch.comments.iter_mut().flatten() {
+            let before = stored.iter().find(|c| c.id == comment.id);
+            match before {
+                Some(c) if !self.trust_client => comment.created_at = c.created_at.clone(),
+                _ => self.stamp("comment createdAt", &mut comment.created_at)?,
+            }
+            if let Some(resolved_at) = comment.resolved_at.as_mut() {
+                match before.and_then(|c| c.resolved_at.clone()) {
+                    Some(t) if !self.trust_client => *resolved_at = t,
+                    _ => self.stamp("comment resolvedAt", resolved_at)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(created_at: &str) -> Proposal {
+        serde_json::from_value(serde_json::json!({
+            "id": "p-1",
+            "status": "open",
+            "operations": [],
+            "comments": [{
+                "id": "c-1", "content": "hi", "author": "u", "createdAt": "2020-01-01T00:00:00Z"
+            }],
+            "metadata": {
+                "createdAt": created_at,
+                "createdBy": "u",
+                "modifiedAt": created_at,
+                "modifiedBy": "u"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn client_times_are_replaced_unless_trusted() {
+        let future = "2999-01-01T00:00:00Z";
+        let mut p = proposal(future);
+        Stamper::new(false).proposal(&mut p).unwrap();
+        assert_ne!(p.metadata.created_at, future);
+        assert!(p.metadata.created_at.as_str() < "2999");
+
+        let mut kept = proposal(future);
+        Stamper::new(true).proposal(&mut kept).unwrap();
+        assert_eq!(kept.metadata.created_at, future);
+        assert!(Stamper::new(true)
+            .proposal(&mut proposal("last tuesday"))
+            .is_err());
+
+        // A PATCH cannot backdate a stored comment; a new one gets the server time.
+        let mut patch: ProposalPatch = serde_json::from_value(serde_json::json!({
+            "comments": [
+                { "id": "c-1", "content": "hi", "author": "u", "createdAt": "1999-01-01T00:00:00Z" },
+                { "id": "c-2", "content": "yo", "author": "u", "createdAt": future }
+            ]
+        }))
+        .unwrap();
+        let stamper = Stamper::new(false);
+        stamper.update(&mut patch, &p).unwrap();
+        let comments = patch.comments.as_ref().unwrap();
+        assert_eq!(
+            comments[0].created_at,
+            p.comments.as_ref().unwrap()[0].created_at
+        );
+        assert_eq!(comments[1].created_at, stamper.now);
+        assert_eq!(
+            patch.metadata.unwrap().modified_at.as_deref(),
+            Some(stamper.now.as_str())
+        );
+    }
+}
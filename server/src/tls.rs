@@ -2,12 +2,18 @@
 //!
 //! QUIC mandates TLS 1.3. This module loads PEM certificates from disk
 //! for production, or generates self-signed certificates for development.
-//! The resulting `quinn::ServerConfig` is used by the HTTP/3 server.
+//! The resulting `quinn::ServerConfig` is used by the HTTP/3 server; the same
+//! certificates back the TLS TCP fallback listener (HTTP/1.1 + HTTP/2).
+//! With ACME enabled, both listeners use a shared cert resolver instead (see `acme`).
+//! Every builder takes an optional client certificate verifier for mTLS (see `mtls`).
 
 use std::path::Path;
 use std::sync::Arc;
 
 use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::danger::ClientCertVerifier;
+use rustls::server::ResolvesServerCert;
+use serde::{Deserialize, Serialize};
 
 /// Load TLS certificate chain and private key from PEM files.
 pub fn load_certs_from_pem(
@@ -53,45 +59,199 @@ pub fn generate_dev_cert(
     Ok((vec![cert_der], key_der))
 }
 
+/// rustls builder with mTLS client verification when a verifier is given.
+fn server_tls_builder(
+    client_verifier: Option<Arc<dyn ClientCertVerifier>>,
+) -> rustls::ConfigBuilder<rustls::ServerConfig, rustls::server::WantsServerCert> {
+    let builder = rustls::ServerConfig::builder();
+    match client_verifier {
+        Some(verifier) => builder.with_client_cert_verifier(verifier),
+        None => builder.with_no_client_auth(),
+    }
+}
+
 /// Build a `quinn::ServerConfig` from TLS certificates.
 ///
 /// - Sets ALPN to `h3` for HTTP/3 negotiation.
 /// - Enables 0-RTT for fast reconnection (laptop sleep/wake, VPN reconnect).
-/// - Configures QUIC keep-alive (15s) and idle timeout (5 min) for SSE streams.
+/// - Applies default transport settings (keep-alive 15s, idle timeout 5 min); override
+///   with [`QuicTransportConfig`] from config.
 pub fn build_quinn_server_config(
     certs: Vec<CertificateDer<'static>>,
     key: PrivateKeyDer<'static>,
+    client_verifier: Option<Arc<dyn ClientCertVerifier>>,
 ) -> Result<quinn::ServerConfig, Box<dyn std::error::Error + Send + Sync>> {
-    let mut tls_config = rustls::ServerConfig::builder()
-        .with_no_client_auth()
+    let tls_config = server_tls_builder(client_verifier)
         .with_single_cert(certs, key)
         .map_err(|e| format!("TLS config error: {}", e))?;
+    quinn_server_config_from(tls_config)
+}
+
+/// Build a `quinn::ServerConfig` whose certificate comes from a resolver (e.g. ACME renewals).
+pub fn build_quinn_server_config_with_resolver(
+    resolver: Arc<dyn ResolvesServerCert>,
+    client_verifier: Option<Arc<dyn ClientCertVerifier>>,
+) -> Result<quinn::ServerConfig, Box<dyn std::error::Error + Send + Sync>> {
+    let tls_config = server_tls_builder(client_verifier).with_cert_resolver(resolver);
+    quinn_server_config_from(tls_config)
+}
 
+fn quinn_server_config_from(
+    mut tls_config: rustls::ServerConfig,
+) -> Result<quinn::ServerConfig, Box<dyn std::error::Error + Send + Sync>> {
     // HTTP/3 ALPN negotiation
     tls_config.alpn_protocols = vec![b"h3".to_vec()];
-    // Enable 0-RTT early data for fast reconnection
+    // Enable 0-RTT early data for fast reconnection. Replay-safe because h3_server only
+    // routes safe methods before the handshake completes (others get 425 Too Early).
     tls_config.max_early_data_size = u32::MAX;
 
     let quic_crypto = quinn::crypto::rustls::QuicServerConfig::try_from(tls_config)
         .map_err(|e| format!("QUIC crypto config error: {}", e))?;
 
     let mut server_config = quinn::ServerConfig::with_crypto(Arc::new(quic_crypto));
-
-    // Transport tuning for SSE streaming + API latency
-    let mut transport = quinn::TransportConfig::default();
-    // QUIC-level keep-alive prevents NAT/firewall timeouts on long-lived SSE streams
-    transport.keep_alive_interval(Some(std::time::Duration::from_secs(15)));
-    // 5-minute idle timeout: SSE streams with keep-alive will never hit this;
-    // abandoned connections are cleaned up after 5 minutes of silence.
-    transport.max_idle_timeout(Some(
-        quinn::IdleTimeout::try_from(std::time::Duration::from_secs(300))
-            .map_err(|e| format!("invalid idle timeout: {}", e))?,
-    ));
-    server_config.transport_config(Arc::new(transport));
+    server_config.transport_config(Arc::new(QuicTransportConfig::default().transport_config()?));
 
     Ok(server_config)
 }
 
+/// QUIC congestion controller (`quic_transport.congestion_controller`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CongestionController {
+    #[default]
+    Cubic,
+    NewReno,
+    /// Experimental in quinn; can help on lossy links (mobile, VPNs).
+    Bbr,
+}
+
+/// Tunable QUIC transport parameters (`quic_transport` in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuicTransportConfig {
+    /// QUIC keep-alive interval; prevents NAT/firewall timeouts on long-lived SSE streams.
+    /// 0 disables keep-alives.
+    #[serde(default = "default_keep_alive_interval_secs")]
+    pub keep_alive_interval_secs: u64,
+    /// Idle timeout: SSE streams with keep-alive never hit it; abandoned connections are
+    /// cleaned up after this much silence. 0 disables the timeout.
+    #[serde(default = "default_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+    /// Largest UDP payload sent or accepted (also the MTU discovery ceiling). Min 1200.
+    #[serde(default = "default_max_udp_payload_size")]
+    pub max_udp_payload_size: u16,
+    #[serde(default)]
+    pub congestion_controller: CongestionController,
+}
+
+fn default_keep_alive_interval_secs() -> u64 {
+    15
+}
+
+fn default_idle_timeout_secs() -> u64 {
+    300
+}
+
+fn default_max_udp_payload_size() -> u16 {
+    1452
+}
+
+impl Default for QuicTransportConfig {
+    fn default() -> Self {
+        Self {
+            keep_alive_interval_secs: default_keep_alive_interval_secs(),
+            idle_timeout_secs: default_idle_timeout_secs(),
+            max_udp_payload_size: default_max_udp_payload_size(),
+            congestion_controller: CongestionController::default(),
+        }
+    }
+}
+
+impl QuicTransportConfig {
+    /// Per-connection transport settings for `quinn::ServerConfig::transport_config`.
+    pub fn transport_config(
+        &self,
+    ) -> Result<quinn::TransportConfig, Box<dyn std::error::Error + Send + Sync>> {
+        use std::time::Duration;
+
+        if self.max_udp_payload_size < 1200 {
+            return Err(format!(
+                "quic_transport.max_udp_payload_size must be at least 1200 (got {})",
+                self.max_udp_payload_size
+            )
+            .into());
+        }
+        let mut transport = quinn::TransportConfig::default();
+        transport.keep_alive_interval(
+            (self.keep_alive_interval_secs > 0)
+                .then(|| Duration::from_secs(self.keep_alive_interval_secs)),
+        );
+        let idle_timeout = if self.idle_timeout_secs > 0 {
+            Some(
+                quinn::IdleTimeout::try_from(Duration::from_secs(self.idle_timeout_secs))
+                    .map_err(|e| format!("invalid idle timeout: {}", e))?,
+            )
+        } else {
+            None
+        };
+        transport.max_idle_timeout(idle_timeout);
+        let mut mtu_discovery = quinn::MtuDiscoveryConfig::default();
+        mtu_discovery.upper_bound(self.max_udp_payload_size);
+        transport.mtu_discovery_config(Some(mtu_discovery));
+        match self.congestion_controller {
+            CongestionController::Cubic => transport
+                .congestion_controller_factory(Arc::new(quinn::congestion::CubicConfig::default())),
+            CongestionController::NewReno => transport.congestion_controller_factory(Arc::new(
+                quinn::congestion::NewRenoConfig::default(),
+            )),
+            CongestionController::Bbr => transport
+                .congestion_controller_factory(Arc::new(quinn::congestion::BbrConfig::default())),
+        };
+        Ok(transport)
+    }
+
+    /// Endpoint settings (UDP payload ceiling) for `quinn::Endpoint::new`.
+    pub fn endpoint_config(
+        &self,
+    ) -> Result<quinn::EndpointConfig, Box<dyn std::error::Error + Send + Sync>> {
+        let mut endpoint = quinn::EndpointConfig::default();
+        endpoint
+            .max_udp_payload_size(self.max_udp_payload_size)
+            .map_err(|e| format!("invalid max_udp_payload_size: {}", e))?;
+        Ok(endpoint)
+    }
+}
+
+/// Build a `rustls::ServerConfig` for the TLS TCP fallback listener.
+///
+/// - ALPN advertises `h2` then `http/1.1`; clients without HTTP/2 fall back to HTTP/1.1.
+/// - No 0-RTT: early data is a QUIC-only optimization here.
+pub fn build_tcp_tls_config(
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+    client_verifier: Option<Arc<dyn ClientCertVerifier>>,
+) -> Result<Arc<rustls::ServerConfig>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut tls_config = server_tls_builder(client_verifier)
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("TLS config error: {}", e))?;
+    tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(tls_config))
+}
+
+/// Build the TLS TCP config from a resolver (ACME). Also advertises `acme-tls/1` so the
+/// CA can complete TLS-ALPN-01 validation against this listener.
+pub fn build_tcp_tls_config_with_resolver(
+    resolver: Arc<dyn ResolvesServerCert>,
+    client_verifier: Option<Arc<dyn ClientCertVerifier>>,
+) -> Arc<rustls::ServerConfig> {
+    let mut tls_config = server_tls_builder(client_verifier).with_cert_resolver(resolver);
+    tls_config.alpn_protocols = vec![
+        b"h2".to_vec(),
+        b"http/1.1".to_vec(),
+        crate::acme::ACME_TLS_ALPN.to_vec(),
+    ];
+    Arc::new(tls_config)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,7 +265,45 @@ mod tests {
     #[test]
     fn build_server_config_from_dev_cert() {
         let (certs, key) = generate_dev_cert().unwrap();
-        let config = build_quinn_server_config(certs, key);
+        let config = build_quinn_server_config(certs, key, None);
         assert!(config.is_ok(), "server config should build from dev cert");
     }
+
+    #[test]
+    fn quic_transport_config_parses_and_validates() {
+        let cfg: QuicTransportConfig = serde_json::from_str(
+            r#"{ "keep_alive_interval_secs": 0, "congestion_controller": "bbr" }"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.idle_timeout_secs, 300);
+        assert_eq!(cfg.congestion_controller, CongestionController::Bbr);
+        assert!(cfg.transport_config().is_ok());
+        assert!(cfg.endpoint_config().is_ok());
+
+        let too_small = QuicTransportConfig {
+            max_udp_payload_size: 1000,
+            ..Default::default()
+        };
+        assert!(too_small.transport_config().is_err());
+    }
+
+    #[test]
+    fn build_tcp_tls_config_advertises_h2_and_http11() {
+        let (certs, key) = generate_dev_cert().unwrap();
+        let config = build_tcp_tls_config(certs, key, None).unwrap();
+        assert_eq!(
+            config.alpn_protocols,
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+    }
+
+    #[test]
+    fn resolver_configs_build() {
+        let resolver = Arc::new(crate::acme::AcmeCertResolver::new());
+        assert!(build_quinn_server_config_with_resolver(resolver.clone(), None).is_ok());
+        let tcp = build_tcp_tls_config_with_resolver(resolver, None);
+        assert!(tcp
+            .alpn_protocols
+            .contains(&crate::acme::ACME_TLS_ALPN.to_vec()));
+    }
 }
@@ -0,0 +1,87 @@
+//! TLS TCP server: HTTP/1.1 and HTTP/2 over rustls, bridged to the same axum `Router`.
+//!
+//! Officially supported fallback transport for clients without QUIC (Node.js `fetch`,
+//! corporate proxies that block UDP). Unlike the dev TCP listener it is TLS-only and
+//! may be enabled in production, independently of `TRUTHTLAYER_DEV_TCP`.
+//!
+//! ```text
+//! tokio::net::TcpListener
+//!   └── per-connection task
+//!         ├── TLS handshake (tokio-rustls, ALPN h2 / http/1.1)
+//!         └── hyper-util auto builder (HTTP/1.1 or HTTP/2)
+//!               └── axum Router (all middleware: auth, RBAC, OTEL, CORS)
+//! ```
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::http::Request;
+use axum::Router;
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+use crate::mtls::ClientCertIdentity;
+
+/// Start the TLS TCP listener and serve the axum router over HTTP/1.1 and HTTP/2.
+///
+/// Runs until the listener fails or the process is shut down. Per-connection errors
+/// (failed handshakes, client resets) are logged at debug level and do not stop the server.
+pub async fn serve_tls_tcp(
+    tls_config: Arc<rustls::ServerConfig>,
+    addr: SocketAddr,
+    app: Router,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let acceptor = TlsAcceptor::from(tls_config);
+    tracing::info!(%addr, protocol = "HTTP/1.1+HTTP/2 (TLS/TCP)", "listening");
+
+    loop {
+        let (tcp, remote) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                tracing::warn!(error = %e, "TLS TCP accept failed");
+                continue;
+            }
+        };
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        tokio::spawn(async move {
+            let tls = match acceptor.accept(tcp).await {
+                Ok(s) => s,
+                Err(e) => {
+                    tracing::debug!(%remote, error = %e, "TLS handshake failed");
+                    return;
+                }
+            };
+            // TLS-ALPN-01 validation completes in the handshake; nothing to serve afterwards.
+            if tls.get_ref().1.alpn_protocol() == Some(crate::acme::ACME_TLS_ALPN) {
+                tracing::debug!(%remote, "ACME TLS-ALPN-01 validation connection");
+                return;
+            }
+            // mTLS: expose the verified client certificate to AuthLayer on every request.
+            let client_cert = tls
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(ClientCertIdentity::from_chain);
+            let app = app.map_request(move |mut req: Request<Incoming>| {
+                if let Some(identity) = &client_cert {
+                    req.extensions_mut().insert(identity.clone());
+                }
+                req
+            });
+            let service = TowerToHyperService::new(app);
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(tls), service)
+                .await
+            {
+                // Debug level: most errors are client disconnects, not server bugs
+                tracing::debug!(%remote, error = %e, "TLS TCP connection error");
+            }
+        });
+    }
+}
@@ -2,6 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::policy::PolicyViolation;
+use crate::retention::RetentionAction;
+use crate::sensitivity::Sensitivity;
+use crate::store::{CompactReport, ImportSummary};
+use crate::types::{
+    ConflictResolution, ExportFormat, ExportKind, ForgeLink, ProposalStatus, ReviewAction,
+    TriageAction,
+};
+
 /// Actions that are recorded in the audit log.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -11,14 +20,260 @@ pub enum AuditAction {
     ReviewSubmitted,
     ProposalApplied,
     ProposalWithdrawn,
+    /// Quarantined agent proposal released into review or rejected (`POST /proposals/triage`).
+    ProposalTriaged,
+    /// Open proposal replaced by the one `POST /proposals/auto-merge` combined it into.
+    ProposalSuperseded,
     NodeCreated,
     NodeUpdated,
     NodeDeleted,
     RoleChanged,
     PolicyEvaluated,
     StoreReset,
+    /// Fixture data loaded via `POST /admin/seed`.
+    StoreSeeded,
     /// Agent read of sensitive content.
     SensitiveRead,
+    /// Context pack built (`GET /context-pack`); details list the included nodes.
+    ContextPackGenerated,
+    /// Background export started (`POST /admin/exports`); details give kind and format.
+    ExportRequested,
+    /// Failed background job re-queued (`POST /admin/jobs/:id/retry`).
+    JobRetried,
+    /// Scheduled task run on demand (`POST /admin/tasks/:name/run`).
+    TaskTriggered,
+    /// Store compacted (`POST /admin/store/compact` or the `store_compaction` task);
+    /// details hold the report.
+    StoreCompacted,
+    /// Read-only mode turned on or off (`PUT /admin/read-only` or `server.read_only` on
+    /// reload); details hold `enabled`, `reason` and `source`.
+    ReadOnlyChanged,
+    /// Deleted node brought back from the trash (`POST /admin/trash/:id/restore`).
+    NodeRestored,
+    /// Deleted nodes removed for good by a retention sweep; details list them.
+    NodesPurged,
+    /// Unfinished tasks found past their due date by the `overdue_task_check` task;
+    /// details list them.
+    TasksOverdue,
+    /// High or critical risks without a mitigation or not reviewed lately, found by the
+    /// `risk_review_reminder` task; details list them.
+    RisksNeedReview,
+    /// Slack button press or slash command (`crate::slack`), by the Slack service
+    /// identity; details map the Slack user to the actor it acted as.
+    SlackInteraction,
+    /// SCIM user created or changed (`crate::api::scim`); resource is the actor id.
+    ActorProvisioned,
+    /// SCIM user deactivated or deleted; details carry `tokensRevokedAt`.
+    ActorDeprovisioned,
+    /// SCIM group created or changed; resource is the group id.
+    GroupProvisioned,
+    /// SCIM group deleted.
+    GroupDeprovisioned,
+    /// Comment or reply added to a proposal (create, review or `PATCH`); resource is the
+    /// proposal, details carry the comment id and author.
+    CommentAdded,
+    /// Conflicts of a proposal with the other open ones computed
+    /// (`GET /proposals/:id?include=conflicts`); details hold the count.
+    ConflictsDetected,
+    /// Merge of proposals computed (`POST /proposals/merge`); resource is the ids, comma
+    /// separated, details the merged and conflicting field counts.
+    ProposalsMerged,
+    /// Conflicting field values settled for a proposal
+    /// (`POST /proposals/:id/resolve-conflicts`); details hold the resolutions.
+    ConflictsResolved,
+    /// Event stream opened (`GET /events`, `GET /ws`); details give transport and filters.
+    EventsSubscribed,
+    /// DSAR export served (`GET /admin/dsar/export`); resource is the subject, details
+    /// hold `subject` and `auditEvents` (events exported).
+    DsarExport,
+    /// DSAR erase recorded (`POST /admin/dsar/erase`); resource is the subject, details
+    /// hold `subject` and `auditEvents` (events by the subject at the time).
+    DsarErase,
+}
+
+/// `details` of an audit event: one shape per action (and outcome), with camelCase keys,
+/// so a log consumer can parse by `action` alone. Stored as plain JSON, so events recorded
+/// before a shape changed still load.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged, rename_all_fields = "camelCase")]
+pub enum AuditDetails {
+    /// `policy_evaluated` with outcome `policy_violation`: the rules the write broke.
+    Violations { violations: Vec<PolicyViolation> },
+    /// `policy_evaluated`: review policies settled the proposal.
+    StatusTransition {
+        from: ProposalStatus,
+        to: ProposalStatus,
+    },
+    /// `policy_evaluated` by the retention sweep (resource `retention:{type}`).
+    Retention {
+        retention_rule: String,
+        retention_days: u32,
+        action: RetentionAction,
+    },
+    /// `nodes_purged`: node keys removed for good.
+    Purged {
+        nodes: Vec<String>,
+        retention_days: u32,
+    },
+    /// `sensitive_read` denied on a query: how many nodes were left out.
+    Redaction {
+        redacted_count: u64,
+        agent_max_sensitivity: Sensitivity,
+    },
+    /// `sensitive_read` of one node; the clearance is given when the read was denied.
+    SensitiveRead {
+        node_sensitivity: Sensitivity,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        agent_max_sensitivity: Option<Sensitivity>,
+    },
+    /// Any action, as served to a caller below the clearance of the node it is about.
+    Redacted {
+        redacted: bool,
+        sensitivity: Sensitivity,
+    },
+    /// `proposal_applied`: each field an operation set (`{ operationId, node, field, from,
+    /// to }`), and the ones that were forced status transitions. Emergency applies add the
+    /// justification and the checks they bypassed.
+    Applied {
+        field_changes: Vec<serde_json::Value>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        forced_transitions: Vec<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        bypassed: Vec<PolicyViolation>,
+    },
+    /// `proposal_withdrawn` by someone other than the author (denied, or an Admin override).
+    Withdrawal {
+        author: String,
+        admin_override: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// `proposal_triaged`.
+    Triage {
+        action: TriageAction,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// `proposal_superseded`: the auto-merged proposal that replaced it.
+    Superseded { superseded_by: String },
+    /// `review_submitted` by a delegate standing in for required approvers
+    /// (`POST /me/delegations`).
+    Delegated {
+        delegate: String,
+        on_behalf_of: Vec<String>,
+        delegation_ids: Vec<String>,
+    },
+    /// `proposal_updated` by `PATCH /proposals/:id/operations`: the operation ids added,
+    /// replaced and removed, and whether the order changed.
+    OperationsEdited {
+        added: Vec<String>,
+        replaced: Vec<String>,
+        removed: Vec<String>,
+        reordered: bool,
+    },
+    /// `proposal_updated` by `POST /proposals/:id/forge`.
+    Forge { forge: ForgeLink },
+    /// `comment_added`.
+    Comment {
+        comment_id: String,
+        author: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        operation_id: Option<String>,
+    },
+    /// `conflicts_detected`.
+    Conflicts { conflicts: usize },
+    /// `proposals_merged`.
+    Merge {
+        proposal_ids: Vec<String>,
+        merged: usize,
+        auto_merged: usize,
+        conflicts: usize,
+    },
+    /// `conflicts_resolved`: each field's chosen value and the discarded ones.
+    Resolutions {
+        resolutions: Vec<ConflictResolution>,
+    },
+    /// `events_subscribed`: the stream's filters; `resumed` when it picked up from a
+    /// `Last-Event-ID`.
+    Subscription {
+        workspace: Option<String>,
+        event_types: Option<String>,
+        resumed: bool,
+    },
+    /// `context_pack_generated`.
+    ContextPack {
+        task: String,
+        budget_tokens: usize,
+        estimated_tokens: usize,
+        nodes: Vec<String>,
+        omitted: usize,
+    },
+    /// `dsar_export` / `dsar_erase`: the subject's audit events exported or on record.
+    Dsar { subject: String, audit_events: u64 },
+    /// `read_only_changed`.
+    ReadOnly {
+        enabled: bool,
+        reason: Option<String>,
+        source: String,
+    },
+    /// `node_restored`: the version brought back.
+    Restored { version: u32 },
+    /// `export_requested`.
+    Export {
+        kind: ExportKind,
+        format: ExportFormat,
+    },
+    /// `job_retried`.
+    JobRetried { kind: String },
+    /// `task_triggered`: the job that runs it.
+    TaskTriggered { job_id: String },
+    /// `store_seeded`.
+    Seeded(ImportSummary),
+    /// `store_compacted`.
+    Compacted(CompactReport),
+    /// `tasks_overdue`: each `{ node, assignee, dueDate, state }`.
+    TasksOverdue {
+        checked: usize,
+        overdue: Vec<serde_json::Value>,
+    },
+    /// `risks_need_review`: each `{ node, severity, score, unmitigated, lastReviewedAt }`.
+    RisksNeedReview {
+        checked: usize,
+        review_after_days: i64,
+        risks: Vec<serde_json::Value>,
+    },
+    /// `slack_interaction`: the Slack user and the actor it maps to; `error` when the
+    /// action was refused or failed.
+    Slack {
+        slack_user_id: String,
+        slack_user_name: Option<String>,
+        slack_team_id: Option<String>,
+        actor_id: Option<String>,
+        action: ReviewAction,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// `actor_provisioned` / `actor_deprovisioned`.
+    ScimUser {
+        operation: String,
+        scim_id: String,
+        external_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        active: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        previous_user_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tokens_revoked_at: Option<String>,
+    },
+    /// `group_provisioned` / `group_deprovisioned`.
+    ScimGroup {
+        operation: String,
+        display_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        members: Option<Vec<String>>,
+    },
 }
 
 /// Outcome of the audited action.
@@ -28,6 +283,8 @@ pub enum AuditOutcome {
     Success,
     Denied,
     PolicyViolation,
+    /// A rule in `warn` or `shadow` enforcement was violated; the action went ahead.
+    PolicyWarning,
     Error,
 }
 
@@ -70,8 +327,152 @@ impl AuditEvent {
         }
     }
 
-    pub fn with_details(mut self, details: serde_json::Value) -> Self {
-        self.details = Some(details);
+    /// The event with the workspace of the resource it is about.
+    pub fn in_workspace(mut self, workspace: &str) -> Self {
+        self.workspace_id = Some(workspace.to_string());
         self
     }
+
+    pub fn with_details(mut self, details: AuditDetails) -> Self {
+        self.details = serde_json::to_value(details).ok();
+        self
+    }
+
+    /// Header of the CSV export; [`csv_row`](Self::csv_row) writes the columns in this order.
+    pub const CSV_HEADER: &'static str =
+        "event_id,timestamp,actor_id,actor_type,action,resource_id,workspace_id,outcome,details\r\n";
+
+    /// The event as one CSV record (RFC 4180): fields holding a comma, quote or line break
+    /// are quoted, with quotes doubled; `details` is its JSON text. A field a spreadsheet
+    /// would read as a formula (starting with `=`, `+`, `-`, `@`, a tab or a carriage
+    /// return) gets a leading `'`, so opening an export runs nothing.
+    pub fn csv_row(&self) -> String {
+        let details = self
+            .details
+            .as_ref()
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+        let fields = [
+            self.event_id.as_str(),
+            self.timestamp.as_str(),
+            self.actor_id.as_str(),
+            self.actor_type.as_str(),
+            &enum_str(&self.action),
+            self.resource_id.as_str(),
+            self.workspace_id.as_deref().unwrap_or_default(),
+            &enum_str(&self.outcome),
+            &details,
+        ];
+        let mut row = fields.map(csv_field).join(",");
+        row.push_str("\r\n");
+        row
+    }
+
+    /// CSV export (header + one row per event), as written by `truthlayer export-audit`
+    /// and audit export jobs. `GET /audit/export?format=csv` streams the same rows.
+    pub fn to_csv(events: &[AuditEvent]) -> String {
+        let mut csv = String::from(Self::CSV_HEADER);
+        for e in events {
+            csv.push_str(&e.csv_row());
+        }
+        csv
+    }
+}
+
+/// The serde name of a unit enum variant.
+fn enum_str<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details_serialize_with_camel_case_keys() {
+        let event = AuditEvent::new(
+            "system",
+            "system",
+            AuditAction::NodesPurged,
+            "retention:node",
+            AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::Purged {
+            nodes: vec!["goal-1".to_string()],
+            retention_days: 30,
+        });
+        assert_eq!(
+            event.details,
+            Some(serde_json::json!({ "nodes": ["goal-1"], "retentionDays": 30 }))
+        );
+        let read = AuditDetails::SensitiveRead {
+            node_sensitivity: Sensitivity::Confidential,
+            agent_max_sensitivity: None,
+        };
+        assert_eq!(
+            serde_json::to_value(read).unwrap(),
+            serde_json::json!({ "nodeSensitivity": "confidential" })
+        );
+    }
+
+    #[test]
+    fn csv_rows_quote_commas_quotes_and_details() {
+        let mut event = AuditEvent::new(
+            "svc,\"ops\"",
+            "human",
+            AuditAction::CommentAdded,
+            "p-1",
+            AuditOutcome::Success,
+        )
+        .with_details(AuditDetails::Conflicts { conflicts: 2 });
+        event.workspace_id = Some("ws-1".to_string());
+        let row = event.csv_row();
+        assert!(row.starts_with(&event.event_id));
+        assert!(row.ends_with(
+            ",\"svc,\"\"ops\"\"\",human,comment_added,p-1,ws-1,success,\"{\"\"conflicts\"\":2}\"\r\n"
+        ));
+        assert_eq!(AuditEvent::CSV_HEADER.split(',').count(), 9);
+    }
+
+    #[test]
+    fn csv_rows_defuse_formulas() {
+        let event = AuditEvent::new(
+            "=HYPERLINK(\"http://x\",\"y\")",
+            "human",
+            AuditAction::CommentAdded,
+            "@SUM(1+1)",
+            AuditOutcome::Success,
+        );
+        let row = event.csv_row();
+        assert!(
+            row.contains(",\"'=HYPERLINK(\"\"http://x\"\",\"\"y\"\")\",human,"),
+            "{}",
+            row
+        );
+        assert!(row.contains(",'@SUM(1+1),"), "{}", row);
+        for (field, expected) in [
+            ("+1", "'+1"),
+            ("-2", "'-2"),
+            ("\tx", "'\tx"),
+            ("a=b", "a=b"),
+        ] {
+            assert_eq!(csv_field(field), expected);
+        }
+    }
 }
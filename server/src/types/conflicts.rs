@@ -35,6 +35,22 @@ pub struct ConflictDetectionResult {
     pub conflicts: Vec<ProposalConflict>,
     pub mergeable: Vec<String>,
     pub needs_resolution: Vec<String>,
+    /// Nodes the proposal touches that someone other than its author has locked for
+    /// editing (`POST /nodes/:id/lock`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locked_nodes: Vec<NodeLock>,
+}
+
+/// An advisory edit reservation on a node (`POST /nodes/:id/lock`): a warning to other
+/// authors, not an enforced lock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeLock {
+    pub node_id: NodeId,
+    /// Actor id of whoever reserved the node.
+    pub holder: String,
+    pub acquired_at: String,
+    pub expires_at: String,
 }
 
 /// Field-level change. Used in MergeResult (merged, auto_merged).
@@ -55,6 +71,17 @@ pub struct MergeConflictField {
     pub node_id: NodeId,
     pub proposal1_value: serde_json::Value,
     pub proposal2_value: serde_json::Value,
+    /// The field's value at the proposals' base version, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_value: Option<serde_json::Value>,
+    /// The proposals that changed the field from its base (all that set it when the
+    /// base is unknown); `proposal1Value` and `proposal2Value` are the first two's.
+    #[serde(default)]
+    pub proposal_ids: Vec<String>,
+    /// For `content`: the line-level merge with diff3 conflict markers around the hunks
+    /// the proposals changed differently.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marked_content: Option<String>,
 }
 
 /// Result of mergeProposals(proposalIds). Per AGENT_API: merged, conflicts, autoMerged.
@@ -65,3 +92,26 @@ pub struct MergeResult {
     pub conflicts: Vec<MergeConflictField>,
     pub auto_merged: Vec<FieldChange>,
 }
+
+/// A human's choice for a field that open proposals set differently
+/// (`POST /proposals/:id/resolve-conflicts`), kept in the resolving proposal's
+/// `metadata.conflictResolutions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictResolution {
+    pub node_id: NodeId,
+    pub field: String,
+    /// The value the proposal's update now sets.
+    pub value: serde_json::Value,
+    pub resolved_by: String,
+    pub resolved_at: String,
+    /// The other values proposed for the field, and by which proposal.
+    pub discarded: Vec<DiscardedValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscardedValue {
+    pub proposal_id: String,
+    pub value: serde_json::Value,
+}
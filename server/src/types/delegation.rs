@@ -0,0 +1,35 @@
+//! Review delegation (`POST /me/delegations`): an approver hands their approval to
+//! another actor for a time window, e.g. over a holiday. A review the delegate submits
+//! while the delegation is active counts as the approver's for proposals that require
+//! them (`metadata.requiredApprovers`), and is audited with both identities.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Delegation {
+    pub id: String,
+    /// The approver handing over their approval.
+    pub delegator: String,
+    /// The actor who may approve in their place.
+    pub delegate: String,
+    pub starts_at: DateTime<Utc>,
+    /// Exclusive.
+    pub ends_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+impl Delegation {
+    /// Whether the delegate may approve for the delegator at `at`.
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        self.starts_at <= at && at < self.ends_at
+    }
+
+    /// Whether the window is over at `at` (the delegation can be dropped).
+    pub fn is_expired(&self, at: DateTime<Utc>) -> bool {
+        self.ends_at <= at
+    }
+}
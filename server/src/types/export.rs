@@ -0,0 +1,93 @@
+//! Export job records: large downloads produced in the background (`POST /admin/exports`).
+
+use serde::{Deserialize, Serialize};
+
+/// What an export job dumps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKind {
+    /// The whole audit log (JSON or CSV, like `GET /audit/export`).
+    Audit,
+    /// A full store bundle (nodes, proposals, reviews, audit log), like `snapshot`.
+    Bundle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// One export job. The finished artifact is stored separately (see
+/// `ContextStore::get_export_artifact`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportJob {
+    pub id: String,
+    pub kind: ExportKind,
+    pub format: ExportFormat,
+    pub status: ExportJobStatus,
+    pub requested_by: String,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    /// Records written so far (progress while running).
+    #[serde(default)]
+    pub processed: u64,
+    /// Artifact size once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ExportJob {
+    /// A queued job with a generated ID.
+    pub fn new(kind: ExportKind, format: ExportFormat, requested_by: &str) -> Self {
+        Self {
+            id: format!("export-{}", uuid::Uuid::new_v4()),
+            kind,
+            format,
+            status: ExportJobStatus::Queued,
+            requested_by: requested_by.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            started_at: None,
+            completed_at: None,
+            processed: 0,
+            size_bytes: None,
+            error: None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self.format {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv",
+        }
+    }
+
+    /// Download file name, e.g. `audit-export-<uuid>.csv`.
+    pub fn file_name(&self) -> String {
+        let kind = match self.kind {
+            ExportKind::Audit => "audit",
+            ExportKind::Bundle => "bundle",
+        };
+        let ext = match self.format {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        };
+        format!("{}-{}.{}", kind, self.id, ext)
+    }
+}
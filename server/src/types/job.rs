@@ -0,0 +1,71 @@
+//! Background job records: the persistent queue behind `crate::jobs` (`/admin/jobs`).
+
+use serde::{Deserialize, Serialize};
+
+/// Attempts a job gets unless the queue is configured otherwise.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for a worker (new, or retrying after `runAfter`).
+    Queued,
+    Running,
+    Completed,
+    /// Out of attempts; `lastError` says why.
+    Failed,
+}
+
+/// One queued unit of background work. `kind` selects the handler; `payload` is
+/// handler-specific input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRecord {
+    pub id: String,
+    pub kind: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    /// Attempts started so far (including the running one).
+    #[serde(default)]
+    pub attempts: u32,
+    pub max_attempts: u32,
+    pub created_at: String,
+    /// Earliest start (RFC 3339); set while waiting out a retry backoff.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Handler output of a completed job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+}
+
+impl JobRecord {
+    /// A queued job with a generated ID, due immediately.
+    pub fn new(kind: &str, payload: serde_json::Value) -> Self {
+        Self {
+            id: format!("job-{}", uuid::Uuid::new_v4()),
+            kind: kind.to_string(),
+            payload,
+            status: JobStatus::Queued,
+            attempts: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            run_after: None,
+            started_at: None,
+            finished_at: None,
+            last_error: None,
+            result: None,
+        }
+    }
+
+    /// Queued and past any backoff at `now` (RFC 3339).
+    pub fn is_due(&self, now: &str) -> bool {
+        self.status == JobStatus::Queued && self.run_after.as_deref().is_none_or(|t| t <= now)
+    }
+}
@@ -1,11 +1,19 @@
 pub mod audit;
 pub mod conflicts;
+pub mod delegation;
+pub mod export;
+pub mod job;
 pub mod node;
+pub mod preferences;
 pub mod proposal;
 pub mod query;
 
 pub use audit::*;
 pub use conflicts::*;
+pub use delegation::*;
+pub use export::*;
+pub use job::*;
 pub use node::*;
+pub use preferences::*;
 pub use proposal::*;
 pub use query::*;
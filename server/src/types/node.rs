@@ -1,6 +1,8 @@
 //! Core node types for the context graph.
 //! Mirrors src/types/node.ts.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::sensitivity::Sensitivity;
@@ -35,29 +37,88 @@ impl NodeType {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum NodeStatus {
     Accepted,
     Proposed,
     Rejected,
     Superseded,
+    /// Obsolete but kept for reference: left out of node queries unless asked for.
+    Archived,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+impl NodeStatus {
+    /// The node status transition table. Staying put is always allowed and a create may
+    /// start in any status; otherwise:
+    ///
+    /// - proposed → accepted, rejected, archived
+    /// - accepted → superseded, archived
+    /// - rejected → proposed (reopened), archived
+    /// - superseded → archived
+    /// - archived → proposed
+    pub fn can_become(self, to: NodeStatus) -> bool {
+        use NodeStatus::*;
+        self == to
+            || matches!(
+                (self, to),
+                (Proposed, Accepted | Rejected | Archived)
+                    | (Accepted, Superseded | Archived)
+                    | (Rejected, Proposed | Archived)
+                    | (Superseded, Archived)
+                    | (Archived, Proposed)
+            )
+    }
+
+    /// Lowercase name, as in the JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeStatus::Accepted => "accepted",
+            NodeStatus::Proposed => "proposed",
+            NodeStatus::Rejected => "rejected",
+            NodeStatus::Superseded => "superseded",
+            NodeStatus::Archived => "archived",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodeId {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub namespace: Option<String>,
 }
 
+/// Workspace of nodes without a namespace. The server has no workspaces of its own: a
+/// node's workspace is its namespace.
+pub const DEFAULT_WORKSPACE: &str = "default";
+
 impl NodeId {
+    /// The workspace the node belongs to: its namespace, [`DEFAULT_WORKSPACE`] without one.
+    pub fn workspace(&self) -> &str {
+        self.namespace.as_deref().unwrap_or(DEFAULT_WORKSPACE)
+    }
+
     pub fn key(&self) -> String {
         self.namespace
             .as_ref()
             .map(|n| format!("{}:{}", n, self.id))
             .unwrap_or_else(|| self.id.clone())
     }
+
+    /// Parse a key (`namespace:id`, or `id` without a namespace).
+    pub fn from_key(key: &str) -> Self {
+        match key.split_once(':') {
+            Some((namespace, id)) => NodeId {
+                id: id.to_string(),
+                namespace: Some(namespace.to_string()),
+            },
+            None => NodeId {
+                id: key.to_string(),
+                namespace: None,
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -157,6 +218,37 @@ pub struct NodeMetadata {
     /// License identifier for content.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+    /// Set when a delete operation moved the node to the trash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<DeletedMetadata>,
+    /// Last change of each field, keyed by field name (`GET /nodes/:id/blame`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_changes: Option<BTreeMap<String, FieldBlame>>,
+}
+
+/// Who last changed a node field, and through which proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldBlame {
+    pub proposal_id: String,
+    /// The proposal's author.
+    pub author: String,
+    pub applied_by: String,
+    pub changed_at: String,
+    /// Node version the change produced.
+    pub version: u32,
+}
+
+/// Tombstone of a deleted node. The node keeps its content and status so it can be
+/// restored (`POST /admin/trash/:id/restore`) until retention purges it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedMetadata {
+    pub deleted_at: String,
+    pub deleted_by: String,
+    /// Proposal whose delete operation removed the node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposal_id: Option<String>,
 }
 
 /// Context node: unified struct for all node types.
@@ -164,6 +256,8 @@ pub struct NodeMetadata {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContextNode {
+    /// Assigned by the server (see `crate::ids`) when a create operation omits it.
+    #[serde(default)]
     pub id: NodeId,
     #[serde(rename = "type")]
     pub node_type: NodeType,
@@ -233,6 +327,70 @@ pub enum TaskState {
     Cancelled,
 }
 
+impl TaskState {
+    /// The task state transition table. Staying put is always allowed and a task may be
+    /// created in any state; otherwise:
+    ///
+    /// - open → in-progress, blocked, cancelled
+    /// - in-progress → blocked, completed, cancelled, open (unassigned / put back)
+    /// - blocked → in-progress, open, cancelled
+    /// - completed → open (reopened)
+    /// - cancelled → open (reopened)
+    pub fn can_become(self, to: TaskState) -> bool {
+        use TaskState::*;
+        self == to
+            || matches!(
+                (self, to),
+                (Open, InProgress | Blocked | Cancelled)
+                    | (InProgress, Blocked | Completed | Cancelled | Open)
+                    | (Blocked, InProgress | Open | Cancelled)
+                    | (Completed, Open)
+                    | (Cancelled, Open)
+            )
+    }
+
+    /// Whether the task is finished (completed or cancelled), so it cannot be overdue.
+    pub fn is_done(self) -> bool {
+        matches!(self, TaskState::Completed | TaskState::Cancelled)
+    }
+
+    /// Kebab-case name, as in the JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskState::Open => "open",
+            TaskState::InProgress => "in-progress",
+            TaskState::Blocked => "blocked",
+            TaskState::Completed => "completed",
+            TaskState::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl ContextNode {
+    /// Whether this is an unfinished task whose `dueDate` is before `now`. A due date is
+    /// an RFC 3339 date-time or a plain `YYYY-MM-DD` (due by the end of that day, UTC).
+    pub fn is_overdue(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        if self.node_type != NodeType::Task || self.state.is_some_and(TaskState::is_done) {
+            return false;
+        }
+        let Some(due) = self.due_date.as_deref() else {
+            return false;
+        };
+        if let Ok(due) = chrono::DateTime::parse_from_rfc3339(due) {
+            return due < now;
+        }
+        match chrono::NaiveDate::parse_from_str(due, "%Y-%m-%d") {
+            Ok(day) => day < now.date_naive(),
+            Err(_) => false,
+        }
+    }
+
+    /// Risk score: severity weight × likelihood weight, 1–16. `None` unless both are set.
+    pub fn risk_score(&self) -> Option<u8> {
+        Some(self.severity?.weight() * self.likelihood?.weight())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum RiskSeverity {
@@ -250,3 +408,17 @@ pub enum RiskLikelihood {
     Likely,
     Certain,
 }
+
+impl RiskSeverity {
+    /// 1 (low) to 4 (critical).
+    pub fn weight(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
+impl RiskLikelihood {
+    /// 1 (unlikely) to 4 (certain).
+    pub fn weight(self) -> u8 {
+        self as u8 + 1
+    }
+}
@@ -0,0 +1,120 @@
+//! Per-user settings shared across a user's devices and extensions
+//! (`GET/PUT /me/preferences`). The server stores them; clients interpret them.
+
+use serde::{Deserialize, Serialize};
+
+/// Most saved filters one user can keep.
+pub const MAX_SAVED_FILTERS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    Email,
+    Slack,
+    Webhook,
+}
+
+/// Where the user wants to be notified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannel {
+    pub kind: NotificationKind,
+    /// Address, Slack channel / user id or URL, per `kind`.
+    pub target: String,
+    /// Event types to notify about; empty = all.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+/// A named query a client can re-run, e.g. `{ "name": "my reviews", "resource":
+/// "proposals", "query": "status=open&reviewer=alice" }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedFilter {
+    pub name: String,
+    /// Collection the query applies to (`nodes`, `proposals`, `audit`, …).
+    pub resource: String,
+    /// Query string, without the leading `?`.
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserPreferences {
+    #[serde(default)]
+    pub notification_channels: Vec<NotificationChannel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_workspace: Option<String>,
+    #[serde(default)]
+    pub saved_filters: Vec<SavedFilter>,
+    /// Event types to subscribe to on `/events` (and the other event streams); empty = all.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Set by the server on save.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl UserPreferences {
+    /// Problems that make the preferences unsaveable (empty = valid).
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for (i, channel) in self.notification_channels.iter().enumerate() {
+            if channel.target.trim().is_empty() {
+                issues.push(format!("notificationChannels[{}].target is empty", i));
+            }
+        }
+        if self.saved_filters.len() > MAX_SAVED_FILTERS {
+            issues.push(format!(
+                "at most {} saved filters, got {}",
+                MAX_SAVED_FILTERS,
+                self.saved_filters.len()
+            ));
+        }
+        for (i, filter) in self.saved_filters.iter().enumerate() {
+            if filter.name.trim().is_empty() {
+                issues.push(format!("savedFilters[{}].name is empty", i));
+            } else if self.saved_filters[..i]
+                .iter()
+                .any(|f| f.name == filter.name)
+            {
+                issues.push(format!("savedFilters: duplicate name '{}'", filter.name));
+            }
+        }
+        if self.default_workspace.as_deref() == Some("") {
+            issues.push("defaultWorkspace is empty".to_string());
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_flags_empty_targets_and_duplicate_filters() {
+        let filter = SavedFilter {
+            name: "mine".to_string(),
+            resource: "proposals".to_string(),
+            query: "createdBy=alice".to_string(),
+        };
+        let prefs = UserPreferences {
+            notification_channels: vec![NotificationChannel {
+                kind: NotificationKind::Email,
+                target: " ".to_string(),
+                event_types: Vec::new(),
+            }],
+            saved_filters: vec![filter.clone(), filter],
+            ..Default::default()
+        };
+        assert_eq!(
+            prefs.validate(),
+            [
+                "notificationChannels[0].target is empty",
+                "savedFilters: duplicate name 'mine'"
+            ]
+        );
+        assert!(UserPreferences::default().validate().is_empty());
+    }
+}
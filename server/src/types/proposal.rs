@@ -3,7 +3,11 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{ContextNode, NodeId, NodeStatus};
+use crate::sensitivity::Sensitivity;
+use crate::types::{
+    ConflictResolution, ContextNode, NodeId, NodeRelationship, NodeStatus, RiskLikelihood,
+    RiskSeverity, TaskState, TextRange, DEFAULT_WORKSPACE,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -14,14 +18,24 @@ pub enum ProposalStatus {
     Withdrawn,
     /// Terminal: proposal has been applied to accepted truth. Prevents double-apply.
     Applied,
+    /// Agent proposal held for human triage (`agent_quarantine` policy) before review.
+    Quarantined,
+    /// Terminal: combined into another proposal by `POST /proposals/auto-merge`
+    /// (`metadata.supersededBy`).
+    Superseded,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProposalMetadata {
+    #[serde(default)]
     pub created_at: String,
+    /// Set by the server to the authenticated actor; a different value is refused.
+    #[serde(default)]
     pub created_by: String,
+    #[serde(default)]
     pub modified_at: String,
+    #[serde(default)]
     pub modified_by: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rationale: Option<String>,
@@ -31,10 +45,76 @@ pub struct ProposalMetadata {
     pub approved_by: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_versions: Option<std::collections::HashMap<String, u32>>,
+    /// Apply status changes that break the transition table (`NodeStatus::can_become`).
+    /// Only an Admin may apply such a proposal; the forced transitions are audited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_status_transitions: Option<bool>,
+    /// Pull / merge request mirroring this proposal (see `crate::forge`). Set by the
+    /// server only: `POST /proposals/:id/forge` and forge webhooks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<ForgeLink>,
+    /// Size and complexity score (see `crate::complexity`). Set by the server on create.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<Complexity>,
+    /// Authors of the proposals `POST /proposals/auto-merge` combined into this one.
+    /// Set by the server only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authors: Option<Vec<String>>,
+    /// The auto-merged proposal that replaced this one. Set by the server only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    /// Conflicts with other proposals a human settled for this one
+    /// (`POST /proposals/:id/resolve-conflicts`). Set by the server only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict_resolutions: Option<Vec<ConflictResolution>>,
+}
+
+/// How big and risky a proposal is, so reviewers can tell a large change to sensitive
+/// nodes from a typo fix.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Complexity {
+    pub score: u32,
+    pub operations: u32,
+    /// Bytes of content, titles and descriptions the operations write.
+    pub content_bytes: u64,
+    /// Distinct nodes touched.
+    pub nodes: u32,
+    /// Touched nodes per sensitivity level.
+    pub sensitivity: std::collections::BTreeMap<Sensitivity, u32>,
+}
+
+/// A proposal's linked pull request (GitHub) or merge request (GitLab).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgeLink {
+    /// Workspace whose `forge` config the link uses.
+    pub workspace: String,
+    /// Pull request number or merge request IID.
+    pub number: u64,
+    pub linked_by: String,
+    pub linked_at: String,
+    /// Last pipeline / check suite result reported for the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<PipelineStatus>,
+    /// Commit the pipeline ran on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline_sha: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStatus {
+    Pending,
+    Running,
+    Success,
+    Failed,
+    Canceled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "kebab-case")]
+#[allow(clippy::large_enum_variant)]
 pub enum Operation {
     Create {
         id: String,
@@ -65,6 +145,8 @@ pub enum Operation {
     },
 }
 
+/// Fields an update operation sets; absent fields are left as they are. Names match
+/// `ContextNode`; `tags` through `license` are set in the node's metadata.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateChanges {
@@ -72,8 +154,139 @@ pub struct UpdateChanges {
     pub content: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<NodeStatus>,
-    #[serde(flatten)]
-    pub extra: Option<std::collections::HashMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_range: Option<TextRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationships: Option<Vec<NodeRelationship>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relations: Option<Vec<NodeId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_files: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternatives: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<TaskState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<NodeId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<RiskSeverity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub likelihood: Option<RiskLikelihood>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mitigation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answered_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    // Metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<Sensitivity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implemented_in_commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_in_commits: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_attribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_classification: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
+/// Metadata fields an update can set (camelCase, as in the JSON).
+pub const UPDATABLE_METADATA_FIELDS: [&str; 7] = [
+    "tags",
+    "sensitivity",
+    "implementedInCommit",
+    "referencedInCommits",
+    "sourceAttribution",
+    "ipClassification",
+    "license",
+];
+
+impl UpdateChanges {
+    /// The fields this update sets, with their new values, keyed by camelCase name.
+    pub fn fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        }
+    }
+
+    /// Set the fields this update carries on `node`. Versioning, hashes and modification
+    /// metadata are left to the store.
+    pub fn apply_to(&self, node: &mut ContextNode) {
+        fn set<T: Clone>(target: &mut T, value: &Option<T>) {
+            if let Some(v) = value {
+                *target = v.clone();
+            }
+        }
+        fn set_opt<T: Clone>(target: &mut Option<T>, value: &Option<T>) {
+            if value.is_some() {
+                *target = value.clone();
+            }
+        }
+        set(&mut node.content, &self.content);
+        set(&mut node.status, &self.status);
+        set_opt(&mut node.title, &self.title);
+        set_opt(&mut node.description, &self.description);
+        set_opt(&mut node.text_range, &self.text_range);
+        set_opt(&mut node.relationships, &self.relationships);
+        set_opt(&mut node.relations, &self.relations);
+        set_opt(&mut node.source_files, &self.source_files);
+        set_opt(&mut node.decision, &self.decision);
+        set_opt(&mut node.rationale, &self.rationale);
+        set_opt(&mut node.alternatives, &self.alternatives);
+        set_opt(&mut node.decided_at, &self.decided_at);
+        set_opt(&mut node.state, &self.state);
+        set_opt(&mut node.assignee, &self.assignee);
+        set_opt(&mut node.due_date, &self.due_date);
+        set_opt(&mut node.dependencies, &self.dependencies);
+        set_opt(&mut node.severity, &self.severity);
+        set_opt(&mut node.likelihood, &self.likelihood);
+        set_opt(&mut node.mitigation, &self.mitigation);
+        set_opt(&mut node.question, &self.question);
+        set_opt(&mut node.answer, &self.answer);
+        set_opt(&mut node.answered_at, &self.answered_at);
+        set_opt(&mut node.constraint, &self.constraint);
+        set_opt(&mut node.reason, &self.reason);
+        let metadata = &mut node.metadata;
+        set_opt(&mut metadata.tags, &self.tags);
+        set_opt(&mut metadata.sensitivity, &self.sensitivity);
+        set_opt(
+            &mut metadata.implemented_in_commit,
+            &self.implemented_in_commit,
+        );
+        set_opt(
+            &mut metadata.referenced_in_commits,
+            &self.referenced_in_commits,
+        );
+        set_opt(&mut metadata.source_attribution, &self.source_attribution);
+        set_opt(&mut metadata.ip_classification, &self.ip_classification);
+        set_opt(&mut metadata.license, &self.license);
+    }
 }
 
 /// Metadata recorded when a proposal is applied. Required for audit and idempotency.
@@ -92,9 +305,12 @@ pub struct AppliedMetadata {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Proposal {
+    /// Assigned by the server (see `crate::ids`) when omitted on create.
+    #[serde(default)]
     pub id: String,
     pub status: ProposalStatus,
     pub operations: Vec<Operation>,
+    #[serde(default)]
     pub metadata: ProposalMetadata,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comments: Option<Vec<Comment>>,
@@ -105,6 +321,77 @@ pub struct Proposal {
     pub applied: Option<AppliedMetadata>,
 }
 
+/// Partial update of a proposal: the body of `PATCH /proposals/:id` and the argument
+/// of `ContextStore::update_proposal`. Fields left out are kept; unknown fields are
+/// refused, so a misspelt one is not silently dropped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProposalPatch {
+    /// Subject to the lifecycle (`store::lifecycle`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProposalStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProposalMetadataPatch>,
+    /// Replaces the proposal's comments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comments: Option<Vec<Comment>>,
+}
+
+impl Proposal {
+    /// The workspace the proposal belongs to: that of the node its first operation
+    /// touches.
+    pub fn workspace(&self) -> &str {
+        match self.operations.first() {
+            Some(Operation::Create { node, .. }) => node.id.workspace(),
+            Some(
+                Operation::Update { node_id, .. }
+                | Operation::Delete { node_id, .. }
+                | Operation::StatusChange { node_id, .. },
+            ) => node_id.workspace(),
+            None => DEFAULT_WORKSPACE,
+        }
+    }
+}
+
+impl ProposalPatch {
+    /// A patch that only moves the proposal to `status`.
+    pub fn status(status: ProposalStatus) -> Self {
+        Self {
+            status: Some(status),
+            ..Default::default()
+        }
+    }
+}
+
+/// The metadata fields a [`ProposalPatch`] can change. `modified_at` / `modified_by`
+/// are also accepted in snake_case, as earlier clients sent them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProposalMetadataPatch {
+    /// Stamped by the server (`crate::timestamps`).
+    #[serde(
+        default,
+        alias = "modified_at",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub modified_at: Option<String>,
+    /// Set by the server to the authenticated actor; a different value is refused.
+    #[serde(
+        default,
+        alias = "modified_by",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub modified_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// Server only (`POST /proposals/:id/forge`, forge webhooks); `PATCH` refuses it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<ForgeLink>,
+    /// Server only (`POST /proposals/:id/resolve-conflicts`); `PATCH` refuses it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict_resolutions: Option<Vec<ConflictResolution>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CommentAnchor {
@@ -137,6 +424,8 @@ pub struct Comment {
     pub id: String,
     pub content: String,
     pub author: String,
+    /// Stamped by the server (`crate::timestamps`).
+    #[serde(default)]
     pub created_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<CommentStatus>,
@@ -160,6 +449,15 @@ pub enum ReviewAction {
     RequestChanges,
 }
 
+/// Outcome of triaging a quarantined proposal (`POST /proposals/triage`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriageAction {
+    /// Into the review queue (open).
+    Release,
+    Reject,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Review {
@@ -168,6 +466,10 @@ pub struct Review {
     pub reviewer: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reviewer_role: Option<String>,
+    /// The reviewer's directory groups (SCIM) when the review was submitted, for
+    /// `min_approvals` group quorums. Set by the server only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewer_groups: Option<Vec<String>>,
     pub reviewed_at: String,
     pub action: ReviewAction,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -178,4 +480,8 @@ pub struct Review {
     pub operation_ids: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_approval: Option<bool>,
+    /// Required approvers the reviewer stood in for under their active delegations
+    /// (`POST /me/delegations`). Set by the server only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_behalf_of: Option<Vec<String>>,
 }
@@ -1,4 +1,4 @@
-//! Query types for nodes and proposals.
+//! Query types for nodes, proposals and the audit log.
 //! Mirrors context-store NodeQuery, NodeQueryResult, ProposalQuery.
 
 use serde::{Deserialize, Serialize};
@@ -29,6 +29,24 @@ pub struct NodeQuery {
     pub sort_by: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<SortOrder>,
+    /// Also return archived nodes. They are returned anyway when `status` asks for them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
+    /// Nodes implemented in or referencing this commit. A prefix matches, so abbreviated
+    /// SHAs find nodes linked with full ones; case is ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl NodeQuery {
+    /// Whether archived nodes belong in the result.
+    pub fn shows_archived(&self) -> bool {
+        self.include_archived == Some(true)
+            || self
+                .status
+                .as_ref()
+                .is_some_and(|s| s.contains(&NodeStatus::Archived))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -48,6 +66,17 @@ pub struct NodeQueryResult {
     pub has_more: bool,
 }
 
+/// One page of audit events, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQueryResult {
+    pub events: Vec<crate::types::AuditEvent>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+    pub has_more: bool,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProposalQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
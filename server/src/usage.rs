@@ -0,0 +1,277 @@
+//! Usage metering for chargeback: API calls, storage and agent reads of sensitive content,
+//! per workspace and day (UTC), exported by `GET /admin/usage?month=` (`crate::api::usage`).
+//!
+//! - **API calls:** [`count`] middleware tallies requests by the workspace they name
+//!   (`workspace` or `namespace` query parameter, else `default`). Each instance keeps
+//!   its tallies in a [`UsageMeter`] and adds them to the store every
+//!   [`FLUSH_INTERVAL`].
+//! - **Storage and agent reads:** the `usage_rollup` task (job [`USAGE_ROLLUP_JOB`],
+//!   payload `{ "date": "YYYY-MM-DD" }`, default yesterday) measures each workspace's
+//!   nodes and counts the day's successful `sensitive_read` audit events by agents.
+//!   Re-running a day replaces these measures and keeps its API calls.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::jobs::JobHandler;
+use crate::store::context_store::StoreError;
+use crate::store::{ContextStore, UsageRecord};
+use crate::types::{AuditOutcome, JobRecord, NodeQuery};
+
+/// Job kind that rolls up one day's usage.
+pub const USAGE_ROLLUP_JOB: &str = "usage_rollup";
+
+/// How often an instance adds its API call tallies to the store.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Workspace of requests and nodes without one.
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+const PAGE_SIZE: u32 = 1000;
+
+/// This instance's API calls not yet added to the store, by (date, workspace).
+#[derive(Clone, Default)]
+pub struct UsageMeter {
+    pending: Arc<Mutex<BTreeMap<(String, String), u64>>>,
+}
+
+impl UsageMeter {
+    pub fn record(&self, workspace: &str) {
+        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending.entry((date, workspace.to_string())).or_default() += 1;
+        }
+    }
+
+    /// Add the pending tallies to the store; on failure they stay pending.
+    pub async fn flush(&self, store: &Arc<dyn ContextStore>) -> Result<(), StoreError> {
+        let taken = match self.pending.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return Ok(()),
+        };
+        let mut by_date: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+        for ((date, workspace), n) in &taken {
+            by_date
+                .entry(date.clone())
+                .or_default()
+                .insert(workspace.clone(), *n);
+        }
+        for (date, calls) in by_date {
+            if let Err(e) = store.add_api_calls(&date, &calls).await {
+                if let Ok(mut pending) = self.pending.lock() {
+                    for ((d, workspace), n) in taken.into_iter().filter(|((d, _), _)| *d >= date) {
+                        *pending.entry((d, workspace)).or_default() += n;
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush every [`FLUSH_INTERVAL`] until the runtime stops.
+    pub fn start(&self, store: Arc<dyn ContextStore>) -> tokio::task::JoinHandle<()> {
+        let meter = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(e) = meter.flush(&store).await {
+                    tracing::warn!(error = %e, "usage flush failed");
+                }
+            }
+        })
+    }
+}
+
+/// The workspace a request names.
+fn request_workspace(req: &Request) -> String {
+    req.uri()
+        .query()
+        .and_then(|q| serde_urlencoded::from_str::<BTreeMap<String, String>>(q).ok())
+        .and_then(|params| {
+            params
+                .get("workspace")
+                .or_else(|| params.get("namespace"))
+                .filter(|w| !w.is_empty())
+                .cloned()
+        })
+        .unwrap_or_else(|| DEFAULT_WORKSPACE.to_string())
+}
+
+/// Axum middleware: count the request as an API call of its workspace.
+pub async fn count(State(meter): State<UsageMeter>, req: Request, next: Next) -> Response {
+    meter.record(&request_workspace(&req));
+    next.run(req).await
+}
+
+/// Workspace of a node key (`namespace:id`, or `id` in the default workspace).
+fn key_workspace(key: &str) -> &str {
+    key.split_once(':').map_or(DEFAULT_WORKSPACE, |(ns, _)| ns)
+}
+
+fn day_record<'a>(
+    records: &'a mut BTreeMap<String, UsageRecord>,
+    date: &str,
+    workspace: &str,
+) -> &'a mut UsageRecord {
+    records
+        .entry(workspace.to_string())
+        .or_insert_with(|| UsageRecord {
+            date: date.to_string(),
+            workspace: workspace.to_string(),
+            ..Default::default()
+        })
+}
+
+pub struct UsageRollupHandler;
+
+#[async_trait]
+impl JobHandler for UsageRollupHandler {
+    fn kind(&self) -> &'static str {
+        USAGE_ROLLUP_JOB
+    }
+
+    async fn run(
+        &self,
+        store: Arc<dyn ContextStore>,
+        job: &JobRecord,
+    ) -> Result<Option<serde_json::Value>, String> {
+        let date = match job.payload.get("date").and_then(|v| v.as_str()) {
+            Some(d) => chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map_err(|e| format!("date '{}': {}", d, e))?,
+            None => chrono::Utc::now().date_naive() - chrono::Duration::days(1),
+        };
+        let day = date.format("%Y-%m-%d").to_string();
+        let mut records: BTreeMap<String, UsageRecord> = BTreeMap::new();
+
+        let mut offset = 0;
+        loop {
+            let page = store
+                .query_nodes(NodeQuery {
+                    limit: Some(PAGE_SIZE),
+                    offset: Some(offset),
+                    include_archived: Some(true),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| e.to_string())?;
+            for node in &page.nodes {
+                let workspace = node.id.namespace.as_deref().unwrap_or(DEFAULT_WORKSPACE);
+                let entry = day_record(&mut records, &day, workspace);
+                entry.nodes += 1;
+                entry.storage_bytes += crate::store::limits::json_size(node) as u64;
+            }
+            if !page.has_more {
+                break;
+            }
+            offset += PAGE_SIZE;
+        }
+
+        let from = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let to = from + chrono::Duration::days(1);
+        let mut offset = 0;
+        loop {
+            let page = store
+                .query_audit(
+                    None,
+                    Some("sensitive_read"),
+                    None,
+                    Some(&from.to_rfc3339()),
+                    Some(&to.to_rfc3339()),
+                    Some(PAGE_SIZE),
+                    Some(offset),
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            for event in &page.events {
+                let in_day = chrono::DateTime::parse_from_rfc3339(&event.timestamp)
+                    .is_ok_and(|t| t >= from && t < to);
+                if in_day && event.actor_type == "agent" && event.outcome == AuditOutcome::Success {
+                    let workspace = key_workspace(&event.resource_id);
+                    day_record(&mut records, &day, workspace).agent_sensitive_reads += 1;
+                }
+            }
+            if !page.has_more || page.events.is_empty() {
+                break;
+            }
+            offset += page.events.len() as u32;
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let records: Vec<UsageRecord> = records
+            .into_values()
+            .map(|r| UsageRecord {
+                rolled_up_at: Some(now.clone()),
+                ..r
+            })
+            .collect();
+        let workspaces = records.len();
+        store
+            .save_usage_rollup(records)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Some(
+            serde_json::json!({ "date": day, "workspaces": workspaces }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AuditAction, AuditEvent};
+
+    #[tokio::test]
+    async fn flush_and_rollup_fill_the_day() {
+        let store: Arc<dyn ContextStore> = Arc::new(crate::store::InMemoryStore::new());
+        let meter = UsageMeter::default();
+        meter.record("payments");
+        meter.record("payments");
+        meter.record(DEFAULT_WORKSPACE);
+        meter.flush(&store).await.unwrap();
+        meter.flush(&store).await.unwrap();
+
+        for (actor_type, outcome) in [
+            ("agent", AuditOutcome::Success),
+            ("agent", AuditOutcome::Denied),
+            ("human", AuditOutcome::Success),
+        ] {
+            let event = AuditEvent::new(
+                "a",
+                actor_type,
+                AuditAction::SensitiveRead,
+                "payments:card-vault",
+                outcome,
+            );
+            store.append_audit(event).await.unwrap();
+        }
+
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let job = JobRecord::new(USAGE_ROLLUP_JOB, serde_json::json!({ "date": today }));
+        UsageRollupHandler.run(store.clone(), &job).await.unwrap();
+
+        let month = store.list_usage(&today[..7]).await.unwrap();
+        let payments = month
+            .iter()
+            .find(|r| r.date == today && r.workspace == "payments")
+            .unwrap();
+        assert_eq!(payments.api_calls, 2);
+        assert_eq!(payments.agent_sensitive_reads, 1);
+        assert!(payments.rolled_up_at.is_some());
+        let default = month
+            .iter()
+            .find(|r| r.date == today && r.workspace == DEFAULT_WORKSPACE)
+            .unwrap();
+        assert_eq!(default.api_calls, 1);
+    }
+}
@@ -0,0 +1,79 @@
+//! Build and deployment info for `GET /version`.
+//! Lets operators and the extension verify what is deployed and gate features on
+//! server capabilities (enabled transports, storage backend).
+
+use serde::Serialize;
+
+/// Crate version from Cargo.toml.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Short git commit hash, or "unknown" when built outside a git checkout (see build.rs).
+pub const GIT_COMMIT: &str = env!("TRUTHLAYER_GIT_COMMIT");
+
+/// Raw build timestamp from build.rs: Unix seconds, or an override string.
+const BUILD_TIMESTAMP_RAW: &str = env!("TRUTHLAYER_BUILD_TIMESTAMP");
+
+/// Build timestamp as RFC 3339. Overrides that are not Unix seconds are returned as-is.
+pub fn build_timestamp() -> String {
+    BUILD_TIMESTAMP_RAW
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| BUILD_TIMESTAMP_RAW.to_string())
+}
+
+/// Runtime deployment info, decided at startup in main.rs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+    /// Enabled transports: "h3" (QUIC, always on), "tls-tcp" (TLS fallback), "dev-tcp" (TRUTHTLAYER_DEV_TCP).
+    pub transports: Vec<String>,
+    /// Active storage backend: "memory" | "file".
+    pub storage_backend: String,
+}
+
+impl Default for ServerInfo {
+    fn default() -> Self {
+        Self {
+            transports: vec!["h3".to_string()],
+            storage_backend: "memory".to_string(),
+        }
+    }
+}
+
+/// Response body for `GET /version`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub build_timestamp: String,
+    pub transports: Vec<String>,
+    pub storage_backend: String,
+}
+
+impl VersionInfo {
+    pub fn new(server: &ServerInfo) -> Self {
+        Self {
+            version: VERSION.to_string(),
+            git_commit: GIT_COMMIT.to_string(),
+            build_timestamp: build_timestamp(),
+            transports: server.transports.clone(),
+            storage_backend: server.storage_backend.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_matches_cargo() {
+        let info = VersionInfo::new(&ServerInfo::default());
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_commit.is_empty());
+        assert_eq!(info.transports, vec!["h3".to_string()]);
+    }
+}
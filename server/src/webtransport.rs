@@ -0,0 +1,285 @@
+//! WebTransport event streams on the HTTP/3 endpoint: a browser alternative to SSE.
+//!
+//! Some proxies buffer SSE responses; WebTransport streams are delivered as they are
+//! written. A browser opens a session with `new WebTransport("https://host:port/webtransport")`.
+//! The extended CONNECT goes through the axum router first (`CONNECT /webtransport`), so
+//! auth, RBAC (Reader) and CORS apply exactly as for `GET /events`.
+//!
+//! Subscriptions are per stream:
+//! 1. The client opens a unidirectional stream, writes one JSON subscription
+//!    (`{"id":"p","workspace":"ws-1","eventTypes":["proposal_updated"]}`, all fields
+//!    optional) and closes it.
+//! 2. The server opens a unidirectional stream back and writes newline-delimited JSON:
+//!    `subscribed` (echoing the filters and `id`), then `event`, `lagged` (`missed`) and
+//!    periodic `keepalive` lines. A bad subscription gets a single `error` line.
+//! 3. The client ends a subscription by cancelling that stream's reader.
+//!
+//! A session takes over its QUIC connection (h3-webtransport drives the connection);
+//! ordinary requests that arrive on it are still routed through axum.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use h3::ext::Protocol;
+use h3::server::RequestStream;
+use h3_webtransport::server::{AcceptedBi, WebTransportSession};
+use h3_webtransport::stream::RecvStream;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Semaphore;
+
+use crate::events::{EventBus, ServerEvent};
+
+/// Path of the extended CONNECT request that opens a session.
+pub const WEBTRANSPORT_PATH: &str = "/webtransport";
+
+/// Most concurrent subscription streams per session; further subscriptions are refused.
+pub const MAX_SUBSCRIPTIONS_PER_SESSION: usize = 16;
+
+/// Largest subscription message accepted.
+const MAX_SUBSCRIPTION_BYTES: u64 = 4096;
+
+/// Interval between keep-alive lines (same as the SSE keep-alive).
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A WebTransport session on the quinn-backed HTTP/3 connection.
+pub type Session = WebTransportSession<h3_quinn::Connection, Bytes>;
+
+/// HTTP/3 request stream, as handled by `h3_server`.
+pub type H3RequestStream = RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>;
+
+/// True for an extended CONNECT with `:protocol = webtransport`.
+pub fn is_webtransport_connect<T>(req: &http::Request<T>) -> bool {
+    req.method() == http::Method::CONNECT
+        && req.extensions().get::<Protocol>() == Some(&Protocol::WEB_TRANSPORT)
+}
+
+/// Filters for one event stream.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Subscription {
+    /// Client label echoed in `subscribed`, to tell streams apart.
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Event types to deliver; all types when empty.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+impl Subscription {
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() as u64 > MAX_SUBSCRIPTION_BYTES {
+            return Err(format!(
+                "subscription exceeds {} bytes",
+                MAX_SUBSCRIPTION_BYTES
+            ));
+        }
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Self::default());
+        }
+        serde_json::from_slice(bytes).map_err(|e| format!("invalid subscription: {}", e))
+    }
+
+    pub fn matches(&self, event: &ServerEvent) -> bool {
+        if let Some(ref ws_id) = self.workspace {
+            if event.workspace_id.as_deref() != Some(ws_id.as_str()) {
+                return false;
+            }
+        }
+        self.event_types.is_empty() || self.event_types.iter().any(|t| t == event.event_type())
+    }
+}
+
+/// One line on a server → client event stream.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamMessage<'a> {
+    Subscribed {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace: Option<&'a str>,
+        #[serde(rename = "eventTypes")]
+        event_types: &'a [String],
+    },
+    Event {
+        event: &'a ServerEvent,
+    },
+    Lagged {
+        missed: u64,
+    },
+    Keepalive,
+    Error {
+        message: String,
+    },
+}
+
+impl StreamMessage<'_> {
+    fn line(&self) -> Vec<u8> {
+        let mut line = serde_json::to_vec(self).unwrap_or_default();
+        line.push(b'\n');
+        line
+    }
+}
+
+/// Serve a session until the connection closes: each incoming unidirectional stream is a
+/// subscription; incoming requests are passed to `on_request`.
+pub async fn serve_session<F>(session: Session, event_bus: EventBus, mut on_request: F)
+where
+    F: FnMut(http::Request<()>, H3RequestStream) + Send + 'static,
+{
+    let session = Arc::new(session);
+    tracing::debug!("WebTransport session established");
+
+    // Accepting bidi streams in its own task keeps both accept loops cancel-free.
+    let requests = tokio::spawn({
+        let session = session.clone();
+        async move {
+            while let Ok(Some(accepted)) = session.accept_bi().await {
+                match accepted {
+                    AcceptedBi::Request(req, stream) => on_request(req, stream),
+                    // No bidirectional protocol is defined; dropping resets the stream.
+                    AcceptedBi::BidiStream(..) => {}
+                }
+            }
+        }
+    });
+
+    let slots = Arc::new(Semaphore::new(MAX_SUBSCRIPTIONS_PER_SESSION));
+    loop {
+        match session.accept_uni().await {
+            Ok(Some((_, recv))) => {
+                let Ok(permit) = slots.clone().try_acquire_owned() else {
+                    tracing::debug!("WebTransport subscription limit reached; ignoring stream");
+                    continue;
+                };
+                let session = session.clone();
+                let event_bus = event_bus.clone();
+                tokio::spawn(async move {
+                    let _permit = permit;
+                    stream_events(session, recv, event_bus).await;
+                });
+            }
+            Ok(None) => break,
+            Err(e) => {
+                tracing::debug!(error = %e, "WebTransport session closed");
+                break;
+            }
+        }
+    }
+    requests.abort();
+}
+
+/// Read one subscription from `recv` and stream matching events on a new uni stream.
+async fn stream_events(
+    session: Arc<Session>,
+    mut recv: RecvStream<h3_quinn::RecvStream, Bytes>,
+    event_bus: EventBus,
+) {
+    let mut request = Vec::new();
+    let read = (&mut recv)
+        .take(MAX_SUBSCRIPTION_BYTES + 1)
+        .read_to_end(&mut request)
+        .await;
+    let Ok(mut send) = session.open_uni(session.session_id()).await else {
+        return;
+    };
+    let parsed = read
+        .map_err(|e| format!("reading subscription: {}", e))
+        .and_then(|_| Subscription::parse(&request));
+    let subscription = match parsed {
+        Ok(s) => s,
+        Err(message) => {
+            let _ = send
+                .write_all(&StreamMessage::Error { message }.line())
+                .await;
+            let _ = send.shutdown().await;
+            return;
+        }
+    };
+
+    // Subscribe before confirming so no event published after `subscribed` is missed.
+    let mut rx = event_bus.subscribe();
+    let subscribed = StreamMessage::Subscribed {
+        id: subscription.id.as_deref(),
+        workspace: subscription.workspace.as_deref(),
+        event_types: &subscription.event_types,
+    };
+    if send.write_all(&subscribed.line()).await.is_err() {
+        return;
+    }
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+
+    loop {
+        let line = tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) if subscription.matches(&event) => StreamMessage::Event { event: &event }.line(),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(missed)) => StreamMessage::Lagged { missed }.line(),
+                Err(RecvError::Closed) => break,
+            },
+            _ = keepalive.tick() => StreamMessage::Keepalive.line(),
+        };
+        // Fails once the client cancels the stream or the session ends.
+        if send.write_all(&line).await.is_err() {
+            return;
+        }
+    }
+    let _ = send.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::test_event as event;
+
+    #[test]
+    fn subscription_filters_by_workspace_and_type() {
+        let sub = Subscription::parse(
+            br#"{"id":"p","workspace":"ws-1","eventTypes":["proposal_updated"]}"#,
+        )
+        .unwrap();
+        assert_eq!(sub.id.as_deref(), Some("p"));
+        assert!(sub.matches(&event("proposal_updated", Some("ws-1"))));
+        assert!(!sub.matches(&event("proposal_updated", Some("ws-2"))));
+        assert!(!sub.matches(&event("review_submitted", Some("ws-1"))));
+
+        // An empty stream subscribes to everything.
+        let all = Subscription::parse(b"").unwrap();
+        assert!(all.matches(&event("config_changed", None)));
+    }
+
+    #[test]
+    fn subscription_rejects_bad_input() {
+        assert!(Subscription::parse(b"{not json").is_err());
+        assert!(Subscription::parse(br#"{"workspaces":"typo"}"#).is_err());
+        let huge = vec![b' '; MAX_SUBSCRIPTION_BYTES as usize + 1];
+        assert!(Subscription::parse(&huge).is_err());
+    }
+
+    #[test]
+    fn recognises_extended_connect() {
+        let mut req = http::Request::builder()
+            .method(http::Method::CONNECT)
+            .uri("https://localhost/webtransport")
+            .body(())
+            .unwrap();
+        assert!(!is_webtransport_connect(&req));
+        req.extensions_mut().insert(Protocol::WEB_TRANSPORT);
+        assert!(is_webtransport_connect(&req));
+
+        let line = StreamMessage::Event {
+            event: &event("proposal_updated", None),
+        }
+        .line();
+        assert!(line.ends_with(b"\n"));
+        let value: serde_json::Value = serde_json::from_slice(&line).unwrap();
+        assert_eq!(value["type"], "event");
+        assert_eq!(value["event"]["eventType"], "proposal_updated");
+    }
+}
@@ -20,6 +20,8 @@ import { trace, context, metrics } from "@opentelemetry/api";
 import { injectTraceHeaders } from "./telemetry.js";
 import type {
   AnyNode,
+  AuditAction,
+  AuditEvent,
   NodeId,
   NodeQuery,
   NodeQueryResult,
@@ -54,6 +56,31 @@ interface ProposalListResponse {
   hasMore: boolean;
 }
 
+/** Filters of GET /audit. */
+export interface AuditQuery {
+  actor?: string;
+  action?: AuditAction;
+  resourceId?: string;
+  /** RFC 3339; events at or after. */
+  from?: string;
+  /** RFC 3339; events at or before. */
+  to?: string;
+  limit?: number;
+  offset?: number;
+}
+
+/**
+ * Server response for GET /audit (paginated, oldest first). The endpoint used to return
+ * a bare array of events; read `events` now.
+ */
+export interface AuditQueryResponse {
+  events: AuditEvent[];
+  total: number;
+  limit: number;
+  offset: number;
+  hasMore: boolean;
+}
+
 function getBase(): string {
   return typeof process !== "undefined" && process.env?.TRUTHTLAYER_SERVER_URL
     ? process.env.TRUTHTLAYER_SERVER_URL
@@ -263,6 +290,17 @@ export class RustServerClient {
     return this.queryProposals({ status: ["open"] });
   }
 
+  /** One page of the audit log (Admin, or the author for their own proposal). */
+  async queryAudit(query: AuditQuery = {}): Promise<AuditQueryResponse> {
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(query)) {
+      if (value != null) params.set(key, String(value));
+    }
+    const qs = params.toString();
+    const url = qs ? `${this.base}/audit?${qs}` : `${this.base}/audit`;
+    return fetchJson<AuditQueryResponse>(url);
+  }
+
   async reset(): Promise<void> {
     await fetchJson(`${this.base}/reset`, { method: "POST" });
   }
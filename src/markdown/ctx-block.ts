@@ -64,6 +64,7 @@ const NODE_STATUSES = [
   "proposed",
   "rejected",
   "superseded",
+  "archived",
  ] as const;
 
 const isNodeType = isOneOf(NODE_TYPES);
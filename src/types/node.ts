@@ -14,7 +14,12 @@ export type NodeType =
   | "plan"
   | "note";
 
-export type NodeStatus = "accepted" | "proposed" | "rejected" | "superseded";
+export type NodeStatus =
+  | "accepted"
+  | "proposed"
+  | "rejected"
+  | "superseded"
+  | "archived";
 
 export interface NodeId {
   /** Stable identifier for this node, survives rebases and merges */
@@ -93,6 +98,29 @@ export interface NodeMetadata {
   ipClassification?: IpClassification;
   /** License identifier for content (e.g. "proprietary", "CC-BY-4.0"). */
   license?: string;
+  /** Set when a delete operation moved the node to the trash (server). */
+  deleted?: DeletedMetadata;
+  /** Last change of each field, keyed by field name (`GET /nodes/:id/blame`). */
+  fieldChanges?: Record<string, FieldBlame>;
+}
+
+/** Tombstone of a deleted node; restorable until retention purges it. */
+export interface DeletedMetadata {
+  deletedAt: string;
+  deletedBy: string;
+  /** Proposal whose delete operation removed the node. */
+  proposalId?: string;
+}
+
+/** Who last changed a node field, and through which proposal. */
+export interface FieldBlame {
+  proposalId: string;
+  /** The proposal's author. */
+  author: string;
+  appliedBy: string;
+  changedAt: string;
+  /** Node version the change produced. */
+  version: number;
 }
 
 // --- Governance types ---
@@ -45,6 +45,8 @@ export interface ProposalMetadata {
   baseVersions?: {
     [nodeId: string]: number; // Node ID -> version number
   };
+  /** Allow status changes outside the node transition table (applied by an Admin only) */
+  forceStatusTransitions?: boolean;
 
   /**
    * Optional projection of expected/actual codebase changes for this proposal.
@@ -91,7 +93,11 @@ export interface UpdateOperation extends Operation {
   type: "update";
   /** ID of the node to update */
   nodeId: NodeId;
-  /** Field-level changes */
+  /**
+   * Field-level changes: any node field (content, status, title, type-specific fields)
+   * plus the metadata fields tags, sensitivity, implementedInCommit, referencedInCommits,
+   * sourceAttribution, ipClassification and license. Omitted fields are left unchanged.
+   */
   changes: {
     content?: string;
     status?: NodeStatus;
@@ -274,10 +280,10 @@ export interface Review {
   id: string;
   /** The proposal being reviewed */
   proposalId: string;
-  /** Who reviewed */
+  /** Who reviewed (set by the server to the authenticated actor) */
   reviewer: string;
-  /** Role of the reviewer (contributor, approver, admin) */
-  reviewerRole?: "contributor" | "approver" | "admin";
+  /** Highest RBAC role of the reviewer (set by the server) */
+  reviewerRole?: "reader" | "contributor" | "reviewer" | "applier" | "admin";
   /** When reviewed */
   reviewedAt: string;
   /** Action taken */
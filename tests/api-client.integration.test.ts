@@ -21,6 +21,9 @@ const BASE =
     ? process.env.TRUTHTLAYER_SERVER_URL
     : "http://127.0.0.1:3080";
 
+/** The actor of a server run with auth disabled; it refuses proposals credited to anyone else. */
+const ACTOR = "dev-user";
+
 let serverAvailable = false;
 
 async function isServerUp(): Promise<boolean> {
@@ -87,9 +90,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "integration",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "integration",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
@@ -110,15 +113,26 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "integration",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "integration",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
-    await client.updateProposal("p-int-patch", { status: "accepted" });
+    // Settling a proposal by PATCH needs the reviews that would have settled it.
+    await expect(client.updateProposal("p-int-patch", { status: "accepted" })).rejects.toThrow(
+      "policy violation"
+    );
+    await client.submitReview({
+      id: "r-int-patch",
+      proposalId: "p-int-patch",
+      reviewer: ACTOR,
+      reviewedAt: "2026-01-02T00:00:00Z",
+      action: "request-changes",
+    });
+    await client.updateProposal("p-int-patch", { status: "rejected" });
     const got = await client.getProposal("p-int-patch");
-    expect(got?.status).toBe("accepted");
+    expect(got?.status).toBe("rejected");
   });
 
   skipOrRun("queryProposals with limit and offset", async () => {
@@ -131,9 +145,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
         operations: [],
         metadata: {
           createdAt: "2026-01-01T00:00:00Z",
-          createdBy: "integration",
+          createdBy: ACTOR,
           modifiedAt: "2026-01-01T00:00:00Z",
-          modifiedBy: "integration",
+          modifiedBy: ACTOR,
         },
       });
     }
@@ -159,9 +173,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "integration",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "integration",
+        modifiedBy: ACTOR,
       },
     });
     await client.withdrawProposal("p-int-withdraw");
@@ -179,9 +193,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "integration",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "integration",
+        modifiedBy: ACTOR,
       },
     });
 
@@ -196,10 +210,36 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
 
     const history = await client.getReviewHistory("p-int-review");
     expect(history.length).toBe(1);
-    expect(history[0].reviewer).toBe("reviewer-int");
+    expect(history[0].reviewer).toBe(ACTOR);
     expect(history[0].action).toBe("accept");
   });
 
+  skipOrRun("submitReview ignores a client-supplied reviewer", async () => {
+    await client.reset();
+    await client.createProposal({
+      id: "p-int-reviewer",
+      status: "open",
+      operations: [],
+      metadata: {
+        createdAt: "2026-01-01T00:00:00Z",
+        createdBy: ACTOR,
+        modifiedAt: "2026-01-01T00:00:00Z",
+        modifiedBy: ACTOR,
+      },
+    });
+
+    await client.submitReview({
+      id: "r-int-claimed",
+      proposalId: "p-int-reviewer",
+      reviewer: "someone-else",
+      reviewedAt: "2026-01-02T00:00:00Z",
+      action: "request-changes",
+    });
+
+    const history = await client.getReviewHistory("p-int-reviewer");
+    expect(history.map((r) => r.reviewer)).toEqual([ACTOR]);
+  });
+
   // ── Apply ───────────────────────────────────────────────────────────
 
   skipOrRun("apply proposal creates node and sets applied status", async () => {
@@ -229,9 +269,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       ],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
@@ -251,9 +291,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
@@ -290,9 +330,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       ],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
@@ -337,9 +377,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       ],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
@@ -360,9 +400,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "integration",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "integration",
+        modifiedBy: ACTOR,
       },
     });
 
@@ -409,9 +449,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       ],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     };
     await client.createProposal(proposal);
@@ -435,8 +475,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       headers: { "Content-Type": "application/json" },
     });
     expect(res.ok).toBe(true);
-    const events = (await res.json()) as unknown[];
-    expect(events.length).toBeGreaterThanOrEqual(1);
+    const page = (await res.json()) as { events: unknown[]; total: number; hasMore: boolean };
+    expect(page.events.length).toBeGreaterThanOrEqual(1);
+    expect(page.total).toBeGreaterThanOrEqual(page.events.length);
   });
 
   skipOrRun("GET /audit/export?format=csv returns CSV", async () => {
@@ -447,9 +488,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     });
 
@@ -473,9 +514,9 @@ describe("RustServerClient (integration – real server, in-memory store)", () =
       operations: [],
       metadata: {
         createdAt: "2026-01-01T00:00:00Z",
-        createdBy: "u",
+        createdBy: ACTOR,
         modifiedAt: "2026-01-01T00:00:00Z",
-        modifiedBy: "u",
+        modifiedBy: ACTOR,
       },
     });
 